@@ -0,0 +1,185 @@
+import cls from 'classnames';
+import { filesize } from 'filesize';
+import { FC } from 'react';
+import s from './Sidebar.module.css';
+import { AnalyzeModule, AnalyzeReport, SIZE_TYPES, SizeType } from './types';
+
+interface SidebarProps {
+  report: AnalyzeReport;
+  sizeType: SizeType;
+  onSizeTypeChange: (sizeType: SizeType) => void;
+  chunkId: string;
+  onChunkIdChange: (chunkId: string) => void;
+  selected: AnalyzeModule | null;
+}
+
+export const formatSize = (size: number) => filesize(size) as string;
+
+const ModuleDetail: FC<{ module: AnalyzeModule; duplicate: boolean }> = ({
+  module,
+  duplicate,
+}) => (
+  <>
+    <h2>{module.id}</h2>
+    <table>
+      <tbody>
+        {SIZE_TYPES.map((sizeType) => (
+          <tr key={sizeType}>
+            <td>{sizeType}</td>
+            <td className={s.size}>
+              {formatSize(module[`${sizeType}Size`])}
+            </td>
+          </tr>
+        ))}
+      </tbody>
+    </table>
+    {module.package && (
+      <div>
+        Package:{' '}
+        <strong>
+          {module.package.name}@{module.package.version}
+        </strong>
+        {duplicate && <span className={s.grew}> (duplicate)</span>}
+      </div>
+    )}
+    <h2>Why is this module included</h2>
+    <ol>
+      {module.reasons.map((id) => (
+        <li key={id}>{id}</li>
+      ))}
+    </ol>
+    {module.issuers.length > 0 && (
+      <>
+        <h2>Imported by</h2>
+        <ul>
+          {module.issuers.map((id) => (
+            <li key={id}>{id}</li>
+          ))}
+        </ul>
+      </>
+    )}
+    {module.concatenated && module.concatenated.length > 0 && (
+      <>
+        <h2>Concatenated modules</h2>
+        <ul>
+          {module.concatenated.map((id) => (
+            <li key={id}>{id}</li>
+          ))}
+        </ul>
+      </>
+    )}
+  </>
+);
+
+const Sidebar: FC<SidebarProps> = ({
+  report,
+  sizeType,
+  onSizeTypeChange,
+  chunkId,
+  onChunkIdChange,
+  selected,
+}) => {
+  const duplicateNames = new Set(report.duplicates.map((d) => d.name));
+  const chunks = report.chunks.filter(
+    (chunk) => !chunkId || chunk.id === chunkId,
+  );
+  return (
+    <div className={s.sidebar}>
+      <label>
+        Size
+        <select
+          value={sizeType}
+          onChange={(e) => onSizeTypeChange(e.target.value as SizeType)}
+        >
+          {SIZE_TYPES.map((sizeType) => (
+            <option key={sizeType} value={sizeType}>
+              {sizeType}
+            </option>
+          ))}
+        </select>
+      </label>
+      <label>
+        Chunk
+        <select
+          value={chunkId}
+          onChange={(e) => onChunkIdChange(e.target.value)}
+        >
+          <option value="">All</option>
+          {report.chunks.map((chunk) => (
+            <option key={chunk.id} value={chunk.id}>
+              {chunk.id}
+            </option>
+          ))}
+        </select>
+      </label>
+      <h2>Files</h2>
+      <table>
+        <tbody>
+          {chunks.flatMap((chunk) =>
+            chunk.files.map((file) => (
+              <tr key={file.name}>
+                <td>{file.name}</td>
+                <td className={s.size}>{formatSize(file.size)}</td>
+                <td className={s.size}>gzip {formatSize(file.gzipSize)}</td>
+                <td className={s.size}>br {formatSize(file.brotliSize)}</td>
+              </tr>
+            )),
+          )}
+        </tbody>
+      </table>
+      {selected && (
+        <ModuleDetail
+          module={selected}
+          duplicate={
+            !!selected.package && duplicateNames.has(selected.package.name)
+          }
+        />
+      )}
+      {report.duplicates.length > 0 && (
+        <>
+          <h2 className={s.grew}>Duplicate packages</h2>
+          <ul>
+            {report.duplicates.map((duplicate) => (
+              <li key={duplicate.name}>
+                {duplicate.name}
+                <ul>
+                  {duplicate.versions.map((version) => (
+                    <li key={version.path}>
+                      {version.version}{' '}
+                      <span className={s.muted}>{version.path}</span>
+                    </li>
+                  ))}
+                </ul>
+              </li>
+            ))}
+          </ul>
+        </>
+      )}
+      {report.diff && report.diff.length > 0 && (
+        <>
+          <h2>Changed since baseline (minified)</h2>
+          <table>
+            <tbody>
+              {report.diff.map((item) => {
+                const delta = item.size - item.baselineSize;
+                return (
+                  <tr key={item.id}>
+                    <td>{item.id}</td>
+                    <td
+                      className={cls(s.size, delta > 0 ? s.grew : s.shrank)}
+                    >
+                      {delta > 0 ? '+' : '-'}
+                      {formatSize(Math.abs(delta))}
+                    </td>
+                  </tr>
+                );
+              })}
+            </tbody>
+          </table>
+        </>
+      )}
+    </div>
+  );
+};
+
+export default Sidebar;
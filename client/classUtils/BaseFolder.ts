@@ -1,20 +1,20 @@
+import { SIZE_TYPES, SizeType } from '../types';
 import Node from './Node';
 class BaseFolder extends Node {
   constructor(name, parent) {
     super(name, parent);
     this.children = Object.create(null);
+    this._sizes = Object.create(null);
   }
-  get src() {
-    if (!Object.prototype.hasOwnProperty.call(this, '_src')) {
-      this._src = this.walk((node, src) => (src += node.src || ''), '', false);
+  getSize(sizeType: SizeType) {
+    if (!(sizeType in this._sizes)) {
+      this._sizes[sizeType] = this.walk(
+        (node, size) => size + node.getSize(sizeType),
+        0,
+        false,
+      );
     }
-    return this._src;
-  }
-  get size() {
-    if (!Object.prototype.hasOwnProperty.call(this, '_size')) {
-      this._size = this.walk((node, size) => size + node.size, 0, false);
-    }
-    return this._size;
+    return this._sizes[sizeType];
   }
   getChild(name) {
     return this.children[name];
@@ -25,23 +25,16 @@ class BaseFolder extends Node {
 
     // For some reason we already have this node in children and it's a folder.
     if (currentChild && currentChild instanceof BaseFolder) return;
-    if (currentChild) {
-      // We already have this node in children and it's a module.
-      // Merging it's data.
-      currentChild.mergeData(module.data);
-    } else {
-      // Pushing new module
+    if (!currentChild) {
       module.parent = this;
       this.children[name] = module;
     }
-    delete this._size;
-    delete this._src;
+    this._sizes = Object.create(null);
   }
   addChildFolder(folder) {
     folder.parent = this;
     this.children[folder.name] = folder;
-    delete this._size;
-    delete this._src;
+    this._sizes = Object.create(null);
     return folder;
   }
   walk(walker, state = {}, deep = true) {
@@ -85,13 +78,19 @@ class BaseFolder extends Node {
       false,
     );
   }
+  get duplicate() {
+    return this.walk((node, duplicate) => duplicate || node.duplicate, false);
+  }
   toChartData() {
-    return {
+    const data = {
       label: this.name,
       path: this.path,
-      statSize: this.size,
       groups: Object.values(this.children).map((child) => child.toChartData()),
     };
+    SIZE_TYPES.forEach((sizeType) => {
+      data[`${sizeType}Size`] = this.getSize(sizeType);
+    });
+    return data;
   }
 }
 export default BaseFolder;
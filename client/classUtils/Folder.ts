@@ -1,26 +1,12 @@
+import { AnalyzeModule } from '../types';
 import BaseFolder from './BaseFolder';
 import Module from './Module';
 class Folder extends BaseFolder {
-  get parsedSize() {
-    return this.src ? this.src.length : 0;
-  }
-  get gzipSize() {
-    if (!Object.prototype.hasOwnProperty.call(this, '_gzipSize')) {
-      this._gzipSize = this.src ? _gzipSize.default.sync(this.src) : 0;
-    }
-    return this._gzipSize;
-  }
-  addModule(moduleData) {
-    const loaders = moduleData.id.split('!');
-    const parsedPath = loaders[loaders.length - 1]
-      // Splitting module path into parts
-      .split('/')
-      // Removing first `.`
-      .slice(1)
-      // Replacing `~` with `node_modules`
-      .map((part) => (part === '~' ? 'node_modules' : part));
+  addModule(moduleData: AnalyzeModule, duplicate: boolean) {
+    // the module ids are relative to the root, e.g. `src/index.ts`
+    const parsedPath = moduleData.id.split('/').filter((part) => part !== '.');
     // 如果路径不存在，则结束
-    if (!parsedPath) {
+    if (!parsedPath.length) {
       return;
     }
     const [folders, fileName] = [
@@ -34,10 +20,8 @@ class Folder extends BaseFolder {
       if (
         // Folder is not created yet
         !childNode ||
-        // In some situations (invalid usage of dynamic `require()`) webpack generates a module with empty require
-        // context, but it's moduleId points to a directory in filesystem.
-        // In this case we replace this `File` node with `Folder`.
-        // See `test/stats/with-invalid-dynamic-require.json` as an example.
+        // A module and a folder may have the same name, e.g. a module id
+        // without the extension, the folder wins.
         !(childNode instanceof Folder)
       ) {
         childNode = currentFolder.addChildFolder(new Folder(folderName));
@@ -45,15 +29,8 @@ class Folder extends BaseFolder {
       currentFolder = childNode;
     });
 
-    const module = new Module(fileName, moduleData, this);
+    const module = new Module(fileName, moduleData, this, duplicate);
     currentFolder.addChildModule(module);
   }
-  toChartData() {
-    return {
-      ...super.toChartData(),
-      parsedSize: this.parsedSize,
-      gzipSize: this.gzipSize,
-    };
-  }
 }
 export default Folder;
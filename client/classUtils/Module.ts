@@ -1,55 +1,27 @@
+import { AnalyzeModule, SIZE_TYPES, SizeType } from '../types';
 import Node from './Node';
 
 class Module extends Node {
-  constructor(name, data, parent) {
+  constructor(name, data: AnalyzeModule, parent, duplicate: boolean) {
     super(name, parent);
     this.data = data;
+    this.duplicate = duplicate;
   }
-  get src() {
-    return this.data.parsedSrc;
-  }
-  set src(value) {
-    this.data.parsedSrc = value;
-    delete this._gzipSize;
-  }
-  get size() {
-    return this.data.size;
-  }
-  set size(value) {
-    this.data.size = value;
-  }
-  get parsedSize() {
-    return this.getParsedSize();
-  }
-  get gzipSize() {
-    return this.getGzipSize();
-  }
-  getParsedSize() {
-    return this.src ? this.src.length : undefined;
-  }
-  getGzipSize() {
-    if (!('_gzipSize' in this)) {
-      this._gzipSize = this.src ? _gzipSize.default.sync(this.src) : undefined;
-    }
-    return this._gzipSize;
-  }
-  mergeData(data) {
-    if (data.size) {
-      this.size += data.size;
-    }
-    if (data.parsedSrc) {
-      this.src = (this.src || '') + data.parsedSrc;
-    }
+  getSize(sizeType: SizeType) {
+    return this.data[`${sizeType}Size`] || 0;
   }
   toChartData() {
-    return {
+    const data = {
       id: this.data.id,
       label: this.name,
       path: this.path,
-      statSize: this.size,
-      parsedSize: this.parsedSize,
-      gzipSize: this.gzipSize,
+      module: this.data,
+      duplicate: this.duplicate,
     };
+    SIZE_TYPES.forEach((sizeType) => {
+      data[`${sizeType}Size`] = this.getSize(sizeType);
+    });
+    return data;
   }
 }
 export default Module;
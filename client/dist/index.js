@@ -1 +1 @@
-!function(e,t,n){var r={};function a(t){var n=r[t];if(void 0!==n)return n.exports;var i={id:t,exports:{}};r[t]=i;var o={id:t,module:i,factory:e[t],require:a};return a.requireInterceptors.forEach(function(e){e(o);}),o.factory.call(o.module.exports,o.module,o.module.exports,o.require),i.exports;}a.requireInterceptors=[],a.e=function(e,t){for(var n in t)Object.defineProperty(e,n,{enumerable:!0,get:t[n]});},a.es=function(e,t){Object.keys(t).forEach(function(n){"default"===n||Object.prototype.hasOwnProperty.call(e,n)||Object.defineProperty(e,n,{enumerable:!0,get:t[n]});});},a.d=Object.defineProperty.bind(Object),a.dr=function(e,t){return function(){var n=t();return n&&("object"==typeof n||"function"==typeof n)&&"function"==typeof n.then?n.then(function(t){return e(t);}):e(n);};},a.publicPath="/",function(t){for(var n in t)e[n]=t[n];}({"@swc/helpers/_/_interop_require_default":function(e,t,n){function r(e){return e&&e.__esModule?e:{default:e};}n.d(t,"__esModule",{value:!0}),n.e(t,{_interop_require_default:function(){return r;},_:function(){return r;}});},"@swc/helpers/_/_interop_require_wildcard":function(e,t,n){function r(e){if("function"!=typeof WeakMap)return null;var t=new WeakMap,n=new WeakMap;return(r=function(e){return e?n:t;})(e);}function a(e,t){if(!t&&e&&e.__esModule)return e;if(null===e||"object"!=typeof e&&"function"!=typeof e)return{default:e};var n=r(t);if(n&&n.has(e))return n.get(e);var a={},i=Object.defineProperty&&Object.getOwnPropertyDescriptor;for(var o in e)if("default"!==o&&Object.prototype.hasOwnProperty.call(e,o)){var l=i?Object.getOwnPropertyDescriptor(e,o):null;l&&(l.get||l.set)?Object.defineProperty(a,o,l):a[o]=e[o];}return a.default=e,n&&n.set(e,a),a;}n.d(t,"__esModule",{value:!0}),n.e(t,{_interop_require_wildcard:function(){return a;},_:function(){return a;}});},"@swc/helpers/_/_export_star":function(e,t,n){function r(e,t){return Object.keys(e).forEach(function(n){"default"===n||Object.prototype.hasOwnProperty.call(t,n)||Object.defineProperty(t,n,{enumerable:!0,get:function(){return e[n];}});}),e;}n.d(t,"__esModule",{value:!0}),n.e(t,{_export_star:function(){return r;},_:function(){return r;}});}}),n.__mako_require_module__=a,n.__mako_chunk_load__=a.ensure,a(t);}({"../node_modules/.pnpm/classnames@2.3.2/node_modules/classnames/index.js":function(e,t,n){!function(){"use strict";var t={}.hasOwnProperty;function n(){for(var e=[],r=0;r<arguments.length;r++){var a=arguments[r];if(a){var i=typeof a;if("string"===i||"number"===i)e.push(a);else if(Array.isArray(a)){if(a.length){var o=n.apply(null,a);o&&e.push(o);}}else if("object"===i){if(a.toString!==Object.prototype.toString&&!a.toString.toString().includes("[native code]")){e.push(a.toString());continue;}for(var l in a)t.call(a,l)&&a[l]&&e.push(l);}}}return e.join(" ");}void 0!==e&&e.exports?(n.default=n,e.exports=n):window.classNames=n;}();},"../node_modules/.pnpm/react-dom@18.2.0_react@18.2.0/node_modules/react-dom/cjs/react-dom.production.min.js":function(e,t,n){"use strict";var r,a,i,o,l,u,s,c,f,d,h,p,g,v,b,m,y,w=n("../node_modules/.pnpm/react@18.2.0/node_modules/react/index.js"),x=n("../node_modules/.pnpm/scheduler@0.23.2/node_modules/scheduler/index.js");function S(e){for(var t="https://reactjs.org/docs/error-decoder.html?invariant="+e,n=1;n<arguments.length;n++)t+="&args[]="+encodeURIComponent(arguments[n]);return"Minified React error #"+e+"; visit "+t+" for the full message or use the non-minified dev environment for full errors and additional helpful warnings.";}var k=new Set,C={};function T(e,t){z(e,t),z(e+"Capture",t);}function z(e,t){for(C[e]=t,e=0;e<t.length;e++)k.add(t[e]);}var M=!("undefined"==typeof window||void 0===window.document||void 0===window.document.createElement),P=Object.prototype.hasOwnProperty,E=/^[:A-Z_a-z\u00C0-\u00D6\u00D8-\u00F6\u00F8-\u02FF\u0370-\u037D\u037F-\u1FFF\u200C-\u200D\u2070-\u218F\u2C00-\u2FEF\u3001-\uD7FF\uF900-\uFDCF\uFDF0-\uFFFD][:A-Z_a-z\u00C0-\u00D6\u00D8-\u00F6\u00F8-\u02FF\u0370-\u037D\u037F-\u1FFF\u200C-\u200D\u2070-\u218F\u2C00-\u2FEF\u3001-\uD7FF\uF900-\uFDCF\uFDF0-\uFFFD\-.0-9\u00B7\u0300-\u036F\u203F-\u2040]*$/,_={},L={};function D(e,t,n,r,a,i,o){this.acceptsBooleans=2===t||3===t||4===t,this.attributeName=r,this.attributeNamespace=a,this.mustUseProperty=n,this.propertyName=e,this.type=t,this.sanitizeURL=i,this.removeEmptyString=o;}var F={};"children dangerouslySetInnerHTML defaultValue defaultChecked innerHTML suppressContentEditableWarning suppressHydrationWarning style".split(" ").forEach(function(e){F[e]=new D(e,0,!1,e,null,!1,!1);}),[["acceptCharset","accept-charset"],["className","class"],["htmlFor","for"],["httpEquiv","http-equiv"]].forEach(function(e){var t=e[0];F[t]=new D(t,1,!1,e[1],null,!1,!1);}),["contentEditable","draggable","spellCheck","value"].forEach(function(e){F[e]=new D(e,2,!1,e.toLowerCase(),null,!1,!1);}),["autoReverse","externalResourcesRequired","focusable","preserveAlpha"].forEach(function(e){F[e]=new D(e,2,!1,e,null,!1,!1);}),"allowFullScreen async autoFocus autoPlay controls default defer disabled disablePictureInPicture disableRemotePlayback formNoValidate hidden loop noModule noValidate open playsInline readOnly required reversed scoped seamless itemScope".split(" ").forEach(function(e){F[e]=new D(e,3,!1,e.toLowerCase(),null,!1,!1);}),["checked","multiple","muted","selected"].forEach(function(e){F[e]=new D(e,3,!0,e,null,!1,!1);}),["capture","download"].forEach(function(e){F[e]=new D(e,4,!1,e,null,!1,!1);}),["cols","rows","size","span"].forEach(function(e){F[e]=new D(e,6,!1,e,null,!1,!1);}),["rowSpan","start"].forEach(function(e){F[e]=new D(e,5,!1,e.toLowerCase(),null,!1,!1);});var j=/[\-:]([a-z])/g;function N(e){return e[1].toUpperCase();}function O(e,t,n,r){var a,i=F.hasOwnProperty(t)?F[t]:null;(null!==i?0!==i.type:r||!(2<t.length)||"o"!==t[0]&&"O"!==t[0]||"n"!==t[1]&&"N"!==t[1])&&(function(e,t,n,r){if(null==t||function(e,t,n,r){if(null!==n&&0===n.type)return!1;switch(typeof t){case"function":case"symbol":return!0;case"boolean":if(r)return!1;if(null!==n)return!n.acceptsBooleans;return"data-"!==(e=e.toLowerCase().slice(0,5))&&"aria-"!==e;default:return!1;}}(e,t,n,r))return!0;if(r)return!1;if(null!==n)switch(n.type){case 3:return!t;case 4:return!1===t;case 5:return isNaN(t);case 6:return isNaN(t)||1>t;}return!1;}(t,n,i,r)&&(n=null),r||null===i?(a=t,(!!P.call(L,a)||!P.call(_,a)&&(E.test(a)?L[a]=!0:(_[a]=!0,!1)))&&(null===n?e.removeAttribute(t):e.setAttribute(t,""+n))):i.mustUseProperty?e[i.propertyName]=null===n?3!==i.type&&"":n:(t=i.attributeName,r=i.attributeNamespace,null===n?e.removeAttribute(t):(n=3===(i=i.type)||4===i&&!0===n?"":""+n,r?e.setAttributeNS(r,t,n):e.setAttribute(t,n))));}"accent-height alignment-baseline arabic-form baseline-shift cap-height clip-path clip-rule color-interpolation color-interpolation-filters color-profile color-rendering dominant-baseline enable-background fill-opacity fill-rule flood-color flood-opacity font-family font-size font-size-adjust font-stretch font-style font-variant font-weight glyph-name glyph-orientation-horizontal glyph-orientation-vertical horiz-adv-x horiz-origin-x image-rendering letter-spacing lighting-color marker-end marker-mid marker-start overline-position overline-thickness paint-order panose-1 pointer-events rendering-intent shape-rendering stop-color stop-opacity strikethrough-position strikethrough-thickness stroke-dasharray stroke-dashoffset stroke-linecap stroke-linejoin stroke-miterlimit stroke-opacity stroke-width text-anchor text-decoration text-rendering underline-position underline-thickness unicode-bidi unicode-range units-per-em v-alphabetic v-hanging v-ideographic v-mathematical vector-effect vert-adv-y vert-origin-x vert-origin-y word-spacing writing-mode xmlns:xlink x-height".split(" ").forEach(function(e){var t=e.replace(j,N);F[t]=new D(t,1,!1,e,null,!1,!1);}),"xlink:actuate xlink:arcrole xlink:role xlink:show xlink:title xlink:type".split(" ").forEach(function(e){var t=e.replace(j,N);F[t]=new D(t,1,!1,e,"http://www.w3.org/1999/xlink",!1,!1);}),["xml:base","xml:lang","xml:space"].forEach(function(e){var t=e.replace(j,N);F[t]=new D(t,1,!1,e,"http://www.w3.org/XML/1998/namespace",!1,!1);}),["tabIndex","crossOrigin"].forEach(function(e){F[e]=new D(e,1,!1,e.toLowerCase(),null,!1,!1);}),F.xlinkHref=new D("xlinkHref",1,!1,"xlink:href","http://www.w3.org/1999/xlink",!0,!1),["src","href","action","formAction"].forEach(function(e){F[e]=new D(e,1,!1,e.toLowerCase(),null,!0,!0);});var R=w.__SECRET_INTERNALS_DO_NOT_USE_OR_YOU_WILL_BE_FIRED,A=Symbol.for("react.element"),I=Symbol.for("react.portal"),B=Symbol.for("react.fragment"),G=Symbol.for("react.strict_mode"),H=Symbol.for("react.profiler"),q=Symbol.for("react.provider"),U=Symbol.for("react.context"),W=Symbol.for("react.forward_ref"),V=Symbol.for("react.suspense"),$=Symbol.for("react.suspense_list"),K=Symbol.for("react.memo"),Q=Symbol.for("react.lazy");Symbol.for("react.scope"),Symbol.for("react.debug_trace_mode");var Y=Symbol.for("react.offscreen");Symbol.for("react.legacy_hidden"),Symbol.for("react.cache"),Symbol.for("react.tracing_marker");var X=Symbol.iterator;function J(e){return null===e||"object"!=typeof e?null:"function"==typeof(e=X&&e[X]||e["@@iterator"])?e:null;}var Z=Object.assign;function ee(e){if(void 0===a)try{throw Error();}catch(e){var t=e.stack.trim().match(/\n( *(at )?)/);a=t&&t[1]||"";}return"\n"+a+e;}var et=!1;function en(e,t){if(!e||et)return"";et=!0;var n=Error.prepareStackTrace;Error.prepareStackTrace=void 0;try{if(t){if(t=function(){throw Error();},Object.defineProperty(t.prototype,"props",{set:function(){throw Error();}}),"object"==typeof Reflect&&Reflect.construct){try{Reflect.construct(t,[]);}catch(e){var r=e;}Reflect.construct(e,[],t);}else{try{t.call();}catch(e){r=e;}e.call(t.prototype);}}else{try{throw Error();}catch(e){r=e;}e();}}catch(t){if(t&&r&&"string"==typeof t.stack){for(var a=t.stack.split("\n"),i=r.stack.split("\n"),o=a.length-1,l=i.length-1;1<=o&&0<=l&&a[o]!==i[l];)l--;for(;1<=o&&0<=l;o--,l--)if(a[o]!==i[l]){if(1!==o||1!==l)do if(o--,0>--l||a[o]!==i[l]){var u="\n"+a[o].replace(" at new "," at ");return e.displayName&&u.includes("<anonymous>")&&(u=u.replace("<anonymous>",e.displayName)),u;}while(1<=o&&0<=l)break;}}}finally{et=!1,Error.prepareStackTrace=n;}return(e=e?e.displayName||e.name:"")?ee(e):"";}function er(e){switch(typeof e){case"boolean":case"number":case"string":case"undefined":case"object":return e;default:return"";}}function ea(e){var t=e.type;return(e=e.nodeName)&&"input"===e.toLowerCase()&&("checkbox"===t||"radio"===t);}function ei(e){e._valueTracker||(e._valueTracker=function(e){var t=ea(e)?"checked":"value",n=Object.getOwnPropertyDescriptor(e.constructor.prototype,t),r=""+e[t];if(!e.hasOwnProperty(t)&&void 0!==n&&"function"==typeof n.get&&"function"==typeof n.set){var a=n.get,i=n.set;return Object.defineProperty(e,t,{configurable:!0,get:function(){return a.call(this);},set:function(e){r=""+e,i.call(this,e);}}),Object.defineProperty(e,t,{enumerable:n.enumerable}),{getValue:function(){return r;},setValue:function(e){r=""+e;},stopTracking:function(){e._valueTracker=null,delete e[t];}};}}(e));}function eo(e){if(!e)return!1;var t=e._valueTracker;if(!t)return!0;var n=t.getValue(),r="";return e&&(r=ea(e)?e.checked?"true":"false":e.value),(e=r)!==n&&(t.setValue(e),!0);}function el(e){if(void 0===(e=e||("undefined"!=typeof document?document:void 0)))return null;try{return e.activeElement||e.body;}catch(t){return e.body;}}function eu(e,t){var n=t.checked;return Z({},t,{defaultChecked:void 0,defaultValue:void 0,value:void 0,checked:null!=n?n:e._wrapperState.initialChecked});}function es(e,t){var n=null==t.defaultValue?"":t.defaultValue,r=null!=t.checked?t.checked:t.defaultChecked;n=er(null!=t.value?t.value:n),e._wrapperState={initialChecked:r,initialValue:n,controlled:"checkbox"===t.type||"radio"===t.type?null!=t.checked:null!=t.value};}function ec(e,t){null!=(t=t.checked)&&O(e,"checked",t,!1);}function ef(e,t){ec(e,t);var n=er(t.value),r=t.type;if(null!=n)"number"===r?(0===n&&""===e.value||e.value!=n)&&(e.value=""+n):e.value!==""+n&&(e.value=""+n);else if("submit"===r||"reset"===r){e.removeAttribute("value");return;}t.hasOwnProperty("value")?eh(e,t.type,n):t.hasOwnProperty("defaultValue")&&eh(e,t.type,er(t.defaultValue)),null==t.checked&&null!=t.defaultChecked&&(e.defaultChecked=!!t.defaultChecked);}function ed(e,t,n){if(t.hasOwnProperty("value")||t.hasOwnProperty("defaultValue")){var r=t.type;if(!("submit"!==r&&"reset"!==r||void 0!==t.value&&null!==t.value))return;t=""+e._wrapperState.initialValue,n||t===e.value||(e.value=t),e.defaultValue=t;}""!==(n=e.name)&&(e.name=""),e.defaultChecked=!!e._wrapperState.initialChecked,""!==n&&(e.name=n);}function eh(e,t,n){("number"!==t||el(e.ownerDocument)!==e)&&(null==n?e.defaultValue=""+e._wrapperState.initialValue:e.defaultValue!==""+n&&(e.defaultValue=""+n));}var ep=Array.isArray;function eg(e,t,n,r){if(e=e.options,t){t={};for(var a=0;a<n.length;a++)t["$"+n[a]]=!0;for(n=0;n<e.length;n++)a=t.hasOwnProperty("$"+e[n].value),e[n].selected!==a&&(e[n].selected=a),a&&r&&(e[n].defaultSelected=!0);}else{for(a=0,n=""+er(n),t=null;a<e.length;a++){if(e[a].value===n){e[a].selected=!0,r&&(e[a].defaultSelected=!0);return;}null!==t||e[a].disabled||(t=e[a]);}null!==t&&(t.selected=!0);}}function ev(e,t){if(null!=t.dangerouslySetInnerHTML)throw Error(S(91));return Z({},t,{value:void 0,defaultValue:void 0,children:""+e._wrapperState.initialValue});}function eb(e,t){var n=t.value;if(null==n){if(n=t.children,t=t.defaultValue,null!=n){if(null!=t)throw Error(S(92));if(ep(n)){if(1<n.length)throw Error(S(93));n=n[0];}t=n;}null==t&&(t=""),n=t;}e._wrapperState={initialValue:er(n)};}function em(e,t){var n=er(t.value),r=er(t.defaultValue);null!=n&&((n=""+n)!==e.value&&(e.value=n),null==t.defaultValue&&e.defaultValue!==n&&(e.defaultValue=n)),null!=r&&(e.defaultValue=""+r);}function ey(e){var t=e.textContent;t===e._wrapperState.initialValue&&""!==t&&null!==t&&(e.value=t);}function ew(e){switch(e){case"svg":return"http://www.w3.org/2000/svg";case"math":return"http://www.w3.org/1998/Math/MathML";default:return"http://www.w3.org/1999/xhtml";}}function ex(e,t){return null==e||"http://www.w3.org/1999/xhtml"===e?ew(t):"http://www.w3.org/2000/svg"===e&&"foreignObject"===t?"http://www.w3.org/1999/xhtml":e;}var eS=(r=function(e,t){if("http://www.w3.org/2000/svg"!==e.namespaceURI||"innerHTML"in e)e.innerHTML=t;else{for((i=i||document.createElement("div")).innerHTML="<svg>"+t.valueOf().toString()+"</svg>",t=i.firstChild;e.firstChild;)e.removeChild(e.firstChild);for(;t.firstChild;)e.appendChild(t.firstChild);}},"undefined"!=typeof MSApp&&MSApp.execUnsafeLocalFunction?function(e,t,n,a){MSApp.execUnsafeLocalFunction(function(){return r(e,t,n,a);});}:r);function ek(e,t){if(t){var n=e.firstChild;if(n&&n===e.lastChild&&3===n.nodeType){n.nodeValue=t;return;}}e.textContent=t;}var eC={animationIterationCount:!0,aspectRatio:!0,borderImageOutset:!0,borderImageSlice:!0,borderImageWidth:!0,boxFlex:!0,boxFlexGroup:!0,boxOrdinalGroup:!0,columnCount:!0,columns:!0,flex:!0,flexGrow:!0,flexPositive:!0,flexShrink:!0,flexNegative:!0,flexOrder:!0,gridArea:!0,gridRow:!0,gridRowEnd:!0,gridRowSpan:!0,gridRowStart:!0,gridColumn:!0,gridColumnEnd:!0,gridColumnSpan:!0,gridColumnStart:!0,fontWeight:!0,lineClamp:!0,lineHeight:!0,opacity:!0,order:!0,orphans:!0,tabSize:!0,widows:!0,zIndex:!0,zoom:!0,fillOpacity:!0,floodOpacity:!0,stopOpacity:!0,strokeDasharray:!0,strokeDashoffset:!0,strokeMiterlimit:!0,strokeOpacity:!0,strokeWidth:!0},eT=["Webkit","ms","Moz","O"];function ez(e,t,n){return null==t||"boolean"==typeof t||""===t?"":n||"number"!=typeof t||0===t||eC.hasOwnProperty(e)&&eC[e]?(""+t).trim():t+"px";}function eM(e,t){for(var n in e=e.style,t)if(t.hasOwnProperty(n)){var r=0===n.indexOf("--"),a=ez(n,t[n],r);"float"===n&&(n="cssFloat"),r?e.setProperty(n,a):e[n]=a;}}Object.keys(eC).forEach(function(e){eT.forEach(function(t){eC[t=t+e.charAt(0).toUpperCase()+e.substring(1)]=eC[e];});});var eP=Z({menuitem:!0},{area:!0,base:!0,br:!0,col:!0,embed:!0,hr:!0,img:!0,input:!0,keygen:!0,link:!0,meta:!0,param:!0,source:!0,track:!0,wbr:!0});function eE(e,t){if(t){if(eP[e]&&(null!=t.children||null!=t.dangerouslySetInnerHTML))throw Error(S(137,e));if(null!=t.dangerouslySetInnerHTML){if(null!=t.children)throw Error(S(60));if("object"!=typeof t.dangerouslySetInnerHTML||!("__html"in t.dangerouslySetInnerHTML))throw Error(S(61));}if(null!=t.style&&"object"!=typeof t.style)throw Error(S(62));}}function e_(e,t){if(-1===e.indexOf("-"))return"string"==typeof t.is;switch(e){case"annotation-xml":case"color-profile":case"font-face":case"font-face-src":case"font-face-uri":case"font-face-format":case"font-face-name":case"missing-glyph":return!1;default:return!0;}}var eL=null;function eD(e){return(e=e.target||e.srcElement||window).correspondingUseElement&&(e=e.correspondingUseElement),3===e.nodeType?e.parentNode:e;}var eF=null,ej=null,eN=null;function eO(e){if(e=rO(e)){if("function"!=typeof eF)throw Error(S(280));var t=e.stateNode;t&&(t=rA(t),eF(e.stateNode,e.type,t));}}function eR(e){ej?eN?eN.push(e):eN=[e]:ej=e;}function eA(){if(ej){var e=ej,t=eN;if(eN=ej=null,eO(e),t)for(e=0;e<t.length;e++)eO(t[e]);}}function eI(e,t){return e(t);}function eB(){}var eG=!1;function eH(e,t,n){if(eG)return e(t,n);eG=!0;try{return eI(e,t,n);}finally{eG=!1,(null!==ej||null!==eN)&&(eB(),eA());}}function eq(e,t){var n=e.stateNode;if(null===n)return null;var r=rA(n);if(null===r)return null;switch(n=r[t],t){case"onClick":case"onClickCapture":case"onDoubleClick":case"onDoubleClickCapture":case"onMouseDown":case"onMouseDownCapture":case"onMouseMove":case"onMouseMoveCapture":case"onMouseUp":case"onMouseUpCapture":case"onMouseEnter":(r=!r.disabled)||(r=!("button"===(e=e.type)||"input"===e||"select"===e||"textarea"===e)),e=!r;break;default:e=!1;}if(e)return null;if(n&&"function"!=typeof n)throw Error(S(231,t,typeof n));return n;}var eU=!1;if(M)try{var eW={};Object.defineProperty(eW,"passive",{get:function(){eU=!0;}}),window.addEventListener("test",eW,eW),window.removeEventListener("test",eW,eW);}catch(e){eU=!1;}function eV(e,t,n,r,a,i,o,l,u){var s=Array.prototype.slice.call(arguments,3);try{t.apply(n,s);}catch(e){this.onError(e);}}var e$=!1,eK=null,eQ=!1,eY=null,eX={onError:function(e){e$=!0,eK=e;}};function eJ(e,t,n,r,a,i,o,l,u){e$=!1,eK=null,eV.apply(eX,arguments);}function eZ(e){var t=e,n=e;if(e.alternate)for(;t.return;)t=t.return;else{e=t;do 0!=(4098&(t=e).flags)&&(n=t.return),e=t.return;while(e)}return 3===t.tag?n:null;}function e0(e){if(13===e.tag){var t=e.memoizedState;if(null===t&&null!==(e=e.alternate)&&(t=e.memoizedState),null!==t)return t.dehydrated;}return null;}function e1(e){if(eZ(e)!==e)throw Error(S(188));}function e2(e){return null!==(e=function(e){var t=e.alternate;if(!t){if(null===(t=eZ(e)))throw Error(S(188));return t!==e?null:e;}for(var n=e,r=t;;){var a=n.return;if(null===a)break;var i=a.alternate;if(null===i){if(null!==(r=a.return)){n=r;continue;}break;}if(a.child===i.child){for(i=a.child;i;){if(i===n)return e1(a),e;if(i===r)return e1(a),t;i=i.sibling;}throw Error(S(188));}if(n.return!==r.return)n=a,r=i;else{for(var o=!1,l=a.child;l;){if(l===n){o=!0,n=a,r=i;break;}if(l===r){o=!0,r=a,n=i;break;}l=l.sibling;}if(!o){for(l=i.child;l;){if(l===n){o=!0,n=i,r=a;break;}if(l===r){o=!0,r=i,n=a;break;}l=l.sibling;}if(!o)throw Error(S(189));}}if(n.alternate!==r)throw Error(S(190));}if(3!==n.tag)throw Error(S(188));return n.stateNode.current===n?e:t;}(e))?function e(t){if(5===t.tag||6===t.tag)return t;for(t=t.child;null!==t;){var n=e(t);if(null!==n)return n;t=t.sibling;}return null;}(e):null;}var e3=x.unstable_scheduleCallback,e5=x.unstable_cancelCallback,e4=x.unstable_shouldYield,e6=x.unstable_requestPaint,e8=x.unstable_now,e9=x.unstable_getCurrentPriorityLevel,e7=x.unstable_ImmediatePriority,te=x.unstable_UserBlockingPriority,tt=x.unstable_NormalPriority,tn=x.unstable_LowPriority,tr=x.unstable_IdlePriority,ta=null,ti=null,to=Math.clz32?Math.clz32:function(e){return 0==(e>>>=0)?32:31-(tl(e)/tu|0)|0;},tl=Math.log,tu=Math.LN2,ts=64,tc=4194304;function tf(e){switch(e&-e){case 1:return 1;case 2:return 2;case 4:return 4;case 8:return 8;case 16:return 16;case 32:return 32;case 64:case 128:case 256:case 512:case 1024:case 2048:case 4096:case 8192:case 16384:case 32768:case 65536:case 131072:case 262144:case 524288:case 1048576:case 2097152:return 4194240&e;case 4194304:case 8388608:case 16777216:case 33554432:case 67108864:return 130023424&e;case 134217728:return 134217728;case 268435456:return 268435456;case 536870912:return 536870912;case 1073741824:return 1073741824;default:return e;}}function td(e,t){var n=e.pendingLanes;if(0===n)return 0;var r=0,a=e.suspendedLanes,i=e.pingedLanes,o=268435455&n;if(0!==o){var l=o&~a;0!==l?r=tf(l):0!=(i&=o)&&(r=tf(i));}else 0!=(o=n&~a)?r=tf(o):0!==i&&(r=tf(i));if(0===r)return 0;if(0!==t&&t!==r&&0==(t&a)&&((a=r&-r)>=(i=t&-t)||16===a&&0!=(4194240&i)))return t;if(0!=(4&r)&&(r|=16&n),0!==(t=e.entangledLanes))for(e=e.entanglements,t&=r;0<t;)a=1<<(n=31-to(t)),r|=e[n],t&=~a;return r;}function th(e){return 0!=(e=-1073741825&e.pendingLanes)?e:1073741824&e?1073741824:0;}function tp(){var e=ts;return 0==(4194240&(ts<<=1))&&(ts=64),e;}function tg(e){for(var t=[],n=0;31>n;n++)t.push(e);return t;}function tv(e,t,n){e.pendingLanes|=t,536870912!==t&&(e.suspendedLanes=0,e.pingedLanes=0),(e=e.eventTimes)[t=31-to(t)]=n;}function tb(e,t){var n=e.entangledLanes|=t;for(e=e.entanglements;n;){var r=31-to(n),a=1<<r;a&t|e[r]&t&&(e[r]|=t),n&=~a;}}var tm=0;function ty(e){return 1<(e&=-e)?4<e?0!=(268435455&e)?16:536870912:4:1;}var tw=!1,tx=[],tS=null,tk=null,tC=null,tT=new Map,tz=new Map,tM=[],tP="mousedown mouseup touchcancel touchend touchstart auxclick dblclick pointercancel pointerdown pointerup dragend dragstart drop compositionend compositionstart keydown keypress keyup input textInput copy cut paste click change contextmenu reset submit".split(" ");function tE(e,t){switch(e){case"focusin":case"focusout":tS=null;break;case"dragenter":case"dragleave":tk=null;break;case"mouseover":case"mouseout":tC=null;break;case"pointerover":case"pointerout":tT.delete(t.pointerId);break;case"gotpointercapture":case"lostpointercapture":tz.delete(t.pointerId);}}function t_(e,t,n,r,a,i){return null===e||e.nativeEvent!==i?(e={blockedOn:t,domEventName:n,eventSystemFlags:r,nativeEvent:i,targetContainers:[a]},null!==t&&null!==(t=rO(t))&&l(t)):(e.eventSystemFlags|=r,t=e.targetContainers,null!==a&&-1===t.indexOf(a)&&t.push(a)),e;}function tL(e){var t=rN(e.target);if(null!==t){var n=eZ(t);if(null!==n){if(13===(t=n.tag)){if(null!==(t=e0(n))){e.blockedOn=t,c(e.priority,function(){u(n);});return;}}else if(3===t&&n.stateNode.current.memoizedState.isDehydrated){e.blockedOn=3===n.tag?n.stateNode.containerInfo:null;return;}}}e.blockedOn=null;}function tD(e){if(null!==e.blockedOn)return!1;for(var t=e.targetContainers;0<t.length;){var n=tq(e.domEventName,e.eventSystemFlags,t[0],e.nativeEvent);if(null!==n)return null!==(t=rO(n))&&l(t),e.blockedOn=n,!1;var r=new(n=e.nativeEvent).constructor(n.type,n);eL=r,n.target.dispatchEvent(r),eL=null,t.shift();}return!0;}function tF(e,t,n){tD(e)&&n.delete(t);}function tj(){tw=!1,null!==tS&&tD(tS)&&(tS=null),null!==tk&&tD(tk)&&(tk=null),null!==tC&&tD(tC)&&(tC=null),tT.forEach(tF),tz.forEach(tF);}function tN(e,t){e.blockedOn===t&&(e.blockedOn=null,tw||(tw=!0,x.unstable_scheduleCallback(x.unstable_NormalPriority,tj)));}function tO(e){function t(t){return tN(t,e);}if(0<tx.length){tN(tx[0],e);for(var n=1;n<tx.length;n++){var r=tx[n];r.blockedOn===e&&(r.blockedOn=null);}}for(null!==tS&&tN(tS,e),null!==tk&&tN(tk,e),null!==tC&&tN(tC,e),tT.forEach(t),tz.forEach(t),n=0;n<tM.length;n++)(r=tM[n]).blockedOn===e&&(r.blockedOn=null);for(;0<tM.length&&null===(n=tM[0]).blockedOn;)tL(n),null===n.blockedOn&&tM.shift();}var tR=R.ReactCurrentBatchConfig,tA=!0;function tI(e,t,n,r){var a=tm,i=tR.transition;tR.transition=null;try{tm=1,tG(e,t,n,r);}finally{tm=a,tR.transition=i;}}function tB(e,t,n,r){var a=tm,i=tR.transition;tR.transition=null;try{tm=4,tG(e,t,n,r);}finally{tm=a,tR.transition=i;}}function tG(e,t,n,r){if(tA){var a=tq(e,t,n,r);if(null===a)rl(e,t,r,tH,n),tE(e,r);else if(function(e,t,n,r,a){switch(t){case"focusin":return tS=t_(tS,e,t,n,r,a),!0;case"dragenter":return tk=t_(tk,e,t,n,r,a),!0;case"mouseover":return tC=t_(tC,e,t,n,r,a),!0;case"pointerover":var i=a.pointerId;return tT.set(i,t_(tT.get(i)||null,e,t,n,r,a)),!0;case"gotpointercapture":return i=a.pointerId,tz.set(i,t_(tz.get(i)||null,e,t,n,r,a)),!0;}return!1;}(a,e,t,n,r))r.stopPropagation();else if(tE(e,r),4&t&&-1<tP.indexOf(e)){for(;null!==a;){var i=rO(a);if(null!==i&&o(i),null===(i=tq(e,t,n,r))&&rl(e,t,r,tH,n),i===a)break;a=i;}null!==a&&r.stopPropagation();}else rl(e,t,r,null,n);}}var tH=null;function tq(e,t,n,r){if(tH=null,null!==(e=rN(e=eD(r)))){if(null===(t=eZ(e)))e=null;else if(13===(n=t.tag)){if(null!==(e=e0(t)))return e;e=null;}else if(3===n){if(t.stateNode.current.memoizedState.isDehydrated)return 3===t.tag?t.stateNode.containerInfo:null;e=null;}else t!==e&&(e=null);}return tH=e,null;}function tU(e){switch(e){case"cancel":case"click":case"close":case"contextmenu":case"copy":case"cut":case"auxclick":case"dblclick":case"dragend":case"dragstart":case"drop":case"focusin":case"focusout":case"input":case"invalid":case"keydown":case"keypress":case"keyup":case"mousedown":case"mouseup":case"paste":case"pause":case"play":case"pointercancel":case"pointerdown":case"pointerup":case"ratechange":case"reset":case"resize":case"seeked":case"submit":case"touchcancel":case"touchend":case"touchstart":case"volumechange":case"change":case"selectionchange":case"textInput":case"compositionstart":case"compositionend":case"compositionupdate":case"beforeblur":case"afterblur":case"beforeinput":case"blur":case"fullscreenchange":case"focus":case"hashchange":case"popstate":case"select":case"selectstart":return 1;case"drag":case"dragenter":case"dragexit":case"dragleave":case"dragover":case"mousemove":case"mouseout":case"mouseover":case"pointermove":case"pointerout":case"pointerover":case"scroll":case"toggle":case"touchmove":case"wheel":case"mouseenter":case"mouseleave":case"pointerenter":case"pointerleave":return 4;case"message":switch(e9()){case e7:return 1;case te:return 4;case tt:case tn:return 16;case tr:return 536870912;default:return 16;}default:return 16;}}var tW=null,tV=null,t$=null;function tK(){if(t$)return t$;var e,t,n=tV,r=n.length,a="value"in tW?tW.value:tW.textContent,i=a.length;for(e=0;e<r&&n[e]===a[e];e++);var o=r-e;for(t=1;t<=o&&n[r-t]===a[i-t];t++);return t$=a.slice(e,1<t?1-t:void 0);}function tQ(e){var t=e.keyCode;return"charCode"in e?0===(e=e.charCode)&&13===t&&(e=13):e=t,10===e&&(e=13),32<=e||13===e?e:0;}function tY(){return!0;}function tX(){return!1;}function tJ(e){function t(t,n,r,a,i){for(var o in this._reactName=t,this._targetInst=r,this.type=n,this.nativeEvent=a,this.target=i,this.currentTarget=null,e)e.hasOwnProperty(o)&&(t=e[o],this[o]=t?t(a):a[o]);return this.isDefaultPrevented=(null!=a.defaultPrevented?a.defaultPrevented:!1===a.returnValue)?tY:tX,this.isPropagationStopped=tX,this;}return Z(t.prototype,{preventDefault:function(){this.defaultPrevented=!0;var e=this.nativeEvent;e&&(e.preventDefault?e.preventDefault():"unknown"!=typeof e.returnValue&&(e.returnValue=!1),this.isDefaultPrevented=tY);},stopPropagation:function(){var e=this.nativeEvent;e&&(e.stopPropagation?e.stopPropagation():"unknown"!=typeof e.cancelBubble&&(e.cancelBubble=!0),this.isPropagationStopped=tY);},persist:function(){},isPersistent:tY}),t;}var tZ={eventPhase:0,bubbles:0,cancelable:0,timeStamp:function(e){return e.timeStamp||Date.now();},defaultPrevented:0,isTrusted:0},t0=tJ(tZ),t1=Z({},tZ,{view:0,detail:0}),t2=tJ(t1),t3=Z({},t1,{screenX:0,screenY:0,clientX:0,clientY:0,pageX:0,pageY:0,ctrlKey:0,shiftKey:0,altKey:0,metaKey:0,getModifierState:na,button:0,buttons:0,relatedTarget:function(e){return void 0===e.relatedTarget?e.fromElement===e.srcElement?e.toElement:e.fromElement:e.relatedTarget;},movementX:function(e){return"movementX"in e?e.movementX:(e!==h&&(h&&"mousemove"===e.type?(f=e.screenX-h.screenX,d=e.screenY-h.screenY):d=f=0,h=e),f);},movementY:function(e){return"movementY"in e?e.movementY:d;}}),t5=tJ(t3),t4=tJ(Z({},t3,{dataTransfer:0})),t6=tJ(Z({},t1,{relatedTarget:0})),t8=tJ(Z({},tZ,{animationName:0,elapsedTime:0,pseudoElement:0})),t9=tJ(Z({},tZ,{clipboardData:function(e){return"clipboardData"in e?e.clipboardData:window.clipboardData;}})),t7=tJ(Z({},tZ,{data:0})),ne={Esc:"Escape",Spacebar:" ",Left:"ArrowLeft",Up:"ArrowUp",Right:"ArrowRight",Down:"ArrowDown",Del:"Delete",Win:"OS",Menu:"ContextMenu",Apps:"ContextMenu",Scroll:"ScrollLock",MozPrintableKey:"Unidentified"},nt={8:"Backspace",9:"Tab",12:"Clear",13:"Enter",16:"Shift",17:"Control",18:"Alt",19:"Pause",20:"CapsLock",27:"Escape",32:" ",33:"PageUp",34:"PageDown",35:"End",36:"Home",37:"ArrowLeft",38:"ArrowUp",39:"ArrowRight",40:"ArrowDown",45:"Insert",46:"Delete",112:"F1",113:"F2",114:"F3",115:"F4",116:"F5",117:"F6",118:"F7",119:"F8",120:"F9",121:"F10",122:"F11",123:"F12",144:"NumLock",145:"ScrollLock",224:"Meta"},nn={Alt:"altKey",Control:"ctrlKey",Meta:"metaKey",Shift:"shiftKey"};function nr(e){var t=this.nativeEvent;return t.getModifierState?t.getModifierState(e):!!(e=nn[e])&&!!t[e];}function na(){return nr;}var ni=tJ(Z({},t1,{key:function(e){if(e.key){var t=ne[e.key]||e.key;if("Unidentified"!==t)return t;}return"keypress"===e.type?13===(e=tQ(e))?"Enter":String.fromCharCode(e):"keydown"===e.type||"keyup"===e.type?nt[e.keyCode]||"Unidentified":"";},code:0,location:0,ctrlKey:0,shiftKey:0,altKey:0,metaKey:0,repeat:0,locale:0,getModifierState:na,charCode:function(e){return"keypress"===e.type?tQ(e):0;},keyCode:function(e){return"keydown"===e.type||"keyup"===e.type?e.keyCode:0;},which:function(e){return"keypress"===e.type?tQ(e):"keydown"===e.type||"keyup"===e.type?e.keyCode:0;}})),no=tJ(Z({},t3,{pointerId:0,width:0,height:0,pressure:0,tangentialPressure:0,tiltX:0,tiltY:0,twist:0,pointerType:0,isPrimary:0})),nl=tJ(Z({},t1,{touches:0,targetTouches:0,changedTouches:0,altKey:0,metaKey:0,ctrlKey:0,shiftKey:0,getModifierState:na})),nu=tJ(Z({},tZ,{propertyName:0,elapsedTime:0,pseudoElement:0})),ns=tJ(Z({},t3,{deltaX:function(e){return"deltaX"in e?e.deltaX:"wheelDeltaX"in e?-e.wheelDeltaX:0;},deltaY:function(e){return"deltaY"in e?e.deltaY:"wheelDeltaY"in e?-e.wheelDeltaY:"wheelDelta"in e?-e.wheelDelta:0;},deltaZ:0,deltaMode:0})),nc=[9,13,27,32],nf=M&&"CompositionEvent"in window,nd=null;M&&"documentMode"in document&&(nd=document.documentMode);var nh=M&&"TextEvent"in window&&!nd,np=M&&(!nf||nd&&8<nd&&11>=nd),ng=!1;function nv(e,t){switch(e){case"keyup":return -1!==nc.indexOf(t.keyCode);case"keydown":return 229!==t.keyCode;case"keypress":case"mousedown":case"focusout":return!0;default:return!1;}}function nb(e){return"object"==typeof(e=e.detail)&&"data"in e?e.data:null;}var nm=!1,ny={color:!0,date:!0,datetime:!0,"datetime-local":!0,email:!0,month:!0,number:!0,password:!0,range:!0,search:!0,tel:!0,text:!0,time:!0,url:!0,week:!0};function nw(e){var t=e&&e.nodeName&&e.nodeName.toLowerCase();return"input"===t?!!ny[e.type]:"textarea"===t;}function nx(e,t,n,r){eR(r),0<(t=rs(t,"onChange")).length&&(n=new t0("onChange","change",null,n,r),e.push({event:n,listeners:t}));}var nS=null,nk=null;function nC(e){rt(e,0);}function nT(e){if(eo(rR(e)))return e;}function nz(e,t){if("change"===e)return t;}var nM=!1;if(M){if(M){var nP="oninput"in document;if(!nP){var nE=document.createElement("div");nE.setAttribute("oninput","return;"),nP="function"==typeof nE.oninput;}p=nP;}else p=!1;nM=p&&(!document.documentMode||9<document.documentMode);}function n_(){nS&&(nS.detachEvent("onpropertychange",nL),nk=nS=null);}function nL(e){if("value"===e.propertyName&&nT(nk)){var t=[];nx(t,nk,e,eD(e)),eH(nC,t);}}function nD(e,t,n){"focusin"===e?(n_(),nS=t,nk=n,nS.attachEvent("onpropertychange",nL)):"focusout"===e&&n_();}function nF(e){if("selectionchange"===e||"keyup"===e||"keydown"===e)return nT(nk);}function nj(e,t){if("click"===e)return nT(t);}function nN(e,t){if("input"===e||"change"===e)return nT(t);}var nO="function"==typeof Object.is?Object.is:function(e,t){return e===t&&(0!==e||1/e==1/t)||e!=e&&t!=t;};function nR(e,t){if(nO(e,t))return!0;if("object"!=typeof e||null===e||"object"!=typeof t||null===t)return!1;var n=Object.keys(e),r=Object.keys(t);if(n.length!==r.length)return!1;for(r=0;r<n.length;r++){var a=n[r];if(!P.call(t,a)||!nO(e[a],t[a]))return!1;}return!0;}function nA(e){for(;e&&e.firstChild;)e=e.firstChild;return e;}function nI(e,t){var n,r=nA(e);for(e=0;r;){if(3===r.nodeType){if(n=e+r.textContent.length,e<=t&&n>=t)return{node:r,offset:t-e};e=n;}e:{for(;r;){if(r.nextSibling){r=r.nextSibling;break e;}r=r.parentNode;}r=void 0;}r=nA(r);}}function nB(){for(var e=window,t=el();t instanceof e.HTMLIFrameElement;){try{var n="string"==typeof t.contentWindow.location.href;}catch(e){n=!1;}if(n)e=t.contentWindow;else break;t=el(e.document);}return t;}function nG(e){var t=e&&e.nodeName&&e.nodeName.toLowerCase();return t&&("input"===t&&("text"===e.type||"search"===e.type||"tel"===e.type||"url"===e.type||"password"===e.type)||"textarea"===t||"true"===e.contentEditable);}var nH=M&&"documentMode"in document&&11>=document.documentMode,nq=null,nU=null,nW=null,nV=!1;function n$(e,t,n){var r=n.window===n?n.document:9===n.nodeType?n:n.ownerDocument;nV||null==nq||nq!==el(r)||(r="selectionStart"in(r=nq)&&nG(r)?{start:r.selectionStart,end:r.selectionEnd}:{anchorNode:(r=(r.ownerDocument&&r.ownerDocument.defaultView||window).getSelection()).anchorNode,anchorOffset:r.anchorOffset,focusNode:r.focusNode,focusOffset:r.focusOffset},nW&&nR(nW,r)||(nW=r,0<(r=rs(nU,"onSelect")).length&&(t=new t0("onSelect","select",null,t,n),e.push({event:t,listeners:r}),t.target=nq)));}function nK(e,t){var n={};return n[e.toLowerCase()]=t.toLowerCase(),n["Webkit"+e]="webkit"+t,n["Moz"+e]="moz"+t,n;}var nQ={animationend:nK("Animation","AnimationEnd"),animationiteration:nK("Animation","AnimationIteration"),animationstart:nK("Animation","AnimationStart"),transitionend:nK("Transition","TransitionEnd")},nY={},nX={};function nJ(e){if(nY[e])return nY[e];if(!nQ[e])return e;var t,n=nQ[e];for(t in n)if(n.hasOwnProperty(t)&&t in nX)return nY[e]=n[t];return e;}M&&(nX=document.createElement("div").style,"AnimationEvent"in window||(delete nQ.animationend.animation,delete nQ.animationiteration.animation,delete nQ.animationstart.animation),"TransitionEvent"in window||delete nQ.transitionend.transition);var nZ=nJ("animationend"),n0=nJ("animationiteration"),n1=nJ("animationstart"),n2=nJ("transitionend"),n3=new Map,n5="abort auxClick cancel canPlay canPlayThrough click close contextMenu copy cut drag dragEnd dragEnter dragExit dragLeave dragOver dragStart drop durationChange emptied encrypted ended error gotPointerCapture input invalid keyDown keyPress keyUp load loadedData loadedMetadata loadStart lostPointerCapture mouseDown mouseMove mouseOut mouseOver mouseUp paste pause play playing pointerCancel pointerDown pointerMove pointerOut pointerOver pointerUp progress rateChange reset resize seeked seeking stalled submit suspend timeUpdate touchCancel touchEnd touchStart volumeChange scroll toggle touchMove waiting wheel".split(" ");function n4(e,t){n3.set(e,t),T(t,[e]);}for(var n6=0;n6<n5.length;n6++){var n8=n5[n6];n4(n8.toLowerCase(),"on"+(n8[0].toUpperCase()+n8.slice(1)));}n4(nZ,"onAnimationEnd"),n4(n0,"onAnimationIteration"),n4(n1,"onAnimationStart"),n4("dblclick","onDoubleClick"),n4("focusin","onFocus"),n4("focusout","onBlur"),n4(n2,"onTransitionEnd"),z("onMouseEnter",["mouseout","mouseover"]),z("onMouseLeave",["mouseout","mouseover"]),z("onPointerEnter",["pointerout","pointerover"]),z("onPointerLeave",["pointerout","pointerover"]),T("onChange","change click focusin focusout input keydown keyup selectionchange".split(" ")),T("onSelect","focusout contextmenu dragend focusin keydown keyup mousedown mouseup selectionchange".split(" ")),T("onBeforeInput",["compositionend","keypress","textInput","paste"]),T("onCompositionEnd","compositionend focusout keydown keypress keyup mousedown".split(" ")),T("onCompositionStart","compositionstart focusout keydown keypress keyup mousedown".split(" ")),T("onCompositionUpdate","compositionupdate focusout keydown keypress keyup mousedown".split(" "));var n9="abort canplay canplaythrough durationchange emptied encrypted ended error loadeddata loadedmetadata loadstart pause play playing progress ratechange resize seeked seeking stalled suspend timeupdate volumechange waiting".split(" "),n7=new Set("cancel close invalid load scroll toggle".split(" ").concat(n9));function re(e,t,n){var r=e.type||"unknown-event";e.currentTarget=n,function(e,t,n,r,a,i,o,l,u){if(eJ.apply(this,arguments),e$){if(e$){var s=eK;e$=!1,eK=null;}else throw Error(S(198));eQ||(eQ=!0,eY=s);}}(r,t,void 0,e),e.currentTarget=null;}function rt(e,t){t=0!=(4&t);for(var n=0;n<e.length;n++){var r=e[n],a=r.event;r=r.listeners;e:{var i=void 0;if(t)for(var o=r.length-1;0<=o;o--){var l=r[o],u=l.instance,s=l.currentTarget;if(l=l.listener,u!==i&&a.isPropagationStopped())break e;re(a,l,s),i=u;}else for(o=0;o<r.length;o++){if(u=(l=r[o]).instance,s=l.currentTarget,l=l.listener,u!==i&&a.isPropagationStopped())break e;re(a,l,s),i=u;}}}if(eQ)throw e=eY,eQ=!1,eY=null,e;}function rn(e,t){var n=t[rD];void 0===n&&(n=t[rD]=new Set);var r=e+"__bubble";n.has(r)||(ro(t,e,2,!1),n.add(r));}function rr(e,t,n){var r=0;t&&(r|=4),ro(n,e,r,t);}var ra="_reactListening"+Math.random().toString(36).slice(2);function ri(e){if(!e[ra]){e[ra]=!0,k.forEach(function(t){"selectionchange"!==t&&(n7.has(t)||rr(t,!1,e),rr(t,!0,e));});var t=9===e.nodeType?e:e.ownerDocument;null===t||t[ra]||(t[ra]=!0,rr("selectionchange",!1,t));}}function ro(e,t,n,r){switch(tU(t)){case 1:var a=tI;break;case 4:a=tB;break;default:a=tG;}n=a.bind(null,t,n,e),a=void 0,eU&&("touchstart"===t||"touchmove"===t||"wheel"===t)&&(a=!0),r?void 0!==a?e.addEventListener(t,n,{capture:!0,passive:a}):e.addEventListener(t,n,!0):void 0!==a?e.addEventListener(t,n,{passive:a}):e.addEventListener(t,n,!1);}function rl(e,t,n,r,a){var i=r;if(0==(1&t)&&0==(2&t)&&null!==r)e:for(;;){if(null===r)return;var o=r.tag;if(3===o||4===o){var l=r.stateNode.containerInfo;if(l===a||8===l.nodeType&&l.parentNode===a)break;if(4===o)for(o=r.return;null!==o;){var u=o.tag;if((3===u||4===u)&&((u=o.stateNode.containerInfo)===a||8===u.nodeType&&u.parentNode===a))return;o=o.return;}for(;null!==l;){if(null===(o=rN(l)))return;if(5===(u=o.tag)||6===u){r=i=o;continue e;}l=l.parentNode;}}r=r.return;}eH(function(){var r=i,a=eD(n),o=[];e:{var l=n3.get(e);if(void 0!==l){var u=t0,s=e;switch(e){case"keypress":if(0===tQ(n))break e;case"keydown":case"keyup":u=ni;break;case"focusin":s="focus",u=t6;break;case"focusout":s="blur",u=t6;break;case"beforeblur":case"afterblur":u=t6;break;case"click":if(2===n.button)break e;case"auxclick":case"dblclick":case"mousedown":case"mousemove":case"mouseup":case"mouseout":case"mouseover":case"contextmenu":u=t5;break;case"drag":case"dragend":case"dragenter":case"dragexit":case"dragleave":case"dragover":case"dragstart":case"drop":u=t4;break;case"touchcancel":case"touchend":case"touchmove":case"touchstart":u=nl;break;case nZ:case n0:case n1:u=t8;break;case n2:u=nu;break;case"scroll":u=t2;break;case"wheel":u=ns;break;case"copy":case"cut":case"paste":u=t9;break;case"gotpointercapture":case"lostpointercapture":case"pointercancel":case"pointerdown":case"pointermove":case"pointerout":case"pointerover":case"pointerup":u=no;}var c=0!=(4&t),f=!c&&"scroll"===e,d=c?null!==l?l+"Capture":null:l;c=[];for(var h,p=r;null!==p;){var g=(h=p).stateNode;if(5===h.tag&&null!==g&&(h=g,null!==d&&null!=(g=eq(p,d))&&c.push(ru(p,g,h))),f)break;p=p.return;}0<c.length&&(l=new u(l,s,null,n,a),o.push({event:l,listeners:c}));}}if(0==(7&t)){if(l="mouseover"===e||"pointerover"===e,u="mouseout"===e||"pointerout"===e,!(l&&n!==eL&&(s=n.relatedTarget||n.fromElement)&&(rN(s)||s[rL]))&&(u||l)&&(l=a.window===a?a:(l=a.ownerDocument)?l.defaultView||l.parentWindow:window,u?(s=n.relatedTarget||n.toElement,u=r,null!==(s=s?rN(s):null)&&(f=eZ(s),s!==f||5!==s.tag&&6!==s.tag)&&(s=null)):(u=null,s=r),u!==s)){if(c=t5,g="onMouseLeave",d="onMouseEnter",p="mouse",("pointerout"===e||"pointerover"===e)&&(c=no,g="onPointerLeave",d="onPointerEnter",p="pointer"),f=null==u?l:rR(u),h=null==s?l:rR(s),(l=new c(g,p+"leave",u,n,a)).target=f,l.relatedTarget=h,g=null,rN(a)===r&&((c=new c(d,p+"enter",s,n,a)).target=h,c.relatedTarget=f,g=c),f=g,u&&s)t:{for(c=u,d=s,p=0,h=c;h;h=rc(h))p++;for(h=0,g=d;g;g=rc(g))h++;for(;0<p-h;)c=rc(c),p--;for(;0<h-p;)d=rc(d),h--;for(;p--;){if(c===d||null!==d&&c===d.alternate)break t;c=rc(c),d=rc(d);}c=null;}else c=null;null!==u&&rf(o,l,u,c,!1),null!==s&&null!==f&&rf(o,f,s,c,!0);}e:{if("select"===(u=(l=r?rR(r):window).nodeName&&l.nodeName.toLowerCase())||"input"===u&&"file"===l.type)var v,b=nz;else if(nw(l)){if(nM)b=nN;else{b=nF;var m=nD;}}else(u=l.nodeName)&&"input"===u.toLowerCase()&&("checkbox"===l.type||"radio"===l.type)&&(b=nj);if(b&&(b=b(e,r))){nx(o,b,n,a);break e;}m&&m(e,l,r),"focusout"===e&&(m=l._wrapperState)&&m.controlled&&"number"===l.type&&eh(l,"number",l.value);}switch(m=r?rR(r):window,e){case"focusin":(nw(m)||"true"===m.contentEditable)&&(nq=m,nU=r,nW=null);break;case"focusout":nW=nU=nq=null;break;case"mousedown":nV=!0;break;case"contextmenu":case"mouseup":case"dragend":nV=!1,n$(o,n,a);break;case"selectionchange":if(nH)break;case"keydown":case"keyup":n$(o,n,a);}if(nf)t:{switch(e){case"compositionstart":var y="onCompositionStart";break t;case"compositionend":y="onCompositionEnd";break t;case"compositionupdate":y="onCompositionUpdate";break t;}y=void 0;}else nm?nv(e,n)&&(y="onCompositionEnd"):"keydown"===e&&229===n.keyCode&&(y="onCompositionStart");y&&(np&&"ko"!==n.locale&&(nm||"onCompositionStart"!==y?"onCompositionEnd"===y&&nm&&(v=tK()):(tV="value"in(tW=a)?tW.value:tW.textContent,nm=!0)),0<(m=rs(r,y)).length&&(y=new t7(y,e,null,n,a),o.push({event:y,listeners:m}),v?y.data=v:null!==(v=nb(n))&&(y.data=v))),(v=nh?function(e,t){switch(e){case"compositionend":return nb(t);case"keypress":if(32!==t.which)return null;return ng=!0," ";case"textInput":return" "===(e=t.data)&&ng?null:e;default:return null;}}(e,n):function(e,t){if(nm)return"compositionend"===e||!nf&&nv(e,t)?(e=tK(),t$=tV=tW=null,nm=!1,e):null;switch(e){case"paste":default:return null;case"keypress":if(!(t.ctrlKey||t.altKey||t.metaKey)||t.ctrlKey&&t.altKey){if(t.char&&1<t.char.length)return t.char;if(t.which)return String.fromCharCode(t.which);}return null;case"compositionend":return np&&"ko"!==t.locale?null:t.data;}}(e,n))&&0<(r=rs(r,"onBeforeInput")).length&&(a=new t7("onBeforeInput","beforeinput",null,n,a),o.push({event:a,listeners:r}),a.data=v);}rt(o,t);});}function ru(e,t,n){return{instance:e,listener:t,currentTarget:n};}function rs(e,t){for(var n=t+"Capture",r=[];null!==e;){var a=e,i=a.stateNode;5===a.tag&&null!==i&&(a=i,null!=(i=eq(e,n))&&r.unshift(ru(e,i,a)),null!=(i=eq(e,t))&&r.push(ru(e,i,a))),e=e.return;}return r;}function rc(e){if(null===e)return null;do e=e.return;while(e&&5!==e.tag)return e||null;}function rf(e,t,n,r,a){for(var i=t._reactName,o=[];null!==n&&n!==r;){var l=n,u=l.alternate,s=l.stateNode;if(null!==u&&u===r)break;5===l.tag&&null!==s&&(l=s,a?null!=(u=eq(n,i))&&o.unshift(ru(n,u,l)):a||null!=(u=eq(n,i))&&o.push(ru(n,u,l))),n=n.return;}0!==o.length&&e.push({event:t,listeners:o});}var rd=/\r\n?/g,rh=/\u0000|\uFFFD/g;function rp(e){return("string"==typeof e?e:""+e).replace(rd,"\n").replace(rh,"");}function rg(e,t,n){if(t=rp(t),rp(e)!==t&&n)throw Error(S(425));}function rv(){}var rb=null,rm=null;function ry(e,t){return"textarea"===e||"noscript"===e||"string"==typeof t.children||"number"==typeof t.children||"object"==typeof t.dangerouslySetInnerHTML&&null!==t.dangerouslySetInnerHTML&&null!=t.dangerouslySetInnerHTML.__html;}var rw="function"==typeof setTimeout?setTimeout:void 0,rx="function"==typeof clearTimeout?clearTimeout:void 0,rS="function"==typeof Promise?Promise:void 0,rk="function"==typeof queueMicrotask?queueMicrotask:void 0!==rS?function(e){return rS.resolve(null).then(e).catch(rC);}:rw;function rC(e){setTimeout(function(){throw e;});}function rT(e,t){var n=t,r=0;do{var a=n.nextSibling;if(e.removeChild(n),a&&8===a.nodeType){if("/$"===(n=a.data)){if(0===r){e.removeChild(a),tO(t);return;}r--;}else"$"!==n&&"$?"!==n&&"$!"!==n||r++;}n=a;}while(n)tO(t);}function rz(e){for(;null!=e;e=e.nextSibling){var t=e.nodeType;if(1===t||3===t)break;if(8===t){if("$"===(t=e.data)||"$!"===t||"$?"===t)break;if("/$"===t)return null;}}return e;}function rM(e){e=e.previousSibling;for(var t=0;e;){if(8===e.nodeType){var n=e.data;if("$"===n||"$!"===n||"$?"===n){if(0===t)return e;t--;}else"/$"===n&&t++;}e=e.previousSibling;}return null;}var rP=Math.random().toString(36).slice(2),rE="__reactFiber$"+rP,r_="__reactProps$"+rP,rL="__reactContainer$"+rP,rD="__reactEvents$"+rP,rF="__reactListeners$"+rP,rj="__reactHandles$"+rP;function rN(e){var t=e[rE];if(t)return t;for(var n=e.parentNode;n;){if(t=n[rL]||n[rE]){if(n=t.alternate,null!==t.child||null!==n&&null!==n.child)for(e=rM(e);null!==e;){if(n=e[rE])return n;e=rM(e);}return t;}n=(e=n).parentNode;}return null;}function rO(e){return(e=e[rE]||e[rL])&&(5===e.tag||6===e.tag||13===e.tag||3===e.tag)?e:null;}function rR(e){if(5===e.tag||6===e.tag)return e.stateNode;throw Error(S(33));}function rA(e){return e[r_]||null;}var rI=[],rB=-1;function rG(e){return{current:e};}function rH(e){0>rB||(e.current=rI[rB],rI[rB]=null,rB--);}function rq(e,t){rI[++rB]=e.current,e.current=t;}var rU={},rW=rG(rU),rV=rG(!1),r$=rU;function rK(e,t){var n=e.type.contextTypes;if(!n)return rU;var r=e.stateNode;if(r&&r.__reactInternalMemoizedUnmaskedChildContext===t)return r.__reactInternalMemoizedMaskedChildContext;var a,i={};for(a in n)i[a]=t[a];return r&&((e=e.stateNode).__reactInternalMemoizedUnmaskedChildContext=t,e.__reactInternalMemoizedMaskedChildContext=i),i;}function rQ(e){return null!=(e=e.childContextTypes);}function rY(){rH(rV),rH(rW);}function rX(e,t,n){if(rW.current!==rU)throw Error(S(168));rq(rW,t),rq(rV,n);}function rJ(e,t,n){var r=e.stateNode;if(t=t.childContextTypes,"function"!=typeof r.getChildContext)return n;for(var a in r=r.getChildContext())if(!(a in t))throw Error(S(108,function(e){var t=e.type;switch(e.tag){case 24:return"Cache";case 9:return(t.displayName||"Context")+".Consumer";case 10:return(t._context.displayName||"Context")+".Provider";case 18:return"DehydratedFragment";case 11:return e=(e=t.render).displayName||e.name||"",t.displayName||(""!==e?"ForwardRef("+e+")":"ForwardRef");case 7:return"Fragment";case 5:return t;case 4:return"Portal";case 3:return"Root";case 6:return"Text";case 16:return function e(t){if(null==t)return null;if("function"==typeof t)return t.displayName||t.name||null;if("string"==typeof t)return t;switch(t){case B:return"Fragment";case I:return"Portal";case H:return"Profiler";case G:return"StrictMode";case V:return"Suspense";case $:return"SuspenseList";}if("object"==typeof t)switch(t.$$typeof){case U:return(t.displayName||"Context")+".Consumer";case q:return(t._context.displayName||"Context")+".Provider";case W:var n=t.render;return(t=t.displayName)||(t=""!==(t=n.displayName||n.name||"")?"ForwardRef("+t+")":"ForwardRef"),t;case K:return null!==(n=t.displayName||null)?n:e(t.type)||"Memo";case Q:n=t._payload,t=t._init;try{return e(t(n));}catch(e){}}return null;}(t);case 8:return t===G?"StrictMode":"Mode";case 22:return"Offscreen";case 12:return"Profiler";case 21:return"Scope";case 13:return"Suspense";case 19:return"SuspenseList";case 25:return"TracingMarker";case 1:case 0:case 17:case 2:case 14:case 15:if("function"==typeof t)return t.displayName||t.name||null;if("string"==typeof t)return t;}return null;}(e)||"Unknown",a));return Z({},n,r);}function rZ(e){return e=(e=e.stateNode)&&e.__reactInternalMemoizedMergedChildContext||rU,r$=rW.current,rq(rW,e),rq(rV,rV.current),!0;}function r0(e,t,n){var r=e.stateNode;if(!r)throw Error(S(169));n?(e=rJ(e,t,r$),r.__reactInternalMemoizedMergedChildContext=e,rH(rV),rH(rW),rq(rW,e)):rH(rV),rq(rV,n);}var r1=null,r2=!1,r3=!1;function r5(e){null===r1?r1=[e]:r1.push(e);}function r4(){if(!r3&&null!==r1){r3=!0;var e=0,t=tm;try{var n=r1;for(tm=1;e<n.length;e++){var r=n[e];do r=r(!0);while(null!==r)}r1=null,r2=!1;}catch(t){throw null!==r1&&(r1=r1.slice(e+1)),e3(e7,r4),t;}finally{tm=t,r3=!1;}}return null;}var r6=[],r8=0,r9=null,r7=0,ae=[],at=0,an=null,ar=1,aa="";function ai(e,t){r6[r8++]=r7,r6[r8++]=r9,r9=e,r7=t;}function ao(e,t,n){ae[at++]=ar,ae[at++]=aa,ae[at++]=an,an=e;var r=ar;e=aa;var a=32-to(r)-1;r&=~(1<<a),n+=1;var i=32-to(t)+a;if(30<i){var o=a-a%5;i=(r&(1<<o)-1).toString(32),r>>=o,a-=o,ar=1<<32-to(t)+a|n<<a|r,aa=i+e;}else ar=1<<i|n<<a|r,aa=e;}function al(e){null!==e.return&&(ai(e,1),ao(e,1,0));}function au(e){for(;e===r9;)r9=r6[--r8],r6[r8]=null,r7=r6[--r8],r6[r8]=null;for(;e===an;)an=ae[--at],ae[at]=null,aa=ae[--at],ae[at]=null,ar=ae[--at],ae[at]=null;}var as=null,ac=null,af=!1,ad=null;function ah(e,t){var n=lQ(5,null,null,0);n.elementType="DELETED",n.stateNode=t,n.return=e,null===(t=e.deletions)?(e.deletions=[n],e.flags|=16):t.push(n);}function ap(e,t){switch(e.tag){case 5:var n=e.type;return null!==(t=1!==t.nodeType||n.toLowerCase()!==t.nodeName.toLowerCase()?null:t)&&(e.stateNode=t,as=e,ac=rz(t.firstChild),!0);case 6:return null!==(t=""===e.pendingProps||3!==t.nodeType?null:t)&&(e.stateNode=t,as=e,ac=null,!0);case 13:return null!==(t=8!==t.nodeType?null:t)&&(n=null!==an?{id:ar,overflow:aa}:null,e.memoizedState={dehydrated:t,treeContext:n,retryLane:1073741824},(n=lQ(18,null,null,0)).stateNode=t,n.return=e,e.child=n,as=e,ac=null,!0);default:return!1;}}function ag(e){return 0!=(1&e.mode)&&0==(128&e.flags);}function av(e){if(af){var t=ac;if(t){var n=t;if(!ap(e,t)){if(ag(e))throw Error(S(418));t=rz(n.nextSibling);var r=as;t&&ap(e,t)?ah(r,n):(e.flags=-4097&e.flags|2,af=!1,as=e);}}else{if(ag(e))throw Error(S(418));e.flags=-4097&e.flags|2,af=!1,as=e;}}}function ab(e){for(e=e.return;null!==e&&5!==e.tag&&3!==e.tag&&13!==e.tag;)e=e.return;as=e;}function am(e){if(e!==as)return!1;if(!af)return ab(e),af=!0,!1;if((t=3!==e.tag)&&!(t=5!==e.tag)&&(t="head"!==(t=e.type)&&"body"!==t&&!ry(e.type,e.memoizedProps)),t&&(t=ac)){if(ag(e))throw ay(),Error(S(418));for(;t;)ah(e,t),t=rz(t.nextSibling);}if(ab(e),13===e.tag){if(!(e=null!==(e=e.memoizedState)?e.dehydrated:null))throw Error(S(317));e:{for(t=0,e=e.nextSibling;e;){if(8===e.nodeType){var t,n=e.data;if("/$"===n){if(0===t){ac=rz(e.nextSibling);break e;}t--;}else"$"!==n&&"$!"!==n&&"$?"!==n||t++;}e=e.nextSibling;}ac=null;}}else ac=as?rz(e.stateNode.nextSibling):null;return!0;}function ay(){for(var e=ac;e;)e=rz(e.nextSibling);}function aw(){ac=as=null,af=!1;}function ax(e){null===ad?ad=[e]:ad.push(e);}var aS=R.ReactCurrentBatchConfig;function ak(e,t){if(e&&e.defaultProps)for(var n in t=Z({},t),e=e.defaultProps)void 0===t[n]&&(t[n]=e[n]);return t;}var aC=rG(null),aT=null,az=null,aM=null;function aP(){aM=az=aT=null;}function aE(e){var t=aC.current;rH(aC),e._currentValue=t;}function a_(e,t,n){for(;null!==e;){var r=e.alternate;if((e.childLanes&t)!==t?(e.childLanes|=t,null!==r&&(r.childLanes|=t)):null!==r&&(r.childLanes&t)!==t&&(r.childLanes|=t),e===n)break;e=e.return;}}function aL(e,t){aT=e,aM=az=null,null!==(e=e.dependencies)&&null!==e.firstContext&&(0!=(e.lanes&t)&&(ou=!0),e.firstContext=null);}function aD(e){var t=e._currentValue;if(aM!==e){if(e={context:e,memoizedValue:t,next:null},null===az){if(null===aT)throw Error(S(308));az=e,aT.dependencies={lanes:0,firstContext:e};}else az=az.next=e;}return t;}var aF=null;function aj(e){null===aF?aF=[e]:aF.push(e);}function aN(e,t,n,r){var a=t.interleaved;return null===a?(n.next=n,aj(t)):(n.next=a.next,a.next=n),t.interleaved=n,aO(e,r);}function aO(e,t){e.lanes|=t;var n=e.alternate;for(null!==n&&(n.lanes|=t),n=e,e=e.return;null!==e;)e.childLanes|=t,null!==(n=e.alternate)&&(n.childLanes|=t),n=e,e=e.return;return 3===n.tag?n.stateNode:null;}var aR=!1;function aA(e){e.updateQueue={baseState:e.memoizedState,firstBaseUpdate:null,lastBaseUpdate:null,shared:{pending:null,interleaved:null,lanes:0},effects:null};}function aI(e,t){e=e.updateQueue,t.updateQueue===e&&(t.updateQueue={baseState:e.baseState,firstBaseUpdate:e.firstBaseUpdate,lastBaseUpdate:e.lastBaseUpdate,shared:e.shared,effects:e.effects});}function aB(e,t){return{eventTime:e,lane:t,tag:0,payload:null,callback:null,next:null};}function aG(e,t,n){var r=e.updateQueue;if(null===r)return null;if(r=r.shared,0!=(2&o4)){var a=r.pending;return null===a?t.next=t:(t.next=a.next,a.next=t),r.pending=t,aO(e,n);}return null===(a=r.interleaved)?(t.next=t,aj(r)):(t.next=a.next,a.next=t),r.interleaved=t,aO(e,n);}function aH(e,t,n){if(null!==(t=t.updateQueue)&&(t=t.shared,0!=(4194240&n))){var r=t.lanes;r&=e.pendingLanes,n|=r,t.lanes=n,tb(e,n);}}function aq(e,t){var n=e.updateQueue,r=e.alternate;if(null!==r&&n===(r=r.updateQueue)){var a=null,i=null;if(null!==(n=n.firstBaseUpdate)){do{var o={eventTime:n.eventTime,lane:n.lane,tag:n.tag,payload:n.payload,callback:n.callback,next:null};null===i?a=i=o:i=i.next=o,n=n.next;}while(null!==n)null===i?a=i=t:i=i.next=t;}else a=i=t;n={baseState:r.baseState,firstBaseUpdate:a,lastBaseUpdate:i,shared:r.shared,effects:r.effects},e.updateQueue=n;return;}null===(e=n.lastBaseUpdate)?n.firstBaseUpdate=t:e.next=t,n.lastBaseUpdate=t;}function aU(e,t,n,r){var a=e.updateQueue;aR=!1;var i=a.firstBaseUpdate,o=a.lastBaseUpdate,l=a.shared.pending;if(null!==l){a.shared.pending=null;var u=l,s=u.next;u.next=null,null===o?i=s:o.next=s,o=u;var c=e.alternate;null!==c&&(l=(c=c.updateQueue).lastBaseUpdate)!==o&&(null===l?c.firstBaseUpdate=s:l.next=s,c.lastBaseUpdate=u);}if(null!==i){var f=a.baseState;for(o=0,c=s=u=null,l=i;;){var d=l.lane,h=l.eventTime;if((r&d)===d){null!==c&&(c=c.next={eventTime:h,lane:0,tag:l.tag,payload:l.payload,callback:l.callback,next:null});e:{var p=e,g=l;switch(d=t,h=n,g.tag){case 1:if("function"==typeof(p=g.payload)){f=p.call(h,f,d);break e;}f=p;break e;case 3:p.flags=-65537&p.flags|128;case 0:if(null==(d="function"==typeof(p=g.payload)?p.call(h,f,d):p))break e;f=Z({},f,d);break e;case 2:aR=!0;}}null!==l.callback&&0!==l.lane&&(e.flags|=64,null===(d=a.effects)?a.effects=[l]:d.push(l));}else h={eventTime:h,lane:d,tag:l.tag,payload:l.payload,callback:l.callback,next:null},null===c?(s=c=h,u=f):c=c.next=h,o|=d;if(null===(l=l.next)){if(null===(l=a.shared.pending))break;l=(d=l).next,d.next=null,a.lastBaseUpdate=d,a.shared.pending=null;}}if(null===c&&(u=f),a.baseState=u,a.firstBaseUpdate=s,a.lastBaseUpdate=c,null!==(t=a.shared.interleaved)){a=t;do o|=a.lane,a=a.next;while(a!==t)}else null===i&&(a.shared.lanes=0);lr|=o,e.lanes=o,e.memoizedState=f;}}function aW(e,t,n){if(e=t.effects,t.effects=null,null!==e)for(t=0;t<e.length;t++){var r=e[t],a=r.callback;if(null!==a){if(r.callback=null,r=n,"function"!=typeof a)throw Error(S(191,a));a.call(r);}}}var aV=(new w.Component).refs;function a$(e,t,n,r){n=null==(n=n(r,t=e.memoizedState))?t:Z({},t,n),e.memoizedState=n,0===e.lanes&&(e.updateQueue.baseState=n);}var aK={isMounted:function(e){return!!(e=e._reactInternals)&&eZ(e)===e;},enqueueSetState:function(e,t,n){e=e._reactInternals;var r=lx(),a=lS(e),i=aB(r,a);i.payload=t,null!=n&&(i.callback=n),null!==(t=aG(e,i,a))&&(lk(t,e,a,r),aH(t,e,a));},enqueueReplaceState:function(e,t,n){e=e._reactInternals;var r=lx(),a=lS(e),i=aB(r,a);i.tag=1,i.payload=t,null!=n&&(i.callback=n),null!==(t=aG(e,i,a))&&(lk(t,e,a,r),aH(t,e,a));},enqueueForceUpdate:function(e,t){e=e._reactInternals;var n=lx(),r=lS(e),a=aB(n,r);a.tag=2,null!=t&&(a.callback=t),null!==(t=aG(e,a,r))&&(lk(t,e,r,n),aH(t,e,r));}};function aQ(e,t,n,r,a,i,o){return"function"==typeof(e=e.stateNode).shouldComponentUpdate?e.shouldComponentUpdate(r,i,o):!t.prototype||!t.prototype.isPureReactComponent||!nR(n,r)||!nR(a,i);}function aY(e,t,n){var r=!1,a=rU,i=t.contextType;return"object"==typeof i&&null!==i?i=aD(i):(a=rQ(t)?r$:rW.current,i=(r=null!=(r=t.contextTypes))?rK(e,a):rU),t=new t(n,i),e.memoizedState=null!==t.state&&void 0!==t.state?t.state:null,t.updater=aK,e.stateNode=t,t._reactInternals=e,r&&((e=e.stateNode).__reactInternalMemoizedUnmaskedChildContext=a,e.__reactInternalMemoizedMaskedChildContext=i),t;}function aX(e,t,n,r){e=t.state,"function"==typeof t.componentWillReceiveProps&&t.componentWillReceiveProps(n,r),"function"==typeof t.UNSAFE_componentWillReceiveProps&&t.UNSAFE_componentWillReceiveProps(n,r),t.state!==e&&aK.enqueueReplaceState(t,t.state,null);}function aJ(e,t,n,r){var a=e.stateNode;a.props=n,a.state=e.memoizedState,a.refs=aV,aA(e);var i=t.contextType;"object"==typeof i&&null!==i?a.context=aD(i):(i=rQ(t)?r$:rW.current,a.context=rK(e,i)),a.state=e.memoizedState,"function"==typeof(i=t.getDerivedStateFromProps)&&(a$(e,t,i,n),a.state=e.memoizedState),"function"==typeof t.getDerivedStateFromProps||"function"==typeof a.getSnapshotBeforeUpdate||"function"!=typeof a.UNSAFE_componentWillMount&&"function"!=typeof a.componentWillMount||(t=a.state,"function"==typeof a.componentWillMount&&a.componentWillMount(),"function"==typeof a.UNSAFE_componentWillMount&&a.UNSAFE_componentWillMount(),t!==a.state&&aK.enqueueReplaceState(a,a.state,null),aU(e,n,a,r),a.state=e.memoizedState),"function"==typeof a.componentDidMount&&(e.flags|=4194308);}function aZ(e,t,n){if(null!==(e=n.ref)&&"function"!=typeof e&&"object"!=typeof e){if(n._owner){if(n=n._owner){if(1!==n.tag)throw Error(S(309));var r=n.stateNode;}if(!r)throw Error(S(147,e));var a=r,i=""+e;return null!==t&&null!==t.ref&&"function"==typeof t.ref&&t.ref._stringRef===i?t.ref:((t=function(e){var t=a.refs;t===aV&&(t=a.refs={}),null===e?delete t[i]:t[i]=e;})._stringRef=i,t);}if("string"!=typeof e)throw Error(S(284));if(!n._owner)throw Error(S(290,e));}return e;}function a0(e,t){throw Error(S(31,"[object Object]"===(e=Object.prototype.toString.call(t))?"object with keys {"+Object.keys(t).join(", ")+"}":e));}function a1(e){return(0,e._init)(e._payload);}function a2(e){function t(t,n){if(e){var r=t.deletions;null===r?(t.deletions=[n],t.flags|=16):r.push(n);}}function n(n,r){if(!e)return null;for(;null!==r;)t(n,r),r=r.sibling;return null;}function r(e,t){for(e=new Map;null!==t;)null!==t.key?e.set(t.key,t):e.set(t.index,t),t=t.sibling;return e;}function a(e,t){return(e=lX(e,t)).index=0,e.sibling=null,e;}function i(t,n,r){return(t.index=r,e)?null!==(r=t.alternate)?(r=r.index)<n?(t.flags|=2,n):r:(t.flags|=2,n):(t.flags|=1048576,n);}function o(t){return e&&null===t.alternate&&(t.flags|=2),t;}function l(e,t,n,r){return null===t||6!==t.tag?(t=l1(n,e.mode,r)).return=e:(t=a(t,n)).return=e,t;}function u(e,t,n,r){var i=n.type;return i===B?c(e,t,n.props.children,r,n.key):(null!==t&&(t.elementType===i||"object"==typeof i&&null!==i&&i.$$typeof===Q&&a1(i)===t.type)?(r=a(t,n.props)).ref=aZ(e,t,n):(r=lJ(n.type,n.key,n.props,null,e.mode,r)).ref=aZ(e,t,n),r.return=e,r);}function s(e,t,n,r){return null===t||4!==t.tag||t.stateNode.containerInfo!==n.containerInfo||t.stateNode.implementation!==n.implementation?(t=l2(n,e.mode,r)).return=e:(t=a(t,n.children||[])).return=e,t;}function c(e,t,n,r,i){return null===t||7!==t.tag?(t=lZ(n,e.mode,r,i)).return=e:(t=a(t,n)).return=e,t;}function f(e,t,n){if("string"==typeof t&&""!==t||"number"==typeof t)return(t=l1(""+t,e.mode,n)).return=e,t;if("object"==typeof t&&null!==t){switch(t.$$typeof){case A:return(n=lJ(t.type,t.key,t.props,null,e.mode,n)).ref=aZ(e,null,t),n.return=e,n;case I:return(t=l2(t,e.mode,n)).return=e,t;case Q:return f(e,(0,t._init)(t._payload),n);}if(ep(t)||J(t))return(t=lZ(t,e.mode,n,null)).return=e,t;a0(e,t);}return null;}function d(e,t,n,r){var a=null!==t?t.key:null;if("string"==typeof n&&""!==n||"number"==typeof n)return null!==a?null:l(e,t,""+n,r);if("object"==typeof n&&null!==n){switch(n.$$typeof){case A:return n.key===a?u(e,t,n,r):null;case I:return n.key===a?s(e,t,n,r):null;case Q:return d(e,t,(a=n._init)(n._payload),r);}if(ep(n)||J(n))return null!==a?null:c(e,t,n,r,null);a0(e,n);}return null;}function h(e,t,n,r,a){if("string"==typeof r&&""!==r||"number"==typeof r)return l(t,e=e.get(n)||null,""+r,a);if("object"==typeof r&&null!==r){switch(r.$$typeof){case A:return u(t,e=e.get(null===r.key?n:r.key)||null,r,a);case I:return s(t,e=e.get(null===r.key?n:r.key)||null,r,a);case Q:return h(e,t,n,(0,r._init)(r._payload),a);}if(ep(r)||J(r))return c(t,e=e.get(n)||null,r,a,null);a0(t,r);}return null;}return function l(u,s,c,p){if("object"==typeof c&&null!==c&&c.type===B&&null===c.key&&(c=c.props.children),"object"==typeof c&&null!==c){switch(c.$$typeof){case A:e:{for(var g=c.key,v=s;null!==v;){if(v.key===g){if((g=c.type)===B){if(7===v.tag){n(u,v.sibling),(s=a(v,c.props.children)).return=u,u=s;break e;}}else if(v.elementType===g||"object"==typeof g&&null!==g&&g.$$typeof===Q&&a1(g)===v.type){n(u,v.sibling),(s=a(v,c.props)).ref=aZ(u,v,c),s.return=u,u=s;break e;}n(u,v);break;}t(u,v),v=v.sibling;}c.type===B?((s=lZ(c.props.children,u.mode,p,c.key)).return=u,u=s):((p=lJ(c.type,c.key,c.props,null,u.mode,p)).ref=aZ(u,s,c),p.return=u,u=p);}return o(u);case I:e:{for(v=c.key;null!==s;){if(s.key===v){if(4===s.tag&&s.stateNode.containerInfo===c.containerInfo&&s.stateNode.implementation===c.implementation){n(u,s.sibling),(s=a(s,c.children||[])).return=u,u=s;break e;}n(u,s);break;}t(u,s),s=s.sibling;}(s=l2(c,u.mode,p)).return=u,u=s;}return o(u);case Q:return l(u,s,(v=c._init)(c._payload),p);}if(ep(c))return function(a,o,l,u){for(var s=null,c=null,p=o,g=o=0,v=null;null!==p&&g<l.length;g++){p.index>g?(v=p,p=null):v=p.sibling;var b=d(a,p,l[g],u);if(null===b){null===p&&(p=v);break;}e&&p&&null===b.alternate&&t(a,p),o=i(b,o,g),null===c?s=b:c.sibling=b,c=b,p=v;}if(g===l.length)return n(a,p),af&&ai(a,g),s;if(null===p){for(;g<l.length;g++)null!==(p=f(a,l[g],u))&&(o=i(p,o,g),null===c?s=p:c.sibling=p,c=p);return af&&ai(a,g),s;}for(p=r(a,p);g<l.length;g++)null!==(v=h(p,a,g,l[g],u))&&(e&&null!==v.alternate&&p.delete(null===v.key?g:v.key),o=i(v,o,g),null===c?s=v:c.sibling=v,c=v);return e&&p.forEach(function(e){return t(a,e);}),af&&ai(a,g),s;}(u,s,c,p);if(J(c))return function(a,o,l,u){var s=J(l);if("function"!=typeof s)throw Error(S(150));if(null==(l=s.call(l)))throw Error(S(151));for(var c=s=null,p=o,g=o=0,v=null,b=l.next();null!==p&&!b.done;g++,b=l.next()){p.index>g?(v=p,p=null):v=p.sibling;var m=d(a,p,b.value,u);if(null===m){null===p&&(p=v);break;}e&&p&&null===m.alternate&&t(a,p),o=i(m,o,g),null===c?s=m:c.sibling=m,c=m,p=v;}if(b.done)return n(a,p),af&&ai(a,g),s;if(null===p){for(;!b.done;g++,b=l.next())null!==(b=f(a,b.value,u))&&(o=i(b,o,g),null===c?s=b:c.sibling=b,c=b);return af&&ai(a,g),s;}for(p=r(a,p);!b.done;g++,b=l.next())null!==(b=h(p,a,g,b.value,u))&&(e&&null!==b.alternate&&p.delete(null===b.key?g:b.key),o=i(b,o,g),null===c?s=b:c.sibling=b,c=b);return e&&p.forEach(function(e){return t(a,e);}),af&&ai(a,g),s;}(u,s,c,p);a0(u,c);}return"string"==typeof c&&""!==c||"number"==typeof c?(c=""+c,null!==s&&6===s.tag?(n(u,s.sibling),(s=a(s,c)).return=u):(n(u,s),(s=l1(c,u.mode,p)).return=u),o(u=s)):n(u,s);};}var a3=a2(!0),a5=a2(!1),a4={},a6=rG(a4),a8=rG(a4),a9=rG(a4);function a7(e){if(e===a4)throw Error(S(174));return e;}function ie(e,t){switch(rq(a9,t),rq(a8,e),rq(a6,a4),e=t.nodeType){case 9:case 11:t=(t=t.documentElement)?t.namespaceURI:ex(null,"");break;default:t=ex(t=(e=8===e?t.parentNode:t).namespaceURI||null,e=e.tagName);}rH(a6),rq(a6,t);}function it(){rH(a6),rH(a8),rH(a9);}function ir(e){a7(a9.current);var t=a7(a6.current),n=ex(t,e.type);t!==n&&(rq(a8,e),rq(a6,n));}function ia(e){a8.current===e&&(rH(a6),rH(a8));}var ii=rG(0);function io(e){for(var t=e;null!==t;){if(13===t.tag){var n=t.memoizedState;if(null!==n&&(null===(n=n.dehydrated)||"$?"===n.data||"$!"===n.data))return t;}else if(19===t.tag&&void 0!==t.memoizedProps.revealOrder){if(0!=(128&t.flags))return t;}else if(null!==t.child){t.child.return=t,t=t.child;continue;}if(t===e)break;for(;null===t.sibling;){if(null===t.return||t.return===e)return null;t=t.return;}t.sibling.return=t.return,t=t.sibling;}return null;}var il=[];function iu(){for(var e=0;e<il.length;e++)il[e]._workInProgressVersionPrimary=null;il.length=0;}var is=R.ReactCurrentDispatcher,ic=R.ReactCurrentBatchConfig,id=0,ih=null,ip=null,ig=null,iv=!1,ib=!1,im=0,iy=0;function iw(){throw Error(S(321));}function ix(e,t){if(null===t)return!1;for(var n=0;n<t.length&&n<e.length;n++)if(!nO(e[n],t[n]))return!1;return!0;}function iS(e,t,n,r,a,i){if(id=i,ih=t,t.memoizedState=null,t.updateQueue=null,t.lanes=0,is.current=null===e||null===e.memoizedState?i4:i6,e=n(r,a),ib){i=0;do{if(ib=!1,im=0,25<=i)throw Error(S(301));i+=1,ig=ip=null,t.updateQueue=null,is.current=i8,e=n(r,a);}while(ib)}if(is.current=i5,t=null!==ip&&null!==ip.next,id=0,ig=ip=ih=null,iv=!1,t)throw Error(S(300));return e;}function ik(){var e=0!==im;return im=0,e;}function iC(){var e={memoizedState:null,baseState:null,baseQueue:null,queue:null,next:null};return null===ig?ih.memoizedState=ig=e:ig=ig.next=e,ig;}function iT(){if(null===ip){var e=ih.alternate;e=null!==e?e.memoizedState:null;}else e=ip.next;var t=null===ig?ih.memoizedState:ig.next;if(null!==t)ig=t,ip=e;else{if(null===e)throw Error(S(310));e={memoizedState:(ip=e).memoizedState,baseState:ip.baseState,baseQueue:ip.baseQueue,queue:ip.queue,next:null},null===ig?ih.memoizedState=ig=e:ig=ig.next=e;}return ig;}function iz(e,t){return"function"==typeof t?t(e):t;}function iM(e){var t=iT(),n=t.queue;if(null===n)throw Error(S(311));n.lastRenderedReducer=e;var r=ip,a=r.baseQueue,i=n.pending;if(null!==i){if(null!==a){var o=a.next;a.next=i.next,i.next=o;}r.baseQueue=a=i,n.pending=null;}if(null!==a){i=a.next,r=r.baseState;var l=o=null,u=null,s=i;do{var c=s.lane;if((id&c)===c)null!==u&&(u=u.next={lane:0,action:s.action,hasEagerState:s.hasEagerState,eagerState:s.eagerState,next:null}),r=s.hasEagerState?s.eagerState:e(r,s.action);else{var f={lane:c,action:s.action,hasEagerState:s.hasEagerState,eagerState:s.eagerState,next:null};null===u?(l=u=f,o=r):u=u.next=f,ih.lanes|=c,lr|=c;}s=s.next;}while(null!==s&&s!==i)null===u?o=r:u.next=l,nO(r,t.memoizedState)||(ou=!0),t.memoizedState=r,t.baseState=o,t.baseQueue=u,n.lastRenderedState=r;}if(null!==(e=n.interleaved)){a=e;do i=a.lane,ih.lanes|=i,lr|=i,a=a.next;while(a!==e)}else null===a&&(n.lanes=0);return[t.memoizedState,n.dispatch];}function iP(e){var t=iT(),n=t.queue;if(null===n)throw Error(S(311));n.lastRenderedReducer=e;var r=n.dispatch,a=n.pending,i=t.memoizedState;if(null!==a){n.pending=null;var o=a=a.next;do i=e(i,o.action),o=o.next;while(o!==a)nO(i,t.memoizedState)||(ou=!0),t.memoizedState=i,null===t.baseQueue&&(t.baseState=i),n.lastRenderedState=i;}return[i,r];}function iE(){}function i_(e,t){var n=ih,r=iT(),a=t(),i=!nO(r.memoizedState,a);if(i&&(r.memoizedState=a,ou=!0),r=r.queue,iH(iF.bind(null,n,r,e),[e]),r.getSnapshot!==t||i||null!==ig&&1&ig.memoizedState.tag){if(n.flags|=2048,iR(9,iD.bind(null,n,r,a,t),void 0,null),null===o6)throw Error(S(349));0!=(30&id)||iL(n,t,a);}return a;}function iL(e,t,n){e.flags|=16384,e={getSnapshot:t,value:n},null===(t=ih.updateQueue)?(t={lastEffect:null,stores:null},ih.updateQueue=t,t.stores=[e]):null===(n=t.stores)?t.stores=[e]:n.push(e);}function iD(e,t,n,r){t.value=n,t.getSnapshot=r,ij(t)&&iN(e);}function iF(e,t,n){return n(function(){ij(t)&&iN(e);});}function ij(e){var t=e.getSnapshot;e=e.value;try{var n=t();return!nO(e,n);}catch(e){return!0;}}function iN(e){var t=aO(e,1);null!==t&&lk(t,e,1,-1);}function iO(e){var t=iC();return"function"==typeof e&&(e=e()),t.memoizedState=t.baseState=e,e={pending:null,interleaved:null,lanes:0,dispatch:null,lastRenderedReducer:iz,lastRenderedState:e},t.queue=e,e=e.dispatch=i0.bind(null,ih,e),[t.memoizedState,e];}function iR(e,t,n,r){return e={tag:e,create:t,destroy:n,deps:r,next:null},null===(t=ih.updateQueue)?(t={lastEffect:null,stores:null},ih.updateQueue=t,t.lastEffect=e.next=e):null===(n=t.lastEffect)?t.lastEffect=e.next=e:(r=n.next,n.next=e,e.next=r,t.lastEffect=e),e;}function iA(){return iT().memoizedState;}function iI(e,t,n,r){var a=iC();ih.flags|=e,a.memoizedState=iR(1|t,n,void 0,void 0===r?null:r);}function iB(e,t,n,r){var a=iT();r=void 0===r?null:r;var i=void 0;if(null!==ip){var o=ip.memoizedState;if(i=o.destroy,null!==r&&ix(r,o.deps)){a.memoizedState=iR(t,n,i,r);return;}}ih.flags|=e,a.memoizedState=iR(1|t,n,i,r);}function iG(e,t){return iI(8390656,8,e,t);}function iH(e,t){return iB(2048,8,e,t);}function iq(e,t){return iB(4,2,e,t);}function iU(e,t){return iB(4,4,e,t);}function iW(e,t){return"function"==typeof t?(t(e=e()),function(){t(null);}):null!=t?(e=e(),t.current=e,function(){t.current=null;}):void 0;}function iV(e,t,n){return n=null!=n?n.concat([e]):null,iB(4,4,iW.bind(null,t,e),n);}function i$(){}function iK(e,t){var n=iT();t=void 0===t?null:t;var r=n.memoizedState;return null!==r&&null!==t&&ix(t,r[1])?r[0]:(n.memoizedState=[e,t],e);}function iQ(e,t){var n=iT();t=void 0===t?null:t;var r=n.memoizedState;return null!==r&&null!==t&&ix(t,r[1])?r[0]:(e=e(),n.memoizedState=[e,t],e);}function iY(e,t,n){return 0==(21&id)?(e.baseState&&(e.baseState=!1,ou=!0),e.memoizedState=n):(nO(n,t)||(n=tp(),ih.lanes|=n,lr|=n,e.baseState=!0),t);}function iX(e,t){var n=tm;tm=0!==n&&4>n?n:4,e(!0);var r=ic.transition;ic.transition={};try{e(!1),t();}finally{tm=n,ic.transition=r;}}function iJ(){return iT().memoizedState;}function iZ(e,t,n){var r=lS(e);n={lane:r,action:n,hasEagerState:!1,eagerState:null,next:null},i1(e)?i2(t,n):null!==(n=aN(e,t,n,r))&&(lk(n,e,r,lx()),i3(n,t,r));}function i0(e,t,n){var r=lS(e),a={lane:r,action:n,hasEagerState:!1,eagerState:null,next:null};if(i1(e))i2(t,a);else{var i=e.alternate;if(0===e.lanes&&(null===i||0===i.lanes)&&null!==(i=t.lastRenderedReducer))try{var o=t.lastRenderedState,l=i(o,n);if(a.hasEagerState=!0,a.eagerState=l,nO(l,o)){var u=t.interleaved;null===u?(a.next=a,aj(t)):(a.next=u.next,u.next=a),t.interleaved=a;return;}}catch(e){}finally{}null!==(n=aN(e,t,a,r))&&(lk(n,e,r,a=lx()),i3(n,t,r));}}function i1(e){var t=e.alternate;return e===ih||null!==t&&t===ih;}function i2(e,t){ib=iv=!0;var n=e.pending;null===n?t.next=t:(t.next=n.next,n.next=t),e.pending=t;}function i3(e,t,n){if(0!=(4194240&n)){var r=t.lanes;r&=e.pendingLanes,n|=r,t.lanes=n,tb(e,n);}}var i5={readContext:aD,useCallback:iw,useContext:iw,useEffect:iw,useImperativeHandle:iw,useInsertionEffect:iw,useLayoutEffect:iw,useMemo:iw,useReducer:iw,useRef:iw,useState:iw,useDebugValue:iw,useDeferredValue:iw,useTransition:iw,useMutableSource:iw,useSyncExternalStore:iw,useId:iw,unstable_isNewReconciler:!1},i4={readContext:aD,useCallback:function(e,t){return iC().memoizedState=[e,void 0===t?null:t],e;},useContext:aD,useEffect:iG,useImperativeHandle:function(e,t,n){return n=null!=n?n.concat([e]):null,iI(4194308,4,iW.bind(null,t,e),n);},useLayoutEffect:function(e,t){return iI(4194308,4,e,t);},useInsertionEffect:function(e,t){return iI(4,2,e,t);},useMemo:function(e,t){var n=iC();return t=void 0===t?null:t,e=e(),n.memoizedState=[e,t],e;},useReducer:function(e,t,n){var r=iC();return t=void 0!==n?n(t):t,r.memoizedState=r.baseState=t,e={pending:null,interleaved:null,lanes:0,dispatch:null,lastRenderedReducer:e,lastRenderedState:t},r.queue=e,e=e.dispatch=iZ.bind(null,ih,e),[r.memoizedState,e];},useRef:function(e){return e={current:e},iC().memoizedState=e;},useState:iO,useDebugValue:i$,useDeferredValue:function(e){return iC().memoizedState=e;},useTransition:function(){var e=iO(!1),t=e[0];return e=iX.bind(null,e[1]),iC().memoizedState=e,[t,e];},useMutableSource:function(){},useSyncExternalStore:function(e,t,n){var r=ih,a=iC();if(af){if(void 0===n)throw Error(S(407));n=n();}else{if(n=t(),null===o6)throw Error(S(349));0!=(30&id)||iL(r,t,n);}a.memoizedState=n;var i={value:n,getSnapshot:t};return a.queue=i,iG(iF.bind(null,r,i,e),[e]),r.flags|=2048,iR(9,iD.bind(null,r,i,n,t),void 0,null),n;},useId:function(){var e=iC(),t=o6.identifierPrefix;if(af){var n=aa,r=ar;t=":"+t+"R"+(n=(r&~(1<<32-to(r)-1)).toString(32)+n),0<(n=im++)&&(t+="H"+n.toString(32)),t+=":";}else t=":"+t+"r"+(n=iy++).toString(32)+":";return e.memoizedState=t;},unstable_isNewReconciler:!1},i6={readContext:aD,useCallback:iK,useContext:aD,useEffect:iH,useImperativeHandle:iV,useInsertionEffect:iq,useLayoutEffect:iU,useMemo:iQ,useReducer:iM,useRef:iA,useState:function(){return iM(iz);},useDebugValue:i$,useDeferredValue:function(e){return iY(iT(),ip.memoizedState,e);},useTransition:function(){return[iM(iz)[0],iT().memoizedState];},useMutableSource:iE,useSyncExternalStore:i_,useId:iJ,unstable_isNewReconciler:!1},i8={readContext:aD,useCallback:iK,useContext:aD,useEffect:iH,useImperativeHandle:iV,useInsertionEffect:iq,useLayoutEffect:iU,useMemo:iQ,useReducer:iP,useRef:iA,useState:function(){return iP(iz);},useDebugValue:i$,useDeferredValue:function(e){var t=iT();return null===ip?t.memoizedState=e:iY(t,ip.memoizedState,e);},useTransition:function(){return[iP(iz)[0],iT().memoizedState];},useMutableSource:iE,useSyncExternalStore:i_,useId:iJ,unstable_isNewReconciler:!1};function i9(e,t){try{var n="",r=t;do n+=function(e){switch(e.tag){case 5:return ee(e.type);case 16:return ee("Lazy");case 13:return ee("Suspense");case 19:return ee("SuspenseList");case 0:case 2:case 15:return e=en(e.type,!1);case 11:return e=en(e.type.render,!1);case 1:return e=en(e.type,!0);default:return"";}}(r),r=r.return;while(r)var a=n;}catch(e){a="\nError generating stack: "+e.message+"\n"+e.stack;}return{value:e,source:t,stack:a,digest:null};}function i7(e,t,n){return{value:e,source:null,stack:null!=n?n:null,digest:null!=t?t:null};}function oe(e,t){try{console.error(t.value);}catch(e){setTimeout(function(){throw e;});}}var ot="function"==typeof WeakMap?WeakMap:Map;function on(e,t,n){(n=aB(-1,n)).tag=3,n.payload={element:null};var r=t.value;return n.callback=function(){lf||(lf=!0,ld=r),oe(e,t);},n;}function or(e,t,n){(n=aB(-1,n)).tag=3;var r=e.type.getDerivedStateFromError;if("function"==typeof r){var a=t.value;n.payload=function(){return r(a);},n.callback=function(){oe(e,t);};}var i=e.stateNode;return null!==i&&"function"==typeof i.componentDidCatch&&(n.callback=function(){oe(e,t),"function"!=typeof r&&(null===lh?lh=new Set([this]):lh.add(this));var n=t.stack;this.componentDidCatch(t.value,{componentStack:null!==n?n:""});}),n;}function oa(e,t,n){var r=e.pingCache;if(null===r){r=e.pingCache=new ot;var a=new Set;r.set(t,a);}else void 0===(a=r.get(t))&&(a=new Set,r.set(t,a));a.has(n)||(a.add(n),e=lU.bind(null,e,t,n),t.then(e,e));}function oi(e){do{var t;if((t=13===e.tag)&&(t=null===(t=e.memoizedState)||null!==t.dehydrated),t)return e;e=e.return;}while(null!==e)return null;}function oo(e,t,n,r,a){return 0==(1&e.mode)?e===t?e.flags|=65536:(e.flags|=128,n.flags|=131072,n.flags&=-52805,1===n.tag&&(null===n.alternate?n.tag=17:((t=aB(-1,1)).tag=2,aG(n,t,1))),n.lanes|=1):(e.flags|=65536,e.lanes=a),e;}var ol=R.ReactCurrentOwner,ou=!1;function os(e,t,n,r){t.child=null===e?a5(t,null,n,r):a3(t,e.child,n,r);}function oc(e,t,n,r,a){n=n.render;var i=t.ref;return(aL(t,a),r=iS(e,t,n,r,i,a),n=ik(),null===e||ou)?(af&&n&&al(t),t.flags|=1,os(e,t,r,a),t.child):(t.updateQueue=e.updateQueue,t.flags&=-2053,e.lanes&=~a,oE(e,t,a));}function of(e,t,n,r,a){if(null===e){var i=n.type;return"function"!=typeof i||lY(i)||void 0!==i.defaultProps||null!==n.compare||void 0!==n.defaultProps?((e=lJ(n.type,null,r,t,t.mode,a)).ref=t.ref,e.return=t,t.child=e):(t.tag=15,t.type=i,od(e,t,i,r,a));}if(i=e.child,0==(e.lanes&a)){var o=i.memoizedProps;if((n=null!==(n=n.compare)?n:nR)(o,r)&&e.ref===t.ref)return oE(e,t,a);}return t.flags|=1,(e=lX(i,r)).ref=t.ref,e.return=t,t.child=e;}function od(e,t,n,r,a){if(null!==e){var i=e.memoizedProps;if(nR(i,r)&&e.ref===t.ref){if(ou=!1,t.pendingProps=r=i,0==(e.lanes&a))return t.lanes=e.lanes,oE(e,t,a);0!=(131072&e.flags)&&(ou=!0);}}return og(e,t,n,r,a);}function oh(e,t,n){var r=t.pendingProps,a=r.children,i=null!==e?e.memoizedState:null;if("hidden"===r.mode){if(0==(1&t.mode))t.memoizedState={baseLanes:0,cachePool:null,transitions:null},rq(le,o7),o7|=n;else{if(0==(1073741824&n))return e=null!==i?i.baseLanes|n:n,t.lanes=t.childLanes=1073741824,t.memoizedState={baseLanes:e,cachePool:null,transitions:null},t.updateQueue=null,rq(le,o7),o7|=e,null;t.memoizedState={baseLanes:0,cachePool:null,transitions:null},r=null!==i?i.baseLanes:n,rq(le,o7),o7|=r;}}else null!==i?(r=i.baseLanes|n,t.memoizedState=null):r=n,rq(le,o7),o7|=r;return os(e,t,a,n),t.child;}function op(e,t){var n=t.ref;(null===e&&null!==n||null!==e&&e.ref!==n)&&(t.flags|=512,t.flags|=2097152);}function og(e,t,n,r,a){var i=rQ(n)?r$:rW.current;return(i=rK(t,i),aL(t,a),n=iS(e,t,n,r,i,a),r=ik(),null===e||ou)?(af&&r&&al(t),t.flags|=1,os(e,t,n,a),t.child):(t.updateQueue=e.updateQueue,t.flags&=-2053,e.lanes&=~a,oE(e,t,a));}function ov(e,t,n,r,a){if(rQ(n)){var i=!0;rZ(t);}else i=!1;if(aL(t,a),null===t.stateNode)oP(e,t),aY(t,n,r),aJ(t,n,r,a),r=!0;else if(null===e){var o=t.stateNode,l=t.memoizedProps;o.props=l;var u=o.context,s=n.contextType;s="object"==typeof s&&null!==s?aD(s):rK(t,s=rQ(n)?r$:rW.current);var c=n.getDerivedStateFromProps,f="function"==typeof c||"function"==typeof o.getSnapshotBeforeUpdate;f||"function"!=typeof o.UNSAFE_componentWillReceiveProps&&"function"!=typeof o.componentWillReceiveProps||(l!==r||u!==s)&&aX(t,o,r,s),aR=!1;var d=t.memoizedState;o.state=d,aU(t,r,o,a),u=t.memoizedState,l!==r||d!==u||rV.current||aR?("function"==typeof c&&(a$(t,n,c,r),u=t.memoizedState),(l=aR||aQ(t,n,l,r,d,u,s))?(f||"function"!=typeof o.UNSAFE_componentWillMount&&"function"!=typeof o.componentWillMount||("function"==typeof o.componentWillMount&&o.componentWillMount(),"function"==typeof o.UNSAFE_componentWillMount&&o.UNSAFE_componentWillMount()),"function"==typeof o.componentDidMount&&(t.flags|=4194308)):("function"==typeof o.componentDidMount&&(t.flags|=4194308),t.memoizedProps=r,t.memoizedState=u),o.props=r,o.state=u,o.context=s,r=l):("function"==typeof o.componentDidMount&&(t.flags|=4194308),r=!1);}else{o=t.stateNode,aI(e,t),l=t.memoizedProps,s=t.type===t.elementType?l:ak(t.type,l),o.props=s,f=t.pendingProps,d=o.context,u="object"==typeof(u=n.contextType)&&null!==u?aD(u):rK(t,u=rQ(n)?r$:rW.current);var h=n.getDerivedStateFromProps;(c="function"==typeof h||"function"==typeof o.getSnapshotBeforeUpdate)||"function"!=typeof o.UNSAFE_componentWillReceiveProps&&"function"!=typeof o.componentWillReceiveProps||(l!==f||d!==u)&&aX(t,o,r,u),aR=!1,d=t.memoizedState,o.state=d,aU(t,r,o,a);var p=t.memoizedState;l!==f||d!==p||rV.current||aR?("function"==typeof h&&(a$(t,n,h,r),p=t.memoizedState),(s=aR||aQ(t,n,s,r,d,p,u)||!1)?(c||"function"!=typeof o.UNSAFE_componentWillUpdate&&"function"!=typeof o.componentWillUpdate||("function"==typeof o.componentWillUpdate&&o.componentWillUpdate(r,p,u),"function"==typeof o.UNSAFE_componentWillUpdate&&o.UNSAFE_componentWillUpdate(r,p,u)),"function"==typeof o.componentDidUpdate&&(t.flags|=4),"function"==typeof o.getSnapshotBeforeUpdate&&(t.flags|=1024)):("function"!=typeof o.componentDidUpdate||l===e.memoizedProps&&d===e.memoizedState||(t.flags|=4),"function"!=typeof o.getSnapshotBeforeUpdate||l===e.memoizedProps&&d===e.memoizedState||(t.flags|=1024),t.memoizedProps=r,t.memoizedState=p),o.props=r,o.state=p,o.context=u,r=s):("function"!=typeof o.componentDidUpdate||l===e.memoizedProps&&d===e.memoizedState||(t.flags|=4),"function"!=typeof o.getSnapshotBeforeUpdate||l===e.memoizedProps&&d===e.memoizedState||(t.flags|=1024),r=!1);}return ob(e,t,n,r,i,a);}function ob(e,t,n,r,a,i){op(e,t);var o=0!=(128&t.flags);if(!r&&!o)return a&&r0(t,n,!1),oE(e,t,i);r=t.stateNode,ol.current=t;var l=o&&"function"!=typeof n.getDerivedStateFromError?null:r.render();return t.flags|=1,null!==e&&o?(t.child=a3(t,e.child,null,i),t.child=a3(t,null,l,i)):os(e,t,l,i),t.memoizedState=r.state,a&&r0(t,n,!0),t.child;}function om(e){var t=e.stateNode;t.pendingContext?rX(e,t.pendingContext,t.pendingContext!==t.context):t.context&&rX(e,t.context,!1),ie(e,t.containerInfo);}function oy(e,t,n,r,a){return aw(),ax(a),t.flags|=256,os(e,t,n,r),t.child;}var ow={dehydrated:null,treeContext:null,retryLane:0};function ox(e){return{baseLanes:e,cachePool:null,transitions:null};}function oS(e,t,n){var r,a=t.pendingProps,i=ii.current,o=!1,l=0!=(128&t.flags);if((r=l)||(r=(null===e||null!==e.memoizedState)&&0!=(2&i)),r?(o=!0,t.flags&=-129):(null===e||null!==e.memoizedState)&&(i|=1),rq(ii,1&i),null===e)return(av(t),null!==(e=t.memoizedState)&&null!==(e=e.dehydrated))?(0==(1&t.mode)?t.lanes=1:"$!"===e.data?t.lanes=8:t.lanes=1073741824,null):(l=a.children,e=a.fallback,o?(a=t.mode,o=t.child,l={mode:"hidden",children:l},0==(1&a)&&null!==o?(o.childLanes=0,o.pendingProps=l):o=l0(l,a,0,null),e=lZ(e,a,n,null),o.return=t,e.return=t,o.sibling=e,t.child=o,t.child.memoizedState=ox(n),t.memoizedState=ow,e):ok(t,l));if(null!==(i=e.memoizedState)&&null!==(r=i.dehydrated))return function(e,t,n,r,a,i,o){if(n)return 256&t.flags?(t.flags&=-257,oC(e,t,o,r=i7(Error(S(422))))):null!==t.memoizedState?(t.child=e.child,t.flags|=128,null):(i=r.fallback,a=t.mode,r=l0({mode:"visible",children:r.children},a,0,null),i=lZ(i,a,o,null),i.flags|=2,r.return=t,i.return=t,r.sibling=i,t.child=r,0!=(1&t.mode)&&a3(t,e.child,null,o),t.child.memoizedState=ox(o),t.memoizedState=ow,i);if(0==(1&t.mode))return oC(e,t,o,null);if("$!"===a.data){if(r=a.nextSibling&&a.nextSibling.dataset)var l=r.dgst;return r=l,oC(e,t,o,r=i7(i=Error(S(419)),r,void 0));}if(l=0!=(o&e.childLanes),ou||l){if(null!==(r=o6)){switch(o&-o){case 4:a=2;break;case 16:a=8;break;case 64:case 128:case 256:case 512:case 1024:case 2048:case 4096:case 8192:case 16384:case 32768:case 65536:case 131072:case 262144:case 524288:case 1048576:case 2097152:case 4194304:case 8388608:case 16777216:case 33554432:case 67108864:a=32;break;case 536870912:a=268435456;break;default:a=0;}0!==(a=0!=(a&(r.suspendedLanes|o))?0:a)&&a!==i.retryLane&&(i.retryLane=a,aO(e,a),lk(r,e,a,-1));}return lO(),oC(e,t,o,r=i7(Error(S(421))));}return"$?"===a.data?(t.flags|=128,t.child=e.child,t=lV.bind(null,e),a._reactRetry=t,null):(e=i.treeContext,ac=rz(a.nextSibling),as=t,af=!0,ad=null,null!==e&&(ae[at++]=ar,ae[at++]=aa,ae[at++]=an,ar=e.id,aa=e.overflow,an=t),t=ok(t,r.children),t.flags|=4096,t);}(e,t,l,a,r,i,n);if(o){o=a.fallback,l=t.mode,r=(i=e.child).sibling;var u={mode:"hidden",children:a.children};return 0==(1&l)&&t.child!==i?((a=t.child).childLanes=0,a.pendingProps=u,t.deletions=null):(a=lX(i,u)).subtreeFlags=14680064&i.subtreeFlags,null!==r?o=lX(r,o):(o=lZ(o,l,n,null),o.flags|=2),o.return=t,a.return=t,a.sibling=o,t.child=a,a=o,o=t.child,l=null===(l=e.child.memoizedState)?ox(n):{baseLanes:l.baseLanes|n,cachePool:null,transitions:l.transitions},o.memoizedState=l,o.childLanes=e.childLanes&~n,t.memoizedState=ow,a;}return e=(o=e.child).sibling,a=lX(o,{mode:"visible",children:a.children}),0==(1&t.mode)&&(a.lanes=n),a.return=t,a.sibling=null,null!==e&&(null===(n=t.deletions)?(t.deletions=[e],t.flags|=16):n.push(e)),t.child=a,t.memoizedState=null,a;}function ok(e,t){return(t=l0({mode:"visible",children:t},e.mode,0,null)).return=e,e.child=t;}function oC(e,t,n,r){return null!==r&&ax(r),a3(t,e.child,null,n),e=ok(t,t.pendingProps.children),e.flags|=2,t.memoizedState=null,e;}function oT(e,t,n){e.lanes|=t;var r=e.alternate;null!==r&&(r.lanes|=t),a_(e.return,t,n);}function oz(e,t,n,r,a){var i=e.memoizedState;null===i?e.memoizedState={isBackwards:t,rendering:null,renderingStartTime:0,last:r,tail:n,tailMode:a}:(i.isBackwards=t,i.rendering=null,i.renderingStartTime=0,i.last=r,i.tail=n,i.tailMode=a);}function oM(e,t,n){var r=t.pendingProps,a=r.revealOrder,i=r.tail;if(os(e,t,r.children,n),0!=(2&(r=ii.current)))r=1&r|2,t.flags|=128;else{if(null!==e&&0!=(128&e.flags))e:for(e=t.child;null!==e;){if(13===e.tag)null!==e.memoizedState&&oT(e,n,t);else if(19===e.tag)oT(e,n,t);else if(null!==e.child){e.child.return=e,e=e.child;continue;}if(e===t)break;for(;null===e.sibling;){if(null===e.return||e.return===t)break e;e=e.return;}e.sibling.return=e.return,e=e.sibling;}r&=1;}if(rq(ii,r),0==(1&t.mode))t.memoizedState=null;else switch(a){case"forwards":for(a=null,n=t.child;null!==n;)null!==(e=n.alternate)&&null===io(e)&&(a=n),n=n.sibling;null===(n=a)?(a=t.child,t.child=null):(a=n.sibling,n.sibling=null),oz(t,!1,a,n,i);break;case"backwards":for(n=null,a=t.child,t.child=null;null!==a;){if(null!==(e=a.alternate)&&null===io(e)){t.child=a;break;}e=a.sibling,a.sibling=n,n=a,a=e;}oz(t,!0,n,null,i);break;case"together":oz(t,!1,null,null,void 0);break;default:t.memoizedState=null;}return t.child;}function oP(e,t){0==(1&t.mode)&&null!==e&&(e.alternate=null,t.alternate=null,t.flags|=2);}function oE(e,t,n){if(null!==e&&(t.dependencies=e.dependencies),lr|=t.lanes,0==(n&t.childLanes))return null;if(null!==e&&t.child!==e.child)throw Error(S(153));if(null!==t.child){for(n=lX(e=t.child,e.pendingProps),t.child=n,n.return=t;null!==e.sibling;)e=e.sibling,(n=n.sibling=lX(e,e.pendingProps)).return=t;n.sibling=null;}return t.child;}function o_(e,t){if(!af)switch(e.tailMode){case"hidden":t=e.tail;for(var n=null;null!==t;)null!==t.alternate&&(n=t),t=t.sibling;null===n?e.tail=null:n.sibling=null;break;case"collapsed":n=e.tail;for(var r=null;null!==n;)null!==n.alternate&&(r=n),n=n.sibling;null===r?t||null===e.tail?e.tail=null:e.tail.sibling=null:r.sibling=null;}}function oL(e){var t=null!==e.alternate&&e.alternate.child===e.child,n=0,r=0;if(t)for(var a=e.child;null!==a;)n|=a.lanes|a.childLanes,r|=14680064&a.subtreeFlags,r|=14680064&a.flags,a.return=e,a=a.sibling;else for(a=e.child;null!==a;)n|=a.lanes|a.childLanes,r|=a.subtreeFlags,r|=a.flags,a.return=e,a=a.sibling;return e.subtreeFlags|=r,e.childLanes=n,t;}g=function(e,t){for(var n=t.child;null!==n;){if(5===n.tag||6===n.tag)e.appendChild(n.stateNode);else if(4!==n.tag&&null!==n.child){n.child.return=n,n=n.child;continue;}if(n===t)break;for(;null===n.sibling;){if(null===n.return||n.return===t)return;n=n.return;}n.sibling.return=n.return,n=n.sibling;}},v=function(){},b=function(e,t,n,r){var a=e.memoizedProps;if(a!==r){e=t.stateNode,a7(a6.current);var i,o=null;switch(n){case"input":a=eu(e,a),r=eu(e,r),o=[];break;case"select":a=Z({},a,{value:void 0}),r=Z({},r,{value:void 0}),o=[];break;case"textarea":a=ev(e,a),r=ev(e,r),o=[];break;default:"function"!=typeof a.onClick&&"function"==typeof r.onClick&&(e.onclick=rv);}for(s in eE(n,r),n=null,a)if(!r.hasOwnProperty(s)&&a.hasOwnProperty(s)&&null!=a[s]){if("style"===s){var l=a[s];for(i in l)l.hasOwnProperty(i)&&(n||(n={}),n[i]="");}else"dangerouslySetInnerHTML"!==s&&"children"!==s&&"suppressContentEditableWarning"!==s&&"suppressHydrationWarning"!==s&&"autoFocus"!==s&&(C.hasOwnProperty(s)?o||(o=[]):(o=o||[]).push(s,null));}for(s in r){var u=r[s];if(l=null!=a?a[s]:void 0,r.hasOwnProperty(s)&&u!==l&&(null!=u||null!=l)){if("style"===s){if(l){for(i in l)!l.hasOwnProperty(i)||u&&u.hasOwnProperty(i)||(n||(n={}),n[i]="");for(i in u)u.hasOwnProperty(i)&&l[i]!==u[i]&&(n||(n={}),n[i]=u[i]);}else n||(o||(o=[]),o.push(s,n)),n=u;}else"dangerouslySetInnerHTML"===s?(u=u?u.__html:void 0,l=l?l.__html:void 0,null!=u&&l!==u&&(o=o||[]).push(s,u)):"children"===s?"string"!=typeof u&&"number"!=typeof u||(o=o||[]).push(s,""+u):"suppressContentEditableWarning"!==s&&"suppressHydrationWarning"!==s&&(C.hasOwnProperty(s)?(null!=u&&"onScroll"===s&&rn("scroll",e),o||l===u||(o=[])):(o=o||[]).push(s,u));}}n&&(o=o||[]).push("style",n);var s=o;(t.updateQueue=s)&&(t.flags|=4);}},m=function(e,t,n,r){n!==r&&(t.flags|=4);};var oD=!1,oF=!1,oj="function"==typeof WeakSet?WeakSet:Set,oN=null;function oO(e,t){var n=e.ref;if(null!==n){if("function"==typeof n)try{n(null);}catch(n){lq(e,t,n);}else n.current=null;}}function oR(e,t,n){try{n();}catch(n){lq(e,t,n);}}var oA=!1;function oI(e,t,n){var r=t.updateQueue;if(null!==(r=null!==r?r.lastEffect:null)){var a=r=r.next;do{if((a.tag&e)===e){var i=a.destroy;a.destroy=void 0,void 0!==i&&oR(t,n,i);}a=a.next;}while(a!==r)}}function oB(e,t){if(null!==(t=null!==(t=t.updateQueue)?t.lastEffect:null)){var n=t=t.next;do{if((n.tag&e)===e){var r=n.create;n.destroy=r();}n=n.next;}while(n!==t)}}function oG(e){var t=e.ref;if(null!==t){var n=e.stateNode;e.tag,e=n,"function"==typeof t?t(e):t.current=e;}}function oH(e){return 5===e.tag||3===e.tag||4===e.tag;}function oq(e){e:for(;;){for(;null===e.sibling;){if(null===e.return||oH(e.return))return null;e=e.return;}for(e.sibling.return=e.return,e=e.sibling;5!==e.tag&&6!==e.tag&&18!==e.tag;){if(2&e.flags||null===e.child||4===e.tag)continue e;e.child.return=e,e=e.child;}if(!(2&e.flags))return e.stateNode;}}var oU=null,oW=!1;function oV(e,t,n){for(n=n.child;null!==n;)o$(e,t,n),n=n.sibling;}function o$(e,t,n){if(ti&&"function"==typeof ti.onCommitFiberUnmount)try{ti.onCommitFiberUnmount(ta,n);}catch(e){}switch(n.tag){case 5:oF||oO(n,t);case 6:var r=oU,a=oW;oU=null,oV(e,t,n),oU=r,oW=a,null!==oU&&(oW?(e=oU,n=n.stateNode,8===e.nodeType?e.parentNode.removeChild(n):e.removeChild(n)):oU.removeChild(n.stateNode));break;case 18:null!==oU&&(oW?(e=oU,n=n.stateNode,8===e.nodeType?rT(e.parentNode,n):1===e.nodeType&&rT(e,n),tO(e)):rT(oU,n.stateNode));break;case 4:r=oU,a=oW,oU=n.stateNode.containerInfo,oW=!0,oV(e,t,n),oU=r,oW=a;break;case 0:case 11:case 14:case 15:if(!oF&&null!==(r=n.updateQueue)&&null!==(r=r.lastEffect)){a=r=r.next;do{var i=a,o=i.destroy;i=i.tag,void 0!==o&&(0!=(2&i)?oR(n,t,o):0!=(4&i)&&oR(n,t,o)),a=a.next;}while(a!==r)}oV(e,t,n);break;case 1:if(!oF&&(oO(n,t),"function"==typeof(r=n.stateNode).componentWillUnmount))try{r.props=n.memoizedProps,r.state=n.memoizedState,r.componentWillUnmount();}catch(e){lq(n,t,e);}oV(e,t,n);break;case 21:default:oV(e,t,n);break;case 22:1&n.mode?(oF=(r=oF)||null!==n.memoizedState,oV(e,t,n),oF=r):oV(e,t,n);}}function oK(e){var t=e.updateQueue;if(null!==t){e.updateQueue=null;var n=e.stateNode;null===n&&(n=e.stateNode=new oj),t.forEach(function(t){var r=l$.bind(null,e,t);n.has(t)||(n.add(t),t.then(r,r));});}}function oQ(e,t){var n=t.deletions;if(null!==n)for(var r=0;r<n.length;r++){var a=n[r];try{var i=t,o=i;e:for(;null!==o;){switch(o.tag){case 5:oU=o.stateNode,oW=!1;break e;case 3:case 4:oU=o.stateNode.containerInfo,oW=!0;break e;}o=o.return;}if(null===oU)throw Error(S(160));o$(e,i,a),oU=null,oW=!1;var l=a.alternate;null!==l&&(l.return=null),a.return=null;}catch(e){lq(a,t,e);}}if(12854&t.subtreeFlags)for(t=t.child;null!==t;)oY(t,e),t=t.sibling;}function oY(e,t){var n=e.alternate,r=e.flags;switch(e.tag){case 0:case 11:case 14:case 15:if(oQ(t,e),oX(e),4&r){try{oI(3,e,e.return),oB(3,e);}catch(t){lq(e,e.return,t);}try{oI(5,e,e.return);}catch(t){lq(e,e.return,t);}}break;case 1:oQ(t,e),oX(e),512&r&&null!==n&&oO(n,n.return);break;case 5:if(oQ(t,e),oX(e),512&r&&null!==n&&oO(n,n.return),32&e.flags){var a=e.stateNode;try{ek(a,"");}catch(t){lq(e,e.return,t);}}if(4&r&&null!=(a=e.stateNode)){var i=e.memoizedProps,o=null!==n?n.memoizedProps:i,l=e.type,u=e.updateQueue;if(e.updateQueue=null,null!==u)try{"input"===l&&"radio"===i.type&&null!=i.name&&ec(a,i),e_(l,o);var s=e_(l,i);for(o=0;o<u.length;o+=2){var c=u[o],f=u[o+1];"style"===c?eM(a,f):"dangerouslySetInnerHTML"===c?eS(a,f):"children"===c?ek(a,f):O(a,c,f,s);}switch(l){case"input":ef(a,i);break;case"textarea":em(a,i);break;case"select":var d=a._wrapperState.wasMultiple;a._wrapperState.wasMultiple=!!i.multiple;var h=i.value;null!=h?eg(a,!!i.multiple,h,!1):!!i.multiple!==d&&(null!=i.defaultValue?eg(a,!!i.multiple,i.defaultValue,!0):eg(a,!!i.multiple,i.multiple?[]:"",!1));}a[r_]=i;}catch(t){lq(e,e.return,t);}}break;case 6:if(oQ(t,e),oX(e),4&r){if(null===e.stateNode)throw Error(S(162));a=e.stateNode,i=e.memoizedProps;try{a.nodeValue=i;}catch(t){lq(e,e.return,t);}}break;case 3:if(oQ(t,e),oX(e),4&r&&null!==n&&n.memoizedState.isDehydrated)try{tO(t.containerInfo);}catch(t){lq(e,e.return,t);}break;case 4:default:oQ(t,e),oX(e);break;case 13:oQ(t,e),oX(e),8192&(a=e.child).flags&&(i=null!==a.memoizedState,a.stateNode.isHidden=i,i&&(null===a.alternate||null===a.alternate.memoizedState)&&(lu=e8())),4&r&&oK(e);break;case 22:if(c=null!==n&&null!==n.memoizedState,1&e.mode?(oF=(s=oF)||c,oQ(t,e),oF=s):oQ(t,e),oX(e),8192&r){if(s=null!==e.memoizedState,(e.stateNode.isHidden=s)&&!c&&0!=(1&e.mode))for(oN=e,c=e.child;null!==c;){for(f=oN=c;null!==oN;){switch(h=(d=oN).child,d.tag){case 0:case 11:case 14:case 15:oI(4,d,d.return);break;case 1:oO(d,d.return);var p=d.stateNode;if("function"==typeof p.componentWillUnmount){r=d,n=d.return;try{t=r,p.props=t.memoizedProps,p.state=t.memoizedState,p.componentWillUnmount();}catch(e){lq(r,n,e);}}break;case 5:oO(d,d.return);break;case 22:if(null!==d.memoizedState){oZ(f);continue;}}null!==h?(h.return=d,oN=h):oZ(f);}c=c.sibling;}e:for(c=null,f=e;;){if(5===f.tag){if(null===c){c=f;try{a=f.stateNode,s?(i=a.style,"function"==typeof i.setProperty?i.setProperty("display","none","important"):i.display="none"):(l=f.stateNode,o=null!=(u=f.memoizedProps.style)&&u.hasOwnProperty("display")?u.display:null,l.style.display=ez("display",o));}catch(t){lq(e,e.return,t);}}}else if(6===f.tag){if(null===c)try{f.stateNode.nodeValue=s?"":f.memoizedProps;}catch(t){lq(e,e.return,t);}}else if((22!==f.tag&&23!==f.tag||null===f.memoizedState||f===e)&&null!==f.child){f.child.return=f,f=f.child;continue;}if(f===e)break;for(;null===f.sibling;){if(null===f.return||f.return===e)break e;c===f&&(c=null),f=f.return;}c===f&&(c=null),f.sibling.return=f.return,f=f.sibling;}}break;case 19:oQ(t,e),oX(e),4&r&&oK(e);case 21:}}function oX(e){var t=e.flags;if(2&t){try{e:{for(var n=e.return;null!==n;){if(oH(n)){var r=n;break e;}n=n.return;}throw Error(S(160));}switch(r.tag){case 5:var a=r.stateNode;32&r.flags&&(ek(a,""),r.flags&=-33);var i=oq(e);!function e(t,n,r){var a=t.tag;if(5===a||6===a)t=t.stateNode,n?r.insertBefore(t,n):r.appendChild(t);else if(4!==a&&null!==(t=t.child))for(e(t,n,r),t=t.sibling;null!==t;)e(t,n,r),t=t.sibling;}(e,i,a);break;case 3:case 4:var o=r.stateNode.containerInfo,l=oq(e);!function e(t,n,r){var a=t.tag;if(5===a||6===a)t=t.stateNode,n?8===r.nodeType?r.parentNode.insertBefore(t,n):r.insertBefore(t,n):(8===r.nodeType?(n=r.parentNode).insertBefore(t,r):(n=r).appendChild(t),null!=(r=r._reactRootContainer)||null!==n.onclick||(n.onclick=rv));else if(4!==a&&null!==(t=t.child))for(e(t,n,r),t=t.sibling;null!==t;)e(t,n,r),t=t.sibling;}(e,l,o);break;default:throw Error(S(161));}}catch(t){lq(e,e.return,t);}e.flags&=-3;}4096&t&&(e.flags&=-4097);}function oJ(e){for(;null!==oN;){var t=oN;if(0!=(8772&t.flags)){var n=t.alternate;try{if(0!=(8772&t.flags))switch(t.tag){case 0:case 11:case 15:oF||oB(5,t);break;case 1:var r=t.stateNode;if(4&t.flags&&!oF){if(null===n)r.componentDidMount();else{var a=t.elementType===t.type?n.memoizedProps:ak(t.type,n.memoizedProps);r.componentDidUpdate(a,n.memoizedState,r.__reactInternalSnapshotBeforeUpdate);}}var i=t.updateQueue;null!==i&&aW(t,i,r);break;case 3:var o=t.updateQueue;if(null!==o){if(n=null,null!==t.child)switch(t.child.tag){case 5:case 1:n=t.child.stateNode;}aW(t,o,n);}break;case 5:var l=t.stateNode;if(null===n&&4&t.flags){n=l;var u=t.memoizedProps;switch(t.type){case"button":case"input":case"select":case"textarea":u.autoFocus&&n.focus();break;case"img":u.src&&(n.src=u.src);}}break;case 6:case 4:case 12:case 19:case 17:case 21:case 22:case 23:case 25:break;case 13:if(null===t.memoizedState){var s=t.alternate;if(null!==s){var c=s.memoizedState;if(null!==c){var f=c.dehydrated;null!==f&&tO(f);}}}break;default:throw Error(S(163));}oF||512&t.flags&&oG(t);}catch(e){lq(t,t.return,e);}}if(t===e){oN=null;break;}if(null!==(n=t.sibling)){n.return=t.return,oN=n;break;}oN=t.return;}}function oZ(e){for(;null!==oN;){var t=oN;if(t===e){oN=null;break;}var n=t.sibling;if(null!==n){n.return=t.return,oN=n;break;}oN=t.return;}}function o0(e){for(;null!==oN;){var t=oN;try{switch(t.tag){case 0:case 11:case 15:var n=t.return;try{oB(4,t);}catch(e){lq(t,n,e);}break;case 1:var r=t.stateNode;if("function"==typeof r.componentDidMount){var a=t.return;try{r.componentDidMount();}catch(e){lq(t,a,e);}}var i=t.return;try{oG(t);}catch(e){lq(t,i,e);}break;case 5:var o=t.return;try{oG(t);}catch(e){lq(t,o,e);}}}catch(e){lq(t,t.return,e);}if(t===e){oN=null;break;}var l=t.sibling;if(null!==l){l.return=t.return,oN=l;break;}oN=t.return;}}var o1=Math.ceil,o2=R.ReactCurrentDispatcher,o3=R.ReactCurrentOwner,o5=R.ReactCurrentBatchConfig,o4=0,o6=null,o8=null,o9=0,o7=0,le=rG(0),lt=0,ln=null,lr=0,la=0,li=0,lo=null,ll=null,lu=0,ls=1/0,lc=null,lf=!1,ld=null,lh=null,lp=!1,lg=null,lv=0,lb=0,lm=null,ly=-1,lw=0;function lx(){return 0!=(6&o4)?e8():-1!==ly?ly:ly=e8();}function lS(e){return 0==(1&e.mode)?1:0!=(2&o4)&&0!==o9?o9&-o9:null!==aS.transition?(0===lw&&(lw=tp()),lw):0!==(e=tm)?e:e=void 0===(e=window.event)?16:tU(e.type);}function lk(e,t,n,r){if(50<lb)throw lb=0,lm=null,Error(S(185));tv(e,n,r),(0==(2&o4)||e!==o6)&&(e===o6&&(0==(2&o4)&&(la|=n),4===lt&&lP(e,o9)),lC(e,r),1===n&&0===o4&&0==(1&t.mode)&&(ls=e8()+500,r2&&r4()));}function lC(e,t){var n,r=e.callbackNode;!function(e,t){for(var n=e.suspendedLanes,r=e.pingedLanes,a=e.expirationTimes,i=e.pendingLanes;0<i;){var o=31-to(i),l=1<<o,u=a[o];-1===u?(0==(l&n)||0!=(l&r))&&(a[o]=function(e,t){switch(e){case 1:case 2:case 4:return t+250;case 8:case 16:case 32:case 64:case 128:case 256:case 512:case 1024:case 2048:case 4096:case 8192:case 16384:case 32768:case 65536:case 131072:case 262144:case 524288:case 1048576:case 2097152:return t+5e3;default:return -1;}}(l,t)):u<=t&&(e.expiredLanes|=l),i&=~l;}}(e,t);var a=td(e,e===o6?o9:0);if(0===a)null!==r&&e5(r),e.callbackNode=null,e.callbackPriority=0;else if(t=a&-a,e.callbackPriority!==t){if(null!=r&&e5(r),1===t)0===e.tag?(n=lE.bind(null,e),r2=!0,r5(n)):r5(lE.bind(null,e)),rk(function(){0==(6&o4)&&r4();}),r=null;else{switch(ty(a)){case 1:r=e7;break;case 4:r=te;break;case 16:default:r=tt;break;case 536870912:r=tr;}r=e3(r,lT.bind(null,e));}e.callbackPriority=t,e.callbackNode=r;}}function lT(e,t){if(ly=-1,lw=0,0!=(6&o4))throw Error(S(327));var n=e.callbackNode;if(lG()&&e.callbackNode!==n)return null;var r=td(e,e===o6?o9:0);if(0===r)return null;if(0!=(30&r)||0!=(r&e.expiredLanes)||t)t=lR(e,r);else{t=r;var a=o4;o4|=2;var i=lN();for((o6!==e||o9!==t)&&(lc=null,ls=e8()+500,lF(e,t));;)try{!function(){for(;null!==o8&&!e4();)lA(o8);}();break;}catch(t){lj(e,t);}aP(),o2.current=i,o4=a,null!==o8?t=0:(o6=null,o9=0,t=lt);}if(0!==t){if(2===t&&0!==(a=th(e))&&(r=a,t=lz(e,a)),1===t)throw n=ln,lF(e,0),lP(e,r),lC(e,e8()),n;if(6===t)lP(e,r);else{if(a=e.current.alternate,0==(30&r)&&!function(e){for(var t=e;;){if(16384&t.flags){var n=t.updateQueue;if(null!==n&&null!==(n=n.stores))for(var r=0;r<n.length;r++){var a=n[r],i=a.getSnapshot;a=a.value;try{if(!nO(i(),a))return!1;}catch(e){return!1;}}}if(n=t.child,16384&t.subtreeFlags&&null!==n)n.return=t,t=n;else{if(t===e)break;for(;null===t.sibling;){if(null===t.return||t.return===e)return!0;t=t.return;}t.sibling.return=t.return,t=t.sibling;}}return!0;}(a)&&(2===(t=lR(e,r))&&0!==(i=th(e))&&(r=i,t=lz(e,i)),1===t))throw n=ln,lF(e,0),lP(e,r),lC(e,e8()),n;switch(e.finishedWork=a,e.finishedLanes=r,t){case 0:case 1:throw Error(S(345));case 2:case 5:lB(e,ll,lc);break;case 3:if(lP(e,r),(130023424&r)===r&&10<(t=lu+500-e8())){if(0!==td(e,0))break;if(((a=e.suspendedLanes)&r)!==r){lx(),e.pingedLanes|=e.suspendedLanes&a;break;}e.timeoutHandle=rw(lB.bind(null,e,ll,lc),t);break;}lB(e,ll,lc);break;case 4:if(lP(e,r),(4194240&r)===r)break;for(a=-1,t=e.eventTimes;0<r;){var o=31-to(r);i=1<<o,(o=t[o])>a&&(a=o),r&=~i;}if(r=a,10<(r=(120>(r=e8()-r)?120:480>r?480:1080>r?1080:1920>r?1920:3e3>r?3e3:4320>r?4320:1960*o1(r/1960))-r)){e.timeoutHandle=rw(lB.bind(null,e,ll,lc),r);break;}lB(e,ll,lc);break;default:throw Error(S(329));}}}return lC(e,e8()),e.callbackNode===n?lT.bind(null,e):null;}function lz(e,t){var n=lo;return e.current.memoizedState.isDehydrated&&(lF(e,t).flags|=256),2!==(e=lR(e,t))&&(t=ll,ll=n,null!==t&&lM(t)),e;}function lM(e){null===ll?ll=e:ll.push.apply(ll,e);}function lP(e,t){for(t&=~li,t&=~la,e.suspendedLanes|=t,e.pingedLanes&=~t,e=e.expirationTimes;0<t;){var n=31-to(t),r=1<<n;e[n]=-1,t&=~r;}}function lE(e){if(0!=(6&o4))throw Error(S(327));lG();var t=td(e,0);if(0==(1&t))return lC(e,e8()),null;var n=lR(e,t);if(0!==e.tag&&2===n){var r=th(e);0!==r&&(t=r,n=lz(e,r));}if(1===n)throw n=ln,lF(e,0),lP(e,t),lC(e,e8()),n;if(6===n)throw Error(S(345));return e.finishedWork=e.current.alternate,e.finishedLanes=t,lB(e,ll,lc),lC(e,e8()),null;}function l_(e,t){var n=o4;o4|=1;try{return e(t);}finally{0===(o4=n)&&(ls=e8()+500,r2&&r4());}}function lL(e){null!==lg&&0===lg.tag&&0==(6&o4)&&lG();var t=o4;o4|=1;var n=o5.transition,r=tm;try{if(o5.transition=null,tm=1,e)return e();}finally{tm=r,o5.transition=n,0==(6&(o4=t))&&r4();}}function lD(){o7=le.current,rH(le);}function lF(e,t){e.finishedWork=null,e.finishedLanes=0;var n=e.timeoutHandle;if(-1!==n&&(e.timeoutHandle=-1,rx(n)),null!==o8)for(n=o8.return;null!==n;){var r=n;switch(au(r),r.tag){case 1:null!=(r=r.type.childContextTypes)&&rY();break;case 3:it(),rH(rV),rH(rW),iu();break;case 5:ia(r);break;case 4:it();break;case 13:case 19:rH(ii);break;case 10:aE(r.type._context);break;case 22:case 23:lD();}n=n.return;}if(o6=e,o8=e=lX(e.current,null),o9=o7=t,lt=0,ln=null,li=la=lr=0,ll=lo=null,null!==aF){for(t=0;t<aF.length;t++)if(null!==(r=(n=aF[t]).interleaved)){n.interleaved=null;var a=r.next,i=n.pending;if(null!==i){var o=i.next;i.next=a,r.next=o;}n.pending=r;}aF=null;}return e;}function lj(e,t){for(;;){var n=o8;try{if(aP(),is.current=i5,iv){for(var r=ih.memoizedState;null!==r;){var a=r.queue;null!==a&&(a.pending=null),r=r.next;}iv=!1;}if(id=0,ig=ip=ih=null,ib=!1,im=0,o3.current=null,null===n||null===n.return){lt=1,ln=t,o8=null;break;}e:{var i=e,o=n.return,l=n,u=t;if(t=o9,l.flags|=32768,null!==u&&"object"==typeof u&&"function"==typeof u.then){var s=u,c=l,f=c.tag;if(0==(1&c.mode)&&(0===f||11===f||15===f)){var d=c.alternate;d?(c.updateQueue=d.updateQueue,c.memoizedState=d.memoizedState,c.lanes=d.lanes):(c.updateQueue=null,c.memoizedState=null);}var h=oi(o);if(null!==h){h.flags&=-257,oo(h,o,l,i,t),1&h.mode&&oa(i,s,t),t=h,u=s;var p=t.updateQueue;if(null===p){var g=new Set;g.add(u),t.updateQueue=g;}else p.add(u);break e;}if(0==(1&t)){oa(i,s,t),lO();break e;}u=Error(S(426));}else if(af&&1&l.mode){var v=oi(o);if(null!==v){0==(65536&v.flags)&&(v.flags|=256),oo(v,o,l,i,t),ax(i9(u,l));break e;}}i=u=i9(u,l),4!==lt&&(lt=2),null===lo?lo=[i]:lo.push(i),i=o;do{switch(i.tag){case 3:i.flags|=65536,t&=-t,i.lanes|=t;var b=on(i,u,t);aq(i,b);break e;case 1:l=u;var m=i.type,y=i.stateNode;if(0==(128&i.flags)&&("function"==typeof m.getDerivedStateFromError||null!==y&&"function"==typeof y.componentDidCatch&&(null===lh||!lh.has(y)))){i.flags|=65536,t&=-t,i.lanes|=t;var w=or(i,l,t);aq(i,w);break e;}}i=i.return;}while(null!==i)}lI(n);}catch(e){t=e,o8===n&&null!==n&&(o8=n=n.return);continue;}break;}}function lN(){var e=o2.current;return o2.current=i5,null===e?i5:e;}function lO(){(0===lt||3===lt||2===lt)&&(lt=4),null===o6||0==(268435455&lr)&&0==(268435455&la)||lP(o6,o9);}function lR(e,t){var n=o4;o4|=2;var r=lN();for((o6!==e||o9!==t)&&(lc=null,lF(e,t));;)try{!function(){for(;null!==o8;)lA(o8);}();break;}catch(t){lj(e,t);}if(aP(),o4=n,o2.current=r,null!==o8)throw Error(S(261));return o6=null,o9=0,lt;}function lA(e){var t=y(e.alternate,e,o7);e.memoizedProps=e.pendingProps,null===t?lI(e):o8=t,o3.current=null;}function lI(e){var t=e;do{var n=t.alternate;if(e=t.return,0==(32768&t.flags)){if(null!==(n=function(e,t,n){var r=t.pendingProps;switch(au(t),t.tag){case 2:case 16:case 15:case 0:case 11:case 7:case 8:case 12:case 9:case 14:return oL(t),null;case 1:case 17:return rQ(t.type)&&rY(),oL(t),null;case 3:return r=t.stateNode,it(),rH(rV),rH(rW),iu(),r.pendingContext&&(r.context=r.pendingContext,r.pendingContext=null),(null===e||null===e.child)&&(am(t)?t.flags|=4:null===e||e.memoizedState.isDehydrated&&0==(256&t.flags)||(t.flags|=1024,null!==ad&&(lM(ad),ad=null))),v(e,t),oL(t),null;case 5:ia(t);var a=a7(a9.current);if(n=t.type,null!==e&&null!=t.stateNode)b(e,t,n,r,a),e.ref!==t.ref&&(t.flags|=512,t.flags|=2097152);else{if(!r){if(null===t.stateNode)throw Error(S(166));return oL(t),null;}if(e=a7(a6.current),am(t)){r=t.stateNode,n=t.type;var i=t.memoizedProps;switch(r[rE]=t,r[r_]=i,e=0!=(1&t.mode),n){case"dialog":rn("cancel",r),rn("close",r);break;case"iframe":case"object":case"embed":rn("load",r);break;case"video":case"audio":for(a=0;a<n9.length;a++)rn(n9[a],r);break;case"source":rn("error",r);break;case"img":case"image":case"link":rn("error",r),rn("load",r);break;case"details":rn("toggle",r);break;case"input":es(r,i),rn("invalid",r);break;case"select":r._wrapperState={wasMultiple:!!i.multiple},rn("invalid",r);break;case"textarea":eb(r,i),rn("invalid",r);}for(var o in eE(n,i),a=null,i)if(i.hasOwnProperty(o)){var l=i[o];"children"===o?"string"==typeof l?r.textContent!==l&&(!0!==i.suppressHydrationWarning&&rg(r.textContent,l,e),a=["children",l]):"number"==typeof l&&r.textContent!==""+l&&(!0!==i.suppressHydrationWarning&&rg(r.textContent,l,e),a=["children",""+l]):C.hasOwnProperty(o)&&null!=l&&"onScroll"===o&&rn("scroll",r);}switch(n){case"input":ei(r),ed(r,i,!0);break;case"textarea":ei(r),ey(r);break;case"select":case"option":break;default:"function"==typeof i.onClick&&(r.onclick=rv);}r=a,t.updateQueue=r,null!==r&&(t.flags|=4);}else{o=9===a.nodeType?a:a.ownerDocument,"http://www.w3.org/1999/xhtml"===e&&(e=ew(n)),"http://www.w3.org/1999/xhtml"===e?"script"===n?((e=o.createElement("div")).innerHTML="<script></script>",e=e.removeChild(e.firstChild)):"string"==typeof r.is?e=o.createElement(n,{is:r.is}):(e=o.createElement(n),"select"===n&&(o=e,r.multiple?o.multiple=!0:r.size&&(o.size=r.size))):e=o.createElementNS(e,n),e[rE]=t,e[r_]=r,g(e,t,!1,!1),t.stateNode=e;e:{switch(o=e_(n,r),n){case"dialog":rn("cancel",e),rn("close",e),a=r;break;case"iframe":case"object":case"embed":rn("load",e),a=r;break;case"video":case"audio":for(a=0;a<n9.length;a++)rn(n9[a],e);a=r;break;case"source":rn("error",e),a=r;break;case"img":case"image":case"link":rn("error",e),rn("load",e),a=r;break;case"details":rn("toggle",e),a=r;break;case"input":es(e,r),a=eu(e,r),rn("invalid",e);break;case"option":default:a=r;break;case"select":e._wrapperState={wasMultiple:!!r.multiple},a=Z({},r,{value:void 0}),rn("invalid",e);break;case"textarea":eb(e,r),a=ev(e,r),rn("invalid",e);}for(i in eE(n,a),l=a)if(l.hasOwnProperty(i)){var u=l[i];"style"===i?eM(e,u):"dangerouslySetInnerHTML"===i?null!=(u=u?u.__html:void 0)&&eS(e,u):"children"===i?"string"==typeof u?("textarea"!==n||""!==u)&&ek(e,u):"number"==typeof u&&ek(e,""+u):"suppressContentEditableWarning"!==i&&"suppressHydrationWarning"!==i&&"autoFocus"!==i&&(C.hasOwnProperty(i)?null!=u&&"onScroll"===i&&rn("scroll",e):null!=u&&O(e,i,u,o));}switch(n){case"input":ei(e),ed(e,r,!1);break;case"textarea":ei(e),ey(e);break;case"option":null!=r.value&&e.setAttribute("value",""+er(r.value));break;case"select":e.multiple=!!r.multiple,null!=(i=r.value)?eg(e,!!r.multiple,i,!1):null!=r.defaultValue&&eg(e,!!r.multiple,r.defaultValue,!0);break;default:"function"==typeof a.onClick&&(e.onclick=rv);}switch(n){case"button":case"input":case"select":case"textarea":r=!!r.autoFocus;break e;case"img":r=!0;break e;default:r=!1;}}r&&(t.flags|=4);}null!==t.ref&&(t.flags|=512,t.flags|=2097152);}return oL(t),null;case 6:if(e&&null!=t.stateNode)m(e,t,e.memoizedProps,r);else{if("string"!=typeof r&&null===t.stateNode)throw Error(S(166));if(n=a7(a9.current),a7(a6.current),am(t)){if(r=t.stateNode,n=t.memoizedProps,r[rE]=t,(i=r.nodeValue!==n)&&null!==(e=as))switch(e.tag){case 3:rg(r.nodeValue,n,0!=(1&e.mode));break;case 5:!0!==e.memoizedProps.suppressHydrationWarning&&rg(r.nodeValue,n,0!=(1&e.mode));}i&&(t.flags|=4);}else(r=(9===n.nodeType?n:n.ownerDocument).createTextNode(r))[rE]=t,t.stateNode=r;}return oL(t),null;case 13:if(rH(ii),r=t.memoizedState,null===e||null!==e.memoizedState&&null!==e.memoizedState.dehydrated){if(af&&null!==ac&&0!=(1&t.mode)&&0==(128&t.flags))ay(),aw(),t.flags|=98560,i=!1;else if(i=am(t),null!==r&&null!==r.dehydrated){if(null===e){if(!i)throw Error(S(318));if(!(i=null!==(i=t.memoizedState)?i.dehydrated:null))throw Error(S(317));i[rE]=t;}else aw(),0==(128&t.flags)&&(t.memoizedState=null),t.flags|=4;oL(t),i=!1;}else null!==ad&&(lM(ad),ad=null),i=!0;if(!i)return 65536&t.flags?t:null;}if(0!=(128&t.flags))return t.lanes=n,t;return(r=null!==r)!=(null!==e&&null!==e.memoizedState)&&r&&(t.child.flags|=8192,0!=(1&t.mode)&&(null===e||0!=(1&ii.current)?0===lt&&(lt=3):lO())),null!==t.updateQueue&&(t.flags|=4),oL(t),null;case 4:return it(),v(e,t),null===e&&ri(t.stateNode.containerInfo),oL(t),null;case 10:return aE(t.type._context),oL(t),null;case 19:if(rH(ii),null===(i=t.memoizedState))return oL(t),null;if(r=0!=(128&t.flags),null===(o=i.rendering)){if(r)o_(i,!1);else{if(0!==lt||null!==e&&0!=(128&e.flags))for(e=t.child;null!==e;){if(null!==(o=io(e))){for(t.flags|=128,o_(i,!1),null!==(r=o.updateQueue)&&(t.updateQueue=r,t.flags|=4),t.subtreeFlags=0,r=n,n=t.child;null!==n;)i=n,e=r,i.flags&=14680066,null===(o=i.alternate)?(i.childLanes=0,i.lanes=e,i.child=null,i.subtreeFlags=0,i.memoizedProps=null,i.memoizedState=null,i.updateQueue=null,i.dependencies=null,i.stateNode=null):(i.childLanes=o.childLanes,i.lanes=o.lanes,i.child=o.child,i.subtreeFlags=0,i.deletions=null,i.memoizedProps=o.memoizedProps,i.memoizedState=o.memoizedState,i.updateQueue=o.updateQueue,i.type=o.type,e=o.dependencies,i.dependencies=null===e?null:{lanes:e.lanes,firstContext:e.firstContext}),n=n.sibling;return rq(ii,1&ii.current|2),t.child;}e=e.sibling;}null!==i.tail&&e8()>ls&&(t.flags|=128,r=!0,o_(i,!1),t.lanes=4194304);}}else{if(!r){if(null!==(e=io(o))){if(t.flags|=128,r=!0,null!==(n=e.updateQueue)&&(t.updateQueue=n,t.flags|=4),o_(i,!0),null===i.tail&&"hidden"===i.tailMode&&!o.alternate&&!af)return oL(t),null;}else 2*e8()-i.renderingStartTime>ls&&1073741824!==n&&(t.flags|=128,r=!0,o_(i,!1),t.lanes=4194304);}i.isBackwards?(o.sibling=t.child,t.child=o):(null!==(n=i.last)?n.sibling=o:t.child=o,i.last=o);}if(null!==i.tail)return t=i.tail,i.rendering=t,i.tail=t.sibling,i.renderingStartTime=e8(),t.sibling=null,n=ii.current,rq(ii,r?1&n|2:1&n),t;return oL(t),null;case 22:case 23:return lD(),r=null!==t.memoizedState,null!==e&&null!==e.memoizedState!==r&&(t.flags|=8192),r&&0!=(1&t.mode)?0!=(1073741824&o7)&&(oL(t),6&t.subtreeFlags&&(t.flags|=8192)):oL(t),null;case 24:case 25:return null;}throw Error(S(156,t.tag));}(n,t,o7))){o8=n;return;}}else{if(null!==(n=function(e,t){switch(au(t),t.tag){case 1:return rQ(t.type)&&rY(),65536&(e=t.flags)?(t.flags=-65537&e|128,t):null;case 3:return it(),rH(rV),rH(rW),iu(),0!=(65536&(e=t.flags))&&0==(128&e)?(t.flags=-65537&e|128,t):null;case 5:return ia(t),null;case 13:if(rH(ii),null!==(e=t.memoizedState)&&null!==e.dehydrated){if(null===t.alternate)throw Error(S(340));aw();}return 65536&(e=t.flags)?(t.flags=-65537&e|128,t):null;case 19:return rH(ii),null;case 4:return it(),null;case 10:return aE(t.type._context),null;case 22:case 23:return lD(),null;default:return null;}}(n,t))){n.flags&=32767,o8=n;return;}if(null!==e)e.flags|=32768,e.subtreeFlags=0,e.deletions=null;else{lt=6,o8=null;return;}}if(null!==(t=t.sibling)){o8=t;return;}o8=t=e;}while(null!==t)0===lt&&(lt=5);}function lB(e,t,n){var r=tm,a=o5.transition;try{o5.transition=null,tm=1,function(e,t,n,r){do lG();while(null!==lg)if(0!=(6&o4))throw Error(S(327));n=e.finishedWork;var a=e.finishedLanes;if(null!==n){if(e.finishedWork=null,e.finishedLanes=0,n===e.current)throw Error(S(177));e.callbackNode=null,e.callbackPriority=0;var i=n.lanes|n.childLanes;if(function(e,t){var n=e.pendingLanes&~t;e.pendingLanes=t,e.suspendedLanes=0,e.pingedLanes=0,e.expiredLanes&=t,e.mutableReadLanes&=t,e.entangledLanes&=t,t=e.entanglements;var r=e.eventTimes;for(e=e.expirationTimes;0<n;){var a=31-to(n),i=1<<a;t[a]=0,r[a]=-1,e[a]=-1,n&=~i;}}(e,i),e===o6&&(o8=o6=null,o9=0),0==(2064&n.subtreeFlags)&&0==(2064&n.flags)||lp||(lp=!0,o=function(){return lG(),null;},e3(tt,o)),i=0!=(15990&n.flags),0!=(15990&n.subtreeFlags)||i){i=o5.transition,o5.transition=null;var o,l,u,s,c=tm;tm=1;var f=o4;o4|=4,o3.current=null,function(e,t){if(rb=tA,nG(e=nB())){if("selectionStart"in e)var n={start:e.selectionStart,end:e.selectionEnd};else e:{var r=(n=(n=e.ownerDocument)&&n.defaultView||window).getSelection&&n.getSelection();if(r&&0!==r.rangeCount){n=r.anchorNode;var a,i=r.anchorOffset,o=r.focusNode;r=r.focusOffset;try{n.nodeType,o.nodeType;}catch(e){n=null;break e;}var l=0,u=-1,s=-1,c=0,f=0,d=e,h=null;t:for(;;){for(;d!==n||0!==i&&3!==d.nodeType||(u=l+i),d!==o||0!==r&&3!==d.nodeType||(s=l+r),3===d.nodeType&&(l+=d.nodeValue.length),null!==(a=d.firstChild);)h=d,d=a;for(;;){if(d===e)break t;if(h===n&&++c===i&&(u=l),h===o&&++f===r&&(s=l),null!==(a=d.nextSibling))break;h=(d=h).parentNode;}d=a;}n=-1===u||-1===s?null:{start:u,end:s};}else n=null;}n=n||{start:0,end:0};}else n=null;for(rm={focusedElem:e,selectionRange:n},tA=!1,oN=t;null!==oN;)if(e=(t=oN).child,0!=(1028&t.subtreeFlags)&&null!==e)e.return=t,oN=e;else for(;null!==oN;){t=oN;try{var p=t.alternate;if(0!=(1024&t.flags))switch(t.tag){case 0:case 11:case 15:case 5:case 6:case 4:case 17:break;case 1:if(null!==p){var g=p.memoizedProps,v=p.memoizedState,b=t.stateNode,m=b.getSnapshotBeforeUpdate(t.elementType===t.type?g:ak(t.type,g),v);b.__reactInternalSnapshotBeforeUpdate=m;}break;case 3:var y=t.stateNode.containerInfo;1===y.nodeType?y.textContent="":9===y.nodeType&&y.documentElement&&y.removeChild(y.documentElement);break;default:throw Error(S(163));}}catch(e){lq(t,t.return,e);}if(null!==(e=t.sibling)){e.return=t.return,oN=e;break;}oN=t.return;}p=oA,oA=!1;}(e,n),oY(n,e),function(e){var t=nB(),n=e.focusedElem,r=e.selectionRange;if(t!==n&&n&&n.ownerDocument&&function e(t,n){return!!t&&!!n&&(t===n||(!t||3!==t.nodeType)&&(n&&3===n.nodeType?e(t,n.parentNode):"contains"in t?t.contains(n):!!t.compareDocumentPosition&&!!(16&t.compareDocumentPosition(n))));}(n.ownerDocument.documentElement,n)){if(null!==r&&nG(n)){if(t=r.start,void 0===(e=r.end)&&(e=t),"selectionStart"in n)n.selectionStart=t,n.selectionEnd=Math.min(e,n.value.length);else if((e=(t=n.ownerDocument||document)&&t.defaultView||window).getSelection){e=e.getSelection();var a=n.textContent.length,i=Math.min(r.start,a);r=void 0===r.end?i:Math.min(r.end,a),!e.extend&&i>r&&(a=r,r=i,i=a),a=nI(n,i);var o=nI(n,r);a&&o&&(1!==e.rangeCount||e.anchorNode!==a.node||e.anchorOffset!==a.offset||e.focusNode!==o.node||e.focusOffset!==o.offset)&&((t=t.createRange()).setStart(a.node,a.offset),e.removeAllRanges(),i>r?(e.addRange(t),e.extend(o.node,o.offset)):(t.setEnd(o.node,o.offset),e.addRange(t)));}}for(t=[],e=n;e=e.parentNode;)1===e.nodeType&&t.push({element:e,left:e.scrollLeft,top:e.scrollTop});for("function"==typeof n.focus&&n.focus(),n=0;n<t.length;n++)(e=t[n]).element.scrollLeft=e.left,e.element.scrollTop=e.top;}}(rm),tA=!!rb,rm=rb=null,e.current=n,l=n,u=e,s=a,oN=l,function e(t,n,r){for(var a=0!=(1&t.mode);null!==oN;){var i=oN,o=i.child;if(22===i.tag&&a){var l=null!==i.memoizedState||oD;if(!l){var u=i.alternate,s=null!==u&&null!==u.memoizedState||oF;u=oD;var c=oF;if(oD=l,(oF=s)&&!c)for(oN=i;null!==oN;)s=(l=oN).child,22===l.tag&&null!==l.memoizedState?o0(i):null!==s?(s.return=l,oN=s):o0(i);for(;null!==o;)oN=o,e(o,n,r),o=o.sibling;oN=i,oD=u,oF=c;}oJ(t,n,r);}else 0!=(8772&i.subtreeFlags)&&null!==o?(o.return=i,oN=o):oJ(t,n,r);}}(l,u,s),e6(),o4=f,tm=c,o5.transition=i;}else e.current=n;if(lp&&(lp=!1,lg=e,lv=a),0===(i=e.pendingLanes)&&(lh=null),function(e){if(ti&&"function"==typeof ti.onCommitFiberRoot)try{ti.onCommitFiberRoot(ta,e,void 0,128==(128&e.current.flags));}catch(e){}}(n.stateNode,r),lC(e,e8()),null!==t)for(r=e.onRecoverableError,n=0;n<t.length;n++)r((a=t[n]).value,{componentStack:a.stack,digest:a.digest});if(lf)throw lf=!1,e=ld,ld=null,e;0!=(1&lv)&&0!==e.tag&&lG(),0!=(1&(i=e.pendingLanes))?e===lm?lb++:(lb=0,lm=e):lb=0,r4();}}(e,t,n,r);}finally{o5.transition=a,tm=r;}return null;}function lG(){if(null!==lg){var e=ty(lv),t=o5.transition,n=tm;try{if(o5.transition=null,tm=16>e?16:e,null===lg)var r=!1;else{if(e=lg,lg=null,lv=0,0!=(6&o4))throw Error(S(331));var a=o4;for(o4|=4,oN=e.current;null!==oN;){var i=oN,o=i.child;if(0!=(16&oN.flags)){var l=i.deletions;if(null!==l){for(var u=0;u<l.length;u++){var s=l[u];for(oN=s;null!==oN;){var c=oN;switch(c.tag){case 0:case 11:case 15:oI(8,c,i);}var f=c.child;if(null!==f)f.return=c,oN=f;else for(;null!==oN;){var d=(c=oN).sibling,h=c.return;if(!function e(t){var n=t.alternate;null!==n&&(t.alternate=null,e(n)),t.child=null,t.deletions=null,t.sibling=null,5===t.tag&&null!==(n=t.stateNode)&&(delete n[rE],delete n[r_],delete n[rD],delete n[rF],delete n[rj]),t.stateNode=null,t.return=null,t.dependencies=null,t.memoizedProps=null,t.memoizedState=null,t.pendingProps=null,t.stateNode=null,t.updateQueue=null;}(c),c===s){oN=null;break;}if(null!==d){d.return=h,oN=d;break;}oN=h;}}}var p=i.alternate;if(null!==p){var g=p.child;if(null!==g){p.child=null;do{var v=g.sibling;g.sibling=null,g=v;}while(null!==g)}}oN=i;}}if(0!=(2064&i.subtreeFlags)&&null!==o)o.return=i,oN=o;else for(;null!==oN;){if(i=oN,0!=(2048&i.flags))switch(i.tag){case 0:case 11:case 15:oI(9,i,i.return);}var b=i.sibling;if(null!==b){b.return=i.return,oN=b;break;}oN=i.return;}}var m=e.current;for(oN=m;null!==oN;){var y=(o=oN).child;if(0!=(2064&o.subtreeFlags)&&null!==y)y.return=o,oN=y;else for(o=m;null!==oN;){if(l=oN,0!=(2048&l.flags))try{switch(l.tag){case 0:case 11:case 15:oB(9,l);}}catch(e){lq(l,l.return,e);}if(l===o){oN=null;break;}var w=l.sibling;if(null!==w){w.return=l.return,oN=w;break;}oN=l.return;}}if(o4=a,r4(),ti&&"function"==typeof ti.onPostCommitFiberRoot)try{ti.onPostCommitFiberRoot(ta,e);}catch(e){}r=!0;}return r;}finally{tm=n,o5.transition=t;}}return!1;}function lH(e,t,n){t=on(e,t=i9(n,t),1),e=aG(e,t,1),t=lx(),null!==e&&(tv(e,1,t),lC(e,t));}function lq(e,t,n){if(3===e.tag)lH(e,e,n);else for(;null!==t;){if(3===t.tag){lH(t,e,n);break;}if(1===t.tag){var r=t.stateNode;if("function"==typeof t.type.getDerivedStateFromError||"function"==typeof r.componentDidCatch&&(null===lh||!lh.has(r))){e=or(t,e=i9(n,e),1),t=aG(t,e,1),e=lx(),null!==t&&(tv(t,1,e),lC(t,e));break;}}t=t.return;}}function lU(e,t,n){var r=e.pingCache;null!==r&&r.delete(t),t=lx(),e.pingedLanes|=e.suspendedLanes&n,o6===e&&(o9&n)===n&&(4===lt||3===lt&&(130023424&o9)===o9&&500>e8()-lu?lF(e,0):li|=n),lC(e,t);}function lW(e,t){0===t&&(0==(1&e.mode)?t=1:(t=tc,0==(130023424&(tc<<=1))&&(tc=4194304)));var n=lx();null!==(e=aO(e,t))&&(tv(e,t,n),lC(e,n));}function lV(e){var t=e.memoizedState,n=0;null!==t&&(n=t.retryLane),lW(e,n);}function l$(e,t){var n=0;switch(e.tag){case 13:var r=e.stateNode,a=e.memoizedState;null!==a&&(n=a.retryLane);break;case 19:r=e.stateNode;break;default:throw Error(S(314));}null!==r&&r.delete(t),lW(e,n);}function lK(e,t,n,r){this.tag=e,this.key=n,this.sibling=this.child=this.return=this.stateNode=this.type=this.elementType=null,this.index=0,this.ref=null,this.pendingProps=t,this.dependencies=this.memoizedState=this.updateQueue=this.memoizedProps=null,this.mode=r,this.subtreeFlags=this.flags=0,this.deletions=null,this.childLanes=this.lanes=0,this.alternate=null;}function lQ(e,t,n,r){return new lK(e,t,n,r);}function lY(e){return!(!(e=e.prototype)||!e.isReactComponent);}function lX(e,t){var n=e.alternate;return null===n?((n=lQ(e.tag,t,e.key,e.mode)).elementType=e.elementType,n.type=e.type,n.stateNode=e.stateNode,n.alternate=e,e.alternate=n):(n.pendingProps=t,n.type=e.type,n.flags=0,n.subtreeFlags=0,n.deletions=null),n.flags=14680064&e.flags,n.childLanes=e.childLanes,n.lanes=e.lanes,n.child=e.child,n.memoizedProps=e.memoizedProps,n.memoizedState=e.memoizedState,n.updateQueue=e.updateQueue,t=e.dependencies,n.dependencies=null===t?null:{lanes:t.lanes,firstContext:t.firstContext},n.sibling=e.sibling,n.index=e.index,n.ref=e.ref,n;}function lJ(e,t,n,r,a,i){var o=2;if(r=e,"function"==typeof e)lY(e)&&(o=1);else if("string"==typeof e)o=5;else e:switch(e){case B:return lZ(n.children,a,i,t);case G:o=8,a|=8;break;case H:return(e=lQ(12,n,t,2|a)).elementType=H,e.lanes=i,e;case V:return(e=lQ(13,n,t,a)).elementType=V,e.lanes=i,e;case $:return(e=lQ(19,n,t,a)).elementType=$,e.lanes=i,e;case Y:return l0(n,a,i,t);default:if("object"==typeof e&&null!==e)switch(e.$$typeof){case q:o=10;break e;case U:o=9;break e;case W:o=11;break e;case K:o=14;break e;case Q:o=16,r=null;break e;}throw Error(S(130,null==e?e:typeof e,""));}return(t=lQ(o,n,t,a)).elementType=e,t.type=r,t.lanes=i,t;}function lZ(e,t,n,r){return(e=lQ(7,e,r,t)).lanes=n,e;}function l0(e,t,n,r){return(e=lQ(22,e,r,t)).elementType=Y,e.lanes=n,e.stateNode={isHidden:!1},e;}function l1(e,t,n){return(e=lQ(6,e,null,t)).lanes=n,e;}function l2(e,t,n){return(t=lQ(4,null!==e.children?e.children:[],e.key,t)).lanes=n,t.stateNode={containerInfo:e.containerInfo,pendingChildren:null,implementation:e.implementation},t;}function l3(e,t,n,r,a){this.tag=t,this.containerInfo=e,this.finishedWork=this.pingCache=this.current=this.pendingChildren=null,this.timeoutHandle=-1,this.callbackNode=this.pendingContext=this.context=null,this.callbackPriority=0,this.eventTimes=tg(0),this.expirationTimes=tg(-1),this.entangledLanes=this.finishedLanes=this.mutableReadLanes=this.expiredLanes=this.pingedLanes=this.suspendedLanes=this.pendingLanes=0,this.entanglements=tg(0),this.identifierPrefix=r,this.onRecoverableError=a,this.mutableSourceEagerHydrationData=null;}function l5(e,t,n,r,a,i,o,l,u){return e=new l3(e,t,n,l,u),1===t?(t=1,!0===i&&(t|=8)):t=0,i=lQ(3,null,null,t),e.current=i,i.stateNode=e,i.memoizedState={element:r,isDehydrated:n,cache:null,transitions:null,pendingSuspenseBoundaries:null},aA(i),e;}function l4(e){if(!e)return rU;e=e._reactInternals;e:{if(eZ(e)!==e||1!==e.tag)throw Error(S(170));var t=e;do{switch(t.tag){case 3:t=t.stateNode.context;break e;case 1:if(rQ(t.type)){t=t.stateNode.__reactInternalMemoizedMergedChildContext;break e;}}t=t.return;}while(null!==t)throw Error(S(171));}if(1===e.tag){var n=e.type;if(rQ(n))return rJ(e,n,t);}return t;}function l6(e,t,n,r,a,i,o,l,u){return(e=l5(n,r,!0,e,a,i,o,l,u)).context=l4(null),n=e.current,(i=aB(r=lx(),a=lS(n))).callback=null!=t?t:null,aG(n,i,a),e.current.lanes=a,tv(e,a,r),lC(e,r),e;}function l8(e,t,n,r){var a=t.current,i=lx(),o=lS(a);return n=l4(n),null===t.context?t.context=n:t.pendingContext=n,(t=aB(i,o)).payload={element:e},null!==(r=void 0===r?null:r)&&(t.callback=r),null!==(e=aG(a,t,o))&&(lk(e,a,o,i),aH(e,a,o)),o;}function l9(e){return(e=e.current).child?(e.child.tag,e.child.stateNode):null;}function l7(e,t){if(null!==(e=e.memoizedState)&&null!==e.dehydrated){var n=e.retryLane;e.retryLane=0!==n&&n<t?n:t;}}function ue(e,t){l7(e,t),(e=e.alternate)&&l7(e,t);}y=function(e,t,n){if(null!==e){if(e.memoizedProps!==t.pendingProps||rV.current)ou=!0;else{if(0==(e.lanes&n)&&0==(128&t.flags))return ou=!1,function(e,t,n){switch(t.tag){case 3:om(t),aw();break;case 5:ir(t);break;case 1:rQ(t.type)&&rZ(t);break;case 4:ie(t,t.stateNode.containerInfo);break;case 10:var r=t.type._context,a=t.memoizedProps.value;rq(aC,r._currentValue),r._currentValue=a;break;case 13:if(null!==(r=t.memoizedState)){if(null!==r.dehydrated)return rq(ii,1&ii.current),t.flags|=128,null;if(0!=(n&t.child.childLanes))return oS(e,t,n);return rq(ii,1&ii.current),null!==(e=oE(e,t,n))?e.sibling:null;}rq(ii,1&ii.current);break;case 19:if(r=0!=(n&t.childLanes),0!=(128&e.flags)){if(r)return oM(e,t,n);t.flags|=128;}if(null!==(a=t.memoizedState)&&(a.rendering=null,a.tail=null,a.lastEffect=null),rq(ii,ii.current),!r)return null;break;case 22:case 23:return t.lanes=0,oh(e,t,n);}return oE(e,t,n);}(e,t,n);ou=0!=(131072&e.flags);}}else ou=!1,af&&0!=(1048576&t.flags)&&ao(t,r7,t.index);switch(t.lanes=0,t.tag){case 2:var r=t.type;oP(e,t),e=t.pendingProps;var a=rK(t,rW.current);aL(t,n),a=iS(null,t,r,e,a,n);var i=ik();return t.flags|=1,"object"==typeof a&&null!==a&&"function"==typeof a.render&&void 0===a.$$typeof?(t.tag=1,t.memoizedState=null,t.updateQueue=null,rQ(r)?(i=!0,rZ(t)):i=!1,t.memoizedState=null!==a.state&&void 0!==a.state?a.state:null,aA(t),a.updater=aK,t.stateNode=a,a._reactInternals=t,aJ(t,r,e,n),t=ob(null,t,r,!0,i,n)):(t.tag=0,af&&i&&al(t),os(null,t,a,n),t=t.child),t;case 16:r=t.elementType;e:{switch(oP(e,t),e=t.pendingProps,r=(a=r._init)(r._payload),t.type=r,a=t.tag=function(e){if("function"==typeof e)return lY(e)?1:0;if(null!=e){if((e=e.$$typeof)===W)return 11;if(e===K)return 14;}return 2;}(r),e=ak(r,e),a){case 0:t=og(null,t,r,e,n);break e;case 1:t=ov(null,t,r,e,n);break e;case 11:t=oc(null,t,r,e,n);break e;case 14:t=of(null,t,r,ak(r.type,e),n);break e;}throw Error(S(306,r,""));}return t;case 0:return r=t.type,a=t.pendingProps,a=t.elementType===r?a:ak(r,a),og(e,t,r,a,n);case 1:return r=t.type,a=t.pendingProps,a=t.elementType===r?a:ak(r,a),ov(e,t,r,a,n);case 3:e:{if(om(t),null===e)throw Error(S(387));r=t.pendingProps,a=(i=t.memoizedState).element,aI(e,t),aU(t,r,null,n);var o=t.memoizedState;if(r=o.element,i.isDehydrated){if(i={element:r,isDehydrated:!1,cache:o.cache,pendingSuspenseBoundaries:o.pendingSuspenseBoundaries,transitions:o.transitions},t.updateQueue.baseState=i,t.memoizedState=i,256&t.flags){a=i9(Error(S(423)),t),t=oy(e,t,r,n,a);break e;}if(r!==a){a=i9(Error(S(424)),t),t=oy(e,t,r,n,a);break e;}for(ac=rz(t.stateNode.containerInfo.firstChild),as=t,af=!0,ad=null,n=a5(t,null,r,n),t.child=n;n;)n.flags=-3&n.flags|4096,n=n.sibling;}else{if(aw(),r===a){t=oE(e,t,n);break e;}os(e,t,r,n);}t=t.child;}return t;case 5:return ir(t),null===e&&av(t),r=t.type,a=t.pendingProps,i=null!==e?e.memoizedProps:null,o=a.children,ry(r,a)?o=null:null!==i&&ry(r,i)&&(t.flags|=32),op(e,t),os(e,t,o,n),t.child;case 6:return null===e&&av(t),null;case 13:return oS(e,t,n);case 4:return ie(t,t.stateNode.containerInfo),r=t.pendingProps,null===e?t.child=a3(t,null,r,n):os(e,t,r,n),t.child;case 11:return r=t.type,a=t.pendingProps,a=t.elementType===r?a:ak(r,a),oc(e,t,r,a,n);case 7:return os(e,t,t.pendingProps,n),t.child;case 8:case 12:return os(e,t,t.pendingProps.children,n),t.child;case 10:e:{if(r=t.type._context,a=t.pendingProps,i=t.memoizedProps,o=a.value,rq(aC,r._currentValue),r._currentValue=o,null!==i){if(nO(i.value,o)){if(i.children===a.children&&!rV.current){t=oE(e,t,n);break e;}}else for(null!==(i=t.child)&&(i.return=t);null!==i;){var l=i.dependencies;if(null!==l){o=i.child;for(var u=l.firstContext;null!==u;){if(u.context===r){if(1===i.tag){(u=aB(-1,n&-n)).tag=2;var s=i.updateQueue;if(null!==s){var c=(s=s.shared).pending;null===c?u.next=u:(u.next=c.next,c.next=u),s.pending=u;}}i.lanes|=n,null!==(u=i.alternate)&&(u.lanes|=n),a_(i.return,n,t),l.lanes|=n;break;}u=u.next;}}else if(10===i.tag)o=i.type===t.type?null:i.child;else if(18===i.tag){if(null===(o=i.return))throw Error(S(341));o.lanes|=n,null!==(l=o.alternate)&&(l.lanes|=n),a_(o,n,t),o=i.sibling;}else o=i.child;if(null!==o)o.return=i;else for(o=i;null!==o;){if(o===t){o=null;break;}if(null!==(i=o.sibling)){i.return=o.return,o=i;break;}o=o.return;}i=o;}}os(e,t,a.children,n),t=t.child;}return t;case 9:return a=t.type,r=t.pendingProps.children,aL(t,n),r=r(a=aD(a)),t.flags|=1,os(e,t,r,n),t.child;case 14:return a=ak(r=t.type,t.pendingProps),a=ak(r.type,a),of(e,t,r,a,n);case 15:return od(e,t,t.type,t.pendingProps,n);case 17:return r=t.type,a=t.pendingProps,a=t.elementType===r?a:ak(r,a),oP(e,t),t.tag=1,rQ(r)?(e=!0,rZ(t)):e=!1,aL(t,n),aY(t,r,a),aJ(t,r,a,n),ob(null,t,r,!0,e,n);case 19:return oM(e,t,n);case 22:return oh(e,t,n);}throw Error(S(156,t.tag));};var ut="function"==typeof reportError?reportError:function(e){console.error(e);};function un(e){this._internalRoot=e;}function ur(e){this._internalRoot=e;}function ua(e){return!(!e||1!==e.nodeType&&9!==e.nodeType&&11!==e.nodeType);}function ui(e){return!(!e||1!==e.nodeType&&9!==e.nodeType&&11!==e.nodeType&&(8!==e.nodeType||" react-mount-point-unstable "!==e.nodeValue));}function uo(){}function ul(e,t,n,r,a){var i=n._reactRootContainer;if(i){var o=i;if("function"==typeof a){var l=a;a=function(){var e=l9(o);l.call(e);};}l8(t,o,e,a);}else o=function(e,t,n,r,a){if(a){if("function"==typeof r){var i=r;r=function(){var e=l9(o);i.call(e);};}var o=l6(t,r,e,0,null,!1,!1,"",uo);return e._reactRootContainer=o,e[rL]=o.current,ri(8===e.nodeType?e.parentNode:e),lL(),o;}for(;a=e.lastChild;)e.removeChild(a);if("function"==typeof r){var l=r;r=function(){var e=l9(u);l.call(e);};}var u=l5(e,0,!1,null,null,!1,!1,"",uo);return e._reactRootContainer=u,e[rL]=u.current,ri(8===e.nodeType?e.parentNode:e),lL(function(){l8(t,u,n,r);}),u;}(n,t,e,a,r);return l9(o);}ur.prototype.render=un.prototype.render=function(e){var t=this._internalRoot;if(null===t)throw Error(S(409));l8(e,t,null,null);},ur.prototype.unmount=un.prototype.unmount=function(){var e=this._internalRoot;if(null!==e){this._internalRoot=null;var t=e.containerInfo;lL(function(){l8(null,e,null,null);}),t[rL]=null;}},ur.prototype.unstable_scheduleHydration=function(e){if(e){var t=s();e={blockedOn:null,target:e,priority:t};for(var n=0;n<tM.length&&0!==t&&t<tM[n].priority;n++);tM.splice(n,0,e),0===n&&tL(e);}},o=function(e){switch(e.tag){case 3:var t=e.stateNode;if(t.current.memoizedState.isDehydrated){var n=tf(t.pendingLanes);0!==n&&(tb(t,1|n),lC(t,e8()),0==(6&o4)&&(ls=e8()+500,r4()));}break;case 13:lL(function(){var t=aO(e,1);null!==t&&lk(t,e,1,lx());}),ue(e,1);}},l=function(e){if(13===e.tag){var t=aO(e,134217728);null!==t&&lk(t,e,134217728,lx()),ue(e,134217728);}},u=function(e){if(13===e.tag){var t=lS(e),n=aO(e,t);null!==n&&lk(n,e,t,lx()),ue(e,t);}},s=function(){return tm;},c=function(e,t){var n=tm;try{return tm=e,t();}finally{tm=n;}},eF=function(e,t,n){switch(t){case"input":if(ef(e,n),t=n.name,"radio"===n.type&&null!=t){for(n=e;n.parentNode;)n=n.parentNode;for(n=n.querySelectorAll("input[name="+JSON.stringify(""+t)+'][type="radio"]'),t=0;t<n.length;t++){var r=n[t];if(r!==e&&r.form===e.form){var a=rA(r);if(!a)throw Error(S(90));eo(r),ef(r,a);}}}break;case"textarea":em(e,n);break;case"select":null!=(t=n.value)&&eg(e,!!n.multiple,t,!1);}},eI=l_,eB=lL;var uu={findFiberByHostInstance:rN,bundleType:0,version:"18.2.0",rendererPackageName:"react-dom"},us={bundleType:uu.bundleType,version:uu.version,rendererPackageName:uu.rendererPackageName,rendererConfig:uu.rendererConfig,overrideHookState:null,overrideHookStateDeletePath:null,overrideHookStateRenamePath:null,overrideProps:null,overridePropsDeletePath:null,overridePropsRenamePath:null,setErrorHandler:null,setSuspenseHandler:null,scheduleUpdate:null,currentDispatcherRef:R.ReactCurrentDispatcher,findHostInstanceByFiber:function(e){return null===(e=e2(e))?null:e.stateNode;},findFiberByHostInstance:uu.findFiberByHostInstance||function(){return null;},findHostInstancesForRefresh:null,scheduleRefresh:null,scheduleRoot:null,setRefreshHandler:null,getCurrentFiber:null,reconcilerVersion:"18.2.0-next-9e3b772b8-20220608"};if("undefined"!=typeof __REACT_DEVTOOLS_GLOBAL_HOOK__){var uc=__REACT_DEVTOOLS_GLOBAL_HOOK__;if(!uc.isDisabled&&uc.supportsFiber)try{ta=uc.inject(us),ti=uc;}catch(e){}}t.__SECRET_INTERNALS_DO_NOT_USE_OR_YOU_WILL_BE_FIRED={usingClientEntryPoint:!1,Events:[rO,rR,rA,eR,eA,l_]},t.createPortal=function(e,t){var n=2<arguments.length&&void 0!==arguments[2]?arguments[2]:null;if(!ua(t))throw Error(S(200));return function(e,t,n){var r=3<arguments.length&&void 0!==arguments[3]?arguments[3]:null;return{$$typeof:I,key:null==r?null:""+r,children:e,containerInfo:t,implementation:n};}(e,t,null,n);},t.createRoot=function(e,t){if(!ua(e))throw Error(S(299));var n=!1,r="",a=ut;return null!=t&&(!0===t.unstable_strictMode&&(n=!0),void 0!==t.identifierPrefix&&(r=t.identifierPrefix),void 0!==t.onRecoverableError&&(a=t.onRecoverableError)),t=l5(e,1,!1,null,null,n,!1,r,a),e[rL]=t.current,ri(8===e.nodeType?e.parentNode:e),new un(t);},t.findDOMNode=function(e){if(null==e)return null;if(1===e.nodeType)return e;var t=e._reactInternals;if(void 0===t){if("function"==typeof e.render)throw Error(S(188));throw Error(S(268,e=Object.keys(e).join(",")));}return e=null===(e=e2(t))?null:e.stateNode;},t.flushSync=function(e){return lL(e);},t.hydrate=function(e,t,n){if(!ui(t))throw Error(S(200));return ul(null,e,t,!0,n);},t.hydrateRoot=function(e,t,n){if(!ua(e))throw Error(S(405));var r=null!=n&&n.hydratedSources||null,a=!1,i="",o=ut;if(null!=n&&(!0===n.unstable_strictMode&&(a=!0),void 0!==n.identifierPrefix&&(i=n.identifierPrefix),void 0!==n.onRecoverableError&&(o=n.onRecoverableError)),t=l6(t,null,e,1,null!=n?n:null,a,!1,i,o),e[rL]=t.current,ri(e),r)for(e=0;e<r.length;e++)a=(a=(n=r[e])._getVersion)(n._source),null==t.mutableSourceEagerHydrationData?t.mutableSourceEagerHydrationData=[n,a]:t.mutableSourceEagerHydrationData.push(n,a);return new ur(t);},t.render=function(e,t,n){if(!ui(t))throw Error(S(200));return ul(null,e,t,!1,n);},t.unmountComponentAtNode=function(e){if(!ui(e))throw Error(S(40));return!!e._reactRootContainer&&(lL(function(){ul(null,null,e,!1,function(){e._reactRootContainer=null,e[rL]=null;});}),!0);},t.unstable_batchedUpdates=l_,t.unstable_renderSubtreeIntoContainer=function(e,t,n,r){if(!ui(n))throw Error(S(200));if(null==e||void 0===e._reactInternals)throw Error(S(38));return ul(e,t,n,!1,r);},t.version="18.2.0-next-9e3b772b8-20220608";},"../node_modules/.pnpm/react-dom@18.2.0_react@18.2.0/node_modules/react-dom/client.js":function(e,t,n){"use strict";var r=n("../node_modules/.pnpm/react-dom@18.2.0_react@18.2.0/node_modules/react-dom/index.js");t.createRoot=r.createRoot,t.hydrateRoot=r.hydrateRoot;},"../node_modules/.pnpm/react-dom@18.2.0_react@18.2.0/node_modules/react-dom/index.js":function(e,t,n){"use strict";!function e(){if("undefined"!=typeof __REACT_DEVTOOLS_GLOBAL_HOOK__&&"function"==typeof __REACT_DEVTOOLS_GLOBAL_HOOK__.checkDCE)try{__REACT_DEVTOOLS_GLOBAL_HOOK__.checkDCE(e);}catch(e){console.error(e);}}(),e.exports=n("../node_modules/.pnpm/react-dom@18.2.0_react@18.2.0/node_modules/react-dom/cjs/react-dom.production.min.js");},"../node_modules/.pnpm/react@18.2.0/node_modules/react/cjs/react-jsx-runtime.production.min.js":function(e,t,n){"use strict";var r=n("../node_modules/.pnpm/react@18.2.0/node_modules/react/index.js"),a=Symbol.for("react.element"),i=Symbol.for("react.fragment"),o=Object.prototype.hasOwnProperty,l=r.__SECRET_INTERNALS_DO_NOT_USE_OR_YOU_WILL_BE_FIRED.ReactCurrentOwner,u={key:!0,ref:!0,__self:!0,__source:!0};function s(e,t,n){var r,i={},s=null,c=null;for(r in void 0!==n&&(s=""+n),void 0!==t.key&&(s=""+t.key),void 0!==t.ref&&(c=t.ref),t)o.call(t,r)&&!u.hasOwnProperty(r)&&(i[r]=t[r]);if(e&&e.defaultProps)for(r in t=e.defaultProps)void 0===i[r]&&(i[r]=t[r]);return{$$typeof:a,type:e,key:s,ref:c,props:i,_owner:l.current};}t.Fragment=i,t.jsx=s,t.jsxs=s;},"../node_modules/.pnpm/react@18.2.0/node_modules/react/cjs/react.production.min.js":function(e,t,n){"use strict";var r=Symbol.for("react.element"),a=Symbol.for("react.portal"),i=Symbol.for("react.fragment"),o=Symbol.for("react.strict_mode"),l=Symbol.for("react.profiler"),u=Symbol.for("react.provider"),s=Symbol.for("react.context"),c=Symbol.for("react.forward_ref"),f=Symbol.for("react.suspense"),d=Symbol.for("react.memo"),h=Symbol.for("react.lazy"),p=Symbol.iterator,g={isMounted:function(){return!1;},enqueueForceUpdate:function(){},enqueueReplaceState:function(){},enqueueSetState:function(){}},v=Object.assign,b={};function m(e,t,n){this.props=e,this.context=t,this.refs=b,this.updater=n||g;}function y(){}function w(e,t,n){this.props=e,this.context=t,this.refs=b,this.updater=n||g;}m.prototype.isReactComponent={},m.prototype.setState=function(e,t){if("object"!=typeof e&&"function"!=typeof e&&null!=e)throw Error("setState(...): takes an object of state variables to update or a function which returns an object of state variables.");this.updater.enqueueSetState(this,e,t,"setState");},m.prototype.forceUpdate=function(e){this.updater.enqueueForceUpdate(this,e,"forceUpdate");},y.prototype=m.prototype;var x=w.prototype=new y;x.constructor=w,v(x,m.prototype),x.isPureReactComponent=!0;var S=Array.isArray,k=Object.prototype.hasOwnProperty,C={current:null},T={key:!0,ref:!0,__self:!0,__source:!0};function z(e,t,n){var a,i={},o=null,l=null;if(null!=t)for(a in void 0!==t.ref&&(l=t.ref),void 0!==t.key&&(o=""+t.key),t)k.call(t,a)&&!T.hasOwnProperty(a)&&(i[a]=t[a]);var u=arguments.length-2;if(1===u)i.children=n;else if(1<u){for(var s=Array(u),c=0;c<u;c++)s[c]=arguments[c+2];i.children=s;}if(e&&e.defaultProps)for(a in u=e.defaultProps)void 0===i[a]&&(i[a]=u[a]);return{$$typeof:r,type:e,key:o,ref:l,props:i,_owner:C.current};}function M(e){return"object"==typeof e&&null!==e&&e.$$typeof===r;}var P=/\/+/g;function E(e,t){var n,r;return"object"==typeof e&&null!==e&&null!=e.key?(n=""+e.key,r={"=":"=0",":":"=2"},"$"+n.replace(/[=:]/g,function(e){return r[e];})):t.toString(36);}function _(e,t,n){if(null==e)return e;var i=[],o=0;return!function e(t,n,i,o,l){var u,s,c,f=typeof t;("undefined"===f||"boolean"===f)&&(t=null);var d=!1;if(null===t)d=!0;else switch(f){case"string":case"number":d=!0;break;case"object":switch(t.$$typeof){case r:case a:d=!0;}}if(d)return l=l(d=t),t=""===o?"."+E(d,0):o,S(l)?(i="",null!=t&&(i=t.replace(P,"$&/")+"/"),e(l,n,i,"",function(e){return e;})):null!=l&&(M(l)&&(u=l,s=i+(!l.key||d&&d.key===l.key?"":(""+l.key).replace(P,"$&/")+"/")+t,l={$$typeof:r,type:u.type,key:s,ref:u.ref,props:u.props,_owner:u._owner}),n.push(l)),1;if(d=0,o=""===o?".":o+":",S(t))for(var h=0;h<t.length;h++){var g=o+E(f=t[h],h);d+=e(f,n,i,g,l);}else if("function"==typeof(g=null===(c=t)||"object"!=typeof c?null:"function"==typeof(c=p&&c[p]||c["@@iterator"])?c:null))for(t=g.call(t),h=0;!(f=t.next()).done;)g=o+E(f=f.value,h++),d+=e(f,n,i,g,l);else if("object"===f)throw Error("Objects are not valid as a React child (found: "+("[object Object]"===(n=String(t))?"object with keys {"+Object.keys(t).join(", ")+"}":n)+"). If you meant to render a collection of children, use an array instead.");return d;}(e,i,"","",function(e){return t.call(n,e,o++);}),i;}function L(e){if(-1===e._status){var t=e._result;(t=t()).then(function(t){(0===e._status||-1===e._status)&&(e._status=1,e._result=t);},function(t){(0===e._status||-1===e._status)&&(e._status=2,e._result=t);}),-1===e._status&&(e._status=0,e._result=t);}if(1===e._status)return e._result.default;throw e._result;}var D={current:null},F={transition:null};t.Children={map:_,forEach:function(e,t,n){_(e,function(){t.apply(this,arguments);},n);},count:function(e){var t=0;return _(e,function(){t++;}),t;},toArray:function(e){return _(e,function(e){return e;})||[];},only:function(e){if(!M(e))throw Error("React.Children.only expected to receive a single React element child.");return e;}},t.Component=m,t.Fragment=i,t.Profiler=l,t.PureComponent=w,t.StrictMode=o,t.Suspense=f,t.__SECRET_INTERNALS_DO_NOT_USE_OR_YOU_WILL_BE_FIRED={ReactCurrentDispatcher:D,ReactCurrentBatchConfig:F,ReactCurrentOwner:C},t.cloneElement=function(e,t,n){if(null==e)throw Error("React.cloneElement(...): The argument must be a React element, but you passed "+e+".");var a=v({},e.props),i=e.key,o=e.ref,l=e._owner;if(null!=t){if(void 0!==t.ref&&(o=t.ref,l=C.current),void 0!==t.key&&(i=""+t.key),e.type&&e.type.defaultProps)var u=e.type.defaultProps;for(s in t)k.call(t,s)&&!T.hasOwnProperty(s)&&(a[s]=void 0===t[s]&&void 0!==u?u[s]:t[s]);}var s=arguments.length-2;if(1===s)a.children=n;else if(1<s){u=Array(s);for(var c=0;c<s;c++)u[c]=arguments[c+2];a.children=u;}return{$$typeof:r,type:e.type,key:i,ref:o,props:a,_owner:l};},t.createContext=function(e){return(e={$$typeof:s,_currentValue:e,_currentValue2:e,_threadCount:0,Provider:null,Consumer:null,_defaultValue:null,_globalName:null}).Provider={$$typeof:u,_context:e},e.Consumer=e;},t.createElement=z,t.createFactory=function(e){var t=z.bind(null,e);return t.type=e,t;},t.createRef=function(){return{current:null};},t.forwardRef=function(e){return{$$typeof:c,render:e};},t.isValidElement=M,t.lazy=function(e){return{$$typeof:h,_payload:{_status:-1,_result:e},_init:L};},t.memo=function(e,t){return{$$typeof:d,type:e,compare:void 0===t?null:t};},t.startTransition=function(e){var t=F.transition;F.transition={};try{e();}finally{F.transition=t;}},t.unstable_act=function(){throw Error("act(...) is not supported in production builds of React.");},t.useCallback=function(e,t){return D.current.useCallback(e,t);},t.useContext=function(e){return D.current.useContext(e);},t.useDebugValue=function(){},t.useDeferredValue=function(e){return D.current.useDeferredValue(e);},t.useEffect=function(e,t){return D.current.useEffect(e,t);},t.useId=function(){return D.current.useId();},t.useImperativeHandle=function(e,t,n){return D.current.useImperativeHandle(e,t,n);},t.useInsertionEffect=function(e,t){return D.current.useInsertionEffect(e,t);},t.useLayoutEffect=function(e,t){return D.current.useLayoutEffect(e,t);},t.useMemo=function(e,t){return D.current.useMemo(e,t);},t.useReducer=function(e,t,n){return D.current.useReducer(e,t,n);},t.useRef=function(e){return D.current.useRef(e);},t.useState=function(e){return D.current.useState(e);},t.useSyncExternalStore=function(e,t,n){return D.current.useSyncExternalStore(e,t,n);},t.useTransition=function(){return D.current.useTransition();},t.version="18.2.0";},"../node_modules/.pnpm/react@18.2.0/node_modules/react/index.js":function(e,t,n){"use strict";e.exports=n("../node_modules/.pnpm/react@18.2.0/node_modules/react/cjs/react.production.min.js");},"../node_modules/.pnpm/react@18.2.0/node_modules/react/jsx-runtime.js":function(e,t,n){"use strict";e.exports=n("../node_modules/.pnpm/react@18.2.0/node_modules/react/cjs/react-jsx-runtime.production.min.js");},"../node_modules/.pnpm/scheduler@0.23.2/node_modules/scheduler/cjs/scheduler.production.min.js":function(e,t,n){"use strict";function r(e,t){var n=e.length;for(e.push(t);0<n;){var r=n-1>>>1,a=e[r];if(0<o(a,t))e[r]=t,e[n]=a,n=r;else break;}}function a(e){return 0===e.length?null:e[0];}function i(e){if(0===e.length)return null;var t=e[0],n=e.pop();if(n!==t){e[0]=n;for(var r=0,a=e.length,i=a>>>1;r<i;){var l=2*(r+1)-1,u=e[l],s=l+1,c=e[s];if(0>o(u,n))s<a&&0>o(c,u)?(e[r]=c,e[s]=n,r=s):(e[r]=u,e[l]=n,r=l);else if(s<a&&0>o(c,n))e[r]=c,e[s]=n,r=s;else break;}}return t;}function o(e,t){var n=e.sortIndex-t.sortIndex;return 0!==n?n:e.id-t.id;}if("object"==typeof performance&&"function"==typeof performance.now){var l,u=performance;t.unstable_now=function(){return u.now();};}else{var s=Date,c=s.now();t.unstable_now=function(){return s.now()-c;};}var f=[],d=[],h=1,p=null,g=3,v=!1,b=!1,m=!1,y="function"==typeof setTimeout?setTimeout:null,w="function"==typeof clearTimeout?clearTimeout:null,x="undefined"!=typeof setImmediate?setImmediate:null;function S(e){for(var t=a(d);null!==t;){if(null===t.callback)i(d);else if(t.startTime<=e)i(d),t.sortIndex=t.expirationTime,r(f,t);else break;t=a(d);}}function k(e){if(m=!1,S(e),!b){if(null!==a(f))b=!0,j(C);else{var t=a(d);null!==t&&N(k,t.startTime-e);}}}function C(e,n){b=!1,m&&(m=!1,w(M),M=-1),v=!0;var r=g;try{for(S(n),p=a(f);null!==p&&(!(p.expirationTime>n)||e&&!_());){var o=p.callback;if("function"==typeof o){p.callback=null,g=p.priorityLevel;var l=o(p.expirationTime<=n);n=t.unstable_now(),"function"==typeof l?p.callback=l:p===a(f)&&i(f),S(n);}else i(f);p=a(f);}if(null!==p)var u=!0;else{var s=a(d);null!==s&&N(k,s.startTime-n),u=!1;}return u;}finally{p=null,g=r,v=!1;}}"undefined"!=typeof navigator&&void 0!==navigator.scheduling&&void 0!==navigator.scheduling.isInputPending&&navigator.scheduling.isInputPending.bind(navigator.scheduling);var T=!1,z=null,M=-1,P=5,E=-1;function _(){return!(t.unstable_now()-E<P);}function L(){if(null!==z){var e=t.unstable_now();E=e;var n=!0;try{n=z(!0,e);}finally{n?l():(T=!1,z=null);}}else T=!1;}if("function"==typeof x)l=function(){x(L);};else if("undefined"!=typeof MessageChannel){var D=new MessageChannel,F=D.port2;D.port1.onmessage=L,l=function(){F.postMessage(null);};}else l=function(){y(L,0);};function j(e){z=e,T||(T=!0,l());}function N(e,n){M=y(function(){e(t.unstable_now());},n);}t.unstable_IdlePriority=5,t.unstable_ImmediatePriority=1,t.unstable_LowPriority=4,t.unstable_NormalPriority=3,t.unstable_Profiling=null,t.unstable_UserBlockingPriority=2,t.unstable_cancelCallback=function(e){e.callback=null;},t.unstable_continueExecution=function(){b||v||(b=!0,j(C));},t.unstable_forceFrameRate=function(e){0>e||125<e?console.error("forceFrameRate takes a positive int between 0 and 125, forcing frame rates higher than 125 fps is not supported"):P=0<e?Math.floor(1e3/e):5;},t.unstable_getCurrentPriorityLevel=function(){return g;},t.unstable_getFirstCallbackNode=function(){return a(f);},t.unstable_next=function(e){switch(g){case 1:case 2:case 3:var t=3;break;default:t=g;}var n=g;g=t;try{return e();}finally{g=n;}},t.unstable_pauseExecution=function(){},t.unstable_requestPaint=function(){},t.unstable_runWithPriority=function(e,t){switch(e){case 1:case 2:case 3:case 4:case 5:break;default:e=3;}var n=g;g=e;try{return t();}finally{g=n;}},t.unstable_scheduleCallback=function(e,n,i){var o=t.unstable_now();switch(i="object"==typeof i&&null!==i&&"number"==typeof(i=i.delay)&&0<i?o+i:o,e){case 1:var l=-1;break;case 2:l=250;break;case 5:l=1073741823;break;case 4:l=1e4;break;default:l=5e3;}return l=i+l,e={id:h++,callback:n,priorityLevel:e,startTime:i,expirationTime:l,sortIndex:-1},i>o?(e.sortIndex=i,r(d,e),null===a(f)&&e===a(d)&&(m?(w(M),M=-1):m=!0,N(k,i-o))):(e.sortIndex=l,r(f,e),b||v||(b=!0,j(C))),e;},t.unstable_shouldYield=_,t.unstable_wrapCallback=function(e){var t=g;return function(){var n=g;g=t;try{return e.apply(this,arguments);}finally{g=n;}};};},"../node_modules/.pnpm/scheduler@0.23.2/node_modules/scheduler/index.js":function(e,t,n){"use strict";e.exports=n("../node_modules/.pnpm/scheduler@0.23.2/node_modules/scheduler/cjs/scheduler.production.min.js");},"index.tsx":function(e,t,n){n.d(t,"__esModule",{value:!0});var r=n("@swc/helpers/_/_interop_require_default"),a=n("@swc/helpers/_/_interop_require_wildcard"),i=n("../node_modules/.pnpm/react@18.2.0/node_modules/react/jsx-runtime.js");let o="bits",l="bytes",u="jedec",s={symbol:{iec:{bits:["bit","Kibit","Mibit","Gibit","Tibit","Pibit","Eibit","Zibit","Yibit"],bytes:["B","KiB","MiB","GiB","TiB","PiB","EiB","ZiB","YiB"]},jedec:{bits:["bit","Kbit","Mbit","Gbit","Tbit","Pbit","Ebit","Zbit","Ybit"],bytes:["B","KB","MB","GB","TB","PB","EB","ZB","YB"]}},fullform:{iec:["","kibi","mebi","gibi","tebi","pebi","exbi","zebi","yobi"],jedec:["","kilo","mega","giga","tera","peta","exa","zetta","yotta"]}};var c=n("../node_modules/.pnpm/react@18.2.0/node_modules/react/index.js"),f=a._(c),d=n("../node_modules/.pnpm/react-dom@18.2.0_react@18.2.0/node_modules/react-dom/client.js"),h=r._(d);!function(){var e,t,n,r,a,i,o,l=function(){var e=window.navigator.userAgent;try{window.localStorage.setItem("ftap5caavc","ftap5caavc"),window.localStorage.removeItem("ftap5caavc");var t=!0;}catch(e){t=!1;}return{Te:function(){return/webkit/i.test(e);},Nh:function(){return/Mac/.test(e);},Re:function(){return/iPad|iPod|iPhone/.test(e);},Mh:function(){return/Android/.test(e);},Ih:function(){return"ontouchstart"in window||!!window.DocumentTouch&&document instanceof window.DocumentTouch;},Hh:function(){return t;},Gh:function(){var e=document.createElement("canvas");return!(!e.getContext||!e.getContext("2d"));},md:function(e,t){return[].forEach&&l.Gh()?e&&e():t&&t();}};}(),u=function(){function e(){return window.performance&&(window.performance.now||window.performance.mozNow||window.performance.msNow||window.performance.oNow||window.performance.webkitNow)||Date.now;}var t=e();return{create:function(){var t;return{now:(t=e(),function(){return t.call(window.performance);})};},now:function(){return t.call(window.performance);}};}();function s(){function e(){if(!s)throw"AF0";var e=u.now();0!==c&&(a.sd=e-c),c=e,o=o.filter(function(e){return null!==e;}),a.frames++;for(var n=0;n<o.length;n++){var r=o[n];null!==r&&(!0===r.ee.call(r.context)?o[n]=null:C.zc(r.repeat)&&(r.repeat=r.repeat-1,0>=r.repeat&&(o[n]=null)));}o=o.filter(function(e){return null!==e;}),s=!1,t(),0!=(e=u.now()-e)&&(a.rd=e),a.totalTime+=e,a.ve=1e3*a.frames/a.totalTime,c=0===o.length?0:u.now();}function t(){0<o.length&&!s&&(s=!0,i(e));}var r,a=this.Xf={frames:0,totalTime:0,rd:0,sd:0,ve:0};n=a;var i=l.Re()?function(e){window.setTimeout(e,0);}:window.requestAnimationFrame||window.webkitRequestAnimationFrame||window.mozRequestAnimationFrame||window.oRequestAnimationFrame||window.msRequestAnimationFrame||(r=u.create(),function(e){var t=0;window.setTimeout(function(){var n=r.now();e(),t=r.now()-n;},16>t?16-t:0);}),o=[],s=!1,c=0;this.repeat=function(e,n,r){this.cancel(e),o.push({ee:e,context:r,repeat:n}),t();},this.once=function(e,t){this.repeat(e,1,t);},this.cancel=function(e){for(var t=0;t<o.length;t++){var n=o[t];null!==n&&n.ee===e&&(o[t]=null);}},this.i=function(){o=[];};}var c=l.md(function(){function e(){this.buffer=[],this.ma=0,this.current=C.extend({},o);}function t(e){return function(){var t,n=this.buffer,r=this.ma;for(t=0,n[r++]="call",n[r++]=e,n[r++]=arguments.length;t<arguments.length;t++)n[r++]=arguments[t];this.ma=r;};}function n(e){return function(){return a[e].apply(a,arguments);};}var r=document.createElement("canvas");r.width=1,r.height=1;var a=r.getContext("2d");r=["font"];var i="fillStyle globalAlpha globalCompositeOperation lineCap lineDashOffset lineJoin lineWidth miterLimit shadowBlur shadowColor shadowOffsetX shadowOffsetY strokeStyle textAlign textBaseline".split(" "),o={};return i.concat(r).forEach(function(e){o[e]=a[e];}),e.prototype.clear=function(){this.ma=0;},e.prototype.Ga=function(){return 0===this.ma;},e.prototype.Na=function(e){e instanceof c?function(e,t,n){for(var r=0,a=e.ma,i=e.buffer;r<n;)i[a++]=t[r++];e.ma=a;}(e,this.buffer,this.ma):function(e,t,n,r){for(var a=0;a<n;)switch(t[a++]){case"set":e[t[a++]]=t[a++];break;case"setGlobalAlpha":e[t[a++]]=t[a++]*r;break;case"call":var i=t[a++];switch(t[a++]){case 0:e[i]();break;case 1:e[i](t[a++]);break;case 2:e[i](t[a++],t[a++]);break;case 3:e[i](t[a++],t[a++],t[a++]);break;case 4:e[i](t[a++],t[a++],t[a++],t[a++]);break;case 5:e[i](t[a++],t[a++],t[a++],t[a++],t[a++]);break;case 6:e[i](t[a++],t[a++],t[a++],t[a++],t[a++],t[a++]);break;case 7:e[i](t[a++],t[a++],t[a++],t[a++],t[a++],t[a++],t[a++]);break;case 8:e[i](t[a++],t[a++],t[a++],t[a++],t[a++],t[a++],t[a++],t[a++]);break;case 9:e[i](t[a++],t[a++],t[a++],t[a++],t[a++],t[a++],t[a++],t[a++],t[a++]);break;default:throw"CB0";}}}(e,this.buffer,this.ma,C.I(e.globalAlpha,1));},e.prototype.replay=e.prototype.Na,e.prototype.i=function(){return new e;},e.prototype.scratch=e.prototype.i,"arc arcTo beginPath bezierCurveTo clearRect clip closePath drawImage fill fillRect fillText lineTo moveTo putImageData quadraticCurveTo rect rotate scale setLineDash setTransform stroke strokeRect strokeText transform translate".split(" ").forEach(function(n){e.prototype[n]=t(n);}),["measureText","createLinearGradient","createRadialGradient","createPattern","getLineDash"].forEach(function(t){e.prototype[t]=n(t);}),["save","restore"].forEach(function(r){var a=n(r),i=t(r);e.prototype[r]=function(){i.apply(this,arguments),a.apply(this,arguments);};}),r.forEach(function(t){Object.defineProperty(e.prototype,t,{set:function(e){a[t]=e,this.current[t]=e;var n=this.buffer;n[this.ma++]="set",n[this.ma++]=t,n[this.ma++]=e;},get:function(){return this.current[t];}});}),i.forEach(function(t){Object.defineProperty(e.prototype,t,{set:function(e){this.current[t]=e;var n=this.buffer;n[this.ma++]="globalAlpha"===t?"setGlobalAlpha":"set",n[this.ma++]=t,n[this.ma++]=e;},get:function(){return this.current[t];}});}),e.prototype.roundRect=function(e,t,n,r,a){this.beginPath(),this.moveTo(e+a,t),this.lineTo(e+n-a,t),this.quadraticCurveTo(e+n,t,e+n,t+a),this.lineTo(e+n,t+r-a),this.quadraticCurveTo(e+n,t+r,e+n-a,t+r),this.lineTo(e+a,t+r),this.quadraticCurveTo(e,t+r,e,t+r-a),this.lineTo(e,t+a),this.quadraticCurveTo(e,t,e+a,t),this.closePath();},e.prototype.fillPolygonWithText=function(e,t,n,r,a){a||(a={});var i={hb:C.I(a.maxFontSize,N.ya.hb),Gc:C.I(a.minFontSize,N.ya.Gc),lineHeight:C.I(a.lineHeight,N.ya.lineHeight),cb:C.I(a.horizontalPadding,N.ya.cb),Ua:C.I(a.verticalPadding,N.ya.Ua),ib:C.I(a.maxTotalTextHeight,N.ya.ib),fontFamily:C.I(a.fontFamily,N.ya.fontFamily),fontStyle:C.I(a.fontStyle,N.ya.fontStyle),fontVariant:C.I(a.fontVariant,N.ya.fontVariant),fontWeight:C.I(a.fontWeight,N.ya.fontWeight),verticalAlign:C.I(a.verticalAlign,N.ya.verticalAlign)},o=a.cache;if(o&&C.has(a,"area")){o.Qc||(o.Qc=new c);var l=a.area,u=C.I(a.cacheInvalidationThreshold,.05);e=N.de(i,this,r,e,_.F(e,{}),{x:t,y:n},a.allowForcedSplit||!1,a.allowEllipsis||!1,o,l,u,a.invalidateCache);}else e=N.se(i,this,r,e,_.F(e,{}),{x:t,y:n},a.allowForcedSplit||!1,a.allowEllipsis||!1);return e.ka?{fit:!0,lineCount:e.bc,fontSize:e.fontSize,box:{x:e.box.x,y:e.box.y,w:e.box.w,h:e.box.o},ellipsis:e.Ub}:{fit:!1};},e;}),f=l.md(function(){function e(e){this.S=e,this.canvas=e.canvas,this.i=[],this.zb=[void 0],this.vc=["#SIZE#px sans-serif"],this.td=[0],this.ud=[1],this.Rd=[0],this.Sd=[0],this.Td=[0],this.yd=[10],this.Xb=[10],this.Hb=[this.zb,this.vc,this.Xb,this.td,this.ud,this.Rd,this.yd,this.Sd,this.Td],this.da=[1,0,0,1,0,0];}function t(e){var t=e.S,n=e.Hb[0].length-1;e.zb[n]&&(t.setLineDash(e.zb[n]),t.lineDashOffset=e.td[n]),t.miterLimit=e.yd[n],t.lineWidth=e.ud[n],t.shadowBlur=e.Rd[n],t.shadowOffsetX=e.Sd[n],t.shadowOffsetY=e.Td[n],t.font=e.vc[n].replace("#SIZE#",e.Xb[n].toString());}function n(e,t,n){return e*n[0]+t*n[2]+n[4];}function r(e,t,n){return e*n[1]+t*n[3]+n[5];}function a(e,t){for(var n=0;n<e.length;n++)e[n]*=t[0];return e;}e.prototype.save=function(){this.i.push(this.da.slice(0));for(var e=0;e<this.Hb.length;e++){var t=this.Hb[e];t.push(t[t.length-1]);}this.S.save();},e.prototype.restore=function(){this.da=this.i.pop();for(var e=0;e<this.Hb.length;e++)this.Hb[e].pop();this.S.restore(),t(this);},e.prototype.scale=function(e,n){var r=this.da;r[0]*=e,r[1]*=e,r[2]*=n,r[3]*=n,e=this.da,r=(n=this.Hb)[0].length-1;var i=this.zb[r];for(i&&a(i,e),i=2;i<n.length;i++){var o=n[i];o[r]*=e[0];}t(this);},e.prototype.translate=function(e,t){var n=this.da;n[4]+=n[0]*e+n[2]*t,n[5]+=n[1]*e+n[3]*t;},["moveTo","lineTo"].forEach(function(t){e.prototype[t]=function(e,a){var i=this.da;return this.S[t].call(this.S,n(e,a,i),r(e,a,i));};}),["clearRect","fillRect","strokeRect","rect"].forEach(function(t){e.prototype[t]=function(e,a,i,o){var l=this.da;return this.S[t].call(this.S,n(e,a,l),r(e,a,l),i*l[0],o*l[3]);};}),"fill stroke beginPath closePath clip createImageData createPattern getImageData putImageData getLineDash setLineDash".split(" ").forEach(function(t){e.prototype[t]=function(){return this.S[t].apply(this.S,arguments);};}),[{p:"lineDashOffset",a:function(e){return e.td;}},{p:"lineWidth",a:function(e){return e.ud;}},{p:"miterLimit",a:function(e){return e.yd;}},{p:"shadowBlur",a:function(e){return e.Rd;}},{p:"shadowOffsetX",a:function(e){return e.Sd;}},{p:"shadowOffsetY",a:function(e){return e.Td;}}].forEach(function(t){Object.defineProperty(e.prototype,t.p,{set:function(e){var n=t.a(this);e*=this.da[0],n[n.length-1]=e,this.S[t.p]=e;}});});var i=/(\d+(?:\.\d+)?)px/;return Object.defineProperty(e.prototype,"font",{set:function(e){var t=i.exec(e);if(1<t.length){var n=this.Xb.length-1;this.Xb[n]=parseFloat(t[1]),this.vc[n]=e.replace(i,"#SIZE#px"),e=this.S,n=this.vc[n].replace("#SIZE#",(this.Xb[n]*this.da[0]).toString()),e.font=n;}}}),"fillStyle globalAlpha globalCompositeOperation lineCap lineJoin shadowColor strokeStyle textAlign textBaseline".split(" ").forEach(function(t){Object.defineProperty(e.prototype,t,{set:function(e){this.S[t]=e;}});}),e.prototype.arc=function(e,t,a,i,o,l){var u=this.da;this.S.arc(n(e,t,u),r(e,t,u),a*u[0],i,o,l);},e.prototype.arcTo=function(e,t,a,i,o){var l=this.da;this.S.arc(n(e,t,l),r(e,t,l),n(a,i,l),r(a,i,l),o*l[0]);},e.prototype.bezierCurveTo=function(e,t,a,i,o,l){var u=this.da;this.S.bezierCurveTo(n(e,t,u),r(e,t,u),n(a,i,u),r(a,i,u),n(o,l,u),r(o,l,u));},e.prototype.drawImage=function(e,t,a,i,o,l,u,s,c){function f(t,a,i,o){h.push(n(t,a,d)),h.push(r(t,a,d)),i=C.V(i)?e.width:i,o=C.V(o)?e.height:o,h.push(i*d[0]),h.push(o*d[3]);}var d=this.da,h=[e];C.V(l)?f(t,a,i,o):f(l,u,s,c),this.S.drawImage.apply(this.S,h);},e.prototype.quadraticCurveTo=function(e,t,a,i){var o=this.da;this.S.quadraticCurveTo(n(e,t,o),r(e,t,o),n(a,i,o),r(a,i,o));},e.prototype.fillText=function(e,t,a,i){var o=this.da;this.S.fillText(e,n(t,a,o),r(t,a,o),C.zc(i)?i*o[0]:1e20);},e.prototype.setLineDash=function(e){e=a(e.slice(0),this.da),this.zb[this.zb.length-1]=e,this.S.setLineDash(e);},e;}),d=(e=!l.Te()||l.Re()||l.Mh()?1:7,{estimate:function(){function t(e){e.beginPath(),h.Ud(e,l);}var n=document.createElement("canvas");n.width=800,n.height=600;var r=n.getContext("2d"),a=n.width;n=n.height;var i,o=0,l=[{x:0,y:100}];for(i=1;6>=i;i++)o=2*i*Math.PI/6,l.push({x:100*Math.sin(o),y:100*Math.cos(o)});o=100/Object.keys(i={polygonPlainFill:[t,function(e){e.fillStyle="rgb(255, 0, 0)",e.fill();}],polygonPlainStroke:[t,function(e){e.strokeStyle="rgb(128, 0, 0)",e.lineWidth=2,e.closePath(),e.stroke();}],polygonGradientFill:[t,function(e){var t=e.createRadialGradient(0,0,10,0,0,60);t.addColorStop(0,"rgb(255, 0, 0)"),t.addColorStop(1,"rgb(255, 255, 0)"),e.fillStyle=t,e.fill();}],polygonGradientStroke:[t,function(e){var t=e.createLinearGradient(-100,-100,100,100);t.addColorStop(0,"rgb(224, 0, 0)"),t.addColorStop(1,"rgb(32, 0, 0)"),e.strokeStyle=t,e.lineWidth=2,e.closePath(),e.stroke();}],polygonExposureShadow:[t,function(e){e.shadowBlur=50,e.shadowColor="rgba(0, 0, 0, 1)",e.fillStyle="rgba(0, 0, 0, 1)",e.globalCompositeOperation="source-over",e.fill(),e.shadowBlur=0,e.shadowColor="transparent",e.globalCompositeOperation="destination-out",e.fill();}],labelPlainFill:[function(e){e.fillStyle="#000",e.font="24px sans-serif",e.textAlign="center";},function(e){e.fillText("Some text",0,-16),e.fillText("for testing purposes",0,16);}]}).length;var s,c=u.now(),f={};for(s in i){var d=i[s],p=u.now(),g=0;do{r.save(),r.translate(Math.random()*a,Math.random()*n);var v=3*Math.random()+.5;for(r.scale(v,v),v=0;v<d.length;v++)d[v](r);r.restore(),g++,v=u.now();}while(v-p<o)f[s]=e*(v-p)/g;}return f.total=u.now()-c,f;}}),h={Ud:function(e,t){var n=t[0];e.moveTo(n.x,n.y);for(var r=t.length-1;0<r;r--)n=t[r],e.lineTo(n.x,n.y);},Wi:function(e,t,n,r){var a,i=[],o=0,l=t.length;for(a=0;a<l;a++){var u=t[a],s=t[(a+1)%l];u=Math.sqrt(u=_.i(u,s)),i.push(u),o+=u;}n=r*(n+.5*r*o/l),o={};var c={},f={},d=0;for(a=0;a<l;a++){u=t[a],s=t[(a+1)%l],r=t[(a+2)%l];var h=i[(a+1)%l];h=Math.min(.5,n/h),_.ga(1-h,s,r,c),_.ga(h,s,r,f),d++,0==a&&(_.ga(Math.min(.5,n/i[0]),u,s,o),d++,e.moveTo(o.x,o.y)),e.quadraticCurveTo(s.x,s.y,c.x,c.y),e.lineTo(f.x,f.y);}return!0;}};function p(e){function t(){return"embedded"===a.getAttribute("data-foamtree");}function n(e){f[e]&&(f[e].style.opacity=h*d[e]);}function r(e){e.width=Math.round(o*e.B),e.height=Math.round(l*e.B);}var a,i,o,l,u,s,c=[],f={},d={},h=0;this.M=function(n){0!==(a=n).clientWidth&&0!==a.clientHeight||A.i("element has zero dimensions: "+a.clientWidth+" x "+a.clientHeight+"."),a.innerHTML="",o=a.clientWidth,l=a.clientHeight,u=0!==o?o:void 0,s=0!==l?l:void 0,t()&&A.i("visualization already embedded in the element."),a.setAttribute("data-foamtree","embedded"),(i=document.createElement("div")).style.width="100%",i.style.height="100%",i.style.position="relative",a.appendChild(i),e.j.D("stage:initialized",this,i,o,l);},this.Za=function(){t()&&(a.removeAttribute("data-foamtree"),c=[],f={},a.removeChild(i),e.j.D("stage:disposed",this,i));},this.u=function(){if(o=a.clientWidth,l=a.clientHeight,0!==o&&0!==l&&(o!==u||l!==s)){for(var t=c.length-1;0<=t;t--)r(c[t]);e.j.D("stage:resized",u,s,o,l),u=o,s=l;}},this.Ki=function(e,t){e.B=t,r(e);},this.dc=function(t,a,o){var l=document.createElement("canvas");return l.setAttribute("style","position: absolute; top: 0; bottom: 0; left: 0; right: 0; width: 100%; height: 100%; -webkit-touch-callout: none; -webkit-user-select: none; -moz-user-select: none; -ms-user-select: none; user-select: none;"),l.B=a,r(l),c.push(l),f[t]=l,d[t]=1,n(t),o||i.appendChild(l),e.j.D("stage:newLayer",t,l),l;},this.$b=function(e,t){return C.V(t)||(d[e]=t,n(e)),d[e];},this.i=function(e){return C.V(e)||(h=e,C.Aa(f,function(e,t){n(t);})),h;};}function g(e){function t(e,t,n){return m=!0,p.x=0,p.y=0,g.x=0,g.y=0,o=d,l.x=h.x,l.y=h.y,t(),u*=e,s=n?u/o:e,s=Math.max(.25/o,s),!0;}function n(e,t){return t.x=e.x/d+h.x,t.y=e.y/d+h.y,t;}function r(e,t,n,r,a,i,o,l,u){var s=(e-n)*(i-l)-(t-r)*(a-o);return!(1e-5>Math.abs(s))&&(u.x=((e*r-t*n)*(a-o)-(e-n)*(a*l-i*o))/s,u.y=((e*r-t*n)*(i-l)-(t-r)*(a*l-i*o))/s,!0);}var a,i,o=1,l={x:0,y:0},u=1,s=1,c=1,f={x:0,y:0},d=1,h={x:0,y:0},p={x:0,y:0},g={x:0,y:0},v={x:0,y:0,w:0,o:0},b={x:0,y:0,w:0,o:0,scale:1},m=!0;e.j.subscribe("stage:initialized",function(e,t,n,r){a=n,i=r,v.x=0,v.y=0,v.w=n,v.o=r,b.x=0,b.y=0,b.w=n,b.o=r,b.scale=1;}),e.j.subscribe("stage:resized",function(e,t,n,r){function o(e){e.x*=s,e.y*=c;}function u(e){o(e),e.w*=s,e.o*=c;}a=n,i=r;var s=n/e,c=r/t;o(l),o(h),o(f),o(p),o(g),u(v),u(b);}),this.Nb=function(e,r){return t(r,function(){n(e,f);},!0);},this.ga=function(e,n){if(1==Math.round(1e4*n)/1e4){n=v.x-h.x;var a=v.y-h.y;return t(1,function(){},!0),this.i(-n,-a);}return t(n,function(){for(var t=!1;!t;){t=Math.random();var n=Math.random(),a=Math.random(),i=Math.random();t=r(e.x+t*e.w,e.y+n*e.o,v.x+t*v.w,v.y+n*v.o,e.x+a*e.w,e.y+i*e.o,v.x+a*v.w,v.y+i*v.o,f);}},!0);},this.ic=function(e,n){var o=e.w/e.o,l=a/i;if(o<l){var u=e.o*l,s=e.o;o=e.x-.5*(u-e.w),l=e.y;}else o>l?(u=e.w,s=e.w*i/a,o=e.x,l=e.y-.5*(s-e.o)):(o=e.x,l=e.y,u=e.w,s=e.o);return(o-=u*n,l-=s*n,u*=1+2*n,r(o,l,h.x,h.y,o+u,l,h.x+a/d,h.y,f))?t(a/d/u,C.qa,!1):(m=!1,this.i(d*(h.x-o),d*(h.y-l)));},this.i=function(e,t){return e=Math.round(1e4*e)/1e4,t=Math.round(1e4*t)/1e4,g.x+=e/d,g.y+=t/d,0!==e||0!==t;},this.reset=function(e){return e&&this.content(0,0,a,i),this.ga({x:v.x+h.x,y:v.y+h.y,w:v.w/d,o:v.o/d},c/u);},this.Fb=function(e){c=Math.min(1,Math.round(1e4*(e||u))/1e4);},this.u=function(){return h.x<v.x?(v.x-h.x)*d:h.x+a/d>v.x+v.w?-(h.x+a/d-v.x-v.w)*d:0;},this.H=function(){return h.y<v.y?(v.y-h.y)*d:h.y+i/d>v.y+v.o?-(h.y+i/d-v.y-v.o)*d:0;},this.update=function(e){var t=Math.abs(Math.log(s));6>t?t=2:(t/=4,t+=3*t*(1<s?e:1-e)),t=1<s?Math.pow(e,t):1-Math.pow(1-e,t),d=o*(t=(m?t:1)*(s-1)+1),h.x=f.x-(f.x-l.x)/t,h.y=f.y-(f.y-l.y)/t,h.x-=p.x*(1-e)+g.x*e,h.y-=p.y*(1-e)+g.y*e,1===e&&(p.x=g.x,p.y=g.y),b.x=h.x,b.y=h.y,b.w=a/d,b.o=i/d,b.scale=d;},this.T=function(e){return e.x=b.x,e.y=b.y,e.scale=b.scale,e;},this.absolute=function(e,t){return n(e,t||{});},this.Uc=function(e,t){return(t=t||{}).x=(e.x-h.x)*d,t.y=(e.y-h.y)*d,t;},this.pc=function(e){return this.scale()<c/e;},this.zd=function(){return C.od(d,1);},this.scale=function(){return Math.round(1e4*d)/1e4;},this.content=function(e,t,n,r){v.x=e,v.y=t,v.w=n,v.o=r;},this.rc=function(e,t){var n;for(n=e.length-1;0<=n;n--){var r=e[n];r.save(),r.scale(d,d),r.translate(-h.x,-h.y);}for(t(b),n=e.length-1;0<=n;n--)(r=e[n]).restore();};}var v=new function(){function e(e){if("hsl"==e.model||"hsla"==e.model)return e;var t=e.r/=255,n=e.g/=255,r=e.b/=255,a=Math.max(t,n,r),i=Math.min(t,n,r),o=(a+i)/2;if(a==i)var l=i=0;else{var u=a-i;switch(i=.5<o?u/(2-a-i):u/(a+i),a){case t:l=(n-r)/u+(n<r?6:0);break;case n:l=(r-t)/u+2;break;case r:l=(t-n)/u+4;}l/=6;}return e.h=360*l,e.s=100*i,e.l=100*o,e.model="hsl",e;}var t={h:0,s:0,l:0,a:1,model:"hsla"};this.u=function(n){return C.Ac(n)?e(v.ga(n)):C.wb(n)?e(n):t;},this.ga=function(e){var n;return(n=/rgba\(\s*([^,\s]+)\s*,\s*([^,\s]+)\s*,\s*([^,\s]+)\s*,\s*([^,\s]+)\s*\)/.exec(e))&&5==n.length?{r:parseFloat(n[1]),g:parseFloat(n[2]),b:parseFloat(n[3]),a:parseFloat(n[4]),model:"rgba"}:(n=/hsla\(\s*([^,\s]+)\s*,\s*([^,%\s]+)%\s*,\s*([^,\s%]+)%\s*,\s*([^,\s]+)\s*\)/.exec(e))&&5==n.length?{h:parseFloat(n[1]),s:parseFloat(n[2]),l:parseFloat(n[3]),a:parseFloat(n[4]),model:"hsla"}:(n=/rgb\(\s*([^,\s]+)\s*,\s*([^,\s]+)\s*,\s*([^,\s]+)\s*\)/.exec(e))&&4==n.length?{r:parseFloat(n[1]),g:parseFloat(n[2]),b:parseFloat(n[3]),a:1,model:"rgb"}:(n=/hsl\(\s*([^,\s]+)\s*,\s*([^,\s%]+)%\s*,\s*([^,\s%]+)%\s*\)/.exec(e))&&4==n.length?{h:parseFloat(n[1]),s:parseFloat(n[2]),l:parseFloat(n[3]),a:1,model:"hsl"}:(n=/#([0-9a-fA-F]{2})([0-9a-fA-F]{2})([0-9a-fA-F]{2})/.exec(e))&&4==n.length?{r:parseInt(n[1],16),g:parseInt(n[2],16),b:parseInt(n[3],16),a:1,model:"rgb"}:(n=/#([0-9a-fA-F])([0-9a-fA-F])([0-9a-fA-F])/.exec(e))&&4==n.length?{r:17*parseInt(n[1],16),g:17*parseInt(n[2],16),b:17*parseInt(n[3],16),a:1,model:"rgb"}:t;},this.T=function(e){function t(e,t,n){return 0>n&&(n+=1),1<n&&--n,n<1/6?e+6*(t-e)*n:.5>n?t:n<2/3?e+(t-e)*(2/3-n)*6:e;}function n(e,t,n){return Math.sqrt(e*e*.241+t*t*.691+n*n*.068)/255;}if("rgb"==e.model||"rgba"==e.model)return n(e.r,e.g,e.b);var r=e.l/100,a=e.s/100,i=e.h/360;if(0==e.Cj)r=e=i=r;else{a=.5>r?r*(1+a):r+a-r*a;var o=2*r-a;r=t(o,a,i+1/3),e=t(o,a,i),i=t(o,a,i-1/3);}return n(255*r,255*e,255*i);},this.wa=function(e){if(C.Ac(e))return e;if(!C.wb(e))return"#000";switch(e.model){case"hsla":return v.sa(e);case"hsl":return v.H(e);case"rgba":return v.ua(e);case"rgb":return v.ta(e);default:return"#000";}},this.ua=function(e){return"rgba("+(.5+e.r|0)+","+(.5+e.g|0)+","+(.5+e.b|0)+","+e.a+")";},this.ta=function(e){return"rgba("+(.5+e.r|0)+","+(.5+e.g|0)+","+(.5+e.b|0)+")";},this.sa=function(e){return"hsla("+(.5+e.h|0)+","+(.5+e.s|0)+"%,"+(.5+e.l|0)+"%,"+e.a+")";},this.H=function(e){return"hsl("+(.5+e.h|0)+","+(.5+e.s|0)+"%,"+(.5+e.l|0)+"%)";},this.i=function(e,t,n){return"hsl("+(.5+e|0)+","+(.5+t|0)+"%,"+(.5+n|0)+"%)";};};function b(){var e,t=!1,n=[],r=this,a=new function(){this.then=function(a){return a&&(t?a.apply(r,e):n.push(a)),this;},this.Gg=function(e){return r=e,{then:this.then};};};this.resolve=function(){e=arguments;for(var a=0;a<n.length;a++)n[a].apply(r,e);return t=!0,this;},this.promise=function(){return a;};}function m(e){var t=new b,n=e.length;if(0<e.length)for(var r=e.length-1;0<=r;r--)e[r].then(function(){0==--n&&t.resolve();});else t.resolve();return t.promise();}function y(e){var t=0;this.i=function(){t++;},this.u=function(){0==--t&&e();},this.clear=function(){t=0;},this.initial=function(){return 0===t;};}var w={pe:function(e,t,n,r){r=r||{};try{var a=e.getBoundingClientRect();}catch(t){if(!w.Kh){w.Kh=!0,window.console.log("getBoundingClientRect() failed."),window.console.log("Element",e),a=window.console;for(var i=a.log;null!==e.parentElement;)e=e.parentElement;i.call(a,"Attached to DOM",e===document.body.parentElement);}a={left:0,top:0};}return r.x=t-a.left,r.y=n-a.top,r;}};function x(){var e=document,t={};this.addEventListener=function(n,r,a){var i=t[n];i||(i=[],t[n]=i),i.push(r),e.addEventListener(n,r,a);},this.i=function(){C.Aa(t,function(t,n){for(var r=t.length-1;0<=r;r--)e.removeEventListener(n,t[r]);});};}function S(e){function t(e){return function(t){n(t)&&e.apply(this,arguments);};}function n(t){for(t=t.target;t;){if(t===e)return!0;t=t.parentElement;}return!1;}function r(e,t,n){a(e,n=n||{});for(var r=0;r<t.length;r++)t[r].call(e.target,n);return a(e,n),(void 0===n.Db&&n.ci||"prevent"===n.Db)&&e.preventDefault(),n;}function a(t,n){return w.pe(e,t.clientX,t.clientY,n),n.altKey=t.altKey,n.metaKey=t.metaKey,n.ctrlKey=t.ctrlKey,n.shiftKey=t.shiftKey,n.lb=3===t.which,n;}var i=new x,o=[],l=[],u=[],s=[],c=[],f=[],d=[],h=[],p=[],g=[],v=[];this.i=function(e){o.push(e);},this.u=function(e){c.push(e);},this.sa=function(e){l.push(e);},this.wa=function(e){u.push(e);},this.Ka=function(e){s.push(e);},this.ua=function(e){v.push(e);},this.ta=function(e){f.push(e);},this.Ja=function(e){d.push(e);},this.ga=function(e){h.push(e);},this.H=function(e){p.push(e);},this.T=function(e){g.push(e);},this.Za=function(){i.i();};var b,m,y,S,k={x:0,y:0},C={x:0,y:0},T=!1,z=!1;i.addEventListener("mousedown",t(function(t){if(t.target!==e){var n=r(t,u);C.x=n.x,C.y=n.y,k.x=n.x,k.y=n.y,T=!0,r(t,h),m=!1,b=window.setTimeout(function(){100>_.i(k,n)&&(window.clearTimeout(S),r(t,l),m=!0);},400);}})),i.addEventListener("mouseup",function(e){var t=r(e,s);T&&(z&&r(e,g),window.clearTimeout(b),m||z||!n(e)||(t={x:t.x,y:t.y},y&&100>_.i(t,y)?r(e,c):r(e,o),y=t,S=window.setTimeout(function(){y=null;},350)),z=T=!1);}),i.addEventListener("mousemove",function(e){var t=a(e,{});n(e)&&r(e,f,{type:"move"}),k.x=t.x,k.y=t.y,T&&!z&&100<_.i(C,k)&&(z=!0),z&&r(e,p,t);}),i.addEventListener("mouseout",t(function(e){r(e,d,{type:"out"});})),i.addEventListener("wheel",t(function(e){if("deltaY"in e)var t=e.deltaY;else t=0,"detail"in e&&(t=e.detail),"wheelDelta"in e&&(t=-e.wheelDelta/120),"wheelDeltaY"in e&&(t=-e.wheelDeltaY/120),"axis"in e&&e.axis===e.HORIZONTAL_AXIS&&(t=0),t*=10;t&&e.deltaMode&&(t=1===e.deltaMode?67*t:800*t),r(e,v,{ed:-t/200,ci:!0});}),{passive:!1}),i.addEventListener("contextmenu",t(function(e){e.preventDefault();}));}var k=function(){function e(e){return function(t){return Math.pow(t,e);};}function t(e){return function(t){return 1-Math.pow(1-t,e);};}function n(e){return function(t){return 1>(t*=2)?.5*Math.pow(t,e):1-.5*Math.abs(Math.pow(2-t,e));};}function r(e){return function(t){for(var n=0;n<e.length;n++)t=(0,e[n])(t);return t;};}return{ia:function(e){switch(e){case"linear":default:return k.Ab;case"bounce":return k.ug;case"squareIn":return k.Uf;case"squareOut":return k.Gb;case"squareInOut":return k.Vf;case"cubicIn":return k.xg;case"cubicOut":return k.fe;case"cubicInOut":return k.yg;case"quadIn":return k.vi;case"quadOut":return k.xi;case"quadInOut":return k.wi;}},Ab:function(e){return e;},ug:r([n(2),function(e){return 0===e?0:1===e?1:e*(e*(e*(e*(25.9425*e-85.88)+105.78)-58.69)+13.8475);}]),Uf:e(2),Gb:t(2),Vf:n(2),xg:e(3),fe:t(3),yg:n(3),vi:e(2),xi:t(2),wi:n(2),rj:r};}(),C={V:function(e){return void 0===e;},Se:function(e){return null===e;},zc:function(e){return"[object Number]"===Object.prototype.toString.call(e);},Ac:function(e){return"[object String]"===Object.prototype.toString.call(e);},Qe:function(e){return"function"==typeof e;},wb:function(e){return e===Object(e);},od:function(e,t){return 1e-6>e-t&&-.000001<e-t;},Oe:function(e){return C.V(e)||C.Se(e)||C.Ac(e)&&!/\S/.test(e);},has:function(e,t){return e&&e.hasOwnProperty(t);},bb:function(e,t){if(e){for(var n=t.length-1;0<=n;n--)if(e.hasOwnProperty(t[n]))return!0;}return!1;},extend:function(e){return C.Cg(Array.prototype.slice.call(arguments,1),function(t){if(t)for(var n in t)t.hasOwnProperty(n)&&(e[n]=t[n]);}),e;},Aj:function(e,t){return e.map(function(e){return e[t];},[]);},Cg:function(e,t,n){null!=e&&(e.forEach?e.forEach(t,n):C.Aa(e,t,n));},Aa:function(e,t,n){for(var r in e)if(e.hasOwnProperty(r)&&!1===t.call(n,e[r],r,e))break;},I:function(){for(var e=0;e<arguments.length;e++){var t=arguments[e];if(!(C.V(t)||C.zc(t)&&isNaN(t)||C.Ac(t)&&C.Oe(t)))return t;}},Jf:function(e,t){0<=(t=e.indexOf(t))&&e.splice(t,1);},zg:function(e,t,n){var r;return function(){var a=this,i=arguments,o=n&&!r;clearTimeout(r),r=setTimeout(function(){r=null,n||e.apply(a,i);},t),o&&e.apply(a,i);};},defer:function(e){setTimeout(e,1);},yj:function(e){return e;},qa:function(){}},T={A:function(e,t){function n(){var n=[];if(Array.isArray(e))for(var r=0;r<e.length;r++){var a=e[r];a&&n.push(a.apply(t,arguments));}else e&&n.push(e.apply(t,arguments));return n;}return n.empty=function(){return 0===e.length&&!C.Qe(e);},n;}};function z(){var e={};this.subscribe=function(t,n){var r=e[t];r||(r=[],e[t]=r),r.push(n);},this.D=function(t,n){var r=e[t];if(r)for(var a=Array.prototype.slice.call(arguments,1),i=0;i<r.length;i++)r[i].apply(this,a);};}var M={Qf:function(e){for(var t="",n=0;n<e.length;n++)t+=String.fromCharCode(1^e.charCodeAt(n));return t;}};function P(e){function t(t,l,u){var s,c=this,f=0;this.id=a++,this.name=u||"{unnamed on "+t+"}",this.target=function(){return t;},this.xb=function(){return -1!=o.indexOf(c);},this.start=function(){if(!c.xb()){if(-1==o.indexOf(c)){var t=i.now();!0===c.bf(t)&&(o=o.slice()).push(c);}0<o.length&&e.repeat(n);}return this;},this.stop=function(){for(r(c);s<l.length;s++){var e=l[s];e.Xa&&e.gb.call();}return this;},this.bf=function(e){if(f++,0!==l.length){if(C.V(s)){var t=l[s=0];t.before&&t.before.call(t,e,f,c);}else t=l[s];for(;s<l.length;){if(t.gb&&t.gb.call(t,e,f,c))return!0;t.after&&t.after.call(t,e,f,c),C.V(s)&&(s=-1),++s<l.length&&(t=l[s]).before&&t.before.call(t,e,f,c);}}return!1;};}function n(){var t;t=i.now(),o.forEach(function(e){!0!==e.bf(t)&&r(e);}),0==o.length&&e.cancel(n);}function r(e){o=o.filter(function(t){return t!==e;});}var a=0,i=u.create(),o=[];this.i=function(){for(var e=o.length-1;0<=e;e--)o[e].stop();o=[];},this.K=function(){function e(){}function n(e){function t(e){return C.Qe(e)?e.call(void 0):e;}var n,r,a=e.target,o=e.duration,l=e.ba;this.before=function(){for(var o in n={},e.P)a.hasOwnProperty(o)&&(n[o]={start:C.V(e.P[o].start)?a[o]:t(e.P[o].start),end:C.V(e.P[o].end)?a[o]:t(e.P[o].end),easing:C.V(e.P[o].easing)?k.Ab:e.P[o].easing});r=i.now();},this.gb=function(){var e=i.now()-r;for(var t in e=0===o?1:Math.min(o,e)/o,n){var u=n[t];a[t]=u.start+(u.end-u.start)*u.easing(e);}return l&&l.call(a,e),1>e;};}function r(e,t,n){this.Xa=n,this.gb=function(){return e.call(t),!1;};}function a(e){var t;this.before=function(n,r){t=r+e;},this.gb=function(e,n){return n<t;};}function l(e){var t;this.before=function(n){t=n+e;},this.gb=function(e){return e<t;};}function u(e){this.before=function(){e.forEach(function(e){e.start();});},this.gb=function(){for(var t=0;t<e.length;t++)if(e[t].xb())return!0;return!1;};}return e.A=function(e,i){return new function(){function o(t,n,a,i){return n?(C.V(a)&&(a=e),t.Mb(new r(n,a,i))):t;}var s=[];this.Mb=function(e){return s.push(e),this;},this.wait=function(e){return this.Mb(new l(e));},this.Xd=function(e){return this.Mb(new a(e||1));},this.call=function(e,t){return o(this,e,t,!1);},this.Xa=function(e,t){return o(this,e,t,!0);},this.fa=function(t){return C.V(t.target)&&(t.target=e),this.Mb(new n(t));},this.Qa=function(e){return this.Mb(new u(e));},this.done=function(){return new t(e,s,i);},this.start=function(){return this.done().start();},this.i=function(){var e=new b;return this.Xd().call(e.resolve).done(),e.promise();},this.Ta=function(){var e=this.i();return this.start(),e;};};},e.jc=function(t){return(C.V(t)?o.slice():o.filter(function(e){return e.target()===t;})).forEach(function(e){e.stop();}),e.A(t,void 0);},e;}();}var E=((t={oe:function(e,t){if(e.m){e=e.m;for(var n=0;n<e.length;n++)t(e[n],n);}},sc:function(e,n){if(e.m){e=e.m;for(var r=0;r<e.length;r++)if(!1===t.sc(e[r],n)||!1===n(e[r],r))return!1;}}}).L=t.sc,t.tc=function(e,n){if(e.m){e=e.m;for(var r=0;r<e.length;r++)if(!1===n(e[r],r)||!1===t.tc(e[r],n))return!1;}},t.za=function(e,n){if(e.m){for(var r=e.m,a=0;a<r.length;a++)if(!1===t.za(r[a],n))return!1;}return n(e);},t.sj=t.za,t.fd=function(e,n){!1!==n(e)&&t.tc(e,n);},t.uc=function(e,n){var r=[];return t.tc(e,function(e){r.push(e);}),n?r.filter(n):r;},t.ne=function(e,t){for(e=e.parent;e&&!1!==t(e);)e=e.parent;},t.Lh=function(e,t){for(e=e.parent;e&&e!==t;)e=e.parent;return!!e;},t),_=new function(){function e(e,t){var n=e.x-t.x;return n*n+(e=e.y-t.y)*e;}function t(e,t,n){for(var r=0;r<e.length;r++){var a=_.T(e[r],e[r+1]||e[0],t,n,!0);if(a)return a;}}return this.T=function(e,t,n,r,a){var i=e.x;e=e.y;var o=t.x-i;t=t.y-e;var l=n.x,u=n.y;n=r.x-l;var s=r.y-u;if(!(1e-12>=(r=o*s-n*t)&&-.000000000001<=r)&&(l-=i,u-=e,n=(l*s-n*u)/r,0<=(r=(l*t-o*u)/r)&&(a||1>=r)&&0<=n&&1>=n))return{x:i+o*n,y:e+t*n};},this.Lb=function(e,t,n,r){var a=e.x;e=e.y;var i=t.x-a;t=t.y-e;var o=n.x;n=n.y;var l=r.x-o,u=i*(r=r.y-n)-l*t;if(!(1e-12>=u&&-.000000000001<=u)&&0<=(r=((o-a)*r-l*(n-e))/u)&&1>=r)return{x:a+i*r,y:e+t*r};},this.wa=function(e,n,r){var a=_.u(n,{}),i=_.u(r,{}),o=i.x-a.x,l=i.y-a.y,u=[];for(i=0;i<r.length;i++){var s=r[i];u.push({x:s.x-o,y:s.y-l});}for(i=0,r=[],s=[];i<e.length;i++){var c=e[i],f=t(n,a,c);f?(r.push(f),s.push(t(u,a,c))):(r.push(null),s.push(null));}for(i=0;i<e.length;i++)if(f=r[i],c=s[i],f&&c){n=e[i],u=a;var d=f.x-a.x;if(1e-12<(f=Math.sqrt(d*d+(f=f.y-a.y)*f))){d=n.x-a.x;var h=n.y-a.y;f=Math.sqrt(d*d+h*h)/f,n.x=u.x+f*(c.x-u.x),n.y=u.y+f*(c.y-u.y);}else n.x=u.x,n.y=u.y;}for(i=0;i<e.length;i++)s=e[i],s.x+=o,s.y+=l;},this.F=function(e,t){if(0!==e.length){for(var n,r,a=n=e[0].x,i=r=e[0].y,o=e.length;0<--o;)a=Math.min(a,e[o].x),n=Math.max(n,e[o].x),i=Math.min(i,e[o].y),r=Math.max(r,e[o].y);return t.x=a,t.y=i,t.w=n-a,t.o=r-i,t;}},this.H=function(e){return[{x:e.x,y:e.y},{x:e.x+e.w,y:e.y},{x:e.x+e.w,y:e.y+e.o},{x:e.x,y:e.y+e.o}];},this.u=function(e,t){for(var n=0,r=0,a=e.length,i=e[0],o=0,l=1;l<a-1;l++){var u=e[l],s=e[l+1],c=i.y+u.y+s.y,f=(u.x-i.x)*(s.y-i.y)-(s.x-i.x)*(u.y-i.y);n+=f*(i.x+u.x+s.x),r+=f*c,o+=f;}return t.x=n/(3*o),t.y=r/(3*o),t.ha=o/2,t;},this.Ja=function(e,t){this.u(e,t),t.r=Math.sqrt(t.ha/Math.PI);},this.sa=function(e,t){for(var n=0;n<e.length;n++){var r=e[n],a=e[n+1]||e[0];if(0>(t.y-r.y)*(a.x-r.x)-(t.x-r.x)*(a.y-r.y))return!1;}return!0;},this.Vc=function(e,t,n){var r=e.x,a=t.x;if(e.x>t.x&&(r=t.x,a=e.x),a>n.x+n.w&&(a=n.x+n.w),r<n.x&&(r=n.x),r>a)return!1;var i=e.y,o=t.y,l=t.x-e.x;return 1e-7<Math.abs(l)&&(o=(t.y-e.y)/l,e=e.y-o*e.x,i=o*r+e,o=o*a+e),i>o&&(r=o,o=i,i=r),o>n.y+n.o&&(o=n.y+n.o),i<n.y&&(i=n.y),i<=o;},this.Ka=function(n,r,a,i,o){function l(a,i,o){if(r.x===f.x&&r.y===f.y)return o;var l=t(n,r,f),d=Math.sqrt(e(l,r)/(a*a+i*i));return d<s?(s=d,u=l.x,c=l.y,0!==i?Math.abs(c-r.y)/Math.abs(i):Math.abs(u-r.x)/Math.abs(a)):o;}i=C.I(i,.5),o=C.I(o,.5),a=C.I(a,1);var u,s=Number.MAX_VALUE,c=u=0,f={x:0,y:0},d=i*a;a=(1-i)*a,i=1-o,f.x=r.x-d,f.y=r.y-o;var h=l(d,o,h);return f.x=r.x+a,f.y=r.y-o,h=l(a,o,h),f.x=r.x-d,f.y=r.y+i,h=l(d,i,h),f.x=r.x+a,f.y=r.y+i,l(a,i,h);},this.pb=function(e,t){function n(e,t,n){var r=t.x,a=n.x;t=t.y;var i=a-r,o=(n=n.y)-t;return Math.abs(o*e.x-i*e.y-r*n+a*t)/Math.sqrt(i*i+o*o);}for(var r=e.length,a=n(t,e[r-1],e[0]),i=0;i<r-1;i++){var o=n(t,e[i],e[i+1]);o<a&&(a=o);}return a;},this.ua=function(e,t,n){n={x:t.x+Math.cos(n),y:t.y-Math.sin(n)};var r,a=[],i=[],o=e.length;for(r=0;r<o;r++){var l=_.Lb(e[r],e[(r+1)%o],t,n);if(l&&(a.push(l),2==i.push(r)))break;}if(2==a.length){l=a[0],a=a[1];var u=i[0];i=i[1];var s=[a,l];for(r=u+1;r<=i;r++)s.push(e[r]);for(r=[l,a];i!=u;)i=(i+1)%o,r.push(e[i]);return e=[s,r],o=n.x-t.x,r=a.x-l.x,0===o&&(o=n.y-t.y,r=a.y-l.y),(0>o?-1:0<o?1:0)!=(0>r?-1:0<r?1:0)&&e.reverse(),e;}},this.ga=function(e,t,n,r){return r.x=e*(t.x-n.x)+n.x,r.y=e*(t.y-n.y)+n.y,r;},this.i=e,this.ta=function(e,n,r){if(C.zc(n))var a=2*Math.PI*n/360;else switch(a=_.F(e,{}),n){case"random":a=Math.random()*Math.PI*2;break;case"top":a=Math.atan2(-a.o,0);break;case"bottom":a=Math.atan2(a.o,0);break;case"left":a=Math.atan2(0,-a.w);break;case"right":a=Math.atan2(0,a.w);break;case"topleft":a=Math.atan2(-a.o,-a.w);break;case"topright":a=Math.atan2(-a.o,a.w);break;case"bottomleft":a=Math.atan2(a.o,-a.w);break;default:a=Math.atan2(a.o,a.w);}return n=_.u(e,{}),e=t(e,n,{x:n.x+Math.cos(a),y:n.y+Math.sin(a)}),_.ga(r,e,n,{});},this;},L=new function(){function e(e,t){this.face=e,this.Rc=t,this.ec=this.Lc=null;}function t(e,t,n){this.la=[e,t,n],this.J=[,,,];var r=t.y-e.y,a=n.z-e.z,i=t.x-e.x;t=t.z-e.z;var o=n.x-e.x;e=n.y-e.y,this.Ha={x:r*a-t*e,y:t*o-i*a,z:i*e-r*o},this.Ya=[],this.ad=this.visible=!1;}this.i=function(r){var i,o,l=r.length;for(i=0;i<l;i++)r[i].index=i,r[i].Pb=null;var u,s=[];if(0<(u=function(){function n(e,n,r,a){var i={x:n.x-e.x,y:n.y-e.y,z:n.z-e.z},o={x:r.x-e.x,y:r.y-e.y,z:r.z-e.z},l=i.y*o.z-i.z*o.y,u=i.z*o.x-i.x*o.z;return i=i.x*o.y-i.y*o.x,l*a.x+u*a.y+i*a.z>l*e.x+u*e.y+i*e.z?new t(e,n,r):new t(r,n,e);}function a(e,t,n,r){function a(e,t,n){return t=(e=e.la)[0]==t?0:e[1]==t?1:2,e[(t+1)%3]!=n?(t+2)%3:t;}t.J[a(t,n,r)]=e,e.J[a(e,r,n)]=t;}if(4>l)return 0;var i=r[0],o=r[1],u=r[2],c=r[3],f=n(i,o,u,c),d=n(i,u,c,o),h=n(i,o,c,u),p=n(o,u,c,i);for(a(f,d,u,i),a(f,h,i,o),a(f,p,o,u),a(d,h,c,i),a(d,p,u,c),a(h,p,c,o),s.push(f,d,h,p),i=4;i<l;i++)for(o=r[i],u=0;4>u;u++)f=(c=s[u]).la[0],0>(d=c.Ha).x*(o.x-f.x)+d.y*(o.y-f.y)+d.z*(o.z-f.z)&&e.add(c,o);return 4;}())){for(;u<l;){var c=r[u];if(c.Pb){for(i=c.Pb;null!==i;)i.face.visible=!0,i=i.ec;i=0;e:for(;i<s.length;i++){var f=s[i];if(!1===f.visible){var d=f.J;for(o=0;3>o;o++)if(!0===d[o].visible){var h=f,p=o;break e;}}}f=[],d=[];var g=h,v=p;do if(f.push(g),d.push(v),v=(v+1)%3,!1===g.J[v].visible)do for(i=g.la[v],g=g.J[v],o=0;3>o;o++)g.la[o]==i&&(v=o);while(!1===g.J[v].visible&&(g!==h||v!==p))while(g!==h||v!==p)var b=null,m=null;for(i=0;i<f.length;i++){g=f[i],v=d[i];var y=g.J[v],w=g.la[(v+1)%3],x=g.la[v],S=w.y-c.y,k=x.z-c.z,C=w.x-c.x,T=w.z-c.z,z=x.x-c.x,M=x.y-c.y;if(0<a.length){var P=a.pop();P.la[0]=c,P.la[1]=w,P.la[2]=x,P.Ha.x=S*k-T*M,P.Ha.y=T*z-C*k,P.Ha.z=C*M-S*z,P.Ya.length=0,P.visible=!1,P.ad=!0;}else P={la:[c,w,x],J:[,,,],Ha:{x:S*k-T*M,y:T*z-C*k,z:C*M-S*z},Ya:[],visible:!1};s.push(P),g.J[v]=P,P.J[1]=g,null!==m&&(m.J[0]=P,P.J[2]=m),m=P,null===b&&(b=P),function(t,n,r){var a=t.la[0],i=t.Ha,u=i.x,s=i.y;i=i.z;var c=Array(l),f=(n=n.Ya).length;for(o=0;o<f;o++){var d=n[o].Rc;c[d.index]=!0,0>u*(d.x-a.x)+s*(d.y-a.y)+i*(d.z-a.z)&&e.add(t,d);}for(o=0,f=(n=r.Ya).length;o<f;o++)!0!==c[(d=n[o].Rc).index]&&0>u*(d.x-a.x)+s*(d.y-a.y)+i*(d.z-a.z)&&e.add(t,d);}(P,g,y);}for(o=0,m.J[0]=b,b.J[2]=m,i=[];o<s.length;o++)if(!0===(f=s[o]).visible){for(c=0,g=(d=f.Ya).length;c<g;c++)b=(v=d[c]).Lc,m=v.ec,null!==b&&(b.ec=m),null!==m&&(m.Lc=b),null===b&&(v.Rc.Pb=m),n.push(v);f.ad&&a.push(f);}else i.push(f);s=i;}u++;}for(i=0;i<s.length;i++)(f=s[i]).ad&&a.push(f);}return{qe:s};},e.add=function(t,r){if(0<n.length){var a=n.pop();a.face=t,a.Rc=r,a.ec=null,a.Lc=null;}else a=new e(t,r);t.Ya.push(a),null!==(t=r.Pb)&&(t.Lc=a),a.ec=t,r.Pb=a;};for(var n=Array(2e3),r=0;r<n.length;r++)n[r]=new e(null,null);var a=Array(1e3);for(r=0;r<a.length;r++)a[r]={la:[,,,],J:[,,,],Ha:{x:0,y:0,z:0},Ya:[],visible:!1};},D=new function(){function e(e,t,n,r,a,i,o,l){var u=(e-n)*(i-l)-(t-r)*(a-o);if(!(1e-12>Math.abs(u)))return{x:((e*r-t*n)*(a-o)-(e-n)*(a*l-i*o))/u,y:((e*r-t*n)*(i-l)-(t-r)*(a*l-i*o))/u};}return this.i=function(t,n){for(var r=t[0],a=r.x,i=r.y,o=r.x,l=r.y,u=t.length-1;0<u;u--)a=Math.min(a,(r=t[u]).x),i=Math.min(i,r.y),o=Math.max(o,r.x),l=Math.max(l,r.y);if(!(o-a<3*n||l-i<3*n)){e:{for(void 0==(r=!0)&&(r=!1),a=[],i=t.length,o=0;o<=i;o++){l=t[o%i],u=t[(o+1)%i];var s=t[(o+2)%i],c=u.x-l.x,f=u.y-l.y,d=Math.sqrt(c*c+f*f),h=n*c/d,p=n*f/d;if(d=Math.sqrt((c=s.x-u.x)*c+(f=s.y-u.y)*f),c=n*c/d,f=n*f/d,(l=e(l.x-p,l.y+h,u.x-p,u.y+h,u.x-f,u.y+c,s.x-f,s.y+c))&&(a.push(l),s=a.length,r&&3<=s&&(l=a[s-3],u=a[s-2],s=a[s-1],0>(u.x-l.x)*(s.y-l.y)-(s.x-l.x)*(u.y-l.y)))){r=void 0;break e;}}a.shift(),r=3>a.length?void 0:a;}if(!r)e:{for(r=0,a=t.slice(0);r<t.length;r++){if(o=t[r%t.length],l=Math.sqrt((s=(u=t[(r+1)%t.length]).x-o.x)*s+(i=u.y-o.y)*i),s=n*s/l,l=n*i/l,i=o.x-l,o=o.y+s,l=u.x-l,s=u.y+s,0!=a.length){for(u=0,p=i-l,f=o-s,h=[],c=d=!0;u<a.length;u++){var g=p*(o-a[u].y)-(i-a[u].x)*f;1e-12>=g&&-.000000000001<=g&&(g=0),h.push(g),0<g&&(d=!1),0>g&&(c=!1);}if(d)a=[];else if(!c){for(u=0,p=[];u<a.length;u++)f=(u+1)%a.length,d=h[u],c=h[f],0<=d&&p.push(a[u]),(0<d&&0>c||0>d&&0<c)&&p.push(e(a[u].x,a[u].y,a[f].x,a[f].y,i,o,l,s));a=p;}}if(3>a.length){r=void 0;break e;}}r=a;}return r;}},this;},F=new function(){function e(e){for(var t=e[0].x,n=e[0].y,r=t,a=n,i=1;i<e.length;i++){var o=e[i];t=Math.min(t,o.x),n=Math.min(n,o.y),r=Math.max(r,o.x),a=Math.max(a,o.y);}return e=r-t,a-=n,[{x:t+2*e,y:n+2*a,w:0},{x:t+2*e,y:n-2*a,w:0},{x:t-2*e,y:n+2*a,w:0}];}this.i=function(t,n){if(1===t.length)t[0].C=n.slice(0),t[0].J=[];else{var r,a=e(n),i=[];for(r=0;r<a.length;r++){var o=a[r];i.push({x:o.x,y:o.y,z:o.x*o.x+o.y*o.y-o.w});}for(r=0;r<t.length;r++)(o=t[r]).C=null,i.push({x:o.x,y:o.y,z:o.x*o.x+o.y*o.y-o.w});var l=L.i(i).qe;for(function(){for(r=0;r<l.length;r++){var e=l[r],t=e.la,n=t[0],a=t[1],i=t[2];t=n.x;var o=n.y;n=n.z;var u=a.x,s=a.y;a=a.z;var c=i.x,f=i.y;i=i.z;var d=t*(s-f)+u*(f-o)+c*(o-s);e.ea={x:-(o*(a-i)+s*(i-n)+f*(n-a))/d/2,y:-(n*(u-c)+a*(c-t)+i*(t-u))/d/2};}}(),function(e){for(r=0;r<l.length;r++){var t=l[r];t.jb=!_.sa(e,t.ea);}}(n),i=function(e,t){var n,r=Array(t.length);for(n=0;n<r.length;n++)r[n]=[];for(n=0;n<e.length;n++){var a=e[n];if(!(0>a.Ha.z))for(var i=a.J,o=0;o<i.length;o++){var l=i[o];if(!(0>l.Ha.z)){var u=a.la,s=u[(o+1)%3].index;u=u[o].index,2<s&&r[s-3].push([a,l,2<u?t[u-3]:null]);}}}return r;}(l,t),r=0;r<t.length;r++)if(0!==(o=i[r]).length){var u=t[r],s=(o=function(e){var t=[e[0]],n=e[0][0],r=e[0][1],a=e.length,i=1;e:for(;i<a;i++)for(var o=1;o<a;o++){var l=e[o];if(null!==l){if(l[1]===n){if(t.unshift(l),n=l[0],e[o]=null,t.length!==a)continue;break e;}if(l[0]===r&&(t.push(l),r=l[1],e[o]=null,t.length===a))break e;}}return t[0][0]!=t[a-1][1]&&t.push([t[a-1][1],t[0][0]]),t;}(o)).length,c=-1;for(a=0;a<s;a++)o[a][0].jb&&(c=a);if(0<=c)!function(e,t,n,r){var a,i,o=[],l=[],u=n.length,s=t.length,c=0,f=-1,d=-1,h=r;for(r=0;r<u;r++){var p=(h+1)%u,g=n[h][0],v=n[p][0];if(1e-12<_.i(g.ea,v.ea)){if(g.jb&&v.jb){var b=[],m=[];for(a=0;a<s;a++){var y=(c+1)%s;if((i=_.T(t[c],t[y],g.ea,v.ea,!1))&&(m.push(c),2===b.push(i)))break;c=y;}if(2===b.length){if(a=b[1],g=(i=_.i(g.ea,b[0]))<(a=_.i(g.ea,a))?0:1,i=i<a?1:0,a=m[g],-1===f&&(f=a),-1!==d)for(;a!=d;)d=(d+1)%s,o.push(t[d]),l.push(null);o.push(b[g],b[i]),l.push(n[h][2],null),d=m[i];}}else if(g.jb&&!v.jb)for(a=0;a<s;a++){if(y=(c+1)%s,i=_.T(t[c],t[y],g.ea,v.ea,!1)){if(-1!==d)for(b=d;c!=b;)b=(b+1)%s,o.push(t[b]),l.push(null);o.push(i),l.push(n[h][2]),-1===f&&(f=c);break;}c=y;}else if(!g.jb&&v.jb)for(a=0;a<s;a++){if(y=(c+1)%s,i=_.T(t[c],t[y],g.ea,v.ea,!1)){o.push(g.ea,i),l.push(n[h][2],null),d=c;break;}c=y;}else o.push(g.ea),l.push(n[h][2]);}h=p;}if(0==o.length)l=o=null;else if(-1!==d)for(;f!=d;)d=(d+1)%s,o.push(t[d]),l.push(null);e.C=o,e.J=l;}(u,n,o,c);else{c=[];var f=[];for(a=0;a<s;a++)1e-12<_.i(o[a][0].ea,o[(a+1)%s][0].ea)&&(c.push(o[a][0].ea),f.push(o[a][2]));u.C=c,u.J=f;}u.C&&3>u.C.length&&(u.C=null,u.J=null);}}},this.u=function(t,n){var r,a=!1,i=t.length;for(r=0;r<i;r++){var o=t[r];null===o.C&&(a=!0),o.Yd=o.w;}if(a){a=e(n);var l=[];for(o=0,r=t.length;o<a.length;o++){var u=a[o];l.push({x:u.x,y:u.y,z:u.x*u.x+u.y*u.y});}for(o=0;o<r;o++)u=t[o],l.push({x:u.x,y:u.y,z:u.x*u.x+u.y*u.y});for(o=0,u=L.i(l).qe,a=Array(r);o<r;o++)a[o]={};for(o=0,l=u.length;o<l;o++){var s=u[o];if(0<s.Ha.z){var c=s.la,f=c.length;for(s=0;s<f-1;s++){var d=c[s].index-3,h=c[s+1].index-3;0<=d&&0<=h&&(a[d][h]=!0,a[h][d]=!0);}s=c[0].index-3,0<=h&&0<=s&&(a[h][s]=!0,a[s][h]=!0);}}for(o=0;o<r;o++){for(var p in s=a[o],u=t[o],h=Number.MAX_VALUE,l=null,s)s=t[p],h>(c=_.i(u,s))&&(h=c,l=s);u.zj=l,u.$e=Math.sqrt(h);}for(r=0;r<i;r++)p=Math.min(Math.sqrt((o=t[r]).w),.95*o.$e),o.w=p*p;for(this.i(t,n),r=0;r<i;r++)(o=t[r]).Yd!==o.w&&0<o.kc&&(n=Math.min(o.kc,o.Yd-o.w),o.w+=n,o.kc-=n);}};},j=new function(){this.H=function(e){e=e.m;for(var t=0,n=e.length,r=0;r<n;r++){var a=e[r];if(a.C){var i=a.x,o=a.y;_.u(a.C,a),i-=a.x,t<(a=(0<i?i:-i)+(0<(a=o-a.y)?a:-a))&&(t=a);}}return t;},this.i=function(e,t){var n=e.m;switch(t){case"random":return e.m[Math.floor(n.length*Math.random())];case"topleft":var r=(e=n[0]).x+e.y;for(t=1;t<n.length;t++){var a=n[t],i=a.x+a.y;i<r&&(r=i,e=a);}return e;case"bottomright":for(t=1,r=(e=n[0]).x+e.y;t<n.length;t++)(i=(a=n[t]).x+a.y)>r&&(r=i,e=a);return e;default:for(a=n[0],r=i=_.i(e,a),t=n.length-1;1<=t;t--){var o=n[t];(i=_.i(e,o))<r&&(r=i,a=o);}return a;}},this.u=function(e,t,n){var r=e.m;if(r[0].J){var a,i=r.length;for(e=0;e<i;e++)r[e].Sc=!1,r[e].Zb=0;var o=a=0;for((i=[])[a++]=t||r[0],t=t.Zb=0;o<a;)if(!(r=i[o++]).Sc&&r.J){n(r,t++,r.Zb),r.Sc=!0;var l=r.J,u=l.length;for(e=0;e<u;e++){var s=l[e];s&&!0!==s.Sc&&(0===s.Zb&&(s.Zb=r.Zb+1),i[a++]=s);}}}else for(e=0;e<r.length;e++)n(r[e],e,1);};},N=function(){function e(e,l,u,s,c,h,p,g){var v,b,m=C.extend({},i,e);1>e.lineHeight&&(e.lineHeight=1),e=m.fontFamily;var y=m.fontStyle+" "+m.fontVariant+" "+m.fontWeight,w=m.hb,x=m.Gc,S=y+" "+e;m.ue=S;var k={ka:!1,bc:0,fontSize:0};if(l.save(),l.font=y+" 100px "+e,l.textBaseline="middle",l.textAlign="center",void 0===(b=o[v=(v=m).ue])&&(b={},o[v]=b),b[" "]=l.measureText(" ").width,b["\u2026"]=l.measureText("\u2026").width,u=u.trim(),f.text=u,function(e,t,n,r){for(var a,i,o=0;o<e.length;o++)e[o].y===t.y&&(void 0===a?a=o:i=o);void 0===i&&(i=a),a!==i&&e[i].x<e[a].x&&(o=a,a=i,i=o),r.C=e,r.F=t,r.cd=n,r.Ye=a,r.Ze=i;}(s,c,h,d),/[\u3000-\u303f\u3040-\u309f\u30a0-\u30ff\uff00-\uff9f\u4e00-\u9faf\u3400-\u4dbf]/.test(u)?(n(f),t(l,f,S),r(m,f,d,x,w,!0,k)):(t(l,f,S),r(m,f,d,x,w,!1,k),!k.ka&&(p&&(n(f),t(l,f,S)),g||p)&&(g&&(k.Ub=!0),r(m,f,d,x,x,!0,k))),k.ka){var T="",z=0,M=Number.MAX_VALUE,P=Number.MIN_VALUE;a(m,f,k.bc,k.fontSize,d,k.Ub,function(e,t){0<T.length&&" "===t&&(T+=" "),T+=e;},function(e,t,n,r,a){"\xad"===r&&(T+="\u2010"),l.save(),l.translate(h.x,t),e=k.fontSize/100,l.scale(e,e),l.fillText(T,0,0),l.restore(),T=n,z<a&&(z=a),M>t&&(M=t),P<t&&(P=t);}),k.box={x:h.x-z/2,y:M-k.fontSize/2,w:z,o:P-M+k.fontSize},l.restore();}else l.clear&&l.clear();return k;}function t(e,t,n){var r,a=t.text.split(/(\n|[ \f\r\t\v\u2028\u2029]+|\u00ad+|\u200b+)/),i=[],l=[],u=a.length>>>1;for(r=0;r<u;r++)i.push(a[2*r]),l.push(a[2*r+1]);for(2*r<a.length&&(i.push(a[2*r]),l.push(void 0)),n=o[n],r=0;r<i.length;r++)void 0===(u=n[a=i[r]])&&(u=e.measureText(a).width,n[a]=u);t.Tc=i,t.Rf=l;}function n(e){for(var t=e.text.split(/\s+/),n=[],r={".":!0,",":!0,";":!0,"?":!0,"!":!0,":":!0,"\u3002":!0},a=0;a<t.length;a++){var i=t[a];if(3<i.length){var o="";o+=i.charAt(0)+i.charAt(1);for(var l=2;l<i.length-2;l++){var u=i.charAt(l);r[u]||(o+="\u200B"),o+=u;}o+="\u200B"+i.charAt(i.length-2)+i.charAt(i.length-1),n.push(o);}else n.push(i);}e.text=n.join(" ");}function r(e,t,n,r,i,o,l){var u=e.lineHeight,s=Math.max(e.Ua,.001),c=e.ib,f=t.Tc,d=n.cd,h=n.F,p=void 0,g=void 0;switch(e.verticalAlign){case"top":d=h.y+h.o-d.y;break;case"bottom":d=d.y-h.y;break;default:d=2*Math.min(d.y-h.y,h.y+h.o-d.y);}if(0>=(c=Math.min(d,c*n.F.o)))l.ka=!1;else{d=r,i=Math.min(i,c),h=Math.min(1,c/Math.max(20,t.Tc.length));do{var v=(d+i)/2,b=Math.min(f.length,Math.floor((c+v*(u-1-2*s))/(v*u))),m=void 0;if(0<b)for(var y=1,w=b;;){var x=Math.floor((y+w)/2);if(a(e,t,x,v,n,o&&v===r&&x===b,null,null)){if(y===(w=p=m=x))break;}else if((y=x+1)>w)break;}void 0!==m?d=g=v:i=v;}while(i-d>h)return void 0===g?(l.ka=!1,l.fontSize=0):(l.ka=!0,l.fontSize=g,l.bc=p,l.Ub=o&&v===d),l;}}function a(e,t,n,r,a,i,c,f){var d=e.cb,h=r*(e.lineHeight-1),p=Math.max(e.Ua,.001),g=o[e.ue],v=t.Tc;t=t.Rf;var b=a.C,m=a.cd,y=a.Ye,w=a.Ze;switch(e.verticalAlign){case"top":a=m.y+r/2+r*p;var x=1;break;case"bottom":a=m.y-(r*n+h*(n-1))+r/2-r*p,x=-1;break;default:a=m.y-(r*(n-1)/2+h*(n-1)/2),x=1;}for(p=0,e=a;p<n;p++)l[2*p]=a-r/2,l[2*p+1]=a+r/2,a+=x*r+x*h;for(;u.length<l.length;)u.push([,,]);p=l,a=2*n,x=u;var S=b.length,k=y;y=(y-1+S)%S;var C=w;w=(w+1)%S;for(var T=0;T<a;){for(var z=p[T],M=b[y];M.y<z;)k=y,M=b[y=(y-1+S)%S];for(var P=b[w];P.y<z;)C=w,P=b[w=(w+1)%S];var E=b[k],_=b[C];P=_.x+(P.x-_.x)*(z-_.y)/(P.y-_.y),x[T][0]=E.x+(M.x-E.x)*(z-E.y)/(M.y-E.y),x[T][1]=P,T++;}for(p=0;p<n;p++)b=2*p,x=(x=(a=m.x)-u[b][0])<(S=u[b][1]-a)?x:S,b=(S=a-u[b+1][0])<(b=u[b+1][1]-a)?S:b,s[p]=2*(x<b?x:b)-d*r;for(p=0,k=g[" "]*r/100,x=g["\u2026"]*r/100,y=s[d=0],m=0,b=void 0;p<v.length;p++){if(a=v[p],C=t[p],m+(S=g[a]*r/100)<y&&v.length-p>=n-d&&"\n"!=b)m+=S," "===C&&(m+=k),c&&c(a,b);else{if(S>y&&(d!==n-1||!i))return!1;if(d+1>=n){if(i)return((n=y-m-x)>x||S>x)&&0<(n=Math.floor(a.length*n/S))&&c&&c(a.substring(0,n),b),c&&c("\u2026",void 0),f&&f(d,e,a,b,m),!0;return!1;}if(d++,f&&f(d,e,a,b,m),e+=r+h,y=s[d],m=S," "===C&&(m+=k),S>y&&(d!==n||!i))return!1;}b=C;}return f&&f(d,e,void 0,void 0,m),!0;}var i={hb:72,Gc:0,lineHeight:1.05,cb:1,Ua:.5,ib:.9,fontFamily:"sans-serif",fontStyle:"normal",fontWeight:"normal",fontVariant:"normal",verticalAlign:"center"},o={},l=[],u=[],s=[],f={text:"",Tc:void 0,Rf:void 0},d={C:void 0,F:void 0,cd:void 0,Ye:0,Ze:0};return{se:e,de:function(t,n,r,a,i,o,l,u,s,c,f,d){var h=0,p=0;if(r=r.toString().trim(),!d&&s.result&&r===s.Yf&&Math.abs(c-s.Zd)/c<=f){var g=s.result;g.ka&&(h=o.x-s.fg,p=o.y-s.gg,f=s.Qc,n.save(),n.translate(h,p),f.Na(n),n.restore());}return g||((f=s.Qc).clear(),(g=e(t,f,r,a,i,o,l,u)).ka&&f.Na(n),s.Zd=c,s.fg=o.x,s.gg=o.y,s.result=g,s.Yf=r),g.ka?{ka:!0,bc:g.bc,fontSize:g.fontSize,box:{x:g.box.x+h,y:g.box.y+p,w:g.box.w,o:g.box.o},Ub:g.Ub}:{ka:!1};},bi:function(){return{Zd:0,fg:0,gg:0,result:void 0,Qc:new c,Yf:void 0};},ya:i};}(),O=new function(){function e(e,t){return function(r,a,i,o){function l(e,t,n,r,a){e.C=[{x:t,y:n},{x:t+r,y:n},{x:t+r,y:n+a},{x:t,y:n+a}];}var u=a.x,s=a.y,c=a.w;if(a=a.o,0!=r.length){if(1==r.length)r[0].x=u+c/2,r[0].y=s+a/2,r[0].nd=0,i&&l(r[0],u,s,c,a);else{r=r.slice(0);for(var f=0,d=0;d<r.length;d++)f+=r[d].weight;for(d=0,f=c*a/f;d<r.length;d++)r[d].lc=r[d].weight*f;!function e(r,a,o,u,s){if(0!=r.length){var c=r.shift(),f=n(c);if(t(u,s)){var d=a,h=f/u;do{var p=(f=c.shift()).lc,g=p/h,v=o,b=h;(p=f).x=d+g/2,p.y=v+b/2,i&&l(f,d,o,g,h),d+=g;}while(0<c.length)return e(r,a,o+h,u,s-h);}d=o,g=f/s;do h=(p=(f=c.shift()).lc)/g,p=f,v=d,b=h,p.x=a+g/2,p.y=v+b/2,i&&l(f,a,d,g,h),d+=h;while(0<c.length)return e(r,a+g,o,u-g,s);}}(o=e(r,c,a,[[r.shift()]],o),u,s,c,a);}}};}function t(e,t,r,a){function i(e){return Math.max(Math.pow(u*e/l,r),Math.pow(l/(u*e),a));}var o=n(e),l=o*o,u=t*t;for(o=1,t=i(e[0].lc);o<e.length;o++)t=Math.max(t,i(e[o].lc));return t;}function n(e){for(var t=0,n=0;n<e.length;n++)t+=e[n].lc;return t;}this.u=e(function(e,r,a,i,o){o=Math.pow(2,o);for(var l=1/o,u=r<a;0<e.length;){var s=i[i.length-1],c=e.shift(),f=u?r:a,d=u?o:l,h=u?l:o,p=t(s,f,d,h);s.push(c),p<(f=t(s,f,d,h))&&(s.pop(),i.push([c]),u?a-=n(s)/r:r-=n(s)/a,u=r<a);}return i;},function(e,t){return e<t;}),this.i=e(function(e,n,r,a,i){function o(e){if(1<a.length){for(var r=a[a.length-1],i=a[a.length-2].slice(0),o=0;o<r.length;o++)i.push(r[o]);t(i,n,l,u)<e&&a.splice(-2,2,i);}}for(var l=Math.pow(2,i),u=1/l;0<e.length;){if(i=t(r=a[a.length-1],n,l,u),0==e.length)return;var s=e.shift();r.push(s),i<t(r,n,l,u)&&(r.pop(),o(i),a.push([s]));}return o(t(a[a.length-1],n,l,u)),a;},function(){return!0;});};function R(e){var t,n={},r=e.Cd;e.j.subscribe("model:loaded",function(e){t=e;}),this.M=function(){e.j.D("api:initialized",this);},this.nc=function(e,t,a,i){this.Xc(n,t),this.Yc(n,t),this.Wc(n,t,!1),i&&i(n),e(r,n,a);},this.bd=function(e,n,r,a,i,o,l){if(e){for(e=n.length-1;0<=e;e--){var u=n[e],s=C.extend({group:u.group},i);s[r]=a(u),o(s);}0<n.length&&l(C.extend({groups:E.uc(t,a).map(function(e){return e.group;})},i));}},this.Yc=function(e,t){return e.selected=t.selected,e.hovered=t.ub,e.open=t.open,e.openness=t.Cb,e.exposed=t.U,e.exposure=t.ja,e.transitionProgress=t.ra,e.revealed=!t.aa.Ga(),e.browseable=t.Ia?t.R:void 0,e.visible=t.Y,e.labelDrawn=t.oa&&t.oa.ka,e;},this.Xc=function(e,t){var n=t.parent;return e.group=t.group,e.parent=n&&n.group,e.weightNormalized=t.dg,e.level=t.level-1,e.siblingCount=n&&n.m.length,e.hasChildren=!t.empty(),e.index=t.index,e.indexByWeight=t.nd,e.description=t.description,e.attribution=t.attribution,e;},this.Wc=function(e,t,n){if(e.polygonCenterX=t.O.x,e.polygonCenterY=t.O.y,e.polygonArea=t.O.ha,e.boxLeft=t.F.x,e.boxTop=t.F.y,e.boxWidth=t.F.w,e.boxHeight=t.F.o,t.oa&&t.oa.ka){var r=t.oa.box;e.labelBoxLeft=r.x,e.labelBoxTop=r.y,e.labelBoxWidth=r.w,e.labelBoxHeight=r.o,e.labelFontSize=t.oa.fontSize;}return n&&t.$&&(e.polygon=t.$.map(function(e){return{x:e.x,y:e.y};}),e.neighbors=t.J&&t.J.map(function(e){return e&&e.group;})),e;};}var A=new function(){var e=window.console;this.i=function(e){throw"FoamTree: "+e;},this.info=function(t){e.info("FoamTree: "+t);},this.warn=function(t){e.warn("FoamTree: "+t);};};function I(e){function t(t,r){t.m=[],t.Ea=!0;var i=a(r),o=0;if(("flattened"===e.mb||"always"===e.Ag&&t.group&&t.group.description)&&0<r.length&&0<t.level){var l=r.reduce(function(e,t){return e+C.I(t.weight,1);},0),u=n(t.group,!1);u.description=!0,u.weight=l*e.Sb,u.index=o++,u.parent=t,u.level=t.level+1,u.id=u.id+"_d",t.m.push(u);}for(l=0;l<r.length;l++){var s=r[l];if(0>=(u=C.I(s.weight,1))){if(!e.Zi)continue;u=.9*i;}(s=n(s,!0)).weight=u,s.index=o,s.parent=t,s.level=t.level+1,t.m.push(s),o++;}}function n(e,t){var n=new ee;return r(e),n.id=e.__id,n.group=e,t&&(s[e.__id]=n),n;}function r(e){C.has(e,"__id")||(Object.defineProperty(e,"__id",{enumerable:!1,configurable:!1,writable:!1,value:u}),u++);}function a(e){for(var t=Number.MAX_VALUE,n=0;n<e.length;n++){var r=e[n].weight;0<r&&t>r&&(t=r);}return t===Number.MAX_VALUE&&(t=1),t;}function i(e){if(!e.empty()){e=e.m;var t,n=0;for(t=e.length-1;0<=t;t--){var r=e[t].weight;n<r&&(n=r);}for(t=e.length-1;0<=t;t--)(r=e[t]).dg=r.weight/n;}}function o(e){if(!e.empty()){e=e.m.slice(0).sort(function(e,t){return e.weight<t.weight?1:e.weight>t.weight?-1:e.index-t.index;});for(var t=0;t<e.length;t++)e[t].nd=t;}}function l(){for(var t=p.m.reduce(function(e,t){return e+t.weight;},0),n=0;n<p.m.length;n++){var r=p.m[n];r.attribution&&(r.weight=Math.max(.025,e.tg)*t);}}var u,s,c,f,d,h=this,p=new ee;this.M=function(){return p;},this.T=function(n){var r=n.group.groups,a=e.Uh;return!n.m&&!n.description&&!!r&&0<r.length&&d+r.length<=a&&(d+=r.length,t(n,r),i(n),o(n),!0);},this.load=function(e){p.group=e,p.xa=!1,p.R=!1,p.Ia=!1,p.open=!0,p.Cb=1,u=function e(t,n){if(!t)return n;if(n=Math.max(n,t.__id||0),(t=t.groups)&&0<t.length)for(var r=t.length-1;0<=r;r--)n=e(t[r],n);return n;}(e,0)+1,s={},c={},f={},d=0,e&&(r(e),s[e.__id]=p,C.V(e.id)||(c[e.id]=e),function e(t){var n=t.groups;if(n)for(var a=0;a<n.length;a++){var i=n[a];r(i);var o=i.__id;s[o]=null,f[o]=t,o=i.id,C.V(o)||(c[o]=i),e(i);}}(e)),t(p,e&&e.groups||[]),function(e){if(!e.empty()){var t=n({attribution:!0});t.index=e.m.length,t.parent=e,t.level=e.level+1,t.attribution=!0,e.m.push(t);}}(p),i(p),l(),o(p);},this.update=function(e){e.forEach(function(e){E.za(e,function(e){if(!e.empty()){e=e.m;for(var t=a(e.map(function(e){return e.group;})),n=0;n<e.length;n++){var r=e[n];r.weight=0<r.group.weight?r.group.weight:.9*t;}}}),i(e),e===p&&l(),o(e);});},this.u=function(e){return(function(){if(C.V(e)||C.Se(e))return[];if(Array.isArray(e))return e.map(h.i,h);if(C.wb(e)){if(C.has(e,"__id"))return[h.i(e)];if(C.has(e,"all")){var t=[];return E.L(p,function(e){t.push(e);}),t;}if(C.has(e,"groups"))return h.u(e.groups);}return[h.i(e)];})().filter(function(e){return void 0!==e;});},this.i=function(e){if(C.wb(e)&&C.has(e,"__id")){if(e=e.__id,C.has(s,e)){if(null===s[e]){for(var t=f[e],n=[];t&&(n.push(t=t.__id),!s[t]);)t=f[t];for(t=n.length-1;0<=t;t--)this.T(s[n[t]]);}return s[e];}}else if(C.has(c,e))return this.i(c[e]);},this.H=function(e,t,n){return{m:h.u(e),Ca:C.I(e&&e[t],!0),Ba:C.I(e&&e.keepPrevious,n)};};}function B(e,t,n){var r={};t.Ba&&E.L(e,function(e){n(e)&&(r[e.id]=e);}),e=t.m,t=t.Ca;for(var a=e.length-1;0<=a;a--){var i=e[a];r[i.id]=t?i:void 0;}var o=[];return C.Aa(r,function(e){void 0!==e&&o.push(e);}),o;}function G(e){function t(e,t){e=e.ja,t.opacity=1,t.Da=1,t.va=0>e?1-x.Eh/100*e:1,t.saturation=0>e?1-x.Fh/100*e:1,t.ca=0>e?1+.5*e:1;}function n(e){return Math.max(.001,0===(e=e.ja)?1:1+e*(x.Pa-1));}function r(e){g.reduce(l(!0,void 0,function(t){return t.U||e[t.id];}),u(v)),v.x-=v.w*(x.Pa-1)/2,v.y-=v.o*(x.Pa-1)/2,v.w*=x.Pa,v.o*=x.Pa;}function a(){return!!g&&g.reduce(function(e,t){return e||0!==t.ja;},!1);}function i(n,r,a){var i=h.K.A(n);return 0===n.ja&&0!==r&&i.call(function(){this.mc(S),this.qb(t);}),i.fa({duration:x.Oa,P:{ja:{end:r,easing:k.ia(x.Wb)}},ba:function(){s.N=!0,s.Fa=!0,e.j.D("foamtree:dirty",!0);}}),0===r&&i.call(function(){this.vd(),this.cc(),this.Nc(S),this.Mc(t);}),i.call(a).done();}function o(){var e=s.m.reduce(l(!1,S.transformPoint,void 0),u({})).box,t=x.Yb,n=Math.min(e.x,v.x-v.w*t),r=Math.min(e.y,v.y-v.o*t);d.content(n,r,Math.max(e.x+e.w,v.x+v.w*(1+t))-n,Math.max(e.y+e.o,v.y+v.o*(1+t))-r);}function l(e,t,n){var r={};return function(a,i){if(!n||n(i)){for(var o,l=e&&i.$||i.C,u=l.length-1;0<=u;u--)o=void 0!==t?t(i,l[u],r):l[u],a.Hc=Math.min(a.Hc,o.x),a.wd=Math.max(a.wd,o.x),a.Ic=Math.min(a.Ic,o.y),a.xd=Math.max(a.xd,o.y);a.box.x=a.Hc,a.box.y=a.Ic,a.box.w=a.wd-a.Hc,a.box.o=a.xd-a.Ic;}return a;};}function u(e){return{Hc:Number.MAX_VALUE,wd:Number.MIN_VALUE,Ic:Number.MAX_VALUE,xd:Number.MIN_VALUE,box:e};}var s,c,f,d,h,p,g,v,y,w,x=e.options,S={We:function(e,t){return t.scale=n(e),!1;},Ib:function(e,t){e=n(e);var r=c.x,a=c.y;t.translate(r,a),t.scale(e,e),t.translate(-r,-a);},Jb:function(e,t,r){e=n(e);var a=c.x,i=c.y;r.x=(t.x-a)/e+a,r.y=(t.y-i)/e+i;},transformPoint:function(e,t,r){e=n(e);var a=c.x,i=c.y;return r.x=(t.x-a)*e+a,r.y=(t.y-i)*e+i,r;}};e.j.subscribe("stage:initialized",function(e,t,n,r){c={x:n/2,y:r/2},v={x:0,y:0,w:y=n,o:w=r};}),e.j.subscribe("stage:resized",function(e,t,n,r){c.x*=n/e,c.y*=r/t,y=n,w=r;}),e.j.subscribe("api:initialized",function(e){f=e;}),e.j.subscribe("zoom:initialized",function(e){d=e;}),e.j.subscribe("model:loaded",function(e,t){s=e,g=t;}),e.j.subscribe("model:childrenAttached",function(e){g=e;}),e.j.subscribe("timeline:initialized",function(e){h=e;}),e.j.subscribe("openclose:initialized",function(e){p=e;});var T=["groupExposureScale","groupUnexposureScale","groupExposureZoomMargin"];e.j.subscribe("options:changed",function(e){C.bb(e,T)&&a()&&(r({}),d.fj(v,x.Yb),d.Fb());}),this.M=function(){e.j.D("expose:initialized",this);},this.Vb=function(t,n,l,u){var S=t.m.reduce(function(e,t){for(;t=t.parent;)e[t.id]=!0;return e;},{}),T=B(s,t,function(e){return e.U&&!e.open&&!S[e.id];}),z=new b;return(function(e,t){for(var n=e.reduce(function(e,t){return e[t.id]=t,e;},{}),r=e.length-1;0<=r;r--)E.L(e[r],function(e){n[e.id]=void 0;});var a=[];C.Aa(n,function(e){e&&E.ne(e,function(e){e.open||a.push(e);});});var i=[];return C.Aa(n,function(e){e&&e.open&&i.push(e);}),e=[],0!==a.length&&e.push(p.Bb({m:a,Ca:!0,Ba:!0},t,!0)),m(e);})(T,n).then(function(){(function(t,n,l,u){var p=a();if(0===t.length&&!p)return(new b).resolve().promise();var S=t.reduce(function(e,t){return e[t.id]=!0,e;},{}),C=[];if(t=[],g.reduce(function(e,t){return e||S[t.id]&&(!t.U||1!==t.ja)||!S[t.id]&&!t.parent.U&&(t.U||-1!==t.ja);},!1)){var T,z,M=[],P={};g.forEach(function(e){S[e.id]&&(e.U||C.push(e),e.U=!0,E.za(e,function(e){M.push(i(e,1)),P[e.id]=!0;}));}),0<M.length?(E.L(s,function(e){S[e.id]||(e.U&&C.push(e),e.U=!1),P[e.id]||M.push(i(e,-1));}),t.push(h.K.A({}).Qa(M).call(o).Ta()),r(S),t.push(p||!d.zd()?h.K.A(c).fa({duration:.7*x.Oa,P:{x:{end:v.x+v.w/2,easing:k.ia(x.Wb)},y:{end:v.y+v.o/2,easing:k.ia(x.Wb)}},ba:function(){e.j.D("foamtree:dirty",!0);}}).Ta():(c.x=v.x+v.w/2,c.y=v.y+v.o/2,(new b).resolve().promise())),l&&(d.ic(v,x.Yb,x.Oa,k.ia(x.Wb)),d.Fb())):(t.push((T=[],z=[],E.L(s,function(e){0!==e.ja&&z.push(i(e,0,function(){this.U=!1;}));}),T.push(h.K.A({}).Qa(z).Ta()),d.content(0,0,y,w),l&&(T.push(d.reset(x.Oa,k.ia(x.Wb))),d.Fb()),m(T))),n&&E.L(s,function(e){e.U&&C.push(e);}));}return m(t).then(function(){f.bd(n,C,"exposed",function(e){return e.U;},{indirect:u},e.options.jf,e.options.hf);});})(T.filter(function(e){return e.C&&e.$;}),n,l,u).then(z.resolve);}),z.promise();};}function H(e){var t,n,r=[],a=new y(C.qa);e.j.subscribe("stage:initialized",function(){}),e.j.subscribe("stage:resized",function(){}),e.j.subscribe("stage:newLayer",function(e,t){r.push(t);}),e.j.subscribe("model:loaded",function(e){t=e,a.clear();}),e.j.subscribe("zoom:initialized",function(){}),e.j.subscribe("timeline:initialized",function(e){n=e;});var i=!1;e.j.subscribe("render:renderers:resolved",function(e){i=e.labelPlainFill||!1;});var o=new function(){var e=0,t=0,n=0,r=0,a=0,i=0;this.i=function(o,l,u,s,c){t=1-(e=1+l),n=u,r=s,a=c,i=o;},this.We=function(i,o){return o.scale=e+t*i.ra,0!==a||0!==n||0!==r;},this.Ib=function(o,l){var u=e+t*o.ra,s=o.parent,c=i*o.x+(1-i)*s.x,f=i*o.y+(1-i)*s.y;l.translate(c,f),l.scale(u,u),o=1-o.ra,l.rotate(a*Math.PI*o),l.translate(-c,-f),l.translate(s.F.w*n*o,s.F.o*r*o);},this.Jb=function(a,o,l){var u=e+t*a.ra,s=i*a.x+(1-i)*a.parent.x,c=i*a.y+(1-i)*a.parent.y,f=1-a.ra;a=a.parent,l.x=(o.x-s)/u+s-a.F.w*n*f,l.y=(o.y-c)/u+c-a.F.o*r*f;},this.transformPoint=function(a,o,l){var u=e+t*a.ra,s=i*a.x+(1-i)*a.parent.x,c=i*a.y+(1-i)*a.parent.y,f=1-a.ra;a=a.parent,l.x=(o.x-s)*u+s-a.F.w*n*f,l.y=(o.y-c)*u+c-a.F.o*r*f;};};this.M=function(){},this.u=function(){function r(e,t){var n=Math.min(1,Math.max(0,e.ra));t.opacity=n,t.va=1,t.saturation=n,t.Da=n,t.ca=e.yb;}function l(e,t){var n=Math.min(1,Math.max(0,e.Hd));t.opacity=n,t.Da=n,t.va=1,t.saturation=1,t.ca=e.yb;}var u=e.options,s=u.Gd,c=u.li,f=u.mi,d=u.ni,h=u.hi,p=u.ii,g=u.ji,v=u.di,b=u.ei,m=u.fi,y=h+p+g+v+b+m+c+f+d,w=0<y?s/y:0,x=[];return a.initial()?o.i(u.si,u.pi,u.ti,u.ui,u.oi):o.i(u.Nf,u.Mf,u.Of,u.Pf,u.Lf),j.u(t,j.i(t,e.options.ri),function(t,a,s){var y="groups"===e.options.ki?s:a;x.push(n.K.A(t).call(function(){this.qb(r);}).wait(i?w*(h+y*p):0).fa({duration:i?w*g:0,P:{yb:{end:0,easing:k.Ab}},ba:function(){this.N=!0,e.j.D("foamtree:dirty",!0);}}).done()),E.L(t,function(t){x.push(n.K.A(t).call(function(){this.mc(o),this.qb(l);}).wait(w*(v+b*y)).fa({duration:w*m,P:{Hd:{end:0,easing:k.Ab}},ba:function(){this.N=!0,e.j.D("foamtree:dirty",!0);}}).Xa(function(){this.selected=!1,this.Nc(o);}).done());}),x.push(n.K.A(t).call(function(){this.mc(o);}).wait(w*(c+f*y)).fa({duration:w*d,P:{ra:{end:0,easing:k.ia(u.gi)}},ba:function(){this.N=!0,e.j.D("foamtree:dirty",!0);}}).Xa(function(){this.selected=!1,this.Nc(o);}).done());}),n.K.A({}).Qa(x).Ta();},this.i=function(t){return function(t){function r(e,t){var n=Math.min(1,Math.max(0,e.ra));t.opacity=n,t.va=1,t.saturation=n,t.Da=n,t.ca=e.yb;}var l=e.options,u=l.Ui,s=l.Vi,c=l.Ri,f=l.Si,d=l.Ti,h=l.Od,p=u+s+c+f+d,g=0<p?h/p:0,v=[];if(o.i(l.Nf,l.Mf,l.Of,l.Pf,l.Lf),0===g&&t.m&&t.R){for(p=0,h=t.m;p<h.length;p++){var m=h[p];m.ra=1,m.yb=1,m.qb(r),m.cc(),m.Mc(r);}return t.N=!0,e.j.D("foamtree:dirty",0<g),(new b).resolve().promise();}if(t.m&&t.R){j.u(t,j.i(t,e.options.Qd),function(t,a,h){t.mc(o),t.qb(r),h="groups"===e.options.Pd?h:a,a=n.K.A(t).wait(h*g*u).fa({duration:g*s,P:{ra:{end:1,easing:k.ia(l.Qi)}},ba:function(){this.N=!0,e.j.D("foamtree:dirty",0<g);}}).done(),h=n.K.A(t).wait(i?g*(c+h*f):0).fa({duration:i?g*d:0,P:{yb:{end:1,easing:k.Ab}},ba:function(){this.N=!0,e.j.D("foamtree:dirty",0<g);}}).done(),t=n.K.A(t).Qa([a,h]).Xd().Xa(function(){this.vd(),this.cc(),this.Nc(o),this.Mc(r);}).done(),v.push(t);}),a.i();var y=new b;return n.K.A({}).Qa(v).call(function(){a.u(),y.resolve();}).start(),y.promise();}return(new b).resolve().promise();}(t);};}function q(e){var t,n,r,a,i;e.j.subscribe("api:initialized",function(e){i=e;}),e.j.subscribe("model:loaded",function(e){a=e,t={};}),e.j.subscribe("timeline:initialized",function(e){n=e;}),e.j.subscribe("expose:initialized",function(e){r=e;}),this.M=function(){e.j.D("openclose:initialized",this);},this.Bb=function(o,l,u){if("flattened"==e.options.mb)return(new b).resolve().promise();o=B(a,o,function(e){return e.open||e.Va;});for(var s=new b,c=0;c<o.length;c++)o[c].Va=!0;0<o.length&&e.j.D("foamtree:attachChildren",o);var f=o.reduce(function(e,t){return e[t.id]=!0,e;},{});return(function(e,t){var n,i=[];if(E.L(a,function(t){if(t.m){var n=C.has(e,t.id);t.open!==n&&(n||t.U||E.L(t,function(e){if(e.U)return i.push(t),!1;}));}}),0===i.length)return(new b).resolve().promise();for(n=i.length-1;0<=n;n--)i[n].open=!1;for(t=r.Vb({m:i,Ca:!0,Ba:!0},t,!0,!0),n=i.length-1;0<=n;n--)i[n].open=!0;return t;})(f,l).then(function(){(function(r,o,l){function u(e,t){t.opacity=1-e.Cb,t.va=1,t.saturation=1,t.ca=1,t.Da=1;}var s=[],c=[];return E.L(a,function(a){if(a.R&&a.X){var i=C.has(r,a.id),o=t[a.id];if(o&&o.xb())o.stop();else if(a.open===i)return;a.Va=i,i||(a.open=i,a.Bd=!1),c.push(a),s.push(function(r,a){r.qb(u);var i=n.K.A(r).fa({duration:e.options.Kc,P:{Cb:{end:a?1:0,easing:k.fe}},ba:function(){this.N=!0,e.j.D("foamtree:dirty",!0);}}).call(function(){this.open=a,r.Va=!1;}).Xa(function(){this.cc(),this.Mc(u),delete t[this.id];}).done();return t[r.id]=i;}(a,i));}}),0<s.length?(e.j.D("openclose:changing"),n.K.A({}).Qa(s).Ta().then(function(){i.bd(o,c,"open",function(e){return e.open;},{indirect:l},e.options.sf,e.options.rf);})):(new b).resolve().promise();})(f,l,u).then(s.resolve);}),s.promise();};}function U(e){var t,n;e.j.subscribe("api:initialized",function(e){n=e;}),e.j.subscribe("model:loaded",function(e){t=e;}),this.M=function(){e.j.D("select:initialized",this);},this.select=function(r,a){return function(r,a){for(r=B(t,r,function(e){return e.selected;}),E.L(t,function(e){!0===e.selected&&(e.selected=!e.selected,e.N=!e.N,e.Sa=!e.Sa);}),i=r.length-1;0<=i;i--){var i,o=r[i];o.selected=!o.selected,o.N=!o.N,o.Sa=!o.Sa;}var l=[];E.L(t,function(e){e.N&&l.push(e);}),0<l.length&&e.j.D("foamtree:dirty",!1),n.bd(a,l,"selected",function(e){return e.selected;},{},e.options.uf,e.options.tf);}(r,a);};}function W(e){function t(e){return function(t){e.call(this,{x:t.x,y:t.y,scale:t.scale,ed:t.delta,ctrlKey:t.ctrlKey,metaKey:t.metaKey,altKey:t.altKey,shiftKey:t.shiftKey,lb:t.secondary,touches:t.touches});};}function n(){y.pc(2)?e.j.D("interaction:reset"):y.normalize($.ob,k.ia($.Kb));}function r(e){return function(){F.empty()||e.apply(this,arguments);};}function a(e,t,n){var r={},a={};return function(o){switch(e){case"click":var l,u,s,c=$.cf;break;case"doubleclick":c=$.df;break;case"hold":c=$.kf;break;case"hover":c=$.lf;break;case"mousemove":c=$.nf;break;case"mousewheel":c=$.qf;break;case"mousedown":c=$.mf;break;case"mouseup":c=$.pf;break;case"dragstart":c=$.gf;break;case"drag":c=$.ef;break;case"dragend":c=$.ff;break;case"transformstart":c=$.xf;break;case"transform":c=$.vf;break;case"transformend":c=$.wf;}var f=!1,d=!c.empty(),h=y.absolute(o,r),p=(t||d)&&i(h),g=(t||d)&&(l=h,u=void 0,s=0,E.sc(F,function(e){!0===e.open&&e.Y&&e.scale>s&&J(e,l)&&(u=e,s=e.scale);}),u);d&&(d=p?p.group:null,h=p?p.Jb(h,a):h,o.Db=void 0,c=c({type:e,group:d,topmostClosedGroup:d,bottommostOpenGroup:g?g.group:null,x:o.x,y:o.y,xAbsolute:h.x,yAbsolute:h.y,scale:C.I(o.scale,1),secondary:o.lb,touches:C.I(o.touches,1),delta:C.I(o.ed,0),ctrlKey:o.ctrlKey,metaKey:o.metaKey,altKey:o.altKey,shiftKey:o.shiftKey,preventDefault:function(){f=!0;},preventOriginalEventDefault:function(){o.Db="prevent";},allowOriginalEventDefault:function(){o.Db="allow";}}),f=f||0<=c.indexOf(!1),p&&p.attribution&&"click"===e&&(f=!1)),f||n&&n({dd:p,vg:g},o);};}function i(e,t){if("flattened"===$.mb)e=function(e){function t(e,n){var r=n.m;if(r){for(var a,i,o=-Number.MAX_VALUE,l=0;l<r.length;l++){var u=r[l];!u.description&&u.Y&&J(u,e)&&u.scale>o&&(i=u,o=u.scale);}return i&&(a=t(e,i)),a||i||n;}return n;}for(var n=b.length,r=b[0].scale,a=b[0].scale,i=0;i<n;i++){var o=b[i];(o=o.scale)<r&&(r=o),o>a&&(a=o);}if(r!==a){for(i=0;i<n;i++)if((o=b[i]).scale===a&&o.Y&&J(o,e))return t(e,o);}return t(e,F);}(e);else{t=t||0;for(var n=b.length,r=void 0,a=0;a<n;a++){var i=b[a];i.scale>t&&!1===i.open&&i.Y&&J(i,e)&&(r=i,t=i.scale);}e=r;}return e&&e.description&&(e=e.parent),e;}var o,u,s,c,f,d,h,p,g,v,b,m,y,T,z,P,L,D,F,j,N,O,R,A,I,B,G,H,q,U,W=l.Nh(),V=this,$=e.options,K=!1;e.j.subscribe("stage:initialized",function(t,n,r,a){m=n,q=r,U=a,function(){function t(e){return function(t){return t.x*=q/m.clientWidth,t.y*=U/m.clientHeight,e(t);};}"external"!==$.Ne&&("hammerjs"===$.Ne&&C.has(window,"Hammer")&&(X.M(m),X.A("tap",t(V.i),!0),X.A("doubletap",t(V.u),!0),X.A("hold",t(V.sa),!0),X.A("touch",t(V.ua),!0),X.A("release",t(V.wa),!1),X.A("dragstart",t(V.ga),!0),X.A("drag",t(V.H),!0),X.A("dragend",t(V.T),!0),X.A("transformstart",t(V.pb),!0),X.A("transform",t(V.transform),!0),X.A("transformend",t(V.Ka),!0)),G=new S(m),H=new x,G.i(t(V.i)),G.u(t(V.u)),G.sa(t(V.sa)),G.wa(t(V.ua)),G.Ka(t(V.wa)),G.ga(t(V.ga)),G.H(t(V.H)),G.T(t(V.T)),G.ta(t(V.ta)),G.Ja(t(V.ta)),G.ua(t(V.Ja)),H.addEventListener("keyup",function(t){var n=!1,r=void 0,a=$.yf({keyCode:t.keyCode,preventDefault:function(){n=!0;},preventOriginalEventDefault:function(){r="prevent";},allowOriginalEventDefault:function(){r="allow";}});"prevent"===r&&t.preventDefault(),(n=n||0<=a.indexOf(!1))||27===t.keyCode&&e.j.D("interaction:reset");}));}();}),e.j.subscribe("stage:resized",function(e,t,n,r){q=n,U=r;}),e.j.subscribe("stage:disposed",function(){G.Za(),X.Za(),H.i();}),e.j.subscribe("expose:initialized",function(e){z=e;}),e.j.subscribe("zoom:initialized",function(e){y=e;}),e.j.subscribe("openclose:initialized",function(e){P=e;}),e.j.subscribe("select:initialized",function(e){L=e;}),e.j.subscribe("titlebar:initialized",function(e){D=e;}),e.j.subscribe("timeline:initialized",function(e){T=e;}),e.j.subscribe("model:loaded",function(e,t){F=e,b=t;}),e.j.subscribe("model:childrenAttached",function(e){b=e;}),this.M=function(){},this.ua=r(a("mousedown",!1,function(){y.Zh();})),this.wa=r(a("mouseup",!1,void 0)),this.i=r(a("click",!0,function(e,t){!t.lb&&!t.shiftKey&&(e=e.dd)&&(e.attribution?t.ctrlKey?document.location.href=M.Qf("iuuqr;..b`ssnurd`sbi/bnl.gn`lusdd"):(t=k.ia($.Kb),e.be?(y.reset($.ob,t),e.be=!1):(y.cg(e,$.Yb,$.ob,t),e.be=!0)):L.select({m:[e],Ca:!e.selected,Ba:t.metaKey||t.ctrlKey},!0));})),this.u=r(a("doubleclick",!0,function(t,n){var r=t.dd;r&&r.attribution||(n.lb||n.shiftKey?r&&(r.parent.U&&(r=r.parent),t={m:r.parent!==F?[r.parent]:[],Ca:!0,Ba:!1},L.select(t,!0),z.Vb(t,!0,!0,!1)):r&&(t={m:[r],Ca:!0,Ba:!1},r.Va=!0,e.j.D("foamtree:attachChildren",[r]),z.Vb(t,!0,!0,!1)),r&&T.K.A({}).wait($.Oa/2).call(function(){P.Bb({m:E.uc(F,function(e){return e.Bd&&!E.Lh(r,e);}),Ca:!1,Ba:!0},!0,!0),r.Bd=!0,P.Bb({m:[r],Ca:!(n.lb||n.shiftKey),Ba:!0},!0,!0);}).start());})),this.sa=r(a("hold",!0,function(e,t){(e=(t=!(t.metaKey||t.ctrlKey||t.shiftKey)&&!t.lb)?e.dd:e.vg)&&e!==F&&P.Bb({m:[e],Ca:t,Ba:!0},!0,!1);})),this.ga=r(a("dragstart",!1,function(e,t){j=t.x,N=t.y,O=Date.now(),K=!0;})),this.H=r(a("drag",!1,function(e,t){if(K){I=Math.min(1,(e=Date.now())-O),O=e,e=t.x-j;var n=t.y-N;y.Xh(e,n),R=e,A=n,j=t.x,N=t.y;}})),this.T=r(a("dragend",!1,function(){if(K){K=!1;var e=Math.sqrt(R*R+A*A)/I;4<=e?y.Yh(e,R,A):y.af();}})),this.pb=r(a("transformstart",!1,function(e,t){B=1,j=t.x,N=t.y;}));var Q=1,Y=!1;this.transform=r(a("transform",!1,function(e,t){e=t.scale-.01,y.qg(t,e/B,t.x-j,t.y-N),B=e,j=t.x,N=t.y,Q=B,Y=Y||2<t.touches;})),this.Ka=r(a("transformend",!1,function(){Y&&.8>Q?e.j.D("interaction:reset"):n(),Y=!1;})),this.Ja=r(a("mousewheel",!1,(o=C.zg(function(){n();},300),function(e,t){1!==(e=$.lj)&&(e=Math.pow(e,t.ed),W?(y.rg(t,e),o()):y.Nb(t,e,$.ob,k.ia($.Kb)).then(n));}))),this.ta=r((s=void 0,c={},f=!1,d=a("hover",!1,function(){s&&(s.ub=!1,0<s.level&&(s.N=!0)),u&&(u.ub=!0,0<u.level&&(u.N=!0)),D.update(u),e.j.D("foamtree:dirty",!1);}),h=a("mousemove",!1,void 0),function(e){if("out"===e.type)f=(u=void 0)!==s;else if(y.absolute(e,c),s&&!s.open&&J(s,c)){var t=i(c,s.scale);t&&t!==s?(f=!0,u=t):f=!1;}else f=(u=i(c))!==s;f&&(d(e),s=u,f=!1),s&&h(e);})),this.Lb={click:t(this.i),doubleclick:t(this.u),hold:t(this.sa),mouseup:t(this.wa),mousedown:t(this.ua),dragstart:t(this.ga),drag:t(this.H),dragend:t(this.T),transformstart:t(this.pb),transform:t(this.transform),transformend:t(this.Ka),hover:t(this.ta),mousewheel:t(this.Ja)};var X=(g={},{M:function(e){p=window.Hammer(e,{doubletap_interval:350,hold_timeout:400,doubletap_distance:10});},A:function(e,t,n){g[e]=t,p.on(e,function(e){var r=(e=e.gesture).center;(r=w.pe(m,r.pageX,r.pageY,{})).scale=e.scale,r.lb=1<e.touches.length,r.touches=e.touches.length,t.call(m,r),(void 0===r.Db&&n||"prevent"===r.Db)&&e.preventDefault();});},Za:function(){p&&C.Aa(g,function(e,t){p.off(t,e);});}}),J=(v={},function(e,t){return e.Jb(t,v),e.$&&_.sa(e.$,v);});}function V(e){function t(e,t,n,r){var a,i=0,o=[];for(a=0;a<t.length;a++){var l=Math.sqrt(_.i(t[a],t[(a+1)%t.length]));o.push(l),i+=l;}for(a=0;a<o.length;a++)o[a]/=i;e[0].x=n.x,e[0].y=n.y;var u=l=i=0;for(a=1;a<e.length;a++){var s=e[a],c=.95*Math.pow(a/e.length,r);for(i+=.3819;l<i;)l+=o[u],u=(u+1)%o.length;var f=(u-1+o.length)%o.length,d=1-(l-i)/o[f],h=t[f].x;f=t[f].y;var p=t[u].x,g=t[u].y;h=(h-n.x)*c+n.x,f=(f-n.y)*c+n.y,p=(p-n.x)*c+n.x,g=(g-n.y)*c+n.y,s.x=h*(1-d)+p*d,s.y=f*(1-d)+g*d;}}var n={random:{vb:function(e,t){for(var n=0;n<e.length;n++){var r=e[n];r.x=t.x+Math.random()*t.w,r.y=t.y+Math.random()*t.o;}},Ob:"box"},ordered:{vb:function(e,t){e=e.slice(0),r.ac&&e.sort(et),O.i(e,t,!1,r.Ld);},Ob:"box"},squarified:{vb:function(e,t){e=e.slice(0),r.ac&&e.sort(et),O.u(e,t,!1,r.Ld);},Ob:"box"},fisheye:{vb:function(e,n,a){e=e.slice(0),r.ac&&e.sort(et),t(e,n,a,.25);},Ob:"polygon"},blackhole:{vb:function(e,n,a){e=e.slice(0),r.ac&&e.sort(et).reverse(),t(e,n,a,1);},Ob:"polygon"}};n.order=n.ordered,n.treemap=n.squarified;var r=e.options;this.i=function(e,t,a){if(0<e.length){if("box"===(a=n[a.relaxationInitializer||a.initializer||r.Li||"random"]).Ob){var i=_.F(t,{});a.vb(e,i),_.wa(e,_.H(i),t);}else a.vb(e,t,_.u(t,{}));for(a=e.length-1;0<=a;a--){if((i=e[a]).description){var o=_.ta(t,r.qc,r.Bg);i.x=o.x,i.y=o.y;}i.attribution&&(o=_.ta(t,r.$d,r.sg),i.x=o.x,i.y=o.y),C.wb(i.group.initialPosition)&&(o=i.group.initialPosition,o=_.ta(t,o.position||"bottomright",o.distanceFromCenter||1),i.x=o.x,i.y=o.y);}}};}function $(e){var t,n=e.options,r=new K(e,this),a=new Q(e,this),i={relaxed:r,ordered:a,squarified:a},o=i[e.options.Dc]||r;this.kg=5e-5,e.j.subscribe("model:loaded",function(e){t=e;}),e.j.subscribe("options:changed",function(e){e.layout&&C.has(i,n.Dc)&&(o=i[n.Dc]);}),this.step=function(e,t,n,r){return o.step(e,t,n,r);},this.complete=function(e){o.complete(e);},this.Pe=function(e){return e===t||2*Math.sqrt(e.O.ha/(Math.PI*e.m.length))>=Math.max(n.Ce,5e-5);},this.gd=function(e,t){var r=Math.pow(n.La,e.level),a=n.$a*r;r*=n.jd,e=e.m;for(var i=e.length-1;0<=i;i--){var l=e[i];o.ce(l,r),l.$=0<a?D.i(l.C,a):l.C,l.$&&(_.F(l.$,l.F),_.Ja(l.$,l.O)),l.m&&t.push(l);}},this.fc=function(e){o.fc(e);},this.Eb=function(e){o.Eb(e);};}function K(e,t){function n(e){if(e.m){e=e.m;for(var t=0;t<e.length;t++){var n=e[t];n.kc=n.hc*c.qh;}}}function r(e,r){t.Pe(e)&&(e.G||(e.G=D.i(e.C,c.jd*Math.pow(c.La,e.level-1)),e.G&&e.m[0]&&e.m[0].description&&"stab"==c.Tb&&l(e)),e.G&&(s.Eb(e),f.i(a(e),e.G,e.group),e.R=!0,r(e)),n(e));}function a(e){return"stab"===c.Tb&&0<e.m.length&&e.m[0].description?e.m.slice(1):e.m;}function i(e){var t=a(e);return F.i(t,e.G),F.u(t,e.G),j.H(e)*Math.sqrt(u.O.ha/e.O.ha);}function o(e){return e<c.If||1e-4>e;}function l(e){var t=c.Sb/(1+c.Sb),n=_.F(e.G,{}),r={x:n.x,y:0},a=n.y,i=n.o,o=c.ie*Math.pow(c.La,e.level-1),l=i*c.he,u=c.qc;"bottom"==u||0<=u&&180>u?(u=Math.PI,a+=i,i=-1):(u=0,i=1);var s=e.G,f=u,d=0,h=1,p=_.u(s,{}),g=p.ha;t*=g;for(var v=0;d<h&&20>v++;){var b=(d+h)/2;r.y=n.y+n.o*b;var m=_.ua(s,r,f);_.u(m[0],p);var y=p.ha-t;if(.01>=Math.abs(y)/g)break;0<(0==f?1:-1)*y?h=b:d=b;}_.F(m[0],n),(n.o<o||n.o>l)&&(r.y=n.o<o?a+i*Math.min(o,l):a+i*l,m=_.ua(e.G,r,u)),e.m[0].C=m[0],e.G=m[1];}var u,s=this,c=e.options,f=new V(e),d=0;e.j.subscribe("model:loaded",function(e){u=e,d=0;}),this.step=function(e,n,l,s){for(var f,h,p=0,g=[u];0<g.length;)p=Math.max(p,function(n){if(n.R&&n.xa?n!==u&&2*Math.sqrt(n.O.ha/(Math.PI*n.m.length))<Math.max(.85*c.Ce,t.kg)&&(n.R=!1,n.xa=!1,n.Ia=!0,n.G=null):n.Ia&&n.C&&r(n,function(){var t=a(n);F.i(t,n.G),F.u(t,n.G),e(n);}),!n.G||!n.R)return 0;if(n.parent&&n.parent.Z||n.Ea){var f=i(n);s&&s(n),n.Ea=!o(f)&&!l,n.Z=!0;}else f=0;return t.gd(n,g),f;}(g.shift()));var v=o(p);return n&&(d<(f=p)&&(d=f),h=c.If,c.Ad(v?1:1-(f-h)/(d-h||1),v,l),v&&(d=0)),v;},this.complete=function(e){for(var n=[u];0<n.length;){var a=n.shift();if(!a.R&&a.Ia&&a.C&&r(a,e),a.G){if(a.parent&&a.parent.Z||a.Ea){for(var l=1e-4>a.O.ha,s=0;!(o(i(a))||l&&32<s++););a.Z=!0,a.Ea=!1;}t.gd(a,n);}}},this.fc=function(e){E.L(e,n);},this.ce=function(e,t){if(e.R){var n=e.G;n&&(e.Fd=n),e.G=D.i(e.C,t),e.G&&e.m[0]&&e.m[0].description&&"stab"==c.Tb&&l(e),n&&!e.G&&(e.Z=!0),e.G&&e.Fd&&_.wa(a(e),e.Fd,e.G);}},this.Eb=function(e){for(var t,n=a(e),r=e.ha,i=t=0;i<n.length;i++)t+=n[i].weight;for(e.Gj=t,e=0;e<n.length;e++)(i=n[e]).Wf=i.w,i.hc=r/Math.PI*(0<t?i.weight/t:1/n.length);};}function Q(e,t){function n(e,t,n){for(var r=0;r<e.length;r++){var a=e[r];if(n(a)){e.splice(r,1),"topleft"==t||135<=t&&315>t?e.unshift(a):e.push(a);break;}}}var r,a=e.options,i={squarified:O.u,ordered:O.i};e.j.subscribe("model:loaded",function(e){r=e;}),this.step=function(e,t,n){return this.complete(e),t&&a.Ad(1,!0,n),!0;},this.complete=function(e){for(var o=[r];0<o.length;){var l=o.shift();(!l.R||l.parent&&l.parent.Z)&&l.Ia&&l.C&&function(e,n){if(t.Pe(e)){if(!e.G||e.parent&&e.parent.Z){var r=a.jd*Math.pow(a.La,e.level-1);e.G=_.H(function(e,t){var n=2*t;return e.x+=t,e.y+=t,e.w-=n,e.o-=n,e;}(_.F(e.C,{}),r));}e.G&&(e.R=!0,n(e));}else e.R=!1,E.za(e,function(e){e.G=null;});}(l,e),l.G&&((l.parent&&l.parent.Z||l.Ea)&&function(e){if("stab"==a.Tb&&0<e.m.length&&e.m[0].description){var t=e.m.slice(1);!function(e){function t(){r.C=_.H(i),r.x=i.x+i.w/2,r.y=i.y+i.o/2;}var n=a.Sb/(1+a.Sb),r=e.m[0],i=_.F(e.G,{}),o=i.o;n=Math.min(Math.max(o*n,a.ie*Math.pow(a.La,e.level-1)),o*a.he);var l=a.qc;"bottom"==l||0<=l&&180>l?(i.o=o-n,e.G=_.H(i),i.y+=o-n,i.o=n,t()):(i.o=n,t(),i.y+=n,i.o=o-n,e.G=_.H(i));}(e);}else t=e.m;a.ac&&t.sort(et),"floating"==a.Tb&&n(t,a.qc,function(e){return e.description;}),n(t,a.$d,function(e){return e.attribution;});var r=_.F(e.G,{});(i[a.Dc]||O.i)(t,r,!0,a.Ld),e.Ea=!1,e.Z=!0,e.N=!0,e.Fa=!0;}(l),t.gd(l,o));}},this.Eb=this.fc=this.ce=C.qa;}var Y=new function(){this.Hg=function(e,t){var n=e.globalAlpha;e.fillStyle="dark"===t?"white":"#1d3557",e.globalAlpha=1*n,e.save(),e.transform(.94115,0,0,.94247,-78.54,-58),e.beginPath(),e.moveTo(86.47,533.3),e.bezierCurveTo(83.52,531.5,83.45,530.6,83.45,488.3),e.bezierCurveTo(83.45,444.6,83.35,445.7,87.34,443.7),e.bezierCurveTo(88.39,443.1,90.5,442.5,92.02,442.4),e.bezierCurveTo(93.54,442.2,113,441.7,135.3,441.4),e.bezierCurveTo(177.9,440.7,179.3,440.7,182.7,443.4),e.bezierCurveTo(185.9,445.9,185.6,445,206.2,510.7),e.bezierCurveTo(207.8,515.8,209.5,521.3,210.1,522.9),e.bezierCurveTo(211.7,528,211.9,531.3,210.6,532.7),e.bezierCurveTo(209.5,534,208.4,534,148.5,534),e.bezierCurveTo(106.4,533.9,87.3,533.7,86.47,533.2),e.closePath(),e.fill(),e.globalAlpha=.8*n,e.beginPath(),e.moveTo(237.3,533.3),e.bezierCurveTo(234.8,532.5,233.1,530.9,231.7,528.1),e.bezierCurveTo(231,526.8,224.6,507,217.4,484.1),e.bezierCurveTo(203.1,438.8,202.6,436.7,205,431.4),e.bezierCurveTo(206.3,428.5,239.2,383.2,242.9,379.3),e.bezierCurveTo(245,377,246.9,376.7,249.7,378.2),e.bezierCurveTo(250.6,378.7,263.1,390.8,277.3,405.2),e.bezierCurveTo(301.1,429.2,303.4,431.6,305.1,435.5),e.bezierCurveTo(306.7,439,306.9,440.4,306.9,445.2),e.bezierCurveTo(306.8,455.3,302.2,526.4,301.5,528.9),e.bezierCurveTo(300.2,533.7,301,533.6,268.3,533.7),e.bezierCurveTo(252.2,533.8,238.3,533.6,237.3,533.3),e.closePath(),e.fill(),e.beginPath(),e.globalAlpha=.05*n,e.moveTo(329,533.3),e.bezierCurveTo(326.2,532.5,323.1,528.8,322.6,525.8),e.bezierCurveTo(322,521.6,327.2,446.1,328.4,442.2),e.bezierCurveTo(330.6,434.9,332.8,432.8,368.5,402.4),e.bezierCurveTo(387,386.7,403.9,372.8,406,371.4),e.bezierCurveTo(413.1,366.7,416,366.2,436.5,365.7),e.bezierCurveTo(456.8,365.2,463.6,365.6,470.2,367.6),e.bezierCurveTo(476.2,369.5,546.1,402.8,549.1,405.3),e.bezierCurveTo(550.4,406.3,552.2,408.7,553.2,410.5),e.lineTo(555,413.9),e.lineTo(555.2,459.5),e.bezierCurveTo(555.3,484.6,555.2,505.8,555,506.5),e.bezierCurveTo(554.4,509.1,548.1,517.9,543.8,522.2),e.bezierCurveTo(537.7,528.3,534.2,530.5,527.8,532.4),e.lineTo(522.3,534),e.lineTo(426.6,533.9),e.bezierCurveTo(371.1,533.9,330.1,533.6,328.9,533.3),e.closePath(),e.fill(),e.globalAlpha=.8*n,e.beginPath(),e.moveTo(87.66,423),e.bezierCurveTo(86.23,422.4,85.02,422,84.97,422),e.bezierCurveTo(84.91,422,84.55,421.1,84.16,419.9),e.bezierCurveTo(83.67,418.6,83.45,404.7,83.45,375.9),e.bezierCurveTo(83.45,328.4,83.27,330.3,88.12,328.1),e.bezierCurveTo(90.22,327.2,101.7,325.6,135.4,321.7),e.bezierCurveTo(159.9,318.8,181.1,316.5,182.5,316.5),e.bezierCurveTo(183.9,316.5,187,317.3,189.4,318.2),e.bezierCurveTo(193.5,319.8,194.7,320.8,210.1,336.2),e.bezierCurveTo(226.6,352.7,229.1,355.7,229.1,360),e.bezierCurveTo(229.1,363,226.8,366.5,212.9,385.4),e.bezierCurveTo(187.3,420.2,189.3,417.7,183.4,420.5),e.lineTo(179.5,422.3),e.lineTo(155.3,422.7),e.bezierCurveTo(89.91,424,90.39,423.9,87.65,423),e.closePath(),e.fill(),e.globalAlpha=.6*n,e.beginPath(),e.moveTo(314.6,415),e.bezierCurveTo(311.4,413.4,213.2,314.6,210.9,310.7),e.bezierCurveTo(208.9,307.2,208.5,303.4,209.9,300),e.bezierCurveTo(211.2,297,241.3,257,244.2,254.4),e.bezierCurveTo(247.3,251.7,252.9,249.7,257.4,249.7),e.bezierCurveTo(261.1,249.7,344.7,255.2,350.8,255.8),e.bezierCurveTo(358.5,256.6,363.1,259.5,366,265.1),e.bezierCurveTo(368.7,270.5,394.3,343.7,394.7,347.2),e.bezierCurveTo(395.1,351.6,393.6,356.1,390.5,359.5),e.bezierCurveTo(389.1,361,375.7,372.6,360.5,385.4),e.bezierCurveTo(326.7,414,327,413.7,324.5,415),e.bezierCurveTo(321.8,416.4,317.4,416.3,314.6,414.9),e.closePath(),e.fill(),e.globalAlpha=.4*n,e.beginPath(),e.moveTo(547.9,383.4),e.bezierCurveTo(547.1,383.2,533,376.6,516.5,368.7),e.bezierCurveTo(497.2,359.5,485.7,353.7,484.3,352.4),e.bezierCurveTo(481.6,349.8,480.2,346.5,480.2,342.5),e.bezierCurveTo(480.2,339.2,499.2,237,500.4,233.9),e.bezierCurveTo(502.2,229.1,506.2,225.8,511.3,224.9),e.bezierCurveTo(516.2,224,545.8,222.2,548.2,222.6),e.bezierCurveTo(551.5,223.2,553.7,224.7,555.1,227.3),e.bezierCurveTo(556.2,229.3,556.3,234,556.5,301.9),e.bezierCurveTo(556.6,341.8,556.5,375.7,556.3,377.2),e.bezierCurveTo(555.6,381.8,552,384.4,547.8,383.4),e.closePath(),e.fill(),e.globalAlpha=.4*n,e.beginPath(),e.moveTo(418.7,347),e.bezierCurveTo(416,346.1,413.6,344.3,412.3,342.1),e.bezierCurveTo(411.6,341,404.4,321.3,396.3,298.3),e.bezierCurveTo(382,258.1,381.5,256.4,381.5,251.7),e.bezierCurveTo(381.5,248.2,381.8,246.2,382.7,244.7),e.bezierCurveTo(383.4,243.4,389.5,233.9,396.5,223.4),e.bezierCurveTo(412.6,199,411.3,199.9,430.6,198.6),e.bezierCurveTo(445,197.6,449.5,197.9,454.2,200.4),e.bezierCurveTo(460.5,203.7,479.6,217.5,481.3,220.1),e.bezierCurveTo(484.3,224.6,484.3,224.6,473.1,284),e.bezierCurveTo(465.3,325.9,462.4,339.9,461.3,341.8),e.bezierCurveTo(458.7,346.4,457.1,346.7,437.5,347.1),e.bezierCurveTo(428.1,347.3,419.6,347.3,418.7,347),e.closePath(),e.fill(),e.globalAlpha=.05*n,e.beginPath(),e.moveTo(89.33,308.2),e.bezierCurveTo(88.1,307.5,86.5,306.2,85.77,305.2),e.bezierCurveTo(84.42,303.4,84.42,303.4,84.24,202.6),e.bezierCurveTo(84.11,131.7,84.27,100.2,84.77,96.34),e.bezierCurveTo(85.65,89.58,87.91,84.64,92.77,78.81),e.bezierCurveTo(96.86,73.9,103.2,68.42,107.1,66.53),e.bezierCurveTo(108.6,65.81,112.8,64.64,116.5,63.92),e.bezierCurveTo(122.7,62.73,125.4,62.64,148.5,62.81),e.lineTo(173.7,63),e.lineTo(177.4,64.82),e.bezierCurveTo(179.5,65.82,182.1,67.75,183.3,69.12),e.bezierCurveTo(185.6,71.9,228.8,145.1,231.3,150.7),e.bezierCurveTo(234.5,157.7,234.9,160.8,234.9,176.9),e.bezierCurveTo(234.8,201.7,233.8,229.6,232.8,233.2),e.bezierCurveTo(232.3,235,231.1,238.1,230.2,240),e.bezierCurveTo(228.3,243.9,196.9,286.6,192.7,290.9),e.bezierCurveTo(189.8,293.9,184.3,297.1,180.2,298.2),e.bezierCurveTo(177.6,298.9,95.84,309.3,93.04,309.3),e.bezierCurveTo(92.22,309.3,90.55,308.8,89.33,308.1),e.closePath(),e.fill(),e.globalAlpha=.4*n,e.beginPath(),e.moveTo(305.7,235.6),e.bezierCurveTo(254.5,232,256.5,232.3,253.9,227.1),e.lineTo(252.4,224.2),e.lineTo(253.1,196.7),e.bezierCurveTo(253.8,170.5,253.8,169.1,255.2,166.3),e.bezierCurveTo(257.7,161.2,256.9,161.4,309.3,151.9),e.bezierCurveTo(354.1,143.8,356.8,143.4,359.7,144.2),e.bezierCurveTo(361.4,144.6,363.8,145.8,365,146.8),e.bezierCurveTo(367.3,148.6,389,179.6,391.9,185.2),e.bezierCurveTo(393.8,188.7,394.1,193.5,392.6,196.9),e.bezierCurveTo(391.5,199.6,370.6,231.4,368.4,233.8),e.bezierCurveTo(365.4,237,362,238.3,356.3,238.5),e.bezierCurveTo(353.5,238.6,330.7,237.3,305.7,235.5),e.closePath(),e.fill(),e.globalAlpha=.2*n,e.beginPath(),e.moveTo(497.1,207.1),e.bezierCurveTo(496.2,206.8,494.4,206,493.2,205.4),e.bezierCurveTo(490,203.8,472.7,191.6,469.7,189),e.bezierCurveTo(467,186.6,465.7,183.2,466.2,180.2),e.bezierCurveTo(466.5,178.1,482.4,138.6,484.9,133.5),e.bezierCurveTo(486.5,130.3,488.4,128.2,490.9,126.8),e.bezierCurveTo(492.6,125.9,496.3,125.7,522.2,125.6),e.lineTo(551.5,125.4),e.lineTo(553.7,127.6),e.bezierCurveTo(555.2,129.1,556,130.5,556.3,132.6),e.bezierCurveTo(556.5,134.2,556.6,149.6,556.5,166.9),e.bezierCurveTo(556.3,195.4,556.2,198.5,555.1,200.4),e.bezierCurveTo(553.1,204.1,551.7,204.4,529.8,206.1),e.bezierCurveTo(509.2,207.7,499.9,207.9,497,207.1),e.closePath(),e.fill(),e.globalAlpha=.2*n,e.beginPath(),e.moveTo(412.5,180.5),e.bezierCurveTo(410.9,179.7,408.7,177.9,407.5,176.4),e.bezierCurveTo(403.5,171.3,380.5,137.2,379.2,134.3),e.bezierCurveTo(377.2,129.6,377.1,126.1,378.9,116.8),e.bezierCurveTo(386.5,77.56,388.4,68.28,389.5,66.46),e.bezierCurveTo(390.1,65.34,391.7,63.83,392.9,63.1),e.bezierCurveTo(395.1,61.84,396.2,61.78,419.4,61.78),e.bezierCurveTo(443.4,61.78,443.7,61.8,446.5,63.25),e.bezierCurveTo(448,64.06,449.9,65.81,450.7,67.14),e.bezierCurveTo(452.3,69.73,468,105.5,470,111.1),e.bezierCurveTo(471.4,114.9,471.6,119.1,470.5,122.3),e.bezierCurveTo(470.1,123.5,465.2,135.8,459.7,149.5),e.bezierCurveTo(446.7,181.4,448.1,179.8,431.5,181.2),e.bezierCurveTo(419,182.2,415.7,182,412.5,180.5),e.closePath(),e.fill(),e.globalAlpha=.4*n,e.beginPath(),e.moveTo(253.6,142.8),e.bezierCurveTo(250.2,141.8,246.6,139.4,244.7,136.7),e.bezierCurveTo(242.1,132.9,207.4,73.28,206.2,70.42),e.bezierCurveTo(205.1,67.89,205,67.1,205.7,65.54),e.bezierCurveTo(207.3,61.54,202.3,61.8,284.4,61.59),e.bezierCurveTo(325.7,61.48,360.8,61.58,362.4,61.81),e.bezierCurveTo(366,62.32,369.3,65.36,369.9,68.75),e.bezierCurveTo(370.4,71.55,362.4,113.9,360.5,118.1),e.bezierCurveTo(359.1,121.3,355,125,351.4,126.4),e.bezierCurveTo(348.9,127.3,267.1,142.3,259.5,143.2),e.bezierCurveTo(257.9,143.4,255.2,143.2,253.6,142.7),e.closePath(),e.fill(),e.globalAlpha=.1*n,e.beginPath(),e.moveTo(493.4,106.8),e.bezierCurveTo(490.3,106,488.2,104.5,486.5,101.7),e.bezierCurveTo(483.8,97.43,471.8,68.81,471.8,66.76),e.bezierCurveTo(471.8,62.64,470.7,62.76,512.1,62.76),e.bezierCurveTo(553.3,62.76,552.3,62.67,554.4,66.68),e.bezierCurveTo(555.2,68.34,555.3,71.23,555.2,85.75),e.lineTo(555,102.8),e.lineTo(551.4,106.4),e.lineTo(534.1,106.8),e.bezierCurveTo(510.7,107.4,495.9,107.4,493.3,106.8),e.closePath(),e.fill(),e.restore(),e.transform(.15905,0,0,.15905,-88.65,443.2),e.globalAlpha=1*n,e.save(),e.beginPath(),e.moveTo(557.4,564.9),e.lineTo(557.4,98),e.lineTo(885.8,98),e.lineTo(885.8,185.1),e.lineTo(650.8,185.1),e.lineTo(650.8,284.7),e.lineTo(824.1,284.7),e.lineTo(824.1,371.6),e.lineTo(650.8,371.6),e.lineTo(650.8,564.9),e.lineTo(557.4,564.9),e.closePath(),e.fill(),e.beginPath(),e.moveTo(1029,568),e.quadraticCurveTo(961.1,568,915.7,522.5),e.quadraticCurveTo(870.2,476.7,870.2,409.2),e.quadraticCurveTo(870.2,341.3,915.7,295.9),e.quadraticCurveTo(961.1,250.4,1029,250.4),e.quadraticCurveTo(1096.8,250.4,1142.3,295.9),e.quadraticCurveTo(1187.7,341.3,1187.7,409.2),e.quadraticCurveTo(1187.7,477.1,1142.3,522.5),e.quadraticCurveTo(1097.3,568.1,1029.3,568.1),e.closePath(),e.moveTo(1028.6,492.6),e.quadraticCurveTo(1064.1,492.6,1086.2,469),e.quadraticCurveTo(1108.3,445,1108.3,409.5),e.quadraticCurveTo(1108.3,374,1086.2,350),e.quadraticCurveTo(1064.1,326.1,1028.3,326.1),e.quadraticCurveTo(993.1,326.1,971,350),e.quadraticCurveTo(948.9,374,948.9,409.5),e.quadraticCurveTo(948.9,445,971,469),e.quadraticCurveTo(993.1,492.6,1028.6,492.6),e.closePath(),e.fill(),e.beginPath(),e.moveTo(1253,291),e.quadraticCurveTo(1312.1,253.6,1390,253.6),e.quadraticCurveTo(1446,253.6,1478.7,284.7),e.quadraticCurveTo(1511.4,315.9,1511.4,378.1),e.lineTo(1511.4,564.9),e.lineTo(1424.2,564.9),e.lineTo(1424.2,540),e.quadraticCurveTo(1386.2,564.9,1355.7,564.9),e.quadraticCurveTo(1293.5,564.9,1262.3,538.5),e.quadraticCurveTo(1231.2,512,1231.2,465.3),e.quadraticCurveTo(1231.2,421.7,1260.4,387.5),e.quadraticCurveTo(1290,353.3,1355.7,353.3),e.quadraticCurveTo(1385.9,353.3,1424.2,371.9),e.lineTo(1424.2,362.6),e.quadraticCurveTo(1423.6,328.4,1374.4,325.2),e.quadraticCurveTo(1318.3,325.2,1287.2,343.9),e.lineTo(1253,291),e.closePath(),e.moveTo(1424.2,471.5),e.lineTo(1424.2,436.3),e.quadraticCurveTo(1411.7,412.3,1365,412.3),e.quadraticCurveTo(1309,418.5,1305.9,455.9),e.quadraticCurveTo(1309,492.9,1365,496),e.quadraticCurveTo(1411.7,496,1424.2,471.5),e.closePath(),e.fill(),e.beginPath(),e.moveTo(1675,365.7),e.lineTo(1675,564.9),e.lineTo(1587.8,564.9),e.lineTo(1587.8,262.5),e.lineTo(1675,253.2),e.lineTo(1675,280.9),e.quadraticCurveTo(1704.2,253.5,1749.7,253.5),e.quadraticCurveTo(1808.8,253.5,1839.9,289.3),e.quadraticCurveTo(1874.2,253.5,1942.6,253.5),e.quadraticCurveTo(2001.8,253.5,2032.9,289.3),e.quadraticCurveTo(2064,325.1,2064,371.8),e.lineTo(2064,564.8),e.lineTo(1976.9,564.8),e.lineTo(1976.9,393.6),e.quadraticCurveTo(1976.9,362.5,1962.9,345.4),e.quadraticCurveTo(1948.8,328.2,1917.4,327.3),e.quadraticCurveTo(1891.6,329.2,1872.6,361.6),e.quadraticCurveTo(1871,371.2,1871,381.2),e.lineTo(1871,564.9),e.lineTo(1783.9,564.9),e.lineTo(1783.9,393.7),e.quadraticCurveTo(1783.9,362.5,1769.9,345.4),e.quadraticCurveTo(1755.9,328.3,1724.4,327.4),e.quadraticCurveTo(1695.8,329.2,1674.9,365.7),e.closePath(),e.fill(),e.beginPath(),e.moveTo(2058,97.96),e.lineTo(2058,185.1),e.lineTo(2213.6,185.1),e.lineTo(2213.6,564.9),e.lineTo(2306.9,564.9),e.lineTo(2306.9,185.1),e.lineTo(2462.5,185.1),e.lineTo(2462.5,97.96),e.lineTo(2057.8,97.96),e.closePath(),e.fill(),e.beginPath(),e.moveTo(2549,287.8),e.quadraticCurveTo(2582.3,253.5,2630.2,253.5),e.quadraticCurveTo(2645.5,253.5,2659.2,256),e.lineTo(2645.5,341.9),e.quadraticCurveTo(2630.2,328.2,2601.9,327.3),e.quadraticCurveTo(2570.1,329.5,2549,373.4),e.lineTo(2549,564.8),e.lineTo(2461.8,564.8),e.lineTo(2461.8,262.5),e.lineTo(2549,253.1),e.lineTo(2549,287.7),e.closePath(),e.fill(),e.beginPath(),e.moveTo(2694,409.2),e.quadraticCurveTo(2694,340.7,2737.5,297.1),e.quadraticCurveTo(2781.1,253.5,2849.6,253.5),e.quadraticCurveTo(2918.1,253.5,2958.5,297.1),e.quadraticCurveTo(2999,340.6,2999,409.2),e.lineTo(2999,440.3),e.lineTo(2784.2,440.3),e.quadraticCurveTo(2787.3,465.2,2806,479.2),e.quadraticCurveTo(2824.7,493.2,2849.6,493.2),e.quadraticCurveTo(2893.1,493.2,2927.4,468.3),e.lineTo(2977.2,518.1),e.quadraticCurveTo(2943,564.8,2849.6,564.8),e.quadraticCurveTo(2781.1,564.8,2737.5,521.2),e.quadraticCurveTo(2693.9,477.6,2693.9,409.1),e.closePath(),e.moveTo(2911.9,378),e.quadraticCurveTo(2911.9,353.1,2893.2,339.1),e.quadraticCurveTo(2874.5,325.1,2849.6,325.1),e.quadraticCurveTo(2824.7,325.1,2806,339.1),e.quadraticCurveTo(2787.3,353.1,2787.3,378),e.lineTo(2911.8,378),e.closePath(),e.fill(),e.beginPath(),e.moveTo(3052,409.2),e.quadraticCurveTo(3052,340.7,3095.5,297.1),e.quadraticCurveTo(3139.1,253.5,3207.6,253.5),e.quadraticCurveTo(3276.1,253.5,3316.5,297.1),e.quadraticCurveTo(3357,340.6,3357,409.2),e.lineTo(3357,440.3),e.lineTo(3142.2,440.3),e.quadraticCurveTo(3145.3,465.2,3164,479.2),e.quadraticCurveTo(3182.7,493.2,3207.6,493.2),e.quadraticCurveTo(3251.1,493.2,3285.4,468.3),e.lineTo(3335.2,518.1),e.quadraticCurveTo(3301,564.8,3207.6,564.8),e.quadraticCurveTo(3139.1,564.8,3095.5,521.2),e.quadraticCurveTo(3051.9,477.6,3051.9,409.1),e.closePath(),e.moveTo(3269.9,378),e.quadraticCurveTo(3269.9,353.1,3251.2,339.1),e.quadraticCurveTo(3232.5,325.1,3207.6,325.1),e.quadraticCurveTo(3182.7,325.1,3164,339.1),e.quadraticCurveTo(3145.3,353.1,3145.3,378),e.lineTo(3269.8,378),e.closePath(),e.fill(),e.restore();};};function X(e,t){function n(e,t){var n=e.O.r,r=n/15,a=.5*n/15;n/=5;var i=e.O.x;e=e.O.y,t.fillRect(i-a,e-a,r,r),t.fillRect(i-a-n,e-a,r,r),t.fillRect(i-a+n,e-a,r,r);}function u(e,t,n,r){null===e&&n.clearRect(0,0,R,A);var a,i=Array(ea.length);for(a=ea.length-1;0<=a;a--)i[a]=ea[a].na(n,r);for(a=ea.length-1;0<=a;a--)i[a]&&ea[a].before(n,r);for(B.rc([n,O],function(r){var a;if(null!==e){for(n.save(),n.globalCompositeOperation="destination-out",n.fillStyle=n.strokeStyle="rgba(255, 255, 255, 1)",a=e.length-1;0<=a;a--){var o=e[a],l=o.C;l&&(n.save(),n.beginPath(),o.Ib(n),h.Ud(n,l),n.fill(),0<(o=ee.$a*Math.pow(ee.La,o.level-1))&&(n.lineWidth=o/2,n.stroke()),n.restore());}n.restore();}if(r=r.scale,0!==t.length){for(a={},l=ea.length-1;0<=l;l--)ea[l].og(a);for(o=er.length-1;0<=o;o--)if(a[(l=er[o]).id]){var u=l.Kd;for(l=0;l<t.length;l++){var s=t[l];!s.parent||s.parent.xa&&s.parent.R?u(s,r):s.aa.clear();}}}for(a=ea.length-1;0<=a;a--)o=ea[a],i[a]&&o.Nd(t,n,r);}),a=ea.length-1;0<=a;a--)i[a]&&ea[a].after(n);ee.Zc&&(n.canvas.style.opacity=.99,setTimeout(function(){n.canvas.style.opacity=1;},1));}function s(){var e,t=null,n=null,r=null;return B.rc([],function(e){a=e.scale,z===P?a<.9*K&&(z=M,D=F,p()):a>=K&&(z=P,D=j,p());var a,i=!1;E.L(q,function(e){e.W&&(i=e.vd()||i,e.cc(),e.Ma=Q.i(e)||e.Ma);}),i&&(q.N=!0);var o="onSurfaceDirty"===ee.Ng;E.fd(q,function(e){e.parent&&e.parent.Z&&(e.aa.clear(),e.Ma=!0,o||(e.oc=!0,e.Qb.clear())),o&&(e.oc=!0,e.Qb.clear());});var l=e.scale*e.scale;if(E.fd(q,function(e){if(e.R){for(var t=e.m,n=0;n<t.length;n++)if(5<t[n].O.ha*l){e.X=!0;return;}e.X=!1;}}),q.Y=!0,E.fd(q,function(t){if(t.W&&t.X&&t.xa&&t.R&&(q.N||t.Z||t.Vd)){t.Vd=!1;var n,r=t.m,a={x:0,y:0,w:0,o:0},i=!!t.G;if(1<R/e.w){for(n=r.length-1;0<=n;n--)r[n].Y=!1;if(t.Y&&i){for(n=r.length-1;0<=n;n--)if(1!==(t=r[n]).scale&&(t.Jb(e,a),a.w=e.w/t.scale,a.o=e.o/t.scale),!1===t.Y&&t.C){var o=(i=t.C).length;if(_.sa(t.C,1===t.scale?e:a))t.Y=!0;else for(var l=0;l<o;l++)if(_.Vc(i[l],i[(l+1)%o],1===t.scale?e:a)){t.Y=!0,t.J&&(t=t.J[l])&&(r[t.index].Y=!0);break;}}}}else for(n=0;n<r.length;n++)r[n].Y=i;}}),r=[],E.tc(q,function(e){if(e.parent.X&&e.Y&&e.W){r.push(e);for(var t=e.parent;t!==q&&(t.open||0===t.opacity);)t=t.parent;t!==q&&.02>Math.abs(t.scale-e.scale)&&(e.scale=Math.min(e.scale,t.scale));}}),function e(t,n,r){t.sb=Math.floor(1e3*t.scale)-r*n,0<t.opacity&&!t.open&&n++;var a=t.m;if(a)for(var i=a.length-1;0<=i;i--)t.W&&e(a[i],n,r);}(q,0,"flattened"===ee.mb?-1:1),r.sort(function(e,t){return e.sb-t.sb;}),c())t=r,n=null;else{var u={},s={},f="none"!=ee.ld&&ee.$a<ee.ab/2,d=ee.$a<ee.yc/2+ee.kd*ee.Ee.a;E.L(q,function(e){if(e.W&&!e.description&&(e.Z||e.N||e.Fc&&e.parent.X&&e.Ma)){var t,n,r,a=[e],i=e.J||e.parent.m;if(f)for(n=0;n<i.length;n++)(r=i[n])&&a.push(r);else if(d){if(!e.selected&&e.Sa){for(n=0,r=!0;n<i.length;n++)i[n]?a.push(i[n]):r=!1;!r&&1<e.level&&a.push(e.parent);}else for(n=0;n<i.length;n++)(r=i[n])&&r.selected&&a.push(r);}for(n=e.parent;n!=q;)n.selected&&(t=n),n=n.parent;for(t&&a.push(t),n=0;n<a.length;n++){for(e=(t=a[n]).parent;e&&e!==q;)0<e.opacity&&(t=e),e=e.parent;s[t.id]=!0,E.za(t,function(e){u[e.id]=!0;});}}}),n=(t=r.filter(function(e){return u[e.id];})).filter(function(e){return s[e.id];});}}),e=!1,ee.Hf&&E.L(q,function(t){if(t.W&&0!==t.pa.a&&1!==t.pa.a)return e=!0,!1;}),e?(E.sc(q,function(e){if(e.W&&(e.opacity!==e.Jc||e.Fa)){var t=e.m;if(t){for(var n=0,r=t.length-1;0<=r;r--)n=Math.max(n,t[r].Ec);e.Ec=n+e.opacity*e.pa.a;}else e.Ec=e.opacity*e.pa.a;}}),E.L(q,function(e){if(e.W&&(e.opacity!==e.Jc||e.Fa)){for(var t=e.Ec,n=e;(n=n.parent)&&n!==q;)t+=n.opacity*n.pa.a*ee.Ff;e.$c=0<t?1-Math.pow(1-e.pa.a,1/t):0,e.Jc=e.opacity;}})):E.L(q,function(e){e.W&&(e.$c=1,e.Jc=-1);}),{bg:t,ag:n,Y:r};}function c(){var e=q.Z||q.N||"none"==ee.Le;if(!e&&!q.empty()){var t=q.m[0].scale;E.L(q,function(n){if(n.W&&n.Y&&n.scale!==t)return e=!0,!1;});}return!e&&0<ee.ye&&1!=ee.Pa&&E.L(q,function(t){if(t.W&&0<t.ja)return e=!0,!1;}),"accurate"!=ee.Le||(e=(e=e||0===ee.$a)||"none"!=ee.ld&&ee.$a<ee.ab/2)||!(ee.$a<ee.yc/2+ee.kd*ee.Ee.a)||E.L(q,function(t){if(t.W&&(t.selected&&!t.Sa||!t.selected&&t.Sa))return e=!0,!1;}),e;}function p(){function e(e,n,r,a,i){function o(e,t,n,r,a){return e[r]&&(t-=n*S[r],e[r]=!1,a&&(t+=n*S[a],e[a]=!0)),t;}switch(e=C.extend({},e),r){case"never":e.labelPlainFill=!1;break;case"always":case"auto":e.labelPlainFill=!0;}if(ee.xc)switch(a){case"never":e.contentDecoration=!1;break;case"always":case"auto":e.contentDecoration=!0;}else e.contentDecoration=!1;var l=0;return C.Aa(e,function(e,t){e&&(l+=n*S["contentDecoration"===t?"labelPlainFill":t]);}),e.polygonExposureShadow=t,(l+=2*S.polygonExposureShadow)<=i||(l=o(e,l,2,"polygonExposureShadow"))<=i||(l=o(e,l,n,"polygonGradientFill","polygonPlainFill"))<=i||(l=o(e,l,n,"polygonGradientStroke"))<=i||(l=o(e,l,n,"polygonPlainStroke"))<=i||"auto"===a&&(l=o(e,l,n,"contentDecoration"))<=i||"auto"===r&&(l=o(e,l,n,"labelPlainFill")),e;}var t=z===M,n=0,r=0;E.oe(q,function(e){var t=1;E.L(e,function(){t++;}),n+=t,r=Math.max(r,t);});var a={};switch(ee.Wg){case"plain":a.polygonPlainFill=!0;break;case"gradient":a.polygonPlainFill=!t,a.polygonGradientFill=t;}switch(ee.ld){case"plain":a.polygonPlainStroke=!0;break;case"gradient":a.polygonPlainStroke=!t,a.polygonGradientStroke=t;}W=e(a,n,ee.jj,ee.hj,ee.ij),$=e(a,2*r,"always","always",ee.Fg),V=e(a,n,"always","always",ee.Eg);}function g(e){return function(t,n){return t===z?!0===W[e]:!0===(n?$:V)[e];};}function b(e,t){return function(n,r){return e(n,r)&&t(n,r);};}var m,y,w,x,S,T,z,M,P,L,D,F,j,N,O,R,A,I,B,G,H,q,U,W,V,$,K=l.Te()?50:1e4,Q=new J(e),X=new Z(e),ee=e.options;e.j.subscribe("stage:initialized",function(e,t,n,r){I=e,R=n,A=r,P=new f(M=(T=I.dc("wireframe",ee.nb,!1)).getContext("2d")),j=new f(F=(L=I.dc("hifi",ee.B,!1)).getContext("2d")),z=M,D=F,M.B=ee.nb,P.B=ee.nb,F.B=ee.B,j.B=ee.B,(O=(N=I.dc("tmp",Math.max(ee.B,ee.nb),!0)).getContext("2d")).B=1,[M,F,O].forEach(function(e){e.scale(e.B,e.B);});}),e.j.subscribe("stage:resized",function(e,t,n,r){R=n,A=r,[M,F,O].forEach(function(e){e.scale(e.B,e.B);});}),e.j.subscribe("model:loaded",function(t){U=!0,function e(t){var n=0;if(!t.empty()){for(var r=t.m,a=r.length-1;0<=a;a--)n=Math.max(n,e(r[a]));n+=1;}return t.Tf=n;}(q=t),p(),e.j.D("render:renderers:resolved",W,$,V);});var et="groupFillType groupStrokeType wireframeDrawMaxDuration wireframeLabelDrawing wireframeContentDecorationDrawing finalCompleteDrawMaxDuration finalIncrementalDrawMaxDuration groupContentDecorator".split(" "),en=["groupLabelLightColor","groupLabelDarkColor","groupLabelColorThreshold","groupUnexposureLabelColorThreshold"];e.j.subscribe("options:changed",function(e){function t(e,t,n,r){I.Ki(e,n),t.B=n,r&&t.scale(n,n);}e.dataObject||(C.bb(e,et)&&p(),C.bb(e,en)&&E.L(q,function(e){e.hd=-1;}));var n=C.has(e,"pixelRatio");e=C.has(e,"wireframePixelRatio"),(n||e)&&(n&&t(L,D,ee.B,!0),e&&t(T,z,ee.nb,!0),t(N,O,Math.max(ee.B,ee.nb),!1));}),e.j.subscribe("zoom:initialized",function(e){B=e;}),e.j.subscribe("timeline:initialized",function(e){G=e;}),e.j.subscribe("api:initialized",function(e){H=e;});var er=[{id:"offsetPolygon",Kd:function(e){if((e.selected||0<e.opacity&&!1===e.open||!e.X)&&e.aa.Ga()){var t=e.aa;if(t.clear(),e.$){var n=e.$,r=ee.Ig;0<r?h.Wi(t,n,e.parent.O.r/32,Math.min(1,r*Math.pow(1-ee.Jg*r,e.Tf))):h.Ud(t,n);}e.Dd=!0;}}},{id:"label",Kd:function(e){e.Ma&&e.Fc&&Q.u(e);}},{id:"custom",Kd:function(t,n){if(t.$&&(0<t.opacity&&(!1===t.open||!0===t.selected)||!t.X)&&t.oc&&e.options.xc&&!t.attribution){var r={};H.Xc(r,t),H.Yc(r,t),H.Wc(r,t,!0),r.context=t.Qb,r.polygonContext=t.aa,r.labelContext=t.Bc,r.shapeDirty=t.Dd,r.viewportScale=n,n={groupLabelDrawn:!0,groupPolygonDrawn:!0},e.options.Mg(e.Cd,r,n),t.Ue=n.groupLabelDrawn,t.Ed=n.groupPolygonDrawn,t.Dd=!1,t.oc=!1;}}}].reverse(),ea=[new function(e){var t=Array(e.length);this.Nd=function(n,r,a){if(0!==n.length){var i,o=[],l=n[0].sb;for(i=0;i<n.length;i++){var u=n[i];u.sb!==l&&(o.push(i),l=u.sb);}o.push(i);for(var s=l=0;s<o.length;s++){for(var c=o[s],f=e.length-1;0<=f;f--)if(t[f]){var d=e[f];for(r.save(),i=l;i<c;i++)u=n[i],r.save(),u.Ib(r),d.kb.call(d,u,r,a),r.restore();d.Wa.call(d,r,a),r.restore();}l=c;}}},this.na=function(n,r){for(var a=!1,i=e.length-1;0<=i;i--)t[i]=e[i].na(n,r),a|=t[i];return a;},this.before=function(n,r){for(var a=e.length-1;0<=a;a--)if(t[a]){var i=e[a];i.before.call(i,n,r);}},this.after=function(n){for(var r=e.length-1;0<=r;r--)if(t[r]){var a=e[r];a.after.call(a,n);}},this.og=function(n){for(var r=e.length-1;0<=r;r--){var a=e[r];if(t[r])for(var i=a.Ra.length-1;0<=i;i--)n[a.Ra[i]]=!0;}};}([{Ra:["offsetPolygon"],na:g("polygonExposureShadow"),before:function(e){O.save(),O.scale(e.B,e.B);},after:function(){O.restore();},rb:function(){},Wa:function(e){this.Sf&&(this.Sf=!1,e.save(),e.setTransform(1,0,0,1,0,0),e.drawImage(N,0,0,e.canvas.width,e.canvas.height,0,0,e.canvas.width,e.canvas.height),e.restore(),O.save(),O.setTransform(1,0,0,1,0,0),O.clearRect(0,0,N.width,N.height),O.restore());},kb:function(e,t,n){if(!(e.open&&e.X||e.aa.Ga())){var r=ee.ye*e.opacity*e.ja*("flattened"===ee.mb?1-e.parent.ja:(1-e.Cb)*e.parent.Cb)*(1.1<=ee.Pa?1:(ee.Pa-1)/.1);0<r&&(O.save(),O.beginPath(),e.Ib(O),e.aa.Na(O),O.shadowBlur=n*t.B*r,O.shadowColor=ee.Og,O.fillStyle="rgba(0, 0, 0, 1)",O.globalCompositeOperation="source-over",O.globalAlpha=e.opacity,O.fill(),O.shadowBlur=0,O.shadowColor="transparent",O.globalCompositeOperation="destination-out",O.fill(),O.restore(),this.Sf=!0);}}},{Ra:["offsetPolygon"],na:function(){return!0;},before:function(){function e(e){var n=e.pa,r=e.ub,a=e.selected,i=(n.h+(r?ee.$g:0)+(a?ee.rh:0))%360,o=t(n.l*e.va+(r?ee.ah:0)+(a?ee.sh:0));return n=t(n.s*e.saturation+(r?ee.bh:0)+(a?ee.th:0)),(e=e.xe).h=i,e.s=n,e.l=o,e;}function t(e){return 100<e?100:0>e?0:e;}var n=[{type:"fill",na:g("polygonPlainFill"),Pc:function(t,n){n.fillStyle=v.H(e(t));}},{type:"fill",na:g("polygonGradientFill"),Pc:function(n,r){var a=n.O.r,i=e(n);a=r.createRadialGradient(n.x,n.y,0,n.x,n.y,a*ee.Sg);var o=i.l,l=ee.Qg;a.addColorStop(0,v.i((i.h+ee.Pg)%360,t(i.s+ee.Rg),t(o+l))),o=i.l,l=ee.Ug,a.addColorStop(1,v.i((i.h+ee.Tg)%360,t(i.s+ee.Vg),t(o+l))),n.aa.Na(r),r.fillStyle=a;}},{type:"stroke",na:b(g("polygonPlainStroke"),function(){return 0<ee.ab;}),Pc:function(e,n){var r=e.pa,a=e.ub,i=e.selected,o=(r.h+ee.Ie+(a?ee.ze:0)+(i?ee.Fe:0))%360,l=t(r.s*e.saturation+ee.Ke+(a?ee.Be:0)+(i?ee.He:0));r=t(r.l*e.va+ee.Je+(a?ee.Ae:0)+(i?ee.Ge:0)),n.strokeStyle=v.i(o,l,r),n.lineWidth=ee.ab*Math.pow(ee.La,e.level-1);}},{type:"stroke",na:b(g("polygonGradientStroke"),function(){return 0<ee.ab;}),Pc:function(e,n){var r=e.O.r*ee.zh,a=e.pa,i=Math.PI*ee.vh/180;r=n.createLinearGradient(e.x+r*Math.cos(i),e.y+r*Math.sin(i),e.x+r*Math.cos(i+Math.PI),e.y+r*Math.sin(i+Math.PI));var o=e.ub,l=e.selected;i=(a.h+ee.Ie+(o?ee.ze:0)+(l?ee.Fe:0))%360;var u=t(a.s*e.saturation+ee.Ke+(o?ee.Be:0)+(l?ee.He:0));a=t(a.l*e.va+ee.Je+(o?ee.Ae:0)+(l?ee.Ge:0)),o=ee.xh,r.addColorStop(0,v.i((i+ee.wh)%360,t(u+ee.yh),t(a+o))),o=ee.Bh,r.addColorStop(1,v.i((i+ee.Ah)%360,t(u+ee.Ch),t(a+o))),n.strokeStyle=r,n.lineWidth=ee.ab*Math.pow(ee.La,e.level-1);}}],r=Array(n.length);return function(e,t){for(var a=n.length-1;0<=a;a--)r[a]=n[a].na(e,t);this.$i=n,this.wg=r;};}(),after:function(){},rb:function(){},Wa:function(){},kb:function(e,t){if(!(!e.Ed||(0===e.opacity||e.open)&&e.X||e.aa.Ga()||!ee.je&&e.description)){var n=this.$i,i=this.wg;t.beginPath(),e.aa.Na(t);for(var o=!1,l=!1,u=n.length-1;0<=u;u--){var s=n[u];if(i[u])switch(s.Pc(e,t),s.type){case"fill":o=!0;break;case"stroke":l=!0;}}n=(e.X?e.opacity:1)*e.pa.a,i=!e.empty(),u=ee.Hf?e.$c:1,o&&(e=i&&e.X&&e.R&&e.m[0].W?1-e.m.reduce(function(e,t){return e+t.ra*t.Hd;},0)/e.m.length*(1-ee.Ff):1,t.globalAlpha=n*e*u,r(t)),l&&(t.globalAlpha=n*(i?ee.$h:1)*u,t.closePath(),a(t),t.stroke());}}},{Ra:["offsetPolygon"],na:function(){return 0<ee.yc;},before:function(){},after:function(){},rb:function(){},Wa:function(){},kb:function(e,t,n){if(e.Ed&&e.selected&&!e.aa.Ga()){t.globalAlpha=e.Da,t.beginPath();var r=Math.pow(ee.La,e.level-1);t.lineWidth=ee.yc*r,t.strokeStyle=ee.uh;var a=ee.kd;0<a&&(t.shadowBlur=a*r*n*t.B,t.shadowColor=ee.De),e.aa.Na(t),t.closePath(),t.stroke();}}},{Ra:[],na:function(){return!0;},before:function(){},after:function(){},rb:function(){},Wa:function(){},Rh:Y,kb:function(e,t){var n,r,a,i,o=this.Rh;e.attribution&&!e.aa.Ga()&&(n=o.ke.width,r=o.ke.height,a=function(e){o.Hg(e,ee.ae);},i=Math.min(Math.min(.9*(i=_.Ka(e.$,e.O,n/r)),.5*e.F.o)/r,.5*e.F.w/n),t.save(),t.translate(e.x,e.y),t.globalAlpha=e.opacity*e.ca,t.scale(i,i),t.translate(-n/2,-r/2),a(t),t.restore());}},{Ra:[],na:(m=g("labelPlainFill"),y=b(g("contentDecoration"),function(){return ee.xc;}),function(e,t){return m(e,t)||y(e,t);}),before:function(){},after:function(){},rb:function(){},Wa:function(){},kb:function(e,t,n){(!(0<e.opacity)||!(0<e.ca)||e.open)&&e.X||e.aa.Ga()||(e.Cc=e.oa&&e.oa.ka&&ee.B*e.oa.fontSize*e.scale*n>=ee.oh,"auto"===e.pd?!ee.je&&e.description?e.fb=e.parent.fb:(t=(n=e.xe).h+(n.s<<9)+(n.l<<16),e.hd!==t&&(n=v.T(n),e.fb=n>(0>e.ja?ee.Dh:ee.dh)?ee.eh:ee.nh,e.hd=t)):e.fb=e.pd);}},{Ra:["custom"],na:b(g("contentDecoration"),function(){return ee.xc;}),before:function(){},after:function(){},rb:function(){},Wa:function(){},kb:function(e,t){!(0<e.opacity&&0<e.ca&&!e.open||!e.X)||e.Qb.Ga()||e.aa.Ga()||(e.Cc||void 0===e.oa?(t.globalAlpha=e.ca*(e.X?e.opacity:1)*(e.empty()?1:ee.Gf),t.fillStyle=e.fb,t.strokeStyle=e.fb,e.Qb.Na(t)):n(e,t));}},{Ra:["label"],na:g("labelPlainFill"),before:function(){},after:function(){},rb:function(){},Wa:function(){},kb:function(e,t,r){e.Ue&&e.Fc&&(0<e.opacity&&0<e.ca&&!e.open||!e.X)&&!e.aa.Ga()&&e.oa&&(t.fillStyle=e.fb,t.globalAlpha=e.ca*(e.X?e.opacity:1)*(e.empty()?1:ee.Gf),e.Cc?o(e,t,r):n(e,t));}}].reverse())];this.M=function(){var e;S=(e=function(){return d.estimate();},l.Hh()?function(){var t="CarrotSearchFoamTree"+":"+JSON.stringify(arguments),n=window.localStorage.getItem(t);return(n&&(n=JSON.parse(n)),n&&Date.now()-n.t<12096e5)?n.v:(n=e.apply(this,arguments),window.localStorage.setItem(t,JSON.stringify({v:n,t:Date.now()})),n);}:e)(ei()),X.M();},this.clear=function(){z.clearRect(0,0,R,A),D.clearRect(0,0,R,A);};var eo=!1,el=void 0;this.u=function(e){eo?el=e:e();},this.Nd=function(e){i(X);var n=null!==(x=s()).ag,r=0<I.$b("hifi"),a=r&&(n||!e);e=n||U||!e,U=!1,r&&!a&&eu.Yi(),u(x.ag,x.bg,a?D:z,e),E.za(q,function(e){e.Z=!1,e.N=!1,e.Sa=!1;}),a||(window.clearTimeout(w),eo=!0,w=setTimeout(function(){if(eo=!1,function(){if(ee.B!==ee.nb)return!0;var e="polygonPlainFill polygonPlainStroke polygonGradientFill polygonGradientStroke labelPlainFill contentDecoration".split(" ");E.L(q,function(t){if(t.W&&t.U)return e.push("polygonExposureShadow"),!1;});for(var t=e.length-1;0<=t;t--){var n=e[t];if(!!W[n]!=!!$[n])return!0;}return!1;}()){var e=!c();u(null,x.Y,D,e),C.defer(function(){eu.Xi(),el&&(el(),el=void 0);});}else el&&(el(),el=void 0);},Math.max(ee.kj,3*t.Xf.sd,3*t.Xf.rd))),ee.Bf(n);},this.i=function(e){e=e||{},i(X),q.N=!0;var t=s(),n=ee.B;try{var r=C.I(e.pixelRatio,ee.B);ee.B=r;var a=I.dc("export",r,!0),o=a.getContext("2d");z===P&&(o=new f(o)),o.scale(r,r);var l=C.has(e,"backgroundColor");l&&(o.save(),o.fillStyle=e.backgroundColor,o.fillRect(0,0,R,A),o.restore()),u(l?[]:null,t.bg,o,!0);}finally{ee.B=n;}return a.toDataURL(C.I(e.format,"image/png"),C.I(e.quality,.8));};var eu=function(){function e(e,t,r,a,i,o){function l(e,t,n,r){return G.K.A({opacity:I.$b(e)}).fa({duration:n,P:{opacity:{end:t,easing:r}},ba:function(){I.$b(e,this.opacity);}}).done();}var u=C.od(I.$b(e),t),s=C.od(I.$b(a),i);if(!u||!s){for(var c=n.length-1;0<=c;c--)n[c].stop();return n=[],u||n.push(l(e,t,r,k.Gb)),s||n.push(l(a,i,o,k.Uf)),G.K.A({}).Qa(n).start();}}var t,n=[];return{Yi:function(){ee.Zc?1!==T.style.opacity&&(T.style.visibility="visible",L.style.visibility="hidden",T.style.opacity=1,L.style.opacity=0):t&&t.xb()||(t=e("wireframe",1,ee.te,"hifi",0,ee.te));},Xi:function(){ee.Zc?(L.style.visibility="visible",T.style.visibility="hidden",T.style.opacity=0,L.style.opacity=1):e("hifi",1,ee.eg,"wireframe",0,ee.eg);}};}();return i=function(e){e.apply();},r=function(e){e.fill();},a=function(e){e.stroke();},this;}function J(e){function t(e){void 0!==e.groupLabelFontFamily&&(a.fontFamily=e.groupLabelFontFamily),void 0!==e.groupLabelFontStyle&&(a.fontStyle=e.groupLabelFontStyle),void 0!==e.groupLabelFontVariant&&(a.fontVariant=e.groupLabelFontVariant),void 0!==e.groupLabelFontWeight&&(a.fontWeight=e.groupLabelFontWeight),void 0!==e.groupLabelLineHeight&&(a.lineHeight=e.groupLabelLineHeight),void 0!==e.groupLabelHorizontalPadding&&(a.cb=e.groupLabelHorizontalPadding),void 0!==e.groupLabelVerticalPadding&&(a.Ua=e.groupLabelVerticalPadding),void 0!==e.groupLabelMaxTotalHeight&&(a.ib=e.groupLabelMaxTotalHeight),void 0!==e.groupLabelMaxFontSize&&(a.hb=e.groupLabelMaxFontSize);}var n,r=e.options,a={},i={},l={groupLabel:""},u={};e.j.subscribe("api:initialized",function(e){n=e;}),e.j.subscribe("options:changed",t),t(e.Cd),this.i=function(e){if(!e.$)return!1;var t=e.group.label;return r.gh&&!e.attribution&&(l.labelText=t,n.nc(r.fh,e,l),t=l.labelText),e.Ve=t,e.qd!==t;},this.u=function(e){var t=e.Ve;if(e.qd=t,e.Bc.clear(),e.oa=void 0,e.$&&!C.Oe(t)&&("flattened"!==r.mb||e.empty()||!e.R||!e.m[0].W)){var o=N.de;if(r.mh){u.fontFamily=a.fontFamily,u.fontStyle=a.fontStyle,u.fontVariant=a.fontVariant,u.fontWeight=a.fontWeight,u.lineHeight=a.lineHeight,u.horizontalPadding=a.cb,u.verticalPadding=a.Ua,u.maxTotalTextHeight=a.ib,u.maxFontSize=a.hb,n.nc(r.lh,e,u),i.fontFamily=u.fontFamily,i.fontStyle=u.fontStyle,i.fontVariant=u.fontVariant,i.fontWeight=u.fontWeight,i.lineHeight=u.lineHeight,i.cb=u.horizontalPadding,i.Ua=u.verticalPadding,i.ib=u.maxTotalTextHeight,i.hb=u.maxFontSize;var l=i;}else l=a;e.oa=o.call(N,l,e.Bc,t,e.$,e.F,e.O,!1,!1,e.Oh,e.O.ha,r.ph,e.Ma);}e.Ma=!1;},o=this.H=function(e,t){e.Bc.Na(t);};}function Z(e){function t(e,t){var n,r=e.m,a=r.length,o=i.O.r;for(n=0;n<a;n++){var l=r[n];l.tb=(180*(Math.atan2(l.x-e.x,l.y-e.y)+t)/Math.PI+180)/360,l.wc=Math.min(1,Math.sqrt(_.i(l,e))/o);}}function n(e,t){var n=(e=e.m).length;if(1===n||2===n&&e[0].description)e[0].tb=.5;else{var r=0,a=Number.MAX_VALUE,i=Math.sin(t),o=Math.cos(t);for(t=0;t<n;t++){var l=e[t],u=l.x*i+l.y*o;r<u&&(r=u),a>u&&(a=u),l.tb=u,l.wc=1;}for(t=0;t<n;t++)(l=e[t]).tb=(l.tb-a)/(r-a);}}function r(e,t,n,r){return(t=t[r])+(n[r]-t)*e;}var a,i,o={radial:t,linear:n},l=e.options,u={groupColor:null,labelColor:null};return e.j.subscribe("model:loaded",function(e){i=e;}),e.j.subscribe("api:initialized",function(e){a=e;}),this.M=function(){},this.apply=function(){function e(e){return 0>e?0:100<e?100:e;}var s=o[l.yi]||t,c=l.Ii,f=l.Bi,d=l.Kg,h=l.Lg,p=l.Ci,g=l.Gi;!function t(i){if(i.R&&i.xa){var o,b=i.m;if(i.Z||i.Fa||h){for(0===i.level?s(i,l.zi*Math.PI/180):n(i,l.Di*Math.PI/180),o=b.length-1;0<=o;o--){var m=b[o];m.Fa=!0;var y=m.tb,w=m.we;if(0===i.level)var x=r(y,c,f,"h"),S=(g+(1-g)*m.wc)*r(y,c,f,"s"),k=(1+(0>m.ja?p*(m.ja+1):p)*(1-m.wc))*r(y,c,f,"l"),T=r(y,c,f,"a");else x=(k=i.pa).h,S=k.s,k=function(t,n,r,a){var i=e(t+r*a);return i+n*((t=e(t-r*(1-a)))-i);}(k.l,y,l.Ei,l.Fi),T=i.we.a;w.h=x,w.s=S,w.l=k,w.a=T,x=m.pa,m.attribution?(x.h=0,x.s=0,x.l="light"==l.ae?90:10,x.a=1):(x.h=w.h,x.s=w.s,x.l=w.l,x.a=w.a),h&&!m.attribution&&(u.groupColor=x,u.labelColor="auto",a.nc(d,m,u,function(e){e.ratio=y;}),m.pa=v.u(u.groupColor),m.pa.a=C.has(u.groupColor,"a")?u.groupColor.a:1,"auto"!==u.labelColor&&(m.pd=v.wa(u.labelColor)));}i.Fa=!1;}for(o=b.length-1;0<=o;o--)t(b[o]);}}(i);},this;}function ee(){this.kc=this.Yd=this.hc=this.Wf=this.w=this.dg=this.weight=this.y=this.x=this.id=0,this.C=this.parent=this.m=null,this.F={x:0,y:0,w:0,o:0},this.J=null,this.qd=this.Ve=void 0,this.Sc=!1,this.wc=this.tb=0,this.we={h:0,s:0,l:0,a:0,model:"hsla"},this.pa={h:0,s:0,l:0,a:0,model:"hsla"},this.xe={h:0,s:0,l:0,model:"hsl"},this.hd=-1,this.pd="auto",this.fb="#000",this.Tf=this.level=this.nd=this.index=0,this.attribution=!1,this.ha=this.$e=0,this.Y=!1,this.$=null,this.O={x:0,y:0,ha:0,r:0},this.Fd=this.G=null,this.Fc=this.W=this.Sa=this.oc=this.Vd=this.Dd=this.Ma=this.Fa=this.N=this.Z=this.Ea=this.xa=this.R=this.Ia=!1,this.saturation=this.va=this.Da=this.ca=this.opacity=this.scale=1,this.ra=0,this.Hd=1,this.Cb=this.ja=this.yb=0,this.description=this.selected=this.ub=this.Bd=this.open=this.U=!1,this.sb=0,this.Ue=this.Ed=this.X=!0,this.oa=void 0,this.Cc=!1,this.Bc=new c,this.aa=new c,this.Qb=new c,this.Oh=N.bi(),this.Ec=0,this.$c=1,this.Jc=-1,this.empty=function(){return!this.m||0===this.m.length;};var e=[];this.mc=function(t){e.push(t);},this.Nc=function(t){C.Jf(e,t);};var t={scale:1};this.vd=function(){var n=!1;this.scale=1;for(var r=0;r<e.length;r++)n=e[r].We(this,t)||n,this.scale*=t.scale;return n;},this.Ib=function(t){for(var n=0;n<e.length;n++)e[n].Ib(this,t);},this.transformPoint=function(t,n){for(n.x=t.x,n.y=t.y,t=0;t<e.length;t++)e[t].transformPoint(this,n,n);return n;},this.Jb=function(t,n){for(n.x=t.x,n.y=t.y,t=0;t<e.length;t++)e[t].Jb(this,n,n);return n;};var n=[];this.qb=function(e){n.push(e);},this.Mc=function(e){C.Jf(n,e);};var r={opacity:1,saturation:1,va:1,ca:1,Da:1};this.cc=function(){if(0!==n.length){this.Da=this.ca=this.va=this.saturation=this.opacity=1;for(var e=n.length-1;0<=e;e--)(0,n[e])(this,r),this.opacity*=r.opacity,this.va*=r.va,this.saturation*=r.saturation,this.ca*=r.ca,this.Da*=r.Da;}};}function et(e,t){return t.weight>e.weight?1:t.weight<e.weight?-1:e.index-t.index;}function en(e){var t,n,r,a,i,o=this,l=e.options;e.j.subscribe("stage:initialized",function(a,i,u,s){n=u,r=s,(t=a.dc("titlebar",l.B,!1).getContext("2d")).B=l.B,t.scale(t.B,t.B),e.j.D("titlebar:initialized",o);}),e.j.subscribe("stage:resized",function(e,a,i,o){n=i,r=o,t.scale(t.B,t.B);}),e.j.subscribe("zoom:initialized",function(e){i=e;}),e.j.subscribe("api:initialized",function(e){a=e;}),e.j.subscribe("model:loaded",function(){t.clearRect(0,0,n,r);}),this.update=function(e){if(t.clearRect(0,0,n,r),e){!e.empty()&&e.m[0].description&&(e=e.m[0]);var o=l.ej,u=l.dj,s=Math.min(r/2,l.Wd+2*o),c=s-2*o,f=n-2*u;if(!(0>=c||0>=f)){var d=e.Cc?e.oa.fontSize*e.scale*i.scale():0,h={titleBarText:e.qd,titleBarTextColor:l.$f,titleBarBackgroundColor:l.Zf,titleBarMaxFontSize:l.Wd,titleBarShown:d<l.Vh};if(e.attribution)var p=M.Qf("B`ssnu!Rd`sbi!Gn`lUsdd!whrt`mh{`uhno/!Busm,bmhbj!uid!mnfn!un!fn!un!iuuqr;..b`ssnurd`sbi/bnl.gn`lusdd!gns!lnsd!edu`hmr/");else a.nc(l.aj,e,h,function(e){e.titleBarWidth=f,e.titleBarHeight=c,e.labelFontSize=d,e.viewportScale=i.scale();}),p=h.titleBarText;p&&0!==p.length&&h.titleBarShown&&(o={x:u,y:(e=i.Uc(e.transformPoint(e,{}),{}).y>r/2)?o:r-s+o,w:f,o:c},u=_.H(o),t.fillStyle=l.Zf,t.fillRect(0,e?0:r-s,n,s),t.fillStyle=l.$f,N.se({fontFamily:l.bj||l.hh,fontStyle:l.Dj||l.ih,fontWeight:l.Fj||l.kh,fontVariant:l.Ej||l.jh,hb:l.Wd,Gc:l.cj,cb:0,Ua:0,ib:1},t,p,u,o,{x:o.x+o.w/2,y:o.y+o.o/2},!0,!0).ka||t.clearRect(0,0,n,r));}}};}function er(e){function t(e,t,n){return y=!0,u&&u.stop(),s&&s.stop(),o(d.reset(e),t,n).then(function(){y=!1;});}function n(t){d.update(t),c.N=!0,e.j.D("foamtree:dirty",!0);}function r(e,t){return d.i((0!==d.u()?.35:1)*e,(0!==d.H()?.35:1)*t);}function a(){if(1===h.ratio){var e=Math.round(1e4*d.u())/1e4;0!==e&&(p.Id=e,u=f.K.jc(p).fa({duration:500,P:{x:{start:e,end:0,easing:k.Gb}},ba:function(){d.i(p.x-p.Id,0),n(1),p.Id=p.x;}}).start());}}function i(){if(1===h.ratio){var e=Math.round(1e4*d.H())/1e4;0!==e&&(v.Jd=e,s=f.K.jc(v).fa({duration:500,P:{y:{start:e,end:0,easing:k.Gb}},ba:function(){d.i(0,v.y-v.Jd),n(1),v.Jd=v.y;}}).start());}}function o(e,t,r){return e?f.K.jc(h).fa({duration:void 0===t?700:t,P:{ratio:{start:0,end:1,easing:r||k.Vf}},ba:function(){n(h.ratio);}}).Ta():(new b).resolve().promise();}function l(e){return function(){return y?(new b).resolve().promise():e.apply(this,arguments);};}var u,s,c,f,d=new g(e),h={ratio:1},p={le:0,x:0,Id:0},v={me:0,y:0,Jd:0},m=this,y=!1;e.j.subscribe("model:loaded",function(e){c=e,d.reset(!1),d.update(1);}),e.j.subscribe("timeline:initialized",function(e){f=e;}),this.M=function(){e.j.D("zoom:initialized",this);},this.reset=function(e,n){return d.Fb(1),t(!0,e,n);},this.normalize=l(function(e,n){d.pc(1)?t(!1,e,n):m.af();}),this.af=function(){a(),i();},this.cg=l(function(e,t,n,r){return m.ic(e.F,t,n,r);}),this.Nb=l(function(e,t,n,r){return o(d.Nb(e,t),n,r);}),this.ic=l(function(e,t,n,r){return o(d.ic(e,t),n,r);}),this.fj=l(function(e,t){d.ic(e,t)&&n(1);}),this.Xh=l(function(e,t){1===h.ratio&&r(e,t)&&n(1);}),this.rg=l(function(e,t){d.Nb(e,t)&&n(1);}),this.qg=l(function(e,t,a,i){(e=0|d.Nb(e,t)|r(a,i))&&n(1);}),this.Yh=l(function(e,t,o){1===h.ratio&&(u=f.K.jc(p).fa({duration:e/.03,P:{le:{start:t,end:0,easing:k.Gb}},ba:function(){d.i(p.le,0)&&n(1),a();}}).start(),s=f.K.jc(v).fa({duration:e/.03,P:{me:{start:o,end:0,easing:k.Gb}},ba:function(){r(0,v.me)&&n(1),i();}}).start());}),this.Zh=function(){u&&0===d.u()&&u.stop(),s&&0===d.H()&&s.stop();},this.rc=function(e,t){d.rc(e,t);},this.Fb=function(e){return d.Fb(e);},this.pc=function(e){return d.pc(e);},this.zd=function(){return d.zd();},this.absolute=function(e,t){return d.absolute(e,t);},this.Uc=function(e,t){return d.Uc(e,t);},this.scale=function(){return d.scale();},this.i=function(e){return d.T(e);},this.content=function(e,t,n,r){d.content(e,t,n,r);};}function ea(e,t,r){function a(e){var t=[];return E.L(d,function(n){e(n)&&t.push(n.group);}),{groups:t};}function i(e,t){var n=g.options,r=n.Pi,a=n.Oi;n=n.Od;var i=0<r+a?n:0,o=[];return j.u(e,j.i(e,g.options.Qd),function(e,n,l){n="groups"===g.options.Pd?l:n,e.m&&(e=w.K.A(e).wait(i*(a+r*n)).call(t).done(),o.push(e));}),w.K.A({}).Qa(o).Ta();}function o(e){ev||(ev=!0,v.once(function(){ev=!1,g.j.D("repaint:before"),F.Nd(this.pg);},{pg:e}));}function l(e){function t(e,a){var i=e.W;if(e.W=a<=n,e.Fc=a<=r,e.W!==i&&E.ne(e,function(e){e.Vd=!0;}),e.open||e.Va||a++,e=e.m)for(i=0;i<e.length;i++)t(e[i],a);}var n=g.options.Xe,r=Math.min(g.options.Xe,g.options.Sh);if(e)for(var a=0;a<e.length;a++){var i=e[a];t(i,function(e){for(var t=0;e.parent;)e.open||e.Va||t++,e=e.parent;return t;}(i));}else t(d,0);}function c(e,t){var n=[];for((e=f(e,t)).Wh&&g.j.D("model:childrenAttached",E.uc(d)),e.Ji&&D.complete(function(e){ep.eb(e),n.push(e);}),t=e=0;t<n.length;t++){var r=n[t];r.m&&(e+=r.m.length),r.xa=!0,A.i(r);}return e;}function f(e,n){var r,a=n||g.options.Th,i=!1,o=!1,l="flattened"===t.mb;for(r=e?e.reduce(function(e,t){return e.push(t,1),e;},[]):[d,1];0<r.length;)!function(e,t){var n=!e.attribution&&t-(e.Va?1:0)<a;o=o||n,e.Ia=e.Ia||n,e.open||e.Va||t++;var u=e.m;if(!u&&n&&(i=L.T(e)||i,u=e.m,l&&(e.Ma=!0)),u)for(e=0;e<u.length;e++)r.push(u[e],t);}(r.shift(),r.shift());return{Wh:i,Ji:o};}var d,h=this,g={j:new z,options:t,Cd:r},v=new s,w=new P(v),x=u.create(),S=new p(g),T=new er(g),M=new R(g),L=new I(g.options),D=new $(g),F=new X(g,v),N=new W(g);new en(g);var O=new G(g),A=new H(g),B=new q(g),V=new U(g);g.j.subscribe("stage:initialized",function(e,t,n,r){ec.Me(n,r);}),g.j.subscribe("stage:resized",function(e,t,n,r){ec.Ni(e,t,n,r);}),g.j.subscribe("foamtree:attachChildren",c),g.j.subscribe("openclose:changing",l),g.j.subscribe("interaction:reset",function(){eg(!0);}),g.j.subscribe("foamtree:dirty",o),this.M=function(){g.j.D("timeline:initialized",w),d=L.M(),S.M(e),M.M(),F.M(),N.M(),O.M(),A.M(),T.M(),B.M(),V.M();},this.Za=function(){w.i(),eh.stop(),v.i(),S.Za();};var K="groupLabelFontFamily groupLabelFontStyle groupLabelFontVariant groupLabelFontWeight groupLabelLineHeight groupLabelHorizontalPadding groupLabelVerticalPadding groupLabelDottingThreshold groupLabelMaxTotalHeight groupLabelMinFontSize groupLabelMaxFontSize groupLabelDecorator".split(" "),Q="rainbowColorDistribution rainbowLightnessDistribution rainbowColorDistributionAngle rainbowLightnessDistributionAngle rainbowColorModelStartPoint rainbowLightnessCorrection rainbowSaturationCorrection rainbowStartColor rainbowEndColor rainbowHueShift rainbowHueShiftCenter rainbowSaturationShift rainbowSaturationShiftCenter rainbowLightnessShift rainbowLightnessShiftCenter attributionTheme".split(" "),Y=!1,J=["groupBorderRadius","groupBorderRadiusCorrection","groupBorderWidth","groupInsetWidth","groupBorderWidthScaling"],Z=["maxGroupLevelsDrawn","maxGroupLabelLevelsDrawn"];this.ig=function(e){g.j.D("options:changed",e),C.bb(e,K)&&E.L(d,function(e){e.Ma=!0;}),C.bb(e,Q)&&(d.Fa=!0),C.bb(e,J)&&(Y=!0),C.bb(e,Z)&&(l(),c());},this.reload=function(){ef.reload();},this.jg=function(e,t){C.defer(function(){if(Y)ec.Ph(e),Y=!1;else{if(t)for(var n=L.u(t),r=n.length-1;0<=r;r--)n[r].N=!0;else d.N=!0;o(e);}});},this.ga=function(){S.u();},this.update=function(e){var t=(e=e?L.u(e):[d]).reduce(function(e,t){return e[t.id]=t,e;},{});e=e.filter(function(e){for(e=e.parent;e;){if(C.has(t,e.id))return!1;e=e.parent;}return!0;}),L.update(e),ec.gj(e);},this.reset=function(){return eg(!1);},this.T=F.i,this.Ja=(ee={},function(e,t){return(e=L.i(e))?M.Wc(ee,e,t):null;}),this.wa=(et={x:0,y:0},ea={x:0,y:0},function(e,t){return(e=L.i(e))?(et.x=t.x,et.y=t.y,e.transformPoint(et,et),T.Uc(et,et),ea.x=et.x,ea.y=et.y,ea):null;}),this.sa=(ei={},function(e){return(e=L.i(e))?M.Yc(ei,e):null;}),this.hg=(eo={},function(e){return(e=L.i(e))?M.Xc(eo,e):null;}),this.ta=(el={},function(){return T.i(el);}),this.lg=function(){this.H({groups:a(function(e){return e.group.selected;}),newState:!0,keepPrevious:!1}),this.u({groups:a(function(e){return e.group.open;}),newState:!0,keepPrevious:!1}),this.i({groups:a(function(e){return e.group.exposed;}),newState:!0,keepPrevious:!1});},this.Ka=function(){return a(function(e){return e.U;});},this.i=function(e){return ef.submit(function(){return O.Vb(L.H(e,"exposed",!1),!1,!0,!1);});},this.pb=function(){return a(function(e){return e.open;});},this.u=function(e){return ef.submit(function(){return B.Bb(L.H(e,"open",!0),!1,!1);});},this.Lb=function(){return a(function(e){return e.selected;});},this.H=function(e){return ef.submit(function(){return V.select(L.H(e,"selected",!0),!1),(new b).resolve().promise();});},this.ng=function(e){return(e=L.i(e))?e===d?T.reset(t.ob,k.ia(t.Kb)):T.cg(e,t.Yb,t.ob,k.ia(t.Kb)):(new b).resolve().promise();},this.ua=function(e,t){return(e=L.u(e))?(t=c(e,t),l(e),t):0;},this.Vc=function(e){return N.Lb[e];},this.mg=function(){var e=n;return{frames:e.frames,totalTime:e.totalTime,lastFrameTime:e.rd,lastInterFrameTime:e.sd,fps:e.ve};};var ee,et,ea,ei,eo,el,eu,es,ec=function(){var e,n;function r(r,i){var o=r||e,l=i||n;e=o,n=l,(r=t.Rb&&t.Rb.boundary)&&2<r.length?d.C=r.map(function(e){return{x:o*e.x,y:l*e.y};}):d.C=[{x:0,y:0},{x:o,y:0},{x:o,y:l},{x:0,y:l}],a();}function a(){d.Z=!0,d.G=d.C,d.F=_.F(d.C,d.F),d.O=d,_.Ja(d.C,d.O);}return{Me:r,Ni:function(e,t,n,a){ep.stop();var i=n/e,l=a/t;E.oe(d,function(e){e.x=e.x*i+(Math.random()-.5)*n/1e3,e.y=e.y*l+(Math.random()-.5)*a/1e3;}),r(n,a),d.Ea=!0,D.step(ep.eb,!0,!1,function(e){var t=e.m;if(t){D.Eb(e);for(var n=t.length-1;0<=n;n--){var r=t[n];r.w=r.hc;}e.Ea=!0;}})?o(!1):(D.fc(d),g.options.Md?(o(!1),eh.Kf(),eh.Oc()):(D.complete(ep.eb),d.Fa=!0,o(!1)));},Ph:function(e){var t=!1;return d.empty()||(a(),eh.xb()||(t=D.step(ep.eb,!1,!1),o(e))),t;},gj:function(e){e.forEach(function(e){E.za(e,function(e){e.empty()||D.Eb(e);}),D.fc(e),g.options.Md?(eh.Kf(),E.za(e,function(e){e.empty()||ep.grow(e);})):(E.za(e,function(e){e.empty()||ep.eb(e);}),D.complete(ep.eb),e.Fa=!0,o(!1));});}};}(),ef=function(){function e(){if(0===t.Gd&&T.reset(0),g.options.Af(t.Rb),ec.Me(),L.load(t.Rb),f(),l(),g.j.D("model:loaded",d,E.uc(d)),!d.empty()){if(d.open=!0,d.Ia=!0,t.Md)var e,r,a,o,u=eh.Oc();else eh.ai(),E.za(d,function(e){e.xa=!1;}),(r=new y((e=new b).resolve)).i(),d.xa=!0,A.i(d).then(r.u),i(d,function e(){this.R&&this.C&&(this.Z=this.xa=!0,r.i(),A.i(this).then(r.u),r.i(),i(this,e).then(r.u));}),u=e.promise();a=t.Oa,o=t.Kc,t.Oa=0,t.Kc=0,h.lg(),t.Oa=a,t.Kc=o,0<t.Od?(F.clear(),S.i(1)):u=m([u,n(1)]);}g.options.zf(t.Rb),u&&(g.options.Df(),u.then(function(){F.u(function(){v.once(g.options.Cf);});}));}function n(e,n){return 0===t.re||n?(S.i(e),(new b).resolve().promise()):w.K.A({opacity:S.i()}).Xd(2).fa({duration:t.re,P:{opacity:{end:e,easing:k.ia(t.Dg)}},ba:function(){S.i(this.opacity);}}).Ta();}function r(){for(var e=0;e<o.length;e++){var t=o[e],n=t.action();C.has(n,"then")?n.then(t.ge.resolve):t.ge.resolve();}o=[];}var a=!1,o=[];return{reload:function(){a||(d.empty()?e():(ep.stop(),w.i(),eh.stop(),a=!0,m(0<t.Gd?[A.u(),eg(!1)]:[n(0)]).then(function(){n(0,!0),a=!1,e(),C.defer(r);})));},submit:function(e){if(a){var t=new b;return o.push({action:e,ge:t}),t.promise();}return e();}};}(),ed=new y(function(){es.resolve();}),eh=function(){function e(){return l||(ed.initial()&&(es=new b),ed.i(),n(),l=!0,v.repeat(r)),es.promise();}function n(){a=x.now();}function r(){var n=x.now()-a>t.Mi;return n=D.step(function(t){t.xa=!0,ep.grow(t),ed.i(),A.i(t).then(ed.u),ed.i(),i(t,function(){this.Ia=!0,e();}).then(ed.u);},!0,n)||n,o(!0),n&&(l=!1,ed.u()),n;}var a,l=!1;return{ai:function(){D.complete(ep.eb);},Oc:e,Kf:n,xb:function(){return!ed.initial();},stop:function(){v.cancel(r),l=!1,ed.clear();}};}(),ep=function(){function e(e){var t=!e.empty();if(e.xa=!0,t){for(var n=e.m,r=n.length-1;0<=r;r--){var a=n[r];a.w=a.hc;}e.Ea=!0;}return t;}var t=[];return{grow:function(n){var r=g.options,a=r.Yg;0<a?j.u(n,j.i(n,g.options.Qd),function(e,n,i){n="groups"===g.options.Pd?i:n,ed.i(),t.push(w.K.A(e).wait(n*r.Xg*a).fa({duration:a,P:{w:{start:e.Wf,end:e.hc,easing:k.ia(r.Zg)}},ba:function(){this.w=Math.max(0,this.w),this.parent.Ea=!0,eh.Oc();}}).Xa(ed.u).start());}):e(n)&&eh.Oc();},eb:e,stop:function(){for(var e=t.length-1;0<=e;e--)t[e].stop();t=[];}};}(),eg=(eu=!1,function(e){if(eu)return(new b).resolve().promise();eu=!0;var n=[];n.push(T.reset(t.ob,k.ia(t.Kb)));var r=new b;return O.Vb({m:[],Ca:!1,Ba:!1},e,!1,!0).then(function(){B.Bb({m:[],Ca:!1,Ba:!1},e,!1).then(r.resolve);}),n.push(r.promise()),m(n).then(function(){eu=!1,e&&t.Ef();});}),ev=!1;}function ei(){return{version:"3.5.1",build:"bugfix/3.5.x/26b14206",brandingAllowed:!1};}Y.ke={width:445.2,height:533.5},l.md(function(){window.CarrotSearchFoamTree=function(e){function t(e){function t(e,t){return C.has(n,e)?(t(n[e]),delete n[e],1):0;}if(0==arguments.length)return 0;if(1==arguments.length)var n=C.extend({},arguments[0]);else 2==arguments.length&&((n={})[arguments[0]]=arguments[1]);o&&o.validate(n,u.Qh);var a=0;s&&(a+=t("selection",s.H)+t("open",s.u)+t("exposure",s.i));var i={};return C.Aa(n,function(e,t){(l[t]!==e||C.wb(e))&&(i[t]=e,a++),l[t]=e;}),0<a&&r(i),a;}function n(e,t){var n=l[e="on"+e.charAt(0).toUpperCase()+e.slice(1)];l[e]=t(Array.isArray(n)?n:[n]),(t={})[e]=l[e],r(t);}function r(e){(function(){function t(t,n){return C.has(e,t)||void 0===n?T.A(l[t],i):n;}u.Qh=l.logging,u.Rb=l.dataObject,u.B=l.pixelRatio,u.nb=l.wireframePixelRatio,u.mb=l.stacking,u.Ag=l.descriptionGroup,u.Tb=l.descriptionGroupType,u.qc=l.descriptionGroupPosition,u.Bg=l.descriptionGroupDistanceFromCenter,u.Sb=l.descriptionGroupSize,u.ie=l.descriptionGroupMinHeight,u.he=l.descriptionGroupMaxHeight,u.je=l.descriptionGroupPolygonDrawn,u.Dc=l.layout,u.ac=l.layoutByWeightOrder,u.Zi=l.showZeroWeightGroups,u.Ce=l.groupMinDiameter,u.Ld=l.rectangleAspectRatioPreference,u.Li=l.initializer||l.relaxationInitializer,u.Mi=l.relaxationMaxDuration,u.Md=l.relaxationVisible,u.If=l.relaxationQualityThreshold,u.qh=l.groupResizingBudget,u.Yg=l.groupGrowingDuration,u.Xg=l.groupGrowingDrag,u.Zg=l.groupGrowingEasing,u.Ig=l.groupBorderRadius,u.$a=l.groupBorderWidth,u.La=l.groupBorderWidthScaling,u.jd=l.groupInsetWidth,u.Jg=l.groupBorderRadiusCorrection,u.ab=l.groupStrokeWidth,u.yc=l.groupSelectionOutlineWidth,u.uh=l.groupSelectionOutlineColor,u.kd=l.groupSelectionOutlineShadowSize,u.De=l.groupSelectionOutlineShadowColor,u.rh=l.groupSelectionFillHueShift,u.th=l.groupSelectionFillSaturationShift,u.sh=l.groupSelectionFillLightnessShift,u.Fe=l.groupSelectionStrokeHueShift,u.He=l.groupSelectionStrokeSaturationShift,u.Ge=l.groupSelectionStrokeLightnessShift,u.Wg=l.groupFillType,u.Sg=l.groupFillGradientRadius,u.Pg=l.groupFillGradientCenterHueShift,u.Rg=l.groupFillGradientCenterSaturationShift,u.Qg=l.groupFillGradientCenterLightnessShift,u.Tg=l.groupFillGradientRimHueShift,u.Vg=l.groupFillGradientRimSaturationShift,u.Ug=l.groupFillGradientRimLightnessShift,u.ld=l.groupStrokeType,u.ab=l.groupStrokeWidth,u.Ie=l.groupStrokePlainHueShift,u.Ke=l.groupStrokePlainSaturationShift,u.Je=l.groupStrokePlainLightnessShift,u.zh=l.groupStrokeGradientRadius,u.vh=l.groupStrokeGradientAngle,u.Ah=l.groupStrokeGradientUpperHueShift,u.Ch=l.groupStrokeGradientUpperSaturationShift,u.Bh=l.groupStrokeGradientUpperLightnessShift,u.wh=l.groupStrokeGradientLowerHueShift,u.yh=l.groupStrokeGradientLowerSaturationShift,u.xh=l.groupStrokeGradientLowerLightnessShift,u.$g=l.groupHoverFillHueShift,u.bh=l.groupHoverFillSaturationShift,u.ah=l.groupHoverFillLightnessShift,u.ze=l.groupHoverStrokeHueShift,u.Be=l.groupHoverStrokeSaturationShift,u.Ae=l.groupHoverStrokeLightnessShift,u.Pa=l.groupExposureScale,u.Og=l.groupExposureShadowColor,u.ye=l.groupExposureShadowSize,u.Yb=l.groupExposureZoomMargin,u.Eh=l.groupUnexposureLightnessShift,u.Fh=l.groupUnexposureSaturationShift,u.Dh=l.groupUnexposureLabelColorThreshold,u.Oa=l.exposeDuration,u.Wb=l.exposeEasing,u.Kc=l.openCloseDuration,u.Kg=T.A(l.groupColorDecorator,i),u.Lg=l.groupColorDecorator!==C.qa,u.fh=T.A(l.groupLabelDecorator,i),u.gh=l.groupLabelDecorator!==C.qa,u.lh=T.A(l.groupLabelLayoutDecorator,i),u.mh=l.groupLabelLayoutDecorator!==C.qa,u.Mg=T.A(l.groupContentDecorator,i),u.xc=l.groupContentDecorator!==C.qa,u.Ng=l.groupContentDecoratorTriggering,u.Hi=l.rainbowStartColor,u.Ai=l.rainbowEndColor,u.yi=l.rainbowColorDistribution,u.zi=l.rainbowColorDistributionAngle,u.Di=l.rainbowLightnessDistributionAngle,u.Ei=l.rainbowLightnessShift,u.Fi=l.rainbowLightnessShiftCenter,u.Gi=l.rainbowSaturationCorrection,u.Ci=l.rainbowLightnessCorrection,u.Ff=l.parentFillOpacity,u.$h=l.parentStrokeOpacity,u.Gf=l.parentLabelOpacity,u.Hf=l.parentOpacityBalancing,u.ph=l.groupLabelUpdateThreshold,u.hh=l.groupLabelFontFamily,u.ih=l.groupLabelFontStyle,u.jh=l.groupLabelFontVariant,u.kh=l.groupLabelFontWeight,u.oh=l.groupLabelMinFontSize,u.vj=l.groupLabelMaxFontSize,u.uj=l.groupLabelLineHeight,u.tj=l.groupLabelHorizontalPadding,u.xj=l.groupLabelVerticalPadding,u.wj=l.groupLabelMaxTotalHeight,u.eh=l.groupLabelDarkColor,u.nh=l.groupLabelLightColor,u.dh=l.groupLabelColorThreshold,u.ij=l.wireframeDrawMaxDuration,u.jj=l.wireframeLabelDrawing,u.hj=l.wireframeContentDecorationDrawing,u.eg=l.wireframeToFinalFadeDuration,u.kj=l.wireframeToFinalFadeDelay,u.Eg=l.finalCompleteDrawMaxDuration,u.Fg=l.finalIncrementalDrawMaxDuration,u.te=l.finalToWireframeFadeDuration,u.Zc=l.androidStockBrowserWorkaround,u.Le=l.incrementalDraw,u.Uh=l.maxGroups,u.Th=l.maxGroupLevelsAttached,u.Xe=l.maxGroupLevelsDrawn,u.Sh=l.maxGroupLabelLevelsDrawn,u.Qd=l.rolloutStartPoint,u.Pd=l.rolloutMethod,u.Qi=l.rolloutEasing,u.Od=l.rolloutDuration,u.Mf=l.rolloutScalingStrength,u.Of=l.rolloutTranslationXStrength,u.Pf=l.rolloutTranslationYStrength,u.Lf=l.rolloutRotationStrength,u.Nf=l.rolloutTransformationCenter,u.Ui=l.rolloutPolygonDrag,u.Vi=l.rolloutPolygonDuration,u.Ri=l.rolloutLabelDelay,u.Si=l.rolloutLabelDrag,u.Ti=l.rolloutLabelDuration,u.Pi=l.rolloutChildGroupsDrag,u.Oi=l.rolloutChildGroupsDelay,u.ri=l.pullbackStartPoint,u.ki=l.pullbackMethod,u.gi=l.pullbackEasing,u.Bj=l.pullbackType,u.Gd=l.pullbackDuration,u.pi=l.pullbackScalingStrength,u.ti=l.pullbackTranslationXStrength,u.ui=l.pullbackTranslationYStrength,u.oi=l.pullbackRotationStrength,u.si=l.pullbackTransformationCenter,u.li=l.pullbackPolygonDelay,u.mi=l.pullbackPolygonDrag,u.ni=l.pullbackPolygonDuration,u.hi=l.pullbackLabelDelay,u.ii=l.pullbackLabelDrag,u.ji=l.pullbackLabelDuration,u.di=l.pullbackChildGroupsDelay,u.ei=l.pullbackChildGroupsDrag,u.fi=l.pullbackChildGroupsDuration,u.re=l.fadeDuration,u.Dg=l.fadeEasing,u.lj=l.zoomMouseWheelFactor,u.ob=l.zoomMouseWheelDuration,u.Kb=l.zoomMouseWheelEasing,u.Vh=l.maxLabelSizeForTitleBar,u.bj=l.titleBarFontFamily,u.Zf=l.titleBarBackgroundColor,u.$f=l.titleBarTextColor,u.cj=l.titleBarMinFontSize,u.Wd=l.titleBarMaxFontSize,u.dj=l.titleBarTextPaddingLeftRight,u.ej=l.titleBarTextPaddingTopBottom,u.aj=l.titleBarDecorator,u.pj=l.attributionText,u.mj=l.attributionLogo,u.oj=l.attributionLogoScale,u.qj=l.attributionUrl,u.$d=l.attributionPosition,u.sg=l.attributionDistanceFromCenter,u.tg=l.attributionWeight,u.ae=l.attributionTheme,u.Ne=l.interactionHandler,u.Af=t("onModelChanging",u.Af),u.zf=t("onModelChanged",u.zf),u.Bf=t("onRedraw",u.Bf),u.Df=t("onRolloutStart",u.Df),u.Cf=t("onRolloutComplete",u.Cf),u.Ad=t("onRelaxationStep",u.Ad),u.Ef=t("onViewReset",u.Ef),u.sf=t("onGroupOpenOrCloseChanging",u.sf),u.rf=t("onGroupOpenOrCloseChanged",u.rf),u.jf=t("onGroupExposureChanging",u.jf),u.hf=t("onGroupExposureChanged",u.hf),u.uf=t("onGroupSelectionChanging",u.uf),u.tf=t("onGroupSelectionChanged",u.tf),u.lf=t("onGroupHover",u.lf),u.nf=t("onGroupMouseMove",u.nf),u.cf=t("onGroupClick",u.cf),u.df=t("onGroupDoubleClick",u.df),u.kf=t("onGroupHold",u.kf),u.qf=t("onGroupMouseWheel",u.qf),u.pf=t("onGroupMouseUp",u.pf),u.mf=t("onGroupMouseDown",u.mf),u.gf=t("onGroupDragStart",u.gf),u.ef=t("onGroupDrag",u.ef),u.ff=t("onGroupDragEnd",u.ff),u.xf=t("onGroupTransformStart",u.xf),u.vf=t("onGroupTransform",u.vf),u.wf=t("onGroupTransformEnd",u.wf),u.yf=t("onKeyUp",u.yf);})(),u.Ii=v.u(u.Hi),u.Bi=v.u(u.Ai),u.Ee=v.u(u.De),u.nj=null,s&&(s.ig(e),C.has(e,"dataObject")&&s.reload());}function a(e){return function(){return e.apply(this,arguments).Gg(i);};}var i=this,o=window.CarrotSearchFoamTree.asserts,l=C.extend({},window.CarrotSearchFoamTree.defaults),u={};t(e),(e=l.element||document.getElementById(l.id))||A.i("Element to embed FoamTree in not found."),l.element=e;var s=new ea(e,u,l);s.M();var c={get:function(e){return 0==arguments.length?C.extend({},l):function(e,t){if(!o||o.exists(e))switch(e){case"selection":return s.Lb();case"open":return s.pb();case"exposure":return s.Ka();case"state":return s.sa.apply(this,t);case"geometry":return s.Ja.apply(this,t);case"hierarchy":return s.hg.apply(this,t);case"containerCoordinates":return s.wa.apply(this,t);case"imageData":return s.T.apply(this,t);case"viewport":return s.ta();case"times":return s.mg();case"onModelChanged":case"onRedraw":case"onRolloutStart":case"onRolloutComplete":case"onRelaxationStep":case"onGroupHover":case"onGroupOpenOrCloseChanging":case"onGroupExposureChanging":case"onGroupSelectionChanging":case"onGroupSelectionChanged":case"onGroupClick":case"onGroupDoubleClick":case"onGroupHold":return Array.isArray(e=l[e])?e:[e];default:return l[e];}}(arguments[0],Array.prototype.slice.call(arguments,1));},set:t,on:function(e,t){n(e,function(e){return e.push(t),e;});},off:function(e,t){n(e,function(e){return e.filter(function(e){return e!==t;});});},resize:s.ga,redraw:s.jg,update:s.update,attach:s.ua,select:a(s.H),expose:a(s.i),open:a(s.u),reset:a(s.reset),zoom:a(s.ng),trigger:function(e,t){(e=s.Vc(e))&&e(t);},dispose:function(){function e(){throw"FoamTree instance disposed";}s.Za(),C.Aa(c,function(t,n){"dispose"!==n&&(i[n]=e);});}};C.Aa(c,function(e,t){i[t]=e;}),s.reload();},window["CarrotSearchFoamTree.asserts"]&&(window.CarrotSearchFoamTree.asserts=window["CarrotSearchFoamTree.asserts"],delete window["CarrotSearchFoamTree.asserts"]),window.CarrotSearchFoamTree.supported=!0,window.CarrotSearchFoamTree.version=ei,window.CarrotSearchFoamTree.defaults=Object.freeze({id:void 0,element:void 0,logging:!1,dataObject:void 0,pixelRatio:1,wireframePixelRatio:1,layout:"relaxed",layoutByWeightOrder:!0,showZeroWeightGroups:!0,groupMinDiameter:10,rectangleAspectRatioPreference:-1,relaxationInitializer:"fisheye",relaxationMaxDuration:3e3,relaxationVisible:!1,relaxationQualityThreshold:1,stacking:"hierarchical",descriptionGroup:"auto",descriptionGroupType:"stab",descriptionGroupPosition:225,descriptionGroupDistanceFromCenter:1,descriptionGroupSize:.125,descriptionGroupMinHeight:35,descriptionGroupMaxHeight:.5,descriptionGroupPolygonDrawn:!1,maxGroups:5e4,maxGroupLevelsAttached:4,maxGroupLevelsDrawn:4,maxGroupLabelLevelsDrawn:3,groupGrowingDuration:0,groupGrowingEasing:"bounce",groupGrowingDrag:0,groupResizingBudget:2,groupBorderRadius:.15,groupBorderWidth:4,groupBorderWidthScaling:.6,groupInsetWidth:6,groupBorderRadiusCorrection:1,groupSelectionOutlineWidth:5,groupSelectionOutlineColor:"#222",groupSelectionOutlineShadowSize:0,groupSelectionOutlineShadowColor:"#fff",groupSelectionFillHueShift:0,groupSelectionFillSaturationShift:0,groupSelectionFillLightnessShift:0,groupSelectionStrokeHueShift:0,groupSelectionStrokeSaturationShift:0,groupSelectionStrokeLightnessShift:-10,groupFillType:"gradient",groupFillGradientRadius:1,groupFillGradientCenterHueShift:0,groupFillGradientCenterSaturationShift:0,groupFillGradientCenterLightnessShift:20,groupFillGradientRimHueShift:0,groupFillGradientRimSaturationShift:0,groupFillGradientRimLightnessShift:-5,groupStrokeType:"plain",groupStrokeWidth:1.5,groupStrokePlainHueShift:0,groupStrokePlainSaturationShift:0,groupStrokePlainLightnessShift:-10,groupStrokeGradientRadius:1,groupStrokeGradientAngle:45,groupStrokeGradientUpperHueShift:0,groupStrokeGradientUpperSaturationShift:0,groupStrokeGradientUpperLightnessShift:20,groupStrokeGradientLowerHueShift:0,groupStrokeGradientLowerSaturationShift:0,groupStrokeGradientLowerLightnessShift:-20,groupHoverFillHueShift:0,groupHoverFillSaturationShift:0,groupHoverFillLightnessShift:20,groupHoverStrokeHueShift:0,groupHoverStrokeSaturationShift:0,groupHoverStrokeLightnessShift:-10,groupExposureScale:1.15,groupExposureShadowColor:"rgba(0, 0, 0, 0.5)",groupExposureShadowSize:50,groupExposureZoomMargin:.1,groupUnexposureLightnessShift:65,groupUnexposureSaturationShift:-65,groupUnexposureLabelColorThreshold:.35,exposeDuration:700,exposeEasing:"squareInOut",groupColorDecorator:C.qa,groupLabelDecorator:C.qa,groupLabelLayoutDecorator:C.qa,groupContentDecorator:C.qa,groupContentDecoratorTriggering:"onLayoutDirty",openCloseDuration:500,rainbowColorDistribution:"radial",rainbowColorDistributionAngle:-45,rainbowLightnessDistributionAngle:45,rainbowSaturationCorrection:.1,rainbowLightnessCorrection:.4,rainbowStartColor:"hsla(0, 100%, 55%, 1)",rainbowEndColor:"hsla(359, 100%, 55%, 1)",rainbowLightnessShift:30,rainbowLightnessShiftCenter:.4,parentFillOpacity:.7,parentStrokeOpacity:1,parentLabelOpacity:1,parentOpacityBalancing:!0,wireframeDrawMaxDuration:15,wireframeLabelDrawing:"auto",wireframeContentDecorationDrawing:"auto",wireframeToFinalFadeDuration:500,wireframeToFinalFadeDelay:300,finalCompleteDrawMaxDuration:80,finalIncrementalDrawMaxDuration:100,finalToWireframeFadeDuration:200,androidStockBrowserWorkaround:!1,incrementalDraw:"fast",groupLabelFontFamily:"sans-serif",groupLabelFontStyle:"normal",groupLabelFontWeight:"normal",groupLabelFontVariant:"normal",groupLabelLineHeight:1.05,groupLabelHorizontalPadding:1,groupLabelVerticalPadding:1,groupLabelMinFontSize:6,groupLabelMaxFontSize:160,groupLabelMaxTotalHeight:.9,groupLabelUpdateThreshold:.05,groupLabelDarkColor:"#000",groupLabelLightColor:"#fff",groupLabelColorThreshold:.35,rolloutStartPoint:"center",rolloutEasing:"squareOut",rolloutMethod:"groups",rolloutDuration:2e3,rolloutScalingStrength:-.7,rolloutTranslationXStrength:0,rolloutTranslationYStrength:0,rolloutRotationStrength:-.7,rolloutTransformationCenter:.7,rolloutPolygonDrag:.1,rolloutPolygonDuration:.5,rolloutLabelDelay:.8,rolloutLabelDrag:.1,rolloutLabelDuration:.5,rolloutChildGroupsDrag:.1,rolloutChildGroupsDelay:.2,pullbackStartPoint:"center",pullbackEasing:"squareIn",pullbackMethod:"groups",pullbackDuration:1500,pullbackScalingStrength:-.7,pullbackTranslationXStrength:0,pullbackTranslationYStrength:0,pullbackRotationStrength:-.7,pullbackTransformationCenter:.7,pullbackPolygonDelay:.3,pullbackPolygonDrag:.1,pullbackPolygonDuration:.8,pullbackLabelDelay:0,pullbackLabelDrag:.1,pullbackLabelDuration:.3,pullbackChildGroupsDelay:.1,pullbackChildGroupsDrag:.1,pullbackChildGroupsDuration:.3,fadeDuration:700,fadeEasing:"cubicInOut",zoomMouseWheelFactor:1.5,zoomMouseWheelDuration:500,zoomMouseWheelEasing:"squareOut",maxLabelSizeForTitleBar:8,titleBarFontFamily:null,titleBarFontStyle:"normal",titleBarFontWeight:"normal",titleBarFontVariant:"normal",titleBarBackgroundColor:"rgba(0, 0, 0, 0.5)",titleBarTextColor:"rgba(255, 255, 255, 1)",titleBarMinFontSize:10,titleBarMaxFontSize:40,titleBarTextPaddingLeftRight:20,titleBarTextPaddingTopBottom:15,titleBarDecorator:C.qa,attributionText:null,attributionLogo:null,attributionLogoScale:.5,attributionUrl:"http://carrotsearch.com/foamtree",attributionPosition:"bottomright",attributionDistanceFromCenter:1,attributionWeight:.025,attributionTheme:"light",interactionHandler:l.Ih()?"hammerjs":"builtin",onModelChanging:[],onModelChanged:[],onRedraw:[],onRolloutStart:[],onRolloutComplete:[],onRelaxationStep:[],onViewReset:[],onGroupOpenOrCloseChanging:[],onGroupOpenOrCloseChanged:[],onGroupExposureChanging:[],onGroupExposureChanged:[],onGroupSelectionChanging:[],onGroupSelectionChanged:[],onGroupHover:[],onGroupMouseMove:[],onGroupClick:[],onGroupDoubleClick:[],onGroupHold:[],onGroupMouseWheel:[],onGroupMouseUp:[],onGroupMouseDown:[],onGroupDragStart:[],onGroupDrag:[],onGroupDragEnd:[],onGroupTransformStart:[],onGroupTransform:[],onGroupTransformEnd:[],onKeyUp:[],selection:null,open:null,exposure:null,imageData:null,hierarchy:null,geometry:null,containerCoordinates:null,state:null,viewport:null,times:null}),window.CarrotSearchFoamTree.geometry=Object.freeze({rectangleInPolygon:function(e,t,n,r,a,i,o){return a=C.I(a,1),i=C.I(i,.5),o=C.I(o,.5),e=_.Ka(e,{x:t,y:n},r,i,o)*a,{x:t-e*r*i,y:n-e*o,w:e*r,h:e};},circleInPolygon:function(e,t,n){return _.pb(e,{x:t,y:n});},stabPolygon:function(e,t,n,r){return _.ua(e,{x:t,y:n},r);},polygonCentroid:function(e){return{x:(e=_.u(e,{})).x,y:e.y,area:e.ha};},boundingBox:function(e){for(var t=e[0].x,n=e[0].y,r=e[0].x,a=e[0].y,i=1;i<e.length;i++){var o=e[i];o.x<t&&(t=o.x),o.y<n&&(n=o.y),o.x>r&&(r=o.x),o.y>a&&(a=o.y);}return{x:t,y:n,w:r-t,h:a-n};}});},function(){window.CarrotSearchFoamTree=function(){window.console.error("FoamTree is not supported on this browser.");},window.CarrotSearchFoamTree.supported=!1;});}();let p=window.CarrotSearchFoamTree;var g=n("../node_modules/.pnpm/classnames@2.3.2/node_modules/classnames/index.js"),v=r._(g),b={container:"container-12M_K4gi",hidden:"hidden-k8R8sW5G"};let m=({visible:e,content:t})=>{let[n,r]=(0,f.useState)({left:0,top:0});console.log("visible==",e);let a=(0,f.useRef)({x:0,y:0}),o=(0,f.useRef)(null),l=t=>{a.current={x:t.pageX,y:t.pageY},e&&u();};(0,f.useEffect)(()=>(document.addEventListener("mousemove",l,!0),()=>{document.removeEventListener("mousemove",l,!0);}),[e]);let u=()=>{if(!e)return;let t={left:a.current.x+10,top:a.current.y+30},n=o.current.getBoundingClientRect();t.left+n.width>window.innerWidth&&(t.left=window.innerWidth-n.width),t.top+n.height>window.innerHeight&&(t.top=a.current.y-30-n.height),r(t);},s=(0,v.default)({[b.container]:!0,[b.hidden]:!e});return(0,i.jsx)("div",{ref:o,className:s,style:{left:n.left,top:n.top},children:t});};class y{constructor(e,t){this.name=e,this.parent=t;}get path(){let e=[],t=this;for(;t;)e.push(t.name),t=t.parent;return e.reverse().join("/");}get isRoot(){return!this.parent;}}class w extends y{constructor(e,t){super(e,t),this.children=Object.create(null);}get src(){return Object.prototype.hasOwnProperty.call(this,"_src")||(this._src=this.walk((e,t)=>t+=e.src||"","",!1)),this._src;}get size(){return Object.prototype.hasOwnProperty.call(this,"_size")||(this._size=this.walk((e,t)=>t+e.size,0,!1)),this._size;}getChild(e){return this.children[e];}addChildModule(e){let{name:t}=e,n=this.children[t];n&&n instanceof w||(n?n.mergeData(e.data):(e.parent=this,this.children[t]=e),delete this._size,delete this._src);}addChildFolder(e){return e.parent=this,this.children[e.name]=e,delete this._size,delete this._src,e;}walk(e,t={},n=!0){let r=!1;return Object.values(this.children).forEach(i=>{if(t=n&&i.walk?i.walk(e,t,a):e(i,t,a),r)return!1;}),t;function a(e){return r=!0,e;}}mergeNestedFolders(){if(!this.isRoot){let e;for(;1===(e=Object.keys(this.children)).length;){let t=e[0],n=this.children[t];if(n instanceof this.constructor)this.name+=`/${n.name}`,this.children=n.children;else break;}}this.walk(e=>{e.parent=this,e.mergeNestedFolders&&e.mergeNestedFolders();},null,!1);}toChartData(){return{label:this.name,path:this.path,statSize:this.size,groups:Object.values(this.children).map(e=>e.toChartData())};}}class x extends y{constructor(e,t,n){super(e,n),this.data=t;}get src(){return this.data.parsedSrc;}set src(e){this.data.parsedSrc=e,delete this._gzipSize;}get size(){return this.data.size;}set size(e){this.data.size=e;}get parsedSize(){return this.getParsedSize();}get gzipSize(){return this.getGzipSize();}getParsedSize(){return this.src?this.src.length:void 0;}getGzipSize(){return"_gzipSize"in this||(this._gzipSize=this.src?_gzipSize.default.sync(this.src):void 0),this._gzipSize;}mergeData(e){e.size&&(this.size+=e.size),e.parsedSrc&&(this.src=(this.src||"")+e.parsedSrc);}toChartData(){return{id:this.data.id,label:this.name,path:this.path,statSize:this.size,parsedSize:this.parsedSize,gzipSize:this.gzipSize};}}class S extends w{get parsedSize(){return this.src?this.src.length:0;}get gzipSize(){return Object.prototype.hasOwnProperty.call(this,"_gzipSize")||(this._gzipSize=this.src?_gzipSize.default.sync(this.src):0),this._gzipSize;}addModule(e){let t=e.id.split("!"),n=t[t.length-1].split("/").slice(1).map(e=>"~"===e?"node_modules":e);if(!n)return;let[r,a]=[n.slice(0,-1),n[n.length-1]],i=this;r.forEach(e=>{let t=i.getChild(e);t&&t instanceof S||(t=i.addChildFolder(new S(e))),i=t;});let o=new x(a,e,this);i.addChildModule(o);}toChartData(){return{...super.toChartData(),parsedSize:this.parsedSize,gzipSize:this.gzipSize};}}h.default.createRoot(document.getElementById("root")).render((0,i.jsx)(function(){let e=(0,f.useRef)(null),[t,n]=(0,f.useState)(!1),r=(0,f.useRef)(null),[a,c]=(0,f.useState)(""),[d,h]=(0,f.useState)(""),g=e=>{let t=new S(".");return e.forEach(e=>t.addModule(e)),t.mergeNestedFolders(),t;},v=e=>Object.entries({"index.js":{tree:g(e)}}).map(([e,t])=>{var n;return{label:e,isAsset:!0,statSize:t.tree.size||t.size,parsedSize:null==t?void 0:t.parsedSize,gzipSize:null==t?void 0:t.gzipSize,groups:Object.values(null===(n=t.tree)||void 0===n?void 0:n.children).map(e=>e.toChartData())};}),y=(e,t)=>e.reduce((e,n)=>{if(n[t]){if(n.groups){let e=!n.concatenated;n={...n,groups:e?y(n.groups,t):null};}n.weight=n[t],e.push(n);}return e;},[]),w=(e,t)=>{let n=e[`${t}Size`];return"number"==typeof n?(0,i.jsxs)("div",{className:b.activeSize,children:["Size"," size: ",(0,i.jsx)("strong",{children:function(e,{bits:t=!1,pad:n=!1,base:r=-1,round:a=2,locale:i="",localeOptions:c={},separator:f="",spacer:d=" ",symbols:h={},standard:p="",output:g="string",fullform:v=!1,fullforms:b=[],exponent:m=-1,roundingMethod:y="round",precision:w=0}={}){let x=m,S=Number(e),k=[],C=0,T="";"si"===p?(r=10,p=u):"iec"===p||p===u?r=2:2===r?p="iec":(r=10,p=u);let z=10===r?1e3:1024,M=S<0,P=Math[y];if("bigint"!=typeof e&&isNaN(e))throw TypeError("Invalid number");if("function"!=typeof P)throw TypeError("Invalid rounding method");if(M&&(S=-S),(-1===x||isNaN(x))&&(x=Math.floor(Math.log(S)/Math.log(z)))<0&&(x=0),x>8&&(w>0&&(w+=8-x),x=8),"exponent"===g)return x;if(0===S)k[0]=0,T=k[1]=s.symbol[p][t?o:l][x];else{C=S/(2===r?Math.pow(2,10*x):Math.pow(1e3,x)),t&&(C*=8)>=z&&x<8&&(C/=z,x++);let e=Math.pow(10,x>0?a:0);k[0]=P(C*e)/e,k[0]===z&&x<8&&-1===m&&(k[0]=1,x++),T=k[1]=10===r&&1===x?t?"kbit":"kB":s.symbol[p][t?o:l][x];}if(M&&(k[0]=-k[0]),w>0&&(k[0]=k[0].toPrecision(w)),k[1]=h[k[1]]||k[1],!0===i?k[0]=k[0].toLocaleString():i.length>0?k[0]=k[0].toLocaleString(i,c):f.length>0&&(k[0]=k[0].toString().replace(".",f)),n&&!1===Number.isInteger(k[0])&&a>0){let e=f||".",t=k[0].toString().split(e),n=t[1]||"",r=n.length;k[0]=`${t[0]}${e}${n.padEnd(r+(a-r),"0")}`;}return!0===v&&(k[1]=b[x]?b[x]:s.fullform[p][x]+(t?"bit":"byte")+(1===k[0]?"":"s")),"array"===g?k:"object"===g?{value:k[0],symbol:k[1],exponent:x,unit:T}:k.join(d);}(n)})]}):null;},x=e=>e?(0,i.jsxs)("div",{children:[(0,i.jsx)("div",{children:(0,i.jsx)("strong",{children:e.label})}),(0,i.jsx)("br",{}),w(e,"stat"),e.path&&(0,i.jsxs)("div",{children:["Path: ",(0,i.jsx)("strong",{children:e.path})]}),e.isAsset&&(0,i.jsxs)("div",{children:[(0,i.jsx)("br",{}),(0,i.jsx)("strong",{children:(0,i.jsx)("em",{children:"Right-click to view options related to this chunk"})})]})]}):null,k=t=>{let r=y(v((null==t?void 0:t.chunkModules)||[]),"statSize");return new p({element:e.current,layout:"squarified",stacking:"flattened",pixelRatio:window.devicePixelRatio||1,maxGroups:1/0,maxGroupLevelsDrawn:1/0,maxGroupLabelLevelsDrawn:1/0,maxGroupLevelsAttached:1/0,wireframeLabelDrawing:"always",groupMinDiameter:0,groupLabelVerticalPadding:.2,rolloutDuration:0,pullbackDuration:0,fadeDuration:0,groupExposureZoomMargin:.2,zoomMouseWheelDuration:300,openCloseDuration:200,dataObject:{groups:r},titleBarDecorator(e,t,n){n.titleBarShown=!1;},onMouseLeave(){n(!1);},onGroupHover(e){let{group:t}=e;if(null==t?void 0:t.attribution){n(!1);return;}console.log("group==",t),t?(n(!0),h(x(t))):n(!1);}});},C=()=>{r.current&&r.current.resize();};return(0,f.useEffect)(()=>{var e;return c(null===(e=window)||void 0===e?void 0:e.chartData),window.addEventListener("resize",C),()=>{window.removeEventListener("resize",C);};},[]),(0,f.useEffect)(()=>{if(!a){console.warn("\u6570\u636E\u672A\u521D\u59CB\u5316!!");return;}r.current=k(a);},[a]),(0,i.jsxs)(i.Fragment,{children:[(0,i.jsx)("div",{style:{width:"100vw",height:"100vh"},ref:e}),(0,i.jsx)(m,{visible:t,content:d})]});},{}));}},"index.tsx","undefined"!=typeof globalThis?globalThis:self);
\ No newline at end of file
+!function(e,t,n){var r={};function a(t){var n=r[t];if(void 0!==n)return n.exports;var i={id:t,exports:{}};r[t]=i;var o={id:t,module:i,factory:e[t],require:a};return a.requireInterceptors.forEach(function(e){e(o);}),o.factory.call(o.module.exports,o.module,o.module.exports,o.require),i.exports;}a.requireInterceptors=[],a.e=function(e,t){for(var n in t)Object.defineProperty(e,n,{enumerable:!0,get:t[n]});},a.o=function(e,t){return Object.prototype.hasOwnProperty.call(e,t);},a.m=e,a.c=r,a.es=function(e,t){Object.keys(t).forEach(function(n){"default"===n||a.o(e,n)||Object.defineProperty(e,n,{enumerable:!0,get:t[n]});});},a.d=Object.defineProperty.bind(Object),a.dr=function(e,t){return function(){var n=t();return n&&("object"==typeof n||"function"==typeof n)&&"function"==typeof n.then?n.then(function(t){return e(t);}):e(n);};},a.publicPath="/",function(t){for(var n in t)e[n]=t[n];}({"@swc/helpers/_/_interop_require_default":function(e,t,n){function r(e){return e&&e.__esModule?e:{default:e};}n.d(t,"__esModule",{value:!0}),n.e(t,{_interop_require_default:function(){return r;},_:function(){return r;}});},"@swc/helpers/_/_interop_require_wildcard":function(e,t,n){function r(e){if("function"!=typeof WeakMap)return null;var t=new WeakMap,n=new WeakMap;return(r=function(e){return e?n:t;})(e);}function a(e,t){if(!t&&e&&e.__esModule)return e;if(null===e||"object"!=typeof e&&"function"!=typeof e)return{default:e};var n=r(t);if(n&&n.has(e))return n.get(e);var a={},i=Object.defineProperty&&Object.getOwnPropertyDescriptor;for(var o in e)if("default"!==o&&Object.prototype.hasOwnProperty.call(e,o)){var l=i?Object.getOwnPropertyDescriptor(e,o):null;l&&(l.get||l.set)?Object.defineProperty(a,o,l):a[o]=e[o];}return a.default=e,n&&n.set(e,a),a;}n.d(t,"__esModule",{value:!0}),n.e(t,{_interop_require_wildcard:function(){return a;},_:function(){return a;}});},"@swc/helpers/_/_export_star":function(e,t,n){function r(e,t){return Object.keys(e).forEach(function(n){"default"===n||Object.prototype.hasOwnProperty.call(t,n)||Object.defineProperty(t,n,{enumerable:!0,get:function(){return e[n];}});}),e;}n.d(t,"__esModule",{value:!0}),n.e(t,{_export_star:function(){return r;},_:function(){return r;}});}}),n.__mako_require_module__=a,n.__mako_chunk_load__=a.ensure,a(t);}({"index.tsx":function(e,t,n){n.d(t,"__esModule",{value:!0});var r=n("@swc/helpers/_/_interop_require_default"),a=n("@swc/helpers/_/_interop_require_wildcard"),i=n("node_modules/react/jsx-runtime.js"),o=n("node_modules/react/index.js"),l=a._(o),u=n("node_modules/react-dom/client.js"),s=r._(u);!function(){var e,t,n,r,a,i,o,l=function(){var e=window.navigator.userAgent;try{window.localStorage.setItem("ftap5caavc","ftap5caavc"),window.localStorage.removeItem("ftap5caavc");var t=!0;}catch(e){t=!1;}return{Te:function(){return/webkit/i.test(e);},Nh:function(){return/Mac/.test(e);},Re:function(){return/iPad|iPod|iPhone/.test(e);},Mh:function(){return/Android/.test(e);},Ih:function(){return"ontouchstart"in window||!!window.DocumentTouch&&document instanceof window.DocumentTouch;},Hh:function(){return t;},Gh:function(){var e=document.createElement("canvas");return!(!e.getContext||!e.getContext("2d"));},md:function(e,t){return[].forEach&&l.Gh()?e&&e():t&&t();}};}(),u=function(){function e(){return window.performance&&(window.performance.now||window.performance.mozNow||window.performance.msNow||window.performance.oNow||window.performance.webkitNow)||Date.now;}var t=e();return{create:function(){var t;return{now:(t=e(),function(){return t.call(window.performance);})};},now:function(){return t.call(window.performance);}};}();function s(){function e(){if(!s)throw"AF0";var e=u.now();0!==c&&(a.sd=e-c),c=e,o=o.filter(function(e){return null!==e;}),a.frames++;for(var n=0;n<o.length;n++){var r=o[n];null!==r&&(!0===r.ee.call(r.context)?o[n]=null:C.zc(r.repeat)&&(r.repeat=r.repeat-1,0>=r.repeat&&(o[n]=null)));}o=o.filter(function(e){return null!==e;}),s=!1,t(),0!=(e=u.now()-e)&&(a.rd=e),a.totalTime+=e,a.ve=1e3*a.frames/a.totalTime,c=0===o.length?0:u.now();}function t(){0<o.length&&!s&&(s=!0,i(e));}var r,a=this.Xf={frames:0,totalTime:0,rd:0,sd:0,ve:0};n=a;var i=l.Re()?function(e){window.setTimeout(e,0);}:window.requestAnimationFrame||window.webkitRequestAnimationFrame||window.mozRequestAnimationFrame||window.oRequestAnimationFrame||window.msRequestAnimationFrame||(r=u.create(),function(e){var t=0;window.setTimeout(function(){var n=r.now();e(),t=r.now()-n;},16>t?16-t:0);}),o=[],s=!1,c=0;this.repeat=function(e,n,r){this.cancel(e),o.push({ee:e,context:r,repeat:n}),t();},this.once=function(e,t){this.repeat(e,1,t);},this.cancel=function(e){for(var t=0;t<o.length;t++){var n=o[t];null!==n&&n.ee===e&&(o[t]=null);}},this.i=function(){o=[];};}var c=l.md(function(){function e(){this.buffer=[],this.ma=0,this.current=C.extend({},o);}function t(e){return function(){var t,n=this.buffer,r=this.ma;for(t=0,n[r++]="call",n[r++]=e,n[r++]=arguments.length;t<arguments.length;t++)n[r++]=arguments[t];this.ma=r;};}function n(e){return function(){return a[e].apply(a,arguments);};}var r=document.createElement("canvas");r.width=1,r.height=1;var a=r.getContext("2d");r=["font"];var i="fillStyle globalAlpha globalCompositeOperation lineCap lineDashOffset lineJoin lineWidth miterLimit shadowBlur shadowColor shadowOffsetX shadowOffsetY strokeStyle textAlign textBaseline".split(" "),o={};return i.concat(r).forEach(function(e){o[e]=a[e];}),e.prototype.clear=function(){this.ma=0;},e.prototype.Ga=function(){return 0===this.ma;},e.prototype.Na=function(e){e instanceof c?function(e,t,n){for(var r=0,a=e.ma,i=e.buffer;r<n;)i[a++]=t[r++];e.ma=a;}(e,this.buffer,this.ma):function(e,t,n,r){for(var a=0;a<n;)switch(t[a++]){case"set":e[t[a++]]=t[a++];break;case"setGlobalAlpha":e[t[a++]]=t[a++]*r;break;case"call":var i=t[a++];switch(t[a++]){case 0:e[i]();break;case 1:e[i](t[a++]);break;case 2:e[i](t[a++],t[a++]);break;case 3:e[i](t[a++],t[a++],t[a++]);break;case 4:e[i](t[a++],t[a++],t[a++],t[a++]);break;case 5:e[i](t[a++],t[a++],t[a++],t[a++],t[a++]);break;case 6:e[i](t[a++],t[a++],t[a++],t[a++],t[a++],t[a++]);break;case 7:e[i](t[a++],t[a++],t[a++],t[a++],t[a++],t[a++],t[a++]);break;case 8:e[i](t[a++],t[a++],t[a++],t[a++],t[a++],t[a++],t[a++],t[a++]);break;case 9:e[i](t[a++],t[a++],t[a++],t[a++],t[a++],t[a++],t[a++],t[a++],t[a++]);break;default:throw"CB0";}}}(e,this.buffer,this.ma,C.I(e.globalAlpha,1));},e.prototype.replay=e.prototype.Na,e.prototype.i=function(){return new e;},e.prototype.scratch=e.prototype.i,"arc arcTo beginPath bezierCurveTo clearRect clip closePath drawImage fill fillRect fillText lineTo moveTo putImageData quadraticCurveTo rect rotate scale setLineDash setTransform stroke strokeRect strokeText transform translate".split(" ").forEach(function(n){e.prototype[n]=t(n);}),["measureText","createLinearGradient","createRadialGradient","createPattern","getLineDash"].forEach(function(t){e.prototype[t]=n(t);}),["save","restore"].forEach(function(r){var a=n(r),i=t(r);e.prototype[r]=function(){i.apply(this,arguments),a.apply(this,arguments);};}),r.forEach(function(t){Object.defineProperty(e.prototype,t,{set:function(e){a[t]=e,this.current[t]=e;var n=this.buffer;n[this.ma++]="set",n[this.ma++]=t,n[this.ma++]=e;},get:function(){return this.current[t];}});}),i.forEach(function(t){Object.defineProperty(e.prototype,t,{set:function(e){this.current[t]=e;var n=this.buffer;n[this.ma++]="globalAlpha"===t?"setGlobalAlpha":"set",n[this.ma++]=t,n[this.ma++]=e;},get:function(){return this.current[t];}});}),e.prototype.roundRect=function(e,t,n,r,a){this.beginPath(),this.moveTo(e+a,t),this.lineTo(e+n-a,t),this.quadraticCurveTo(e+n,t,e+n,t+a),this.lineTo(e+n,t+r-a),this.quadraticCurveTo(e+n,t+r,e+n-a,t+r),this.lineTo(e+a,t+r),this.quadraticCurveTo(e,t+r,e,t+r-a),this.lineTo(e,t+a),this.quadraticCurveTo(e,t,e+a,t),this.closePath();},e.prototype.fillPolygonWithText=function(e,t,n,r,a){a||(a={});var i={hb:C.I(a.maxFontSize,N.ya.hb),Gc:C.I(a.minFontSize,N.ya.Gc),lineHeight:C.I(a.lineHeight,N.ya.lineHeight),cb:C.I(a.horizontalPadding,N.ya.cb),Ua:C.I(a.verticalPadding,N.ya.Ua),ib:C.I(a.maxTotalTextHeight,N.ya.ib),fontFamily:C.I(a.fontFamily,N.ya.fontFamily),fontStyle:C.I(a.fontStyle,N.ya.fontStyle),fontVariant:C.I(a.fontVariant,N.ya.fontVariant),fontWeight:C.I(a.fontWeight,N.ya.fontWeight),verticalAlign:C.I(a.verticalAlign,N.ya.verticalAlign)},o=a.cache;if(o&&C.has(a,"area")){o.Qc||(o.Qc=new c);var l=a.area,u=C.I(a.cacheInvalidationThreshold,.05);e=N.de(i,this,r,e,L.F(e,{}),{x:t,y:n},a.allowForcedSplit||!1,a.allowEllipsis||!1,o,l,u,a.invalidateCache);}else e=N.se(i,this,r,e,L.F(e,{}),{x:t,y:n},a.allowForcedSplit||!1,a.allowEllipsis||!1);return e.ka?{fit:!0,lineCount:e.bc,fontSize:e.fontSize,box:{x:e.box.x,y:e.box.y,w:e.box.w,h:e.box.o},ellipsis:e.Ub}:{fit:!1};},e;}),f=l.md(function(){function e(e){this.S=e,this.canvas=e.canvas,this.i=[],this.zb=[void 0],this.vc=["#SIZE#px sans-serif"],this.td=[0],this.ud=[1],this.Rd=[0],this.Sd=[0],this.Td=[0],this.yd=[10],this.Xb=[10],this.Hb=[this.zb,this.vc,this.Xb,this.td,this.ud,this.Rd,this.yd,this.Sd,this.Td],this.da=[1,0,0,1,0,0];}function t(e){var t=e.S,n=e.Hb[0].length-1;e.zb[n]&&(t.setLineDash(e.zb[n]),t.lineDashOffset=e.td[n]),t.miterLimit=e.yd[n],t.lineWidth=e.ud[n],t.shadowBlur=e.Rd[n],t.shadowOffsetX=e.Sd[n],t.shadowOffsetY=e.Td[n],t.font=e.vc[n].replace("#SIZE#",e.Xb[n].toString());}function n(e,t,n){return e*n[0]+t*n[2]+n[4];}function r(e,t,n){return e*n[1]+t*n[3]+n[5];}function a(e,t){for(var n=0;n<e.length;n++)e[n]*=t[0];return e;}e.prototype.save=function(){this.i.push(this.da.slice(0));for(var e=0;e<this.Hb.length;e++){var t=this.Hb[e];t.push(t[t.length-1]);}this.S.save();},e.prototype.restore=function(){this.da=this.i.pop();for(var e=0;e<this.Hb.length;e++)this.Hb[e].pop();this.S.restore(),t(this);},e.prototype.scale=function(e,n){var r=this.da;r[0]*=e,r[1]*=e,r[2]*=n,r[3]*=n,e=this.da,r=(n=this.Hb)[0].length-1;var i=this.zb[r];for(i&&a(i,e),i=2;i<n.length;i++){var o=n[i];o[r]*=e[0];}t(this);},e.prototype.translate=function(e,t){var n=this.da;n[4]+=n[0]*e+n[2]*t,n[5]+=n[1]*e+n[3]*t;},["moveTo","lineTo"].forEach(function(t){e.prototype[t]=function(e,a){var i=this.da;return this.S[t].call(this.S,n(e,a,i),r(e,a,i));};}),["clearRect","fillRect","strokeRect","rect"].forEach(function(t){e.prototype[t]=function(e,a,i,o){var l=this.da;return this.S[t].call(this.S,n(e,a,l),r(e,a,l),i*l[0],o*l[3]);};}),"fill stroke beginPath closePath clip createImageData createPattern getImageData putImageData getLineDash setLineDash".split(" ").forEach(function(t){e.prototype[t]=function(){return this.S[t].apply(this.S,arguments);};}),[{p:"lineDashOffset",a:function(e){return e.td;}},{p:"lineWidth",a:function(e){return e.ud;}},{p:"miterLimit",a:function(e){return e.yd;}},{p:"shadowBlur",a:function(e){return e.Rd;}},{p:"shadowOffsetX",a:function(e){return e.Sd;}},{p:"shadowOffsetY",a:function(e){return e.Td;}}].forEach(function(t){Object.defineProperty(e.prototype,t.p,{set:function(e){var n=t.a(this);e*=this.da[0],n[n.length-1]=e,this.S[t.p]=e;}});});var i=/(\d+(?:\.\d+)?)px/;return Object.defineProperty(e.prototype,"font",{set:function(e){var t=i.exec(e);if(1<t.length){var n=this.Xb.length-1;this.Xb[n]=parseFloat(t[1]),this.vc[n]=e.replace(i,"#SIZE#px"),e=this.S,n=this.vc[n].replace("#SIZE#",(this.Xb[n]*this.da[0]).toString()),e.font=n;}}}),"fillStyle globalAlpha globalCompositeOperation lineCap lineJoin shadowColor strokeStyle textAlign textBaseline".split(" ").forEach(function(t){Object.defineProperty(e.prototype,t,{set:function(e){this.S[t]=e;}});}),e.prototype.arc=function(e,t,a,i,o,l){var u=this.da;this.S.arc(n(e,t,u),r(e,t,u),a*u[0],i,o,l);},e.prototype.arcTo=function(e,t,a,i,o){var l=this.da;this.S.arc(n(e,t,l),r(e,t,l),n(a,i,l),r(a,i,l),o*l[0]);},e.prototype.bezierCurveTo=function(e,t,a,i,o,l){var u=this.da;this.S.bezierCurveTo(n(e,t,u),r(e,t,u),n(a,i,u),r(a,i,u),n(o,l,u),r(o,l,u));},e.prototype.drawImage=function(e,t,a,i,o,l,u,s,c){function f(t,a,i,o){h.push(n(t,a,d)),h.push(r(t,a,d)),i=C.V(i)?e.width:i,o=C.V(o)?e.height:o,h.push(i*d[0]),h.push(o*d[3]);}var d=this.da,h=[e];C.V(l)?f(t,a,i,o):f(l,u,s,c),this.S.drawImage.apply(this.S,h);},e.prototype.quadraticCurveTo=function(e,t,a,i){var o=this.da;this.S.quadraticCurveTo(n(e,t,o),r(e,t,o),n(a,i,o),r(a,i,o));},e.prototype.fillText=function(e,t,a,i){var o=this.da;this.S.fillText(e,n(t,a,o),r(t,a,o),C.zc(i)?i*o[0]:1e20);},e.prototype.setLineDash=function(e){e=a(e.slice(0),this.da),this.zb[this.zb.length-1]=e,this.S.setLineDash(e);},e;}),d=(e=!l.Te()||l.Re()||l.Mh()?1:7,{estimate:function(){function t(e){e.beginPath(),h.Ud(e,l);}var n=document.createElement("canvas");n.width=800,n.height=600;var r=n.getContext("2d"),a=n.width;n=n.height;var i,o=0,l=[{x:0,y:100}];for(i=1;6>=i;i++)o=2*i*Math.PI/6,l.push({x:100*Math.sin(o),y:100*Math.cos(o)});o=100/Object.keys(i={polygonPlainFill:[t,function(e){e.fillStyle="rgb(255, 0, 0)",e.fill();}],polygonPlainStroke:[t,function(e){e.strokeStyle="rgb(128, 0, 0)",e.lineWidth=2,e.closePath(),e.stroke();}],polygonGradientFill:[t,function(e){var t=e.createRadialGradient(0,0,10,0,0,60);t.addColorStop(0,"rgb(255, 0, 0)"),t.addColorStop(1,"rgb(255, 255, 0)"),e.fillStyle=t,e.fill();}],polygonGradientStroke:[t,function(e){var t=e.createLinearGradient(-100,-100,100,100);t.addColorStop(0,"rgb(224, 0, 0)"),t.addColorStop(1,"rgb(32, 0, 0)"),e.strokeStyle=t,e.lineWidth=2,e.closePath(),e.stroke();}],polygonExposureShadow:[t,function(e){e.shadowBlur=50,e.shadowColor="rgba(0, 0, 0, 1)",e.fillStyle="rgba(0, 0, 0, 1)",e.globalCompositeOperation="source-over",e.fill(),e.shadowBlur=0,e.shadowColor="transparent",e.globalCompositeOperation="destination-out",e.fill();}],labelPlainFill:[function(e){e.fillStyle="#000",e.font="24px sans-serif",e.textAlign="center";},function(e){e.fillText("Some text",0,-16),e.fillText("for testing purposes",0,16);}]}).length;var s,c=u.now(),f={};for(s in i){var d=i[s],p=u.now(),g=0;do{r.save(),r.translate(Math.random()*a,Math.random()*n);var v=3*Math.random()+.5;for(r.scale(v,v),v=0;v<d.length;v++)d[v](r);r.restore(),g++,v=u.now();}while(v-p<o)f[s]=e*(v-p)/g;}return f.total=u.now()-c,f;}}),h={Ud:function(e,t){var n=t[0];e.moveTo(n.x,n.y);for(var r=t.length-1;0<r;r--)n=t[r],e.lineTo(n.x,n.y);},Wi:function(e,t,n,r){var a,i=[],o=0,l=t.length;for(a=0;a<l;a++){var u=t[a],s=t[(a+1)%l];u=Math.sqrt(u=L.i(u,s)),i.push(u),o+=u;}n=r*(n+.5*r*o/l),o={};var c={},f={},d=0;for(a=0;a<l;a++){u=t[a],s=t[(a+1)%l],r=t[(a+2)%l];var h=i[(a+1)%l];h=Math.min(.5,n/h),L.ga(1-h,s,r,c),L.ga(h,s,r,f),d++,0==a&&(L.ga(Math.min(.5,n/i[0]),u,s,o),d++,e.moveTo(o.x,o.y)),e.quadraticCurveTo(s.x,s.y,c.x,c.y),e.lineTo(f.x,f.y);}return!0;}};function p(e){function t(){return"embedded"===a.getAttribute("data-foamtree");}function n(e){f[e]&&(f[e].style.opacity=h*d[e]);}function r(e){e.width=Math.round(o*e.B),e.height=Math.round(l*e.B);}var a,i,o,l,u,s,c=[],f={},d={},h=0;this.M=function(n){0!==(a=n).clientWidth&&0!==a.clientHeight||A.i("element has zero dimensions: "+a.clientWidth+" x "+a.clientHeight+"."),a.innerHTML="",o=a.clientWidth,l=a.clientHeight,u=0!==o?o:void 0,s=0!==l?l:void 0,t()&&A.i("visualization already embedded in the element."),a.setAttribute("data-foamtree","embedded"),(i=document.createElement("div")).style.width="100%",i.style.height="100%",i.style.position="relative",a.appendChild(i),e.j.D("stage:initialized",this,i,o,l);},this.Za=function(){t()&&(a.removeAttribute("data-foamtree"),c=[],f={},a.removeChild(i),e.j.D("stage:disposed",this,i));},this.u=function(){if(o=a.clientWidth,l=a.clientHeight,0!==o&&0!==l&&(o!==u||l!==s)){for(var t=c.length-1;0<=t;t--)r(c[t]);e.j.D("stage:resized",u,s,o,l),u=o,s=l;}},this.Ki=function(e,t){e.B=t,r(e);},this.dc=function(t,a,o){var l=document.createElement("canvas");return l.setAttribute("style","position: absolute; top: 0; bottom: 0; left: 0; right: 0; width: 100%; height: 100%; -webkit-touch-callout: none; -webkit-user-select: none; -moz-user-select: none; -ms-user-select: none; user-select: none;"),l.B=a,r(l),c.push(l),f[t]=l,d[t]=1,n(t),o||i.appendChild(l),e.j.D("stage:newLayer",t,l),l;},this.$b=function(e,t){return C.V(t)||(d[e]=t,n(e)),d[e];},this.i=function(e){return C.V(e)||(h=e,C.Aa(f,function(e,t){n(t);})),h;};}function g(e){function t(e,t,n){return m=!0,p.x=0,p.y=0,g.x=0,g.y=0,o=d,l.x=h.x,l.y=h.y,t(),u*=e,s=n?u/o:e,s=Math.max(.25/o,s),!0;}function n(e,t){return t.x=e.x/d+h.x,t.y=e.y/d+h.y,t;}function r(e,t,n,r,a,i,o,l,u){var s=(e-n)*(i-l)-(t-r)*(a-o);return!(1e-5>Math.abs(s))&&(u.x=((e*r-t*n)*(a-o)-(e-n)*(a*l-i*o))/s,u.y=((e*r-t*n)*(i-l)-(t-r)*(a*l-i*o))/s,!0);}var a,i,o=1,l={x:0,y:0},u=1,s=1,c=1,f={x:0,y:0},d=1,h={x:0,y:0},p={x:0,y:0},g={x:0,y:0},v={x:0,y:0,w:0,o:0},b={x:0,y:0,w:0,o:0,scale:1},m=!0;e.j.subscribe("stage:initialized",function(e,t,n,r){a=n,i=r,v.x=0,v.y=0,v.w=n,v.o=r,b.x=0,b.y=0,b.w=n,b.o=r,b.scale=1;}),e.j.subscribe("stage:resized",function(e,t,n,r){function o(e){e.x*=s,e.y*=c;}function u(e){o(e),e.w*=s,e.o*=c;}a=n,i=r;var s=n/e,c=r/t;o(l),o(h),o(f),o(p),o(g),u(v),u(b);}),this.Nb=function(e,r){return t(r,function(){n(e,f);},!0);},this.ga=function(e,n){if(1==Math.round(1e4*n)/1e4){n=v.x-h.x;var a=v.y-h.y;return t(1,function(){},!0),this.i(-n,-a);}return t(n,function(){for(var t=!1;!t;){t=Math.random();var n=Math.random(),a=Math.random(),i=Math.random();t=r(e.x+t*e.w,e.y+n*e.o,v.x+t*v.w,v.y+n*v.o,e.x+a*e.w,e.y+i*e.o,v.x+a*v.w,v.y+i*v.o,f);}},!0);},this.ic=function(e,n){var o=e.w/e.o,l=a/i;if(o<l){var u=e.o*l,s=e.o;o=e.x-.5*(u-e.w),l=e.y;}else o>l?(u=e.w,s=e.w*i/a,o=e.x,l=e.y-.5*(s-e.o)):(o=e.x,l=e.y,u=e.w,s=e.o);return(o-=u*n,l-=s*n,u*=1+2*n,r(o,l,h.x,h.y,o+u,l,h.x+a/d,h.y,f))?t(a/d/u,C.qa,!1):(m=!1,this.i(d*(h.x-o),d*(h.y-l)));},this.i=function(e,t){return e=Math.round(1e4*e)/1e4,t=Math.round(1e4*t)/1e4,g.x+=e/d,g.y+=t/d,0!==e||0!==t;},this.reset=function(e){return e&&this.content(0,0,a,i),this.ga({x:v.x+h.x,y:v.y+h.y,w:v.w/d,o:v.o/d},c/u);},this.Fb=function(e){c=Math.min(1,Math.round(1e4*(e||u))/1e4);},this.u=function(){return h.x<v.x?(v.x-h.x)*d:h.x+a/d>v.x+v.w?-(h.x+a/d-v.x-v.w)*d:0;},this.H=function(){return h.y<v.y?(v.y-h.y)*d:h.y+i/d>v.y+v.o?-(h.y+i/d-v.y-v.o)*d:0;},this.update=function(e){var t=Math.abs(Math.log(s));6>t?t=2:(t/=4,t+=3*t*(1<s?e:1-e)),t=1<s?Math.pow(e,t):1-Math.pow(1-e,t),d=o*(t=(m?t:1)*(s-1)+1),h.x=f.x-(f.x-l.x)/t,h.y=f.y-(f.y-l.y)/t,h.x-=p.x*(1-e)+g.x*e,h.y-=p.y*(1-e)+g.y*e,1===e&&(p.x=g.x,p.y=g.y),b.x=h.x,b.y=h.y,b.w=a/d,b.o=i/d,b.scale=d;},this.T=function(e){return e.x=b.x,e.y=b.y,e.scale=b.scale,e;},this.absolute=function(e,t){return n(e,t||{});},this.Uc=function(e,t){return(t=t||{}).x=(e.x-h.x)*d,t.y=(e.y-h.y)*d,t;},this.pc=function(e){return this.scale()<c/e;},this.zd=function(){return C.od(d,1);},this.scale=function(){return Math.round(1e4*d)/1e4;},this.content=function(e,t,n,r){v.x=e,v.y=t,v.w=n,v.o=r;},this.rc=function(e,t){var n;for(n=e.length-1;0<=n;n--){var r=e[n];r.save(),r.scale(d,d),r.translate(-h.x,-h.y);}for(t(b),n=e.length-1;0<=n;n--)(r=e[n]).restore();};}var v=new function(){function e(e){if("hsl"==e.model||"hsla"==e.model)return e;var t=e.r/=255,n=e.g/=255,r=e.b/=255,a=Math.max(t,n,r),i=Math.min(t,n,r),o=(a+i)/2;if(a==i)var l=i=0;else{var u=a-i;switch(i=.5<o?u/(2-a-i):u/(a+i),a){case t:l=(n-r)/u+(n<r?6:0);break;case n:l=(r-t)/u+2;break;case r:l=(t-n)/u+4;}l/=6;}return e.h=360*l,e.s=100*i,e.l=100*o,e.model="hsl",e;}var t={h:0,s:0,l:0,a:1,model:"hsla"};this.u=function(n){return C.Ac(n)?e(v.ga(n)):C.wb(n)?e(n):t;},this.ga=function(e){var n;return(n=/rgba\(\s*([^,\s]+)\s*,\s*([^,\s]+)\s*,\s*([^,\s]+)\s*,\s*([^,\s]+)\s*\)/.exec(e))&&5==n.length?{r:parseFloat(n[1]),g:parseFloat(n[2]),b:parseFloat(n[3]),a:parseFloat(n[4]),model:"rgba"}:(n=/hsla\(\s*([^,\s]+)\s*,\s*([^,%\s]+)%\s*,\s*([^,\s%]+)%\s*,\s*([^,\s]+)\s*\)/.exec(e))&&5==n.length?{h:parseFloat(n[1]),s:parseFloat(n[2]),l:parseFloat(n[3]),a:parseFloat(n[4]),model:"hsla"}:(n=/rgb\(\s*([^,\s]+)\s*,\s*([^,\s]+)\s*,\s*([^,\s]+)\s*\)/.exec(e))&&4==n.length?{r:parseFloat(n[1]),g:parseFloat(n[2]),b:parseFloat(n[3]),a:1,model:"rgb"}:(n=/hsl\(\s*([^,\s]+)\s*,\s*([^,\s%]+)%\s*,\s*([^,\s%]+)%\s*\)/.exec(e))&&4==n.length?{h:parseFloat(n[1]),s:parseFloat(n[2]),l:parseFloat(n[3]),a:1,model:"hsl"}:(n=/#([0-9a-fA-F]{2})([0-9a-fA-F]{2})([0-9a-fA-F]{2})/.exec(e))&&4==n.length?{r:parseInt(n[1],16),g:parseInt(n[2],16),b:parseInt(n[3],16),a:1,model:"rgb"}:(n=/#([0-9a-fA-F])([0-9a-fA-F])([0-9a-fA-F])/.exec(e))&&4==n.length?{r:17*parseInt(n[1],16),g:17*parseInt(n[2],16),b:17*parseInt(n[3],16),a:1,model:"rgb"}:t;},this.T=function(e){function t(e,t,n){return 0>n&&(n+=1),1<n&&--n,n<1/6?e+6*(t-e)*n:.5>n?t:n<2/3?e+(t-e)*(2/3-n)*6:e;}function n(e,t,n){return Math.sqrt(e*e*.241+t*t*.691+n*n*.068)/255;}if("rgb"==e.model||"rgba"==e.model)return n(e.r,e.g,e.b);var r=e.l/100,a=e.s/100,i=e.h/360;if(0==e.Cj)r=e=i=r;else{a=.5>r?r*(1+a):r+a-r*a;var o=2*r-a;r=t(o,a,i+1/3),e=t(o,a,i),i=t(o,a,i-1/3);}return n(255*r,255*e,255*i);},this.wa=function(e){if(C.Ac(e))return e;if(!C.wb(e))return"#000";switch(e.model){case"hsla":return v.sa(e);case"hsl":return v.H(e);case"rgba":return v.ua(e);case"rgb":return v.ta(e);default:return"#000";}},this.ua=function(e){return"rgba("+(.5+e.r|0)+","+(.5+e.g|0)+","+(.5+e.b|0)+","+e.a+")";},this.ta=function(e){return"rgba("+(.5+e.r|0)+","+(.5+e.g|0)+","+(.5+e.b|0)+")";},this.sa=function(e){return"hsla("+(.5+e.h|0)+","+(.5+e.s|0)+"%,"+(.5+e.l|0)+"%,"+e.a+")";},this.H=function(e){return"hsl("+(.5+e.h|0)+","+(.5+e.s|0)+"%,"+(.5+e.l|0)+"%)";},this.i=function(e,t,n){return"hsl("+(.5+e|0)+","+(.5+t|0)+"%,"+(.5+n|0)+"%)";};};function b(){var e,t=!1,n=[],r=this,a=new function(){this.then=function(a){return a&&(t?a.apply(r,e):n.push(a)),this;},this.Gg=function(e){return r=e,{then:this.then};};};this.resolve=function(){e=arguments;for(var a=0;a<n.length;a++)n[a].apply(r,e);return t=!0,this;},this.promise=function(){return a;};}function m(e){var t=new b,n=e.length;if(0<e.length)for(var r=e.length-1;0<=r;r--)e[r].then(function(){0==--n&&t.resolve();});else t.resolve();return t.promise();}function y(e){var t=0;this.i=function(){t++;},this.u=function(){0==--t&&e();},this.clear=function(){t=0;},this.initial=function(){return 0===t;};}var w={pe:function(e,t,n,r){r=r||{};try{var a=e.getBoundingClientRect();}catch(t){if(!w.Kh){w.Kh=!0,window.console.log("getBoundingClientRect() failed."),window.console.log("Element",e),a=window.console;for(var i=a.log;null!==e.parentElement;)e=e.parentElement;i.call(a,"Attached to DOM",e===document.body.parentElement);}a={left:0,top:0};}return r.x=t-a.left,r.y=n-a.top,r;}};function x(){var e=document,t={};this.addEventListener=function(n,r,a){var i=t[n];i||(i=[],t[n]=i),i.push(r),e.addEventListener(n,r,a);},this.i=function(){C.Aa(t,function(t,n){for(var r=t.length-1;0<=r;r--)e.removeEventListener(n,t[r]);});};}function S(e){function t(e){return function(t){n(t)&&e.apply(this,arguments);};}function n(t){for(t=t.target;t;){if(t===e)return!0;t=t.parentElement;}return!1;}function r(e,t,n){a(e,n=n||{});for(var r=0;r<t.length;r++)t[r].call(e.target,n);return a(e,n),(void 0===n.Db&&n.ci||"prevent"===n.Db)&&e.preventDefault(),n;}function a(t,n){return w.pe(e,t.clientX,t.clientY,n),n.altKey=t.altKey,n.metaKey=t.metaKey,n.ctrlKey=t.ctrlKey,n.shiftKey=t.shiftKey,n.lb=3===t.which,n;}var i=new x,o=[],l=[],u=[],s=[],c=[],f=[],d=[],h=[],p=[],g=[],v=[];this.i=function(e){o.push(e);},this.u=function(e){c.push(e);},this.sa=function(e){l.push(e);},this.wa=function(e){u.push(e);},this.Ka=function(e){s.push(e);},this.ua=function(e){v.push(e);},this.ta=function(e){f.push(e);},this.Ja=function(e){d.push(e);},this.ga=function(e){h.push(e);},this.H=function(e){p.push(e);},this.T=function(e){g.push(e);},this.Za=function(){i.i();};var b,m,y,S,k={x:0,y:0},C={x:0,y:0},T=!1,z=!1;i.addEventListener("mousedown",t(function(t){if(t.target!==e){var n=r(t,u);C.x=n.x,C.y=n.y,k.x=n.x,k.y=n.y,T=!0,r(t,h),m=!1,b=window.setTimeout(function(){100>L.i(k,n)&&(window.clearTimeout(S),r(t,l),m=!0);},400);}})),i.addEventListener("mouseup",function(e){var t=r(e,s);T&&(z&&r(e,g),window.clearTimeout(b),m||z||!n(e)||(t={x:t.x,y:t.y},y&&100>L.i(t,y)?r(e,c):r(e,o),y=t,S=window.setTimeout(function(){y=null;},350)),z=T=!1);}),i.addEventListener("mousemove",function(e){var t=a(e,{});n(e)&&r(e,f,{type:"move"}),k.x=t.x,k.y=t.y,T&&!z&&100<L.i(C,k)&&(z=!0),z&&r(e,p,t);}),i.addEventListener("mouseout",t(function(e){r(e,d,{type:"out"});})),i.addEventListener("wheel",t(function(e){if("deltaY"in e)var t=e.deltaY;else t=0,"detail"in e&&(t=e.detail),"wheelDelta"in e&&(t=-e.wheelDelta/120),"wheelDeltaY"in e&&(t=-e.wheelDeltaY/120),"axis"in e&&e.axis===e.HORIZONTAL_AXIS&&(t=0),t*=10;t&&e.deltaMode&&(t=1===e.deltaMode?67*t:800*t),r(e,v,{ed:-t/200,ci:!0});}),{passive:!1}),i.addEventListener("contextmenu",t(function(e){e.preventDefault();}));}var k=function(){function e(e){return function(t){return Math.pow(t,e);};}function t(e){return function(t){return 1-Math.pow(1-t,e);};}function n(e){return function(t){return 1>(t*=2)?.5*Math.pow(t,e):1-.5*Math.abs(Math.pow(2-t,e));};}function r(e){return function(t){for(var n=0;n<e.length;n++)t=(0,e[n])(t);return t;};}return{ia:function(e){switch(e){case"linear":default:return k.Ab;case"bounce":return k.ug;case"squareIn":return k.Uf;case"squareOut":return k.Gb;case"squareInOut":return k.Vf;case"cubicIn":return k.xg;case"cubicOut":return k.fe;case"cubicInOut":return k.yg;case"quadIn":return k.vi;case"quadOut":return k.xi;case"quadInOut":return k.wi;}},Ab:function(e){return e;},ug:r([n(2),function(e){return 0===e?0:1===e?1:e*(e*(e*(e*(25.9425*e-85.88)+105.78)-58.69)+13.8475);}]),Uf:e(2),Gb:t(2),Vf:n(2),xg:e(3),fe:t(3),yg:n(3),vi:e(2),xi:t(2),wi:n(2),rj:r};}(),C={V:function(e){return void 0===e;},Se:function(e){return null===e;},zc:function(e){return"[object Number]"===Object.prototype.toString.call(e);},Ac:function(e){return"[object String]"===Object.prototype.toString.call(e);},Qe:function(e){return"function"==typeof e;},wb:function(e){return e===Object(e);},od:function(e,t){return 1e-6>e-t&&-.000001<e-t;},Oe:function(e){return C.V(e)||C.Se(e)||C.Ac(e)&&!/\S/.test(e);},has:function(e,t){return e&&e.hasOwnProperty(t);},bb:function(e,t){if(e){for(var n=t.length-1;0<=n;n--)if(e.hasOwnProperty(t[n]))return!0;}return!1;},extend:function(e){return C.Cg(Array.prototype.slice.call(arguments,1),function(t){if(t)for(var n in t)t.hasOwnProperty(n)&&(e[n]=t[n]);}),e;},Aj:function(e,t){return e.map(function(e){return e[t];},[]);},Cg:function(e,t,n){null!=e&&(e.forEach?e.forEach(t,n):C.Aa(e,t,n));},Aa:function(e,t,n){for(var r in e)if(e.hasOwnProperty(r)&&!1===t.call(n,e[r],r,e))break;},I:function(){for(var e=0;e<arguments.length;e++){var t=arguments[e];if(!(C.V(t)||C.zc(t)&&isNaN(t)||C.Ac(t)&&C.Oe(t)))return t;}},Jf:function(e,t){0<=(t=e.indexOf(t))&&e.splice(t,1);},zg:function(e,t,n){var r;return function(){var a=this,i=arguments,o=n&&!r;clearTimeout(r),r=setTimeout(function(){r=null,n||e.apply(a,i);},t),o&&e.apply(a,i);};},defer:function(e){setTimeout(e,1);},yj:function(e){return e;},qa:function(){}},T={A:function(e,t){function n(){var n=[];if(Array.isArray(e))for(var r=0;r<e.length;r++){var a=e[r];a&&n.push(a.apply(t,arguments));}else e&&n.push(e.apply(t,arguments));return n;}return n.empty=function(){return 0===e.length&&!C.Qe(e);},n;}};function z(){var e={};this.subscribe=function(t,n){var r=e[t];r||(r=[],e[t]=r),r.push(n);},this.D=function(t,n){var r=e[t];if(r)for(var a=Array.prototype.slice.call(arguments,1),i=0;i<r.length;i++)r[i].apply(this,a);};}var E={Qf:function(e){for(var t="",n=0;n<e.length;n++)t+=String.fromCharCode(1^e.charCodeAt(n));return t;}};function M(e){function t(t,l,u){var s,c=this,f=0;this.id=a++,this.name=u||"{unnamed on "+t+"}",this.target=function(){return t;},this.xb=function(){return -1!=o.indexOf(c);},this.start=function(){if(!c.xb()){if(-1==o.indexOf(c)){var t=i.now();!0===c.bf(t)&&(o=o.slice()).push(c);}0<o.length&&e.repeat(n);}return this;},this.stop=function(){for(r(c);s<l.length;s++){var e=l[s];e.Xa&&e.gb.call();}return this;},this.bf=function(e){if(f++,0!==l.length){if(C.V(s)){var t=l[s=0];t.before&&t.before.call(t,e,f,c);}else t=l[s];for(;s<l.length;){if(t.gb&&t.gb.call(t,e,f,c))return!0;t.after&&t.after.call(t,e,f,c),C.V(s)&&(s=-1),++s<l.length&&(t=l[s]).before&&t.before.call(t,e,f,c);}}return!1;};}function n(){var t;t=i.now(),o.forEach(function(e){!0!==e.bf(t)&&r(e);}),0==o.length&&e.cancel(n);}function r(e){o=o.filter(function(t){return t!==e;});}var a=0,i=u.create(),o=[];this.i=function(){for(var e=o.length-1;0<=e;e--)o[e].stop();o=[];},this.K=function(){function e(){}function n(e){function t(e){return C.Qe(e)?e.call(void 0):e;}var n,r,a=e.target,o=e.duration,l=e.ba;this.before=function(){for(var o in n={},e.P)a.hasOwnProperty(o)&&(n[o]={start:C.V(e.P[o].start)?a[o]:t(e.P[o].start),end:C.V(e.P[o].end)?a[o]:t(e.P[o].end),easing:C.V(e.P[o].easing)?k.Ab:e.P[o].easing});r=i.now();},this.gb=function(){var e=i.now()-r;for(var t in e=0===o?1:Math.min(o,e)/o,n){var u=n[t];a[t]=u.start+(u.end-u.start)*u.easing(e);}return l&&l.call(a,e),1>e;};}function r(e,t,n){this.Xa=n,this.gb=function(){return e.call(t),!1;};}function a(e){var t;this.before=function(n,r){t=r+e;},this.gb=function(e,n){return n<t;};}function l(e){var t;this.before=function(n){t=n+e;},this.gb=function(e){return e<t;};}function u(e){this.before=function(){e.forEach(function(e){e.start();});},this.gb=function(){for(var t=0;t<e.length;t++)if(e[t].xb())return!0;return!1;};}return e.A=function(e,i){return new function(){function o(t,n,a,i){return n?(C.V(a)&&(a=e),t.Mb(new r(n,a,i))):t;}var s=[];this.Mb=function(e){return s.push(e),this;},this.wait=function(e){return this.Mb(new l(e));},this.Xd=function(e){return this.Mb(new a(e||1));},this.call=function(e,t){return o(this,e,t,!1);},this.Xa=function(e,t){return o(this,e,t,!0);},this.fa=function(t){return C.V(t.target)&&(t.target=e),this.Mb(new n(t));},this.Qa=function(e){return this.Mb(new u(e));},this.done=function(){return new t(e,s,i);},this.start=function(){return this.done().start();},this.i=function(){var e=new b;return this.Xd().call(e.resolve).done(),e.promise();},this.Ta=function(){var e=this.i();return this.start(),e;};};},e.jc=function(t){return(C.V(t)?o.slice():o.filter(function(e){return e.target()===t;})).forEach(function(e){e.stop();}),e.A(t,void 0);},e;}();}var P=((t={oe:function(e,t){if(e.m){e=e.m;for(var n=0;n<e.length;n++)t(e[n],n);}},sc:function(e,n){if(e.m){e=e.m;for(var r=0;r<e.length;r++)if(!1===t.sc(e[r],n)||!1===n(e[r],r))return!1;}}}).L=t.sc,t.tc=function(e,n){if(e.m){e=e.m;for(var r=0;r<e.length;r++)if(!1===n(e[r],r)||!1===t.tc(e[r],n))return!1;}},t.za=function(e,n){if(e.m){for(var r=e.m,a=0;a<r.length;a++)if(!1===t.za(r[a],n))return!1;}return n(e);},t.sj=t.za,t.fd=function(e,n){!1!==n(e)&&t.tc(e,n);},t.uc=function(e,n){var r=[];return t.tc(e,function(e){r.push(e);}),n?r.filter(n):r;},t.ne=function(e,t){for(e=e.parent;e&&!1!==t(e);)e=e.parent;},t.Lh=function(e,t){for(e=e.parent;e&&e!==t;)e=e.parent;return!!e;},t),L=new function(){function e(e,t){var n=e.x-t.x;return n*n+(e=e.y-t.y)*e;}function t(e,t,n){for(var r=0;r<e.length;r++){var a=L.T(e[r],e[r+1]||e[0],t,n,!0);if(a)return a;}}return this.T=function(e,t,n,r,a){var i=e.x;e=e.y;var o=t.x-i;t=t.y-e;var l=n.x,u=n.y;n=r.x-l;var s=r.y-u;if(!(1e-12>=(r=o*s-n*t)&&-.000000000001<=r)&&(l-=i,u-=e,n=(l*s-n*u)/r,0<=(r=(l*t-o*u)/r)&&(a||1>=r)&&0<=n&&1>=n))return{x:i+o*n,y:e+t*n};},this.Lb=function(e,t,n,r){var a=e.x;e=e.y;var i=t.x-a;t=t.y-e;var o=n.x;n=n.y;var l=r.x-o,u=i*(r=r.y-n)-l*t;if(!(1e-12>=u&&-.000000000001<=u)&&0<=(r=((o-a)*r-l*(n-e))/u)&&1>=r)return{x:a+i*r,y:e+t*r};},this.wa=function(e,n,r){var a=L.u(n,{}),i=L.u(r,{}),o=i.x-a.x,l=i.y-a.y,u=[];for(i=0;i<r.length;i++){var s=r[i];u.push({x:s.x-o,y:s.y-l});}for(i=0,r=[],s=[];i<e.length;i++){var c=e[i],f=t(n,a,c);f?(r.push(f),s.push(t(u,a,c))):(r.push(null),s.push(null));}for(i=0;i<e.length;i++)if(f=r[i],c=s[i],f&&c){n=e[i],u=a;var d=f.x-a.x;if(1e-12<(f=Math.sqrt(d*d+(f=f.y-a.y)*f))){d=n.x-a.x;var h=n.y-a.y;f=Math.sqrt(d*d+h*h)/f,n.x=u.x+f*(c.x-u.x),n.y=u.y+f*(c.y-u.y);}else n.x=u.x,n.y=u.y;}for(i=0;i<e.length;i++)s=e[i],s.x+=o,s.y+=l;},this.F=function(e,t){if(0!==e.length){for(var n,r,a=n=e[0].x,i=r=e[0].y,o=e.length;0<--o;)a=Math.min(a,e[o].x),n=Math.max(n,e[o].x),i=Math.min(i,e[o].y),r=Math.max(r,e[o].y);return t.x=a,t.y=i,t.w=n-a,t.o=r-i,t;}},this.H=function(e){return[{x:e.x,y:e.y},{x:e.x+e.w,y:e.y},{x:e.x+e.w,y:e.y+e.o},{x:e.x,y:e.y+e.o}];},this.u=function(e,t){for(var n=0,r=0,a=e.length,i=e[0],o=0,l=1;l<a-1;l++){var u=e[l],s=e[l+1],c=i.y+u.y+s.y,f=(u.x-i.x)*(s.y-i.y)-(s.x-i.x)*(u.y-i.y);n+=f*(i.x+u.x+s.x),r+=f*c,o+=f;}return t.x=n/(3*o),t.y=r/(3*o),t.ha=o/2,t;},this.Ja=function(e,t){this.u(e,t),t.r=Math.sqrt(t.ha/Math.PI);},this.sa=function(e,t){for(var n=0;n<e.length;n++){var r=e[n],a=e[n+1]||e[0];if(0>(t.y-r.y)*(a.x-r.x)-(t.x-r.x)*(a.y-r.y))return!1;}return!0;},this.Vc=function(e,t,n){var r=e.x,a=t.x;if(e.x>t.x&&(r=t.x,a=e.x),a>n.x+n.w&&(a=n.x+n.w),r<n.x&&(r=n.x),r>a)return!1;var i=e.y,o=t.y,l=t.x-e.x;return 1e-7<Math.abs(l)&&(o=(t.y-e.y)/l,e=e.y-o*e.x,i=o*r+e,o=o*a+e),i>o&&(r=o,o=i,i=r),o>n.y+n.o&&(o=n.y+n.o),i<n.y&&(i=n.y),i<=o;},this.Ka=function(n,r,a,i,o){function l(a,i,o){if(r.x===f.x&&r.y===f.y)return o;var l=t(n,r,f),d=Math.sqrt(e(l,r)/(a*a+i*i));return d<s?(s=d,u=l.x,c=l.y,0!==i?Math.abs(c-r.y)/Math.abs(i):Math.abs(u-r.x)/Math.abs(a)):o;}i=C.I(i,.5),o=C.I(o,.5),a=C.I(a,1);var u,s=Number.MAX_VALUE,c=u=0,f={x:0,y:0},d=i*a;a=(1-i)*a,i=1-o,f.x=r.x-d,f.y=r.y-o;var h=l(d,o,h);return f.x=r.x+a,f.y=r.y-o,h=l(a,o,h),f.x=r.x-d,f.y=r.y+i,h=l(d,i,h),f.x=r.x+a,f.y=r.y+i,l(a,i,h);},this.pb=function(e,t){function n(e,t,n){var r=t.x,a=n.x;t=t.y;var i=a-r,o=(n=n.y)-t;return Math.abs(o*e.x-i*e.y-r*n+a*t)/Math.sqrt(i*i+o*o);}for(var r=e.length,a=n(t,e[r-1],e[0]),i=0;i<r-1;i++){var o=n(t,e[i],e[i+1]);o<a&&(a=o);}return a;},this.ua=function(e,t,n){n={x:t.x+Math.cos(n),y:t.y-Math.sin(n)};var r,a=[],i=[],o=e.length;for(r=0;r<o;r++){var l=L.Lb(e[r],e[(r+1)%o],t,n);if(l&&(a.push(l),2==i.push(r)))break;}if(2==a.length){l=a[0],a=a[1];var u=i[0];i=i[1];var s=[a,l];for(r=u+1;r<=i;r++)s.push(e[r]);for(r=[l,a];i!=u;)i=(i+1)%o,r.push(e[i]);return e=[s,r],o=n.x-t.x,r=a.x-l.x,0===o&&(o=n.y-t.y,r=a.y-l.y),(0>o?-1:0<o?1:0)!=(0>r?-1:0<r?1:0)&&e.reverse(),e;}},this.ga=function(e,t,n,r){return r.x=e*(t.x-n.x)+n.x,r.y=e*(t.y-n.y)+n.y,r;},this.i=e,this.ta=function(e,n,r){if(C.zc(n))var a=2*Math.PI*n/360;else switch(a=L.F(e,{}),n){case"random":a=Math.random()*Math.PI*2;break;case"top":a=Math.atan2(-a.o,0);break;case"bottom":a=Math.atan2(a.o,0);break;case"left":a=Math.atan2(0,-a.w);break;case"right":a=Math.atan2(0,a.w);break;case"topleft":a=Math.atan2(-a.o,-a.w);break;case"topright":a=Math.atan2(-a.o,a.w);break;case"bottomleft":a=Math.atan2(a.o,-a.w);break;default:a=Math.atan2(a.o,a.w);}return n=L.u(e,{}),e=t(e,n,{x:n.x+Math.cos(a),y:n.y+Math.sin(a)}),L.ga(r,e,n,{});},this;},_=new function(){function e(e,t){this.face=e,this.Rc=t,this.ec=this.Lc=null;}function t(e,t,n){this.la=[e,t,n],this.J=[,,,];var r=t.y-e.y,a=n.z-e.z,i=t.x-e.x;t=t.z-e.z;var o=n.x-e.x;e=n.y-e.y,this.Ha={x:r*a-t*e,y:t*o-i*a,z:i*e-r*o},this.Ya=[],this.ad=this.visible=!1;}this.i=function(r){var i,o,l=r.length;for(i=0;i<l;i++)r[i].index=i,r[i].Pb=null;var u,s=[];if(0<(u=function(){function n(e,n,r,a){var i={x:n.x-e.x,y:n.y-e.y,z:n.z-e.z},o={x:r.x-e.x,y:r.y-e.y,z:r.z-e.z},l=i.y*o.z-i.z*o.y,u=i.z*o.x-i.x*o.z;return i=i.x*o.y-i.y*o.x,l*a.x+u*a.y+i*a.z>l*e.x+u*e.y+i*e.z?new t(e,n,r):new t(r,n,e);}function a(e,t,n,r){function a(e,t,n){return t=(e=e.la)[0]==t?0:e[1]==t?1:2,e[(t+1)%3]!=n?(t+2)%3:t;}t.J[a(t,n,r)]=e,e.J[a(e,r,n)]=t;}if(4>l)return 0;var i=r[0],o=r[1],u=r[2],c=r[3],f=n(i,o,u,c),d=n(i,u,c,o),h=n(i,o,c,u),p=n(o,u,c,i);for(a(f,d,u,i),a(f,h,i,o),a(f,p,o,u),a(d,h,c,i),a(d,p,u,c),a(h,p,c,o),s.push(f,d,h,p),i=4;i<l;i++)for(o=r[i],u=0;4>u;u++)f=(c=s[u]).la[0],0>(d=c.Ha).x*(o.x-f.x)+d.y*(o.y-f.y)+d.z*(o.z-f.z)&&e.add(c,o);return 4;}())){for(;u<l;){var c=r[u];if(c.Pb){for(i=c.Pb;null!==i;)i.face.visible=!0,i=i.ec;i=0;e:for(;i<s.length;i++){var f=s[i];if(!1===f.visible){var d=f.J;for(o=0;3>o;o++)if(!0===d[o].visible){var h=f,p=o;break e;}}}f=[],d=[];var g=h,v=p;do if(f.push(g),d.push(v),v=(v+1)%3,!1===g.J[v].visible)do for(i=g.la[v],g=g.J[v],o=0;3>o;o++)g.la[o]==i&&(v=o);while(!1===g.J[v].visible&&(g!==h||v!==p))while(g!==h||v!==p)var b=null,m=null;for(i=0;i<f.length;i++){g=f[i],v=d[i];var y=g.J[v],w=g.la[(v+1)%3],x=g.la[v],S=w.y-c.y,k=x.z-c.z,C=w.x-c.x,T=w.z-c.z,z=x.x-c.x,E=x.y-c.y;if(0<a.length){var M=a.pop();M.la[0]=c,M.la[1]=w,M.la[2]=x,M.Ha.x=S*k-T*E,M.Ha.y=T*z-C*k,M.Ha.z=C*E-S*z,M.Ya.length=0,M.visible=!1,M.ad=!0;}else M={la:[c,w,x],J:[,,,],Ha:{x:S*k-T*E,y:T*z-C*k,z:C*E-S*z},Ya:[],visible:!1};s.push(M),g.J[v]=M,M.J[1]=g,null!==m&&(m.J[0]=M,M.J[2]=m),m=M,null===b&&(b=M),function(t,n,r){var a=t.la[0],i=t.Ha,u=i.x,s=i.y;i=i.z;var c=Array(l),f=(n=n.Ya).length;for(o=0;o<f;o++){var d=n[o].Rc;c[d.index]=!0,0>u*(d.x-a.x)+s*(d.y-a.y)+i*(d.z-a.z)&&e.add(t,d);}for(o=0,f=(n=r.Ya).length;o<f;o++)!0!==c[(d=n[o].Rc).index]&&0>u*(d.x-a.x)+s*(d.y-a.y)+i*(d.z-a.z)&&e.add(t,d);}(M,g,y);}for(o=0,m.J[0]=b,b.J[2]=m,i=[];o<s.length;o++)if(!0===(f=s[o]).visible){for(c=0,g=(d=f.Ya).length;c<g;c++)b=(v=d[c]).Lc,m=v.ec,null!==b&&(b.ec=m),null!==m&&(m.Lc=b),null===b&&(v.Rc.Pb=m),n.push(v);f.ad&&a.push(f);}else i.push(f);s=i;}u++;}for(i=0;i<s.length;i++)(f=s[i]).ad&&a.push(f);}return{qe:s};},e.add=function(t,r){if(0<n.length){var a=n.pop();a.face=t,a.Rc=r,a.ec=null,a.Lc=null;}else a=new e(t,r);t.Ya.push(a),null!==(t=r.Pb)&&(t.Lc=a),a.ec=t,r.Pb=a;};for(var n=Array(2e3),r=0;r<n.length;r++)n[r]=new e(null,null);var a=Array(1e3);for(r=0;r<a.length;r++)a[r]={la:[,,,],J:[,,,],Ha:{x:0,y:0,z:0},Ya:[],visible:!1};},D=new function(){function e(e,t,n,r,a,i,o,l){var u=(e-n)*(i-l)-(t-r)*(a-o);if(!(1e-12>Math.abs(u)))return{x:((e*r-t*n)*(a-o)-(e-n)*(a*l-i*o))/u,y:((e*r-t*n)*(i-l)-(t-r)*(a*l-i*o))/u};}return this.i=function(t,n){for(var r=t[0],a=r.x,i=r.y,o=r.x,l=r.y,u=t.length-1;0<u;u--)a=Math.min(a,(r=t[u]).x),i=Math.min(i,r.y),o=Math.max(o,r.x),l=Math.max(l,r.y);if(!(o-a<3*n||l-i<3*n)){e:{for(void 0==(r=!0)&&(r=!1),a=[],i=t.length,o=0;o<=i;o++){l=t[o%i],u=t[(o+1)%i];var s=t[(o+2)%i],c=u.x-l.x,f=u.y-l.y,d=Math.sqrt(c*c+f*f),h=n*c/d,p=n*f/d;if(d=Math.sqrt((c=s.x-u.x)*c+(f=s.y-u.y)*f),c=n*c/d,f=n*f/d,(l=e(l.x-p,l.y+h,u.x-p,u.y+h,u.x-f,u.y+c,s.x-f,s.y+c))&&(a.push(l),s=a.length,r&&3<=s&&(l=a[s-3],u=a[s-2],s=a[s-1],0>(u.x-l.x)*(s.y-l.y)-(s.x-l.x)*(u.y-l.y)))){r=void 0;break e;}}a.shift(),r=3>a.length?void 0:a;}if(!r)e:{for(r=0,a=t.slice(0);r<t.length;r++){if(o=t[r%t.length],l=Math.sqrt((s=(u=t[(r+1)%t.length]).x-o.x)*s+(i=u.y-o.y)*i),s=n*s/l,l=n*i/l,i=o.x-l,o=o.y+s,l=u.x-l,s=u.y+s,0!=a.length){for(u=0,p=i-l,f=o-s,h=[],c=d=!0;u<a.length;u++){var g=p*(o-a[u].y)-(i-a[u].x)*f;1e-12>=g&&-.000000000001<=g&&(g=0),h.push(g),0<g&&(d=!1),0>g&&(c=!1);}if(d)a=[];else if(!c){for(u=0,p=[];u<a.length;u++)f=(u+1)%a.length,d=h[u],c=h[f],0<=d&&p.push(a[u]),(0<d&&0>c||0>d&&0<c)&&p.push(e(a[u].x,a[u].y,a[f].x,a[f].y,i,o,l,s));a=p;}}if(3>a.length){r=void 0;break e;}}r=a;}return r;}},this;},j=new function(){function e(e){for(var t=e[0].x,n=e[0].y,r=t,a=n,i=1;i<e.length;i++){var o=e[i];t=Math.min(t,o.x),n=Math.min(n,o.y),r=Math.max(r,o.x),a=Math.max(a,o.y);}return e=r-t,a-=n,[{x:t+2*e,y:n+2*a,w:0},{x:t+2*e,y:n-2*a,w:0},{x:t-2*e,y:n+2*a,w:0}];}this.i=function(t,n){if(1===t.length)t[0].C=n.slice(0),t[0].J=[];else{var r,a=e(n),i=[];for(r=0;r<a.length;r++){var o=a[r];i.push({x:o.x,y:o.y,z:o.x*o.x+o.y*o.y-o.w});}for(r=0;r<t.length;r++)(o=t[r]).C=null,i.push({x:o.x,y:o.y,z:o.x*o.x+o.y*o.y-o.w});var l=_.i(i).qe;for(function(){for(r=0;r<l.length;r++){var e=l[r],t=e.la,n=t[0],a=t[1],i=t[2];t=n.x;var o=n.y;n=n.z;var u=a.x,s=a.y;a=a.z;var c=i.x,f=i.y;i=i.z;var d=t*(s-f)+u*(f-o)+c*(o-s);e.ea={x:-(o*(a-i)+s*(i-n)+f*(n-a))/d/2,y:-(n*(u-c)+a*(c-t)+i*(t-u))/d/2};}}(),function(e){for(r=0;r<l.length;r++){var t=l[r];t.jb=!L.sa(e,t.ea);}}(n),i=function(e,t){var n,r=Array(t.length);for(n=0;n<r.length;n++)r[n]=[];for(n=0;n<e.length;n++){var a=e[n];if(!(0>a.Ha.z))for(var i=a.J,o=0;o<i.length;o++){var l=i[o];if(!(0>l.Ha.z)){var u=a.la,s=u[(o+1)%3].index;u=u[o].index,2<s&&r[s-3].push([a,l,2<u?t[u-3]:null]);}}}return r;}(l,t),r=0;r<t.length;r++)if(0!==(o=i[r]).length){var u=t[r],s=(o=function(e){var t=[e[0]],n=e[0][0],r=e[0][1],a=e.length,i=1;e:for(;i<a;i++)for(var o=1;o<a;o++){var l=e[o];if(null!==l){if(l[1]===n){if(t.unshift(l),n=l[0],e[o]=null,t.length!==a)continue;break e;}if(l[0]===r&&(t.push(l),r=l[1],e[o]=null,t.length===a))break e;}}return t[0][0]!=t[a-1][1]&&t.push([t[a-1][1],t[0][0]]),t;}(o)).length,c=-1;for(a=0;a<s;a++)o[a][0].jb&&(c=a);if(0<=c)!function(e,t,n,r){var a,i,o=[],l=[],u=n.length,s=t.length,c=0,f=-1,d=-1,h=r;for(r=0;r<u;r++){var p=(h+1)%u,g=n[h][0],v=n[p][0];if(1e-12<L.i(g.ea,v.ea)){if(g.jb&&v.jb){var b=[],m=[];for(a=0;a<s;a++){var y=(c+1)%s;if((i=L.T(t[c],t[y],g.ea,v.ea,!1))&&(m.push(c),2===b.push(i)))break;c=y;}if(2===b.length){if(a=b[1],g=(i=L.i(g.ea,b[0]))<(a=L.i(g.ea,a))?0:1,i=i<a?1:0,a=m[g],-1===f&&(f=a),-1!==d)for(;a!=d;)d=(d+1)%s,o.push(t[d]),l.push(null);o.push(b[g],b[i]),l.push(n[h][2],null),d=m[i];}}else if(g.jb&&!v.jb)for(a=0;a<s;a++){if(y=(c+1)%s,i=L.T(t[c],t[y],g.ea,v.ea,!1)){if(-1!==d)for(b=d;c!=b;)b=(b+1)%s,o.push(t[b]),l.push(null);o.push(i),l.push(n[h][2]),-1===f&&(f=c);break;}c=y;}else if(!g.jb&&v.jb)for(a=0;a<s;a++){if(y=(c+1)%s,i=L.T(t[c],t[y],g.ea,v.ea,!1)){o.push(g.ea,i),l.push(n[h][2],null),d=c;break;}c=y;}else o.push(g.ea),l.push(n[h][2]);}h=p;}if(0==o.length)l=o=null;else if(-1!==d)for(;f!=d;)d=(d+1)%s,o.push(t[d]),l.push(null);e.C=o,e.J=l;}(u,n,o,c);else{c=[];var f=[];for(a=0;a<s;a++)1e-12<L.i(o[a][0].ea,o[(a+1)%s][0].ea)&&(c.push(o[a][0].ea),f.push(o[a][2]));u.C=c,u.J=f;}u.C&&3>u.C.length&&(u.C=null,u.J=null);}}},this.u=function(t,n){var r,a=!1,i=t.length;for(r=0;r<i;r++){var o=t[r];null===o.C&&(a=!0),o.Yd=o.w;}if(a){a=e(n);var l=[];for(o=0,r=t.length;o<a.length;o++){var u=a[o];l.push({x:u.x,y:u.y,z:u.x*u.x+u.y*u.y});}for(o=0;o<r;o++)u=t[o],l.push({x:u.x,y:u.y,z:u.x*u.x+u.y*u.y});for(o=0,u=_.i(l).qe,a=Array(r);o<r;o++)a[o]={};for(o=0,l=u.length;o<l;o++){var s=u[o];if(0<s.Ha.z){var c=s.la,f=c.length;for(s=0;s<f-1;s++){var d=c[s].index-3,h=c[s+1].index-3;0<=d&&0<=h&&(a[d][h]=!0,a[h][d]=!0);}s=c[0].index-3,0<=h&&0<=s&&(a[h][s]=!0,a[s][h]=!0);}}for(o=0;o<r;o++){for(var p in s=a[o],u=t[o],h=Number.MAX_VALUE,l=null,s)s=t[p],h>(c=L.i(u,s))&&(h=c,l=s);u.zj=l,u.$e=Math.sqrt(h);}for(r=0;r<i;r++)p=Math.min(Math.sqrt((o=t[r]).w),.95*o.$e),o.w=p*p;for(this.i(t,n),r=0;r<i;r++)(o=t[r]).Yd!==o.w&&0<o.kc&&(n=Math.min(o.kc,o.Yd-o.w),o.w+=n,o.kc-=n);}};},F=new function(){this.H=function(e){e=e.m;for(var t=0,n=e.length,r=0;r<n;r++){var a=e[r];if(a.C){var i=a.x,o=a.y;L.u(a.C,a),i-=a.x,t<(a=(0<i?i:-i)+(0<(a=o-a.y)?a:-a))&&(t=a);}}return t;},this.i=function(e,t){var n=e.m;switch(t){case"random":return e.m[Math.floor(n.length*Math.random())];case"topleft":var r=(e=n[0]).x+e.y;for(t=1;t<n.length;t++){var a=n[t],i=a.x+a.y;i<r&&(r=i,e=a);}return e;case"bottomright":for(t=1,r=(e=n[0]).x+e.y;t<n.length;t++)(i=(a=n[t]).x+a.y)>r&&(r=i,e=a);return e;default:for(a=n[0],r=i=L.i(e,a),t=n.length-1;1<=t;t--){var o=n[t];(i=L.i(e,o))<r&&(r=i,a=o);}return a;}},this.u=function(e,t,n){var r=e.m;if(r[0].J){var a,i=r.length;for(e=0;e<i;e++)r[e].Sc=!1,r[e].Zb=0;var o=a=0;for((i=[])[a++]=t||r[0],t=t.Zb=0;o<a;)if(!(r=i[o++]).Sc&&r.J){n(r,t++,r.Zb),r.Sc=!0;var l=r.J,u=l.length;for(e=0;e<u;e++){var s=l[e];s&&!0!==s.Sc&&(0===s.Zb&&(s.Zb=r.Zb+1),i[a++]=s);}}}else for(e=0;e<r.length;e++)n(r[e],e,1);};},N=function(){function e(e,l,u,s,c,h,p,g){var v,b,m=C.extend({},i,e);1>e.lineHeight&&(e.lineHeight=1),e=m.fontFamily;var y=m.fontStyle+" "+m.fontVariant+" "+m.fontWeight,w=m.hb,x=m.Gc,S=y+" "+e;m.ue=S;var k={ka:!1,bc:0,fontSize:0};if(l.save(),l.font=y+" 100px "+e,l.textBaseline="middle",l.textAlign="center",void 0===(b=o[v=(v=m).ue])&&(b={},o[v]=b),b[" "]=l.measureText(" ").width,b["\u2026"]=l.measureText("\u2026").width,u=u.trim(),f.text=u,function(e,t,n,r){for(var a,i,o=0;o<e.length;o++)e[o].y===t.y&&(void 0===a?a=o:i=o);void 0===i&&(i=a),a!==i&&e[i].x<e[a].x&&(o=a,a=i,i=o),r.C=e,r.F=t,r.cd=n,r.Ye=a,r.Ze=i;}(s,c,h,d),/[\u3000-\u303f\u3040-\u309f\u30a0-\u30ff\uff00-\uff9f\u4e00-\u9faf\u3400-\u4dbf]/.test(u)?(n(f),t(l,f,S),r(m,f,d,x,w,!0,k)):(t(l,f,S),r(m,f,d,x,w,!1,k),!k.ka&&(p&&(n(f),t(l,f,S)),g||p)&&(g&&(k.Ub=!0),r(m,f,d,x,x,!0,k))),k.ka){var T="",z=0,E=Number.MAX_VALUE,M=Number.MIN_VALUE;a(m,f,k.bc,k.fontSize,d,k.Ub,function(e,t){0<T.length&&" "===t&&(T+=" "),T+=e;},function(e,t,n,r,a){"\xad"===r&&(T+="\u2010"),l.save(),l.translate(h.x,t),e=k.fontSize/100,l.scale(e,e),l.fillText(T,0,0),l.restore(),T=n,z<a&&(z=a),E>t&&(E=t),M<t&&(M=t);}),k.box={x:h.x-z/2,y:E-k.fontSize/2,w:z,o:M-E+k.fontSize},l.restore();}else l.clear&&l.clear();return k;}function t(e,t,n){var r,a=t.text.split(/(\n|[ \f\r\t\v\u2028\u2029]+|\u00ad+|\u200b+)/),i=[],l=[],u=a.length>>>1;for(r=0;r<u;r++)i.push(a[2*r]),l.push(a[2*r+1]);for(2*r<a.length&&(i.push(a[2*r]),l.push(void 0)),n=o[n],r=0;r<i.length;r++)void 0===(u=n[a=i[r]])&&(u=e.measureText(a).width,n[a]=u);t.Tc=i,t.Rf=l;}function n(e){for(var t=e.text.split(/\s+/),n=[],r={".":!0,",":!0,";":!0,"?":!0,"!":!0,":":!0,"\u3002":!0},a=0;a<t.length;a++){var i=t[a];if(3<i.length){var o="";o+=i.charAt(0)+i.charAt(1);for(var l=2;l<i.length-2;l++){var u=i.charAt(l);r[u]||(o+="\u200B"),o+=u;}o+="\u200B"+i.charAt(i.length-2)+i.charAt(i.length-1),n.push(o);}else n.push(i);}e.text=n.join(" ");}function r(e,t,n,r,i,o,l){var u=e.lineHeight,s=Math.max(e.Ua,.001),c=e.ib,f=t.Tc,d=n.cd,h=n.F,p=void 0,g=void 0;switch(e.verticalAlign){case"top":d=h.y+h.o-d.y;break;case"bottom":d=d.y-h.y;break;default:d=2*Math.min(d.y-h.y,h.y+h.o-d.y);}if(0>=(c=Math.min(d,c*n.F.o)))l.ka=!1;else{d=r,i=Math.min(i,c),h=Math.min(1,c/Math.max(20,t.Tc.length));do{var v=(d+i)/2,b=Math.min(f.length,Math.floor((c+v*(u-1-2*s))/(v*u))),m=void 0;if(0<b)for(var y=1,w=b;;){var x=Math.floor((y+w)/2);if(a(e,t,x,v,n,o&&v===r&&x===b,null,null)){if(y===(w=p=m=x))break;}else if((y=x+1)>w)break;}void 0!==m?d=g=v:i=v;}while(i-d>h)return void 0===g?(l.ka=!1,l.fontSize=0):(l.ka=!0,l.fontSize=g,l.bc=p,l.Ub=o&&v===d),l;}}function a(e,t,n,r,a,i,c,f){var d=e.cb,h=r*(e.lineHeight-1),p=Math.max(e.Ua,.001),g=o[e.ue],v=t.Tc;t=t.Rf;var b=a.C,m=a.cd,y=a.Ye,w=a.Ze;switch(e.verticalAlign){case"top":a=m.y+r/2+r*p;var x=1;break;case"bottom":a=m.y-(r*n+h*(n-1))+r/2-r*p,x=-1;break;default:a=m.y-(r*(n-1)/2+h*(n-1)/2),x=1;}for(p=0,e=a;p<n;p++)l[2*p]=a-r/2,l[2*p+1]=a+r/2,a+=x*r+x*h;for(;u.length<l.length;)u.push([,,]);p=l,a=2*n,x=u;var S=b.length,k=y;y=(y-1+S)%S;var C=w;w=(w+1)%S;for(var T=0;T<a;){for(var z=p[T],E=b[y];E.y<z;)k=y,E=b[y=(y-1+S)%S];for(var M=b[w];M.y<z;)C=w,M=b[w=(w+1)%S];var P=b[k],L=b[C];M=L.x+(M.x-L.x)*(z-L.y)/(M.y-L.y),x[T][0]=P.x+(E.x-P.x)*(z-P.y)/(E.y-P.y),x[T][1]=M,T++;}for(p=0;p<n;p++)b=2*p,x=(x=(a=m.x)-u[b][0])<(S=u[b][1]-a)?x:S,b=(S=a-u[b+1][0])<(b=u[b+1][1]-a)?S:b,s[p]=2*(x<b?x:b)-d*r;for(p=0,k=g[" "]*r/100,x=g["\u2026"]*r/100,y=s[d=0],m=0,b=void 0;p<v.length;p++){if(a=v[p],C=t[p],m+(S=g[a]*r/100)<y&&v.length-p>=n-d&&"\n"!=b)m+=S," "===C&&(m+=k),c&&c(a,b);else{if(S>y&&(d!==n-1||!i))return!1;if(d+1>=n){if(i)return((n=y-m-x)>x||S>x)&&0<(n=Math.floor(a.length*n/S))&&c&&c(a.substring(0,n),b),c&&c("\u2026",void 0),f&&f(d,e,a,b,m),!0;return!1;}if(d++,f&&f(d,e,a,b,m),e+=r+h,y=s[d],m=S," "===C&&(m+=k),S>y&&(d!==n||!i))return!1;}b=C;}return f&&f(d,e,void 0,void 0,m),!0;}var i={hb:72,Gc:0,lineHeight:1.05,cb:1,Ua:.5,ib:.9,fontFamily:"sans-serif",fontStyle:"normal",fontWeight:"normal",fontVariant:"normal",verticalAlign:"center"},o={},l=[],u=[],s=[],f={text:"",Tc:void 0,Rf:void 0},d={C:void 0,F:void 0,cd:void 0,Ye:0,Ze:0};return{se:e,de:function(t,n,r,a,i,o,l,u,s,c,f,d){var h=0,p=0;if(r=r.toString().trim(),!d&&s.result&&r===s.Yf&&Math.abs(c-s.Zd)/c<=f){var g=s.result;g.ka&&(h=o.x-s.fg,p=o.y-s.gg,f=s.Qc,n.save(),n.translate(h,p),f.Na(n),n.restore());}return g||((f=s.Qc).clear(),(g=e(t,f,r,a,i,o,l,u)).ka&&f.Na(n),s.Zd=c,s.fg=o.x,s.gg=o.y,s.result=g,s.Yf=r),g.ka?{ka:!0,bc:g.bc,fontSize:g.fontSize,box:{x:g.box.x+h,y:g.box.y+p,w:g.box.w,o:g.box.o},Ub:g.Ub}:{ka:!1};},bi:function(){return{Zd:0,fg:0,gg:0,result:void 0,Qc:new c,Yf:void 0};},ya:i};}(),O=new function(){function e(e,t){return function(r,a,i,o){function l(e,t,n,r,a){e.C=[{x:t,y:n},{x:t+r,y:n},{x:t+r,y:n+a},{x:t,y:n+a}];}var u=a.x,s=a.y,c=a.w;if(a=a.o,0!=r.length){if(1==r.length)r[0].x=u+c/2,r[0].y=s+a/2,r[0].nd=0,i&&l(r[0],u,s,c,a);else{r=r.slice(0);for(var f=0,d=0;d<r.length;d++)f+=r[d].weight;for(d=0,f=c*a/f;d<r.length;d++)r[d].lc=r[d].weight*f;!function e(r,a,o,u,s){if(0!=r.length){var c=r.shift(),f=n(c);if(t(u,s)){var d=a,h=f/u;do{var p=(f=c.shift()).lc,g=p/h,v=o,b=h;(p=f).x=d+g/2,p.y=v+b/2,i&&l(f,d,o,g,h),d+=g;}while(0<c.length)return e(r,a,o+h,u,s-h);}d=o,g=f/s;do h=(p=(f=c.shift()).lc)/g,p=f,v=d,b=h,p.x=a+g/2,p.y=v+b/2,i&&l(f,a,d,g,h),d+=h;while(0<c.length)return e(r,a+g,o,u-g,s);}}(o=e(r,c,a,[[r.shift()]],o),u,s,c,a);}}};}function t(e,t,r,a){function i(e){return Math.max(Math.pow(u*e/l,r),Math.pow(l/(u*e),a));}var o=n(e),l=o*o,u=t*t;for(o=1,t=i(e[0].lc);o<e.length;o++)t=Math.max(t,i(e[o].lc));return t;}function n(e){for(var t=0,n=0;n<e.length;n++)t+=e[n].lc;return t;}this.u=e(function(e,r,a,i,o){o=Math.pow(2,o);for(var l=1/o,u=r<a;0<e.length;){var s=i[i.length-1],c=e.shift(),f=u?r:a,d=u?o:l,h=u?l:o,p=t(s,f,d,h);s.push(c),p<(f=t(s,f,d,h))&&(s.pop(),i.push([c]),u?a-=n(s)/r:r-=n(s)/a,u=r<a);}return i;},function(e,t){return e<t;}),this.i=e(function(e,n,r,a,i){function o(e){if(1<a.length){for(var r=a[a.length-1],i=a[a.length-2].slice(0),o=0;o<r.length;o++)i.push(r[o]);t(i,n,l,u)<e&&a.splice(-2,2,i);}}for(var l=Math.pow(2,i),u=1/l;0<e.length;){if(i=t(r=a[a.length-1],n,l,u),0==e.length)return;var s=e.shift();r.push(s),i<t(r,n,l,u)&&(r.pop(),o(i),a.push([s]));}return o(t(a[a.length-1],n,l,u)),a;},function(){return!0;});};function R(e){var t,n={},r=e.Cd;e.j.subscribe("model:loaded",function(e){t=e;}),this.M=function(){e.j.D("api:initialized",this);},this.nc=function(e,t,a,i){this.Xc(n,t),this.Yc(n,t),this.Wc(n,t,!1),i&&i(n),e(r,n,a);},this.bd=function(e,n,r,a,i,o,l){if(e){for(e=n.length-1;0<=e;e--){var u=n[e],s=C.extend({group:u.group},i);s[r]=a(u),o(s);}0<n.length&&l(C.extend({groups:P.uc(t,a).map(function(e){return e.group;})},i));}},this.Yc=function(e,t){return e.selected=t.selected,e.hovered=t.ub,e.open=t.open,e.openness=t.Cb,e.exposed=t.U,e.exposure=t.ja,e.transitionProgress=t.ra,e.revealed=!t.aa.Ga(),e.browseable=t.Ia?t.R:void 0,e.visible=t.Y,e.labelDrawn=t.oa&&t.oa.ka,e;},this.Xc=function(e,t){var n=t.parent;return e.group=t.group,e.parent=n&&n.group,e.weightNormalized=t.dg,e.level=t.level-1,e.siblingCount=n&&n.m.length,e.hasChildren=!t.empty(),e.index=t.index,e.indexByWeight=t.nd,e.description=t.description,e.attribution=t.attribution,e;},this.Wc=function(e,t,n){if(e.polygonCenterX=t.O.x,e.polygonCenterY=t.O.y,e.polygonArea=t.O.ha,e.boxLeft=t.F.x,e.boxTop=t.F.y,e.boxWidth=t.F.w,e.boxHeight=t.F.o,t.oa&&t.oa.ka){var r=t.oa.box;e.labelBoxLeft=r.x,e.labelBoxTop=r.y,e.labelBoxWidth=r.w,e.labelBoxHeight=r.o,e.labelFontSize=t.oa.fontSize;}return n&&t.$&&(e.polygon=t.$.map(function(e){return{x:e.x,y:e.y};}),e.neighbors=t.J&&t.J.map(function(e){return e&&e.group;})),e;};}var A=new function(){var e=window.console;this.i=function(e){throw"FoamTree: "+e;},this.info=function(t){e.info("FoamTree: "+t);},this.warn=function(t){e.warn("FoamTree: "+t);};};function I(e){function t(t,r){t.m=[],t.Ea=!0;var i=a(r),o=0;if(("flattened"===e.mb||"always"===e.Ag&&t.group&&t.group.description)&&0<r.length&&0<t.level){var l=r.reduce(function(e,t){return e+C.I(t.weight,1);},0),u=n(t.group,!1);u.description=!0,u.weight=l*e.Sb,u.index=o++,u.parent=t,u.level=t.level+1,u.id=u.id+"_d",t.m.push(u);}for(l=0;l<r.length;l++){var s=r[l];if(0>=(u=C.I(s.weight,1))){if(!e.Zi)continue;u=.9*i;}(s=n(s,!0)).weight=u,s.index=o,s.parent=t,s.level=t.level+1,t.m.push(s),o++;}}function n(e,t){var n=new ee;return r(e),n.id=e.__id,n.group=e,t&&(s[e.__id]=n),n;}function r(e){C.has(e,"__id")||(Object.defineProperty(e,"__id",{enumerable:!1,configurable:!1,writable:!1,value:u}),u++);}function a(e){for(var t=Number.MAX_VALUE,n=0;n<e.length;n++){var r=e[n].weight;0<r&&t>r&&(t=r);}return t===Number.MAX_VALUE&&(t=1),t;}function i(e){if(!e.empty()){e=e.m;var t,n=0;for(t=e.length-1;0<=t;t--){var r=e[t].weight;n<r&&(n=r);}for(t=e.length-1;0<=t;t--)(r=e[t]).dg=r.weight/n;}}function o(e){if(!e.empty()){e=e.m.slice(0).sort(function(e,t){return e.weight<t.weight?1:e.weight>t.weight?-1:e.index-t.index;});for(var t=0;t<e.length;t++)e[t].nd=t;}}function l(){for(var t=p.m.reduce(function(e,t){return e+t.weight;},0),n=0;n<p.m.length;n++){var r=p.m[n];r.attribution&&(r.weight=Math.max(.025,e.tg)*t);}}var u,s,c,f,d,h=this,p=new ee;this.M=function(){return p;},this.T=function(n){var r=n.group.groups,a=e.Uh;return!n.m&&!n.description&&!!r&&0<r.length&&d+r.length<=a&&(d+=r.length,t(n,r),i(n),o(n),!0);},this.load=function(e){p.group=e,p.xa=!1,p.R=!1,p.Ia=!1,p.open=!0,p.Cb=1,u=function e(t,n){if(!t)return n;if(n=Math.max(n,t.__id||0),(t=t.groups)&&0<t.length)for(var r=t.length-1;0<=r;r--)n=e(t[r],n);return n;}(e,0)+1,s={},c={},f={},d=0,e&&(r(e),s[e.__id]=p,C.V(e.id)||(c[e.id]=e),function e(t){var n=t.groups;if(n)for(var a=0;a<n.length;a++){var i=n[a];r(i);var o=i.__id;s[o]=null,f[o]=t,o=i.id,C.V(o)||(c[o]=i),e(i);}}(e)),t(p,e&&e.groups||[]),function(e){if(!e.empty()){var t=n({attribution:!0});t.index=e.m.length,t.parent=e,t.level=e.level+1,t.attribution=!0,e.m.push(t);}}(p),i(p),l(),o(p);},this.update=function(e){e.forEach(function(e){P.za(e,function(e){if(!e.empty()){e=e.m;for(var t=a(e.map(function(e){return e.group;})),n=0;n<e.length;n++){var r=e[n];r.weight=0<r.group.weight?r.group.weight:.9*t;}}}),i(e),e===p&&l(),o(e);});},this.u=function(e){return(function(){if(C.V(e)||C.Se(e))return[];if(Array.isArray(e))return e.map(h.i,h);if(C.wb(e)){if(C.has(e,"__id"))return[h.i(e)];if(C.has(e,"all")){var t=[];return P.L(p,function(e){t.push(e);}),t;}if(C.has(e,"groups"))return h.u(e.groups);}return[h.i(e)];})().filter(function(e){return void 0!==e;});},this.i=function(e){if(C.wb(e)&&C.has(e,"__id")){if(e=e.__id,C.has(s,e)){if(null===s[e]){for(var t=f[e],n=[];t&&(n.push(t=t.__id),!s[t]);)t=f[t];for(t=n.length-1;0<=t;t--)this.T(s[n[t]]);}return s[e];}}else if(C.has(c,e))return this.i(c[e]);},this.H=function(e,t,n){return{m:h.u(e),Ca:C.I(e&&e[t],!0),Ba:C.I(e&&e.keepPrevious,n)};};}function B(e,t,n){var r={};t.Ba&&P.L(e,function(e){n(e)&&(r[e.id]=e);}),e=t.m,t=t.Ca;for(var a=e.length-1;0<=a;a--){var i=e[a];r[i.id]=t?i:void 0;}var o=[];return C.Aa(r,function(e){void 0!==e&&o.push(e);}),o;}function G(e){function t(e,t){e=e.ja,t.opacity=1,t.Da=1,t.va=0>e?1-x.Eh/100*e:1,t.saturation=0>e?1-x.Fh/100*e:1,t.ca=0>e?1+.5*e:1;}function n(e){return Math.max(.001,0===(e=e.ja)?1:1+e*(x.Pa-1));}function r(e){g.reduce(l(!0,void 0,function(t){return t.U||e[t.id];}),u(v)),v.x-=v.w*(x.Pa-1)/2,v.y-=v.o*(x.Pa-1)/2,v.w*=x.Pa,v.o*=x.Pa;}function a(){return!!g&&g.reduce(function(e,t){return e||0!==t.ja;},!1);}function i(n,r,a){var i=h.K.A(n);return 0===n.ja&&0!==r&&i.call(function(){this.mc(S),this.qb(t);}),i.fa({duration:x.Oa,P:{ja:{end:r,easing:k.ia(x.Wb)}},ba:function(){s.N=!0,s.Fa=!0,e.j.D("foamtree:dirty",!0);}}),0===r&&i.call(function(){this.vd(),this.cc(),this.Nc(S),this.Mc(t);}),i.call(a).done();}function o(){var e=s.m.reduce(l(!1,S.transformPoint,void 0),u({})).box,t=x.Yb,n=Math.min(e.x,v.x-v.w*t),r=Math.min(e.y,v.y-v.o*t);d.content(n,r,Math.max(e.x+e.w,v.x+v.w*(1+t))-n,Math.max(e.y+e.o,v.y+v.o*(1+t))-r);}function l(e,t,n){var r={};return function(a,i){if(!n||n(i)){for(var o,l=e&&i.$||i.C,u=l.length-1;0<=u;u--)o=void 0!==t?t(i,l[u],r):l[u],a.Hc=Math.min(a.Hc,o.x),a.wd=Math.max(a.wd,o.x),a.Ic=Math.min(a.Ic,o.y),a.xd=Math.max(a.xd,o.y);a.box.x=a.Hc,a.box.y=a.Ic,a.box.w=a.wd-a.Hc,a.box.o=a.xd-a.Ic;}return a;};}function u(e){return{Hc:Number.MAX_VALUE,wd:Number.MIN_VALUE,Ic:Number.MAX_VALUE,xd:Number.MIN_VALUE,box:e};}var s,c,f,d,h,p,g,v,y,w,x=e.options,S={We:function(e,t){return t.scale=n(e),!1;},Ib:function(e,t){e=n(e);var r=c.x,a=c.y;t.translate(r,a),t.scale(e,e),t.translate(-r,-a);},Jb:function(e,t,r){e=n(e);var a=c.x,i=c.y;r.x=(t.x-a)/e+a,r.y=(t.y-i)/e+i;},transformPoint:function(e,t,r){e=n(e);var a=c.x,i=c.y;return r.x=(t.x-a)*e+a,r.y=(t.y-i)*e+i,r;}};e.j.subscribe("stage:initialized",function(e,t,n,r){c={x:n/2,y:r/2},v={x:0,y:0,w:y=n,o:w=r};}),e.j.subscribe("stage:resized",function(e,t,n,r){c.x*=n/e,c.y*=r/t,y=n,w=r;}),e.j.subscribe("api:initialized",function(e){f=e;}),e.j.subscribe("zoom:initialized",function(e){d=e;}),e.j.subscribe("model:loaded",function(e,t){s=e,g=t;}),e.j.subscribe("model:childrenAttached",function(e){g=e;}),e.j.subscribe("timeline:initialized",function(e){h=e;}),e.j.subscribe("openclose:initialized",function(e){p=e;});var T=["groupExposureScale","groupUnexposureScale","groupExposureZoomMargin"];e.j.subscribe("options:changed",function(e){C.bb(e,T)&&a()&&(r({}),d.fj(v,x.Yb),d.Fb());}),this.M=function(){e.j.D("expose:initialized",this);},this.Vb=function(t,n,l,u){var S=t.m.reduce(function(e,t){for(;t=t.parent;)e[t.id]=!0;return e;},{}),T=B(s,t,function(e){return e.U&&!e.open&&!S[e.id];}),z=new b;return(function(e,t){for(var n=e.reduce(function(e,t){return e[t.id]=t,e;},{}),r=e.length-1;0<=r;r--)P.L(e[r],function(e){n[e.id]=void 0;});var a=[];C.Aa(n,function(e){e&&P.ne(e,function(e){e.open||a.push(e);});});var i=[];return C.Aa(n,function(e){e&&e.open&&i.push(e);}),e=[],0!==a.length&&e.push(p.Bb({m:a,Ca:!0,Ba:!0},t,!0)),m(e);})(T,n).then(function(){(function(t,n,l,u){var p=a();if(0===t.length&&!p)return(new b).resolve().promise();var S=t.reduce(function(e,t){return e[t.id]=!0,e;},{}),C=[];if(t=[],g.reduce(function(e,t){return e||S[t.id]&&(!t.U||1!==t.ja)||!S[t.id]&&!t.parent.U&&(t.U||-1!==t.ja);},!1)){var T,z,E=[],M={};g.forEach(function(e){S[e.id]&&(e.U||C.push(e),e.U=!0,P.za(e,function(e){E.push(i(e,1)),M[e.id]=!0;}));}),0<E.length?(P.L(s,function(e){S[e.id]||(e.U&&C.push(e),e.U=!1),M[e.id]||E.push(i(e,-1));}),t.push(h.K.A({}).Qa(E).call(o).Ta()),r(S),t.push(p||!d.zd()?h.K.A(c).fa({duration:.7*x.Oa,P:{x:{end:v.x+v.w/2,easing:k.ia(x.Wb)},y:{end:v.y+v.o/2,easing:k.ia(x.Wb)}},ba:function(){e.j.D("foamtree:dirty",!0);}}).Ta():(c.x=v.x+v.w/2,c.y=v.y+v.o/2,(new b).resolve().promise())),l&&(d.ic(v,x.Yb,x.Oa,k.ia(x.Wb)),d.Fb())):(t.push((T=[],z=[],P.L(s,function(e){0!==e.ja&&z.push(i(e,0,function(){this.U=!1;}));}),T.push(h.K.A({}).Qa(z).Ta()),d.content(0,0,y,w),l&&(T.push(d.reset(x.Oa,k.ia(x.Wb))),d.Fb()),m(T))),n&&P.L(s,function(e){e.U&&C.push(e);}));}return m(t).then(function(){f.bd(n,C,"exposed",function(e){return e.U;},{indirect:u},e.options.jf,e.options.hf);});})(T.filter(function(e){return e.C&&e.$;}),n,l,u).then(z.resolve);}),z.promise();};}function H(e){var t,n,r=[],a=new y(C.qa);e.j.subscribe("stage:initialized",function(){}),e.j.subscribe("stage:resized",function(){}),e.j.subscribe("stage:newLayer",function(e,t){r.push(t);}),e.j.subscribe("model:loaded",function(e){t=e,a.clear();}),e.j.subscribe("zoom:initialized",function(){}),e.j.subscribe("timeline:initialized",function(e){n=e;});var i=!1;e.j.subscribe("render:renderers:resolved",function(e){i=e.labelPlainFill||!1;});var o=new function(){var e=0,t=0,n=0,r=0,a=0,i=0;this.i=function(o,l,u,s,c){t=1-(e=1+l),n=u,r=s,a=c,i=o;},this.We=function(i,o){return o.scale=e+t*i.ra,0!==a||0!==n||0!==r;},this.Ib=function(o,l){var u=e+t*o.ra,s=o.parent,c=i*o.x+(1-i)*s.x,f=i*o.y+(1-i)*s.y;l.translate(c,f),l.scale(u,u),o=1-o.ra,l.rotate(a*Math.PI*o),l.translate(-c,-f),l.translate(s.F.w*n*o,s.F.o*r*o);},this.Jb=function(a,o,l){var u=e+t*a.ra,s=i*a.x+(1-i)*a.parent.x,c=i*a.y+(1-i)*a.parent.y,f=1-a.ra;a=a.parent,l.x=(o.x-s)/u+s-a.F.w*n*f,l.y=(o.y-c)/u+c-a.F.o*r*f;},this.transformPoint=function(a,o,l){var u=e+t*a.ra,s=i*a.x+(1-i)*a.parent.x,c=i*a.y+(1-i)*a.parent.y,f=1-a.ra;a=a.parent,l.x=(o.x-s)*u+s-a.F.w*n*f,l.y=(o.y-c)*u+c-a.F.o*r*f;};};this.M=function(){},this.u=function(){function r(e,t){var n=Math.min(1,Math.max(0,e.ra));t.opacity=n,t.va=1,t.saturation=n,t.Da=n,t.ca=e.yb;}function l(e,t){var n=Math.min(1,Math.max(0,e.Hd));t.opacity=n,t.Da=n,t.va=1,t.saturation=1,t.ca=e.yb;}var u=e.options,s=u.Gd,c=u.li,f=u.mi,d=u.ni,h=u.hi,p=u.ii,g=u.ji,v=u.di,b=u.ei,m=u.fi,y=h+p+g+v+b+m+c+f+d,w=0<y?s/y:0,x=[];return a.initial()?o.i(u.si,u.pi,u.ti,u.ui,u.oi):o.i(u.Nf,u.Mf,u.Of,u.Pf,u.Lf),F.u(t,F.i(t,e.options.ri),function(t,a,s){var y="groups"===e.options.ki?s:a;x.push(n.K.A(t).call(function(){this.qb(r);}).wait(i?w*(h+y*p):0).fa({duration:i?w*g:0,P:{yb:{end:0,easing:k.Ab}},ba:function(){this.N=!0,e.j.D("foamtree:dirty",!0);}}).done()),P.L(t,function(t){x.push(n.K.A(t).call(function(){this.mc(o),this.qb(l);}).wait(w*(v+b*y)).fa({duration:w*m,P:{Hd:{end:0,easing:k.Ab}},ba:function(){this.N=!0,e.j.D("foamtree:dirty",!0);}}).Xa(function(){this.selected=!1,this.Nc(o);}).done());}),x.push(n.K.A(t).call(function(){this.mc(o);}).wait(w*(c+f*y)).fa({duration:w*d,P:{ra:{end:0,easing:k.ia(u.gi)}},ba:function(){this.N=!0,e.j.D("foamtree:dirty",!0);}}).Xa(function(){this.selected=!1,this.Nc(o);}).done());}),n.K.A({}).Qa(x).Ta();},this.i=function(t){return function(t){function r(e,t){var n=Math.min(1,Math.max(0,e.ra));t.opacity=n,t.va=1,t.saturation=n,t.Da=n,t.ca=e.yb;}var l=e.options,u=l.Ui,s=l.Vi,c=l.Ri,f=l.Si,d=l.Ti,h=l.Od,p=u+s+c+f+d,g=0<p?h/p:0,v=[];if(o.i(l.Nf,l.Mf,l.Of,l.Pf,l.Lf),0===g&&t.m&&t.R){for(p=0,h=t.m;p<h.length;p++){var m=h[p];m.ra=1,m.yb=1,m.qb(r),m.cc(),m.Mc(r);}return t.N=!0,e.j.D("foamtree:dirty",0<g),(new b).resolve().promise();}if(t.m&&t.R){F.u(t,F.i(t,e.options.Qd),function(t,a,h){t.mc(o),t.qb(r),h="groups"===e.options.Pd?h:a,a=n.K.A(t).wait(h*g*u).fa({duration:g*s,P:{ra:{end:1,easing:k.ia(l.Qi)}},ba:function(){this.N=!0,e.j.D("foamtree:dirty",0<g);}}).done(),h=n.K.A(t).wait(i?g*(c+h*f):0).fa({duration:i?g*d:0,P:{yb:{end:1,easing:k.Ab}},ba:function(){this.N=!0,e.j.D("foamtree:dirty",0<g);}}).done(),t=n.K.A(t).Qa([a,h]).Xd().Xa(function(){this.vd(),this.cc(),this.Nc(o),this.Mc(r);}).done(),v.push(t);}),a.i();var y=new b;return n.K.A({}).Qa(v).call(function(){a.u(),y.resolve();}).start(),y.promise();}return(new b).resolve().promise();}(t);};}function q(e){var t,n,r,a,i;e.j.subscribe("api:initialized",function(e){i=e;}),e.j.subscribe("model:loaded",function(e){a=e,t={};}),e.j.subscribe("timeline:initialized",function(e){n=e;}),e.j.subscribe("expose:initialized",function(e){r=e;}),this.M=function(){e.j.D("openclose:initialized",this);},this.Bb=function(o,l,u){if("flattened"==e.options.mb)return(new b).resolve().promise();o=B(a,o,function(e){return e.open||e.Va;});for(var s=new b,c=0;c<o.length;c++)o[c].Va=!0;0<o.length&&e.j.D("foamtree:attachChildren",o);var f=o.reduce(function(e,t){return e[t.id]=!0,e;},{});return(function(e,t){var n,i=[];if(P.L(a,function(t){if(t.m){var n=C.has(e,t.id);t.open!==n&&(n||t.U||P.L(t,function(e){if(e.U)return i.push(t),!1;}));}}),0===i.length)return(new b).resolve().promise();for(n=i.length-1;0<=n;n--)i[n].open=!1;for(t=r.Vb({m:i,Ca:!0,Ba:!0},t,!0,!0),n=i.length-1;0<=n;n--)i[n].open=!0;return t;})(f,l).then(function(){(function(r,o,l){function u(e,t){t.opacity=1-e.Cb,t.va=1,t.saturation=1,t.ca=1,t.Da=1;}var s=[],c=[];return P.L(a,function(a){if(a.R&&a.X){var i=C.has(r,a.id),o=t[a.id];if(o&&o.xb())o.stop();else if(a.open===i)return;a.Va=i,i||(a.open=i,a.Bd=!1),c.push(a),s.push(function(r,a){r.qb(u);var i=n.K.A(r).fa({duration:e.options.Kc,P:{Cb:{end:a?1:0,easing:k.fe}},ba:function(){this.N=!0,e.j.D("foamtree:dirty",!0);}}).call(function(){this.open=a,r.Va=!1;}).Xa(function(){this.cc(),this.Mc(u),delete t[this.id];}).done();return t[r.id]=i;}(a,i));}}),0<s.length?(e.j.D("openclose:changing"),n.K.A({}).Qa(s).Ta().then(function(){i.bd(o,c,"open",function(e){return e.open;},{indirect:l},e.options.sf,e.options.rf);})):(new b).resolve().promise();})(f,l,u).then(s.resolve);}),s.promise();};}function U(e){var t,n;e.j.subscribe("api:initialized",function(e){n=e;}),e.j.subscribe("model:loaded",function(e){t=e;}),this.M=function(){e.j.D("select:initialized",this);},this.select=function(r,a){return function(r,a){for(r=B(t,r,function(e){return e.selected;}),P.L(t,function(e){!0===e.selected&&(e.selected=!e.selected,e.N=!e.N,e.Sa=!e.Sa);}),i=r.length-1;0<=i;i--){var i,o=r[i];o.selected=!o.selected,o.N=!o.N,o.Sa=!o.Sa;}var l=[];P.L(t,function(e){e.N&&l.push(e);}),0<l.length&&e.j.D("foamtree:dirty",!1),n.bd(a,l,"selected",function(e){return e.selected;},{},e.options.uf,e.options.tf);}(r,a);};}function W(e){function t(e){return function(t){e.call(this,{x:t.x,y:t.y,scale:t.scale,ed:t.delta,ctrlKey:t.ctrlKey,metaKey:t.metaKey,altKey:t.altKey,shiftKey:t.shiftKey,lb:t.secondary,touches:t.touches});};}function n(){y.pc(2)?e.j.D("interaction:reset"):y.normalize($.ob,k.ia($.Kb));}function r(e){return function(){j.empty()||e.apply(this,arguments);};}function a(e,t,n){var r={},a={};return function(o){switch(e){case"click":var l,u,s,c=$.cf;break;case"doubleclick":c=$.df;break;case"hold":c=$.kf;break;case"hover":c=$.lf;break;case"mousemove":c=$.nf;break;case"mousewheel":c=$.qf;break;case"mousedown":c=$.mf;break;case"mouseup":c=$.pf;break;case"dragstart":c=$.gf;break;case"drag":c=$.ef;break;case"dragend":c=$.ff;break;case"transformstart":c=$.xf;break;case"transform":c=$.vf;break;case"transformend":c=$.wf;}var f=!1,d=!c.empty(),h=y.absolute(o,r),p=(t||d)&&i(h),g=(t||d)&&(l=h,u=void 0,s=0,P.sc(j,function(e){!0===e.open&&e.Y&&e.scale>s&&J(e,l)&&(u=e,s=e.scale);}),u);d&&(d=p?p.group:null,h=p?p.Jb(h,a):h,o.Db=void 0,c=c({type:e,group:d,topmostClosedGroup:d,bottommostOpenGroup:g?g.group:null,x:o.x,y:o.y,xAbsolute:h.x,yAbsolute:h.y,scale:C.I(o.scale,1),secondary:o.lb,touches:C.I(o.touches,1),delta:C.I(o.ed,0),ctrlKey:o.ctrlKey,metaKey:o.metaKey,altKey:o.altKey,shiftKey:o.shiftKey,preventDefault:function(){f=!0;},preventOriginalEventDefault:function(){o.Db="prevent";},allowOriginalEventDefault:function(){o.Db="allow";}}),f=f||0<=c.indexOf(!1),p&&p.attribution&&"click"===e&&(f=!1)),f||n&&n({dd:p,vg:g},o);};}function i(e,t){if("flattened"===$.mb)e=function(e){function t(e,n){var r=n.m;if(r){for(var a,i,o=-Number.MAX_VALUE,l=0;l<r.length;l++){var u=r[l];!u.description&&u.Y&&J(u,e)&&u.scale>o&&(i=u,o=u.scale);}return i&&(a=t(e,i)),a||i||n;}return n;}for(var n=b.length,r=b[0].scale,a=b[0].scale,i=0;i<n;i++){var o=b[i];(o=o.scale)<r&&(r=o),o>a&&(a=o);}if(r!==a){for(i=0;i<n;i++)if((o=b[i]).scale===a&&o.Y&&J(o,e))return t(e,o);}return t(e,j);}(e);else{t=t||0;for(var n=b.length,r=void 0,a=0;a<n;a++){var i=b[a];i.scale>t&&!1===i.open&&i.Y&&J(i,e)&&(r=i,t=i.scale);}e=r;}return e&&e.description&&(e=e.parent),e;}var o,u,s,c,f,d,h,p,g,v,b,m,y,T,z,M,_,D,j,F,N,O,R,A,I,B,G,H,q,U,W=l.Nh(),V=this,$=e.options,K=!1;e.j.subscribe("stage:initialized",function(t,n,r,a){m=n,q=r,U=a,function(){function t(e){return function(t){return t.x*=q/m.clientWidth,t.y*=U/m.clientHeight,e(t);};}"external"!==$.Ne&&("hammerjs"===$.Ne&&C.has(window,"Hammer")&&(X.M(m),X.A("tap",t(V.i),!0),X.A("doubletap",t(V.u),!0),X.A("hold",t(V.sa),!0),X.A("touch",t(V.ua),!0),X.A("release",t(V.wa),!1),X.A("dragstart",t(V.ga),!0),X.A("drag",t(V.H),!0),X.A("dragend",t(V.T),!0),X.A("transformstart",t(V.pb),!0),X.A("transform",t(V.transform),!0),X.A("transformend",t(V.Ka),!0)),G=new S(m),H=new x,G.i(t(V.i)),G.u(t(V.u)),G.sa(t(V.sa)),G.wa(t(V.ua)),G.Ka(t(V.wa)),G.ga(t(V.ga)),G.H(t(V.H)),G.T(t(V.T)),G.ta(t(V.ta)),G.Ja(t(V.ta)),G.ua(t(V.Ja)),H.addEventListener("keyup",function(t){var n=!1,r=void 0,a=$.yf({keyCode:t.keyCode,preventDefault:function(){n=!0;},preventOriginalEventDefault:function(){r="prevent";},allowOriginalEventDefault:function(){r="allow";}});"prevent"===r&&t.preventDefault(),(n=n||0<=a.indexOf(!1))||27===t.keyCode&&e.j.D("interaction:reset");}));}();}),e.j.subscribe("stage:resized",function(e,t,n,r){q=n,U=r;}),e.j.subscribe("stage:disposed",function(){G.Za(),X.Za(),H.i();}),e.j.subscribe("expose:initialized",function(e){z=e;}),e.j.subscribe("zoom:initialized",function(e){y=e;}),e.j.subscribe("openclose:initialized",function(e){M=e;}),e.j.subscribe("select:initialized",function(e){_=e;}),e.j.subscribe("titlebar:initialized",function(e){D=e;}),e.j.subscribe("timeline:initialized",function(e){T=e;}),e.j.subscribe("model:loaded",function(e,t){j=e,b=t;}),e.j.subscribe("model:childrenAttached",function(e){b=e;}),this.M=function(){},this.ua=r(a("mousedown",!1,function(){y.Zh();})),this.wa=r(a("mouseup",!1,void 0)),this.i=r(a("click",!0,function(e,t){!t.lb&&!t.shiftKey&&(e=e.dd)&&(e.attribution?t.ctrlKey?document.location.href=E.Qf("iuuqr;..b`ssnurd`sbi/bnl.gn`lusdd"):(t=k.ia($.Kb),e.be?(y.reset($.ob,t),e.be=!1):(y.cg(e,$.Yb,$.ob,t),e.be=!0)):_.select({m:[e],Ca:!e.selected,Ba:t.metaKey||t.ctrlKey},!0));})),this.u=r(a("doubleclick",!0,function(t,n){var r=t.dd;r&&r.attribution||(n.lb||n.shiftKey?r&&(r.parent.U&&(r=r.parent),t={m:r.parent!==j?[r.parent]:[],Ca:!0,Ba:!1},_.select(t,!0),z.Vb(t,!0,!0,!1)):r&&(t={m:[r],Ca:!0,Ba:!1},r.Va=!0,e.j.D("foamtree:attachChildren",[r]),z.Vb(t,!0,!0,!1)),r&&T.K.A({}).wait($.Oa/2).call(function(){M.Bb({m:P.uc(j,function(e){return e.Bd&&!P.Lh(r,e);}),Ca:!1,Ba:!0},!0,!0),r.Bd=!0,M.Bb({m:[r],Ca:!(n.lb||n.shiftKey),Ba:!0},!0,!0);}).start());})),this.sa=r(a("hold",!0,function(e,t){(e=(t=!(t.metaKey||t.ctrlKey||t.shiftKey)&&!t.lb)?e.dd:e.vg)&&e!==j&&M.Bb({m:[e],Ca:t,Ba:!0},!0,!1);})),this.ga=r(a("dragstart",!1,function(e,t){F=t.x,N=t.y,O=Date.now(),K=!0;})),this.H=r(a("drag",!1,function(e,t){if(K){I=Math.min(1,(e=Date.now())-O),O=e,e=t.x-F;var n=t.y-N;y.Xh(e,n),R=e,A=n,F=t.x,N=t.y;}})),this.T=r(a("dragend",!1,function(){if(K){K=!1;var e=Math.sqrt(R*R+A*A)/I;4<=e?y.Yh(e,R,A):y.af();}})),this.pb=r(a("transformstart",!1,function(e,t){B=1,F=t.x,N=t.y;}));var Q=1,Y=!1;this.transform=r(a("transform",!1,function(e,t){e=t.scale-.01,y.qg(t,e/B,t.x-F,t.y-N),B=e,F=t.x,N=t.y,Q=B,Y=Y||2<t.touches;})),this.Ka=r(a("transformend",!1,function(){Y&&.8>Q?e.j.D("interaction:reset"):n(),Y=!1;})),this.Ja=r(a("mousewheel",!1,(o=C.zg(function(){n();},300),function(e,t){1!==(e=$.lj)&&(e=Math.pow(e,t.ed),W?(y.rg(t,e),o()):y.Nb(t,e,$.ob,k.ia($.Kb)).then(n));}))),this.ta=r((s=void 0,c={},f=!1,d=a("hover",!1,function(){s&&(s.ub=!1,0<s.level&&(s.N=!0)),u&&(u.ub=!0,0<u.level&&(u.N=!0)),D.update(u),e.j.D("foamtree:dirty",!1);}),h=a("mousemove",!1,void 0),function(e){if("out"===e.type)f=(u=void 0)!==s;else if(y.absolute(e,c),s&&!s.open&&J(s,c)){var t=i(c,s.scale);t&&t!==s?(f=!0,u=t):f=!1;}else f=(u=i(c))!==s;f&&(d(e),s=u,f=!1),s&&h(e);})),this.Lb={click:t(this.i),doubleclick:t(this.u),hold:t(this.sa),mouseup:t(this.wa),mousedown:t(this.ua),dragstart:t(this.ga),drag:t(this.H),dragend:t(this.T),transformstart:t(this.pb),transform:t(this.transform),transformend:t(this.Ka),hover:t(this.ta),mousewheel:t(this.Ja)};var X=(g={},{M:function(e){p=window.Hammer(e,{doubletap_interval:350,hold_timeout:400,doubletap_distance:10});},A:function(e,t,n){g[e]=t,p.on(e,function(e){var r=(e=e.gesture).center;(r=w.pe(m,r.pageX,r.pageY,{})).scale=e.scale,r.lb=1<e.touches.length,r.touches=e.touches.length,t.call(m,r),(void 0===r.Db&&n||"prevent"===r.Db)&&e.preventDefault();});},Za:function(){p&&C.Aa(g,function(e,t){p.off(t,e);});}}),J=(v={},function(e,t){return e.Jb(t,v),e.$&&L.sa(e.$,v);});}function V(e){function t(e,t,n,r){var a,i=0,o=[];for(a=0;a<t.length;a++){var l=Math.sqrt(L.i(t[a],t[(a+1)%t.length]));o.push(l),i+=l;}for(a=0;a<o.length;a++)o[a]/=i;e[0].x=n.x,e[0].y=n.y;var u=l=i=0;for(a=1;a<e.length;a++){var s=e[a],c=.95*Math.pow(a/e.length,r);for(i+=.3819;l<i;)l+=o[u],u=(u+1)%o.length;var f=(u-1+o.length)%o.length,d=1-(l-i)/o[f],h=t[f].x;f=t[f].y;var p=t[u].x,g=t[u].y;h=(h-n.x)*c+n.x,f=(f-n.y)*c+n.y,p=(p-n.x)*c+n.x,g=(g-n.y)*c+n.y,s.x=h*(1-d)+p*d,s.y=f*(1-d)+g*d;}}var n={random:{vb:function(e,t){for(var n=0;n<e.length;n++){var r=e[n];r.x=t.x+Math.random()*t.w,r.y=t.y+Math.random()*t.o;}},Ob:"box"},ordered:{vb:function(e,t){e=e.slice(0),r.ac&&e.sort(et),O.i(e,t,!1,r.Ld);},Ob:"box"},squarified:{vb:function(e,t){e=e.slice(0),r.ac&&e.sort(et),O.u(e,t,!1,r.Ld);},Ob:"box"},fisheye:{vb:function(e,n,a){e=e.slice(0),r.ac&&e.sort(et),t(e,n,a,.25);},Ob:"polygon"},blackhole:{vb:function(e,n,a){e=e.slice(0),r.ac&&e.sort(et).reverse(),t(e,n,a,1);},Ob:"polygon"}};n.order=n.ordered,n.treemap=n.squarified;var r=e.options;this.i=function(e,t,a){if(0<e.length){if("box"===(a=n[a.relaxationInitializer||a.initializer||r.Li||"random"]).Ob){var i=L.F(t,{});a.vb(e,i),L.wa(e,L.H(i),t);}else a.vb(e,t,L.u(t,{}));for(a=e.length-1;0<=a;a--){if((i=e[a]).description){var o=L.ta(t,r.qc,r.Bg);i.x=o.x,i.y=o.y;}i.attribution&&(o=L.ta(t,r.$d,r.sg),i.x=o.x,i.y=o.y),C.wb(i.group.initialPosition)&&(o=i.group.initialPosition,o=L.ta(t,o.position||"bottomright",o.distanceFromCenter||1),i.x=o.x,i.y=o.y);}}};}function $(e){var t,n=e.options,r=new K(e,this),a=new Q(e,this),i={relaxed:r,ordered:a,squarified:a},o=i[e.options.Dc]||r;this.kg=5e-5,e.j.subscribe("model:loaded",function(e){t=e;}),e.j.subscribe("options:changed",function(e){e.layout&&C.has(i,n.Dc)&&(o=i[n.Dc]);}),this.step=function(e,t,n,r){return o.step(e,t,n,r);},this.complete=function(e){o.complete(e);},this.Pe=function(e){return e===t||2*Math.sqrt(e.O.ha/(Math.PI*e.m.length))>=Math.max(n.Ce,5e-5);},this.gd=function(e,t){var r=Math.pow(n.La,e.level),a=n.$a*r;r*=n.jd,e=e.m;for(var i=e.length-1;0<=i;i--){var l=e[i];o.ce(l,r),l.$=0<a?D.i(l.C,a):l.C,l.$&&(L.F(l.$,l.F),L.Ja(l.$,l.O)),l.m&&t.push(l);}},this.fc=function(e){o.fc(e);},this.Eb=function(e){o.Eb(e);};}function K(e,t){function n(e){if(e.m){e=e.m;for(var t=0;t<e.length;t++){var n=e[t];n.kc=n.hc*c.qh;}}}function r(e,r){t.Pe(e)&&(e.G||(e.G=D.i(e.C,c.jd*Math.pow(c.La,e.level-1)),e.G&&e.m[0]&&e.m[0].description&&"stab"==c.Tb&&l(e)),e.G&&(s.Eb(e),f.i(a(e),e.G,e.group),e.R=!0,r(e)),n(e));}function a(e){return"stab"===c.Tb&&0<e.m.length&&e.m[0].description?e.m.slice(1):e.m;}function i(e){var t=a(e);return j.i(t,e.G),j.u(t,e.G),F.H(e)*Math.sqrt(u.O.ha/e.O.ha);}function o(e){return e<c.If||1e-4>e;}function l(e){var t=c.Sb/(1+c.Sb),n=L.F(e.G,{}),r={x:n.x,y:0},a=n.y,i=n.o,o=c.ie*Math.pow(c.La,e.level-1),l=i*c.he,u=c.qc;"bottom"==u||0<=u&&180>u?(u=Math.PI,a+=i,i=-1):(u=0,i=1);var s=e.G,f=u,d=0,h=1,p=L.u(s,{}),g=p.ha;t*=g;for(var v=0;d<h&&20>v++;){var b=(d+h)/2;r.y=n.y+n.o*b;var m=L.ua(s,r,f);L.u(m[0],p);var y=p.ha-t;if(.01>=Math.abs(y)/g)break;0<(0==f?1:-1)*y?h=b:d=b;}L.F(m[0],n),(n.o<o||n.o>l)&&(r.y=n.o<o?a+i*Math.min(o,l):a+i*l,m=L.ua(e.G,r,u)),e.m[0].C=m[0],e.G=m[1];}var u,s=this,c=e.options,f=new V(e),d=0;e.j.subscribe("model:loaded",function(e){u=e,d=0;}),this.step=function(e,n,l,s){for(var f,h,p=0,g=[u];0<g.length;)p=Math.max(p,function(n){if(n.R&&n.xa?n!==u&&2*Math.sqrt(n.O.ha/(Math.PI*n.m.length))<Math.max(.85*c.Ce,t.kg)&&(n.R=!1,n.xa=!1,n.Ia=!0,n.G=null):n.Ia&&n.C&&r(n,function(){var t=a(n);j.i(t,n.G),j.u(t,n.G),e(n);}),!n.G||!n.R)return 0;if(n.parent&&n.parent.Z||n.Ea){var f=i(n);s&&s(n),n.Ea=!o(f)&&!l,n.Z=!0;}else f=0;return t.gd(n,g),f;}(g.shift()));var v=o(p);return n&&(d<(f=p)&&(d=f),h=c.If,c.Ad(v?1:1-(f-h)/(d-h||1),v,l),v&&(d=0)),v;},this.complete=function(e){for(var n=[u];0<n.length;){var a=n.shift();if(!a.R&&a.Ia&&a.C&&r(a,e),a.G){if(a.parent&&a.parent.Z||a.Ea){for(var l=1e-4>a.O.ha,s=0;!(o(i(a))||l&&32<s++););a.Z=!0,a.Ea=!1;}t.gd(a,n);}}},this.fc=function(e){P.L(e,n);},this.ce=function(e,t){if(e.R){var n=e.G;n&&(e.Fd=n),e.G=D.i(e.C,t),e.G&&e.m[0]&&e.m[0].description&&"stab"==c.Tb&&l(e),n&&!e.G&&(e.Z=!0),e.G&&e.Fd&&L.wa(a(e),e.Fd,e.G);}},this.Eb=function(e){for(var t,n=a(e),r=e.ha,i=t=0;i<n.length;i++)t+=n[i].weight;for(e.Gj=t,e=0;e<n.length;e++)(i=n[e]).Wf=i.w,i.hc=r/Math.PI*(0<t?i.weight/t:1/n.length);};}function Q(e,t){function n(e,t,n){for(var r=0;r<e.length;r++){var a=e[r];if(n(a)){e.splice(r,1),"topleft"==t||135<=t&&315>t?e.unshift(a):e.push(a);break;}}}var r,a=e.options,i={squarified:O.u,ordered:O.i};e.j.subscribe("model:loaded",function(e){r=e;}),this.step=function(e,t,n){return this.complete(e),t&&a.Ad(1,!0,n),!0;},this.complete=function(e){for(var o=[r];0<o.length;){var l=o.shift();(!l.R||l.parent&&l.parent.Z)&&l.Ia&&l.C&&function(e,n){if(t.Pe(e)){if(!e.G||e.parent&&e.parent.Z){var r=a.jd*Math.pow(a.La,e.level-1);e.G=L.H(function(e,t){var n=2*t;return e.x+=t,e.y+=t,e.w-=n,e.o-=n,e;}(L.F(e.C,{}),r));}e.G&&(e.R=!0,n(e));}else e.R=!1,P.za(e,function(e){e.G=null;});}(l,e),l.G&&((l.parent&&l.parent.Z||l.Ea)&&function(e){if("stab"==a.Tb&&0<e.m.length&&e.m[0].description){var t=e.m.slice(1);!function(e){function t(){r.C=L.H(i),r.x=i.x+i.w/2,r.y=i.y+i.o/2;}var n=a.Sb/(1+a.Sb),r=e.m[0],i=L.F(e.G,{}),o=i.o;n=Math.min(Math.max(o*n,a.ie*Math.pow(a.La,e.level-1)),o*a.he);var l=a.qc;"bottom"==l||0<=l&&180>l?(i.o=o-n,e.G=L.H(i),i.y+=o-n,i.o=n,t()):(i.o=n,t(),i.y+=n,i.o=o-n,e.G=L.H(i));}(e);}else t=e.m;a.ac&&t.sort(et),"floating"==a.Tb&&n(t,a.qc,function(e){return e.description;}),n(t,a.$d,function(e){return e.attribution;});var r=L.F(e.G,{});(i[a.Dc]||O.i)(t,r,!0,a.Ld),e.Ea=!1,e.Z=!0,e.N=!0,e.Fa=!0;}(l),t.gd(l,o));}},this.Eb=this.fc=this.ce=C.qa;}var Y=new function(){this.Hg=function(e,t){var n=e.globalAlpha;e.fillStyle="dark"===t?"white":"#1d3557",e.globalAlpha=1*n,e.save(),e.transform(.94115,0,0,.94247,-78.54,-58),e.beginPath(),e.moveTo(86.47,533.3),e.bezierCurveTo(83.52,531.5,83.45,530.6,83.45,488.3),e.bezierCurveTo(83.45,444.6,83.35,445.7,87.34,443.7),e.bezierCurveTo(88.39,443.1,90.5,442.5,92.02,442.4),e.bezierCurveTo(93.54,442.2,113,441.7,135.3,441.4),e.bezierCurveTo(177.9,440.7,179.3,440.7,182.7,443.4),e.bezierCurveTo(185.9,445.9,185.6,445,206.2,510.7),e.bezierCurveTo(207.8,515.8,209.5,521.3,210.1,522.9),e.bezierCurveTo(211.7,528,211.9,531.3,210.6,532.7),e.bezierCurveTo(209.5,534,208.4,534,148.5,534),e.bezierCurveTo(106.4,533.9,87.3,533.7,86.47,533.2),e.closePath(),e.fill(),e.globalAlpha=.8*n,e.beginPath(),e.moveTo(237.3,533.3),e.bezierCurveTo(234.8,532.5,233.1,530.9,231.7,528.1),e.bezierCurveTo(231,526.8,224.6,507,217.4,484.1),e.bezierCurveTo(203.1,438.8,202.6,436.7,205,431.4),e.bezierCurveTo(206.3,428.5,239.2,383.2,242.9,379.3),e.bezierCurveTo(245,377,246.9,376.7,249.7,378.2),e.bezierCurveTo(250.6,378.7,263.1,390.8,277.3,405.2),e.bezierCurveTo(301.1,429.2,303.4,431.6,305.1,435.5),e.bezierCurveTo(306.7,439,306.9,440.4,306.9,445.2),e.bezierCurveTo(306.8,455.3,302.2,526.4,301.5,528.9),e.bezierCurveTo(300.2,533.7,301,533.6,268.3,533.7),e.bezierCurveTo(252.2,533.8,238.3,533.6,237.3,533.3),e.closePath(),e.fill(),e.beginPath(),e.globalAlpha=.05*n,e.moveTo(329,533.3),e.bezierCurveTo(326.2,532.5,323.1,528.8,322.6,525.8),e.bezierCurveTo(322,521.6,327.2,446.1,328.4,442.2),e.bezierCurveTo(330.6,434.9,332.8,432.8,368.5,402.4),e.bezierCurveTo(387,386.7,403.9,372.8,406,371.4),e.bezierCurveTo(413.1,366.7,416,366.2,436.5,365.7),e.bezierCurveTo(456.8,365.2,463.6,365.6,470.2,367.6),e.bezierCurveTo(476.2,369.5,546.1,402.8,549.1,405.3),e.bezierCurveTo(550.4,406.3,552.2,408.7,553.2,410.5),e.lineTo(555,413.9),e.lineTo(555.2,459.5),e.bezierCurveTo(555.3,484.6,555.2,505.8,555,506.5),e.bezierCurveTo(554.4,509.1,548.1,517.9,543.8,522.2),e.bezierCurveTo(537.7,528.3,534.2,530.5,527.8,532.4),e.lineTo(522.3,534),e.lineTo(426.6,533.9),e.bezierCurveTo(371.1,533.9,330.1,533.6,328.9,533.3),e.closePath(),e.fill(),e.globalAlpha=.8*n,e.beginPath(),e.moveTo(87.66,423),e.bezierCurveTo(86.23,422.4,85.02,422,84.97,422),e.bezierCurveTo(84.91,422,84.55,421.1,84.16,419.9),e.bezierCurveTo(83.67,418.6,83.45,404.7,83.45,375.9),e.bezierCurveTo(83.45,328.4,83.27,330.3,88.12,328.1),e.bezierCurveTo(90.22,327.2,101.7,325.6,135.4,321.7),e.bezierCurveTo(159.9,318.8,181.1,316.5,182.5,316.5),e.bezierCurveTo(183.9,316.5,187,317.3,189.4,318.2),e.bezierCurveTo(193.5,319.8,194.7,320.8,210.1,336.2),e.bezierCurveTo(226.6,352.7,229.1,355.7,229.1,360),e.bezierCurveTo(229.1,363,226.8,366.5,212.9,385.4),e.bezierCurveTo(187.3,420.2,189.3,417.7,183.4,420.5),e.lineTo(179.5,422.3),e.lineTo(155.3,422.7),e.bezierCurveTo(89.91,424,90.39,423.9,87.65,423),e.closePath(),e.fill(),e.globalAlpha=.6*n,e.beginPath(),e.moveTo(314.6,415),e.bezierCurveTo(311.4,413.4,213.2,314.6,210.9,310.7),e.bezierCurveTo(208.9,307.2,208.5,303.4,209.9,300),e.bezierCurveTo(211.2,297,241.3,257,244.2,254.4),e.bezierCurveTo(247.3,251.7,252.9,249.7,257.4,249.7),e.bezierCurveTo(261.1,249.7,344.7,255.2,350.8,255.8),e.bezierCurveTo(358.5,256.6,363.1,259.5,366,265.1),e.bezierCurveTo(368.7,270.5,394.3,343.7,394.7,347.2),e.bezierCurveTo(395.1,351.6,393.6,356.1,390.5,359.5),e.bezierCurveTo(389.1,361,375.7,372.6,360.5,385.4),e.bezierCurveTo(326.7,414,327,413.7,324.5,415),e.bezierCurveTo(321.8,416.4,317.4,416.3,314.6,414.9),e.closePath(),e.fill(),e.globalAlpha=.4*n,e.beginPath(),e.moveTo(547.9,383.4),e.bezierCurveTo(547.1,383.2,533,376.6,516.5,368.7),e.bezierCurveTo(497.2,359.5,485.7,353.7,484.3,352.4),e.bezierCurveTo(481.6,349.8,480.2,346.5,480.2,342.5),e.bezierCurveTo(480.2,339.2,499.2,237,500.4,233.9),e.bezierCurveTo(502.2,229.1,506.2,225.8,511.3,224.9),e.bezierCurveTo(516.2,224,545.8,222.2,548.2,222.6),e.bezierCurveTo(551.5,223.2,553.7,224.7,555.1,227.3),e.bezierCurveTo(556.2,229.3,556.3,234,556.5,301.9),e.bezierCurveTo(556.6,341.8,556.5,375.7,556.3,377.2),e.bezierCurveTo(555.6,381.8,552,384.4,547.8,383.4),e.closePath(),e.fill(),e.globalAlpha=.4*n,e.beginPath(),e.moveTo(418.7,347),e.bezierCurveTo(416,346.1,413.6,344.3,412.3,342.1),e.bezierCurveTo(411.6,341,404.4,321.3,396.3,298.3),e.bezierCurveTo(382,258.1,381.5,256.4,381.5,251.7),e.bezierCurveTo(381.5,248.2,381.8,246.2,382.7,244.7),e.bezierCurveTo(383.4,243.4,389.5,233.9,396.5,223.4),e.bezierCurveTo(412.6,199,411.3,199.9,430.6,198.6),e.bezierCurveTo(445,197.6,449.5,197.9,454.2,200.4),e.bezierCurveTo(460.5,203.7,479.6,217.5,481.3,220.1),e.bezierCurveTo(484.3,224.6,484.3,224.6,473.1,284),e.bezierCurveTo(465.3,325.9,462.4,339.9,461.3,341.8),e.bezierCurveTo(458.7,346.4,457.1,346.7,437.5,347.1),e.bezierCurveTo(428.1,347.3,419.6,347.3,418.7,347),e.closePath(),e.fill(),e.globalAlpha=.05*n,e.beginPath(),e.moveTo(89.33,308.2),e.bezierCurveTo(88.1,307.5,86.5,306.2,85.77,305.2),e.bezierCurveTo(84.42,303.4,84.42,303.4,84.24,202.6),e.bezierCurveTo(84.11,131.7,84.27,100.2,84.77,96.34),e.bezierCurveTo(85.65,89.58,87.91,84.64,92.77,78.81),e.bezierCurveTo(96.86,73.9,103.2,68.42,107.1,66.53),e.bezierCurveTo(108.6,65.81,112.8,64.64,116.5,63.92),e.bezierCurveTo(122.7,62.73,125.4,62.64,148.5,62.81),e.lineTo(173.7,63),e.lineTo(177.4,64.82),e.bezierCurveTo(179.5,65.82,182.1,67.75,183.3,69.12),e.bezierCurveTo(185.6,71.9,228.8,145.1,231.3,150.7),e.bezierCurveTo(234.5,157.7,234.9,160.8,234.9,176.9),e.bezierCurveTo(234.8,201.7,233.8,229.6,232.8,233.2),e.bezierCurveTo(232.3,235,231.1,238.1,230.2,240),e.bezierCurveTo(228.3,243.9,196.9,286.6,192.7,290.9),e.bezierCurveTo(189.8,293.9,184.3,297.1,180.2,298.2),e.bezierCurveTo(177.6,298.9,95.84,309.3,93.04,309.3),e.bezierCurveTo(92.22,309.3,90.55,308.8,89.33,308.1),e.closePath(),e.fill(),e.globalAlpha=.4*n,e.beginPath(),e.moveTo(305.7,235.6),e.bezierCurveTo(254.5,232,256.5,232.3,253.9,227.1),e.lineTo(252.4,224.2),e.lineTo(253.1,196.7),e.bezierCurveTo(253.8,170.5,253.8,169.1,255.2,166.3),e.bezierCurveTo(257.7,161.2,256.9,161.4,309.3,151.9),e.bezierCurveTo(354.1,143.8,356.8,143.4,359.7,144.2),e.bezierCurveTo(361.4,144.6,363.8,145.8,365,146.8),e.bezierCurveTo(367.3,148.6,389,179.6,391.9,185.2),e.bezierCurveTo(393.8,188.7,394.1,193.5,392.6,196.9),e.bezierCurveTo(391.5,199.6,370.6,231.4,368.4,233.8),e.bezierCurveTo(365.4,237,362,238.3,356.3,238.5),e.bezierCurveTo(353.5,238.6,330.7,237.3,305.7,235.5),e.closePath(),e.fill(),e.globalAlpha=.2*n,e.beginPath(),e.moveTo(497.1,207.1),e.bezierCurveTo(496.2,206.8,494.4,206,493.2,205.4),e.bezierCurveTo(490,203.8,472.7,191.6,469.7,189),e.bezierCurveTo(467,186.6,465.7,183.2,466.2,180.2),e.bezierCurveTo(466.5,178.1,482.4,138.6,484.9,133.5),e.bezierCurveTo(486.5,130.3,488.4,128.2,490.9,126.8),e.bezierCurveTo(492.6,125.9,496.3,125.7,522.2,125.6),e.lineTo(551.5,125.4),e.lineTo(553.7,127.6),e.bezierCurveTo(555.2,129.1,556,130.5,556.3,132.6),e.bezierCurveTo(556.5,134.2,556.6,149.6,556.5,166.9),e.bezierCurveTo(556.3,195.4,556.2,198.5,555.1,200.4),e.bezierCurveTo(553.1,204.1,551.7,204.4,529.8,206.1),e.bezierCurveTo(509.2,207.7,499.9,207.9,497,207.1),e.closePath(),e.fill(),e.globalAlpha=.2*n,e.beginPath(),e.moveTo(412.5,180.5),e.bezierCurveTo(410.9,179.7,408.7,177.9,407.5,176.4),e.bezierCurveTo(403.5,171.3,380.5,137.2,379.2,134.3),e.bezierCurveTo(377.2,129.6,377.1,126.1,378.9,116.8),e.bezierCurveTo(386.5,77.56,388.4,68.28,389.5,66.46),e.bezierCurveTo(390.1,65.34,391.7,63.83,392.9,63.1),e.bezierCurveTo(395.1,61.84,396.2,61.78,419.4,61.78),e.bezierCurveTo(443.4,61.78,443.7,61.8,446.5,63.25),e.bezierCurveTo(448,64.06,449.9,65.81,450.7,67.14),e.bezierCurveTo(452.3,69.73,468,105.5,470,111.1),e.bezierCurveTo(471.4,114.9,471.6,119.1,470.5,122.3),e.bezierCurveTo(470.1,123.5,465.2,135.8,459.7,149.5),e.bezierCurveTo(446.7,181.4,448.1,179.8,431.5,181.2),e.bezierCurveTo(419,182.2,415.7,182,412.5,180.5),e.closePath(),e.fill(),e.globalAlpha=.4*n,e.beginPath(),e.moveTo(253.6,142.8),e.bezierCurveTo(250.2,141.8,246.6,139.4,244.7,136.7),e.bezierCurveTo(242.1,132.9,207.4,73.28,206.2,70.42),e.bezierCurveTo(205.1,67.89,205,67.1,205.7,65.54),e.bezierCurveTo(207.3,61.54,202.3,61.8,284.4,61.59),e.bezierCurveTo(325.7,61.48,360.8,61.58,362.4,61.81),e.bezierCurveTo(366,62.32,369.3,65.36,369.9,68.75),e.bezierCurveTo(370.4,71.55,362.4,113.9,360.5,118.1),e.bezierCurveTo(359.1,121.3,355,125,351.4,126.4),e.bezierCurveTo(348.9,127.3,267.1,142.3,259.5,143.2),e.bezierCurveTo(257.9,143.4,255.2,143.2,253.6,142.7),e.closePath(),e.fill(),e.globalAlpha=.1*n,e.beginPath(),e.moveTo(493.4,106.8),e.bezierCurveTo(490.3,106,488.2,104.5,486.5,101.7),e.bezierCurveTo(483.8,97.43,471.8,68.81,471.8,66.76),e.bezierCurveTo(471.8,62.64,470.7,62.76,512.1,62.76),e.bezierCurveTo(553.3,62.76,552.3,62.67,554.4,66.68),e.bezierCurveTo(555.2,68.34,555.3,71.23,555.2,85.75),e.lineTo(555,102.8),e.lineTo(551.4,106.4),e.lineTo(534.1,106.8),e.bezierCurveTo(510.7,107.4,495.9,107.4,493.3,106.8),e.closePath(),e.fill(),e.restore(),e.transform(.15905,0,0,.15905,-88.65,443.2),e.globalAlpha=1*n,e.save(),e.beginPath(),e.moveTo(557.4,564.9),e.lineTo(557.4,98),e.lineTo(885.8,98),e.lineTo(885.8,185.1),e.lineTo(650.8,185.1),e.lineTo(650.8,284.7),e.lineTo(824.1,284.7),e.lineTo(824.1,371.6),e.lineTo(650.8,371.6),e.lineTo(650.8,564.9),e.lineTo(557.4,564.9),e.closePath(),e.fill(),e.beginPath(),e.moveTo(1029,568),e.quadraticCurveTo(961.1,568,915.7,522.5),e.quadraticCurveTo(870.2,476.7,870.2,409.2),e.quadraticCurveTo(870.2,341.3,915.7,295.9),e.quadraticCurveTo(961.1,250.4,1029,250.4),e.quadraticCurveTo(1096.8,250.4,1142.3,295.9),e.quadraticCurveTo(1187.7,341.3,1187.7,409.2),e.quadraticCurveTo(1187.7,477.1,1142.3,522.5),e.quadraticCurveTo(1097.3,568.1,1029.3,568.1),e.closePath(),e.moveTo(1028.6,492.6),e.quadraticCurveTo(1064.1,492.6,1086.2,469),e.quadraticCurveTo(1108.3,445,1108.3,409.5),e.quadraticCurveTo(1108.3,374,1086.2,350),e.quadraticCurveTo(1064.1,326.1,1028.3,326.1),e.quadraticCurveTo(993.1,326.1,971,350),e.quadraticCurveTo(948.9,374,948.9,409.5),e.quadraticCurveTo(948.9,445,971,469),e.quadraticCurveTo(993.1,492.6,1028.6,492.6),e.closePath(),e.fill(),e.beginPath(),e.moveTo(1253,291),e.quadraticCurveTo(1312.1,253.6,1390,253.6),e.quadraticCurveTo(1446,253.6,1478.7,284.7),e.quadraticCurveTo(1511.4,315.9,1511.4,378.1),e.lineTo(1511.4,564.9),e.lineTo(1424.2,564.9),e.lineTo(1424.2,540),e.quadraticCurveTo(1386.2,564.9,1355.7,564.9),e.quadraticCurveTo(1293.5,564.9,1262.3,538.5),e.quadraticCurveTo(1231.2,512,1231.2,465.3),e.quadraticCurveTo(1231.2,421.7,1260.4,387.5),e.quadraticCurveTo(1290,353.3,1355.7,353.3),e.quadraticCurveTo(1385.9,353.3,1424.2,371.9),e.lineTo(1424.2,362.6),e.quadraticCurveTo(1423.6,328.4,1374.4,325.2),e.quadraticCurveTo(1318.3,325.2,1287.2,343.9),e.lineTo(1253,291),e.closePath(),e.moveTo(1424.2,471.5),e.lineTo(1424.2,436.3),e.quadraticCurveTo(1411.7,412.3,1365,412.3),e.quadraticCurveTo(1309,418.5,1305.9,455.9),e.quadraticCurveTo(1309,492.9,1365,496),e.quadraticCurveTo(1411.7,496,1424.2,471.5),e.closePath(),e.fill(),e.beginPath(),e.moveTo(1675,365.7),e.lineTo(1675,564.9),e.lineTo(1587.8,564.9),e.lineTo(1587.8,262.5),e.lineTo(1675,253.2),e.lineTo(1675,280.9),e.quadraticCurveTo(1704.2,253.5,1749.7,253.5),e.quadraticCurveTo(1808.8,253.5,1839.9,289.3),e.quadraticCurveTo(1874.2,253.5,1942.6,253.5),e.quadraticCurveTo(2001.8,253.5,2032.9,289.3),e.quadraticCurveTo(2064,325.1,2064,371.8),e.lineTo(2064,564.8),e.lineTo(1976.9,564.8),e.lineTo(1976.9,393.6),e.quadraticCurveTo(1976.9,362.5,1962.9,345.4),e.quadraticCurveTo(1948.8,328.2,1917.4,327.3),e.quadraticCurveTo(1891.6,329.2,1872.6,361.6),e.quadraticCurveTo(1871,371.2,1871,381.2),e.lineTo(1871,564.9),e.lineTo(1783.9,564.9),e.lineTo(1783.9,393.7),e.quadraticCurveTo(1783.9,362.5,1769.9,345.4),e.quadraticCurveTo(1755.9,328.3,1724.4,327.4),e.quadraticCurveTo(1695.8,329.2,1674.9,365.7),e.closePath(),e.fill(),e.beginPath(),e.moveTo(2058,97.96),e.lineTo(2058,185.1),e.lineTo(2213.6,185.1),e.lineTo(2213.6,564.9),e.lineTo(2306.9,564.9),e.lineTo(2306.9,185.1),e.lineTo(2462.5,185.1),e.lineTo(2462.5,97.96),e.lineTo(2057.8,97.96),e.closePath(),e.fill(),e.beginPath(),e.moveTo(2549,287.8),e.quadraticCurveTo(2582.3,253.5,2630.2,253.5),e.quadraticCurveTo(2645.5,253.5,2659.2,256),e.lineTo(2645.5,341.9),e.quadraticCurveTo(2630.2,328.2,2601.9,327.3),e.quadraticCurveTo(2570.1,329.5,2549,373.4),e.lineTo(2549,564.8),e.lineTo(2461.8,564.8),e.lineTo(2461.8,262.5),e.lineTo(2549,253.1),e.lineTo(2549,287.7),e.closePath(),e.fill(),e.beginPath(),e.moveTo(2694,409.2),e.quadraticCurveTo(2694,340.7,2737.5,297.1),e.quadraticCurveTo(2781.1,253.5,2849.6,253.5),e.quadraticCurveTo(2918.1,253.5,2958.5,297.1),e.quadraticCurveTo(2999,340.6,2999,409.2),e.lineTo(2999,440.3),e.lineTo(2784.2,440.3),e.quadraticCurveTo(2787.3,465.2,2806,479.2),e.quadraticCurveTo(2824.7,493.2,2849.6,493.2),e.quadraticCurveTo(2893.1,493.2,2927.4,468.3),e.lineTo(2977.2,518.1),e.quadraticCurveTo(2943,564.8,2849.6,564.8),e.quadraticCurveTo(2781.1,564.8,2737.5,521.2),e.quadraticCurveTo(2693.9,477.6,2693.9,409.1),e.closePath(),e.moveTo(2911.9,378),e.quadraticCurveTo(2911.9,353.1,2893.2,339.1),e.quadraticCurveTo(2874.5,325.1,2849.6,325.1),e.quadraticCurveTo(2824.7,325.1,2806,339.1),e.quadraticCurveTo(2787.3,353.1,2787.3,378),e.lineTo(2911.8,378),e.closePath(),e.fill(),e.beginPath(),e.moveTo(3052,409.2),e.quadraticCurveTo(3052,340.7,3095.5,297.1),e.quadraticCurveTo(3139.1,253.5,3207.6,253.5),e.quadraticCurveTo(3276.1,253.5,3316.5,297.1),e.quadraticCurveTo(3357,340.6,3357,409.2),e.lineTo(3357,440.3),e.lineTo(3142.2,440.3),e.quadraticCurveTo(3145.3,465.2,3164,479.2),e.quadraticCurveTo(3182.7,493.2,3207.6,493.2),e.quadraticCurveTo(3251.1,493.2,3285.4,468.3),e.lineTo(3335.2,518.1),e.quadraticCurveTo(3301,564.8,3207.6,564.8),e.quadraticCurveTo(3139.1,564.8,3095.5,521.2),e.quadraticCurveTo(3051.9,477.6,3051.9,409.1),e.closePath(),e.moveTo(3269.9,378),e.quadraticCurveTo(3269.9,353.1,3251.2,339.1),e.quadraticCurveTo(3232.5,325.1,3207.6,325.1),e.quadraticCurveTo(3182.7,325.1,3164,339.1),e.quadraticCurveTo(3145.3,353.1,3145.3,378),e.lineTo(3269.8,378),e.closePath(),e.fill(),e.restore();};};function X(e,t){function n(e,t){var n=e.O.r,r=n/15,a=.5*n/15;n/=5;var i=e.O.x;e=e.O.y,t.fillRect(i-a,e-a,r,r),t.fillRect(i-a-n,e-a,r,r),t.fillRect(i-a+n,e-a,r,r);}function u(e,t,n,r){null===e&&n.clearRect(0,0,R,A);var a,i=Array(ea.length);for(a=ea.length-1;0<=a;a--)i[a]=ea[a].na(n,r);for(a=ea.length-1;0<=a;a--)i[a]&&ea[a].before(n,r);for(B.rc([n,O],function(r){var a;if(null!==e){for(n.save(),n.globalCompositeOperation="destination-out",n.fillStyle=n.strokeStyle="rgba(255, 255, 255, 1)",a=e.length-1;0<=a;a--){var o=e[a],l=o.C;l&&(n.save(),n.beginPath(),o.Ib(n),h.Ud(n,l),n.fill(),0<(o=ee.$a*Math.pow(ee.La,o.level-1))&&(n.lineWidth=o/2,n.stroke()),n.restore());}n.restore();}if(r=r.scale,0!==t.length){for(a={},l=ea.length-1;0<=l;l--)ea[l].og(a);for(o=er.length-1;0<=o;o--)if(a[(l=er[o]).id]){var u=l.Kd;for(l=0;l<t.length;l++){var s=t[l];!s.parent||s.parent.xa&&s.parent.R?u(s,r):s.aa.clear();}}}for(a=ea.length-1;0<=a;a--)o=ea[a],i[a]&&o.Nd(t,n,r);}),a=ea.length-1;0<=a;a--)i[a]&&ea[a].after(n);ee.Zc&&(n.canvas.style.opacity=.99,setTimeout(function(){n.canvas.style.opacity=1;},1));}function s(){var e,t=null,n=null,r=null;return B.rc([],function(e){a=e.scale,z===M?a<.9*K&&(z=E,D=j,p()):a>=K&&(z=M,D=F,p());var a,i=!1;P.L(q,function(e){e.W&&(i=e.vd()||i,e.cc(),e.Ma=Q.i(e)||e.Ma);}),i&&(q.N=!0);var o="onSurfaceDirty"===ee.Ng;P.fd(q,function(e){e.parent&&e.parent.Z&&(e.aa.clear(),e.Ma=!0,o||(e.oc=!0,e.Qb.clear())),o&&(e.oc=!0,e.Qb.clear());});var l=e.scale*e.scale;if(P.fd(q,function(e){if(e.R){for(var t=e.m,n=0;n<t.length;n++)if(5<t[n].O.ha*l){e.X=!0;return;}e.X=!1;}}),q.Y=!0,P.fd(q,function(t){if(t.W&&t.X&&t.xa&&t.R&&(q.N||t.Z||t.Vd)){t.Vd=!1;var n,r=t.m,a={x:0,y:0,w:0,o:0},i=!!t.G;if(1<R/e.w){for(n=r.length-1;0<=n;n--)r[n].Y=!1;if(t.Y&&i){for(n=r.length-1;0<=n;n--)if(1!==(t=r[n]).scale&&(t.Jb(e,a),a.w=e.w/t.scale,a.o=e.o/t.scale),!1===t.Y&&t.C){var o=(i=t.C).length;if(L.sa(t.C,1===t.scale?e:a))t.Y=!0;else for(var l=0;l<o;l++)if(L.Vc(i[l],i[(l+1)%o],1===t.scale?e:a)){t.Y=!0,t.J&&(t=t.J[l])&&(r[t.index].Y=!0);break;}}}}else for(n=0;n<r.length;n++)r[n].Y=i;}}),r=[],P.tc(q,function(e){if(e.parent.X&&e.Y&&e.W){r.push(e);for(var t=e.parent;t!==q&&(t.open||0===t.opacity);)t=t.parent;t!==q&&.02>Math.abs(t.scale-e.scale)&&(e.scale=Math.min(e.scale,t.scale));}}),function e(t,n,r){t.sb=Math.floor(1e3*t.scale)-r*n,0<t.opacity&&!t.open&&n++;var a=t.m;if(a)for(var i=a.length-1;0<=i;i--)t.W&&e(a[i],n,r);}(q,0,"flattened"===ee.mb?-1:1),r.sort(function(e,t){return e.sb-t.sb;}),c())t=r,n=null;else{var u={},s={},f="none"!=ee.ld&&ee.$a<ee.ab/2,d=ee.$a<ee.yc/2+ee.kd*ee.Ee.a;P.L(q,function(e){if(e.W&&!e.description&&(e.Z||e.N||e.Fc&&e.parent.X&&e.Ma)){var t,n,r,a=[e],i=e.J||e.parent.m;if(f)for(n=0;n<i.length;n++)(r=i[n])&&a.push(r);else if(d){if(!e.selected&&e.Sa){for(n=0,r=!0;n<i.length;n++)i[n]?a.push(i[n]):r=!1;!r&&1<e.level&&a.push(e.parent);}else for(n=0;n<i.length;n++)(r=i[n])&&r.selected&&a.push(r);}for(n=e.parent;n!=q;)n.selected&&(t=n),n=n.parent;for(t&&a.push(t),n=0;n<a.length;n++){for(e=(t=a[n]).parent;e&&e!==q;)0<e.opacity&&(t=e),e=e.parent;s[t.id]=!0,P.za(t,function(e){u[e.id]=!0;});}}}),n=(t=r.filter(function(e){return u[e.id];})).filter(function(e){return s[e.id];});}}),e=!1,ee.Hf&&P.L(q,function(t){if(t.W&&0!==t.pa.a&&1!==t.pa.a)return e=!0,!1;}),e?(P.sc(q,function(e){if(e.W&&(e.opacity!==e.Jc||e.Fa)){var t=e.m;if(t){for(var n=0,r=t.length-1;0<=r;r--)n=Math.max(n,t[r].Ec);e.Ec=n+e.opacity*e.pa.a;}else e.Ec=e.opacity*e.pa.a;}}),P.L(q,function(e){if(e.W&&(e.opacity!==e.Jc||e.Fa)){for(var t=e.Ec,n=e;(n=n.parent)&&n!==q;)t+=n.opacity*n.pa.a*ee.Ff;e.$c=0<t?1-Math.pow(1-e.pa.a,1/t):0,e.Jc=e.opacity;}})):P.L(q,function(e){e.W&&(e.$c=1,e.Jc=-1);}),{bg:t,ag:n,Y:r};}function c(){var e=q.Z||q.N||"none"==ee.Le;if(!e&&!q.empty()){var t=q.m[0].scale;P.L(q,function(n){if(n.W&&n.Y&&n.scale!==t)return e=!0,!1;});}return!e&&0<ee.ye&&1!=ee.Pa&&P.L(q,function(t){if(t.W&&0<t.ja)return e=!0,!1;}),"accurate"!=ee.Le||(e=(e=e||0===ee.$a)||"none"!=ee.ld&&ee.$a<ee.ab/2)||!(ee.$a<ee.yc/2+ee.kd*ee.Ee.a)||P.L(q,function(t){if(t.W&&(t.selected&&!t.Sa||!t.selected&&t.Sa))return e=!0,!1;}),e;}function p(){function e(e,n,r,a,i){function o(e,t,n,r,a){return e[r]&&(t-=n*S[r],e[r]=!1,a&&(t+=n*S[a],e[a]=!0)),t;}switch(e=C.extend({},e),r){case"never":e.labelPlainFill=!1;break;case"always":case"auto":e.labelPlainFill=!0;}if(ee.xc)switch(a){case"never":e.contentDecoration=!1;break;case"always":case"auto":e.contentDecoration=!0;}else e.contentDecoration=!1;var l=0;return C.Aa(e,function(e,t){e&&(l+=n*S["contentDecoration"===t?"labelPlainFill":t]);}),e.polygonExposureShadow=t,(l+=2*S.polygonExposureShadow)<=i||(l=o(e,l,2,"polygonExposureShadow"))<=i||(l=o(e,l,n,"polygonGradientFill","polygonPlainFill"))<=i||(l=o(e,l,n,"polygonGradientStroke"))<=i||(l=o(e,l,n,"polygonPlainStroke"))<=i||"auto"===a&&(l=o(e,l,n,"contentDecoration"))<=i||"auto"===r&&(l=o(e,l,n,"labelPlainFill")),e;}var t=z===E,n=0,r=0;P.oe(q,function(e){var t=1;P.L(e,function(){t++;}),n+=t,r=Math.max(r,t);});var a={};switch(ee.Wg){case"plain":a.polygonPlainFill=!0;break;case"gradient":a.polygonPlainFill=!t,a.polygonGradientFill=t;}switch(ee.ld){case"plain":a.polygonPlainStroke=!0;break;case"gradient":a.polygonPlainStroke=!t,a.polygonGradientStroke=t;}W=e(a,n,ee.jj,ee.hj,ee.ij),$=e(a,2*r,"always","always",ee.Fg),V=e(a,n,"always","always",ee.Eg);}function g(e){return function(t,n){return t===z?!0===W[e]:!0===(n?$:V)[e];};}function b(e,t){return function(n,r){return e(n,r)&&t(n,r);};}var m,y,w,x,S,T,z,E,M,_,D,j,F,N,O,R,A,I,B,G,H,q,U,W,V,$,K=l.Te()?50:1e4,Q=new J(e),X=new Z(e),ee=e.options;e.j.subscribe("stage:initialized",function(e,t,n,r){I=e,R=n,A=r,M=new f(E=(T=I.dc("wireframe",ee.nb,!1)).getContext("2d")),F=new f(j=(_=I.dc("hifi",ee.B,!1)).getContext("2d")),z=E,D=j,E.B=ee.nb,M.B=ee.nb,j.B=ee.B,F.B=ee.B,(O=(N=I.dc("tmp",Math.max(ee.B,ee.nb),!0)).getContext("2d")).B=1,[E,j,O].forEach(function(e){e.scale(e.B,e.B);});}),e.j.subscribe("stage:resized",function(e,t,n,r){R=n,A=r,[E,j,O].forEach(function(e){e.scale(e.B,e.B);});}),e.j.subscribe("model:loaded",function(t){U=!0,function e(t){var n=0;if(!t.empty()){for(var r=t.m,a=r.length-1;0<=a;a--)n=Math.max(n,e(r[a]));n+=1;}return t.Tf=n;}(q=t),p(),e.j.D("render:renderers:resolved",W,$,V);});var et="groupFillType groupStrokeType wireframeDrawMaxDuration wireframeLabelDrawing wireframeContentDecorationDrawing finalCompleteDrawMaxDuration finalIncrementalDrawMaxDuration groupContentDecorator".split(" "),en=["groupLabelLightColor","groupLabelDarkColor","groupLabelColorThreshold","groupUnexposureLabelColorThreshold"];e.j.subscribe("options:changed",function(e){function t(e,t,n,r){I.Ki(e,n),t.B=n,r&&t.scale(n,n);}e.dataObject||(C.bb(e,et)&&p(),C.bb(e,en)&&P.L(q,function(e){e.hd=-1;}));var n=C.has(e,"pixelRatio");e=C.has(e,"wireframePixelRatio"),(n||e)&&(n&&t(_,D,ee.B,!0),e&&t(T,z,ee.nb,!0),t(N,O,Math.max(ee.B,ee.nb),!1));}),e.j.subscribe("zoom:initialized",function(e){B=e;}),e.j.subscribe("timeline:initialized",function(e){G=e;}),e.j.subscribe("api:initialized",function(e){H=e;});var er=[{id:"offsetPolygon",Kd:function(e){if((e.selected||0<e.opacity&&!1===e.open||!e.X)&&e.aa.Ga()){var t=e.aa;if(t.clear(),e.$){var n=e.$,r=ee.Ig;0<r?h.Wi(t,n,e.parent.O.r/32,Math.min(1,r*Math.pow(1-ee.Jg*r,e.Tf))):h.Ud(t,n);}e.Dd=!0;}}},{id:"label",Kd:function(e){e.Ma&&e.Fc&&Q.u(e);}},{id:"custom",Kd:function(t,n){if(t.$&&(0<t.opacity&&(!1===t.open||!0===t.selected)||!t.X)&&t.oc&&e.options.xc&&!t.attribution){var r={};H.Xc(r,t),H.Yc(r,t),H.Wc(r,t,!0),r.context=t.Qb,r.polygonContext=t.aa,r.labelContext=t.Bc,r.shapeDirty=t.Dd,r.viewportScale=n,n={groupLabelDrawn:!0,groupPolygonDrawn:!0},e.options.Mg(e.Cd,r,n),t.Ue=n.groupLabelDrawn,t.Ed=n.groupPolygonDrawn,t.Dd=!1,t.oc=!1;}}}].reverse(),ea=[new function(e){var t=Array(e.length);this.Nd=function(n,r,a){if(0!==n.length){var i,o=[],l=n[0].sb;for(i=0;i<n.length;i++){var u=n[i];u.sb!==l&&(o.push(i),l=u.sb);}o.push(i);for(var s=l=0;s<o.length;s++){for(var c=o[s],f=e.length-1;0<=f;f--)if(t[f]){var d=e[f];for(r.save(),i=l;i<c;i++)u=n[i],r.save(),u.Ib(r),d.kb.call(d,u,r,a),r.restore();d.Wa.call(d,r,a),r.restore();}l=c;}}},this.na=function(n,r){for(var a=!1,i=e.length-1;0<=i;i--)t[i]=e[i].na(n,r),a|=t[i];return a;},this.before=function(n,r){for(var a=e.length-1;0<=a;a--)if(t[a]){var i=e[a];i.before.call(i,n,r);}},this.after=function(n){for(var r=e.length-1;0<=r;r--)if(t[r]){var a=e[r];a.after.call(a,n);}},this.og=function(n){for(var r=e.length-1;0<=r;r--){var a=e[r];if(t[r])for(var i=a.Ra.length-1;0<=i;i--)n[a.Ra[i]]=!0;}};}([{Ra:["offsetPolygon"],na:g("polygonExposureShadow"),before:function(e){O.save(),O.scale(e.B,e.B);},after:function(){O.restore();},rb:function(){},Wa:function(e){this.Sf&&(this.Sf=!1,e.save(),e.setTransform(1,0,0,1,0,0),e.drawImage(N,0,0,e.canvas.width,e.canvas.height,0,0,e.canvas.width,e.canvas.height),e.restore(),O.save(),O.setTransform(1,0,0,1,0,0),O.clearRect(0,0,N.width,N.height),O.restore());},kb:function(e,t,n){if(!(e.open&&e.X||e.aa.Ga())){var r=ee.ye*e.opacity*e.ja*("flattened"===ee.mb?1-e.parent.ja:(1-e.Cb)*e.parent.Cb)*(1.1<=ee.Pa?1:(ee.Pa-1)/.1);0<r&&(O.save(),O.beginPath(),e.Ib(O),e.aa.Na(O),O.shadowBlur=n*t.B*r,O.shadowColor=ee.Og,O.fillStyle="rgba(0, 0, 0, 1)",O.globalCompositeOperation="source-over",O.globalAlpha=e.opacity,O.fill(),O.shadowBlur=0,O.shadowColor="transparent",O.globalCompositeOperation="destination-out",O.fill(),O.restore(),this.Sf=!0);}}},{Ra:["offsetPolygon"],na:function(){return!0;},before:function(){function e(e){var n=e.pa,r=e.ub,a=e.selected,i=(n.h+(r?ee.$g:0)+(a?ee.rh:0))%360,o=t(n.l*e.va+(r?ee.ah:0)+(a?ee.sh:0));return n=t(n.s*e.saturation+(r?ee.bh:0)+(a?ee.th:0)),(e=e.xe).h=i,e.s=n,e.l=o,e;}function t(e){return 100<e?100:0>e?0:e;}var n=[{type:"fill",na:g("polygonPlainFill"),Pc:function(t,n){n.fillStyle=v.H(e(t));}},{type:"fill",na:g("polygonGradientFill"),Pc:function(n,r){var a=n.O.r,i=e(n);a=r.createRadialGradient(n.x,n.y,0,n.x,n.y,a*ee.Sg);var o=i.l,l=ee.Qg;a.addColorStop(0,v.i((i.h+ee.Pg)%360,t(i.s+ee.Rg),t(o+l))),o=i.l,l=ee.Ug,a.addColorStop(1,v.i((i.h+ee.Tg)%360,t(i.s+ee.Vg),t(o+l))),n.aa.Na(r),r.fillStyle=a;}},{type:"stroke",na:b(g("polygonPlainStroke"),function(){return 0<ee.ab;}),Pc:function(e,n){var r=e.pa,a=e.ub,i=e.selected,o=(r.h+ee.Ie+(a?ee.ze:0)+(i?ee.Fe:0))%360,l=t(r.s*e.saturation+ee.Ke+(a?ee.Be:0)+(i?ee.He:0));r=t(r.l*e.va+ee.Je+(a?ee.Ae:0)+(i?ee.Ge:0)),n.strokeStyle=v.i(o,l,r),n.lineWidth=ee.ab*Math.pow(ee.La,e.level-1);}},{type:"stroke",na:b(g("polygonGradientStroke"),function(){return 0<ee.ab;}),Pc:function(e,n){var r=e.O.r*ee.zh,a=e.pa,i=Math.PI*ee.vh/180;r=n.createLinearGradient(e.x+r*Math.cos(i),e.y+r*Math.sin(i),e.x+r*Math.cos(i+Math.PI),e.y+r*Math.sin(i+Math.PI));var o=e.ub,l=e.selected;i=(a.h+ee.Ie+(o?ee.ze:0)+(l?ee.Fe:0))%360;var u=t(a.s*e.saturation+ee.Ke+(o?ee.Be:0)+(l?ee.He:0));a=t(a.l*e.va+ee.Je+(o?ee.Ae:0)+(l?ee.Ge:0)),o=ee.xh,r.addColorStop(0,v.i((i+ee.wh)%360,t(u+ee.yh),t(a+o))),o=ee.Bh,r.addColorStop(1,v.i((i+ee.Ah)%360,t(u+ee.Ch),t(a+o))),n.strokeStyle=r,n.lineWidth=ee.ab*Math.pow(ee.La,e.level-1);}}],r=Array(n.length);return function(e,t){for(var a=n.length-1;0<=a;a--)r[a]=n[a].na(e,t);this.$i=n,this.wg=r;};}(),after:function(){},rb:function(){},Wa:function(){},kb:function(e,t){if(!(!e.Ed||(0===e.opacity||e.open)&&e.X||e.aa.Ga()||!ee.je&&e.description)){var n=this.$i,i=this.wg;t.beginPath(),e.aa.Na(t);for(var o=!1,l=!1,u=n.length-1;0<=u;u--){var s=n[u];if(i[u])switch(s.Pc(e,t),s.type){case"fill":o=!0;break;case"stroke":l=!0;}}n=(e.X?e.opacity:1)*e.pa.a,i=!e.empty(),u=ee.Hf?e.$c:1,o&&(e=i&&e.X&&e.R&&e.m[0].W?1-e.m.reduce(function(e,t){return e+t.ra*t.Hd;},0)/e.m.length*(1-ee.Ff):1,t.globalAlpha=n*e*u,r(t)),l&&(t.globalAlpha=n*(i?ee.$h:1)*u,t.closePath(),a(t),t.stroke());}}},{Ra:["offsetPolygon"],na:function(){return 0<ee.yc;},before:function(){},after:function(){},rb:function(){},Wa:function(){},kb:function(e,t,n){if(e.Ed&&e.selected&&!e.aa.Ga()){t.globalAlpha=e.Da,t.beginPath();var r=Math.pow(ee.La,e.level-1);t.lineWidth=ee.yc*r,t.strokeStyle=ee.uh;var a=ee.kd;0<a&&(t.shadowBlur=a*r*n*t.B,t.shadowColor=ee.De),e.aa.Na(t),t.closePath(),t.stroke();}}},{Ra:[],na:function(){return!0;},before:function(){},after:function(){},rb:function(){},Wa:function(){},Rh:Y,kb:function(e,t){var n,r,a,i,o=this.Rh;e.attribution&&!e.aa.Ga()&&(n=o.ke.width,r=o.ke.height,a=function(e){o.Hg(e,ee.ae);},i=Math.min(Math.min(.9*(i=L.Ka(e.$,e.O,n/r)),.5*e.F.o)/r,.5*e.F.w/n),t.save(),t.translate(e.x,e.y),t.globalAlpha=e.opacity*e.ca,t.scale(i,i),t.translate(-n/2,-r/2),a(t),t.restore());}},{Ra:[],na:(m=g("labelPlainFill"),y=b(g("contentDecoration"),function(){return ee.xc;}),function(e,t){return m(e,t)||y(e,t);}),before:function(){},after:function(){},rb:function(){},Wa:function(){},kb:function(e,t,n){(!(0<e.opacity)||!(0<e.ca)||e.open)&&e.X||e.aa.Ga()||(e.Cc=e.oa&&e.oa.ka&&ee.B*e.oa.fontSize*e.scale*n>=ee.oh,"auto"===e.pd?!ee.je&&e.description?e.fb=e.parent.fb:(t=(n=e.xe).h+(n.s<<9)+(n.l<<16),e.hd!==t&&(n=v.T(n),e.fb=n>(0>e.ja?ee.Dh:ee.dh)?ee.eh:ee.nh,e.hd=t)):e.fb=e.pd);}},{Ra:["custom"],na:b(g("contentDecoration"),function(){return ee.xc;}),before:function(){},after:function(){},rb:function(){},Wa:function(){},kb:function(e,t){!(0<e.opacity&&0<e.ca&&!e.open||!e.X)||e.Qb.Ga()||e.aa.Ga()||(e.Cc||void 0===e.oa?(t.globalAlpha=e.ca*(e.X?e.opacity:1)*(e.empty()?1:ee.Gf),t.fillStyle=e.fb,t.strokeStyle=e.fb,e.Qb.Na(t)):n(e,t));}},{Ra:["label"],na:g("labelPlainFill"),before:function(){},after:function(){},rb:function(){},Wa:function(){},kb:function(e,t,r){e.Ue&&e.Fc&&(0<e.opacity&&0<e.ca&&!e.open||!e.X)&&!e.aa.Ga()&&e.oa&&(t.fillStyle=e.fb,t.globalAlpha=e.ca*(e.X?e.opacity:1)*(e.empty()?1:ee.Gf),e.Cc?o(e,t,r):n(e,t));}}].reverse())];this.M=function(){var e;S=(e=function(){return d.estimate();},l.Hh()?function(){var t="CarrotSearchFoamTree"+":"+JSON.stringify(arguments),n=window.localStorage.getItem(t);return(n&&(n=JSON.parse(n)),n&&Date.now()-n.t<12096e5)?n.v:(n=e.apply(this,arguments),window.localStorage.setItem(t,JSON.stringify({v:n,t:Date.now()})),n);}:e)(ei()),X.M();},this.clear=function(){z.clearRect(0,0,R,A),D.clearRect(0,0,R,A);};var eo=!1,el=void 0;this.u=function(e){eo?el=e:e();},this.Nd=function(e){i(X);var n=null!==(x=s()).ag,r=0<I.$b("hifi"),a=r&&(n||!e);e=n||U||!e,U=!1,r&&!a&&eu.Yi(),u(x.ag,x.bg,a?D:z,e),P.za(q,function(e){e.Z=!1,e.N=!1,e.Sa=!1;}),a||(window.clearTimeout(w),eo=!0,w=setTimeout(function(){if(eo=!1,function(){if(ee.B!==ee.nb)return!0;var e="polygonPlainFill polygonPlainStroke polygonGradientFill polygonGradientStroke labelPlainFill contentDecoration".split(" ");P.L(q,function(t){if(t.W&&t.U)return e.push("polygonExposureShadow"),!1;});for(var t=e.length-1;0<=t;t--){var n=e[t];if(!!W[n]!=!!$[n])return!0;}return!1;}()){var e=!c();u(null,x.Y,D,e),C.defer(function(){eu.Xi(),el&&(el(),el=void 0);});}else el&&(el(),el=void 0);},Math.max(ee.kj,3*t.Xf.sd,3*t.Xf.rd))),ee.Bf(n);},this.i=function(e){e=e||{},i(X),q.N=!0;var t=s(),n=ee.B;try{var r=C.I(e.pixelRatio,ee.B);ee.B=r;var a=I.dc("export",r,!0),o=a.getContext("2d");z===M&&(o=new f(o)),o.scale(r,r);var l=C.has(e,"backgroundColor");l&&(o.save(),o.fillStyle=e.backgroundColor,o.fillRect(0,0,R,A),o.restore()),u(l?[]:null,t.bg,o,!0);}finally{ee.B=n;}return a.toDataURL(C.I(e.format,"image/png"),C.I(e.quality,.8));};var eu=function(){function e(e,t,r,a,i,o){function l(e,t,n,r){return G.K.A({opacity:I.$b(e)}).fa({duration:n,P:{opacity:{end:t,easing:r}},ba:function(){I.$b(e,this.opacity);}}).done();}var u=C.od(I.$b(e),t),s=C.od(I.$b(a),i);if(!u||!s){for(var c=n.length-1;0<=c;c--)n[c].stop();return n=[],u||n.push(l(e,t,r,k.Gb)),s||n.push(l(a,i,o,k.Uf)),G.K.A({}).Qa(n).start();}}var t,n=[];return{Yi:function(){ee.Zc?1!==T.style.opacity&&(T.style.visibility="visible",_.style.visibility="hidden",T.style.opacity=1,_.style.opacity=0):t&&t.xb()||(t=e("wireframe",1,ee.te,"hifi",0,ee.te));},Xi:function(){ee.Zc?(_.style.visibility="visible",T.style.visibility="hidden",T.style.opacity=0,_.style.opacity=1):e("hifi",1,ee.eg,"wireframe",0,ee.eg);}};}();return i=function(e){e.apply();},r=function(e){e.fill();},a=function(e){e.stroke();},this;}function J(e){function t(e){void 0!==e.groupLabelFontFamily&&(a.fontFamily=e.groupLabelFontFamily),void 0!==e.groupLabelFontStyle&&(a.fontStyle=e.groupLabelFontStyle),void 0!==e.groupLabelFontVariant&&(a.fontVariant=e.groupLabelFontVariant),void 0!==e.groupLabelFontWeight&&(a.fontWeight=e.groupLabelFontWeight),void 0!==e.groupLabelLineHeight&&(a.lineHeight=e.groupLabelLineHeight),void 0!==e.groupLabelHorizontalPadding&&(a.cb=e.groupLabelHorizontalPadding),void 0!==e.groupLabelVerticalPadding&&(a.Ua=e.groupLabelVerticalPadding),void 0!==e.groupLabelMaxTotalHeight&&(a.ib=e.groupLabelMaxTotalHeight),void 0!==e.groupLabelMaxFontSize&&(a.hb=e.groupLabelMaxFontSize);}var n,r=e.options,a={},i={},l={groupLabel:""},u={};e.j.subscribe("api:initialized",function(e){n=e;}),e.j.subscribe("options:changed",t),t(e.Cd),this.i=function(e){if(!e.$)return!1;var t=e.group.label;return r.gh&&!e.attribution&&(l.labelText=t,n.nc(r.fh,e,l),t=l.labelText),e.Ve=t,e.qd!==t;},this.u=function(e){var t=e.Ve;if(e.qd=t,e.Bc.clear(),e.oa=void 0,e.$&&!C.Oe(t)&&("flattened"!==r.mb||e.empty()||!e.R||!e.m[0].W)){var o=N.de;if(r.mh){u.fontFamily=a.fontFamily,u.fontStyle=a.fontStyle,u.fontVariant=a.fontVariant,u.fontWeight=a.fontWeight,u.lineHeight=a.lineHeight,u.horizontalPadding=a.cb,u.verticalPadding=a.Ua,u.maxTotalTextHeight=a.ib,u.maxFontSize=a.hb,n.nc(r.lh,e,u),i.fontFamily=u.fontFamily,i.fontStyle=u.fontStyle,i.fontVariant=u.fontVariant,i.fontWeight=u.fontWeight,i.lineHeight=u.lineHeight,i.cb=u.horizontalPadding,i.Ua=u.verticalPadding,i.ib=u.maxTotalTextHeight,i.hb=u.maxFontSize;var l=i;}else l=a;e.oa=o.call(N,l,e.Bc,t,e.$,e.F,e.O,!1,!1,e.Oh,e.O.ha,r.ph,e.Ma);}e.Ma=!1;},o=this.H=function(e,t){e.Bc.Na(t);};}function Z(e){function t(e,t){var n,r=e.m,a=r.length,o=i.O.r;for(n=0;n<a;n++){var l=r[n];l.tb=(180*(Math.atan2(l.x-e.x,l.y-e.y)+t)/Math.PI+180)/360,l.wc=Math.min(1,Math.sqrt(L.i(l,e))/o);}}function n(e,t){var n=(e=e.m).length;if(1===n||2===n&&e[0].description)e[0].tb=.5;else{var r=0,a=Number.MAX_VALUE,i=Math.sin(t),o=Math.cos(t);for(t=0;t<n;t++){var l=e[t],u=l.x*i+l.y*o;r<u&&(r=u),a>u&&(a=u),l.tb=u,l.wc=1;}for(t=0;t<n;t++)(l=e[t]).tb=(l.tb-a)/(r-a);}}function r(e,t,n,r){return(t=t[r])+(n[r]-t)*e;}var a,i,o={radial:t,linear:n},l=e.options,u={groupColor:null,labelColor:null};return e.j.subscribe("model:loaded",function(e){i=e;}),e.j.subscribe("api:initialized",function(e){a=e;}),this.M=function(){},this.apply=function(){function e(e){return 0>e?0:100<e?100:e;}var s=o[l.yi]||t,c=l.Ii,f=l.Bi,d=l.Kg,h=l.Lg,p=l.Ci,g=l.Gi;!function t(i){if(i.R&&i.xa){var o,b=i.m;if(i.Z||i.Fa||h){for(0===i.level?s(i,l.zi*Math.PI/180):n(i,l.Di*Math.PI/180),o=b.length-1;0<=o;o--){var m=b[o];m.Fa=!0;var y=m.tb,w=m.we;if(0===i.level)var x=r(y,c,f,"h"),S=(g+(1-g)*m.wc)*r(y,c,f,"s"),k=(1+(0>m.ja?p*(m.ja+1):p)*(1-m.wc))*r(y,c,f,"l"),T=r(y,c,f,"a");else x=(k=i.pa).h,S=k.s,k=function(t,n,r,a){var i=e(t+r*a);return i+n*((t=e(t-r*(1-a)))-i);}(k.l,y,l.Ei,l.Fi),T=i.we.a;w.h=x,w.s=S,w.l=k,w.a=T,x=m.pa,m.attribution?(x.h=0,x.s=0,x.l="light"==l.ae?90:10,x.a=1):(x.h=w.h,x.s=w.s,x.l=w.l,x.a=w.a),h&&!m.attribution&&(u.groupColor=x,u.labelColor="auto",a.nc(d,m,u,function(e){e.ratio=y;}),m.pa=v.u(u.groupColor),m.pa.a=C.has(u.groupColor,"a")?u.groupColor.a:1,"auto"!==u.labelColor&&(m.pd=v.wa(u.labelColor)));}i.Fa=!1;}for(o=b.length-1;0<=o;o--)t(b[o]);}}(i);},this;}function ee(){this.kc=this.Yd=this.hc=this.Wf=this.w=this.dg=this.weight=this.y=this.x=this.id=0,this.C=this.parent=this.m=null,this.F={x:0,y:0,w:0,o:0},this.J=null,this.qd=this.Ve=void 0,this.Sc=!1,this.wc=this.tb=0,this.we={h:0,s:0,l:0,a:0,model:"hsla"},this.pa={h:0,s:0,l:0,a:0,model:"hsla"},this.xe={h:0,s:0,l:0,model:"hsl"},this.hd=-1,this.pd="auto",this.fb="#000",this.Tf=this.level=this.nd=this.index=0,this.attribution=!1,this.ha=this.$e=0,this.Y=!1,this.$=null,this.O={x:0,y:0,ha:0,r:0},this.Fd=this.G=null,this.Fc=this.W=this.Sa=this.oc=this.Vd=this.Dd=this.Ma=this.Fa=this.N=this.Z=this.Ea=this.xa=this.R=this.Ia=!1,this.saturation=this.va=this.Da=this.ca=this.opacity=this.scale=1,this.ra=0,this.Hd=1,this.Cb=this.ja=this.yb=0,this.description=this.selected=this.ub=this.Bd=this.open=this.U=!1,this.sb=0,this.Ue=this.Ed=this.X=!0,this.oa=void 0,this.Cc=!1,this.Bc=new c,this.aa=new c,this.Qb=new c,this.Oh=N.bi(),this.Ec=0,this.$c=1,this.Jc=-1,this.empty=function(){return!this.m||0===this.m.length;};var e=[];this.mc=function(t){e.push(t);},this.Nc=function(t){C.Jf(e,t);};var t={scale:1};this.vd=function(){var n=!1;this.scale=1;for(var r=0;r<e.length;r++)n=e[r].We(this,t)||n,this.scale*=t.scale;return n;},this.Ib=function(t){for(var n=0;n<e.length;n++)e[n].Ib(this,t);},this.transformPoint=function(t,n){for(n.x=t.x,n.y=t.y,t=0;t<e.length;t++)e[t].transformPoint(this,n,n);return n;},this.Jb=function(t,n){for(n.x=t.x,n.y=t.y,t=0;t<e.length;t++)e[t].Jb(this,n,n);return n;};var n=[];this.qb=function(e){n.push(e);},this.Mc=function(e){C.Jf(n,e);};var r={opacity:1,saturation:1,va:1,ca:1,Da:1};this.cc=function(){if(0!==n.length){this.Da=this.ca=this.va=this.saturation=this.opacity=1;for(var e=n.length-1;0<=e;e--)(0,n[e])(this,r),this.opacity*=r.opacity,this.va*=r.va,this.saturation*=r.saturation,this.ca*=r.ca,this.Da*=r.Da;}};}function et(e,t){return t.weight>e.weight?1:t.weight<e.weight?-1:e.index-t.index;}function en(e){var t,n,r,a,i,o=this,l=e.options;e.j.subscribe("stage:initialized",function(a,i,u,s){n=u,r=s,(t=a.dc("titlebar",l.B,!1).getContext("2d")).B=l.B,t.scale(t.B,t.B),e.j.D("titlebar:initialized",o);}),e.j.subscribe("stage:resized",function(e,a,i,o){n=i,r=o,t.scale(t.B,t.B);}),e.j.subscribe("zoom:initialized",function(e){i=e;}),e.j.subscribe("api:initialized",function(e){a=e;}),e.j.subscribe("model:loaded",function(){t.clearRect(0,0,n,r);}),this.update=function(e){if(t.clearRect(0,0,n,r),e){!e.empty()&&e.m[0].description&&(e=e.m[0]);var o=l.ej,u=l.dj,s=Math.min(r/2,l.Wd+2*o),c=s-2*o,f=n-2*u;if(!(0>=c||0>=f)){var d=e.Cc?e.oa.fontSize*e.scale*i.scale():0,h={titleBarText:e.qd,titleBarTextColor:l.$f,titleBarBackgroundColor:l.Zf,titleBarMaxFontSize:l.Wd,titleBarShown:d<l.Vh};if(e.attribution)var p=E.Qf("B`ssnu!Rd`sbi!Gn`lUsdd!whrt`mh{`uhno/!Busm,bmhbj!uid!mnfn!un!fn!un!iuuqr;..b`ssnurd`sbi/bnl.gn`lusdd!gns!lnsd!edu`hmr/");else a.nc(l.aj,e,h,function(e){e.titleBarWidth=f,e.titleBarHeight=c,e.labelFontSize=d,e.viewportScale=i.scale();}),p=h.titleBarText;p&&0!==p.length&&h.titleBarShown&&(o={x:u,y:(e=i.Uc(e.transformPoint(e,{}),{}).y>r/2)?o:r-s+o,w:f,o:c},u=L.H(o),t.fillStyle=l.Zf,t.fillRect(0,e?0:r-s,n,s),t.fillStyle=l.$f,N.se({fontFamily:l.bj||l.hh,fontStyle:l.Dj||l.ih,fontWeight:l.Fj||l.kh,fontVariant:l.Ej||l.jh,hb:l.Wd,Gc:l.cj,cb:0,Ua:0,ib:1},t,p,u,o,{x:o.x+o.w/2,y:o.y+o.o/2},!0,!0).ka||t.clearRect(0,0,n,r));}}};}function er(e){function t(e,t,n){return y=!0,u&&u.stop(),s&&s.stop(),o(d.reset(e),t,n).then(function(){y=!1;});}function n(t){d.update(t),c.N=!0,e.j.D("foamtree:dirty",!0);}function r(e,t){return d.i((0!==d.u()?.35:1)*e,(0!==d.H()?.35:1)*t);}function a(){if(1===h.ratio){var e=Math.round(1e4*d.u())/1e4;0!==e&&(p.Id=e,u=f.K.jc(p).fa({duration:500,P:{x:{start:e,end:0,easing:k.Gb}},ba:function(){d.i(p.x-p.Id,0),n(1),p.Id=p.x;}}).start());}}function i(){if(1===h.ratio){var e=Math.round(1e4*d.H())/1e4;0!==e&&(v.Jd=e,s=f.K.jc(v).fa({duration:500,P:{y:{start:e,end:0,easing:k.Gb}},ba:function(){d.i(0,v.y-v.Jd),n(1),v.Jd=v.y;}}).start());}}function o(e,t,r){return e?f.K.jc(h).fa({duration:void 0===t?700:t,P:{ratio:{start:0,end:1,easing:r||k.Vf}},ba:function(){n(h.ratio);}}).Ta():(new b).resolve().promise();}function l(e){return function(){return y?(new b).resolve().promise():e.apply(this,arguments);};}var u,s,c,f,d=new g(e),h={ratio:1},p={le:0,x:0,Id:0},v={me:0,y:0,Jd:0},m=this,y=!1;e.j.subscribe("model:loaded",function(e){c=e,d.reset(!1),d.update(1);}),e.j.subscribe("timeline:initialized",function(e){f=e;}),this.M=function(){e.j.D("zoom:initialized",this);},this.reset=function(e,n){return d.Fb(1),t(!0,e,n);},this.normalize=l(function(e,n){d.pc(1)?t(!1,e,n):m.af();}),this.af=function(){a(),i();},this.cg=l(function(e,t,n,r){return m.ic(e.F,t,n,r);}),this.Nb=l(function(e,t,n,r){return o(d.Nb(e,t),n,r);}),this.ic=l(function(e,t,n,r){return o(d.ic(e,t),n,r);}),this.fj=l(function(e,t){d.ic(e,t)&&n(1);}),this.Xh=l(function(e,t){1===h.ratio&&r(e,t)&&n(1);}),this.rg=l(function(e,t){d.Nb(e,t)&&n(1);}),this.qg=l(function(e,t,a,i){(e=0|d.Nb(e,t)|r(a,i))&&n(1);}),this.Yh=l(function(e,t,o){1===h.ratio&&(u=f.K.jc(p).fa({duration:e/.03,P:{le:{start:t,end:0,easing:k.Gb}},ba:function(){d.i(p.le,0)&&n(1),a();}}).start(),s=f.K.jc(v).fa({duration:e/.03,P:{me:{start:o,end:0,easing:k.Gb}},ba:function(){r(0,v.me)&&n(1),i();}}).start());}),this.Zh=function(){u&&0===d.u()&&u.stop(),s&&0===d.H()&&s.stop();},this.rc=function(e,t){d.rc(e,t);},this.Fb=function(e){return d.Fb(e);},this.pc=function(e){return d.pc(e);},this.zd=function(){return d.zd();},this.absolute=function(e,t){return d.absolute(e,t);},this.Uc=function(e,t){return d.Uc(e,t);},this.scale=function(){return d.scale();},this.i=function(e){return d.T(e);},this.content=function(e,t,n,r){d.content(e,t,n,r);};}function ea(e,t,r){function a(e){var t=[];return P.L(d,function(n){e(n)&&t.push(n.group);}),{groups:t};}function i(e,t){var n=g.options,r=n.Pi,a=n.Oi;n=n.Od;var i=0<r+a?n:0,o=[];return F.u(e,F.i(e,g.options.Qd),function(e,n,l){n="groups"===g.options.Pd?l:n,e.m&&(e=w.K.A(e).wait(i*(a+r*n)).call(t).done(),o.push(e));}),w.K.A({}).Qa(o).Ta();}function o(e){ev||(ev=!0,v.once(function(){ev=!1,g.j.D("repaint:before"),j.Nd(this.pg);},{pg:e}));}function l(e){function t(e,a){var i=e.W;if(e.W=a<=n,e.Fc=a<=r,e.W!==i&&P.ne(e,function(e){e.Vd=!0;}),e.open||e.Va||a++,e=e.m)for(i=0;i<e.length;i++)t(e[i],a);}var n=g.options.Xe,r=Math.min(g.options.Xe,g.options.Sh);if(e)for(var a=0;a<e.length;a++){var i=e[a];t(i,function(e){for(var t=0;e.parent;)e.open||e.Va||t++,e=e.parent;return t;}(i));}else t(d,0);}function c(e,t){var n=[];for((e=f(e,t)).Wh&&g.j.D("model:childrenAttached",P.uc(d)),e.Ji&&D.complete(function(e){ep.eb(e),n.push(e);}),t=e=0;t<n.length;t++){var r=n[t];r.m&&(e+=r.m.length),r.xa=!0,A.i(r);}return e;}function f(e,n){var r,a=n||g.options.Th,i=!1,o=!1,l="flattened"===t.mb;for(r=e?e.reduce(function(e,t){return e.push(t,1),e;},[]):[d,1];0<r.length;)!function(e,t){var n=!e.attribution&&t-(e.Va?1:0)<a;o=o||n,e.Ia=e.Ia||n,e.open||e.Va||t++;var u=e.m;if(!u&&n&&(i=_.T(e)||i,u=e.m,l&&(e.Ma=!0)),u)for(e=0;e<u.length;e++)r.push(u[e],t);}(r.shift(),r.shift());return{Wh:i,Ji:o};}var d,h=this,g={j:new z,options:t,Cd:r},v=new s,w=new M(v),x=u.create(),S=new p(g),T=new er(g),E=new R(g),_=new I(g.options),D=new $(g),j=new X(g,v),N=new W(g);new en(g);var O=new G(g),A=new H(g),B=new q(g),V=new U(g);g.j.subscribe("stage:initialized",function(e,t,n,r){ec.Me(n,r);}),g.j.subscribe("stage:resized",function(e,t,n,r){ec.Ni(e,t,n,r);}),g.j.subscribe("foamtree:attachChildren",c),g.j.subscribe("openclose:changing",l),g.j.subscribe("interaction:reset",function(){eg(!0);}),g.j.subscribe("foamtree:dirty",o),this.M=function(){g.j.D("timeline:initialized",w),d=_.M(),S.M(e),E.M(),j.M(),N.M(),O.M(),A.M(),T.M(),B.M(),V.M();},this.Za=function(){w.i(),eh.stop(),v.i(),S.Za();};var K="groupLabelFontFamily groupLabelFontStyle groupLabelFontVariant groupLabelFontWeight groupLabelLineHeight groupLabelHorizontalPadding groupLabelVerticalPadding groupLabelDottingThreshold groupLabelMaxTotalHeight groupLabelMinFontSize groupLabelMaxFontSize groupLabelDecorator".split(" "),Q="rainbowColorDistribution rainbowLightnessDistribution rainbowColorDistributionAngle rainbowLightnessDistributionAngle rainbowColorModelStartPoint rainbowLightnessCorrection rainbowSaturationCorrection rainbowStartColor rainbowEndColor rainbowHueShift rainbowHueShiftCenter rainbowSaturationShift rainbowSaturationShiftCenter rainbowLightnessShift rainbowLightnessShiftCenter attributionTheme".split(" "),Y=!1,J=["groupBorderRadius","groupBorderRadiusCorrection","groupBorderWidth","groupInsetWidth","groupBorderWidthScaling"],Z=["maxGroupLevelsDrawn","maxGroupLabelLevelsDrawn"];this.ig=function(e){g.j.D("options:changed",e),C.bb(e,K)&&P.L(d,function(e){e.Ma=!0;}),C.bb(e,Q)&&(d.Fa=!0),C.bb(e,J)&&(Y=!0),C.bb(e,Z)&&(l(),c());},this.reload=function(){ef.reload();},this.jg=function(e,t){C.defer(function(){if(Y)ec.Ph(e),Y=!1;else{if(t)for(var n=_.u(t),r=n.length-1;0<=r;r--)n[r].N=!0;else d.N=!0;o(e);}});},this.ga=function(){S.u();},this.update=function(e){var t=(e=e?_.u(e):[d]).reduce(function(e,t){return e[t.id]=t,e;},{});e=e.filter(function(e){for(e=e.parent;e;){if(C.has(t,e.id))return!1;e=e.parent;}return!0;}),_.update(e),ec.gj(e);},this.reset=function(){return eg(!1);},this.T=j.i,this.Ja=(ee={},function(e,t){return(e=_.i(e))?E.Wc(ee,e,t):null;}),this.wa=(et={x:0,y:0},ea={x:0,y:0},function(e,t){return(e=_.i(e))?(et.x=t.x,et.y=t.y,e.transformPoint(et,et),T.Uc(et,et),ea.x=et.x,ea.y=et.y,ea):null;}),this.sa=(ei={},function(e){return(e=_.i(e))?E.Yc(ei,e):null;}),this.hg=(eo={},function(e){return(e=_.i(e))?E.Xc(eo,e):null;}),this.ta=(el={},function(){return T.i(el);}),this.lg=function(){this.H({groups:a(function(e){return e.group.selected;}),newState:!0,keepPrevious:!1}),this.u({groups:a(function(e){return e.group.open;}),newState:!0,keepPrevious:!1}),this.i({groups:a(function(e){return e.group.exposed;}),newState:!0,keepPrevious:!1});},this.Ka=function(){return a(function(e){return e.U;});},this.i=function(e){return ef.submit(function(){return O.Vb(_.H(e,"exposed",!1),!1,!0,!1);});},this.pb=function(){return a(function(e){return e.open;});},this.u=function(e){return ef.submit(function(){return B.Bb(_.H(e,"open",!0),!1,!1);});},this.Lb=function(){return a(function(e){return e.selected;});},this.H=function(e){return ef.submit(function(){return V.select(_.H(e,"selected",!0),!1),(new b).resolve().promise();});},this.ng=function(e){return(e=_.i(e))?e===d?T.reset(t.ob,k.ia(t.Kb)):T.cg(e,t.Yb,t.ob,k.ia(t.Kb)):(new b).resolve().promise();},this.ua=function(e,t){return(e=_.u(e))?(t=c(e,t),l(e),t):0;},this.Vc=function(e){return N.Lb[e];},this.mg=function(){var e=n;return{frames:e.frames,totalTime:e.totalTime,lastFrameTime:e.rd,lastInterFrameTime:e.sd,fps:e.ve};};var ee,et,ea,ei,eo,el,eu,es,ec=function(){var e,n;function r(r,i){var o=r||e,l=i||n;e=o,n=l,(r=t.Rb&&t.Rb.boundary)&&2<r.length?d.C=r.map(function(e){return{x:o*e.x,y:l*e.y};}):d.C=[{x:0,y:0},{x:o,y:0},{x:o,y:l},{x:0,y:l}],a();}function a(){d.Z=!0,d.G=d.C,d.F=L.F(d.C,d.F),d.O=d,L.Ja(d.C,d.O);}return{Me:r,Ni:function(e,t,n,a){ep.stop();var i=n/e,l=a/t;P.oe(d,function(e){e.x=e.x*i+(Math.random()-.5)*n/1e3,e.y=e.y*l+(Math.random()-.5)*a/1e3;}),r(n,a),d.Ea=!0,D.step(ep.eb,!0,!1,function(e){var t=e.m;if(t){D.Eb(e);for(var n=t.length-1;0<=n;n--){var r=t[n];r.w=r.hc;}e.Ea=!0;}})?o(!1):(D.fc(d),g.options.Md?(o(!1),eh.Kf(),eh.Oc()):(D.complete(ep.eb),d.Fa=!0,o(!1)));},Ph:function(e){var t=!1;return d.empty()||(a(),eh.xb()||(t=D.step(ep.eb,!1,!1),o(e))),t;},gj:function(e){e.forEach(function(e){P.za(e,function(e){e.empty()||D.Eb(e);}),D.fc(e),g.options.Md?(eh.Kf(),P.za(e,function(e){e.empty()||ep.grow(e);})):(P.za(e,function(e){e.empty()||ep.eb(e);}),D.complete(ep.eb),e.Fa=!0,o(!1));});}};}(),ef=function(){function e(){if(0===t.Gd&&T.reset(0),g.options.Af(t.Rb),ec.Me(),_.load(t.Rb),f(),l(),g.j.D("model:loaded",d,P.uc(d)),!d.empty()){if(d.open=!0,d.Ia=!0,t.Md)var e,r,a,o,u=eh.Oc();else eh.ai(),P.za(d,function(e){e.xa=!1;}),(r=new y((e=new b).resolve)).i(),d.xa=!0,A.i(d).then(r.u),i(d,function e(){this.R&&this.C&&(this.Z=this.xa=!0,r.i(),A.i(this).then(r.u),r.i(),i(this,e).then(r.u));}),u=e.promise();a=t.Oa,o=t.Kc,t.Oa=0,t.Kc=0,h.lg(),t.Oa=a,t.Kc=o,0<t.Od?(j.clear(),S.i(1)):u=m([u,n(1)]);}g.options.zf(t.Rb),u&&(g.options.Df(),u.then(function(){j.u(function(){v.once(g.options.Cf);});}));}function n(e,n){return 0===t.re||n?(S.i(e),(new b).resolve().promise()):w.K.A({opacity:S.i()}).Xd(2).fa({duration:t.re,P:{opacity:{end:e,easing:k.ia(t.Dg)}},ba:function(){S.i(this.opacity);}}).Ta();}function r(){for(var e=0;e<o.length;e++){var t=o[e],n=t.action();C.has(n,"then")?n.then(t.ge.resolve):t.ge.resolve();}o=[];}var a=!1,o=[];return{reload:function(){a||(d.empty()?e():(ep.stop(),w.i(),eh.stop(),a=!0,m(0<t.Gd?[A.u(),eg(!1)]:[n(0)]).then(function(){n(0,!0),a=!1,e(),C.defer(r);})));},submit:function(e){if(a){var t=new b;return o.push({action:e,ge:t}),t.promise();}return e();}};}(),ed=new y(function(){es.resolve();}),eh=function(){function e(){return l||(ed.initial()&&(es=new b),ed.i(),n(),l=!0,v.repeat(r)),es.promise();}function n(){a=x.now();}function r(){var n=x.now()-a>t.Mi;return n=D.step(function(t){t.xa=!0,ep.grow(t),ed.i(),A.i(t).then(ed.u),ed.i(),i(t,function(){this.Ia=!0,e();}).then(ed.u);},!0,n)||n,o(!0),n&&(l=!1,ed.u()),n;}var a,l=!1;return{ai:function(){D.complete(ep.eb);},Oc:e,Kf:n,xb:function(){return!ed.initial();},stop:function(){v.cancel(r),l=!1,ed.clear();}};}(),ep=function(){function e(e){var t=!e.empty();if(e.xa=!0,t){for(var n=e.m,r=n.length-1;0<=r;r--){var a=n[r];a.w=a.hc;}e.Ea=!0;}return t;}var t=[];return{grow:function(n){var r=g.options,a=r.Yg;0<a?F.u(n,F.i(n,g.options.Qd),function(e,n,i){n="groups"===g.options.Pd?i:n,ed.i(),t.push(w.K.A(e).wait(n*r.Xg*a).fa({duration:a,P:{w:{start:e.Wf,end:e.hc,easing:k.ia(r.Zg)}},ba:function(){this.w=Math.max(0,this.w),this.parent.Ea=!0,eh.Oc();}}).Xa(ed.u).start());}):e(n)&&eh.Oc();},eb:e,stop:function(){for(var e=t.length-1;0<=e;e--)t[e].stop();t=[];}};}(),eg=(eu=!1,function(e){if(eu)return(new b).resolve().promise();eu=!0;var n=[];n.push(T.reset(t.ob,k.ia(t.Kb)));var r=new b;return O.Vb({m:[],Ca:!1,Ba:!1},e,!1,!0).then(function(){B.Bb({m:[],Ca:!1,Ba:!1},e,!1).then(r.resolve);}),n.push(r.promise()),m(n).then(function(){eu=!1,e&&t.Ef();});}),ev=!1;}function ei(){return{version:"3.5.1",build:"bugfix/3.5.x/26b14206",brandingAllowed:!1};}Y.ke={width:445.2,height:533.5},l.md(function(){window.CarrotSearchFoamTree=function(e){function t(e){function t(e,t){return C.has(n,e)?(t(n[e]),delete n[e],1):0;}if(0==arguments.length)return 0;if(1==arguments.length)var n=C.extend({},arguments[0]);else 2==arguments.length&&((n={})[arguments[0]]=arguments[1]);o&&o.validate(n,u.Qh);var a=0;s&&(a+=t("selection",s.H)+t("open",s.u)+t("exposure",s.i));var i={};return C.Aa(n,function(e,t){(l[t]!==e||C.wb(e))&&(i[t]=e,a++),l[t]=e;}),0<a&&r(i),a;}function n(e,t){var n=l[e="on"+e.charAt(0).toUpperCase()+e.slice(1)];l[e]=t(Array.isArray(n)?n:[n]),(t={})[e]=l[e],r(t);}function r(e){(function(){function t(t,n){return C.has(e,t)||void 0===n?T.A(l[t],i):n;}u.Qh=l.logging,u.Rb=l.dataObject,u.B=l.pixelRatio,u.nb=l.wireframePixelRatio,u.mb=l.stacking,u.Ag=l.descriptionGroup,u.Tb=l.descriptionGroupType,u.qc=l.descriptionGroupPosition,u.Bg=l.descriptionGroupDistanceFromCenter,u.Sb=l.descriptionGroupSize,u.ie=l.descriptionGroupMinHeight,u.he=l.descriptionGroupMaxHeight,u.je=l.descriptionGroupPolygonDrawn,u.Dc=l.layout,u.ac=l.layoutByWeightOrder,u.Zi=l.showZeroWeightGroups,u.Ce=l.groupMinDiameter,u.Ld=l.rectangleAspectRatioPreference,u.Li=l.initializer||l.relaxationInitializer,u.Mi=l.relaxationMaxDuration,u.Md=l.relaxationVisible,u.If=l.relaxationQualityThreshold,u.qh=l.groupResizingBudget,u.Yg=l.groupGrowingDuration,u.Xg=l.groupGrowingDrag,u.Zg=l.groupGrowingEasing,u.Ig=l.groupBorderRadius,u.$a=l.groupBorderWidth,u.La=l.groupBorderWidthScaling,u.jd=l.groupInsetWidth,u.Jg=l.groupBorderRadiusCorrection,u.ab=l.groupStrokeWidth,u.yc=l.groupSelectionOutlineWidth,u.uh=l.groupSelectionOutlineColor,u.kd=l.groupSelectionOutlineShadowSize,u.De=l.groupSelectionOutlineShadowColor,u.rh=l.groupSelectionFillHueShift,u.th=l.groupSelectionFillSaturationShift,u.sh=l.groupSelectionFillLightnessShift,u.Fe=l.groupSelectionStrokeHueShift,u.He=l.groupSelectionStrokeSaturationShift,u.Ge=l.groupSelectionStrokeLightnessShift,u.Wg=l.groupFillType,u.Sg=l.groupFillGradientRadius,u.Pg=l.groupFillGradientCenterHueShift,u.Rg=l.groupFillGradientCenterSaturationShift,u.Qg=l.groupFillGradientCenterLightnessShift,u.Tg=l.groupFillGradientRimHueShift,u.Vg=l.groupFillGradientRimSaturationShift,u.Ug=l.groupFillGradientRimLightnessShift,u.ld=l.groupStrokeType,u.ab=l.groupStrokeWidth,u.Ie=l.groupStrokePlainHueShift,u.Ke=l.groupStrokePlainSaturationShift,u.Je=l.groupStrokePlainLightnessShift,u.zh=l.groupStrokeGradientRadius,u.vh=l.groupStrokeGradientAngle,u.Ah=l.groupStrokeGradientUpperHueShift,u.Ch=l.groupStrokeGradientUpperSaturationShift,u.Bh=l.groupStrokeGradientUpperLightnessShift,u.wh=l.groupStrokeGradientLowerHueShift,u.yh=l.groupStrokeGradientLowerSaturationShift,u.xh=l.groupStrokeGradientLowerLightnessShift,u.$g=l.groupHoverFillHueShift,u.bh=l.groupHoverFillSaturationShift,u.ah=l.groupHoverFillLightnessShift,u.ze=l.groupHoverStrokeHueShift,u.Be=l.groupHoverStrokeSaturationShift,u.Ae=l.groupHoverStrokeLightnessShift,u.Pa=l.groupExposureScale,u.Og=l.groupExposureShadowColor,u.ye=l.groupExposureShadowSize,u.Yb=l.groupExposureZoomMargin,u.Eh=l.groupUnexposureLightnessShift,u.Fh=l.groupUnexposureSaturationShift,u.Dh=l.groupUnexposureLabelColorThreshold,u.Oa=l.exposeDuration,u.Wb=l.exposeEasing,u.Kc=l.openCloseDuration,u.Kg=T.A(l.groupColorDecorator,i),u.Lg=l.groupColorDecorator!==C.qa,u.fh=T.A(l.groupLabelDecorator,i),u.gh=l.groupLabelDecorator!==C.qa,u.lh=T.A(l.groupLabelLayoutDecorator,i),u.mh=l.groupLabelLayoutDecorator!==C.qa,u.Mg=T.A(l.groupContentDecorator,i),u.xc=l.groupContentDecorator!==C.qa,u.Ng=l.groupContentDecoratorTriggering,u.Hi=l.rainbowStartColor,u.Ai=l.rainbowEndColor,u.yi=l.rainbowColorDistribution,u.zi=l.rainbowColorDistributionAngle,u.Di=l.rainbowLightnessDistributionAngle,u.Ei=l.rainbowLightnessShift,u.Fi=l.rainbowLightnessShiftCenter,u.Gi=l.rainbowSaturationCorrection,u.Ci=l.rainbowLightnessCorrection,u.Ff=l.parentFillOpacity,u.$h=l.parentStrokeOpacity,u.Gf=l.parentLabelOpacity,u.Hf=l.parentOpacityBalancing,u.ph=l.groupLabelUpdateThreshold,u.hh=l.groupLabelFontFamily,u.ih=l.groupLabelFontStyle,u.jh=l.groupLabelFontVariant,u.kh=l.groupLabelFontWeight,u.oh=l.groupLabelMinFontSize,u.vj=l.groupLabelMaxFontSize,u.uj=l.groupLabelLineHeight,u.tj=l.groupLabelHorizontalPadding,u.xj=l.groupLabelVerticalPadding,u.wj=l.groupLabelMaxTotalHeight,u.eh=l.groupLabelDarkColor,u.nh=l.groupLabelLightColor,u.dh=l.groupLabelColorThreshold,u.ij=l.wireframeDrawMaxDuration,u.jj=l.wireframeLabelDrawing,u.hj=l.wireframeContentDecorationDrawing,u.eg=l.wireframeToFinalFadeDuration,u.kj=l.wireframeToFinalFadeDelay,u.Eg=l.finalCompleteDrawMaxDuration,u.Fg=l.finalIncrementalDrawMaxDuration,u.te=l.finalToWireframeFadeDuration,u.Zc=l.androidStockBrowserWorkaround,u.Le=l.incrementalDraw,u.Uh=l.maxGroups,u.Th=l.maxGroupLevelsAttached,u.Xe=l.maxGroupLevelsDrawn,u.Sh=l.maxGroupLabelLevelsDrawn,u.Qd=l.rolloutStartPoint,u.Pd=l.rolloutMethod,u.Qi=l.rolloutEasing,u.Od=l.rolloutDuration,u.Mf=l.rolloutScalingStrength,u.Of=l.rolloutTranslationXStrength,u.Pf=l.rolloutTranslationYStrength,u.Lf=l.rolloutRotationStrength,u.Nf=l.rolloutTransformationCenter,u.Ui=l.rolloutPolygonDrag,u.Vi=l.rolloutPolygonDuration,u.Ri=l.rolloutLabelDelay,u.Si=l.rolloutLabelDrag,u.Ti=l.rolloutLabelDuration,u.Pi=l.rolloutChildGroupsDrag,u.Oi=l.rolloutChildGroupsDelay,u.ri=l.pullbackStartPoint,u.ki=l.pullbackMethod,u.gi=l.pullbackEasing,u.Bj=l.pullbackType,u.Gd=l.pullbackDuration,u.pi=l.pullbackScalingStrength,u.ti=l.pullbackTranslationXStrength,u.ui=l.pullbackTranslationYStrength,u.oi=l.pullbackRotationStrength,u.si=l.pullbackTransformationCenter,u.li=l.pullbackPolygonDelay,u.mi=l.pullbackPolygonDrag,u.ni=l.pullbackPolygonDuration,u.hi=l.pullbackLabelDelay,u.ii=l.pullbackLabelDrag,u.ji=l.pullbackLabelDuration,u.di=l.pullbackChildGroupsDelay,u.ei=l.pullbackChildGroupsDrag,u.fi=l.pullbackChildGroupsDuration,u.re=l.fadeDuration,u.Dg=l.fadeEasing,u.lj=l.zoomMouseWheelFactor,u.ob=l.zoomMouseWheelDuration,u.Kb=l.zoomMouseWheelEasing,u.Vh=l.maxLabelSizeForTitleBar,u.bj=l.titleBarFontFamily,u.Zf=l.titleBarBackgroundColor,u.$f=l.titleBarTextColor,u.cj=l.titleBarMinFontSize,u.Wd=l.titleBarMaxFontSize,u.dj=l.titleBarTextPaddingLeftRight,u.ej=l.titleBarTextPaddingTopBottom,u.aj=l.titleBarDecorator,u.pj=l.attributionText,u.mj=l.attributionLogo,u.oj=l.attributionLogoScale,u.qj=l.attributionUrl,u.$d=l.attributionPosition,u.sg=l.attributionDistanceFromCenter,u.tg=l.attributionWeight,u.ae=l.attributionTheme,u.Ne=l.interactionHandler,u.Af=t("onModelChanging",u.Af),u.zf=t("onModelChanged",u.zf),u.Bf=t("onRedraw",u.Bf),u.Df=t("onRolloutStart",u.Df),u.Cf=t("onRolloutComplete",u.Cf),u.Ad=t("onRelaxationStep",u.Ad),u.Ef=t("onViewReset",u.Ef),u.sf=t("onGroupOpenOrCloseChanging",u.sf),u.rf=t("onGroupOpenOrCloseChanged",u.rf),u.jf=t("onGroupExposureChanging",u.jf),u.hf=t("onGroupExposureChanged",u.hf),u.uf=t("onGroupSelectionChanging",u.uf),u.tf=t("onGroupSelectionChanged",u.tf),u.lf=t("onGroupHover",u.lf),u.nf=t("onGroupMouseMove",u.nf),u.cf=t("onGroupClick",u.cf),u.df=t("onGroupDoubleClick",u.df),u.kf=t("onGroupHold",u.kf),u.qf=t("onGroupMouseWheel",u.qf),u.pf=t("onGroupMouseUp",u.pf),u.mf=t("onGroupMouseDown",u.mf),u.gf=t("onGroupDragStart",u.gf),u.ef=t("onGroupDrag",u.ef),u.ff=t("onGroupDragEnd",u.ff),u.xf=t("onGroupTransformStart",u.xf),u.vf=t("onGroupTransform",u.vf),u.wf=t("onGroupTransformEnd",u.wf),u.yf=t("onKeyUp",u.yf);})(),u.Ii=v.u(u.Hi),u.Bi=v.u(u.Ai),u.Ee=v.u(u.De),u.nj=null,s&&(s.ig(e),C.has(e,"dataObject")&&s.reload());}function a(e){return function(){return e.apply(this,arguments).Gg(i);};}var i=this,o=window.CarrotSearchFoamTree.asserts,l=C.extend({},window.CarrotSearchFoamTree.defaults),u={};t(e),(e=l.element||document.getElementById(l.id))||A.i("Element to embed FoamTree in not found."),l.element=e;var s=new ea(e,u,l);s.M();var c={get:function(e){return 0==arguments.length?C.extend({},l):function(e,t){if(!o||o.exists(e))switch(e){case"selection":return s.Lb();case"open":return s.pb();case"exposure":return s.Ka();case"state":return s.sa.apply(this,t);case"geometry":return s.Ja.apply(this,t);case"hierarchy":return s.hg.apply(this,t);case"containerCoordinates":return s.wa.apply(this,t);case"imageData":return s.T.apply(this,t);case"viewport":return s.ta();case"times":return s.mg();case"onModelChanged":case"onRedraw":case"onRolloutStart":case"onRolloutComplete":case"onRelaxationStep":case"onGroupHover":case"onGroupOpenOrCloseChanging":case"onGroupExposureChanging":case"onGroupSelectionChanging":case"onGroupSelectionChanged":case"onGroupClick":case"onGroupDoubleClick":case"onGroupHold":return Array.isArray(e=l[e])?e:[e];default:return l[e];}}(arguments[0],Array.prototype.slice.call(arguments,1));},set:t,on:function(e,t){n(e,function(e){return e.push(t),e;});},off:function(e,t){n(e,function(e){return e.filter(function(e){return e!==t;});});},resize:s.ga,redraw:s.jg,update:s.update,attach:s.ua,select:a(s.H),expose:a(s.i),open:a(s.u),reset:a(s.reset),zoom:a(s.ng),trigger:function(e,t){(e=s.Vc(e))&&e(t);},dispose:function(){function e(){throw"FoamTree instance disposed";}s.Za(),C.Aa(c,function(t,n){"dispose"!==n&&(i[n]=e);});}};C.Aa(c,function(e,t){i[t]=e;}),s.reload();},window["CarrotSearchFoamTree.asserts"]&&(window.CarrotSearchFoamTree.asserts=window["CarrotSearchFoamTree.asserts"],delete window["CarrotSearchFoamTree.asserts"]),window.CarrotSearchFoamTree.supported=!0,window.CarrotSearchFoamTree.version=ei,window.CarrotSearchFoamTree.defaults=Object.freeze({id:void 0,element:void 0,logging:!1,dataObject:void 0,pixelRatio:1,wireframePixelRatio:1,layout:"relaxed",layoutByWeightOrder:!0,showZeroWeightGroups:!0,groupMinDiameter:10,rectangleAspectRatioPreference:-1,relaxationInitializer:"fisheye",relaxationMaxDuration:3e3,relaxationVisible:!1,relaxationQualityThreshold:1,stacking:"hierarchical",descriptionGroup:"auto",descriptionGroupType:"stab",descriptionGroupPosition:225,descriptionGroupDistanceFromCenter:1,descriptionGroupSize:.125,descriptionGroupMinHeight:35,descriptionGroupMaxHeight:.5,descriptionGroupPolygonDrawn:!1,maxGroups:5e4,maxGroupLevelsAttached:4,maxGroupLevelsDrawn:4,maxGroupLabelLevelsDrawn:3,groupGrowingDuration:0,groupGrowingEasing:"bounce",groupGrowingDrag:0,groupResizingBudget:2,groupBorderRadius:.15,groupBorderWidth:4,groupBorderWidthScaling:.6,groupInsetWidth:6,groupBorderRadiusCorrection:1,groupSelectionOutlineWidth:5,groupSelectionOutlineColor:"#222",groupSelectionOutlineShadowSize:0,groupSelectionOutlineShadowColor:"#fff",groupSelectionFillHueShift:0,groupSelectionFillSaturationShift:0,groupSelectionFillLightnessShift:0,groupSelectionStrokeHueShift:0,groupSelectionStrokeSaturationShift:0,groupSelectionStrokeLightnessShift:-10,groupFillType:"gradient",groupFillGradientRadius:1,groupFillGradientCenterHueShift:0,groupFillGradientCenterSaturationShift:0,groupFillGradientCenterLightnessShift:20,groupFillGradientRimHueShift:0,groupFillGradientRimSaturationShift:0,groupFillGradientRimLightnessShift:-5,groupStrokeType:"plain",groupStrokeWidth:1.5,groupStrokePlainHueShift:0,groupStrokePlainSaturationShift:0,groupStrokePlainLightnessShift:-10,groupStrokeGradientRadius:1,groupStrokeGradientAngle:45,groupStrokeGradientUpperHueShift:0,groupStrokeGradientUpperSaturationShift:0,groupStrokeGradientUpperLightnessShift:20,groupStrokeGradientLowerHueShift:0,groupStrokeGradientLowerSaturationShift:0,groupStrokeGradientLowerLightnessShift:-20,groupHoverFillHueShift:0,groupHoverFillSaturationShift:0,groupHoverFillLightnessShift:20,groupHoverStrokeHueShift:0,groupHoverStrokeSaturationShift:0,groupHoverStrokeLightnessShift:-10,groupExposureScale:1.15,groupExposureShadowColor:"rgba(0, 0, 0, 0.5)",groupExposureShadowSize:50,groupExposureZoomMargin:.1,groupUnexposureLightnessShift:65,groupUnexposureSaturationShift:-65,groupUnexposureLabelColorThreshold:.35,exposeDuration:700,exposeEasing:"squareInOut",groupColorDecorator:C.qa,groupLabelDecorator:C.qa,groupLabelLayoutDecorator:C.qa,groupContentDecorator:C.qa,groupContentDecoratorTriggering:"onLayoutDirty",openCloseDuration:500,rainbowColorDistribution:"radial",rainbowColorDistributionAngle:-45,rainbowLightnessDistributionAngle:45,rainbowSaturationCorrection:.1,rainbowLightnessCorrection:.4,rainbowStartColor:"hsla(0, 100%, 55%, 1)",rainbowEndColor:"hsla(359, 100%, 55%, 1)",rainbowLightnessShift:30,rainbowLightnessShiftCenter:.4,parentFillOpacity:.7,parentStrokeOpacity:1,parentLabelOpacity:1,parentOpacityBalancing:!0,wireframeDrawMaxDuration:15,wireframeLabelDrawing:"auto",wireframeContentDecorationDrawing:"auto",wireframeToFinalFadeDuration:500,wireframeToFinalFadeDelay:300,finalCompleteDrawMaxDuration:80,finalIncrementalDrawMaxDuration:100,finalToWireframeFadeDuration:200,androidStockBrowserWorkaround:!1,incrementalDraw:"fast",groupLabelFontFamily:"sans-serif",groupLabelFontStyle:"normal",groupLabelFontWeight:"normal",groupLabelFontVariant:"normal",groupLabelLineHeight:1.05,groupLabelHorizontalPadding:1,groupLabelVerticalPadding:1,groupLabelMinFontSize:6,groupLabelMaxFontSize:160,groupLabelMaxTotalHeight:.9,groupLabelUpdateThreshold:.05,groupLabelDarkColor:"#000",groupLabelLightColor:"#fff",groupLabelColorThreshold:.35,rolloutStartPoint:"center",rolloutEasing:"squareOut",rolloutMethod:"groups",rolloutDuration:2e3,rolloutScalingStrength:-.7,rolloutTranslationXStrength:0,rolloutTranslationYStrength:0,rolloutRotationStrength:-.7,rolloutTransformationCenter:.7,rolloutPolygonDrag:.1,rolloutPolygonDuration:.5,rolloutLabelDelay:.8,rolloutLabelDrag:.1,rolloutLabelDuration:.5,rolloutChildGroupsDrag:.1,rolloutChildGroupsDelay:.2,pullbackStartPoint:"center",pullbackEasing:"squareIn",pullbackMethod:"groups",pullbackDuration:1500,pullbackScalingStrength:-.7,pullbackTranslationXStrength:0,pullbackTranslationYStrength:0,pullbackRotationStrength:-.7,pullbackTransformationCenter:.7,pullbackPolygonDelay:.3,pullbackPolygonDrag:.1,pullbackPolygonDuration:.8,pullbackLabelDelay:0,pullbackLabelDrag:.1,pullbackLabelDuration:.3,pullbackChildGroupsDelay:.1,pullbackChildGroupsDrag:.1,pullbackChildGroupsDuration:.3,fadeDuration:700,fadeEasing:"cubicInOut",zoomMouseWheelFactor:1.5,zoomMouseWheelDuration:500,zoomMouseWheelEasing:"squareOut",maxLabelSizeForTitleBar:8,titleBarFontFamily:null,titleBarFontStyle:"normal",titleBarFontWeight:"normal",titleBarFontVariant:"normal",titleBarBackgroundColor:"rgba(0, 0, 0, 0.5)",titleBarTextColor:"rgba(255, 255, 255, 1)",titleBarMinFontSize:10,titleBarMaxFontSize:40,titleBarTextPaddingLeftRight:20,titleBarTextPaddingTopBottom:15,titleBarDecorator:C.qa,attributionText:null,attributionLogo:null,attributionLogoScale:.5,attributionUrl:"http://carrotsearch.com/foamtree",attributionPosition:"bottomright",attributionDistanceFromCenter:1,attributionWeight:.025,attributionTheme:"light",interactionHandler:l.Ih()?"hammerjs":"builtin",onModelChanging:[],onModelChanged:[],onRedraw:[],onRolloutStart:[],onRolloutComplete:[],onRelaxationStep:[],onViewReset:[],onGroupOpenOrCloseChanging:[],onGroupOpenOrCloseChanged:[],onGroupExposureChanging:[],onGroupExposureChanged:[],onGroupSelectionChanging:[],onGroupSelectionChanged:[],onGroupHover:[],onGroupMouseMove:[],onGroupClick:[],onGroupDoubleClick:[],onGroupHold:[],onGroupMouseWheel:[],onGroupMouseUp:[],onGroupMouseDown:[],onGroupDragStart:[],onGroupDrag:[],onGroupDragEnd:[],onGroupTransformStart:[],onGroupTransform:[],onGroupTransformEnd:[],onKeyUp:[],selection:null,open:null,exposure:null,imageData:null,hierarchy:null,geometry:null,containerCoordinates:null,state:null,viewport:null,times:null}),window.CarrotSearchFoamTree.geometry=Object.freeze({rectangleInPolygon:function(e,t,n,r,a,i,o){return a=C.I(a,1),i=C.I(i,.5),o=C.I(o,.5),e=L.Ka(e,{x:t,y:n},r,i,o)*a,{x:t-e*r*i,y:n-e*o,w:e*r,h:e};},circleInPolygon:function(e,t,n){return L.pb(e,{x:t,y:n});},stabPolygon:function(e,t,n,r){return L.ua(e,{x:t,y:n},r);},polygonCentroid:function(e){return{x:(e=L.u(e,{})).x,y:e.y,area:e.ha};},boundingBox:function(e){for(var t=e[0].x,n=e[0].y,r=e[0].x,a=e[0].y,i=1;i<e.length;i++){var o=e[i];o.x<t&&(t=o.x),o.y<n&&(n=o.y),o.x>r&&(r=o.x),o.y>a&&(a=o.y);}return{x:t,y:n,w:r-t,h:a-n};}});},function(){window.CarrotSearchFoamTree=function(){window.console.error("FoamTree is not supported on this browser.");},window.CarrotSearchFoamTree.supported=!1;});}();var c=window.CarrotSearchFoamTree,f=n("node_modules/classnames/index.js"),d=r._(f);let h="bits",p="bytes",g="jedec",v={symbol:{iec:{bits:["bit","Kibit","Mibit","Gibit","Tibit","Pibit","Eibit","Zibit","Yibit"],bytes:["B","KiB","MiB","GiB","TiB","PiB","EiB","ZiB","YiB"]},jedec:{bits:["bit","Kbit","Mbit","Gbit","Tbit","Pbit","Ebit","Zbit","Ybit"],bytes:["B","KB","MB","GB","TB","PB","EB","ZB","YB"]}},fullform:{iec:["","kibi","mebi","gibi","tebi","pebi","exbi","zebi","yobi"],jedec:["","kilo","mega","giga","tera","peta","exa","zetta","yotta"]}};var b={grew:"grew-SpxLa7qE",muted:"muted-6YgHnQJY",shrank:"shrank-YsRza1Id",sidebar:"sidebar-qEqdt0Td",size:"size-a47XImHe"};let m=["stat","parsed","minified","gzip","brotli"],y=e=>(function(e,{bits:t=!1,pad:n=!1,base:r=-1,round:a=2,locale:i="",localeOptions:o={},separator:l="",spacer:u=" ",symbols:s={},standard:c="",output:f="string",fullform:d=!1,fullforms:b=[],exponent:m=-1,roundingMethod:y="round",precision:w=0}={}){let x=m,S=Number(e),k=[],C=0,T="";"si"===c?(r=10,c=g):"iec"===c||c===g?r=2:2===r?c="iec":(r=10,c=g);let z=10===r?1e3:1024,E=S<0,M=Math[y];if("bigint"!=typeof e&&isNaN(e))throw TypeError("Invalid number");if("function"!=typeof M)throw TypeError("Invalid rounding method");if(E&&(S=-S),(-1===x||isNaN(x))&&(x=Math.floor(Math.log(S)/Math.log(z)))<0&&(x=0),x>8&&(w>0&&(w+=8-x),x=8),"exponent"===f)return x;if(0===S)k[0]=0,T=k[1]=v.symbol[c][t?h:p][x];else{C=S/(2===r?Math.pow(2,10*x):Math.pow(1e3,x)),t&&(C*=8)>=z&&x<8&&(C/=z,x++);let e=Math.pow(10,x>0?a:0);k[0]=M(C*e)/e,k[0]===z&&x<8&&-1===m&&(k[0]=1,x++),T=k[1]=10===r&&1===x?t?"kbit":"kB":v.symbol[c][t?h:p][x];}if(E&&(k[0]=-k[0]),w>0&&(k[0]=k[0].toPrecision(w)),k[1]=s[k[1]]||k[1],!0===i?k[0]=k[0].toLocaleString():i.length>0?k[0]=k[0].toLocaleString(i,o):l.length>0&&(k[0]=k[0].toString().replace(".",l)),n&&!1===Number.isInteger(k[0])&&a>0){let e=l||".",t=k[0].toString().split(e),n=t[1]||"",r=n.length;k[0]=`${t[0]}${e}${n.padEnd(r+(a-r),"0")}`;}return!0===d&&(k[1]=b[x]?b[x]:v.fullform[c][x]+(t?"bit":"byte")+(1===k[0]?"":"s")),"array"===f?k:"object"===f?{value:k[0],symbol:k[1],exponent:x,unit:T}:k.join(u);})(e),w=({module:e,duplicate:t})=>(0,i.jsxs)(i.Fragment,{children:[(0,i.jsx)("h2",{children:e.id}),(0,i.jsx)("table",{children:(0,i.jsx)("tbody",{children:m.map(t=>(0,i.jsxs)("tr",{children:[(0,i.jsx)("td",{children:t}),(0,i.jsx)("td",{className:b.size,children:y(e[`${t}Size`])})]},t))})}),e.package&&(0,i.jsxs)("div",{children:["Package:"," ",(0,i.jsxs)("strong",{children:[e.package.name,"@",e.package.version]}),t&&(0,i.jsx)("span",{className:b.grew,children:" (duplicate)"})]}),(0,i.jsx)("h2",{children:"Why is this module included"}),(0,i.jsx)("ol",{children:e.reasons.map(e=>(0,i.jsx)("li",{children:e},e))}),e.issuers.length>0&&(0,i.jsxs)(i.Fragment,{children:[(0,i.jsx)("h2",{children:"Imported by"}),(0,i.jsx)("ul",{children:e.issuers.map(e=>(0,i.jsx)("li",{children:e},e))})]}),e.concatenated&&e.concatenated.length>0&&(0,i.jsxs)(i.Fragment,{children:[(0,i.jsx)("h2",{children:"Concatenated modules"}),(0,i.jsx)("ul",{children:e.concatenated.map(e=>(0,i.jsx)("li",{children:e},e))})]})]}),x=({report:e,sizeType:t,onSizeTypeChange:n,chunkId:r,onChunkIdChange:a,selected:o})=>{let l=new Set(e.duplicates.map(e=>e.name)),u=e.chunks.filter(e=>!r||e.id===r);return(0,i.jsxs)("div",{className:b.sidebar,children:[(0,i.jsxs)("label",{children:["Size",(0,i.jsx)("select",{value:t,onChange:e=>n(e.target.value),children:m.map(e=>(0,i.jsx)("option",{value:e,children:e},e))})]}),(0,i.jsxs)("label",{children:["Chunk",(0,i.jsxs)("select",{value:r,onChange:e=>a(e.target.value),children:[(0,i.jsx)("option",{value:"",children:"All"}),e.chunks.map(e=>(0,i.jsx)("option",{value:e.id,children:e.id},e.id))]})]}),(0,i.jsx)("h2",{children:"Files"}),(0,i.jsx)("table",{children:(0,i.jsx)("tbody",{children:u.flatMap(e=>e.files.map(e=>(0,i.jsxs)("tr",{children:[(0,i.jsx)("td",{children:e.name}),(0,i.jsx)("td",{className:b.size,children:y(e.size)}),(0,i.jsxs)("td",{className:b.size,children:["gzip ",y(e.gzipSize)]}),(0,i.jsxs)("td",{className:b.size,children:["br ",y(e.brotliSize)]})]},e.name)))})}),o&&(0,i.jsx)(w,{module:o,duplicate:!!o.package&&l.has(o.package.name)}),e.duplicates.length>0&&(0,i.jsxs)(i.Fragment,{children:[(0,i.jsx)("h2",{className:b.grew,children:"Duplicate packages"}),(0,i.jsx)("ul",{children:e.duplicates.map(e=>(0,i.jsxs)("li",{children:[e.name,(0,i.jsx)("ul",{children:e.versions.map(e=>(0,i.jsxs)("li",{children:[e.version," ",(0,i.jsx)("span",{className:b.muted,children:e.path})]},e.path))})]},e.name))})]}),e.diff&&e.diff.length>0&&(0,i.jsxs)(i.Fragment,{children:[(0,i.jsx)("h2",{children:"Changed since baseline (minified)"}),(0,i.jsx)("table",{children:(0,i.jsx)("tbody",{children:e.diff.map(e=>{let t=e.size-e.baselineSize;return(0,i.jsxs)("tr",{children:[(0,i.jsx)("td",{children:e.id}),(0,i.jsxs)("td",{className:(0,d.default)(b.size,t>0?b.grew:b.shrank),children:[t>0?"+":"-",y(Math.abs(t))]})]},e.id);})})})]})]});},S=({visible:e,content:t})=>{let[n,r]=(0,l.useState)({left:0,top:0});console.log("visible==",e);let a=(0,l.useRef)({x:0,y:0}),o=(0,l.useRef)(null),u=t=>{a.current={x:t.pageX,y:t.pageY},e&&s();};(0,l.useEffect)(()=>(document.addEventListener("mousemove",u,!0),()=>{document.removeEventListener("mousemove",u,!0);}),[e]);let s=()=>{if(!e)return;let t={left:a.current.x+10,top:a.current.y+30},n=o.current.getBoundingClientRect();t.left+n.width>window.innerWidth&&(t.left=window.innerWidth-n.width),t.top+n.height>window.innerHeight&&(t.top=a.current.y-30-n.height),r(t);},c=(0,d.default)({"container-XGCy6rRS":!0,"hidden-1r61NpYt":!e});return(0,i.jsx)("div",{ref:o,className:c,style:{left:n.left,top:n.top},children:t});};class k{constructor(e,t){this.name=e,this.parent=t;}get path(){let e=[],t=this;for(;t;)e.push(t.name),t=t.parent;return e.reverse().join("/");}get isRoot(){return!this.parent;}}class C extends k{constructor(e,t){super(e,t),this.children=Object.create(null),this._sizes=Object.create(null);}getSize(e){return e in this._sizes||(this._sizes[e]=this.walk((t,n)=>n+t.getSize(e),0,!1)),this._sizes[e];}getChild(e){return this.children[e];}addChildModule(e){let{name:t}=e,n=this.children[t];n&&n instanceof C||(n||(e.parent=this,this.children[t]=e),this._sizes=Object.create(null));}addChildFolder(e){return e.parent=this,this.children[e.name]=e,this._sizes=Object.create(null),e;}walk(e,t={},n=!0){let r=!1;return Object.values(this.children).forEach(i=>{if(t=n&&i.walk?i.walk(e,t,a):e(i,t,a),r)return!1;}),t;function a(e){return r=!0,e;}}mergeNestedFolders(){if(!this.isRoot){let e;for(;1===(e=Object.keys(this.children)).length;){let t=e[0],n=this.children[t];if(n instanceof this.constructor)this.name+=`/${n.name}`,this.children=n.children;else break;}}this.walk(e=>{e.parent=this,e.mergeNestedFolders&&e.mergeNestedFolders();},null,!1);}get duplicate(){return this.walk((e,t)=>t||e.duplicate,!1);}toChartData(){let e={label:this.name,path:this.path,groups:Object.values(this.children).map(e=>e.toChartData())};return m.forEach(t=>{e[`${t}Size`]=this.getSize(t);}),e;}}class T extends k{constructor(e,t,n,r){super(e,n),this.data=t,this.duplicate=r;}getSize(e){return this.data[`${e}Size`]||0;}toChartData(){let e={id:this.data.id,label:this.name,path:this.path,module:this.data,duplicate:this.duplicate};return m.forEach(t=>{e[`${t}Size`]=this.getSize(t);}),e;}}class z extends C{addModule(e,t){let n=e.id.split("/").filter(e=>"."!==e);if(!n.length)return;let[r,a]=[n.slice(0,-1),n[n.length-1]],i=this;r.forEach(e=>{let t=i.getChild(e);t&&t instanceof z||(t=i.addChildFolder(new z(e))),i=t;});let o=new T(a,e,this,t);i.addChildModule(o);}}s.default.createRoot(document.getElementById("root")).render((0,i.jsx)(function(){let e=(0,l.useRef)(null),[t,n]=(0,l.useState)(!1),r=(0,l.useRef)(null),[a,o]=(0,l.useState)(null),[u,s]=(0,l.useState)("minified"),[f,d]=(0,l.useState)(""),[h,p]=(0,l.useState)(null),[g,v]=(0,l.useState)(""),b=(e,t)=>{let n=new Set(a.duplicates.map(e=>e.name)),r=new z(e);return t.forEach(e=>r.addModule(e,!!e.package&&n.has(e.package.name))),r.mergeNestedFolders(),r;},w=e=>e.chunks.filter(e=>!f||e.id===f).map(e=>({...b(e.id,e.modules).toChartData(),isAsset:!0,files:e.files})),k=(e,t)=>e.reduce((e,n)=>(n[t]&&(n.groups&&(n={...n,groups:k(n.groups,t)}),n.weight=n[t],e.push(n)),e),[]),C=(e,t)=>{let n=e[`${t}Size`];return"number"==typeof n?(0,i.jsxs)("div",{children:[t," size: ",(0,i.jsx)("strong",{children:y(n)})]},t):null;},T=e=>e?(0,i.jsxs)("div",{children:[(0,i.jsx)("div",{children:(0,i.jsx)("strong",{children:e.label})}),(0,i.jsx)("br",{}),m.map(t=>C(e,t)),e.path&&(0,i.jsxs)("div",{children:["Path: ",(0,i.jsx)("strong",{children:e.path})]}),e.duplicate&&(0,i.jsx)("div",{children:(0,i.jsx)("strong",{children:"Contains a duplicate package"})}),e.isAsset&&e.files.map(e=>(0,i.jsxs)("div",{children:["File: ",(0,i.jsx)("strong",{children:e.name})," ",y(e.size)]},e.name)),e.module&&(0,i.jsxs)("div",{children:[(0,i.jsx)("br",{}),(0,i.jsx)("strong",{children:(0,i.jsx)("em",{children:"Click to view why this module is included"})})]})]}):null,E=()=>new c({element:e.current,layout:"squarified",stacking:"flattened",pixelRatio:window.devicePixelRatio||1,maxGroups:1/0,maxGroupLevelsDrawn:1/0,maxGroupLabelLevelsDrawn:1/0,maxGroupLevelsAttached:1/0,wireframeLabelDrawing:"always",groupMinDiameter:0,groupLabelVerticalPadding:.2,rolloutDuration:0,pullbackDuration:0,fadeDuration:0,groupExposureZoomMargin:.2,zoomMouseWheelDuration:300,openCloseDuration:200,titleBarDecorator(e,t,n){n.titleBarShown=!1;},groupColorDecorator(e,t,n){t.group.duplicate&&(n.groupColor="#e53935");},onMouseLeave(){n(!1);},onGroupClick(e){let{group:t}=e;(null==t?void 0:t.module)&&p(t.module);},onGroupHover(e){let{group:t}=e;if(null==t?void 0:t.attribution){n(!1);return;}t?(n(!0),v(T(t))):n(!1);}}),M=()=>{r.current&&r.current.resize();};return(0,l.useEffect)(()=>{var e;return o(null===(e=window)||void 0===e?void 0:e.chartData),window.addEventListener("resize",M),()=>{window.removeEventListener("resize",M);};},[]),(0,l.useEffect)(()=>{if(!a){console.warn("\u6570\u636E\u672A\u521D\u59CB\u5316!!");return;}r.current||(r.current=E());let e=k(w(a),`${u}Size`);r.current.set({dataObject:{groups:e}});},[a,u,f]),(0,i.jsxs)("div",{style:{position:"fixed",inset:0,display:"flex"},children:[a&&(0,i.jsx)(x,{report:a,sizeType:u,onSizeTypeChange:s,chunkId:f,onChunkIdChange:d,selected:h}),(0,i.jsx)("div",{style:{flex:1},ref:e}),(0,i.jsx)(S,{visible:t,content:g})]});},{}));},"node_modules/classnames/index.js":function(e,t,n){!function(){"use strict";var t={}.hasOwnProperty;function n(){for(var e=[],r=0;r<arguments.length;r++){var a=arguments[r];if(a){var i=typeof a;if("string"===i||"number"===i)e.push(a);else if(Array.isArray(a)){if(a.length){var o=n.apply(null,a);o&&e.push(o);}}else if("object"===i){if(a.toString!==Object.prototype.toString&&!a.toString.toString().includes("[native code]")){e.push(a.toString());continue;}for(var l in a)t.call(a,l)&&a[l]&&e.push(l);}}}return e.join(" ");}void 0!==e&&e.exports?(n.default=n,e.exports=n):window.classNames=n;}();},"node_modules/react-dom/cjs/react-dom.production.min.js":function(e,t,n){!function(e,t,r){"use strict";var a,i,o,l,u,s,c,f,d,h,p,g,v,b,m,y,w,x=n("node_modules/react/index.js"),S=n("node_modules/scheduler/index.js");function k(e){for(var t="https://reactjs.org/docs/error-decoder.html?invariant="+e,n=1;n<arguments.length;n++)t+="&args[]="+encodeURIComponent(arguments[n]);return"Minified React error #"+e+"; visit "+t+" for the full message or use the non-minified dev environment for full errors and additional helpful warnings.";}var C=new Set,T={};function z(e,t){E(e,t),E(e+"Capture",t);}function E(e,t){for(T[e]=t,e=0;e<t.length;e++)C.add(t[e]);}var M=!("undefined"==typeof window||void 0===window.document||void 0===window.document.createElement),P=Object.prototype.hasOwnProperty,L=/^[:A-Z_a-z\u00C0-\u00D6\u00D8-\u00F6\u00F8-\u02FF\u0370-\u037D\u037F-\u1FFF\u200C-\u200D\u2070-\u218F\u2C00-\u2FEF\u3001-\uD7FF\uF900-\uFDCF\uFDF0-\uFFFD][:A-Z_a-z\u00C0-\u00D6\u00D8-\u00F6\u00F8-\u02FF\u0370-\u037D\u037F-\u1FFF\u200C-\u200D\u2070-\u218F\u2C00-\u2FEF\u3001-\uD7FF\uF900-\uFDCF\uFDF0-\uFFFD\-.0-9\u00B7\u0300-\u036F\u203F-\u2040]*$/,_={},D={};function j(e,t,n,r,a,i,o){this.acceptsBooleans=2===t||3===t||4===t,this.attributeName=r,this.attributeNamespace=a,this.mustUseProperty=n,this.propertyName=e,this.type=t,this.sanitizeURL=i,this.removeEmptyString=o;}var F={};"children dangerouslySetInnerHTML defaultValue defaultChecked innerHTML suppressContentEditableWarning suppressHydrationWarning style".split(" ").forEach(function(e){F[e]=new j(e,0,!1,e,null,!1,!1);}),[["acceptCharset","accept-charset"],["className","class"],["htmlFor","for"],["httpEquiv","http-equiv"]].forEach(function(e){var t=e[0];F[t]=new j(t,1,!1,e[1],null,!1,!1);}),["contentEditable","draggable","spellCheck","value"].forEach(function(e){F[e]=new j(e,2,!1,e.toLowerCase(),null,!1,!1);}),["autoReverse","externalResourcesRequired","focusable","preserveAlpha"].forEach(function(e){F[e]=new j(e,2,!1,e,null,!1,!1);}),"allowFullScreen async autoFocus autoPlay controls default defer disabled disablePictureInPicture disableRemotePlayback formNoValidate hidden loop noModule noValidate open playsInline readOnly required reversed scoped seamless itemScope".split(" ").forEach(function(e){F[e]=new j(e,3,!1,e.toLowerCase(),null,!1,!1);}),["checked","multiple","muted","selected"].forEach(function(e){F[e]=new j(e,3,!0,e,null,!1,!1);}),["capture","download"].forEach(function(e){F[e]=new j(e,4,!1,e,null,!1,!1);}),["cols","rows","size","span"].forEach(function(e){F[e]=new j(e,6,!1,e,null,!1,!1);}),["rowSpan","start"].forEach(function(e){F[e]=new j(e,5,!1,e.toLowerCase(),null,!1,!1);});var N=/[\-:]([a-z])/g;function O(e){return e[1].toUpperCase();}function R(e,t,n,r){var a,i=F.hasOwnProperty(t)?F[t]:null;(null!==i?0!==i.type:r||!(2<t.length)||"o"!==t[0]&&"O"!==t[0]||"n"!==t[1]&&"N"!==t[1])&&(function(e,t,n,r){if(null==t||function(e,t,n,r){if(null!==n&&0===n.type)return!1;switch(typeof t){case"function":case"symbol":return!0;case"boolean":if(r)return!1;if(null!==n)return!n.acceptsBooleans;return"data-"!==(e=e.toLowerCase().slice(0,5))&&"aria-"!==e;default:return!1;}}(e,t,n,r))return!0;if(r)return!1;if(null!==n)switch(n.type){case 3:return!t;case 4:return!1===t;case 5:return isNaN(t);case 6:return isNaN(t)||1>t;}return!1;}(t,n,i,r)&&(n=null),r||null===i?(a=t,(!!P.call(D,a)||!P.call(_,a)&&(L.test(a)?D[a]=!0:(_[a]=!0,!1)))&&(null===n?e.removeAttribute(t):e.setAttribute(t,""+n))):i.mustUseProperty?e[i.propertyName]=null===n?3!==i.type&&"":n:(t=i.attributeName,r=i.attributeNamespace,null===n?e.removeAttribute(t):(n=3===(i=i.type)||4===i&&!0===n?"":""+n,r?e.setAttributeNS(r,t,n):e.setAttribute(t,n))));}"accent-height alignment-baseline arabic-form baseline-shift cap-height clip-path clip-rule color-interpolation color-interpolation-filters color-profile color-rendering dominant-baseline enable-background fill-opacity fill-rule flood-color flood-opacity font-family font-size font-size-adjust font-stretch font-style font-variant font-weight glyph-name glyph-orientation-horizontal glyph-orientation-vertical horiz-adv-x horiz-origin-x image-rendering letter-spacing lighting-color marker-end marker-mid marker-start overline-position overline-thickness paint-order panose-1 pointer-events rendering-intent shape-rendering stop-color stop-opacity strikethrough-position strikethrough-thickness stroke-dasharray stroke-dashoffset stroke-linecap stroke-linejoin stroke-miterlimit stroke-opacity stroke-width text-anchor text-decoration text-rendering underline-position underline-thickness unicode-bidi unicode-range units-per-em v-alphabetic v-hanging v-ideographic v-mathematical vector-effect vert-adv-y vert-origin-x vert-origin-y word-spacing writing-mode xmlns:xlink x-height".split(" ").forEach(function(e){var t=e.replace(N,O);F[t]=new j(t,1,!1,e,null,!1,!1);}),"xlink:actuate xlink:arcrole xlink:role xlink:show xlink:title xlink:type".split(" ").forEach(function(e){var t=e.replace(N,O);F[t]=new j(t,1,!1,e,"http://www.w3.org/1999/xlink",!1,!1);}),["xml:base","xml:lang","xml:space"].forEach(function(e){var t=e.replace(N,O);F[t]=new j(t,1,!1,e,"http://www.w3.org/XML/1998/namespace",!1,!1);}),["tabIndex","crossOrigin"].forEach(function(e){F[e]=new j(e,1,!1,e.toLowerCase(),null,!1,!1);}),F.xlinkHref=new j("xlinkHref",1,!1,"xlink:href","http://www.w3.org/1999/xlink",!0,!1),["src","href","action","formAction"].forEach(function(e){F[e]=new j(e,1,!1,e.toLowerCase(),null,!0,!0);});var A=x.__SECRET_INTERNALS_DO_NOT_USE_OR_YOU_WILL_BE_FIRED,I=Symbol.for("react.element"),B=Symbol.for("react.portal"),G=Symbol.for("react.fragment"),H=Symbol.for("react.strict_mode"),q=Symbol.for("react.profiler"),U=Symbol.for("react.provider"),W=Symbol.for("react.context"),V=Symbol.for("react.forward_ref"),$=Symbol.for("react.suspense"),K=Symbol.for("react.suspense_list"),Q=Symbol.for("react.memo"),Y=Symbol.for("react.lazy");Symbol.for("react.scope"),Symbol.for("react.debug_trace_mode");var X=Symbol.for("react.offscreen");Symbol.for("react.legacy_hidden"),Symbol.for("react.cache"),Symbol.for("react.tracing_marker");var J=Symbol.iterator;function Z(e){return null===e||"object"!=typeof e?null:"function"==typeof(e=J&&e[J]||e["@@iterator"])?e:null;}var ee=Object.assign;function et(e){if(void 0===i)try{throw Error();}catch(e){var t=e.stack.trim().match(/\n( *(at )?)/);i=t&&t[1]||"";}return"\n"+i+e;}var en=!1;function er(e,t){if(!e||en)return"";en=!0;var n=Error.prepareStackTrace;Error.prepareStackTrace=void 0;try{if(t){if(t=function(){throw Error();},Object.defineProperty(t.prototype,"props",{set:function(){throw Error();}}),"object"==typeof Reflect&&Reflect.construct){try{Reflect.construct(t,[]);}catch(e){var r=e;}Reflect.construct(e,[],t);}else{try{t.call();}catch(e){r=e;}e.call(t.prototype);}}else{try{throw Error();}catch(e){r=e;}e();}}catch(t){if(t&&r&&"string"==typeof t.stack){for(var a=t.stack.split("\n"),i=r.stack.split("\n"),o=a.length-1,l=i.length-1;1<=o&&0<=l&&a[o]!==i[l];)l--;for(;1<=o&&0<=l;o--,l--)if(a[o]!==i[l]){if(1!==o||1!==l)do if(o--,0>--l||a[o]!==i[l]){var u="\n"+a[o].replace(" at new "," at ");return e.displayName&&u.includes("<anonymous>")&&(u=u.replace("<anonymous>",e.displayName)),u;}while(1<=o&&0<=l)break;}}}finally{en=!1,Error.prepareStackTrace=n;}return(e=e?e.displayName||e.name:"")?et(e):"";}function ea(e){switch(typeof e){case"boolean":case"number":case"string":case"undefined":case"object":return e;default:return"";}}function ei(e){var t=e.type;return(e=e.nodeName)&&"input"===e.toLowerCase()&&("checkbox"===t||"radio"===t);}function eo(e){e._valueTracker||(e._valueTracker=function(e){var t=ei(e)?"checked":"value",n=Object.getOwnPropertyDescriptor(e.constructor.prototype,t),r=""+e[t];if(!e.hasOwnProperty(t)&&void 0!==n&&"function"==typeof n.get&&"function"==typeof n.set){var a=n.get,i=n.set;return Object.defineProperty(e,t,{configurable:!0,get:function(){return a.call(this);},set:function(e){r=""+e,i.call(this,e);}}),Object.defineProperty(e,t,{enumerable:n.enumerable}),{getValue:function(){return r;},setValue:function(e){r=""+e;},stopTracking:function(){e._valueTracker=null,delete e[t];}};}}(e));}function el(e){if(!e)return!1;var t=e._valueTracker;if(!t)return!0;var n=t.getValue(),r="";return e&&(r=ei(e)?e.checked?"true":"false":e.value),(e=r)!==n&&(t.setValue(e),!0);}function eu(e){if(void 0===(e=e||("undefined"!=typeof document?document:void 0)))return null;try{return e.activeElement||e.body;}catch(t){return e.body;}}function es(e,t){var n=t.checked;return ee({},t,{defaultChecked:void 0,defaultValue:void 0,value:void 0,checked:null!=n?n:e._wrapperState.initialChecked});}function ec(e,t){var n=null==t.defaultValue?"":t.defaultValue,r=null!=t.checked?t.checked:t.defaultChecked;n=ea(null!=t.value?t.value:n),e._wrapperState={initialChecked:r,initialValue:n,controlled:"checkbox"===t.type||"radio"===t.type?null!=t.checked:null!=t.value};}function ef(e,t){null!=(t=t.checked)&&R(e,"checked",t,!1);}function ed(e,t){ef(e,t);var n=ea(t.value),r=t.type;if(null!=n)"number"===r?(0===n&&""===e.value||e.value!=n)&&(e.value=""+n):e.value!==""+n&&(e.value=""+n);else if("submit"===r||"reset"===r){e.removeAttribute("value");return;}t.hasOwnProperty("value")?ep(e,t.type,n):t.hasOwnProperty("defaultValue")&&ep(e,t.type,ea(t.defaultValue)),null==t.checked&&null!=t.defaultChecked&&(e.defaultChecked=!!t.defaultChecked);}function eh(e,t,n){if(t.hasOwnProperty("value")||t.hasOwnProperty("defaultValue")){var r=t.type;if(!("submit"!==r&&"reset"!==r||void 0!==t.value&&null!==t.value))return;t=""+e._wrapperState.initialValue,n||t===e.value||(e.value=t),e.defaultValue=t;}""!==(n=e.name)&&(e.name=""),e.defaultChecked=!!e._wrapperState.initialChecked,""!==n&&(e.name=n);}function ep(e,t,n){("number"!==t||eu(e.ownerDocument)!==e)&&(null==n?e.defaultValue=""+e._wrapperState.initialValue:e.defaultValue!==""+n&&(e.defaultValue=""+n));}var eg=Array.isArray;function ev(e,t,n,r){if(e=e.options,t){t={};for(var a=0;a<n.length;a++)t["$"+n[a]]=!0;for(n=0;n<e.length;n++)a=t.hasOwnProperty("$"+e[n].value),e[n].selected!==a&&(e[n].selected=a),a&&r&&(e[n].defaultSelected=!0);}else{for(a=0,n=""+ea(n),t=null;a<e.length;a++){if(e[a].value===n){e[a].selected=!0,r&&(e[a].defaultSelected=!0);return;}null!==t||e[a].disabled||(t=e[a]);}null!==t&&(t.selected=!0);}}function eb(e,t){if(null!=t.dangerouslySetInnerHTML)throw Error(k(91));return ee({},t,{value:void 0,defaultValue:void 0,children:""+e._wrapperState.initialValue});}function em(e,t){var n=t.value;if(null==n){if(n=t.children,t=t.defaultValue,null!=n){if(null!=t)throw Error(k(92));if(eg(n)){if(1<n.length)throw Error(k(93));n=n[0];}t=n;}null==t&&(t=""),n=t;}e._wrapperState={initialValue:ea(n)};}function ey(e,t){var n=ea(t.value),r=ea(t.defaultValue);null!=n&&((n=""+n)!==e.value&&(e.value=n),null==t.defaultValue&&e.defaultValue!==n&&(e.defaultValue=n)),null!=r&&(e.defaultValue=""+r);}function ew(e){var t=e.textContent;t===e._wrapperState.initialValue&&""!==t&&null!==t&&(e.value=t);}function ex(e){switch(e){case"svg":return"http://www.w3.org/2000/svg";case"math":return"http://www.w3.org/1998/Math/MathML";default:return"http://www.w3.org/1999/xhtml";}}function eS(e,t){return null==e||"http://www.w3.org/1999/xhtml"===e?ex(t):"http://www.w3.org/2000/svg"===e&&"foreignObject"===t?"http://www.w3.org/1999/xhtml":e;}var ek=(a=function(e,t){if("http://www.w3.org/2000/svg"!==e.namespaceURI||"innerHTML"in e)e.innerHTML=t;else{for((o=o||document.createElement("div")).innerHTML="<svg>"+t.valueOf().toString()+"</svg>",t=o.firstChild;e.firstChild;)e.removeChild(e.firstChild);for(;t.firstChild;)e.appendChild(t.firstChild);}},"undefined"!=typeof MSApp&&MSApp.execUnsafeLocalFunction?function(e,t,n,r){MSApp.execUnsafeLocalFunction(function(){return a(e,t,n,r);});}:a);function eC(e,t){if(t){var n=e.firstChild;if(n&&n===e.lastChild&&3===n.nodeType){n.nodeValue=t;return;}}e.textContent=t;}var eT={animationIterationCount:!0,aspectRatio:!0,borderImageOutset:!0,borderImageSlice:!0,borderImageWidth:!0,boxFlex:!0,boxFlexGroup:!0,boxOrdinalGroup:!0,columnCount:!0,columns:!0,flex:!0,flexGrow:!0,flexPositive:!0,flexShrink:!0,flexNegative:!0,flexOrder:!0,gridArea:!0,gridRow:!0,gridRowEnd:!0,gridRowSpan:!0,gridRowStart:!0,gridColumn:!0,gridColumnEnd:!0,gridColumnSpan:!0,gridColumnStart:!0,fontWeight:!0,lineClamp:!0,lineHeight:!0,opacity:!0,order:!0,orphans:!0,tabSize:!0,widows:!0,zIndex:!0,zoom:!0,fillOpacity:!0,floodOpacity:!0,stopOpacity:!0,strokeDasharray:!0,strokeDashoffset:!0,strokeMiterlimit:!0,strokeOpacity:!0,strokeWidth:!0},ez=["Webkit","ms","Moz","O"];function eE(e,t,n){return null==t||"boolean"==typeof t||""===t?"":n||"number"!=typeof t||0===t||eT.hasOwnProperty(e)&&eT[e]?(""+t).trim():t+"px";}function eM(e,t){for(var n in e=e.style,t)if(t.hasOwnProperty(n)){var r=0===n.indexOf("--"),a=eE(n,t[n],r);"float"===n&&(n="cssFloat"),r?e.setProperty(n,a):e[n]=a;}}Object.keys(eT).forEach(function(e){ez.forEach(function(t){eT[t=t+e.charAt(0).toUpperCase()+e.substring(1)]=eT[e];});});var eP=ee({menuitem:!0},{area:!0,base:!0,br:!0,col:!0,embed:!0,hr:!0,img:!0,input:!0,keygen:!0,link:!0,meta:!0,param:!0,source:!0,track:!0,wbr:!0});function eL(e,t){if(t){if(eP[e]&&(null!=t.children||null!=t.dangerouslySetInnerHTML))throw Error(k(137,e));if(null!=t.dangerouslySetInnerHTML){if(null!=t.children)throw Error(k(60));if("object"!=typeof t.dangerouslySetInnerHTML||!("__html"in t.dangerouslySetInnerHTML))throw Error(k(61));}if(null!=t.style&&"object"!=typeof t.style)throw Error(k(62));}}function e_(e,t){if(-1===e.indexOf("-"))return"string"==typeof t.is;switch(e){case"annotation-xml":case"color-profile":case"font-face":case"font-face-src":case"font-face-uri":case"font-face-format":case"font-face-name":case"missing-glyph":return!1;default:return!0;}}var eD=null;function ej(e){return(e=e.target||e.srcElement||window).correspondingUseElement&&(e=e.correspondingUseElement),3===e.nodeType?e.parentNode:e;}var eF=null,eN=null,eO=null;function eR(e){if(e=rR(e)){if("function"!=typeof eF)throw Error(k(280));var t=e.stateNode;t&&(t=rI(t),eF(e.stateNode,e.type,t));}}function eA(e){eN?eO?eO.push(e):eO=[e]:eN=e;}function eI(){if(eN){var e=eN,t=eO;if(eO=eN=null,eR(e),t)for(e=0;e<t.length;e++)eR(t[e]);}}function eB(e,t){return e(t);}function eG(){}var eH=!1;function eq(e,t,n){if(eH)return e(t,n);eH=!0;try{return eB(e,t,n);}finally{eH=!1,(null!==eN||null!==eO)&&(eG(),eI());}}function eU(e,t){var n=e.stateNode;if(null===n)return null;var r=rI(n);if(null===r)return null;switch(n=r[t],t){case"onClick":case"onClickCapture":case"onDoubleClick":case"onDoubleClickCapture":case"onMouseDown":case"onMouseDownCapture":case"onMouseMove":case"onMouseMoveCapture":case"onMouseUp":case"onMouseUpCapture":case"onMouseEnter":(r=!r.disabled)||(r=!("button"===(e=e.type)||"input"===e||"select"===e||"textarea"===e)),e=!r;break;default:e=!1;}if(e)return null;if(n&&"function"!=typeof n)throw Error(k(231,t,typeof n));return n;}var eW=!1;if(M)try{var eV={};Object.defineProperty(eV,"passive",{get:function(){eW=!0;}}),window.addEventListener("test",eV,eV),window.removeEventListener("test",eV,eV);}catch(e){eW=!1;}function e$(e,t,n,r,a,i,o,l,u){var s=Array.prototype.slice.call(arguments,3);try{t.apply(n,s);}catch(e){this.onError(e);}}var eK=!1,eQ=null,eY=!1,eX=null,eJ={onError:function(e){eK=!0,eQ=e;}};function eZ(e,t,n,r,a,i,o,l,u){eK=!1,eQ=null,e$.apply(eJ,arguments);}function e0(e){var t=e,n=e;if(e.alternate)for(;t.return;)t=t.return;else{e=t;do 0!=(4098&(t=e).flags)&&(n=t.return),e=t.return;while(e)}return 3===t.tag?n:null;}function e1(e){if(13===e.tag){var t=e.memoizedState;if(null===t&&null!==(e=e.alternate)&&(t=e.memoizedState),null!==t)return t.dehydrated;}return null;}function e2(e){if(e0(e)!==e)throw Error(k(188));}function e3(e){return null!==(e=function(e){var t=e.alternate;if(!t){if(null===(t=e0(e)))throw Error(k(188));return t!==e?null:e;}for(var n=e,r=t;;){var a=n.return;if(null===a)break;var i=a.alternate;if(null===i){if(null!==(r=a.return)){n=r;continue;}break;}if(a.child===i.child){for(i=a.child;i;){if(i===n)return e2(a),e;if(i===r)return e2(a),t;i=i.sibling;}throw Error(k(188));}if(n.return!==r.return)n=a,r=i;else{for(var o=!1,l=a.child;l;){if(l===n){o=!0,n=a,r=i;break;}if(l===r){o=!0,r=a,n=i;break;}l=l.sibling;}if(!o){for(l=i.child;l;){if(l===n){o=!0,n=i,r=a;break;}if(l===r){o=!0,r=i,n=a;break;}l=l.sibling;}if(!o)throw Error(k(189));}}if(n.alternate!==r)throw Error(k(190));}if(3!==n.tag)throw Error(k(188));return n.stateNode.current===n?e:t;}(e))?function e(t){if(5===t.tag||6===t.tag)return t;for(t=t.child;null!==t;){var n=e(t);if(null!==n)return n;t=t.sibling;}return null;}(e):null;}var e5=S.unstable_scheduleCallback,e4=S.unstable_cancelCallback,e6=S.unstable_shouldYield,e8=S.unstable_requestPaint,e9=S.unstable_now,e7=S.unstable_getCurrentPriorityLevel,te=S.unstable_ImmediatePriority,tt=S.unstable_UserBlockingPriority,tn=S.unstable_NormalPriority,tr=S.unstable_LowPriority,ta=S.unstable_IdlePriority,ti=null,to=null,tl=Math.clz32?Math.clz32:function(e){return 0==(e>>>=0)?32:31-(tu(e)/ts|0)|0;},tu=Math.log,ts=Math.LN2,tc=64,tf=4194304;function td(e){switch(e&-e){case 1:return 1;case 2:return 2;case 4:return 4;case 8:return 8;case 16:return 16;case 32:return 32;case 64:case 128:case 256:case 512:case 1024:case 2048:case 4096:case 8192:case 16384:case 32768:case 65536:case 131072:case 262144:case 524288:case 1048576:case 2097152:return 4194240&e;case 4194304:case 8388608:case 16777216:case 33554432:case 67108864:return 130023424&e;case 134217728:return 134217728;case 268435456:return 268435456;case 536870912:return 536870912;case 1073741824:return 1073741824;default:return e;}}function th(e,t){var n=e.pendingLanes;if(0===n)return 0;var r=0,a=e.suspendedLanes,i=e.pingedLanes,o=268435455&n;if(0!==o){var l=o&~a;0!==l?r=td(l):0!=(i&=o)&&(r=td(i));}else 0!=(o=n&~a)?r=td(o):0!==i&&(r=td(i));if(0===r)return 0;if(0!==t&&t!==r&&0==(t&a)&&((a=r&-r)>=(i=t&-t)||16===a&&0!=(4194240&i)))return t;if(0!=(4&r)&&(r|=16&n),0!==(t=e.entangledLanes))for(e=e.entanglements,t&=r;0<t;)a=1<<(n=31-tl(t)),r|=e[n],t&=~a;return r;}function tp(e){return 0!=(e=-1073741825&e.pendingLanes)?e:1073741824&e?1073741824:0;}function tg(){var e=tc;return 0==(4194240&(tc<<=1))&&(tc=64),e;}function tv(e){for(var t=[],n=0;31>n;n++)t.push(e);return t;}function tb(e,t,n){e.pendingLanes|=t,536870912!==t&&(e.suspendedLanes=0,e.pingedLanes=0),(e=e.eventTimes)[t=31-tl(t)]=n;}function tm(e,t){var n=e.entangledLanes|=t;for(e=e.entanglements;n;){var r=31-tl(n),a=1<<r;a&t|e[r]&t&&(e[r]|=t),n&=~a;}}var ty=0;function tw(e){return 1<(e&=-e)?4<e?0!=(268435455&e)?16:536870912:4:1;}var tx=!1,tS=[],tk=null,tC=null,tT=null,tz=new Map,tE=new Map,tM=[],tP="mousedown mouseup touchcancel touchend touchstart auxclick dblclick pointercancel pointerdown pointerup dragend dragstart drop compositionend compositionstart keydown keypress keyup input textInput copy cut paste click change contextmenu reset submit".split(" ");function tL(e,t){switch(e){case"focusin":case"focusout":tk=null;break;case"dragenter":case"dragleave":tC=null;break;case"mouseover":case"mouseout":tT=null;break;case"pointerover":case"pointerout":tz.delete(t.pointerId);break;case"gotpointercapture":case"lostpointercapture":tE.delete(t.pointerId);}}function t_(e,t,n,r,a,i){return null===e||e.nativeEvent!==i?(e={blockedOn:t,domEventName:n,eventSystemFlags:r,nativeEvent:i,targetContainers:[a]},null!==t&&null!==(t=rR(t))&&u(t)):(e.eventSystemFlags|=r,t=e.targetContainers,null!==a&&-1===t.indexOf(a)&&t.push(a)),e;}function tD(e){var t=rO(e.target);if(null!==t){var n=e0(t);if(null!==n){if(13===(t=n.tag)){if(null!==(t=e1(n))){e.blockedOn=t,f(e.priority,function(){s(n);});return;}}else if(3===t&&n.stateNode.current.memoizedState.isDehydrated){e.blockedOn=3===n.tag?n.stateNode.containerInfo:null;return;}}}e.blockedOn=null;}function tj(e){if(null!==e.blockedOn)return!1;for(var t=e.targetContainers;0<t.length;){var n=tU(e.domEventName,e.eventSystemFlags,t[0],e.nativeEvent);if(null!==n)return null!==(t=rR(n))&&u(t),e.blockedOn=n,!1;var r=new(n=e.nativeEvent).constructor(n.type,n);eD=r,n.target.dispatchEvent(r),eD=null,t.shift();}return!0;}function tF(e,t,n){tj(e)&&n.delete(t);}function tN(){tx=!1,null!==tk&&tj(tk)&&(tk=null),null!==tC&&tj(tC)&&(tC=null),null!==tT&&tj(tT)&&(tT=null),tz.forEach(tF),tE.forEach(tF);}function tO(e,t){e.blockedOn===t&&(e.blockedOn=null,tx||(tx=!0,S.unstable_scheduleCallback(S.unstable_NormalPriority,tN)));}function tR(e){function t(t){return tO(t,e);}if(0<tS.length){tO(tS[0],e);for(var n=1;n<tS.length;n++){var r=tS[n];r.blockedOn===e&&(r.blockedOn=null);}}for(null!==tk&&tO(tk,e),null!==tC&&tO(tC,e),null!==tT&&tO(tT,e),tz.forEach(t),tE.forEach(t),n=0;n<tM.length;n++)(r=tM[n]).blockedOn===e&&(r.blockedOn=null);for(;0<tM.length&&null===(n=tM[0]).blockedOn;)tD(n),null===n.blockedOn&&tM.shift();}var tA=A.ReactCurrentBatchConfig,tI=!0;function tB(e,t,n,r){var a=ty,i=tA.transition;tA.transition=null;try{ty=1,tH(e,t,n,r);}finally{ty=a,tA.transition=i;}}function tG(e,t,n,r){var a=ty,i=tA.transition;tA.transition=null;try{ty=4,tH(e,t,n,r);}finally{ty=a,tA.transition=i;}}function tH(e,t,n,r){if(tI){var a=tU(e,t,n,r);if(null===a)ru(e,t,r,tq,n),tL(e,r);else if(function(e,t,n,r,a){switch(t){case"focusin":return tk=t_(tk,e,t,n,r,a),!0;case"dragenter":return tC=t_(tC,e,t,n,r,a),!0;case"mouseover":return tT=t_(tT,e,t,n,r,a),!0;case"pointerover":var i=a.pointerId;return tz.set(i,t_(tz.get(i)||null,e,t,n,r,a)),!0;case"gotpointercapture":return i=a.pointerId,tE.set(i,t_(tE.get(i)||null,e,t,n,r,a)),!0;}return!1;}(a,e,t,n,r))r.stopPropagation();else if(tL(e,r),4&t&&-1<tP.indexOf(e)){for(;null!==a;){var i=rR(a);if(null!==i&&l(i),null===(i=tU(e,t,n,r))&&ru(e,t,r,tq,n),i===a)break;a=i;}null!==a&&r.stopPropagation();}else ru(e,t,r,null,n);}}var tq=null;function tU(e,t,n,r){if(tq=null,null!==(e=rO(e=ej(r)))){if(null===(t=e0(e)))e=null;else if(13===(n=t.tag)){if(null!==(e=e1(t)))return e;e=null;}else if(3===n){if(t.stateNode.current.memoizedState.isDehydrated)return 3===t.tag?t.stateNode.containerInfo:null;e=null;}else t!==e&&(e=null);}return tq=e,null;}function tW(e){switch(e){case"cancel":case"click":case"close":case"contextmenu":case"copy":case"cut":case"auxclick":case"dblclick":case"dragend":case"dragstart":case"drop":case"focusin":case"focusout":case"input":case"invalid":case"keydown":case"keypress":case"keyup":case"mousedown":case"mouseup":case"paste":case"pause":case"play":case"pointercancel":case"pointerdown":case"pointerup":case"ratechange":case"reset":case"resize":case"seeked":case"submit":case"touchcancel":case"touchend":case"touchstart":case"volumechange":case"change":case"selectionchange":case"textInput":case"compositionstart":case"compositionend":case"compositionupdate":case"beforeblur":case"afterblur":case"beforeinput":case"blur":case"fullscreenchange":case"focus":case"hashchange":case"popstate":case"select":case"selectstart":return 1;case"drag":case"dragenter":case"dragexit":case"dragleave":case"dragover":case"mousemove":case"mouseout":case"mouseover":case"pointermove":case"pointerout":case"pointerover":case"scroll":case"toggle":case"touchmove":case"wheel":case"mouseenter":case"mouseleave":case"pointerenter":case"pointerleave":return 4;case"message":switch(e7()){case te:return 1;case tt:return 4;case tn:case tr:return 16;case ta:return 536870912;default:return 16;}default:return 16;}}var tV=null,t$=null,tK=null;function tQ(){if(tK)return tK;var e,t,n=t$,r=n.length,a="value"in tV?tV.value:tV.textContent,i=a.length;for(e=0;e<r&&n[e]===a[e];e++);var o=r-e;for(t=1;t<=o&&n[r-t]===a[i-t];t++);return tK=a.slice(e,1<t?1-t:void 0);}function tY(e){var t=e.keyCode;return"charCode"in e?0===(e=e.charCode)&&13===t&&(e=13):e=t,10===e&&(e=13),32<=e||13===e?e:0;}function tX(){return!0;}function tJ(){return!1;}function tZ(e){function t(t,n,r,a,i){for(var o in this._reactName=t,this._targetInst=r,this.type=n,this.nativeEvent=a,this.target=i,this.currentTarget=null,e)e.hasOwnProperty(o)&&(t=e[o],this[o]=t?t(a):a[o]);return this.isDefaultPrevented=(null!=a.defaultPrevented?a.defaultPrevented:!1===a.returnValue)?tX:tJ,this.isPropagationStopped=tJ,this;}return ee(t.prototype,{preventDefault:function(){this.defaultPrevented=!0;var e=this.nativeEvent;e&&(e.preventDefault?e.preventDefault():"unknown"!=typeof e.returnValue&&(e.returnValue=!1),this.isDefaultPrevented=tX);},stopPropagation:function(){var e=this.nativeEvent;e&&(e.stopPropagation?e.stopPropagation():"unknown"!=typeof e.cancelBubble&&(e.cancelBubble=!0),this.isPropagationStopped=tX);},persist:function(){},isPersistent:tX}),t;}var t0={eventPhase:0,bubbles:0,cancelable:0,timeStamp:function(e){return e.timeStamp||Date.now();},defaultPrevented:0,isTrusted:0},t1=tZ(t0),t2=ee({},t0,{view:0,detail:0}),t3=tZ(t2),t5=ee({},t2,{screenX:0,screenY:0,clientX:0,clientY:0,pageX:0,pageY:0,ctrlKey:0,shiftKey:0,altKey:0,metaKey:0,getModifierState:ni,button:0,buttons:0,relatedTarget:function(e){return void 0===e.relatedTarget?e.fromElement===e.srcElement?e.toElement:e.fromElement:e.relatedTarget;},movementX:function(e){return"movementX"in e?e.movementX:(e!==p&&(p&&"mousemove"===e.type?(d=e.screenX-p.screenX,h=e.screenY-p.screenY):h=d=0,p=e),d);},movementY:function(e){return"movementY"in e?e.movementY:h;}}),t4=tZ(t5),t6=tZ(ee({},t5,{dataTransfer:0})),t8=tZ(ee({},t2,{relatedTarget:0})),t9=tZ(ee({},t0,{animationName:0,elapsedTime:0,pseudoElement:0})),t7=tZ(ee({},t0,{clipboardData:function(e){return"clipboardData"in e?e.clipboardData:window.clipboardData;}})),ne=tZ(ee({},t0,{data:0})),nt={Esc:"Escape",Spacebar:" ",Left:"ArrowLeft",Up:"ArrowUp",Right:"ArrowRight",Down:"ArrowDown",Del:"Delete",Win:"OS",Menu:"ContextMenu",Apps:"ContextMenu",Scroll:"ScrollLock",MozPrintableKey:"Unidentified"},nn={8:"Backspace",9:"Tab",12:"Clear",13:"Enter",16:"Shift",17:"Control",18:"Alt",19:"Pause",20:"CapsLock",27:"Escape",32:" ",33:"PageUp",34:"PageDown",35:"End",36:"Home",37:"ArrowLeft",38:"ArrowUp",39:"ArrowRight",40:"ArrowDown",45:"Insert",46:"Delete",112:"F1",113:"F2",114:"F3",115:"F4",116:"F5",117:"F6",118:"F7",119:"F8",120:"F9",121:"F10",122:"F11",123:"F12",144:"NumLock",145:"ScrollLock",224:"Meta"},nr={Alt:"altKey",Control:"ctrlKey",Meta:"metaKey",Shift:"shiftKey"};function na(e){var t=this.nativeEvent;return t.getModifierState?t.getModifierState(e):!!(e=nr[e])&&!!t[e];}function ni(){return na;}var no=tZ(ee({},t2,{key:function(e){if(e.key){var t=nt[e.key]||e.key;if("Unidentified"!==t)return t;}return"keypress"===e.type?13===(e=tY(e))?"Enter":String.fromCharCode(e):"keydown"===e.type||"keyup"===e.type?nn[e.keyCode]||"Unidentified":"";},code:0,location:0,ctrlKey:0,shiftKey:0,altKey:0,metaKey:0,repeat:0,locale:0,getModifierState:ni,charCode:function(e){return"keypress"===e.type?tY(e):0;},keyCode:function(e){return"keydown"===e.type||"keyup"===e.type?e.keyCode:0;},which:function(e){return"keypress"===e.type?tY(e):"keydown"===e.type||"keyup"===e.type?e.keyCode:0;}})),nl=tZ(ee({},t5,{pointerId:0,width:0,height:0,pressure:0,tangentialPressure:0,tiltX:0,tiltY:0,twist:0,pointerType:0,isPrimary:0})),nu=tZ(ee({},t2,{touches:0,targetTouches:0,changedTouches:0,altKey:0,metaKey:0,ctrlKey:0,shiftKey:0,getModifierState:ni})),ns=tZ(ee({},t0,{propertyName:0,elapsedTime:0,pseudoElement:0})),nc=tZ(ee({},t5,{deltaX:function(e){return"deltaX"in e?e.deltaX:"wheelDeltaX"in e?-e.wheelDeltaX:0;},deltaY:function(e){return"deltaY"in e?e.deltaY:"wheelDeltaY"in e?-e.wheelDeltaY:"wheelDelta"in e?-e.wheelDelta:0;},deltaZ:0,deltaMode:0})),nf=[9,13,27,32],nd=M&&"CompositionEvent"in window,nh=null;M&&"documentMode"in document&&(nh=document.documentMode);var np=M&&"TextEvent"in window&&!nh,ng=M&&(!nd||nh&&8<nh&&11>=nh),nv=!1;function nb(e,t){switch(e){case"keyup":return -1!==nf.indexOf(t.keyCode);case"keydown":return 229!==t.keyCode;case"keypress":case"mousedown":case"focusout":return!0;default:return!1;}}function nm(e){return"object"==typeof(e=e.detail)&&"data"in e?e.data:null;}var ny=!1,nw={color:!0,date:!0,datetime:!0,"datetime-local":!0,email:!0,month:!0,number:!0,password:!0,range:!0,search:!0,tel:!0,text:!0,time:!0,url:!0,week:!0};function nx(e){var t=e&&e.nodeName&&e.nodeName.toLowerCase();return"input"===t?!!nw[e.type]:"textarea"===t;}function nS(e,t,n,r){eA(r),0<(t=rc(t,"onChange")).length&&(n=new t1("onChange","change",null,n,r),e.push({event:n,listeners:t}));}var nk=null,nC=null;function nT(e){rn(e,0);}function nz(e){if(el(rA(e)))return e;}function nE(e,t){if("change"===e)return t;}var nM=!1;if(M){if(M){var nP="oninput"in document;if(!nP){var nL=document.createElement("div");nL.setAttribute("oninput","return;"),nP="function"==typeof nL.oninput;}g=nP;}else g=!1;nM=g&&(!document.documentMode||9<document.documentMode);}function n_(){nk&&(nk.detachEvent("onpropertychange",nD),nC=nk=null);}function nD(e){if("value"===e.propertyName&&nz(nC)){var t=[];nS(t,nC,e,ej(e)),eq(nT,t);}}function nj(e,t,n){"focusin"===e?(n_(),nk=t,nC=n,nk.attachEvent("onpropertychange",nD)):"focusout"===e&&n_();}function nF(e){if("selectionchange"===e||"keyup"===e||"keydown"===e)return nz(nC);}function nN(e,t){if("click"===e)return nz(t);}function nO(e,t){if("input"===e||"change"===e)return nz(t);}var nR="function"==typeof Object.is?Object.is:function(e,t){return e===t&&(0!==e||1/e==1/t)||e!=e&&t!=t;};function nA(e,t){if(nR(e,t))return!0;if("object"!=typeof e||null===e||"object"!=typeof t||null===t)return!1;var n=Object.keys(e),r=Object.keys(t);if(n.length!==r.length)return!1;for(r=0;r<n.length;r++){var a=n[r];if(!P.call(t,a)||!nR(e[a],t[a]))return!1;}return!0;}function nI(e){for(;e&&e.firstChild;)e=e.firstChild;return e;}function nB(e,t){var n,r=nI(e);for(e=0;r;){if(3===r.nodeType){if(n=e+r.textContent.length,e<=t&&n>=t)return{node:r,offset:t-e};e=n;}e:{for(;r;){if(r.nextSibling){r=r.nextSibling;break e;}r=r.parentNode;}r=void 0;}r=nI(r);}}function nG(){for(var e=window,t=eu();t instanceof e.HTMLIFrameElement;){try{var n="string"==typeof t.contentWindow.location.href;}catch(e){n=!1;}if(n)e=t.contentWindow;else break;t=eu(e.document);}return t;}function nH(e){var t=e&&e.nodeName&&e.nodeName.toLowerCase();return t&&("input"===t&&("text"===e.type||"search"===e.type||"tel"===e.type||"url"===e.type||"password"===e.type)||"textarea"===t||"true"===e.contentEditable);}var nq=M&&"documentMode"in document&&11>=document.documentMode,nU=null,nW=null,nV=null,n$=!1;function nK(e,t,n){var r=n.window===n?n.document:9===n.nodeType?n:n.ownerDocument;n$||null==nU||nU!==eu(r)||(r="selectionStart"in(r=nU)&&nH(r)?{start:r.selectionStart,end:r.selectionEnd}:{anchorNode:(r=(r.ownerDocument&&r.ownerDocument.defaultView||window).getSelection()).anchorNode,anchorOffset:r.anchorOffset,focusNode:r.focusNode,focusOffset:r.focusOffset},nV&&nA(nV,r)||(nV=r,0<(r=rc(nW,"onSelect")).length&&(t=new t1("onSelect","select",null,t,n),e.push({event:t,listeners:r}),t.target=nU)));}function nQ(e,t){var n={};return n[e.toLowerCase()]=t.toLowerCase(),n["Webkit"+e]="webkit"+t,n["Moz"+e]="moz"+t,n;}var nY={animationend:nQ("Animation","AnimationEnd"),animationiteration:nQ("Animation","AnimationIteration"),animationstart:nQ("Animation","AnimationStart"),transitionend:nQ("Transition","TransitionEnd")},nX={},nJ={};function nZ(e){if(nX[e])return nX[e];if(!nY[e])return e;var t,n=nY[e];for(t in n)if(n.hasOwnProperty(t)&&t in nJ)return nX[e]=n[t];return e;}M&&(nJ=document.createElement("div").style,"AnimationEvent"in window||(delete nY.animationend.animation,delete nY.animationiteration.animation,delete nY.animationstart.animation),"TransitionEvent"in window||delete nY.transitionend.transition);var n0=nZ("animationend"),n1=nZ("animationiteration"),n2=nZ("animationstart"),n3=nZ("transitionend"),n5=new Map,n4="abort auxClick cancel canPlay canPlayThrough click close contextMenu copy cut drag dragEnd dragEnter dragExit dragLeave dragOver dragStart drop durationChange emptied encrypted ended error gotPointerCapture input invalid keyDown keyPress keyUp load loadedData loadedMetadata loadStart lostPointerCapture mouseDown mouseMove mouseOut mouseOver mouseUp paste pause play playing pointerCancel pointerDown pointerMove pointerOut pointerOver pointerUp progress rateChange reset resize seeked seeking stalled submit suspend timeUpdate touchCancel touchEnd touchStart volumeChange scroll toggle touchMove waiting wheel".split(" ");function n6(e,t){n5.set(e,t),z(t,[e]);}for(var n8=0;n8<n4.length;n8++){var n9=n4[n8];n6(n9.toLowerCase(),"on"+(n9[0].toUpperCase()+n9.slice(1)));}n6(n0,"onAnimationEnd"),n6(n1,"onAnimationIteration"),n6(n2,"onAnimationStart"),n6("dblclick","onDoubleClick"),n6("focusin","onFocus"),n6("focusout","onBlur"),n6(n3,"onTransitionEnd"),E("onMouseEnter",["mouseout","mouseover"]),E("onMouseLeave",["mouseout","mouseover"]),E("onPointerEnter",["pointerout","pointerover"]),E("onPointerLeave",["pointerout","pointerover"]),z("onChange","change click focusin focusout input keydown keyup selectionchange".split(" ")),z("onSelect","focusout contextmenu dragend focusin keydown keyup mousedown mouseup selectionchange".split(" ")),z("onBeforeInput",["compositionend","keypress","textInput","paste"]),z("onCompositionEnd","compositionend focusout keydown keypress keyup mousedown".split(" ")),z("onCompositionStart","compositionstart focusout keydown keypress keyup mousedown".split(" ")),z("onCompositionUpdate","compositionupdate focusout keydown keypress keyup mousedown".split(" "));var n7="abort canplay canplaythrough durationchange emptied encrypted ended error loadeddata loadedmetadata loadstart pause play playing progress ratechange resize seeked seeking stalled suspend timeupdate volumechange waiting".split(" "),re=new Set("cancel close invalid load scroll toggle".split(" ").concat(n7));function rt(e,t,n){var r=e.type||"unknown-event";e.currentTarget=n,function(e,t,n,r,a,i,o,l,u){if(eZ.apply(this,arguments),eK){if(eK){var s=eQ;eK=!1,eQ=null;}else throw Error(k(198));eY||(eY=!0,eX=s);}}(r,t,void 0,e),e.currentTarget=null;}function rn(e,t){t=0!=(4&t);for(var n=0;n<e.length;n++){var r=e[n],a=r.event;r=r.listeners;e:{var i=void 0;if(t)for(var o=r.length-1;0<=o;o--){var l=r[o],u=l.instance,s=l.currentTarget;if(l=l.listener,u!==i&&a.isPropagationStopped())break e;rt(a,l,s),i=u;}else for(o=0;o<r.length;o++){if(u=(l=r[o]).instance,s=l.currentTarget,l=l.listener,u!==i&&a.isPropagationStopped())break e;rt(a,l,s),i=u;}}}if(eY)throw e=eX,eY=!1,eX=null,e;}function rr(e,t){var n=t[rj];void 0===n&&(n=t[rj]=new Set);var r=e+"__bubble";n.has(r)||(rl(t,e,2,!1),n.add(r));}function ra(e,t,n){var r=0;t&&(r|=4),rl(n,e,r,t);}var ri="_reactListening"+Math.random().toString(36).slice(2);function ro(e){if(!e[ri]){e[ri]=!0,C.forEach(function(t){"selectionchange"!==t&&(re.has(t)||ra(t,!1,e),ra(t,!0,e));});var t=9===e.nodeType?e:e.ownerDocument;null===t||t[ri]||(t[ri]=!0,ra("selectionchange",!1,t));}}function rl(e,t,n,r){switch(tW(t)){case 1:var a=tB;break;case 4:a=tG;break;default:a=tH;}n=a.bind(null,t,n,e),a=void 0,eW&&("touchstart"===t||"touchmove"===t||"wheel"===t)&&(a=!0),r?void 0!==a?e.addEventListener(t,n,{capture:!0,passive:a}):e.addEventListener(t,n,!0):void 0!==a?e.addEventListener(t,n,{passive:a}):e.addEventListener(t,n,!1);}function ru(e,t,n,r,a){var i=r;if(0==(1&t)&&0==(2&t)&&null!==r)e:for(;;){if(null===r)return;var o=r.tag;if(3===o||4===o){var l=r.stateNode.containerInfo;if(l===a||8===l.nodeType&&l.parentNode===a)break;if(4===o)for(o=r.return;null!==o;){var u=o.tag;if((3===u||4===u)&&((u=o.stateNode.containerInfo)===a||8===u.nodeType&&u.parentNode===a))return;o=o.return;}for(;null!==l;){if(null===(o=rO(l)))return;if(5===(u=o.tag)||6===u){r=i=o;continue e;}l=l.parentNode;}}r=r.return;}eq(function(){var r=i,a=ej(n),o=[];e:{var l=n5.get(e);if(void 0!==l){var u=t1,s=e;switch(e){case"keypress":if(0===tY(n))break e;case"keydown":case"keyup":u=no;break;case"focusin":s="focus",u=t8;break;case"focusout":s="blur",u=t8;break;case"beforeblur":case"afterblur":u=t8;break;case"click":if(2===n.button)break e;case"auxclick":case"dblclick":case"mousedown":case"mousemove":case"mouseup":case"mouseout":case"mouseover":case"contextmenu":u=t4;break;case"drag":case"dragend":case"dragenter":case"dragexit":case"dragleave":case"dragover":case"dragstart":case"drop":u=t6;break;case"touchcancel":case"touchend":case"touchmove":case"touchstart":u=nu;break;case n0:case n1:case n2:u=t9;break;case n3:u=ns;break;case"scroll":u=t3;break;case"wheel":u=nc;break;case"copy":case"cut":case"paste":u=t7;break;case"gotpointercapture":case"lostpointercapture":case"pointercancel":case"pointerdown":case"pointermove":case"pointerout":case"pointerover":case"pointerup":u=nl;}var c=0!=(4&t),f=!c&&"scroll"===e,d=c?null!==l?l+"Capture":null:l;c=[];for(var h,p=r;null!==p;){var g=(h=p).stateNode;if(5===h.tag&&null!==g&&(h=g,null!==d&&null!=(g=eU(p,d))&&c.push(rs(p,g,h))),f)break;p=p.return;}0<c.length&&(l=new u(l,s,null,n,a),o.push({event:l,listeners:c}));}}if(0==(7&t)){if(l="mouseover"===e||"pointerover"===e,u="mouseout"===e||"pointerout"===e,!(l&&n!==eD&&(s=n.relatedTarget||n.fromElement)&&(rO(s)||s[rD]))&&(u||l)&&(l=a.window===a?a:(l=a.ownerDocument)?l.defaultView||l.parentWindow:window,u?(s=n.relatedTarget||n.toElement,u=r,null!==(s=s?rO(s):null)&&(f=e0(s),s!==f||5!==s.tag&&6!==s.tag)&&(s=null)):(u=null,s=r),u!==s)){if(c=t4,g="onMouseLeave",d="onMouseEnter",p="mouse",("pointerout"===e||"pointerover"===e)&&(c=nl,g="onPointerLeave",d="onPointerEnter",p="pointer"),f=null==u?l:rA(u),h=null==s?l:rA(s),(l=new c(g,p+"leave",u,n,a)).target=f,l.relatedTarget=h,g=null,rO(a)===r&&((c=new c(d,p+"enter",s,n,a)).target=h,c.relatedTarget=f,g=c),f=g,u&&s)t:{for(c=u,d=s,p=0,h=c;h;h=rf(h))p++;for(h=0,g=d;g;g=rf(g))h++;for(;0<p-h;)c=rf(c),p--;for(;0<h-p;)d=rf(d),h--;for(;p--;){if(c===d||null!==d&&c===d.alternate)break t;c=rf(c),d=rf(d);}c=null;}else c=null;null!==u&&rd(o,l,u,c,!1),null!==s&&null!==f&&rd(o,f,s,c,!0);}e:{if("select"===(u=(l=r?rA(r):window).nodeName&&l.nodeName.toLowerCase())||"input"===u&&"file"===l.type)var v,b=nE;else if(nx(l)){if(nM)b=nO;else{b=nF;var m=nj;}}else(u=l.nodeName)&&"input"===u.toLowerCase()&&("checkbox"===l.type||"radio"===l.type)&&(b=nN);if(b&&(b=b(e,r))){nS(o,b,n,a);break e;}m&&m(e,l,r),"focusout"===e&&(m=l._wrapperState)&&m.controlled&&"number"===l.type&&ep(l,"number",l.value);}switch(m=r?rA(r):window,e){case"focusin":(nx(m)||"true"===m.contentEditable)&&(nU=m,nW=r,nV=null);break;case"focusout":nV=nW=nU=null;break;case"mousedown":n$=!0;break;case"contextmenu":case"mouseup":case"dragend":n$=!1,nK(o,n,a);break;case"selectionchange":if(nq)break;case"keydown":case"keyup":nK(o,n,a);}if(nd)t:{switch(e){case"compositionstart":var y="onCompositionStart";break t;case"compositionend":y="onCompositionEnd";break t;case"compositionupdate":y="onCompositionUpdate";break t;}y=void 0;}else ny?nb(e,n)&&(y="onCompositionEnd"):"keydown"===e&&229===n.keyCode&&(y="onCompositionStart");y&&(ng&&"ko"!==n.locale&&(ny||"onCompositionStart"!==y?"onCompositionEnd"===y&&ny&&(v=tQ()):(t$="value"in(tV=a)?tV.value:tV.textContent,ny=!0)),0<(m=rc(r,y)).length&&(y=new ne(y,e,null,n,a),o.push({event:y,listeners:m}),v?y.data=v:null!==(v=nm(n))&&(y.data=v))),(v=np?function(e,t){switch(e){case"compositionend":return nm(t);case"keypress":if(32!==t.which)return null;return nv=!0," ";case"textInput":return" "===(e=t.data)&&nv?null:e;default:return null;}}(e,n):function(e,t){if(ny)return"compositionend"===e||!nd&&nb(e,t)?(e=tQ(),tK=t$=tV=null,ny=!1,e):null;switch(e){case"paste":default:return null;case"keypress":if(!(t.ctrlKey||t.altKey||t.metaKey)||t.ctrlKey&&t.altKey){if(t.char&&1<t.char.length)return t.char;if(t.which)return String.fromCharCode(t.which);}return null;case"compositionend":return ng&&"ko"!==t.locale?null:t.data;}}(e,n))&&0<(r=rc(r,"onBeforeInput")).length&&(a=new ne("onBeforeInput","beforeinput",null,n,a),o.push({event:a,listeners:r}),a.data=v);}rn(o,t);});}function rs(e,t,n){return{instance:e,listener:t,currentTarget:n};}function rc(e,t){for(var n=t+"Capture",r=[];null!==e;){var a=e,i=a.stateNode;5===a.tag&&null!==i&&(a=i,null!=(i=eU(e,n))&&r.unshift(rs(e,i,a)),null!=(i=eU(e,t))&&r.push(rs(e,i,a))),e=e.return;}return r;}function rf(e){if(null===e)return null;do e=e.return;while(e&&5!==e.tag)return e||null;}function rd(e,t,n,r,a){for(var i=t._reactName,o=[];null!==n&&n!==r;){var l=n,u=l.alternate,s=l.stateNode;if(null!==u&&u===r)break;5===l.tag&&null!==s&&(l=s,a?null!=(u=eU(n,i))&&o.unshift(rs(n,u,l)):a||null!=(u=eU(n,i))&&o.push(rs(n,u,l))),n=n.return;}0!==o.length&&e.push({event:t,listeners:o});}var rh=/\r\n?/g,rp=/\u0000|\uFFFD/g;function rg(e){return("string"==typeof e?e:""+e).replace(rh,"\n").replace(rp,"");}function rv(e,t,n){if(t=rg(t),rg(e)!==t&&n)throw Error(k(425));}function rb(){}var rm=null,ry=null;function rw(e,t){return"textarea"===e||"noscript"===e||"string"==typeof t.children||"number"==typeof t.children||"object"==typeof t.dangerouslySetInnerHTML&&null!==t.dangerouslySetInnerHTML&&null!=t.dangerouslySetInnerHTML.__html;}var rx="function"==typeof setTimeout?setTimeout:void 0,rS="function"==typeof clearTimeout?clearTimeout:void 0,rk="function"==typeof Promise?Promise:void 0,rC="function"==typeof queueMicrotask?queueMicrotask:void 0!==rk?function(e){return rk.resolve(null).then(e).catch(rT);}:rx;function rT(e){setTimeout(function(){throw e;});}function rz(e,t){var n=t,r=0;do{var a=n.nextSibling;if(e.removeChild(n),a&&8===a.nodeType){if("/$"===(n=a.data)){if(0===r){e.removeChild(a),tR(t);return;}r--;}else"$"!==n&&"$?"!==n&&"$!"!==n||r++;}n=a;}while(n)tR(t);}function rE(e){for(;null!=e;e=e.nextSibling){var t=e.nodeType;if(1===t||3===t)break;if(8===t){if("$"===(t=e.data)||"$!"===t||"$?"===t)break;if("/$"===t)return null;}}return e;}function rM(e){e=e.previousSibling;for(var t=0;e;){if(8===e.nodeType){var n=e.data;if("$"===n||"$!"===n||"$?"===n){if(0===t)return e;t--;}else"/$"===n&&t++;}e=e.previousSibling;}return null;}var rP=Math.random().toString(36).slice(2),rL="__reactFiber$"+rP,r_="__reactProps$"+rP,rD="__reactContainer$"+rP,rj="__reactEvents$"+rP,rF="__reactListeners$"+rP,rN="__reactHandles$"+rP;function rO(e){var t=e[rL];if(t)return t;for(var n=e.parentNode;n;){if(t=n[rD]||n[rL]){if(n=t.alternate,null!==t.child||null!==n&&null!==n.child)for(e=rM(e);null!==e;){if(n=e[rL])return n;e=rM(e);}return t;}n=(e=n).parentNode;}return null;}function rR(e){return(e=e[rL]||e[rD])&&(5===e.tag||6===e.tag||13===e.tag||3===e.tag)?e:null;}function rA(e){if(5===e.tag||6===e.tag)return e.stateNode;throw Error(k(33));}function rI(e){return e[r_]||null;}var rB=[],rG=-1;function rH(e){return{current:e};}function rq(e){0>rG||(e.current=rB[rG],rB[rG]=null,rG--);}function rU(e,t){rB[++rG]=e.current,e.current=t;}var rW={},rV=rH(rW),r$=rH(!1),rK=rW;function rQ(e,t){var n=e.type.contextTypes;if(!n)return rW;var r=e.stateNode;if(r&&r.__reactInternalMemoizedUnmaskedChildContext===t)return r.__reactInternalMemoizedMaskedChildContext;var a,i={};for(a in n)i[a]=t[a];return r&&((e=e.stateNode).__reactInternalMemoizedUnmaskedChildContext=t,e.__reactInternalMemoizedMaskedChildContext=i),i;}function rY(e){return null!=(e=e.childContextTypes);}function rX(){rq(r$),rq(rV);}function rJ(e,t,n){if(rV.current!==rW)throw Error(k(168));rU(rV,t),rU(r$,n);}function rZ(e,t,n){var r=e.stateNode;if(t=t.childContextTypes,"function"!=typeof r.getChildContext)return n;for(var a in r=r.getChildContext())if(!(a in t))throw Error(k(108,function(e){var t=e.type;switch(e.tag){case 24:return"Cache";case 9:return(t.displayName||"Context")+".Consumer";case 10:return(t._context.displayName||"Context")+".Provider";case 18:return"DehydratedFragment";case 11:return e=(e=t.render).displayName||e.name||"",t.displayName||(""!==e?"ForwardRef("+e+")":"ForwardRef");case 7:return"Fragment";case 5:return t;case 4:return"Portal";case 3:return"Root";case 6:return"Text";case 16:return function e(t){if(null==t)return null;if("function"==typeof t)return t.displayName||t.name||null;if("string"==typeof t)return t;switch(t){case G:return"Fragment";case B:return"Portal";case q:return"Profiler";case H:return"StrictMode";case $:return"Suspense";case K:return"SuspenseList";}if("object"==typeof t)switch(t.$$typeof){case W:return(t.displayName||"Context")+".Consumer";case U:return(t._context.displayName||"Context")+".Provider";case V:var n=t.render;return(t=t.displayName)||(t=""!==(t=n.displayName||n.name||"")?"ForwardRef("+t+")":"ForwardRef"),t;case Q:return null!==(n=t.displayName||null)?n:e(t.type)||"Memo";case Y:n=t._payload,t=t._init;try{return e(t(n));}catch(e){}}return null;}(t);case 8:return t===H?"StrictMode":"Mode";case 22:return"Offscreen";case 12:return"Profiler";case 21:return"Scope";case 13:return"Suspense";case 19:return"SuspenseList";case 25:return"TracingMarker";case 1:case 0:case 17:case 2:case 14:case 15:if("function"==typeof t)return t.displayName||t.name||null;if("string"==typeof t)return t;}return null;}(e)||"Unknown",a));return ee({},n,r);}function r0(e){return e=(e=e.stateNode)&&e.__reactInternalMemoizedMergedChildContext||rW,rK=rV.current,rU(rV,e),rU(r$,r$.current),!0;}function r1(e,t,n){var r=e.stateNode;if(!r)throw Error(k(169));n?(e=rZ(e,t,rK),r.__reactInternalMemoizedMergedChildContext=e,rq(r$),rq(rV),rU(rV,e)):rq(r$),rU(r$,n);}var r2=null,r3=!1,r5=!1;function r4(e){null===r2?r2=[e]:r2.push(e);}function r6(){if(!r5&&null!==r2){r5=!0;var e=0,t=ty;try{var n=r2;for(ty=1;e<n.length;e++){var r=n[e];do r=r(!0);while(null!==r)}r2=null,r3=!1;}catch(t){throw null!==r2&&(r2=r2.slice(e+1)),e5(te,r6),t;}finally{ty=t,r5=!1;}}return null;}var r8=[],r9=0,r7=null,ae=0,at=[],an=0,ar=null,aa=1,ai="";function ao(e,t){r8[r9++]=ae,r8[r9++]=r7,r7=e,ae=t;}function al(e,t,n){at[an++]=aa,at[an++]=ai,at[an++]=ar,ar=e;var r=aa;e=ai;var a=32-tl(r)-1;r&=~(1<<a),n+=1;var i=32-tl(t)+a;if(30<i){var o=a-a%5;i=(r&(1<<o)-1).toString(32),r>>=o,a-=o,aa=1<<32-tl(t)+a|n<<a|r,ai=i+e;}else aa=1<<i|n<<a|r,ai=e;}function au(e){null!==e.return&&(ao(e,1),al(e,1,0));}function as(e){for(;e===r7;)r7=r8[--r9],r8[r9]=null,ae=r8[--r9],r8[r9]=null;for(;e===ar;)ar=at[--an],at[an]=null,ai=at[--an],at[an]=null,aa=at[--an],at[an]=null;}var ac=null,af=null,ad=!1,ah=null;function ap(e,t){var n=lY(5,null,null,0);n.elementType="DELETED",n.stateNode=t,n.return=e,null===(t=e.deletions)?(e.deletions=[n],e.flags|=16):t.push(n);}function ag(e,t){switch(e.tag){case 5:var n=e.type;return null!==(t=1!==t.nodeType||n.toLowerCase()!==t.nodeName.toLowerCase()?null:t)&&(e.stateNode=t,ac=e,af=rE(t.firstChild),!0);case 6:return null!==(t=""===e.pendingProps||3!==t.nodeType?null:t)&&(e.stateNode=t,ac=e,af=null,!0);case 13:return null!==(t=8!==t.nodeType?null:t)&&(n=null!==ar?{id:aa,overflow:ai}:null,e.memoizedState={dehydrated:t,treeContext:n,retryLane:1073741824},(n=lY(18,null,null,0)).stateNode=t,n.return=e,e.child=n,ac=e,af=null,!0);default:return!1;}}function av(e){return 0!=(1&e.mode)&&0==(128&e.flags);}function ab(e){if(ad){var t=af;if(t){var n=t;if(!ag(e,t)){if(av(e))throw Error(k(418));t=rE(n.nextSibling);var r=ac;t&&ag(e,t)?ap(r,n):(e.flags=-4097&e.flags|2,ad=!1,ac=e);}}else{if(av(e))throw Error(k(418));e.flags=-4097&e.flags|2,ad=!1,ac=e;}}}function am(e){for(e=e.return;null!==e&&5!==e.tag&&3!==e.tag&&13!==e.tag;)e=e.return;ac=e;}function ay(e){if(e!==ac)return!1;if(!ad)return am(e),ad=!0,!1;if((t=3!==e.tag)&&!(t=5!==e.tag)&&(t="head"!==(t=e.type)&&"body"!==t&&!rw(e.type,e.memoizedProps)),t&&(t=af)){if(av(e))throw aw(),Error(k(418));for(;t;)ap(e,t),t=rE(t.nextSibling);}if(am(e),13===e.tag){if(!(e=null!==(e=e.memoizedState)?e.dehydrated:null))throw Error(k(317));e:{for(t=0,e=e.nextSibling;e;){if(8===e.nodeType){var t,n=e.data;if("/$"===n){if(0===t){af=rE(e.nextSibling);break e;}t--;}else"$"!==n&&"$!"!==n&&"$?"!==n||t++;}e=e.nextSibling;}af=null;}}else af=ac?rE(e.stateNode.nextSibling):null;return!0;}function aw(){for(var e=af;e;)e=rE(e.nextSibling);}function ax(){af=ac=null,ad=!1;}function aS(e){null===ah?ah=[e]:ah.push(e);}var ak=A.ReactCurrentBatchConfig;function aC(e,t){if(e&&e.defaultProps)for(var n in t=ee({},t),e=e.defaultProps)void 0===t[n]&&(t[n]=e[n]);return t;}var aT=rH(null),az=null,aE=null,aM=null;function aP(){aM=aE=az=null;}function aL(e){var t=aT.current;rq(aT),e._currentValue=t;}function a_(e,t,n){for(;null!==e;){var r=e.alternate;if((e.childLanes&t)!==t?(e.childLanes|=t,null!==r&&(r.childLanes|=t)):null!==r&&(r.childLanes&t)!==t&&(r.childLanes|=t),e===n)break;e=e.return;}}function aD(e,t){az=e,aM=aE=null,null!==(e=e.dependencies)&&null!==e.firstContext&&(0!=(e.lanes&t)&&(os=!0),e.firstContext=null);}function aj(e){var t=e._currentValue;if(aM!==e){if(e={context:e,memoizedValue:t,next:null},null===aE){if(null===az)throw Error(k(308));aE=e,az.dependencies={lanes:0,firstContext:e};}else aE=aE.next=e;}return t;}var aF=null;function aN(e){null===aF?aF=[e]:aF.push(e);}function aO(e,t,n,r){var a=t.interleaved;return null===a?(n.next=n,aN(t)):(n.next=a.next,a.next=n),t.interleaved=n,aR(e,r);}function aR(e,t){e.lanes|=t;var n=e.alternate;for(null!==n&&(n.lanes|=t),n=e,e=e.return;null!==e;)e.childLanes|=t,null!==(n=e.alternate)&&(n.childLanes|=t),n=e,e=e.return;return 3===n.tag?n.stateNode:null;}var aA=!1;function aI(e){e.updateQueue={baseState:e.memoizedState,firstBaseUpdate:null,lastBaseUpdate:null,shared:{pending:null,interleaved:null,lanes:0},effects:null};}function aB(e,t){e=e.updateQueue,t.updateQueue===e&&(t.updateQueue={baseState:e.baseState,firstBaseUpdate:e.firstBaseUpdate,lastBaseUpdate:e.lastBaseUpdate,shared:e.shared,effects:e.effects});}function aG(e,t){return{eventTime:e,lane:t,tag:0,payload:null,callback:null,next:null};}function aH(e,t,n){var r=e.updateQueue;if(null===r)return null;if(r=r.shared,0!=(2&o6)){var a=r.pending;return null===a?t.next=t:(t.next=a.next,a.next=t),r.pending=t,aR(e,n);}return null===(a=r.interleaved)?(t.next=t,aN(r)):(t.next=a.next,a.next=t),r.interleaved=t,aR(e,n);}function aq(e,t,n){if(null!==(t=t.updateQueue)&&(t=t.shared,0!=(4194240&n))){var r=t.lanes;r&=e.pendingLanes,n|=r,t.lanes=n,tm(e,n);}}function aU(e,t){var n=e.updateQueue,r=e.alternate;if(null!==r&&n===(r=r.updateQueue)){var a=null,i=null;if(null!==(n=n.firstBaseUpdate)){do{var o={eventTime:n.eventTime,lane:n.lane,tag:n.tag,payload:n.payload,callback:n.callback,next:null};null===i?a=i=o:i=i.next=o,n=n.next;}while(null!==n)null===i?a=i=t:i=i.next=t;}else a=i=t;n={baseState:r.baseState,firstBaseUpdate:a,lastBaseUpdate:i,shared:r.shared,effects:r.effects},e.updateQueue=n;return;}null===(e=n.lastBaseUpdate)?n.firstBaseUpdate=t:e.next=t,n.lastBaseUpdate=t;}function aW(e,t,n,r){var a=e.updateQueue;aA=!1;var i=a.firstBaseUpdate,o=a.lastBaseUpdate,l=a.shared.pending;if(null!==l){a.shared.pending=null;var u=l,s=u.next;u.next=null,null===o?i=s:o.next=s,o=u;var c=e.alternate;null!==c&&(l=(c=c.updateQueue).lastBaseUpdate)!==o&&(null===l?c.firstBaseUpdate=s:l.next=s,c.lastBaseUpdate=u);}if(null!==i){var f=a.baseState;for(o=0,c=s=u=null,l=i;;){var d=l.lane,h=l.eventTime;if((r&d)===d){null!==c&&(c=c.next={eventTime:h,lane:0,tag:l.tag,payload:l.payload,callback:l.callback,next:null});e:{var p=e,g=l;switch(d=t,h=n,g.tag){case 1:if("function"==typeof(p=g.payload)){f=p.call(h,f,d);break e;}f=p;break e;case 3:p.flags=-65537&p.flags|128;case 0:if(null==(d="function"==typeof(p=g.payload)?p.call(h,f,d):p))break e;f=ee({},f,d);break e;case 2:aA=!0;}}null!==l.callback&&0!==l.lane&&(e.flags|=64,null===(d=a.effects)?a.effects=[l]:d.push(l));}else h={eventTime:h,lane:d,tag:l.tag,payload:l.payload,callback:l.callback,next:null},null===c?(s=c=h,u=f):c=c.next=h,o|=d;if(null===(l=l.next)){if(null===(l=a.shared.pending))break;l=(d=l).next,d.next=null,a.lastBaseUpdate=d,a.shared.pending=null;}}if(null===c&&(u=f),a.baseState=u,a.firstBaseUpdate=s,a.lastBaseUpdate=c,null!==(t=a.shared.interleaved)){a=t;do o|=a.lane,a=a.next;while(a!==t)}else null===i&&(a.shared.lanes=0);la|=o,e.lanes=o,e.memoizedState=f;}}function aV(e,t,n){if(e=t.effects,t.effects=null,null!==e)for(t=0;t<e.length;t++){var r=e[t],a=r.callback;if(null!==a){if(r.callback=null,r=n,"function"!=typeof a)throw Error(k(191,a));a.call(r);}}}var a$=(new x.Component).refs;function aK(e,t,n,r){n=null==(n=n(r,t=e.memoizedState))?t:ee({},t,n),e.memoizedState=n,0===e.lanes&&(e.updateQueue.baseState=n);}var aQ={isMounted:function(e){return!!(e=e._reactInternals)&&e0(e)===e;},enqueueSetState:function(e,t,n){e=e._reactInternals;var r=lS(),a=lk(e),i=aG(r,a);i.payload=t,null!=n&&(i.callback=n),null!==(t=aH(e,i,a))&&(lC(t,e,a,r),aq(t,e,a));},enqueueReplaceState:function(e,t,n){e=e._reactInternals;var r=lS(),a=lk(e),i=aG(r,a);i.tag=1,i.payload=t,null!=n&&(i.callback=n),null!==(t=aH(e,i,a))&&(lC(t,e,a,r),aq(t,e,a));},enqueueForceUpdate:function(e,t){e=e._reactInternals;var n=lS(),r=lk(e),a=aG(n,r);a.tag=2,null!=t&&(a.callback=t),null!==(t=aH(e,a,r))&&(lC(t,e,r,n),aq(t,e,r));}};function aY(e,t,n,r,a,i,o){return"function"==typeof(e=e.stateNode).shouldComponentUpdate?e.shouldComponentUpdate(r,i,o):!t.prototype||!t.prototype.isPureReactComponent||!nA(n,r)||!nA(a,i);}function aX(e,t,n){var r=!1,a=rW,i=t.contextType;return"object"==typeof i&&null!==i?i=aj(i):(a=rY(t)?rK:rV.current,i=(r=null!=(r=t.contextTypes))?rQ(e,a):rW),t=new t(n,i),e.memoizedState=null!==t.state&&void 0!==t.state?t.state:null,t.updater=aQ,e.stateNode=t,t._reactInternals=e,r&&((e=e.stateNode).__reactInternalMemoizedUnmaskedChildContext=a,e.__reactInternalMemoizedMaskedChildContext=i),t;}function aJ(e,t,n,r){e=t.state,"function"==typeof t.componentWillReceiveProps&&t.componentWillReceiveProps(n,r),"function"==typeof t.UNSAFE_componentWillReceiveProps&&t.UNSAFE_componentWillReceiveProps(n,r),t.state!==e&&aQ.enqueueReplaceState(t,t.state,null);}function aZ(e,t,n,r){var a=e.stateNode;a.props=n,a.state=e.memoizedState,a.refs=a$,aI(e);var i=t.contextType;"object"==typeof i&&null!==i?a.context=aj(i):(i=rY(t)?rK:rV.current,a.context=rQ(e,i)),a.state=e.memoizedState,"function"==typeof(i=t.getDerivedStateFromProps)&&(aK(e,t,i,n),a.state=e.memoizedState),"function"==typeof t.getDerivedStateFromProps||"function"==typeof a.getSnapshotBeforeUpdate||"function"!=typeof a.UNSAFE_componentWillMount&&"function"!=typeof a.componentWillMount||(t=a.state,"function"==typeof a.componentWillMount&&a.componentWillMount(),"function"==typeof a.UNSAFE_componentWillMount&&a.UNSAFE_componentWillMount(),t!==a.state&&aQ.enqueueReplaceState(a,a.state,null),aW(e,n,a,r),a.state=e.memoizedState),"function"==typeof a.componentDidMount&&(e.flags|=4194308);}function a0(e,t,n){if(null!==(e=n.ref)&&"function"!=typeof e&&"object"!=typeof e){if(n._owner){if(n=n._owner){if(1!==n.tag)throw Error(k(309));var r=n.stateNode;}if(!r)throw Error(k(147,e));var a=r,i=""+e;return null!==t&&null!==t.ref&&"function"==typeof t.ref&&t.ref._stringRef===i?t.ref:((t=function(e){var t=a.refs;t===a$&&(t=a.refs={}),null===e?delete t[i]:t[i]=e;})._stringRef=i,t);}if("string"!=typeof e)throw Error(k(284));if(!n._owner)throw Error(k(290,e));}return e;}function a1(e,t){throw Error(k(31,"[object Object]"===(e=Object.prototype.toString.call(t))?"object with keys {"+Object.keys(t).join(", ")+"}":e));}function a2(e){return(0,e._init)(e._payload);}function a3(e){function t(t,n){if(e){var r=t.deletions;null===r?(t.deletions=[n],t.flags|=16):r.push(n);}}function n(n,r){if(!e)return null;for(;null!==r;)t(n,r),r=r.sibling;return null;}function r(e,t){for(e=new Map;null!==t;)null!==t.key?e.set(t.key,t):e.set(t.index,t),t=t.sibling;return e;}function a(e,t){return(e=lJ(e,t)).index=0,e.sibling=null,e;}function i(t,n,r){return(t.index=r,e)?null!==(r=t.alternate)?(r=r.index)<n?(t.flags|=2,n):r:(t.flags|=2,n):(t.flags|=1048576,n);}function o(t){return e&&null===t.alternate&&(t.flags|=2),t;}function l(e,t,n,r){return null===t||6!==t.tag?(t=l2(n,e.mode,r)).return=e:(t=a(t,n)).return=e,t;}function u(e,t,n,r){var i=n.type;return i===G?c(e,t,n.props.children,r,n.key):(null!==t&&(t.elementType===i||"object"==typeof i&&null!==i&&i.$$typeof===Y&&a2(i)===t.type)?(r=a(t,n.props)).ref=a0(e,t,n):(r=lZ(n.type,n.key,n.props,null,e.mode,r)).ref=a0(e,t,n),r.return=e,r);}function s(e,t,n,r){return null===t||4!==t.tag||t.stateNode.containerInfo!==n.containerInfo||t.stateNode.implementation!==n.implementation?(t=l3(n,e.mode,r)).return=e:(t=a(t,n.children||[])).return=e,t;}function c(e,t,n,r,i){return null===t||7!==t.tag?(t=l0(n,e.mode,r,i)).return=e:(t=a(t,n)).return=e,t;}function f(e,t,n){if("string"==typeof t&&""!==t||"number"==typeof t)return(t=l2(""+t,e.mode,n)).return=e,t;if("object"==typeof t&&null!==t){switch(t.$$typeof){case I:return(n=lZ(t.type,t.key,t.props,null,e.mode,n)).ref=a0(e,null,t),n.return=e,n;case B:return(t=l3(t,e.mode,n)).return=e,t;case Y:return f(e,(0,t._init)(t._payload),n);}if(eg(t)||Z(t))return(t=l0(t,e.mode,n,null)).return=e,t;a1(e,t);}return null;}function d(e,t,n,r){var a=null!==t?t.key:null;if("string"==typeof n&&""!==n||"number"==typeof n)return null!==a?null:l(e,t,""+n,r);if("object"==typeof n&&null!==n){switch(n.$$typeof){case I:return n.key===a?u(e,t,n,r):null;case B:return n.key===a?s(e,t,n,r):null;case Y:return d(e,t,(a=n._init)(n._payload),r);}if(eg(n)||Z(n))return null!==a?null:c(e,t,n,r,null);a1(e,n);}return null;}function h(e,t,n,r,a){if("string"==typeof r&&""!==r||"number"==typeof r)return l(t,e=e.get(n)||null,""+r,a);if("object"==typeof r&&null!==r){switch(r.$$typeof){case I:return u(t,e=e.get(null===r.key?n:r.key)||null,r,a);case B:return s(t,e=e.get(null===r.key?n:r.key)||null,r,a);case Y:return h(e,t,n,(0,r._init)(r._payload),a);}if(eg(r)||Z(r))return c(t,e=e.get(n)||null,r,a,null);a1(t,r);}return null;}return function l(u,s,c,p){if("object"==typeof c&&null!==c&&c.type===G&&null===c.key&&(c=c.props.children),"object"==typeof c&&null!==c){switch(c.$$typeof){case I:e:{for(var g=c.key,v=s;null!==v;){if(v.key===g){if((g=c.type)===G){if(7===v.tag){n(u,v.sibling),(s=a(v,c.props.children)).return=u,u=s;break e;}}else if(v.elementType===g||"object"==typeof g&&null!==g&&g.$$typeof===Y&&a2(g)===v.type){n(u,v.sibling),(s=a(v,c.props)).ref=a0(u,v,c),s.return=u,u=s;break e;}n(u,v);break;}t(u,v),v=v.sibling;}c.type===G?((s=l0(c.props.children,u.mode,p,c.key)).return=u,u=s):((p=lZ(c.type,c.key,c.props,null,u.mode,p)).ref=a0(u,s,c),p.return=u,u=p);}return o(u);case B:e:{for(v=c.key;null!==s;){if(s.key===v){if(4===s.tag&&s.stateNode.containerInfo===c.containerInfo&&s.stateNode.implementation===c.implementation){n(u,s.sibling),(s=a(s,c.children||[])).return=u,u=s;break e;}n(u,s);break;}t(u,s),s=s.sibling;}(s=l3(c,u.mode,p)).return=u,u=s;}return o(u);case Y:return l(u,s,(v=c._init)(c._payload),p);}if(eg(c))return function(a,o,l,u){for(var s=null,c=null,p=o,g=o=0,v=null;null!==p&&g<l.length;g++){p.index>g?(v=p,p=null):v=p.sibling;var b=d(a,p,l[g],u);if(null===b){null===p&&(p=v);break;}e&&p&&null===b.alternate&&t(a,p),o=i(b,o,g),null===c?s=b:c.sibling=b,c=b,p=v;}if(g===l.length)return n(a,p),ad&&ao(a,g),s;if(null===p){for(;g<l.length;g++)null!==(p=f(a,l[g],u))&&(o=i(p,o,g),null===c?s=p:c.sibling=p,c=p);return ad&&ao(a,g),s;}for(p=r(a,p);g<l.length;g++)null!==(v=h(p,a,g,l[g],u))&&(e&&null!==v.alternate&&p.delete(null===v.key?g:v.key),o=i(v,o,g),null===c?s=v:c.sibling=v,c=v);return e&&p.forEach(function(e){return t(a,e);}),ad&&ao(a,g),s;}(u,s,c,p);if(Z(c))return function(a,o,l,u){var s=Z(l);if("function"!=typeof s)throw Error(k(150));if(null==(l=s.call(l)))throw Error(k(151));for(var c=s=null,p=o,g=o=0,v=null,b=l.next();null!==p&&!b.done;g++,b=l.next()){p.index>g?(v=p,p=null):v=p.sibling;var m=d(a,p,b.value,u);if(null===m){null===p&&(p=v);break;}e&&p&&null===m.alternate&&t(a,p),o=i(m,o,g),null===c?s=m:c.sibling=m,c=m,p=v;}if(b.done)return n(a,p),ad&&ao(a,g),s;if(null===p){for(;!b.done;g++,b=l.next())null!==(b=f(a,b.value,u))&&(o=i(b,o,g),null===c?s=b:c.sibling=b,c=b);return ad&&ao(a,g),s;}for(p=r(a,p);!b.done;g++,b=l.next())null!==(b=h(p,a,g,b.value,u))&&(e&&null!==b.alternate&&p.delete(null===b.key?g:b.key),o=i(b,o,g),null===c?s=b:c.sibling=b,c=b);return e&&p.forEach(function(e){return t(a,e);}),ad&&ao(a,g),s;}(u,s,c,p);a1(u,c);}return"string"==typeof c&&""!==c||"number"==typeof c?(c=""+c,null!==s&&6===s.tag?(n(u,s.sibling),(s=a(s,c)).return=u):(n(u,s),(s=l2(c,u.mode,p)).return=u),o(u=s)):n(u,s);};}var a5=a3(!0),a4=a3(!1),a6={},a8=rH(a6),a9=rH(a6),a7=rH(a6);function ie(e){if(e===a6)throw Error(k(174));return e;}function it(e,t){switch(rU(a7,t),rU(a9,e),rU(a8,a6),e=t.nodeType){case 9:case 11:t=(t=t.documentElement)?t.namespaceURI:eS(null,"");break;default:t=eS(t=(e=8===e?t.parentNode:t).namespaceURI||null,e=e.tagName);}rq(a8),rU(a8,t);}function ir(){rq(a8),rq(a9),rq(a7);}function ia(e){ie(a7.current);var t=ie(a8.current),n=eS(t,e.type);t!==n&&(rU(a9,e),rU(a8,n));}function ii(e){a9.current===e&&(rq(a8),rq(a9));}var io=rH(0);function il(e){for(var t=e;null!==t;){if(13===t.tag){var n=t.memoizedState;if(null!==n&&(null===(n=n.dehydrated)||"$?"===n.data||"$!"===n.data))return t;}else if(19===t.tag&&void 0!==t.memoizedProps.revealOrder){if(0!=(128&t.flags))return t;}else if(null!==t.child){t.child.return=t,t=t.child;continue;}if(t===e)break;for(;null===t.sibling;){if(null===t.return||t.return===e)return null;t=t.return;}t.sibling.return=t.return,t=t.sibling;}return null;}var iu=[];function is(){for(var e=0;e<iu.length;e++)iu[e]._workInProgressVersionPrimary=null;iu.length=0;}var ic=A.ReactCurrentDispatcher,id=A.ReactCurrentBatchConfig,ih=0,ip=null,ig=null,iv=null,ib=!1,im=!1,iy=0,iw=0;function ix(){throw Error(k(321));}function iS(e,t){if(null===t)return!1;for(var n=0;n<t.length&&n<e.length;n++)if(!nR(e[n],t[n]))return!1;return!0;}function ik(e,t,n,r,a,i){if(ih=i,ip=t,t.memoizedState=null,t.updateQueue=null,t.lanes=0,ic.current=null===e||null===e.memoizedState?i6:i8,e=n(r,a),im){i=0;do{if(im=!1,iy=0,25<=i)throw Error(k(301));i+=1,iv=ig=null,t.updateQueue=null,ic.current=i9,e=n(r,a);}while(im)}if(ic.current=i4,t=null!==ig&&null!==ig.next,ih=0,iv=ig=ip=null,ib=!1,t)throw Error(k(300));return e;}function iC(){var e=0!==iy;return iy=0,e;}function iT(){var e={memoizedState:null,baseState:null,baseQueue:null,queue:null,next:null};return null===iv?ip.memoizedState=iv=e:iv=iv.next=e,iv;}function iz(){if(null===ig){var e=ip.alternate;e=null!==e?e.memoizedState:null;}else e=ig.next;var t=null===iv?ip.memoizedState:iv.next;if(null!==t)iv=t,ig=e;else{if(null===e)throw Error(k(310));e={memoizedState:(ig=e).memoizedState,baseState:ig.baseState,baseQueue:ig.baseQueue,queue:ig.queue,next:null},null===iv?ip.memoizedState=iv=e:iv=iv.next=e;}return iv;}function iE(e,t){return"function"==typeof t?t(e):t;}function iM(e){var t=iz(),n=t.queue;if(null===n)throw Error(k(311));n.lastRenderedReducer=e;var r=ig,a=r.baseQueue,i=n.pending;if(null!==i){if(null!==a){var o=a.next;a.next=i.next,i.next=o;}r.baseQueue=a=i,n.pending=null;}if(null!==a){i=a.next,r=r.baseState;var l=o=null,u=null,s=i;do{var c=s.lane;if((ih&c)===c)null!==u&&(u=u.next={lane:0,action:s.action,hasEagerState:s.hasEagerState,eagerState:s.eagerState,next:null}),r=s.hasEagerState?s.eagerState:e(r,s.action);else{var f={lane:c,action:s.action,hasEagerState:s.hasEagerState,eagerState:s.eagerState,next:null};null===u?(l=u=f,o=r):u=u.next=f,ip.lanes|=c,la|=c;}s=s.next;}while(null!==s&&s!==i)null===u?o=r:u.next=l,nR(r,t.memoizedState)||(os=!0),t.memoizedState=r,t.baseState=o,t.baseQueue=u,n.lastRenderedState=r;}if(null!==(e=n.interleaved)){a=e;do i=a.lane,ip.lanes|=i,la|=i,a=a.next;while(a!==e)}else null===a&&(n.lanes=0);return[t.memoizedState,n.dispatch];}function iP(e){var t=iz(),n=t.queue;if(null===n)throw Error(k(311));n.lastRenderedReducer=e;var r=n.dispatch,a=n.pending,i=t.memoizedState;if(null!==a){n.pending=null;var o=a=a.next;do i=e(i,o.action),o=o.next;while(o!==a)nR(i,t.memoizedState)||(os=!0),t.memoizedState=i,null===t.baseQueue&&(t.baseState=i),n.lastRenderedState=i;}return[i,r];}function iL(){}function i_(e,t){var n=ip,r=iz(),a=t(),i=!nR(r.memoizedState,a);if(i&&(r.memoizedState=a,os=!0),r=r.queue,iq(iF.bind(null,n,r,e),[e]),r.getSnapshot!==t||i||null!==iv&&1&iv.memoizedState.tag){if(n.flags|=2048,iA(9,ij.bind(null,n,r,a,t),void 0,null),null===o8)throw Error(k(349));0!=(30&ih)||iD(n,t,a);}return a;}function iD(e,t,n){e.flags|=16384,e={getSnapshot:t,value:n},null===(t=ip.updateQueue)?(t={lastEffect:null,stores:null},ip.updateQueue=t,t.stores=[e]):null===(n=t.stores)?t.stores=[e]:n.push(e);}function ij(e,t,n,r){t.value=n,t.getSnapshot=r,iN(t)&&iO(e);}function iF(e,t,n){return n(function(){iN(t)&&iO(e);});}function iN(e){var t=e.getSnapshot;e=e.value;try{var n=t();return!nR(e,n);}catch(e){return!0;}}function iO(e){var t=aR(e,1);null!==t&&lC(t,e,1,-1);}function iR(e){var t=iT();return"function"==typeof e&&(e=e()),t.memoizedState=t.baseState=e,e={pending:null,interleaved:null,lanes:0,dispatch:null,lastRenderedReducer:iE,lastRenderedState:e},t.queue=e,e=e.dispatch=i1.bind(null,ip,e),[t.memoizedState,e];}function iA(e,t,n,r){return e={tag:e,create:t,destroy:n,deps:r,next:null},null===(t=ip.updateQueue)?(t={lastEffect:null,stores:null},ip.updateQueue=t,t.lastEffect=e.next=e):null===(n=t.lastEffect)?t.lastEffect=e.next=e:(r=n.next,n.next=e,e.next=r,t.lastEffect=e),e;}function iI(){return iz().memoizedState;}function iB(e,t,n,r){var a=iT();ip.flags|=e,a.memoizedState=iA(1|t,n,void 0,void 0===r?null:r);}function iG(e,t,n,r){var a=iz();r=void 0===r?null:r;var i=void 0;if(null!==ig){var o=ig.memoizedState;if(i=o.destroy,null!==r&&iS(r,o.deps)){a.memoizedState=iA(t,n,i,r);return;}}ip.flags|=e,a.memoizedState=iA(1|t,n,i,r);}function iH(e,t){return iB(8390656,8,e,t);}function iq(e,t){return iG(2048,8,e,t);}function iU(e,t){return iG(4,2,e,t);}function iW(e,t){return iG(4,4,e,t);}function iV(e,t){return"function"==typeof t?(t(e=e()),function(){t(null);}):null!=t?(e=e(),t.current=e,function(){t.current=null;}):void 0;}function i$(e,t,n){return n=null!=n?n.concat([e]):null,iG(4,4,iV.bind(null,t,e),n);}function iK(){}function iQ(e,t){var n=iz();t=void 0===t?null:t;var r=n.memoizedState;return null!==r&&null!==t&&iS(t,r[1])?r[0]:(n.memoizedState=[e,t],e);}function iY(e,t){var n=iz();t=void 0===t?null:t;var r=n.memoizedState;return null!==r&&null!==t&&iS(t,r[1])?r[0]:(e=e(),n.memoizedState=[e,t],e);}function iX(e,t,n){return 0==(21&ih)?(e.baseState&&(e.baseState=!1,os=!0),e.memoizedState=n):(nR(n,t)||(n=tg(),ip.lanes|=n,la|=n,e.baseState=!0),t);}function iJ(e,t){var n=ty;ty=0!==n&&4>n?n:4,e(!0);var r=id.transition;id.transition={};try{e(!1),t();}finally{ty=n,id.transition=r;}}function iZ(){return iz().memoizedState;}function i0(e,t,n){var r=lk(e);n={lane:r,action:n,hasEagerState:!1,eagerState:null,next:null},i2(e)?i3(t,n):null!==(n=aO(e,t,n,r))&&(lC(n,e,r,lS()),i5(n,t,r));}function i1(e,t,n){var r=lk(e),a={lane:r,action:n,hasEagerState:!1,eagerState:null,next:null};if(i2(e))i3(t,a);else{var i=e.alternate;if(0===e.lanes&&(null===i||0===i.lanes)&&null!==(i=t.lastRenderedReducer))try{var o=t.lastRenderedState,l=i(o,n);if(a.hasEagerState=!0,a.eagerState=l,nR(l,o)){var u=t.interleaved;null===u?(a.next=a,aN(t)):(a.next=u.next,u.next=a),t.interleaved=a;return;}}catch(e){}finally{}null!==(n=aO(e,t,a,r))&&(lC(n,e,r,a=lS()),i5(n,t,r));}}function i2(e){var t=e.alternate;return e===ip||null!==t&&t===ip;}function i3(e,t){im=ib=!0;var n=e.pending;null===n?t.next=t:(t.next=n.next,n.next=t),e.pending=t;}function i5(e,t,n){if(0!=(4194240&n)){var r=t.lanes;r&=e.pendingLanes,n|=r,t.lanes=n,tm(e,n);}}var i4={readContext:aj,useCallback:ix,useContext:ix,useEffect:ix,useImperativeHandle:ix,useInsertionEffect:ix,useLayoutEffect:ix,useMemo:ix,useReducer:ix,useRef:ix,useState:ix,useDebugValue:ix,useDeferredValue:ix,useTransition:ix,useMutableSource:ix,useSyncExternalStore:ix,useId:ix,unstable_isNewReconciler:!1},i6={readContext:aj,useCallback:function(e,t){return iT().memoizedState=[e,void 0===t?null:t],e;},useContext:aj,useEffect:iH,useImperativeHandle:function(e,t,n){return n=null!=n?n.concat([e]):null,iB(4194308,4,iV.bind(null,t,e),n);},useLayoutEffect:function(e,t){return iB(4194308,4,e,t);},useInsertionEffect:function(e,t){return iB(4,2,e,t);},useMemo:function(e,t){var n=iT();return t=void 0===t?null:t,e=e(),n.memoizedState=[e,t],e;},useReducer:function(e,t,n){var r=iT();return t=void 0!==n?n(t):t,r.memoizedState=r.baseState=t,e={pending:null,interleaved:null,lanes:0,dispatch:null,lastRenderedReducer:e,lastRenderedState:t},r.queue=e,e=e.dispatch=i0.bind(null,ip,e),[r.memoizedState,e];},useRef:function(e){return e={current:e},iT().memoizedState=e;},useState:iR,useDebugValue:iK,useDeferredValue:function(e){return iT().memoizedState=e;},useTransition:function(){var e=iR(!1),t=e[0];return e=iJ.bind(null,e[1]),iT().memoizedState=e,[t,e];},useMutableSource:function(){},useSyncExternalStore:function(e,t,n){var r=ip,a=iT();if(ad){if(void 0===n)throw Error(k(407));n=n();}else{if(n=t(),null===o8)throw Error(k(349));0!=(30&ih)||iD(r,t,n);}a.memoizedState=n;var i={value:n,getSnapshot:t};return a.queue=i,iH(iF.bind(null,r,i,e),[e]),r.flags|=2048,iA(9,ij.bind(null,r,i,n,t),void 0,null),n;},useId:function(){var e=iT(),t=o8.identifierPrefix;if(ad){var n=ai,r=aa;t=":"+t+"R"+(n=(r&~(1<<32-tl(r)-1)).toString(32)+n),0<(n=iy++)&&(t+="H"+n.toString(32)),t+=":";}else t=":"+t+"r"+(n=iw++).toString(32)+":";return e.memoizedState=t;},unstable_isNewReconciler:!1},i8={readContext:aj,useCallback:iQ,useContext:aj,useEffect:iq,useImperativeHandle:i$,useInsertionEffect:iU,useLayoutEffect:iW,useMemo:iY,useReducer:iM,useRef:iI,useState:function(){return iM(iE);},useDebugValue:iK,useDeferredValue:function(e){return iX(iz(),ig.memoizedState,e);},useTransition:function(){return[iM(iE)[0],iz().memoizedState];},useMutableSource:iL,useSyncExternalStore:i_,useId:iZ,unstable_isNewReconciler:!1},i9={readContext:aj,useCallback:iQ,useContext:aj,useEffect:iq,useImperativeHandle:i$,useInsertionEffect:iU,useLayoutEffect:iW,useMemo:iY,useReducer:iP,useRef:iI,useState:function(){return iP(iE);},useDebugValue:iK,useDeferredValue:function(e){var t=iz();return null===ig?t.memoizedState=e:iX(t,ig.memoizedState,e);},useTransition:function(){return[iP(iE)[0],iz().memoizedState];},useMutableSource:iL,useSyncExternalStore:i_,useId:iZ,unstable_isNewReconciler:!1};function i7(e,t){try{var n="",r=t;do n+=function(e){switch(e.tag){case 5:return et(e.type);case 16:return et("Lazy");case 13:return et("Suspense");case 19:return et("SuspenseList");case 0:case 2:case 15:return e=er(e.type,!1);case 11:return e=er(e.type.render,!1);case 1:return e=er(e.type,!0);default:return"";}}(r),r=r.return;while(r)var a=n;}catch(e){a="\nError generating stack: "+e.message+"\n"+e.stack;}return{value:e,source:t,stack:a,digest:null};}function oe(e,t,n){return{value:e,source:null,stack:null!=n?n:null,digest:null!=t?t:null};}function ot(e,t){try{console.error(t.value);}catch(e){setTimeout(function(){throw e;});}}var on="function"==typeof WeakMap?WeakMap:Map;function or(e,t,n){(n=aG(-1,n)).tag=3,n.payload={element:null};var r=t.value;return n.callback=function(){ld||(ld=!0,lh=r),ot(e,t);},n;}function oa(e,t,n){(n=aG(-1,n)).tag=3;var r=e.type.getDerivedStateFromError;if("function"==typeof r){var a=t.value;n.payload=function(){return r(a);},n.callback=function(){ot(e,t);};}var i=e.stateNode;return null!==i&&"function"==typeof i.componentDidCatch&&(n.callback=function(){ot(e,t),"function"!=typeof r&&(null===lp?lp=new Set([this]):lp.add(this));var n=t.stack;this.componentDidCatch(t.value,{componentStack:null!==n?n:""});}),n;}function oi(e,t,n){var r=e.pingCache;if(null===r){r=e.pingCache=new on;var a=new Set;r.set(t,a);}else void 0===(a=r.get(t))&&(a=new Set,r.set(t,a));a.has(n)||(a.add(n),e=lW.bind(null,e,t,n),t.then(e,e));}function oo(e){do{var t;if((t=13===e.tag)&&(t=null===(t=e.memoizedState)||null!==t.dehydrated),t)return e;e=e.return;}while(null!==e)return null;}function ol(e,t,n,r,a){return 0==(1&e.mode)?e===t?e.flags|=65536:(e.flags|=128,n.flags|=131072,n.flags&=-52805,1===n.tag&&(null===n.alternate?n.tag=17:((t=aG(-1,1)).tag=2,aH(n,t,1))),n.lanes|=1):(e.flags|=65536,e.lanes=a),e;}var ou=A.ReactCurrentOwner,os=!1;function oc(e,t,n,r){t.child=null===e?a4(t,null,n,r):a5(t,e.child,n,r);}function of(e,t,n,r,a){n=n.render;var i=t.ref;return(aD(t,a),r=ik(e,t,n,r,i,a),n=iC(),null===e||os)?(ad&&n&&au(t),t.flags|=1,oc(e,t,r,a),t.child):(t.updateQueue=e.updateQueue,t.flags&=-2053,e.lanes&=~a,oL(e,t,a));}function od(e,t,n,r,a){if(null===e){var i=n.type;return"function"!=typeof i||lX(i)||void 0!==i.defaultProps||null!==n.compare||void 0!==n.defaultProps?((e=lZ(n.type,null,r,t,t.mode,a)).ref=t.ref,e.return=t,t.child=e):(t.tag=15,t.type=i,oh(e,t,i,r,a));}if(i=e.child,0==(e.lanes&a)){var o=i.memoizedProps;if((n=null!==(n=n.compare)?n:nA)(o,r)&&e.ref===t.ref)return oL(e,t,a);}return t.flags|=1,(e=lJ(i,r)).ref=t.ref,e.return=t,t.child=e;}function oh(e,t,n,r,a){if(null!==e){var i=e.memoizedProps;if(nA(i,r)&&e.ref===t.ref){if(os=!1,t.pendingProps=r=i,0==(e.lanes&a))return t.lanes=e.lanes,oL(e,t,a);0!=(131072&e.flags)&&(os=!0);}}return ov(e,t,n,r,a);}function op(e,t,n){var r=t.pendingProps,a=r.children,i=null!==e?e.memoizedState:null;if("hidden"===r.mode){if(0==(1&t.mode))t.memoizedState={baseLanes:0,cachePool:null,transitions:null},rU(lt,le),le|=n;else{if(0==(1073741824&n))return e=null!==i?i.baseLanes|n:n,t.lanes=t.childLanes=1073741824,t.memoizedState={baseLanes:e,cachePool:null,transitions:null},t.updateQueue=null,rU(lt,le),le|=e,null;t.memoizedState={baseLanes:0,cachePool:null,transitions:null},r=null!==i?i.baseLanes:n,rU(lt,le),le|=r;}}else null!==i?(r=i.baseLanes|n,t.memoizedState=null):r=n,rU(lt,le),le|=r;return oc(e,t,a,n),t.child;}function og(e,t){var n=t.ref;(null===e&&null!==n||null!==e&&e.ref!==n)&&(t.flags|=512,t.flags|=2097152);}function ov(e,t,n,r,a){var i=rY(n)?rK:rV.current;return(i=rQ(t,i),aD(t,a),n=ik(e,t,n,r,i,a),r=iC(),null===e||os)?(ad&&r&&au(t),t.flags|=1,oc(e,t,n,a),t.child):(t.updateQueue=e.updateQueue,t.flags&=-2053,e.lanes&=~a,oL(e,t,a));}function ob(e,t,n,r,a){if(rY(n)){var i=!0;r0(t);}else i=!1;if(aD(t,a),null===t.stateNode)oP(e,t),aX(t,n,r),aZ(t,n,r,a),r=!0;else if(null===e){var o=t.stateNode,l=t.memoizedProps;o.props=l;var u=o.context,s=n.contextType;s="object"==typeof s&&null!==s?aj(s):rQ(t,s=rY(n)?rK:rV.current);var c=n.getDerivedStateFromProps,f="function"==typeof c||"function"==typeof o.getSnapshotBeforeUpdate;f||"function"!=typeof o.UNSAFE_componentWillReceiveProps&&"function"!=typeof o.componentWillReceiveProps||(l!==r||u!==s)&&aJ(t,o,r,s),aA=!1;var d=t.memoizedState;o.state=d,aW(t,r,o,a),u=t.memoizedState,l!==r||d!==u||r$.current||aA?("function"==typeof c&&(aK(t,n,c,r),u=t.memoizedState),(l=aA||aY(t,n,l,r,d,u,s))?(f||"function"!=typeof o.UNSAFE_componentWillMount&&"function"!=typeof o.componentWillMount||("function"==typeof o.componentWillMount&&o.componentWillMount(),"function"==typeof o.UNSAFE_componentWillMount&&o.UNSAFE_componentWillMount()),"function"==typeof o.componentDidMount&&(t.flags|=4194308)):("function"==typeof o.componentDidMount&&(t.flags|=4194308),t.memoizedProps=r,t.memoizedState=u),o.props=r,o.state=u,o.context=s,r=l):("function"==typeof o.componentDidMount&&(t.flags|=4194308),r=!1);}else{o=t.stateNode,aB(e,t),l=t.memoizedProps,s=t.type===t.elementType?l:aC(t.type,l),o.props=s,f=t.pendingProps,d=o.context,u="object"==typeof(u=n.contextType)&&null!==u?aj(u):rQ(t,u=rY(n)?rK:rV.current);var h=n.getDerivedStateFromProps;(c="function"==typeof h||"function"==typeof o.getSnapshotBeforeUpdate)||"function"!=typeof o.UNSAFE_componentWillReceiveProps&&"function"!=typeof o.componentWillReceiveProps||(l!==f||d!==u)&&aJ(t,o,r,u),aA=!1,d=t.memoizedState,o.state=d,aW(t,r,o,a);var p=t.memoizedState;l!==f||d!==p||r$.current||aA?("function"==typeof h&&(aK(t,n,h,r),p=t.memoizedState),(s=aA||aY(t,n,s,r,d,p,u)||!1)?(c||"function"!=typeof o.UNSAFE_componentWillUpdate&&"function"!=typeof o.componentWillUpdate||("function"==typeof o.componentWillUpdate&&o.componentWillUpdate(r,p,u),"function"==typeof o.UNSAFE_componentWillUpdate&&o.UNSAFE_componentWillUpdate(r,p,u)),"function"==typeof o.componentDidUpdate&&(t.flags|=4),"function"==typeof o.getSnapshotBeforeUpdate&&(t.flags|=1024)):("function"!=typeof o.componentDidUpdate||l===e.memoizedProps&&d===e.memoizedState||(t.flags|=4),"function"!=typeof o.getSnapshotBeforeUpdate||l===e.memoizedProps&&d===e.memoizedState||(t.flags|=1024),t.memoizedProps=r,t.memoizedState=p),o.props=r,o.state=p,o.context=u,r=s):("function"!=typeof o.componentDidUpdate||l===e.memoizedProps&&d===e.memoizedState||(t.flags|=4),"function"!=typeof o.getSnapshotBeforeUpdate||l===e.memoizedProps&&d===e.memoizedState||(t.flags|=1024),r=!1);}return om(e,t,n,r,i,a);}function om(e,t,n,r,a,i){og(e,t);var o=0!=(128&t.flags);if(!r&&!o)return a&&r1(t,n,!1),oL(e,t,i);r=t.stateNode,ou.current=t;var l=o&&"function"!=typeof n.getDerivedStateFromError?null:r.render();return t.flags|=1,null!==e&&o?(t.child=a5(t,e.child,null,i),t.child=a5(t,null,l,i)):oc(e,t,l,i),t.memoizedState=r.state,a&&r1(t,n,!0),t.child;}function oy(e){var t=e.stateNode;t.pendingContext?rJ(e,t.pendingContext,t.pendingContext!==t.context):t.context&&rJ(e,t.context,!1),it(e,t.containerInfo);}function ow(e,t,n,r,a){return ax(),aS(a),t.flags|=256,oc(e,t,n,r),t.child;}var ox={dehydrated:null,treeContext:null,retryLane:0};function oS(e){return{baseLanes:e,cachePool:null,transitions:null};}function ok(e,t,n){var r,a=t.pendingProps,i=io.current,o=!1,l=0!=(128&t.flags);if((r=l)||(r=(null===e||null!==e.memoizedState)&&0!=(2&i)),r?(o=!0,t.flags&=-129):(null===e||null!==e.memoizedState)&&(i|=1),rU(io,1&i),null===e)return(ab(t),null!==(e=t.memoizedState)&&null!==(e=e.dehydrated))?(0==(1&t.mode)?t.lanes=1:"$!"===e.data?t.lanes=8:t.lanes=1073741824,null):(l=a.children,e=a.fallback,o?(a=t.mode,o=t.child,l={mode:"hidden",children:l},0==(1&a)&&null!==o?(o.childLanes=0,o.pendingProps=l):o=l1(l,a,0,null),e=l0(e,a,n,null),o.return=t,e.return=t,o.sibling=e,t.child=o,t.child.memoizedState=oS(n),t.memoizedState=ox,e):oC(t,l));if(null!==(i=e.memoizedState)&&null!==(r=i.dehydrated))return function(e,t,n,r,a,i,o){if(n)return 256&t.flags?(t.flags&=-257,oT(e,t,o,r=oe(Error(k(422))))):null!==t.memoizedState?(t.child=e.child,t.flags|=128,null):(i=r.fallback,a=t.mode,r=l1({mode:"visible",children:r.children},a,0,null),i=l0(i,a,o,null),i.flags|=2,r.return=t,i.return=t,r.sibling=i,t.child=r,0!=(1&t.mode)&&a5(t,e.child,null,o),t.child.memoizedState=oS(o),t.memoizedState=ox,i);if(0==(1&t.mode))return oT(e,t,o,null);if("$!"===a.data){if(r=a.nextSibling&&a.nextSibling.dataset)var l=r.dgst;return r=l,oT(e,t,o,r=oe(i=Error(k(419)),r,void 0));}if(l=0!=(o&e.childLanes),os||l){if(null!==(r=o8)){switch(o&-o){case 4:a=2;break;case 16:a=8;break;case 64:case 128:case 256:case 512:case 1024:case 2048:case 4096:case 8192:case 16384:case 32768:case 65536:case 131072:case 262144:case 524288:case 1048576:case 2097152:case 4194304:case 8388608:case 16777216:case 33554432:case 67108864:a=32;break;case 536870912:a=268435456;break;default:a=0;}0!==(a=0!=(a&(r.suspendedLanes|o))?0:a)&&a!==i.retryLane&&(i.retryLane=a,aR(e,a),lC(r,e,a,-1));}return lR(),oT(e,t,o,r=oe(Error(k(421))));}return"$?"===a.data?(t.flags|=128,t.child=e.child,t=l$.bind(null,e),a._reactRetry=t,null):(e=i.treeContext,af=rE(a.nextSibling),ac=t,ad=!0,ah=null,null!==e&&(at[an++]=aa,at[an++]=ai,at[an++]=ar,aa=e.id,ai=e.overflow,ar=t),t=oC(t,r.children),t.flags|=4096,t);}(e,t,l,a,r,i,n);if(o){o=a.fallback,l=t.mode,r=(i=e.child).sibling;var u={mode:"hidden",children:a.children};return 0==(1&l)&&t.child!==i?((a=t.child).childLanes=0,a.pendingProps=u,t.deletions=null):(a=lJ(i,u)).subtreeFlags=14680064&i.subtreeFlags,null!==r?o=lJ(r,o):(o=l0(o,l,n,null),o.flags|=2),o.return=t,a.return=t,a.sibling=o,t.child=a,a=o,o=t.child,l=null===(l=e.child.memoizedState)?oS(n):{baseLanes:l.baseLanes|n,cachePool:null,transitions:l.transitions},o.memoizedState=l,o.childLanes=e.childLanes&~n,t.memoizedState=ox,a;}return e=(o=e.child).sibling,a=lJ(o,{mode:"visible",children:a.children}),0==(1&t.mode)&&(a.lanes=n),a.return=t,a.sibling=null,null!==e&&(null===(n=t.deletions)?(t.deletions=[e],t.flags|=16):n.push(e)),t.child=a,t.memoizedState=null,a;}function oC(e,t){return(t=l1({mode:"visible",children:t},e.mode,0,null)).return=e,e.child=t;}function oT(e,t,n,r){return null!==r&&aS(r),a5(t,e.child,null,n),e=oC(t,t.pendingProps.children),e.flags|=2,t.memoizedState=null,e;}function oz(e,t,n){e.lanes|=t;var r=e.alternate;null!==r&&(r.lanes|=t),a_(e.return,t,n);}function oE(e,t,n,r,a){var i=e.memoizedState;null===i?e.memoizedState={isBackwards:t,rendering:null,renderingStartTime:0,last:r,tail:n,tailMode:a}:(i.isBackwards=t,i.rendering=null,i.renderingStartTime=0,i.last=r,i.tail=n,i.tailMode=a);}function oM(e,t,n){var r=t.pendingProps,a=r.revealOrder,i=r.tail;if(oc(e,t,r.children,n),0!=(2&(r=io.current)))r=1&r|2,t.flags|=128;else{if(null!==e&&0!=(128&e.flags))e:for(e=t.child;null!==e;){if(13===e.tag)null!==e.memoizedState&&oz(e,n,t);else if(19===e.tag)oz(e,n,t);else if(null!==e.child){e.child.return=e,e=e.child;continue;}if(e===t)break;for(;null===e.sibling;){if(null===e.return||e.return===t)break e;e=e.return;}e.sibling.return=e.return,e=e.sibling;}r&=1;}if(rU(io,r),0==(1&t.mode))t.memoizedState=null;else switch(a){case"forwards":for(a=null,n=t.child;null!==n;)null!==(e=n.alternate)&&null===il(e)&&(a=n),n=n.sibling;null===(n=a)?(a=t.child,t.child=null):(a=n.sibling,n.sibling=null),oE(t,!1,a,n,i);break;case"backwards":for(n=null,a=t.child,t.child=null;null!==a;){if(null!==(e=a.alternate)&&null===il(e)){t.child=a;break;}e=a.sibling,a.sibling=n,n=a,a=e;}oE(t,!0,n,null,i);break;case"together":oE(t,!1,null,null,void 0);break;default:t.memoizedState=null;}return t.child;}function oP(e,t){0==(1&t.mode)&&null!==e&&(e.alternate=null,t.alternate=null,t.flags|=2);}function oL(e,t,n){if(null!==e&&(t.dependencies=e.dependencies),la|=t.lanes,0==(n&t.childLanes))return null;if(null!==e&&t.child!==e.child)throw Error(k(153));if(null!==t.child){for(n=lJ(e=t.child,e.pendingProps),t.child=n,n.return=t;null!==e.sibling;)e=e.sibling,(n=n.sibling=lJ(e,e.pendingProps)).return=t;n.sibling=null;}return t.child;}function o_(e,t){if(!ad)switch(e.tailMode){case"hidden":t=e.tail;for(var n=null;null!==t;)null!==t.alternate&&(n=t),t=t.sibling;null===n?e.tail=null:n.sibling=null;break;case"collapsed":n=e.tail;for(var r=null;null!==n;)null!==n.alternate&&(r=n),n=n.sibling;null===r?t||null===e.tail?e.tail=null:e.tail.sibling=null:r.sibling=null;}}function oD(e){var t=null!==e.alternate&&e.alternate.child===e.child,n=0,r=0;if(t)for(var a=e.child;null!==a;)n|=a.lanes|a.childLanes,r|=14680064&a.subtreeFlags,r|=14680064&a.flags,a.return=e,a=a.sibling;else for(a=e.child;null!==a;)n|=a.lanes|a.childLanes,r|=a.subtreeFlags,r|=a.flags,a.return=e,a=a.sibling;return e.subtreeFlags|=r,e.childLanes=n,t;}v=function(e,t){for(var n=t.child;null!==n;){if(5===n.tag||6===n.tag)e.appendChild(n.stateNode);else if(4!==n.tag&&null!==n.child){n.child.return=n,n=n.child;continue;}if(n===t)break;for(;null===n.sibling;){if(null===n.return||n.return===t)return;n=n.return;}n.sibling.return=n.return,n=n.sibling;}},b=function(){},m=function(e,t,n,r){var a=e.memoizedProps;if(a!==r){e=t.stateNode,ie(a8.current);var i,o=null;switch(n){case"input":a=es(e,a),r=es(e,r),o=[];break;case"select":a=ee({},a,{value:void 0}),r=ee({},r,{value:void 0}),o=[];break;case"textarea":a=eb(e,a),r=eb(e,r),o=[];break;default:"function"!=typeof a.onClick&&"function"==typeof r.onClick&&(e.onclick=rb);}for(s in eL(n,r),n=null,a)if(!r.hasOwnProperty(s)&&a.hasOwnProperty(s)&&null!=a[s]){if("style"===s){var l=a[s];for(i in l)l.hasOwnProperty(i)&&(n||(n={}),n[i]="");}else"dangerouslySetInnerHTML"!==s&&"children"!==s&&"suppressContentEditableWarning"!==s&&"suppressHydrationWarning"!==s&&"autoFocus"!==s&&(T.hasOwnProperty(s)?o||(o=[]):(o=o||[]).push(s,null));}for(s in r){var u=r[s];if(l=null!=a?a[s]:void 0,r.hasOwnProperty(s)&&u!==l&&(null!=u||null!=l)){if("style"===s){if(l){for(i in l)!l.hasOwnProperty(i)||u&&u.hasOwnProperty(i)||(n||(n={}),n[i]="");for(i in u)u.hasOwnProperty(i)&&l[i]!==u[i]&&(n||(n={}),n[i]=u[i]);}else n||(o||(o=[]),o.push(s,n)),n=u;}else"dangerouslySetInnerHTML"===s?(u=u?u.__html:void 0,l=l?l.__html:void 0,null!=u&&l!==u&&(o=o||[]).push(s,u)):"children"===s?"string"!=typeof u&&"number"!=typeof u||(o=o||[]).push(s,""+u):"suppressContentEditableWarning"!==s&&"suppressHydrationWarning"!==s&&(T.hasOwnProperty(s)?(null!=u&&"onScroll"===s&&rr("scroll",e),o||l===u||(o=[])):(o=o||[]).push(s,u));}}n&&(o=o||[]).push("style",n);var s=o;(t.updateQueue=s)&&(t.flags|=4);}},y=function(e,t,n,r){n!==r&&(t.flags|=4);};var oj=!1,oF=!1,oN="function"==typeof WeakSet?WeakSet:Set,oO=null;function oR(e,t){var n=e.ref;if(null!==n){if("function"==typeof n)try{n(null);}catch(n){lU(e,t,n);}else n.current=null;}}function oA(e,t,n){try{n();}catch(n){lU(e,t,n);}}var oI=!1;function oB(e,t,n){var r=t.updateQueue;if(null!==(r=null!==r?r.lastEffect:null)){var a=r=r.next;do{if((a.tag&e)===e){var i=a.destroy;a.destroy=void 0,void 0!==i&&oA(t,n,i);}a=a.next;}while(a!==r)}}function oG(e,t){if(null!==(t=null!==(t=t.updateQueue)?t.lastEffect:null)){var n=t=t.next;do{if((n.tag&e)===e){var r=n.create;n.destroy=r();}n=n.next;}while(n!==t)}}function oH(e){var t=e.ref;if(null!==t){var n=e.stateNode;e.tag,e=n,"function"==typeof t?t(e):t.current=e;}}function oq(e){return 5===e.tag||3===e.tag||4===e.tag;}function oU(e){e:for(;;){for(;null===e.sibling;){if(null===e.return||oq(e.return))return null;e=e.return;}for(e.sibling.return=e.return,e=e.sibling;5!==e.tag&&6!==e.tag&&18!==e.tag;){if(2&e.flags||null===e.child||4===e.tag)continue e;e.child.return=e,e=e.child;}if(!(2&e.flags))return e.stateNode;}}var oW=null,oV=!1;function o$(e,t,n){for(n=n.child;null!==n;)oK(e,t,n),n=n.sibling;}function oK(e,t,n){if(to&&"function"==typeof to.onCommitFiberUnmount)try{to.onCommitFiberUnmount(ti,n);}catch(e){}switch(n.tag){case 5:oF||oR(n,t);case 6:var r=oW,a=oV;oW=null,o$(e,t,n),oW=r,oV=a,null!==oW&&(oV?(e=oW,n=n.stateNode,8===e.nodeType?e.parentNode.removeChild(n):e.removeChild(n)):oW.removeChild(n.stateNode));break;case 18:null!==oW&&(oV?(e=oW,n=n.stateNode,8===e.nodeType?rz(e.parentNode,n):1===e.nodeType&&rz(e,n),tR(e)):rz(oW,n.stateNode));break;case 4:r=oW,a=oV,oW=n.stateNode.containerInfo,oV=!0,o$(e,t,n),oW=r,oV=a;break;case 0:case 11:case 14:case 15:if(!oF&&null!==(r=n.updateQueue)&&null!==(r=r.lastEffect)){a=r=r.next;do{var i=a,o=i.destroy;i=i.tag,void 0!==o&&(0!=(2&i)?oA(n,t,o):0!=(4&i)&&oA(n,t,o)),a=a.next;}while(a!==r)}o$(e,t,n);break;case 1:if(!oF&&(oR(n,t),"function"==typeof(r=n.stateNode).componentWillUnmount))try{r.props=n.memoizedProps,r.state=n.memoizedState,r.componentWillUnmount();}catch(e){lU(n,t,e);}o$(e,t,n);break;case 21:default:o$(e,t,n);break;case 22:1&n.mode?(oF=(r=oF)||null!==n.memoizedState,o$(e,t,n),oF=r):o$(e,t,n);}}function oQ(e){var t=e.updateQueue;if(null!==t){e.updateQueue=null;var n=e.stateNode;null===n&&(n=e.stateNode=new oN),t.forEach(function(t){var r=lK.bind(null,e,t);n.has(t)||(n.add(t),t.then(r,r));});}}function oY(e,t){var n=t.deletions;if(null!==n)for(var r=0;r<n.length;r++){var a=n[r];try{var i=t,o=i;e:for(;null!==o;){switch(o.tag){case 5:oW=o.stateNode,oV=!1;break e;case 3:case 4:oW=o.stateNode.containerInfo,oV=!0;break e;}o=o.return;}if(null===oW)throw Error(k(160));oK(e,i,a),oW=null,oV=!1;var l=a.alternate;null!==l&&(l.return=null),a.return=null;}catch(e){lU(a,t,e);}}if(12854&t.subtreeFlags)for(t=t.child;null!==t;)oX(t,e),t=t.sibling;}function oX(e,t){var n=e.alternate,r=e.flags;switch(e.tag){case 0:case 11:case 14:case 15:if(oY(t,e),oJ(e),4&r){try{oB(3,e,e.return),oG(3,e);}catch(t){lU(e,e.return,t);}try{oB(5,e,e.return);}catch(t){lU(e,e.return,t);}}break;case 1:oY(t,e),oJ(e),512&r&&null!==n&&oR(n,n.return);break;case 5:if(oY(t,e),oJ(e),512&r&&null!==n&&oR(n,n.return),32&e.flags){var a=e.stateNode;try{eC(a,"");}catch(t){lU(e,e.return,t);}}if(4&r&&null!=(a=e.stateNode)){var i=e.memoizedProps,o=null!==n?n.memoizedProps:i,l=e.type,u=e.updateQueue;if(e.updateQueue=null,null!==u)try{"input"===l&&"radio"===i.type&&null!=i.name&&ef(a,i),e_(l,o);var s=e_(l,i);for(o=0;o<u.length;o+=2){var c=u[o],f=u[o+1];"style"===c?eM(a,f):"dangerouslySetInnerHTML"===c?ek(a,f):"children"===c?eC(a,f):R(a,c,f,s);}switch(l){case"input":ed(a,i);break;case"textarea":ey(a,i);break;case"select":var d=a._wrapperState.wasMultiple;a._wrapperState.wasMultiple=!!i.multiple;var h=i.value;null!=h?ev(a,!!i.multiple,h,!1):!!i.multiple!==d&&(null!=i.defaultValue?ev(a,!!i.multiple,i.defaultValue,!0):ev(a,!!i.multiple,i.multiple?[]:"",!1));}a[r_]=i;}catch(t){lU(e,e.return,t);}}break;case 6:if(oY(t,e),oJ(e),4&r){if(null===e.stateNode)throw Error(k(162));a=e.stateNode,i=e.memoizedProps;try{a.nodeValue=i;}catch(t){lU(e,e.return,t);}}break;case 3:if(oY(t,e),oJ(e),4&r&&null!==n&&n.memoizedState.isDehydrated)try{tR(t.containerInfo);}catch(t){lU(e,e.return,t);}break;case 4:default:oY(t,e),oJ(e);break;case 13:oY(t,e),oJ(e),8192&(a=e.child).flags&&(i=null!==a.memoizedState,a.stateNode.isHidden=i,i&&(null===a.alternate||null===a.alternate.memoizedState)&&(ls=e9())),4&r&&oQ(e);break;case 22:if(c=null!==n&&null!==n.memoizedState,1&e.mode?(oF=(s=oF)||c,oY(t,e),oF=s):oY(t,e),oJ(e),8192&r){if(s=null!==e.memoizedState,(e.stateNode.isHidden=s)&&!c&&0!=(1&e.mode))for(oO=e,c=e.child;null!==c;){for(f=oO=c;null!==oO;){switch(h=(d=oO).child,d.tag){case 0:case 11:case 14:case 15:oB(4,d,d.return);break;case 1:oR(d,d.return);var p=d.stateNode;if("function"==typeof p.componentWillUnmount){r=d,n=d.return;try{t=r,p.props=t.memoizedProps,p.state=t.memoizedState,p.componentWillUnmount();}catch(e){lU(r,n,e);}}break;case 5:oR(d,d.return);break;case 22:if(null!==d.memoizedState){o0(f);continue;}}null!==h?(h.return=d,oO=h):o0(f);}c=c.sibling;}e:for(c=null,f=e;;){if(5===f.tag){if(null===c){c=f;try{a=f.stateNode,s?(i=a.style,"function"==typeof i.setProperty?i.setProperty("display","none","important"):i.display="none"):(l=f.stateNode,o=null!=(u=f.memoizedProps.style)&&u.hasOwnProperty("display")?u.display:null,l.style.display=eE("display",o));}catch(t){lU(e,e.return,t);}}}else if(6===f.tag){if(null===c)try{f.stateNode.nodeValue=s?"":f.memoizedProps;}catch(t){lU(e,e.return,t);}}else if((22!==f.tag&&23!==f.tag||null===f.memoizedState||f===e)&&null!==f.child){f.child.return=f,f=f.child;continue;}if(f===e)break;for(;null===f.sibling;){if(null===f.return||f.return===e)break e;c===f&&(c=null),f=f.return;}c===f&&(c=null),f.sibling.return=f.return,f=f.sibling;}}break;case 19:oY(t,e),oJ(e),4&r&&oQ(e);case 21:}}function oJ(e){var t=e.flags;if(2&t){try{e:{for(var n=e.return;null!==n;){if(oq(n)){var r=n;break e;}n=n.return;}throw Error(k(160));}switch(r.tag){case 5:var a=r.stateNode;32&r.flags&&(eC(a,""),r.flags&=-33);var i=oU(e);!function e(t,n,r){var a=t.tag;if(5===a||6===a)t=t.stateNode,n?r.insertBefore(t,n):r.appendChild(t);else if(4!==a&&null!==(t=t.child))for(e(t,n,r),t=t.sibling;null!==t;)e(t,n,r),t=t.sibling;}(e,i,a);break;case 3:case 4:var o=r.stateNode.containerInfo,l=oU(e);!function e(t,n,r){var a=t.tag;if(5===a||6===a)t=t.stateNode,n?8===r.nodeType?r.parentNode.insertBefore(t,n):r.insertBefore(t,n):(8===r.nodeType?(n=r.parentNode).insertBefore(t,r):(n=r).appendChild(t),null!=(r=r._reactRootContainer)||null!==n.onclick||(n.onclick=rb));else if(4!==a&&null!==(t=t.child))for(e(t,n,r),t=t.sibling;null!==t;)e(t,n,r),t=t.sibling;}(e,l,o);break;default:throw Error(k(161));}}catch(t){lU(e,e.return,t);}e.flags&=-3;}4096&t&&(e.flags&=-4097);}function oZ(e){for(;null!==oO;){var t=oO;if(0!=(8772&t.flags)){var n=t.alternate;try{if(0!=(8772&t.flags))switch(t.tag){case 0:case 11:case 15:oF||oG(5,t);break;case 1:var r=t.stateNode;if(4&t.flags&&!oF){if(null===n)r.componentDidMount();else{var a=t.elementType===t.type?n.memoizedProps:aC(t.type,n.memoizedProps);r.componentDidUpdate(a,n.memoizedState,r.__reactInternalSnapshotBeforeUpdate);}}var i=t.updateQueue;null!==i&&aV(t,i,r);break;case 3:var o=t.updateQueue;if(null!==o){if(n=null,null!==t.child)switch(t.child.tag){case 5:case 1:n=t.child.stateNode;}aV(t,o,n);}break;case 5:var l=t.stateNode;if(null===n&&4&t.flags){n=l;var u=t.memoizedProps;switch(t.type){case"button":case"input":case"select":case"textarea":u.autoFocus&&n.focus();break;case"img":u.src&&(n.src=u.src);}}break;case 6:case 4:case 12:case 19:case 17:case 21:case 22:case 23:case 25:break;case 13:if(null===t.memoizedState){var s=t.alternate;if(null!==s){var c=s.memoizedState;if(null!==c){var f=c.dehydrated;null!==f&&tR(f);}}}break;default:throw Error(k(163));}oF||512&t.flags&&oH(t);}catch(e){lU(t,t.return,e);}}if(t===e){oO=null;break;}if(null!==(n=t.sibling)){n.return=t.return,oO=n;break;}oO=t.return;}}function o0(e){for(;null!==oO;){var t=oO;if(t===e){oO=null;break;}var n=t.sibling;if(null!==n){n.return=t.return,oO=n;break;}oO=t.return;}}function o1(e){for(;null!==oO;){var t=oO;try{switch(t.tag){case 0:case 11:case 15:var n=t.return;try{oG(4,t);}catch(e){lU(t,n,e);}break;case 1:var r=t.stateNode;if("function"==typeof r.componentDidMount){var a=t.return;try{r.componentDidMount();}catch(e){lU(t,a,e);}}var i=t.return;try{oH(t);}catch(e){lU(t,i,e);}break;case 5:var o=t.return;try{oH(t);}catch(e){lU(t,o,e);}}}catch(e){lU(t,t.return,e);}if(t===e){oO=null;break;}var l=t.sibling;if(null!==l){l.return=t.return,oO=l;break;}oO=t.return;}}var o2=Math.ceil,o3=A.ReactCurrentDispatcher,o5=A.ReactCurrentOwner,o4=A.ReactCurrentBatchConfig,o6=0,o8=null,o9=null,o7=0,le=0,lt=rH(0),ln=0,lr=null,la=0,li=0,lo=0,ll=null,lu=null,ls=0,lc=1/0,lf=null,ld=!1,lh=null,lp=null,lg=!1,lv=null,lb=0,lm=0,ly=null,lw=-1,lx=0;function lS(){return 0!=(6&o6)?e9():-1!==lw?lw:lw=e9();}function lk(e){return 0==(1&e.mode)?1:0!=(2&o6)&&0!==o7?o7&-o7:null!==ak.transition?(0===lx&&(lx=tg()),lx):0!==(e=ty)?e:e=void 0===(e=window.event)?16:tW(e.type);}function lC(e,t,n,r){if(50<lm)throw lm=0,ly=null,Error(k(185));tb(e,n,r),(0==(2&o6)||e!==o8)&&(e===o8&&(0==(2&o6)&&(li|=n),4===ln&&lP(e,o7)),lT(e,r),1===n&&0===o6&&0==(1&t.mode)&&(lc=e9()+500,r3&&r6()));}function lT(e,t){var n,r=e.callbackNode;!function(e,t){for(var n=e.suspendedLanes,r=e.pingedLanes,a=e.expirationTimes,i=e.pendingLanes;0<i;){var o=31-tl(i),l=1<<o,u=a[o];-1===u?(0==(l&n)||0!=(l&r))&&(a[o]=function(e,t){switch(e){case 1:case 2:case 4:return t+250;case 8:case 16:case 32:case 64:case 128:case 256:case 512:case 1024:case 2048:case 4096:case 8192:case 16384:case 32768:case 65536:case 131072:case 262144:case 524288:case 1048576:case 2097152:return t+5e3;default:return -1;}}(l,t)):u<=t&&(e.expiredLanes|=l),i&=~l;}}(e,t);var a=th(e,e===o8?o7:0);if(0===a)null!==r&&e4(r),e.callbackNode=null,e.callbackPriority=0;else if(t=a&-a,e.callbackPriority!==t){if(null!=r&&e4(r),1===t)0===e.tag?(n=lL.bind(null,e),r3=!0,r4(n)):r4(lL.bind(null,e)),rC(function(){0==(6&o6)&&r6();}),r=null;else{switch(tw(a)){case 1:r=te;break;case 4:r=tt;break;case 16:default:r=tn;break;case 536870912:r=ta;}r=e5(r,lz.bind(null,e));}e.callbackPriority=t,e.callbackNode=r;}}function lz(e,t){if(lw=-1,lx=0,0!=(6&o6))throw Error(k(327));var n=e.callbackNode;if(lH()&&e.callbackNode!==n)return null;var r=th(e,e===o8?o7:0);if(0===r)return null;if(0!=(30&r)||0!=(r&e.expiredLanes)||t)t=lA(e,r);else{t=r;var a=o6;o6|=2;var i=lO();for((o8!==e||o7!==t)&&(lf=null,lc=e9()+500,lF(e,t));;)try{!function(){for(;null!==o9&&!e6();)lI(o9);}();break;}catch(t){lN(e,t);}aP(),o3.current=i,o6=a,null!==o9?t=0:(o8=null,o7=0,t=ln);}if(0!==t){if(2===t&&0!==(a=tp(e))&&(r=a,t=lE(e,a)),1===t)throw n=lr,lF(e,0),lP(e,r),lT(e,e9()),n;if(6===t)lP(e,r);else{if(a=e.current.alternate,0==(30&r)&&!function(e){for(var t=e;;){if(16384&t.flags){var n=t.updateQueue;if(null!==n&&null!==(n=n.stores))for(var r=0;r<n.length;r++){var a=n[r],i=a.getSnapshot;a=a.value;try{if(!nR(i(),a))return!1;}catch(e){return!1;}}}if(n=t.child,16384&t.subtreeFlags&&null!==n)n.return=t,t=n;else{if(t===e)break;for(;null===t.sibling;){if(null===t.return||t.return===e)return!0;t=t.return;}t.sibling.return=t.return,t=t.sibling;}}return!0;}(a)&&(2===(t=lA(e,r))&&0!==(i=tp(e))&&(r=i,t=lE(e,i)),1===t))throw n=lr,lF(e,0),lP(e,r),lT(e,e9()),n;switch(e.finishedWork=a,e.finishedLanes=r,t){case 0:case 1:throw Error(k(345));case 2:case 5:lG(e,lu,lf);break;case 3:if(lP(e,r),(130023424&r)===r&&10<(t=ls+500-e9())){if(0!==th(e,0))break;if(((a=e.suspendedLanes)&r)!==r){lS(),e.pingedLanes|=e.suspendedLanes&a;break;}e.timeoutHandle=rx(lG.bind(null,e,lu,lf),t);break;}lG(e,lu,lf);break;case 4:if(lP(e,r),(4194240&r)===r)break;for(a=-1,t=e.eventTimes;0<r;){var o=31-tl(r);i=1<<o,(o=t[o])>a&&(a=o),r&=~i;}if(r=a,10<(r=(120>(r=e9()-r)?120:480>r?480:1080>r?1080:1920>r?1920:3e3>r?3e3:4320>r?4320:1960*o2(r/1960))-r)){e.timeoutHandle=rx(lG.bind(null,e,lu,lf),r);break;}lG(e,lu,lf);break;default:throw Error(k(329));}}}return lT(e,e9()),e.callbackNode===n?lz.bind(null,e):null;}function lE(e,t){var n=ll;return e.current.memoizedState.isDehydrated&&(lF(e,t).flags|=256),2!==(e=lA(e,t))&&(t=lu,lu=n,null!==t&&lM(t)),e;}function lM(e){null===lu?lu=e:lu.push.apply(lu,e);}function lP(e,t){for(t&=~lo,t&=~li,e.suspendedLanes|=t,e.pingedLanes&=~t,e=e.expirationTimes;0<t;){var n=31-tl(t),r=1<<n;e[n]=-1,t&=~r;}}function lL(e){if(0!=(6&o6))throw Error(k(327));lH();var t=th(e,0);if(0==(1&t))return lT(e,e9()),null;var n=lA(e,t);if(0!==e.tag&&2===n){var r=tp(e);0!==r&&(t=r,n=lE(e,r));}if(1===n)throw n=lr,lF(e,0),lP(e,t),lT(e,e9()),n;if(6===n)throw Error(k(345));return e.finishedWork=e.current.alternate,e.finishedLanes=t,lG(e,lu,lf),lT(e,e9()),null;}function l_(e,t){var n=o6;o6|=1;try{return e(t);}finally{0===(o6=n)&&(lc=e9()+500,r3&&r6());}}function lD(e){null!==lv&&0===lv.tag&&0==(6&o6)&&lH();var t=o6;o6|=1;var n=o4.transition,r=ty;try{if(o4.transition=null,ty=1,e)return e();}finally{ty=r,o4.transition=n,0==(6&(o6=t))&&r6();}}function lj(){le=lt.current,rq(lt);}function lF(e,t){e.finishedWork=null,e.finishedLanes=0;var n=e.timeoutHandle;if(-1!==n&&(e.timeoutHandle=-1,rS(n)),null!==o9)for(n=o9.return;null!==n;){var r=n;switch(as(r),r.tag){case 1:null!=(r=r.type.childContextTypes)&&rX();break;case 3:ir(),rq(r$),rq(rV),is();break;case 5:ii(r);break;case 4:ir();break;case 13:case 19:rq(io);break;case 10:aL(r.type._context);break;case 22:case 23:lj();}n=n.return;}if(o8=e,o9=e=lJ(e.current,null),o7=le=t,ln=0,lr=null,lo=li=la=0,lu=ll=null,null!==aF){for(t=0;t<aF.length;t++)if(null!==(r=(n=aF[t]).interleaved)){n.interleaved=null;var a=r.next,i=n.pending;if(null!==i){var o=i.next;i.next=a,r.next=o;}n.pending=r;}aF=null;}return e;}function lN(e,t){for(;;){var n=o9;try{if(aP(),ic.current=i4,ib){for(var r=ip.memoizedState;null!==r;){var a=r.queue;null!==a&&(a.pending=null),r=r.next;}ib=!1;}if(ih=0,iv=ig=ip=null,im=!1,iy=0,o5.current=null,null===n||null===n.return){ln=1,lr=t,o9=null;break;}e:{var i=e,o=n.return,l=n,u=t;if(t=o7,l.flags|=32768,null!==u&&"object"==typeof u&&"function"==typeof u.then){var s=u,c=l,f=c.tag;if(0==(1&c.mode)&&(0===f||11===f||15===f)){var d=c.alternate;d?(c.updateQueue=d.updateQueue,c.memoizedState=d.memoizedState,c.lanes=d.lanes):(c.updateQueue=null,c.memoizedState=null);}var h=oo(o);if(null!==h){h.flags&=-257,ol(h,o,l,i,t),1&h.mode&&oi(i,s,t),t=h,u=s;var p=t.updateQueue;if(null===p){var g=new Set;g.add(u),t.updateQueue=g;}else p.add(u);break e;}if(0==(1&t)){oi(i,s,t),lR();break e;}u=Error(k(426));}else if(ad&&1&l.mode){var v=oo(o);if(null!==v){0==(65536&v.flags)&&(v.flags|=256),ol(v,o,l,i,t),aS(i7(u,l));break e;}}i=u=i7(u,l),4!==ln&&(ln=2),null===ll?ll=[i]:ll.push(i),i=o;do{switch(i.tag){case 3:i.flags|=65536,t&=-t,i.lanes|=t;var b=or(i,u,t);aU(i,b);break e;case 1:l=u;var m=i.type,y=i.stateNode;if(0==(128&i.flags)&&("function"==typeof m.getDerivedStateFromError||null!==y&&"function"==typeof y.componentDidCatch&&(null===lp||!lp.has(y)))){i.flags|=65536,t&=-t,i.lanes|=t;var w=oa(i,l,t);aU(i,w);break e;}}i=i.return;}while(null!==i)}lB(n);}catch(e){t=e,o9===n&&null!==n&&(o9=n=n.return);continue;}break;}}function lO(){var e=o3.current;return o3.current=i4,null===e?i4:e;}function lR(){(0===ln||3===ln||2===ln)&&(ln=4),null===o8||0==(268435455&la)&&0==(268435455&li)||lP(o8,o7);}function lA(e,t){var n=o6;o6|=2;var r=lO();for((o8!==e||o7!==t)&&(lf=null,lF(e,t));;)try{!function(){for(;null!==o9;)lI(o9);}();break;}catch(t){lN(e,t);}if(aP(),o6=n,o3.current=r,null!==o9)throw Error(k(261));return o8=null,o7=0,ln;}function lI(e){var t=w(e.alternate,e,le);e.memoizedProps=e.pendingProps,null===t?lB(e):o9=t,o5.current=null;}function lB(e){var t=e;do{var n=t.alternate;if(e=t.return,0==(32768&t.flags)){if(null!==(n=function(e,t,n){var r=t.pendingProps;switch(as(t),t.tag){case 2:case 16:case 15:case 0:case 11:case 7:case 8:case 12:case 9:case 14:return oD(t),null;case 1:case 17:return rY(t.type)&&rX(),oD(t),null;case 3:return r=t.stateNode,ir(),rq(r$),rq(rV),is(),r.pendingContext&&(r.context=r.pendingContext,r.pendingContext=null),(null===e||null===e.child)&&(ay(t)?t.flags|=4:null===e||e.memoizedState.isDehydrated&&0==(256&t.flags)||(t.flags|=1024,null!==ah&&(lM(ah),ah=null))),b(e,t),oD(t),null;case 5:ii(t);var a=ie(a7.current);if(n=t.type,null!==e&&null!=t.stateNode)m(e,t,n,r,a),e.ref!==t.ref&&(t.flags|=512,t.flags|=2097152);else{if(!r){if(null===t.stateNode)throw Error(k(166));return oD(t),null;}if(e=ie(a8.current),ay(t)){r=t.stateNode,n=t.type;var i=t.memoizedProps;switch(r[rL]=t,r[r_]=i,e=0!=(1&t.mode),n){case"dialog":rr("cancel",r),rr("close",r);break;case"iframe":case"object":case"embed":rr("load",r);break;case"video":case"audio":for(a=0;a<n7.length;a++)rr(n7[a],r);break;case"source":rr("error",r);break;case"img":case"image":case"link":rr("error",r),rr("load",r);break;case"details":rr("toggle",r);break;case"input":ec(r,i),rr("invalid",r);break;case"select":r._wrapperState={wasMultiple:!!i.multiple},rr("invalid",r);break;case"textarea":em(r,i),rr("invalid",r);}for(var o in eL(n,i),a=null,i)if(i.hasOwnProperty(o)){var l=i[o];"children"===o?"string"==typeof l?r.textContent!==l&&(!0!==i.suppressHydrationWarning&&rv(r.textContent,l,e),a=["children",l]):"number"==typeof l&&r.textContent!==""+l&&(!0!==i.suppressHydrationWarning&&rv(r.textContent,l,e),a=["children",""+l]):T.hasOwnProperty(o)&&null!=l&&"onScroll"===o&&rr("scroll",r);}switch(n){case"input":eo(r),eh(r,i,!0);break;case"textarea":eo(r),ew(r);break;case"select":case"option":break;default:"function"==typeof i.onClick&&(r.onclick=rb);}r=a,t.updateQueue=r,null!==r&&(t.flags|=4);}else{o=9===a.nodeType?a:a.ownerDocument,"http://www.w3.org/1999/xhtml"===e&&(e=ex(n)),"http://www.w3.org/1999/xhtml"===e?"script"===n?((e=o.createElement("div")).innerHTML="<script></script>",e=e.removeChild(e.firstChild)):"string"==typeof r.is?e=o.createElement(n,{is:r.is}):(e=o.createElement(n),"select"===n&&(o=e,r.multiple?o.multiple=!0:r.size&&(o.size=r.size))):e=o.createElementNS(e,n),e[rL]=t,e[r_]=r,v(e,t,!1,!1),t.stateNode=e;e:{switch(o=e_(n,r),n){case"dialog":rr("cancel",e),rr("close",e),a=r;break;case"iframe":case"object":case"embed":rr("load",e),a=r;break;case"video":case"audio":for(a=0;a<n7.length;a++)rr(n7[a],e);a=r;break;case"source":rr("error",e),a=r;break;case"img":case"image":case"link":rr("error",e),rr("load",e),a=r;break;case"details":rr("toggle",e),a=r;break;case"input":ec(e,r),a=es(e,r),rr("invalid",e);break;case"option":default:a=r;break;case"select":e._wrapperState={wasMultiple:!!r.multiple},a=ee({},r,{value:void 0}),rr("invalid",e);break;case"textarea":em(e,r),a=eb(e,r),rr("invalid",e);}for(i in eL(n,a),l=a)if(l.hasOwnProperty(i)){var u=l[i];"style"===i?eM(e,u):"dangerouslySetInnerHTML"===i?null!=(u=u?u.__html:void 0)&&ek(e,u):"children"===i?"string"==typeof u?("textarea"!==n||""!==u)&&eC(e,u):"number"==typeof u&&eC(e,""+u):"suppressContentEditableWarning"!==i&&"suppressHydrationWarning"!==i&&"autoFocus"!==i&&(T.hasOwnProperty(i)?null!=u&&"onScroll"===i&&rr("scroll",e):null!=u&&R(e,i,u,o));}switch(n){case"input":eo(e),eh(e,r,!1);break;case"textarea":eo(e),ew(e);break;case"option":null!=r.value&&e.setAttribute("value",""+ea(r.value));break;case"select":e.multiple=!!r.multiple,null!=(i=r.value)?ev(e,!!r.multiple,i,!1):null!=r.defaultValue&&ev(e,!!r.multiple,r.defaultValue,!0);break;default:"function"==typeof a.onClick&&(e.onclick=rb);}switch(n){case"button":case"input":case"select":case"textarea":r=!!r.autoFocus;break e;case"img":r=!0;break e;default:r=!1;}}r&&(t.flags|=4);}null!==t.ref&&(t.flags|=512,t.flags|=2097152);}return oD(t),null;case 6:if(e&&null!=t.stateNode)y(e,t,e.memoizedProps,r);else{if("string"!=typeof r&&null===t.stateNode)throw Error(k(166));if(n=ie(a7.current),ie(a8.current),ay(t)){if(r=t.stateNode,n=t.memoizedProps,r[rL]=t,(i=r.nodeValue!==n)&&null!==(e=ac))switch(e.tag){case 3:rv(r.nodeValue,n,0!=(1&e.mode));break;case 5:!0!==e.memoizedProps.suppressHydrationWarning&&rv(r.nodeValue,n,0!=(1&e.mode));}i&&(t.flags|=4);}else(r=(9===n.nodeType?n:n.ownerDocument).createTextNode(r))[rL]=t,t.stateNode=r;}return oD(t),null;case 13:if(rq(io),r=t.memoizedState,null===e||null!==e.memoizedState&&null!==e.memoizedState.dehydrated){if(ad&&null!==af&&0!=(1&t.mode)&&0==(128&t.flags))aw(),ax(),t.flags|=98560,i=!1;else if(i=ay(t),null!==r&&null!==r.dehydrated){if(null===e){if(!i)throw Error(k(318));if(!(i=null!==(i=t.memoizedState)?i.dehydrated:null))throw Error(k(317));i[rL]=t;}else ax(),0==(128&t.flags)&&(t.memoizedState=null),t.flags|=4;oD(t),i=!1;}else null!==ah&&(lM(ah),ah=null),i=!0;if(!i)return 65536&t.flags?t:null;}if(0!=(128&t.flags))return t.lanes=n,t;return(r=null!==r)!=(null!==e&&null!==e.memoizedState)&&r&&(t.child.flags|=8192,0!=(1&t.mode)&&(null===e||0!=(1&io.current)?0===ln&&(ln=3):lR())),null!==t.updateQueue&&(t.flags|=4),oD(t),null;case 4:return ir(),b(e,t),null===e&&ro(t.stateNode.containerInfo),oD(t),null;case 10:return aL(t.type._context),oD(t),null;case 19:if(rq(io),null===(i=t.memoizedState))return oD(t),null;if(r=0!=(128&t.flags),null===(o=i.rendering)){if(r)o_(i,!1);else{if(0!==ln||null!==e&&0!=(128&e.flags))for(e=t.child;null!==e;){if(null!==(o=il(e))){for(t.flags|=128,o_(i,!1),null!==(r=o.updateQueue)&&(t.updateQueue=r,t.flags|=4),t.subtreeFlags=0,r=n,n=t.child;null!==n;)i=n,e=r,i.flags&=14680066,null===(o=i.alternate)?(i.childLanes=0,i.lanes=e,i.child=null,i.subtreeFlags=0,i.memoizedProps=null,i.memoizedState=null,i.updateQueue=null,i.dependencies=null,i.stateNode=null):(i.childLanes=o.childLanes,i.lanes=o.lanes,i.child=o.child,i.subtreeFlags=0,i.deletions=null,i.memoizedProps=o.memoizedProps,i.memoizedState=o.memoizedState,i.updateQueue=o.updateQueue,i.type=o.type,e=o.dependencies,i.dependencies=null===e?null:{lanes:e.lanes,firstContext:e.firstContext}),n=n.sibling;return rU(io,1&io.current|2),t.child;}e=e.sibling;}null!==i.tail&&e9()>lc&&(t.flags|=128,r=!0,o_(i,!1),t.lanes=4194304);}}else{if(!r){if(null!==(e=il(o))){if(t.flags|=128,r=!0,null!==(n=e.updateQueue)&&(t.updateQueue=n,t.flags|=4),o_(i,!0),null===i.tail&&"hidden"===i.tailMode&&!o.alternate&&!ad)return oD(t),null;}else 2*e9()-i.renderingStartTime>lc&&1073741824!==n&&(t.flags|=128,r=!0,o_(i,!1),t.lanes=4194304);}i.isBackwards?(o.sibling=t.child,t.child=o):(null!==(n=i.last)?n.sibling=o:t.child=o,i.last=o);}if(null!==i.tail)return t=i.tail,i.rendering=t,i.tail=t.sibling,i.renderingStartTime=e9(),t.sibling=null,n=io.current,rU(io,r?1&n|2:1&n),t;return oD(t),null;case 22:case 23:return lj(),r=null!==t.memoizedState,null!==e&&null!==e.memoizedState!==r&&(t.flags|=8192),r&&0!=(1&t.mode)?0!=(1073741824&le)&&(oD(t),6&t.subtreeFlags&&(t.flags|=8192)):oD(t),null;case 24:case 25:return null;}throw Error(k(156,t.tag));}(n,t,le))){o9=n;return;}}else{if(null!==(n=function(e,t){switch(as(t),t.tag){case 1:return rY(t.type)&&rX(),65536&(e=t.flags)?(t.flags=-65537&e|128,t):null;case 3:return ir(),rq(r$),rq(rV),is(),0!=(65536&(e=t.flags))&&0==(128&e)?(t.flags=-65537&e|128,t):null;case 5:return ii(t),null;case 13:if(rq(io),null!==(e=t.memoizedState)&&null!==e.dehydrated){if(null===t.alternate)throw Error(k(340));ax();}return 65536&(e=t.flags)?(t.flags=-65537&e|128,t):null;case 19:return rq(io),null;case 4:return ir(),null;case 10:return aL(t.type._context),null;case 22:case 23:return lj(),null;default:return null;}}(n,t))){n.flags&=32767,o9=n;return;}if(null!==e)e.flags|=32768,e.subtreeFlags=0,e.deletions=null;else{ln=6,o9=null;return;}}if(null!==(t=t.sibling)){o9=t;return;}o9=t=e;}while(null!==t)0===ln&&(ln=5);}function lG(e,t,n){var r=ty,a=o4.transition;try{o4.transition=null,ty=1,function(e,t,n,r){do lH();while(null!==lv)if(0!=(6&o6))throw Error(k(327));n=e.finishedWork;var a=e.finishedLanes;if(null!==n){if(e.finishedWork=null,e.finishedLanes=0,n===e.current)throw Error(k(177));e.callbackNode=null,e.callbackPriority=0;var i=n.lanes|n.childLanes;if(function(e,t){var n=e.pendingLanes&~t;e.pendingLanes=t,e.suspendedLanes=0,e.pingedLanes=0,e.expiredLanes&=t,e.mutableReadLanes&=t,e.entangledLanes&=t,t=e.entanglements;var r=e.eventTimes;for(e=e.expirationTimes;0<n;){var a=31-tl(n),i=1<<a;t[a]=0,r[a]=-1,e[a]=-1,n&=~i;}}(e,i),e===o8&&(o9=o8=null,o7=0),0==(2064&n.subtreeFlags)&&0==(2064&n.flags)||lg||(lg=!0,o=function(){return lH(),null;},e5(tn,o)),i=0!=(15990&n.flags),0!=(15990&n.subtreeFlags)||i){i=o4.transition,o4.transition=null;var o,l,u,s,c=ty;ty=1;var f=o6;o6|=4,o5.current=null,function(e,t){if(rm=tI,nH(e=nG())){if("selectionStart"in e)var n={start:e.selectionStart,end:e.selectionEnd};else e:{var r=(n=(n=e.ownerDocument)&&n.defaultView||window).getSelection&&n.getSelection();if(r&&0!==r.rangeCount){n=r.anchorNode;var a,i=r.anchorOffset,o=r.focusNode;r=r.focusOffset;try{n.nodeType,o.nodeType;}catch(e){n=null;break e;}var l=0,u=-1,s=-1,c=0,f=0,d=e,h=null;t:for(;;){for(;d!==n||0!==i&&3!==d.nodeType||(u=l+i),d!==o||0!==r&&3!==d.nodeType||(s=l+r),3===d.nodeType&&(l+=d.nodeValue.length),null!==(a=d.firstChild);)h=d,d=a;for(;;){if(d===e)break t;if(h===n&&++c===i&&(u=l),h===o&&++f===r&&(s=l),null!==(a=d.nextSibling))break;h=(d=h).parentNode;}d=a;}n=-1===u||-1===s?null:{start:u,end:s};}else n=null;}n=n||{start:0,end:0};}else n=null;for(ry={focusedElem:e,selectionRange:n},tI=!1,oO=t;null!==oO;)if(e=(t=oO).child,0!=(1028&t.subtreeFlags)&&null!==e)e.return=t,oO=e;else for(;null!==oO;){t=oO;try{var p=t.alternate;if(0!=(1024&t.flags))switch(t.tag){case 0:case 11:case 15:case 5:case 6:case 4:case 17:break;case 1:if(null!==p){var g=p.memoizedProps,v=p.memoizedState,b=t.stateNode,m=b.getSnapshotBeforeUpdate(t.elementType===t.type?g:aC(t.type,g),v);b.__reactInternalSnapshotBeforeUpdate=m;}break;case 3:var y=t.stateNode.containerInfo;1===y.nodeType?y.textContent="":9===y.nodeType&&y.documentElement&&y.removeChild(y.documentElement);break;default:throw Error(k(163));}}catch(e){lU(t,t.return,e);}if(null!==(e=t.sibling)){e.return=t.return,oO=e;break;}oO=t.return;}p=oI,oI=!1;}(e,n),oX(n,e),function(e){var t=nG(),n=e.focusedElem,r=e.selectionRange;if(t!==n&&n&&n.ownerDocument&&function e(t,n){return!!t&&!!n&&(t===n||(!t||3!==t.nodeType)&&(n&&3===n.nodeType?e(t,n.parentNode):"contains"in t?t.contains(n):!!t.compareDocumentPosition&&!!(16&t.compareDocumentPosition(n))));}(n.ownerDocument.documentElement,n)){if(null!==r&&nH(n)){if(t=r.start,void 0===(e=r.end)&&(e=t),"selectionStart"in n)n.selectionStart=t,n.selectionEnd=Math.min(e,n.value.length);else if((e=(t=n.ownerDocument||document)&&t.defaultView||window).getSelection){e=e.getSelection();var a=n.textContent.length,i=Math.min(r.start,a);r=void 0===r.end?i:Math.min(r.end,a),!e.extend&&i>r&&(a=r,r=i,i=a),a=nB(n,i);var o=nB(n,r);a&&o&&(1!==e.rangeCount||e.anchorNode!==a.node||e.anchorOffset!==a.offset||e.focusNode!==o.node||e.focusOffset!==o.offset)&&((t=t.createRange()).setStart(a.node,a.offset),e.removeAllRanges(),i>r?(e.addRange(t),e.extend(o.node,o.offset)):(t.setEnd(o.node,o.offset),e.addRange(t)));}}for(t=[],e=n;e=e.parentNode;)1===e.nodeType&&t.push({element:e,left:e.scrollLeft,top:e.scrollTop});for("function"==typeof n.focus&&n.focus(),n=0;n<t.length;n++)(e=t[n]).element.scrollLeft=e.left,e.element.scrollTop=e.top;}}(ry),tI=!!rm,ry=rm=null,e.current=n,l=n,u=e,s=a,oO=l,function e(t,n,r){for(var a=0!=(1&t.mode);null!==oO;){var i=oO,o=i.child;if(22===i.tag&&a){var l=null!==i.memoizedState||oj;if(!l){var u=i.alternate,s=null!==u&&null!==u.memoizedState||oF;u=oj;var c=oF;if(oj=l,(oF=s)&&!c)for(oO=i;null!==oO;)s=(l=oO).child,22===l.tag&&null!==l.memoizedState?o1(i):null!==s?(s.return=l,oO=s):o1(i);for(;null!==o;)oO=o,e(o,n,r),o=o.sibling;oO=i,oj=u,oF=c;}oZ(t,n,r);}else 0!=(8772&i.subtreeFlags)&&null!==o?(o.return=i,oO=o):oZ(t,n,r);}}(l,u,s),e8(),o6=f,ty=c,o4.transition=i;}else e.current=n;if(lg&&(lg=!1,lv=e,lb=a),0===(i=e.pendingLanes)&&(lp=null),function(e){if(to&&"function"==typeof to.onCommitFiberRoot)try{to.onCommitFiberRoot(ti,e,void 0,128==(128&e.current.flags));}catch(e){}}(n.stateNode,r),lT(e,e9()),null!==t)for(r=e.onRecoverableError,n=0;n<t.length;n++)r((a=t[n]).value,{componentStack:a.stack,digest:a.digest});if(ld)throw ld=!1,e=lh,lh=null,e;0!=(1&lb)&&0!==e.tag&&lH(),0!=(1&(i=e.pendingLanes))?e===ly?lm++:(lm=0,ly=e):lm=0,r6();}}(e,t,n,r);}finally{o4.transition=a,ty=r;}return null;}function lH(){if(null!==lv){var e=tw(lb),t=o4.transition,n=ty;try{if(o4.transition=null,ty=16>e?16:e,null===lv)var r=!1;else{if(e=lv,lv=null,lb=0,0!=(6&o6))throw Error(k(331));var a=o6;for(o6|=4,oO=e.current;null!==oO;){var i=oO,o=i.child;if(0!=(16&oO.flags)){var l=i.deletions;if(null!==l){for(var u=0;u<l.length;u++){var s=l[u];for(oO=s;null!==oO;){var c=oO;switch(c.tag){case 0:case 11:case 15:oB(8,c,i);}var f=c.child;if(null!==f)f.return=c,oO=f;else for(;null!==oO;){var d=(c=oO).sibling,h=c.return;if(!function e(t){var n=t.alternate;null!==n&&(t.alternate=null,e(n)),t.child=null,t.deletions=null,t.sibling=null,5===t.tag&&null!==(n=t.stateNode)&&(delete n[rL],delete n[r_],delete n[rj],delete n[rF],delete n[rN]),t.stateNode=null,t.return=null,t.dependencies=null,t.memoizedProps=null,t.memoizedState=null,t.pendingProps=null,t.stateNode=null,t.updateQueue=null;}(c),c===s){oO=null;break;}if(null!==d){d.return=h,oO=d;break;}oO=h;}}}var p=i.alternate;if(null!==p){var g=p.child;if(null!==g){p.child=null;do{var v=g.sibling;g.sibling=null,g=v;}while(null!==g)}}oO=i;}}if(0!=(2064&i.subtreeFlags)&&null!==o)o.return=i,oO=o;else for(;null!==oO;){if(i=oO,0!=(2048&i.flags))switch(i.tag){case 0:case 11:case 15:oB(9,i,i.return);}var b=i.sibling;if(null!==b){b.return=i.return,oO=b;break;}oO=i.return;}}var m=e.current;for(oO=m;null!==oO;){var y=(o=oO).child;if(0!=(2064&o.subtreeFlags)&&null!==y)y.return=o,oO=y;else for(o=m;null!==oO;){if(l=oO,0!=(2048&l.flags))try{switch(l.tag){case 0:case 11:case 15:oG(9,l);}}catch(e){lU(l,l.return,e);}if(l===o){oO=null;break;}var w=l.sibling;if(null!==w){w.return=l.return,oO=w;break;}oO=l.return;}}if(o6=a,r6(),to&&"function"==typeof to.onPostCommitFiberRoot)try{to.onPostCommitFiberRoot(ti,e);}catch(e){}r=!0;}return r;}finally{ty=n,o4.transition=t;}}return!1;}function lq(e,t,n){t=or(e,t=i7(n,t),1),e=aH(e,t,1),t=lS(),null!==e&&(tb(e,1,t),lT(e,t));}function lU(e,t,n){if(3===e.tag)lq(e,e,n);else for(;null!==t;){if(3===t.tag){lq(t,e,n);break;}if(1===t.tag){var r=t.stateNode;if("function"==typeof t.type.getDerivedStateFromError||"function"==typeof r.componentDidCatch&&(null===lp||!lp.has(r))){e=oa(t,e=i7(n,e),1),t=aH(t,e,1),e=lS(),null!==t&&(tb(t,1,e),lT(t,e));break;}}t=t.return;}}function lW(e,t,n){var r=e.pingCache;null!==r&&r.delete(t),t=lS(),e.pingedLanes|=e.suspendedLanes&n,o8===e&&(o7&n)===n&&(4===ln||3===ln&&(130023424&o7)===o7&&500>e9()-ls?lF(e,0):lo|=n),lT(e,t);}function lV(e,t){0===t&&(0==(1&e.mode)?t=1:(t=tf,0==(130023424&(tf<<=1))&&(tf=4194304)));var n=lS();null!==(e=aR(e,t))&&(tb(e,t,n),lT(e,n));}function l$(e){var t=e.memoizedState,n=0;null!==t&&(n=t.retryLane),lV(e,n);}function lK(e,t){var n=0;switch(e.tag){case 13:var r=e.stateNode,a=e.memoizedState;null!==a&&(n=a.retryLane);break;case 19:r=e.stateNode;break;default:throw Error(k(314));}null!==r&&r.delete(t),lV(e,n);}function lQ(e,t,n,r){this.tag=e,this.key=n,this.sibling=this.child=this.return=this.stateNode=this.type=this.elementType=null,this.index=0,this.ref=null,this.pendingProps=t,this.dependencies=this.memoizedState=this.updateQueue=this.memoizedProps=null,this.mode=r,this.subtreeFlags=this.flags=0,this.deletions=null,this.childLanes=this.lanes=0,this.alternate=null;}function lY(e,t,n,r){return new lQ(e,t,n,r);}function lX(e){return!(!(e=e.prototype)||!e.isReactComponent);}function lJ(e,t){var n=e.alternate;return null===n?((n=lY(e.tag,t,e.key,e.mode)).elementType=e.elementType,n.type=e.type,n.stateNode=e.stateNode,n.alternate=e,e.alternate=n):(n.pendingProps=t,n.type=e.type,n.flags=0,n.subtreeFlags=0,n.deletions=null),n.flags=14680064&e.flags,n.childLanes=e.childLanes,n.lanes=e.lanes,n.child=e.child,n.memoizedProps=e.memoizedProps,n.memoizedState=e.memoizedState,n.updateQueue=e.updateQueue,t=e.dependencies,n.dependencies=null===t?null:{lanes:t.lanes,firstContext:t.firstContext},n.sibling=e.sibling,n.index=e.index,n.ref=e.ref,n;}function lZ(e,t,n,r,a,i){var o=2;if(r=e,"function"==typeof e)lX(e)&&(o=1);else if("string"==typeof e)o=5;else e:switch(e){case G:return l0(n.children,a,i,t);case H:o=8,a|=8;break;case q:return(e=lY(12,n,t,2|a)).elementType=q,e.lanes=i,e;case $:return(e=lY(13,n,t,a)).elementType=$,e.lanes=i,e;case K:return(e=lY(19,n,t,a)).elementType=K,e.lanes=i,e;case X:return l1(n,a,i,t);default:if("object"==typeof e&&null!==e)switch(e.$$typeof){case U:o=10;break e;case W:o=9;break e;case V:o=11;break e;case Q:o=14;break e;case Y:o=16,r=null;break e;}throw Error(k(130,null==e?e:typeof e,""));}return(t=lY(o,n,t,a)).elementType=e,t.type=r,t.lanes=i,t;}function l0(e,t,n,r){return(e=lY(7,e,r,t)).lanes=n,e;}function l1(e,t,n,r){return(e=lY(22,e,r,t)).elementType=X,e.lanes=n,e.stateNode={isHidden:!1},e;}function l2(e,t,n){return(e=lY(6,e,null,t)).lanes=n,e;}function l3(e,t,n){return(t=lY(4,null!==e.children?e.children:[],e.key,t)).lanes=n,t.stateNode={containerInfo:e.containerInfo,pendingChildren:null,implementation:e.implementation},t;}function l5(e,t,n,r,a){this.tag=t,this.containerInfo=e,this.finishedWork=this.pingCache=this.current=this.pendingChildren=null,this.timeoutHandle=-1,this.callbackNode=this.pendingContext=this.context=null,this.callbackPriority=0,this.eventTimes=tv(0),this.expirationTimes=tv(-1),this.entangledLanes=this.finishedLanes=this.mutableReadLanes=this.expiredLanes=this.pingedLanes=this.suspendedLanes=this.pendingLanes=0,this.entanglements=tv(0),this.identifierPrefix=r,this.onRecoverableError=a,this.mutableSourceEagerHydrationData=null;}function l4(e,t,n,r,a,i,o,l,u){return e=new l5(e,t,n,l,u),1===t?(t=1,!0===i&&(t|=8)):t=0,i=lY(3,null,null,t),e.current=i,i.stateNode=e,i.memoizedState={element:r,isDehydrated:n,cache:null,transitions:null,pendingSuspenseBoundaries:null},aI(i),e;}function l6(e){if(!e)return rW;e=e._reactInternals;e:{if(e0(e)!==e||1!==e.tag)throw Error(k(170));var t=e;do{switch(t.tag){case 3:t=t.stateNode.context;break e;case 1:if(rY(t.type)){t=t.stateNode.__reactInternalMemoizedMergedChildContext;break e;}}t=t.return;}while(null!==t)throw Error(k(171));}if(1===e.tag){var n=e.type;if(rY(n))return rZ(e,n,t);}return t;}function l8(e,t,n,r,a,i,o,l,u){return(e=l4(n,r,!0,e,a,i,o,l,u)).context=l6(null),n=e.current,(i=aG(r=lS(),a=lk(n))).callback=null!=t?t:null,aH(n,i,a),e.current.lanes=a,tb(e,a,r),lT(e,r),e;}function l9(e,t,n,r){var a=t.current,i=lS(),o=lk(a);return n=l6(n),null===t.context?t.context=n:t.pendingContext=n,(t=aG(i,o)).payload={element:e},null!==(r=void 0===r?null:r)&&(t.callback=r),null!==(e=aH(a,t,o))&&(lC(e,a,o,i),aq(e,a,o)),o;}function l7(e){return(e=e.current).child?(e.child.tag,e.child.stateNode):null;}function ue(e,t){if(null!==(e=e.memoizedState)&&null!==e.dehydrated){var n=e.retryLane;e.retryLane=0!==n&&n<t?n:t;}}function ut(e,t){ue(e,t),(e=e.alternate)&&ue(e,t);}w=function(e,t,n){if(null!==e){if(e.memoizedProps!==t.pendingProps||r$.current)os=!0;else{if(0==(e.lanes&n)&&0==(128&t.flags))return os=!1,function(e,t,n){switch(t.tag){case 3:oy(t),ax();break;case 5:ia(t);break;case 1:rY(t.type)&&r0(t);break;case 4:it(t,t.stateNode.containerInfo);break;case 10:var r=t.type._context,a=t.memoizedProps.value;rU(aT,r._currentValue),r._currentValue=a;break;case 13:if(null!==(r=t.memoizedState)){if(null!==r.dehydrated)return rU(io,1&io.current),t.flags|=128,null;if(0!=(n&t.child.childLanes))return ok(e,t,n);return rU(io,1&io.current),null!==(e=oL(e,t,n))?e.sibling:null;}rU(io,1&io.current);break;case 19:if(r=0!=(n&t.childLanes),0!=(128&e.flags)){if(r)return oM(e,t,n);t.flags|=128;}if(null!==(a=t.memoizedState)&&(a.rendering=null,a.tail=null,a.lastEffect=null),rU(io,io.current),!r)return null;break;case 22:case 23:return t.lanes=0,op(e,t,n);}return oL(e,t,n);}(e,t,n);os=0!=(131072&e.flags);}}else os=!1,ad&&0!=(1048576&t.flags)&&al(t,ae,t.index);switch(t.lanes=0,t.tag){case 2:var r=t.type;oP(e,t),e=t.pendingProps;var a=rQ(t,rV.current);aD(t,n),a=ik(null,t,r,e,a,n);var i=iC();return t.flags|=1,"object"==typeof a&&null!==a&&"function"==typeof a.render&&void 0===a.$$typeof?(t.tag=1,t.memoizedState=null,t.updateQueue=null,rY(r)?(i=!0,r0(t)):i=!1,t.memoizedState=null!==a.state&&void 0!==a.state?a.state:null,aI(t),a.updater=aQ,t.stateNode=a,a._reactInternals=t,aZ(t,r,e,n),t=om(null,t,r,!0,i,n)):(t.tag=0,ad&&i&&au(t),oc(null,t,a,n),t=t.child),t;case 16:r=t.elementType;e:{switch(oP(e,t),e=t.pendingProps,r=(a=r._init)(r._payload),t.type=r,a=t.tag=function(e){if("function"==typeof e)return lX(e)?1:0;if(null!=e){if((e=e.$$typeof)===V)return 11;if(e===Q)return 14;}return 2;}(r),e=aC(r,e),a){case 0:t=ov(null,t,r,e,n);break e;case 1:t=ob(null,t,r,e,n);break e;case 11:t=of(null,t,r,e,n);break e;case 14:t=od(null,t,r,aC(r.type,e),n);break e;}throw Error(k(306,r,""));}return t;case 0:return r=t.type,a=t.pendingProps,a=t.elementType===r?a:aC(r,a),ov(e,t,r,a,n);case 1:return r=t.type,a=t.pendingProps,a=t.elementType===r?a:aC(r,a),ob(e,t,r,a,n);case 3:e:{if(oy(t),null===e)throw Error(k(387));r=t.pendingProps,a=(i=t.memoizedState).element,aB(e,t),aW(t,r,null,n);var o=t.memoizedState;if(r=o.element,i.isDehydrated){if(i={element:r,isDehydrated:!1,cache:o.cache,pendingSuspenseBoundaries:o.pendingSuspenseBoundaries,transitions:o.transitions},t.updateQueue.baseState=i,t.memoizedState=i,256&t.flags){a=i7(Error(k(423)),t),t=ow(e,t,r,n,a);break e;}if(r!==a){a=i7(Error(k(424)),t),t=ow(e,t,r,n,a);break e;}for(af=rE(t.stateNode.containerInfo.firstChild),ac=t,ad=!0,ah=null,n=a4(t,null,r,n),t.child=n;n;)n.flags=-3&n.flags|4096,n=n.sibling;}else{if(ax(),r===a){t=oL(e,t,n);break e;}oc(e,t,r,n);}t=t.child;}return t;case 5:return ia(t),null===e&&ab(t),r=t.type,a=t.pendingProps,i=null!==e?e.memoizedProps:null,o=a.children,rw(r,a)?o=null:null!==i&&rw(r,i)&&(t.flags|=32),og(e,t),oc(e,t,o,n),t.child;case 6:return null===e&&ab(t),null;case 13:return ok(e,t,n);case 4:return it(t,t.stateNode.containerInfo),r=t.pendingProps,null===e?t.child=a5(t,null,r,n):oc(e,t,r,n),t.child;case 11:return r=t.type,a=t.pendingProps,a=t.elementType===r?a:aC(r,a),of(e,t,r,a,n);case 7:return oc(e,t,t.pendingProps,n),t.child;case 8:case 12:return oc(e,t,t.pendingProps.children,n),t.child;case 10:e:{if(r=t.type._context,a=t.pendingProps,i=t.memoizedProps,o=a.value,rU(aT,r._currentValue),r._currentValue=o,null!==i){if(nR(i.value,o)){if(i.children===a.children&&!r$.current){t=oL(e,t,n);break e;}}else for(null!==(i=t.child)&&(i.return=t);null!==i;){var l=i.dependencies;if(null!==l){o=i.child;for(var u=l.firstContext;null!==u;){if(u.context===r){if(1===i.tag){(u=aG(-1,n&-n)).tag=2;var s=i.updateQueue;if(null!==s){var c=(s=s.shared).pending;null===c?u.next=u:(u.next=c.next,c.next=u),s.pending=u;}}i.lanes|=n,null!==(u=i.alternate)&&(u.lanes|=n),a_(i.return,n,t),l.lanes|=n;break;}u=u.next;}}else if(10===i.tag)o=i.type===t.type?null:i.child;else if(18===i.tag){if(null===(o=i.return))throw Error(k(341));o.lanes|=n,null!==(l=o.alternate)&&(l.lanes|=n),a_(o,n,t),o=i.sibling;}else o=i.child;if(null!==o)o.return=i;else for(o=i;null!==o;){if(o===t){o=null;break;}if(null!==(i=o.sibling)){i.return=o.return,o=i;break;}o=o.return;}i=o;}}oc(e,t,a.children,n),t=t.child;}return t;case 9:return a=t.type,r=t.pendingProps.children,aD(t,n),r=r(a=aj(a)),t.flags|=1,oc(e,t,r,n),t.child;case 14:return a=aC(r=t.type,t.pendingProps),a=aC(r.type,a),od(e,t,r,a,n);case 15:return oh(e,t,t.type,t.pendingProps,n);case 17:return r=t.type,a=t.pendingProps,a=t.elementType===r?a:aC(r,a),oP(e,t),t.tag=1,rY(r)?(e=!0,r0(t)):e=!1,aD(t,n),aX(t,r,a),aZ(t,r,a,n),om(null,t,r,!0,e,n);case 19:return oM(e,t,n);case 22:return op(e,t,n);}throw Error(k(156,t.tag));};var un="function"==typeof reportError?reportError:function(e){console.error(e);};function ur(e){this._internalRoot=e;}function ua(e){this._internalRoot=e;}function ui(e){return!(!e||1!==e.nodeType&&9!==e.nodeType&&11!==e.nodeType);}function uo(e){return!(!e||1!==e.nodeType&&9!==e.nodeType&&11!==e.nodeType&&(8!==e.nodeType||" react-mount-point-unstable "!==e.nodeValue));}function ul(){}function uu(e,t,n,r,a){var i=n._reactRootContainer;if(i){var o=i;if("function"==typeof a){var l=a;a=function(){var e=l7(o);l.call(e);};}l9(t,o,e,a);}else o=function(e,t,n,r,a){if(a){if("function"==typeof r){var i=r;r=function(){var e=l7(o);i.call(e);};}var o=l8(t,r,e,0,null,!1,!1,"",ul);return e._reactRootContainer=o,e[rD]=o.current,ro(8===e.nodeType?e.parentNode:e),lD(),o;}for(;a=e.lastChild;)e.removeChild(a);if("function"==typeof r){var l=r;r=function(){var e=l7(u);l.call(e);};}var u=l4(e,0,!1,null,null,!1,!1,"",ul);return e._reactRootContainer=u,e[rD]=u.current,ro(8===e.nodeType?e.parentNode:e),lD(function(){l9(t,u,n,r);}),u;}(n,t,e,a,r);return l7(o);}ua.prototype.render=ur.prototype.render=function(e){var t=this._internalRoot;if(null===t)throw Error(k(409));l9(e,t,null,null);},ua.prototype.unmount=ur.prototype.unmount=function(){var e=this._internalRoot;if(null!==e){this._internalRoot=null;var t=e.containerInfo;lD(function(){l9(null,e,null,null);}),t[rD]=null;}},ua.prototype.unstable_scheduleHydration=function(e){if(e){var t=c();e={blockedOn:null,target:e,priority:t};for(var n=0;n<tM.length&&0!==t&&t<tM[n].priority;n++);tM.splice(n,0,e),0===n&&tD(e);}},l=function(e){switch(e.tag){case 3:var t=e.stateNode;if(t.current.memoizedState.isDehydrated){var n=td(t.pendingLanes);0!==n&&(tm(t,1|n),lT(t,e9()),0==(6&o6)&&(lc=e9()+500,r6()));}break;case 13:lD(function(){var t=aR(e,1);null!==t&&lC(t,e,1,lS());}),ut(e,1);}},u=function(e){if(13===e.tag){var t=aR(e,134217728);null!==t&&lC(t,e,134217728,lS()),ut(e,134217728);}},s=function(e){if(13===e.tag){var t=lk(e),n=aR(e,t);null!==n&&lC(n,e,t,lS()),ut(e,t);}},c=function(){return ty;},f=function(e,t){var n=ty;try{return ty=e,t();}finally{ty=n;}},eF=function(e,t,n){switch(t){case"input":if(ed(e,n),t=n.name,"radio"===n.type&&null!=t){for(n=e;n.parentNode;)n=n.parentNode;for(n=n.querySelectorAll("input[name="+JSON.stringify(""+t)+'][type="radio"]'),t=0;t<n.length;t++){var r=n[t];if(r!==e&&r.form===e.form){var a=rI(r);if(!a)throw Error(k(90));el(r),ed(r,a);}}}break;case"textarea":ey(e,n);break;case"select":null!=(t=n.value)&&ev(e,!!n.multiple,t,!1);}},eB=l_,eG=lD;var us={findFiberByHostInstance:rO,bundleType:0,version:"18.2.0",rendererPackageName:"react-dom"},uc={bundleType:us.bundleType,version:us.version,rendererPackageName:us.rendererPackageName,rendererConfig:us.rendererConfig,overrideHookState:null,overrideHookStateDeletePath:null,overrideHookStateRenamePath:null,overrideProps:null,overridePropsDeletePath:null,overridePropsRenamePath:null,setErrorHandler:null,setSuspenseHandler:null,scheduleUpdate:null,currentDispatcherRef:A.ReactCurrentDispatcher,findHostInstanceByFiber:function(e){return null===(e=e3(e))?null:e.stateNode;},findFiberByHostInstance:us.findFiberByHostInstance||function(){return null;},findHostInstancesForRefresh:null,scheduleRefresh:null,scheduleRoot:null,setRefreshHandler:null,getCurrentFiber:null,reconcilerVersion:"18.2.0-next-9e3b772b8-20220608"};if("undefined"!=typeof __REACT_DEVTOOLS_GLOBAL_HOOK__){var uf=__REACT_DEVTOOLS_GLOBAL_HOOK__;if(!uf.isDisabled&&uf.supportsFiber)try{ti=uf.inject(uc),to=uf;}catch(e){}}t.__SECRET_INTERNALS_DO_NOT_USE_OR_YOU_WILL_BE_FIRED={usingClientEntryPoint:!1,Events:[rR,rA,rI,eA,eI,l_]},t.createPortal=function(e,t){var n=2<arguments.length&&void 0!==arguments[2]?arguments[2]:null;if(!ui(t))throw Error(k(200));return function(e,t,n){var r=3<arguments.length&&void 0!==arguments[3]?arguments[3]:null;return{$$typeof:B,key:null==r?null:""+r,children:e,containerInfo:t,implementation:n};}(e,t,null,n);},t.createRoot=function(e,t){if(!ui(e))throw Error(k(299));var n=!1,r="",a=un;return null!=t&&(!0===t.unstable_strictMode&&(n=!0),void 0!==t.identifierPrefix&&(r=t.identifierPrefix),void 0!==t.onRecoverableError&&(a=t.onRecoverableError)),t=l4(e,1,!1,null,null,n,!1,r,a),e[rD]=t.current,ro(8===e.nodeType?e.parentNode:e),new ur(t);},t.findDOMNode=function(e){if(null==e)return null;if(1===e.nodeType)return e;var t=e._reactInternals;if(void 0===t){if("function"==typeof e.render)throw Error(k(188));throw Error(k(268,e=Object.keys(e).join(",")));}return e=null===(e=e3(t))?null:e.stateNode;},t.flushSync=function(e){return lD(e);},t.hydrate=function(e,t,n){if(!uo(t))throw Error(k(200));return uu(null,e,t,!0,n);},t.hydrateRoot=function(e,t,n){if(!ui(e))throw Error(k(405));var r=null!=n&&n.hydratedSources||null,a=!1,i="",o=un;if(null!=n&&(!0===n.unstable_strictMode&&(a=!0),void 0!==n.identifierPrefix&&(i=n.identifierPrefix),void 0!==n.onRecoverableError&&(o=n.onRecoverableError)),t=l8(t,null,e,1,null!=n?n:null,a,!1,i,o),e[rD]=t.current,ro(e),r)for(e=0;e<r.length;e++)a=(a=(n=r[e])._getVersion)(n._source),null==t.mutableSourceEagerHydrationData?t.mutableSourceEagerHydrationData=[n,a]:t.mutableSourceEagerHydrationData.push(n,a);return new ua(t);},t.render=function(e,t,n){if(!uo(t))throw Error(k(200));return uu(null,e,t,!1,n);},t.unmountComponentAtNode=function(e){if(!uo(e))throw Error(k(40));return!!e._reactRootContainer&&(lD(function(){uu(null,null,e,!1,function(){e._reactRootContainer=null,e[rD]=null;});}),!0);},t.unstable_batchedUpdates=l_,t.unstable_renderSubtreeIntoContainer=function(e,t,n,r){if(!uo(n))throw Error(k(200));if(null==e||void 0===e._reactInternals)throw Error(k(38));return uu(e,t,n,!1,r);},t.version="18.2.0-next-9e3b772b8-20220608";}(0,t);},"node_modules/react-dom/client.js":function(e,t,n){var r;r=n("node_modules/react-dom/index.js"),t.createRoot=r.createRoot,t.hydrateRoot=r.hydrateRoot;},"node_modules/react-dom/index.js":function(e,t,n){!function e(){if("undefined"!=typeof __REACT_DEVTOOLS_GLOBAL_HOOK__&&"function"==typeof __REACT_DEVTOOLS_GLOBAL_HOOK__.checkDCE)try{__REACT_DEVTOOLS_GLOBAL_HOOK__.checkDCE(e);}catch(e){console.error(e);}}(),e.exports=n("node_modules/react-dom/cjs/react-dom.production.min.js");},"node_modules/react/cjs/react-jsx-runtime.production.min.js":function(e,t,n){!function(e,t,r){"use strict";var a=n("node_modules/react/index.js"),i=Symbol.for("react.element"),o=Symbol.for("react.fragment"),l=Object.prototype.hasOwnProperty,u=a.__SECRET_INTERNALS_DO_NOT_USE_OR_YOU_WILL_BE_FIRED.ReactCurrentOwner,s={key:!0,ref:!0,__self:!0,__source:!0};function c(e,t,n){var r,a={},o=null,c=null;for(r in void 0!==n&&(o=""+n),void 0!==t.key&&(o=""+t.key),void 0!==t.ref&&(c=t.ref),t)l.call(t,r)&&!s.hasOwnProperty(r)&&(a[r]=t[r]);if(e&&e.defaultProps)for(r in t=e.defaultProps)void 0===a[r]&&(a[r]=t[r]);return{$$typeof:i,type:e,key:o,ref:c,props:a,_owner:u.current};}t.Fragment=o,t.jsx=c,t.jsxs=c;}(0,t);},"node_modules/react/cjs/react.production.min.js":function(e,t,n){!function(e,t,n){"use strict";var r=Symbol.for("react.element"),a=Symbol.for("react.portal"),i=Symbol.for("react.fragment"),o=Symbol.for("react.strict_mode"),l=Symbol.for("react.profiler"),u=Symbol.for("react.provider"),s=Symbol.for("react.context"),c=Symbol.for("react.forward_ref"),f=Symbol.for("react.suspense"),d=Symbol.for("react.memo"),h=Symbol.for("react.lazy"),p=Symbol.iterator,g={isMounted:function(){return!1;},enqueueForceUpdate:function(){},enqueueReplaceState:function(){},enqueueSetState:function(){}},v=Object.assign,b={};function m(e,t,n){this.props=e,this.context=t,this.refs=b,this.updater=n||g;}function y(){}function w(e,t,n){this.props=e,this.context=t,this.refs=b,this.updater=n||g;}m.prototype.isReactComponent={},m.prototype.setState=function(e,t){if("object"!=typeof e&&"function"!=typeof e&&null!=e)throw Error("setState(...): takes an object of state variables to update or a function which returns an object of state variables.");this.updater.enqueueSetState(this,e,t,"setState");},m.prototype.forceUpdate=function(e){this.updater.enqueueForceUpdate(this,e,"forceUpdate");},y.prototype=m.prototype;var x=w.prototype=new y;x.constructor=w,v(x,m.prototype),x.isPureReactComponent=!0;var S=Array.isArray,k=Object.prototype.hasOwnProperty,C={current:null},T={key:!0,ref:!0,__self:!0,__source:!0};function z(e,t,n){var a,i={},o=null,l=null;if(null!=t)for(a in void 0!==t.ref&&(l=t.ref),void 0!==t.key&&(o=""+t.key),t)k.call(t,a)&&!T.hasOwnProperty(a)&&(i[a]=t[a]);var u=arguments.length-2;if(1===u)i.children=n;else if(1<u){for(var s=Array(u),c=0;c<u;c++)s[c]=arguments[c+2];i.children=s;}if(e&&e.defaultProps)for(a in u=e.defaultProps)void 0===i[a]&&(i[a]=u[a]);return{$$typeof:r,type:e,key:o,ref:l,props:i,_owner:C.current};}function E(e){return"object"==typeof e&&null!==e&&e.$$typeof===r;}var M=/\/+/g;function P(e,t){var n,r;return"object"==typeof e&&null!==e&&null!=e.key?(n=""+e.key,r={"=":"=0",":":"=2"},"$"+n.replace(/[=:]/g,function(e){return r[e];})):t.toString(36);}function L(e,t,n){if(null==e)return e;var i=[],o=0;return!function e(t,n,i,o,l){var u,s,c,f=typeof t;("undefined"===f||"boolean"===f)&&(t=null);var d=!1;if(null===t)d=!0;else switch(f){case"string":case"number":d=!0;break;case"object":switch(t.$$typeof){case r:case a:d=!0;}}if(d)return l=l(d=t),t=""===o?"."+P(d,0):o,S(l)?(i="",null!=t&&(i=t.replace(M,"$&/")+"/"),e(l,n,i,"",function(e){return e;})):null!=l&&(E(l)&&(u=l,s=i+(!l.key||d&&d.key===l.key?"":(""+l.key).replace(M,"$&/")+"/")+t,l={$$typeof:r,type:u.type,key:s,ref:u.ref,props:u.props,_owner:u._owner}),n.push(l)),1;if(d=0,o=""===o?".":o+":",S(t))for(var h=0;h<t.length;h++){var g=o+P(f=t[h],h);d+=e(f,n,i,g,l);}else if("function"==typeof(g=null===(c=t)||"object"!=typeof c?null:"function"==typeof(c=p&&c[p]||c["@@iterator"])?c:null))for(t=g.call(t),h=0;!(f=t.next()).done;)g=o+P(f=f.value,h++),d+=e(f,n,i,g,l);else if("object"===f)throw Error("Objects are not valid as a React child (found: "+("[object Object]"===(n=String(t))?"object with keys {"+Object.keys(t).join(", ")+"}":n)+"). If you meant to render a collection of children, use an array instead.");return d;}(e,i,"","",function(e){return t.call(n,e,o++);}),i;}function _(e){if(-1===e._status){var t=e._result;(t=t()).then(function(t){(0===e._status||-1===e._status)&&(e._status=1,e._result=t);},function(t){(0===e._status||-1===e._status)&&(e._status=2,e._result=t);}),-1===e._status&&(e._status=0,e._result=t);}if(1===e._status)return e._result.default;throw e._result;}var D={current:null},j={transition:null};t.Children={map:L,forEach:function(e,t,n){L(e,function(){t.apply(this,arguments);},n);},count:function(e){var t=0;return L(e,function(){t++;}),t;},toArray:function(e){return L(e,function(e){return e;})||[];},only:function(e){if(!E(e))throw Error("React.Children.only expected to receive a single React element child.");return e;}},t.Component=m,t.Fragment=i,t.Profiler=l,t.PureComponent=w,t.StrictMode=o,t.Suspense=f,t.__SECRET_INTERNALS_DO_NOT_USE_OR_YOU_WILL_BE_FIRED={ReactCurrentDispatcher:D,ReactCurrentBatchConfig:j,ReactCurrentOwner:C},t.cloneElement=function(e,t,n){if(null==e)throw Error("React.cloneElement(...): The argument must be a React element, but you passed "+e+".");var a=v({},e.props),i=e.key,o=e.ref,l=e._owner;if(null!=t){if(void 0!==t.ref&&(o=t.ref,l=C.current),void 0!==t.key&&(i=""+t.key),e.type&&e.type.defaultProps)var u=e.type.defaultProps;for(s in t)k.call(t,s)&&!T.hasOwnProperty(s)&&(a[s]=void 0===t[s]&&void 0!==u?u[s]:t[s]);}var s=arguments.length-2;if(1===s)a.children=n;else if(1<s){u=Array(s);for(var c=0;c<s;c++)u[c]=arguments[c+2];a.children=u;}return{$$typeof:r,type:e.type,key:i,ref:o,props:a,_owner:l};},t.createContext=function(e){return(e={$$typeof:s,_currentValue:e,_currentValue2:e,_threadCount:0,Provider:null,Consumer:null,_defaultValue:null,_globalName:null}).Provider={$$typeof:u,_context:e},e.Consumer=e;},t.createElement=z,t.createFactory=function(e){var t=z.bind(null,e);return t.type=e,t;},t.createRef=function(){return{current:null};},t.forwardRef=function(e){return{$$typeof:c,render:e};},t.isValidElement=E,t.lazy=function(e){return{$$typeof:h,_payload:{_status:-1,_result:e},_init:_};},t.memo=function(e,t){return{$$typeof:d,type:e,compare:void 0===t?null:t};},t.startTransition=function(e){var t=j.transition;j.transition={};try{e();}finally{j.transition=t;}},t.unstable_act=function(){throw Error("act(...) is not supported in production builds of React.");},t.useCallback=function(e,t){return D.current.useCallback(e,t);},t.useContext=function(e){return D.current.useContext(e);},t.useDebugValue=function(){},t.useDeferredValue=function(e){return D.current.useDeferredValue(e);},t.useEffect=function(e,t){return D.current.useEffect(e,t);},t.useId=function(){return D.current.useId();},t.useImperativeHandle=function(e,t,n){return D.current.useImperativeHandle(e,t,n);},t.useInsertionEffect=function(e,t){return D.current.useInsertionEffect(e,t);},t.useLayoutEffect=function(e,t){return D.current.useLayoutEffect(e,t);},t.useMemo=function(e,t){return D.current.useMemo(e,t);},t.useReducer=function(e,t,n){return D.current.useReducer(e,t,n);},t.useRef=function(e){return D.current.useRef(e);},t.useState=function(e){return D.current.useState(e);},t.useSyncExternalStore=function(e,t,n){return D.current.useSyncExternalStore(e,t,n);},t.useTransition=function(){return D.current.useTransition();},t.version="18.2.0";}(0,t);},"node_modules/react/index.js":function(e,t,n){e.exports=n("node_modules/react/cjs/react.production.min.js");},"node_modules/react/jsx-runtime.js":function(e,t,n){e.exports=n("node_modules/react/cjs/react-jsx-runtime.production.min.js");},"node_modules/scheduler/cjs/scheduler.production.min.js":function(e,t,n){!function(e,t,n){"use strict";function r(e,t){var n=e.length;for(e.push(t);0<n;){var r=n-1>>>1,a=e[r];if(0<o(a,t))e[r]=t,e[n]=a,n=r;else break;}}function a(e){return 0===e.length?null:e[0];}function i(e){if(0===e.length)return null;var t=e[0],n=e.pop();if(n!==t){e[0]=n;for(var r=0,a=e.length,i=a>>>1;r<i;){var l=2*(r+1)-1,u=e[l],s=l+1,c=e[s];if(0>o(u,n))s<a&&0>o(c,u)?(e[r]=c,e[s]=n,r=s):(e[r]=u,e[l]=n,r=l);else if(s<a&&0>o(c,n))e[r]=c,e[s]=n,r=s;else break;}}return t;}function o(e,t){var n=e.sortIndex-t.sortIndex;return 0!==n?n:e.id-t.id;}if("object"==typeof performance&&"function"==typeof performance.now){var l,u=performance;t.unstable_now=function(){return u.now();};}else{var s=Date,c=s.now();t.unstable_now=function(){return s.now()-c;};}var f=[],d=[],h=1,p=null,g=3,v=!1,b=!1,m=!1,y="function"==typeof setTimeout?setTimeout:null,w="function"==typeof clearTimeout?clearTimeout:null,x="undefined"!=typeof setImmediate?setImmediate:null;function S(e){for(var t=a(d);null!==t;){if(null===t.callback)i(d);else if(t.startTime<=e)i(d),t.sortIndex=t.expirationTime,r(f,t);else break;t=a(d);}}function k(e){if(m=!1,S(e),!b){if(null!==a(f))b=!0,F(C);else{var t=a(d);null!==t&&N(k,t.startTime-e);}}}function C(e,n){b=!1,m&&(m=!1,w(E),E=-1),v=!0;var r=g;try{for(S(n),p=a(f);null!==p&&(!(p.expirationTime>n)||e&&!L());){var o=p.callback;if("function"==typeof o){p.callback=null,g=p.priorityLevel;var l=o(p.expirationTime<=n);n=t.unstable_now(),"function"==typeof l?p.callback=l:p===a(f)&&i(f),S(n);}else i(f);p=a(f);}if(null!==p)var u=!0;else{var s=a(d);null!==s&&N(k,s.startTime-n),u=!1;}return u;}finally{p=null,g=r,v=!1;}}"undefined"!=typeof navigator&&void 0!==navigator.scheduling&&void 0!==navigator.scheduling.isInputPending&&navigator.scheduling.isInputPending.bind(navigator.scheduling);var T=!1,z=null,E=-1,M=5,P=-1;function L(){return!(t.unstable_now()-P<M);}function _(){if(null!==z){var e=t.unstable_now();P=e;var n=!0;try{n=z(!0,e);}finally{n?l():(T=!1,z=null);}}else T=!1;}if("function"==typeof x)l=function(){x(_);};else if("undefined"!=typeof MessageChannel){var D=new MessageChannel,j=D.port2;D.port1.onmessage=_,l=function(){j.postMessage(null);};}else l=function(){y(_,0);};function F(e){z=e,T||(T=!0,l());}function N(e,n){E=y(function(){e(t.unstable_now());},n);}t.unstable_IdlePriority=5,t.unstable_ImmediatePriority=1,t.unstable_LowPriority=4,t.unstable_NormalPriority=3,t.unstable_Profiling=null,t.unstable_UserBlockingPriority=2,t.unstable_cancelCallback=function(e){e.callback=null;},t.unstable_continueExecution=function(){b||v||(b=!0,F(C));},t.unstable_forceFrameRate=function(e){0>e||125<e?console.error("forceFrameRate takes a positive int between 0 and 125, forcing frame rates higher than 125 fps is not supported"):M=0<e?Math.floor(1e3/e):5;},t.unstable_getCurrentPriorityLevel=function(){return g;},t.unstable_getFirstCallbackNode=function(){return a(f);},t.unstable_next=function(e){switch(g){case 1:case 2:case 3:var t=3;break;default:t=g;}var n=g;g=t;try{return e();}finally{g=n;}},t.unstable_pauseExecution=function(){},t.unstable_requestPaint=function(){},t.unstable_runWithPriority=function(e,t){switch(e){case 1:case 2:case 3:case 4:case 5:break;default:e=3;}var n=g;g=e;try{return t();}finally{g=n;}},t.unstable_scheduleCallback=function(e,n,i){var o=t.unstable_now();switch(i="object"==typeof i&&null!==i&&"number"==typeof(i=i.delay)&&0<i?o+i:o,e){case 1:var l=-1;break;case 2:l=250;break;case 5:l=1073741823;break;case 4:l=1e4;break;default:l=5e3;}return l=i+l,e={id:h++,callback:n,priorityLevel:e,startTime:i,expirationTime:l,sortIndex:-1},i>o?(e.sortIndex=i,r(d,e),null===a(f)&&e===a(d)&&(m?(w(E),E=-1):m=!0,N(k,i-o))):(e.sortIndex=l,r(f,e),b||v||(b=!0,F(C))),e;},t.unstable_shouldYield=L,t.unstable_wrapCallback=function(e){var t=g;return function(){var n=g;g=t;try{return e.apply(this,arguments);}finally{g=n;}};};}(0,t);},"node_modules/scheduler/index.js":function(e,t,n){e.exports=n("node_modules/scheduler/cjs/scheduler.production.min.js");}},"index.tsx","undefined"!=typeof globalThis?globalThis:self);
\ No newline at end of file
@@ -1,11 +1,11 @@
-import { filesize } from 'filesize';
 import React, { useEffect, useRef, useState } from 'react';
 import ReactDOM from 'react-dom/client';
 
 import FoamTree from '@carrotsearch/foamtree';
+import Sidebar, { formatSize } from './Sidebar';
 import Tooltip from './Tooltip';
-import s from './Tooltip.module.css';
 import Folder from './classUtils/Folder';
+import { AnalyzeModule, AnalyzeReport, SIZE_TYPES, SizeType } from './types';
 
 function App() {
   const chartRef = useRef<HTMLDivElement>(null);
@@ -13,51 +13,46 @@ function App() {
 
   // ref 用于保存Treemap实例
   const treeMapRef = useRef(null);
-  const [chartData, setChartData] = useState('');
+  const [chartData, setChartData] = useState<AnalyzeReport | null>(null);
+  const [sizeType, setSizeType] = useState<SizeType>('minified');
+  const [chunkId, setChunkId] = useState('');
+  const [selected, setSelected] = useState<AnalyzeModule | null>(null);
 
   // toolTip展示使用
   const [tooltipContent, setToolTipContent] = useState('');
-  const createModulesTree = (modules) => {
-    const root = new Folder('.');
-    modules.forEach((module) => root.addModule(module));
+  const createModulesTree = (name: string, modules: AnalyzeModule[]) => {
+    const duplicateNames = new Set(
+      chartData!.duplicates.map((duplicate) => duplicate.name),
+    );
+    const root = new Folder(name);
+    modules.forEach((module) =>
+      root.addModule(
+        module,
+        !!module.package && duplicateNames.has(module.package.name),
+      ),
+    );
     root.mergeNestedFolders();
     return root;
   };
-  const format = (modules: any[]) => {
-    const root = createModulesTree(modules);
-    const res = {
-      'index.js': {
-        tree: root,
-      },
-    };
-
-    const data = Object.entries(res).map(([filename, asset]) => {
-      return {
-        label: filename,
-        isAsset: true,
-        // Not using `asset.size` here provided by Webpack because it can be very confusing when `UglifyJsPlugin` is used.
-        // In this case all module sizes from stats file will represent unminified module sizes, but `asset.size` will
-        // be the size of minified bundle.
-        // Using `asset.size` only if current asset doesn't contain any modules (resulting size equals 0)
-        statSize: asset.tree.size || asset.size,
-        parsedSize: asset?.parsedSize,
-        gzipSize: asset?.gzipSize,
-        groups: Object.values(asset.tree?.children).map((i) => i.toChartData()),
-      };
-    });
-    return data;
+  const format = (report: AnalyzeReport) => {
+    return report.chunks
+      .filter((chunk) => !chunkId || chunk.id === chunkId)
+      .map((chunk) => {
+        const tree = createModulesTree(chunk.id, chunk.modules);
+        return {
+          ...tree.toChartData(),
+          isAsset: true,
+          files: chunk.files,
+        };
+      });
   };
   const filterModulesForSize = (modules, sizeProp) => {
     return modules.reduce((filteredModules, module) => {
       if (module[sizeProp]) {
         if (module.groups) {
-          const showContent = !module.concatenated || false;
-
           module = {
             ...module,
-            groups: showContent
-              ? filterModulesForSize(module.groups, sizeProp)
-              : null,
+            groups: filterModulesForSize(module.groups, sizeProp),
           };
         }
 
@@ -68,13 +63,12 @@ function App() {
       return filteredModules;
     }, []);
   };
-  const renderModuleSize = (module, sizeType) => {
+  const renderModuleSize = (module, sizeType: SizeType) => {
     const sizeProp = `${sizeType}Size`;
     const size = module[sizeProp];
-    const sizeLabel = 'Size';
     return typeof size === 'number' ? (
-      <div className={s.activeSize}>
-        {sizeLabel} size: <strong>{filesize(size)}</strong>
+      <div key={sizeType}>
+        {sizeType} size: <strong>{formatSize(size)}</strong>
       </div>
     ) : null;
   };
@@ -89,26 +83,35 @@ function App() {
         </div>
         <br />
 
-        {renderModuleSize(module, 'stat')}
+        {SIZE_TYPES.map((sizeType) => renderModuleSize(module, sizeType))}
         {module.path && (
           <div>
             Path: <strong>{module.path}</strong>
           </div>
         )}
-        {module.isAsset && (
+        {module.duplicate && (
+          <div>
+            <strong>Contains a duplicate package</strong>
+          </div>
+        )}
+        {module.isAsset &&
+          module.files.map((file) => (
+            <div key={file.name}>
+              File: <strong>{file.name}</strong> {formatSize(file.size)}
+            </div>
+          ))}
+        {module.module && (
           <div>
             <br />
             <strong>
-              <em>Right-click to view options related to this chunk</em>
+              <em>Click to view why this module is included</em>
             </strong>
           </div>
         )}
       </div>
     );
   };
-  const createFoamTree = (chartData: any) => {
-    const formatData = format(chartData?.chunkModules || []);
-    const resData = filterModulesForSize(formatData, 'statSize');
+  const createFoamTree = () => {
     return new FoamTree({
       element: chartRef.current,
       layout: 'squarified',
@@ -127,13 +130,24 @@ function App() {
       groupExposureZoomMargin: 0.2,
       zoomMouseWheelDuration: 300,
       openCloseDuration: 200,
-      dataObject: { groups: resData },
       titleBarDecorator(opts, props, vars) {
         vars.titleBarShown = false;
       },
+      groupColorDecorator(opts, props, vars) {
+        // 重复的包标红
+        if (props.group.duplicate) {
+          vars.groupColor = '#e53935';
+        }
+      },
       onMouseLeave() {
         setVisible(false);
       },
+      onGroupClick(event: { group: any }) {
+        const { group } = event;
+        if (group?.module) {
+          setSelected(group.module);
+        }
+      },
       onGroupHover(event: { group: any }) {
         // 判断是否移动到组中
         const { group } = event;
@@ -142,7 +156,6 @@ function App() {
           setVisible(false);
           return;
         }
-        console.log('group==', group);
         // 显示 tooltip
         if (group) {
           setVisible(true);
@@ -170,13 +183,27 @@ function App() {
       console.warn('数据未初始化!!');
       return;
     }
-    treeMapRef.current = createFoamTree(chartData);
-  }, [chartData]);
+    if (!treeMapRef.current) {
+      treeMapRef.current = createFoamTree();
+    }
+    const groups = filterModulesForSize(format(chartData), `${sizeType}Size`);
+    treeMapRef.current.set({ dataObject: { groups } });
+  }, [chartData, sizeType, chunkId]);
   return (
-    <>
-      <div style={{ width: '100vw', height: '100vh' }} ref={chartRef}></div>
+    <div style={{ position: 'fixed', inset: 0, display: 'flex' }}>
+      {chartData && (
+        <Sidebar
+          report={chartData}
+          sizeType={sizeType}
+          onSizeTypeChange={setSizeType}
+          chunkId={chunkId}
+          onChunkIdChange={setChunkId}
+          selected={selected}
+        />
+      )}
+      <div style={{ flex: 1 }} ref={chartRef}></div>
       <Tooltip visible={visible} content={tooltipContent} />
-    </>
+    </div>
   );
 }
 
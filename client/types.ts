@@ -0,0 +1,57 @@
+export const SIZE_TYPES = [
+  'stat',
+  'parsed',
+  'minified',
+  'gzip',
+  'brotli',
+] as const;
+
+export type SizeType = (typeof SIZE_TYPES)[number];
+
+export interface AnalyzeFile {
+  name: string;
+  size: number;
+  gzipSize: number;
+  brotliSize: number;
+}
+
+export interface AnalyzeModule {
+  id: string;
+  statSize: number;
+  parsedSize: number;
+  minifiedSize: number;
+  gzipSize: number;
+  brotliSize: number;
+  package?: { name: string; version: string };
+  // the shortest import chain from an entry to the module
+  reasons: string[];
+  issuers: string[];
+  concatenated?: string[];
+}
+
+export interface AnalyzeChunk {
+  id: string;
+  files: AnalyzeFile[];
+  modules: AnalyzeModule[];
+}
+
+export interface AnalyzeDuplicate {
+  name: string;
+  versions: { version: string; path: string }[];
+}
+
+export interface AnalyzeDiffItem {
+  id: string;
+  baselineSize: number;
+  size: number;
+  baselineGzipSize: number;
+  gzipSize: number;
+  baselineBrotliSize: number;
+  brotliSize: number;
+}
+
+export interface AnalyzeReport {
+  chunks: AnalyzeChunk[];
+  duplicates: AnalyzeDuplicate[];
+  diff?: AnalyzeDiffItem[];
+}
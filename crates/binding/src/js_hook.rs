@@ -53,6 +53,18 @@ pub struct JsHooks {
       entrypoints: Record<string, { name: string; chunks: string[] }>;
       rscClientComponents: { path; string; moduleId: string }[];
       rscCSSModules: { path; string; moduleId: string; modules: boolean }[];
+      concatenation?: {
+        roots: number;
+        inners: number;
+        bailouts: number;
+        modules: {
+          id: string;
+          status: "root" | "inner" | "bailout";
+          root?: string;
+          inners?: string[];
+          bailouts?: { reason: string; hint: string }[];
+        }[];
+      };
       startTime: number;
       endTime: number;
     };
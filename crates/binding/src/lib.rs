@@ -38,6 +38,11 @@ pub struct BuildParams {
         preserveModules?: boolean;
         preserveModulesRoot?: string;
         skipWrite?: boolean;
+        compress?: false | {
+            algorithms?: ("gzip" | "brotli")[];
+            threshold?: number;
+            minRatio?: number;
+        };
     };
     resolve?: {
        alias?: Array<[string, string]>;
@@ -66,6 +71,13 @@ pub struct BuildParams {
         }
     >;
     copy?: (string | { from: string; to: string })[];
+    criticalCss?:
+        | false
+        | {
+              safelist?: string[];
+              maxSize?: number;
+          };
+    declaration?: boolean | { bundle?: boolean };
     codeSplitting?:
       | false
       | {
@@ -97,6 +109,10 @@ pub struct BuildParams {
     publicPath?: string;
     inlineLimit?: number;
     inlineExcludesExtensions?: string[];
+    image?: false | {
+        optimize?: boolean;
+        srcsetWidths?: number[];
+    };
     targets?: Record<string, number>;
     platform?: "node" | "browser";
     hmr?: false | {};
@@ -112,20 +128,53 @@ pub struct BuildParams {
     };
     stats?: false | {
         modules?: boolean;
+        concatenation?: boolean;
+    };
+    analyze?: false | {
+        baseline?: string;
     };
     hash?: boolean;
     autoCSSModules?: boolean;
     ignoreCSSParserErrors?: boolean;
     dynamicImportToRequire?: boolean;
+    lazyCompilation?: boolean;
+    autoPrefetch?: boolean;
     umd?: false | string | { name: string, export?: string[] };
+    unusedCss?:
+        | false
+        | {
+              purge?: {
+                  content?: string[];
+                  safelist?: string[];
+              };
+          };
     cjs?: boolean;
+    singleFile?: boolean;
     writeToDisk?: boolean;
     transformImport?: { libraryName: string; libraryDirectory?: string; style?: boolean | string, camel2DashComponentName?: boolean }[];
     clean?: boolean;
     nodePolyfill?: boolean;
     ignores?: string[];
-    moduleIdStrategy?: "hashed" | "named";
-    minify?: boolean;
+    moduleIdStrategy?: "hashed" | "named" | "numeric" | "deterministic";
+    recordsPath?: string;
+    minify?:
+      | boolean
+      | {
+          dropConsole?: boolean;
+          dropDebugger?: boolean;
+          pureFuncs?: string[];
+          keepClassnames?: boolean;
+          keepFnames?: boolean;
+          passes?: number;
+          ecma?: number;
+          reserved?: string[];
+          mangleProps?: {
+            regex: string;
+            reserved?: string[];
+            nameCache?: string;
+          };
+          legalComments?: "none" | "inline" | "external";
+        };
     _minifish?: false | {
         mapping: Record<string, string>;
         metaPath?: string;
@@ -149,13 +198,43 @@ pub struct BuildParams {
     };
     emitAssets?: boolean;
     cssModulesExportOnlyLocales?: boolean;
+    cssModules?: {
+        localIdentName?: string;
+        hashSalt?: string;
+        mode?: "local" | "global" | "pure";
+        exportLocalsConvention?:
+            | "asIs"
+            | "camelCase"
+            | "camelCaseOnly"
+            | "dashes"
+            | "dashesOnly";
+        dts?: boolean;
+    };
     inlineCSS?: false | {};
+    cssChunking?: false | {
+        ignoreOrder?: boolean;
+    };
+    cssLowering?: false | {
+        nesting?: boolean;
+        customMedia?: boolean;
+        mediaQueryRanges?: boolean;
+        colorFunctions?: boolean;
+        logicalProperties?: boolean;
+        cascadeLayers?: boolean;
+        dir?: "ltr" | "rtl";
+    };
     rscServer?: false | {
         "emitCSS": boolean;
         "clientComponentTpl": string;
+        "serverActionTpl"?: string;
     };
     rscClient?: false | {
         "logServerComponent": "error" | "ignore";
+        "serverActionTpl"?: string;
+    };
+    serviceWorker?: false | {
+        scope?: string;
+        precacheManifest?: boolean;
     };
     moduleFederation?: {
         name: string;
@@ -167,6 +246,7 @@ pub struct BuildParams {
                 strictVersion?: bool;
                 requiredVersion?: string;
                 eager?: bool;
+                import?: false;
                 shareScope?: string;
             }
         >;
@@ -175,6 +255,12 @@ pub struct BuildParams {
         shareScope?: string;
         shareStrategy?: "version-first" | "loaded-first";
         implementation: string;
+        manifest?: bool;
+        dts?: {
+            generateTypes?: bool;
+            consumeTypes?: Record<string, string>;
+            typesFolder?: string;
+        };
     };
     experimental?: {
         webpackSyntaxValidate?: string[];
@@ -2,6 +2,7 @@ use swc_core::common::SyntaxContext;
 
 pub(crate) mod comments;
 pub(crate) mod css_ast;
+pub(crate) mod dts;
 pub(crate) mod error;
 pub mod file;
 pub(crate) mod js_ast;
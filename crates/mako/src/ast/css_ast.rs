@@ -1,10 +1,12 @@
-use std::fmt;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::{fmt, fs};
 
 use anyhow::{anyhow, Result};
 use md5;
 use swc_core::common::FileName;
-use swc_core::css::ast::Stylesheet;
+use swc_core::css::ast::{ComplexSelector, Stylesheet};
 use swc_core::css::codegen::writer::basic::{BasicCssWriter, BasicCssWriterConfig};
 use swc_core::css::codegen::{CodeGenerator, CodegenConfig, Emit};
 use swc_core::css::modules::{compile, CssClassName, TransformConfig, TransformResult};
@@ -17,10 +19,11 @@ use crate::ast::error;
 use crate::ast::file::{Content, File};
 use crate::ast::sourcemap::build_source_map_to_buf;
 use crate::compiler::Context;
-use crate::config::{DevtoolConfig, Mode};
+use crate::config::{CssModulesMode, DevtoolConfig, ExportLocalsConvention, Mode};
 use crate::module::Dependency;
-use crate::utils::{base64_encode, url_safe_base64_encode};
+use crate::utils::{base64_encode, create_cached_regex, url_safe_base64_encode};
 use crate::visitors::css_dep_analyzer::CSSDepAnalyzer;
+use crate::visitors::css_modules_scope::{CSSImpureSelectors, CSSLocalNames};
 
 #[derive(Clone)]
 pub struct CssAst {
@@ -120,7 +123,8 @@ impl CssAst {
         let mut gen = CodeGenerator::new(
             writer,
             CodegenConfig {
-                minify: context.config.minify && matches!(context.config.mode, Mode::Production),
+                minify: context.config.minify.is_some()
+                    && matches!(context.config.mode, Mode::Production),
             },
         );
         gen.emit(&self.ast).map_err(|err| {
@@ -147,21 +151,62 @@ impl CssAst {
         Ok(CSSAstGenerated { code, sourcemap })
     }
 
-    pub fn compile_css_modules(path: &str, ast: &mut Stylesheet) -> TransformResult {
-        compile(
+    pub fn compile_css_modules(
+        path: &str,
+        ast: &mut Stylesheet,
+        context: &Context,
+    ) -> Result<TransformResult> {
+        let config = &context.config.css_modules;
+        let local_names = match config.mode {
+            CssModulesMode::Global => {
+                let mut visitor = CSSLocalNames::default();
+                ast.visit_with(&mut visitor);
+                Some(visitor.names)
+            }
+            CssModulesMode::Pure => {
+                let mut visitor = CSSImpureSelectors::default();
+                ast.visit_with(&mut visitor);
+                if !visitor.selectors.is_empty() {
+                    return Err(anyhow!(
+                        "Selector {} in {} is not pure, pure selectors must contain at least one local class or id",
+                        visitor.selectors.join(", "),
+                        path
+                    ));
+                }
+                None
+            }
+            CssModulesMode::Local => None,
+        };
+        let result = compile(
             ast,
             CssModuleRename {
                 path: path.to_string(),
+                root: context.root.clone(),
+                local_ident_name: config.local_ident_name.clone(),
+                hash_salt: config.hash_salt.clone(),
+                local_names: local_names.clone(),
             },
-        )
+        );
+        Ok(match local_names {
+            // only the local names are exported in global mode
+            Some(local_names) => TransformResult {
+                renamed: result
+                    .renamed
+                    .into_iter()
+                    .filter(|(name, _)| local_names.contains(name))
+                    .collect(),
+            },
+            None => result,
+        })
     }
 
     pub fn generate_css_modules_exports(
         path: &str,
         ast: &mut Stylesheet,
-        export_only: bool,
-    ) -> String {
-        let result = Self::compile_css_modules(path, ast);
+        context: &Context,
+    ) -> Result<String> {
+        let result = Self::compile_css_modules(path, ast, context)?;
+        let mut imports: Vec<String> = vec![];
         let mut export_names = Vec::new();
         for (name, classes) in result.renamed.iter() {
             let mut after_transform_classes = Vec::new();
@@ -174,37 +219,153 @@ impl CssAst {
                         // e.g. composes foo from global
                         after_transform_classes.push(name.value.to_string());
                     }
-                    CssClassName::Import { name, from: _ } => {
-                        // TODO: support composes from external files
-                        after_transform_classes.push(name.value.to_string());
+                    CssClassName::Import { name, from } => {
+                        // e.g. composes foo from './foo.module.css'
+                        // resolve the class name from the css modules exports of the imported file
+                        let from = from.to_string();
+                        let index = match imports.iter().position(|i| *i == from) {
+                            Some(index) => index,
+                            None => {
+                                imports.push(from);
+                                imports.len() - 1
+                            }
+                        };
+                        after_transform_classes.push(format!(
+                            "${{{}[\"{}\"]}}",
+                            composes_import_name(index),
+                            name.value
+                        ));
                     }
                 }
             }
-            export_names.push((name, after_transform_classes));
+            export_names.push((name.to_string(), after_transform_classes));
         }
+        export_names.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let convention = context.config.css_modules.export_locals_convention;
+        let export_names = export_names
+            .iter()
+            .flat_map(|(name, classes)| {
+                let classes = classes.join(" ").trim().to_string();
+                export_keys(name, convention)
+                    .into_iter()
+                    .map(move |key| (key, classes.clone()))
+            })
+            .collect::<Vec<_>>();
+
+        if context.config.css_modules.dts {
+            Self::emit_css_modules_dts(
+                path,
+                &export_names
+                    .iter()
+                    .map(|(key, _)| key.clone())
+                    .collect::<Vec<_>>(),
+            )?;
+        }
+
+        let imports = imports
+            .iter()
+            .enumerate()
+            .map(|(index, from)| {
+                format!(
+                    "import {} from \"{}?asmodule\";\n",
+                    composes_import_name(index),
+                    from
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("");
         let export_names = export_names
             .iter()
-            .map(|(name, classes)| format!("\"{}\": `{}`", name, classes.join(" ").trim()))
+            .map(|(name, classes)| format!("\"{}\": `{}`", name, classes))
             .collect::<Vec<String>>()
             .join(",");
 
-        if export_only {
-            format!(
+        if context.config.css_modules_export_only_locales {
+            Ok(format!(
                 r#"
-export default {{{}}}
+{}export default {{{}}}
 "#,
-                export_names
-            )
+                imports, export_names
+            ))
         } else {
-            format!(
+            Ok(format!(
                 r#"
-import "{}?modules";
+{}import "{}?modules";
 export default {{{}}}
 "#,
-                path, export_names
-            )
+                imports, path, export_names
+            ))
+        }
+    }
+
+    // e.g. index.module.css => index.module.css.d.ts
+    fn emit_css_modules_dts(path: &str, export_names: &[String]) -> Result<()> {
+        if path.contains("node_modules") {
+            return Ok(());
+        }
+        let content = format!(
+            "// This file is generated by mako, do not edit it manually.\ndeclare const styles: {{\n{}}};\nexport default styles;\n",
+            export_names
+                .iter()
+                .map(|name| format!("  readonly \"{}\": string;\n", name))
+                .collect::<Vec<_>>()
+                .join("")
+        );
+        let dts_path = format!("{}.d.ts", path);
+        // avoid triggering the watcher when nothing changed
+        if fs::read_to_string(&dts_path).is_ok_and(|c| c == content) {
+            return Ok(());
+        }
+        fs::write(&dts_path, content)?;
+        Ok(())
+    }
+
+    pub fn selector_to_string(selector: &ComplexSelector) -> String {
+        let mut code = String::new();
+        let writer = BasicCssWriter::new(&mut code, None, BasicCssWriterConfig::default());
+        let mut gen = CodeGenerator::new(writer, CodegenConfig { minify: true });
+        gen.emit(selector).unwrap();
+        code
+    }
+}
+
+fn composes_import_name(index: usize) -> String {
+    format!("__mako_css_modules_composes_{}", index)
+}
+
+fn export_keys(name: &str, convention: ExportLocalsConvention) -> Vec<String> {
+    let keys = match convention {
+        ExportLocalsConvention::AsIs => vec![name.to_string()],
+        ExportLocalsConvention::CamelCase => vec![name.to_string(), camel_case(name, true)],
+        ExportLocalsConvention::CamelCaseOnly => vec![camel_case(name, true)],
+        ExportLocalsConvention::Dashes => vec![name.to_string(), camel_case(name, false)],
+        ExportLocalsConvention::DashesOnly => vec![camel_case(name, false)],
+    };
+    let mut unique_keys = vec![];
+    for key in keys {
+        if !unique_keys.contains(&key) {
+            unique_keys.push(key);
+        }
+    }
+    unique_keys
+}
+
+// foo-bar_baz => fooBarBaz, only dashes are handled when underscore is false
+fn camel_case(name: &str, underscore: bool) -> String {
+    let mut result = String::with_capacity(name.len());
+    let mut upper_next = false;
+    for c in name.chars() {
+        if c == '-' || (underscore && c == '_') {
+            upper_next = !result.is_empty();
+        } else if upper_next {
+            result.extend(c.to_uppercase());
+            upper_next = false;
+        } else {
+            result.push(c);
         }
     }
+    result
 }
 
 pub struct CSSAstGenerated {
@@ -214,20 +375,188 @@ pub struct CSSAstGenerated {
 
 struct CssModuleRename {
     pub path: String,
+    pub root: PathBuf,
+    pub local_ident_name: String,
+    pub hash_salt: String,
+    // only these names are renamed in global mode
+    pub local_names: Option<HashSet<atoms::JsWord>>,
 }
 
 impl TransformConfig for CssModuleRename {
     fn new_name_for(&self, local: &atoms::JsWord) -> atoms::JsWord {
+        if let Some(local_names) = &self.local_names
+            && !local_names.contains(local)
+        {
+            return local.clone();
+        }
         let name = local.to_string();
-        let new_name = ident_name(&self.path, &name);
+        let new_name = ident_name(
+            &self.path,
+            &name,
+            &self.root,
+            &self.local_ident_name,
+            &self.hash_salt,
+        );
         new_name.into()
     }
 }
 
-fn ident_name(path: &str, name: &str) -> String {
-    let source = format!("{}__{}", path, name);
+/**
+ * Generate the class name by the localIdentName template, supported placeholders:
+ * [local], [name], [folder], [path], [hash], [hash:<base64|hex>:<length>]
+ */
+fn ident_name(
+    path: &str,
+    name: &str,
+    root: &Path,
+    local_ident_name: &str,
+    hash_salt: &str,
+) -> String {
+    let source = format!("{}__{}{}", path, name, hash_salt);
     let digest = md5::compute(source);
-    let hash = url_safe_base64_encode(digest.0);
-    let hash_slice = hash[..8].to_string();
-    format!("{}-{}", name, hash_slice)
+
+    let file_path = Path::new(path);
+    let file_name = file_path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    // a.module.css => a
+    let file_stem = file_name
+        .split_once('.')
+        .map(|(stem, _)| stem.to_string())
+        .unwrap_or(file_name.clone());
+    let dir = file_path.parent().unwrap_or(Path::new(""));
+    let folder = dir
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let relative_dir = dir
+        .strip_prefix(root)
+        .unwrap_or(dir)
+        .to_string_lossy()
+        .to_string();
+
+    let placeholder =
+        create_cached_regex(r"\[(local|name|folder|path|hash)(?::(base64|hex))?(?::(\d+))?\]");
+    let ident =
+        placeholder.replace_all(local_ident_name, |caps: &regex::Captures| match &caps[1] {
+            "local" => name.to_string(),
+            "name" => file_stem.clone(),
+            "folder" => folder.clone(),
+            "path" => relative_dir.clone(),
+            _ => {
+                let hash = match caps.get(2).map(|m| m.as_str()) {
+                    Some("hex") => format!("{:x}", digest),
+                    _ => url_safe_base64_encode(digest.0),
+                };
+                let length = caps
+                    .get(3)
+                    .and_then(|m| m.as_str().parse::<usize>().ok())
+                    .unwrap_or(8)
+                    .min(hash.len());
+                hash[..length].to_string()
+            }
+        });
+
+    // replace the characters which are not allowed in class names
+    let ident = ident
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' || !c.is_ascii() {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect::<String>();
+    if ident.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{}", ident)
+    } else {
+        ident
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::compiler::Context;
+
+    #[test]
+    fn test_ident_name() {
+        let root = PathBuf::from("/project");
+        let path = "/project/src/pages/index.module.css";
+        assert_eq!(
+            ident_name(path, "foo", &root, "[local]-[hash:base64:8]", ""),
+            ident_name(path, "foo", &root, "[local]-[hash]", "")
+        );
+        assert_eq!(
+            ident_name(path, "foo", &root, "[name]__[local]", ""),
+            "index__foo"
+        );
+        assert_eq!(
+            ident_name(path, "foo", &root, "[path]_[folder]_[local]", ""),
+            "src-pages_pages_foo"
+        );
+        assert_eq!(ident_name(path, "foo", &root, "x[hash:hex:6]", "").len(), 7);
+        assert_ne!(
+            ident_name(path, "foo", &root, "[hash]", ""),
+            ident_name(path, "foo", &root, "[hash]", "salt")
+        );
+    }
+
+    #[test]
+    fn test_export_locals_convention() {
+        assert_eq!(
+            export_keys("foo-bar_baz", ExportLocalsConvention::CamelCase),
+            vec!["foo-bar_baz", "fooBarBaz"]
+        );
+        assert_eq!(
+            export_keys("foo-bar_baz", ExportLocalsConvention::DashesOnly),
+            vec!["fooBar_baz"]
+        );
+        assert_eq!(
+            export_keys("foo", ExportLocalsConvention::CamelCase),
+            vec!["foo"]
+        );
+    }
+
+    #[test]
+    fn test_composes_from_other_file() {
+        let exports = generate_exports(
+            ".a{composes: b c from './other.module.css';color:red}",
+            Context::default(),
+        );
+        assert!(exports.contains(
+            r#"import __mako_css_modules_composes_0 from "./other.module.css?asmodule";"#
+        ));
+        assert!(exports.contains(r#"${__mako_css_modules_composes_0["b"]}"#));
+        assert!(exports.contains(r#"${__mako_css_modules_composes_0["c"]}"#));
+    }
+
+    #[test]
+    fn test_global_mode() {
+        let mut context = Context::default();
+        context.config.css_modules.mode = CssModulesMode::Global;
+        context.config.css_modules.local_ident_name = "[local]_x".to_string();
+        let exports = generate_exports(".a{} :local(.b){}", context);
+        assert!(exports.contains(r#"export default {"b": `b_x`}"#));
+    }
+
+    #[test]
+    fn test_pure_mode() {
+        let mut context = Context::default();
+        context.config.css_modules.mode = CssModulesMode::Pure;
+        let context = Arc::new(context);
+        let mut ast = CssAst::build("test.css", "div{}", context.clone(), true).unwrap();
+        assert!(CssAst::compile_css_modules("test.css", &mut ast.ast, &context).is_err());
+    }
+
+    fn generate_exports(code: &str, context: Context) -> String {
+        let context = Arc::new(context);
+        let mut ast = CssAst::build("test.css", code, context.clone(), true).unwrap();
+        CssAst::generate_css_modules_exports("test.css", &mut ast.ast, &context).unwrap()
+    }
 }
@@ -0,0 +1,181 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::Result;
+use colored::Colorize;
+use swc_core::common::{FileName, GLOBALS};
+use swc_core::ecma::ast::{
+    ExportAll, ImportDecl, Module, ModuleDecl, ModuleItem, NamedExport, Str, TsImportType,
+};
+use swc_core::ecma::codegen::text_writer::JsWriter;
+use swc_core::ecma::codegen::{Config as JsCodegenConfig, Emitter};
+use swc_core::ecma::visit::{VisitMut, VisitMutWith};
+use swc_core::typescript::fast_dts::FastDts;
+
+use crate::ast::js_ast::JsAst;
+use crate::compiler::Context;
+use crate::module::relative_to_root;
+
+// transform a typescript module to its declaration with isolated declarations, types which
+// can't be inferred without a type checker are reported as warnings
+pub(crate) fn transform_dts(path: &str, content: &str, context: Arc<Context>) -> Result<Module> {
+    let mut ast = JsAst::build(path, content, context.clone())?;
+    let issues = GLOBALS.set(&context.meta.script.globals, || {
+        FastDts::new(Arc::new(FileName::Real(PathBuf::from(path)))).transform(&mut ast.ast)
+    });
+    let cm = context.meta.script.cm.clone();
+    for issue in issues {
+        let loc = cm.lookup_char_pos(issue.range().span.lo);
+        println!(
+            "{}: {} in {}:{}:{}",
+            "warning".to_string().yellow(),
+            issue,
+            relative_to_root(&path.to_string(), &context.root),
+            loc.line,
+            loc.col_display + 1,
+        );
+    }
+    Ok(ast.ast)
+}
+
+pub(crate) fn emit_dts(ast: &Module, context: &Arc<Context>) -> Result<String> {
+    let cm = context.meta.script.cm.clone();
+    let mut buf = vec![];
+    {
+        let comments = context.meta.script.origin_comments.read().unwrap();
+        let mut emitter = Emitter {
+            cfg: JsCodegenConfig::default(),
+            cm: cm.clone(),
+            comments: Some(comments.get_swc_comments()),
+            wr: Box::new(JsWriter::new(cm, "\n", &mut buf, None)),
+        };
+        emitter.emit_module(ast)?;
+    }
+    Ok(String::from_utf8(buf)?)
+}
+
+// sources of the import and re-export declarations
+pub(crate) fn get_dts_sources(ast: &Module) -> Vec<&Str> {
+    ast.body
+        .iter()
+        .filter_map(|item| match item {
+            ModuleItem::ModuleDecl(ModuleDecl::Import(import)) => Some(&*import.src),
+            ModuleItem::ModuleDecl(ModuleDecl::ExportAll(export)) => Some(&*export.src),
+            ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(export)) => export.src.as_deref(),
+            _ => None,
+        })
+        .collect()
+}
+
+// replace the sources of the import and re-export declarations, and `import("...")` types
+pub(crate) fn replace_dts_sources(ast: &mut Module, replace: &dyn Fn(&str) -> Option<String>) {
+    ast.visit_mut_with(&mut DtsSourceReplacer { replace });
+}
+
+struct DtsSourceReplacer<'a> {
+    replace: &'a dyn Fn(&str) -> Option<String>,
+}
+
+impl DtsSourceReplacer<'_> {
+    fn replace_str(&self, src: &mut Str) {
+        if let Some(replacement) = (self.replace)(&src.value) {
+            src.value = replacement.into();
+            src.raw = None;
+        }
+    }
+}
+
+impl VisitMut for DtsSourceReplacer<'_> {
+    fn visit_mut_import_decl(&mut self, import: &mut ImportDecl) {
+        self.replace_str(&mut import.src);
+    }
+
+    fn visit_mut_export_all(&mut self, export: &mut ExportAll) {
+        self.replace_str(&mut export.src);
+    }
+
+    fn visit_mut_named_export(&mut self, export: &mut NamedExport) {
+        if let Some(src) = &mut export.src {
+            self.replace_str(src);
+        }
+    }
+
+    fn visit_mut_ts_import_type(&mut self, import: &mut TsImportType) {
+        self.replace_str(&mut import.arg);
+        import.visit_mut_children_with(self);
+    }
+}
+
+pub(crate) fn resolve_ts_file(dir: &Path, source: &str) -> Option<PathBuf> {
+    let base = dir.join(source);
+    let base_str = base.to_string_lossy().to_string();
+    // `./foo.js` refers to `./foo.ts` in typescript esm
+    let stem = base_str
+        .strip_suffix(".js")
+        .or_else(|| base_str.strip_suffix(".jsx"))
+        .unwrap_or(&base_str);
+    [
+        base_str.clone(),
+        format!("{}.ts", stem),
+        format!("{}.tsx", stem),
+        format!("{}.d.ts", stem),
+        format!("{}/index.ts", base_str),
+        format!("{}/index.tsx", base_str),
+        format!("{}/index.d.ts", base_str),
+    ]
+    .into_iter()
+    .map(PathBuf::from)
+    .find(|p| p.is_file() && is_ts_file(p))
+    .and_then(|p| p.canonicalize().ok())
+}
+
+pub(crate) fn is_ts_file(path: &Path) -> bool {
+    let path = path.to_string_lossy();
+    path.ends_with(".ts") || path.ends_with(".tsx")
+}
+
+pub(crate) fn strip_dts_ext(path: &str) -> &str {
+    [".d.ts", ".tsx", ".ts"]
+        .iter()
+        .find_map(|ext| path.strip_suffix(ext))
+        .unwrap_or(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{emit_dts, get_dts_sources, transform_dts};
+    use crate::compiler::Context;
+    use crate::plugins::declaration::has_default_export;
+
+    #[test]
+    fn test_transform_dts() {
+        let context = std::sync::Arc::new(Context::default());
+        let ast = transform_dts(
+            "/src/App.ts",
+            r#"
+import type { Props } from './types';
+export function add(a: number, b: number): number { return a + b; }
+export const name: string = 'app';
+export default class App { private count = 1; render(props: Props): void {} }
+            "#,
+            context.clone(),
+        )
+        .unwrap();
+        let sources = get_dts_sources(&ast)
+            .into_iter()
+            .map(|src| src.value.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(sources, vec!["./types"]);
+        assert!(has_default_export(&ast));
+        assert_eq!(
+            emit_dts(&ast, &context).unwrap().trim(),
+            r#"import type { Props } from './types';
+export declare function add(a: number, b: number): number;
+export declare const name: string;
+export default class App {
+    private count: number;
+    render(props: Props): void;
+}"#
+        );
+    }
+}
@@ -194,7 +194,7 @@ impl JsAst {
             let comments = context.meta.script.origin_comments.read().unwrap();
             let swc_comments = comments.get_swc_comments();
             let is_prod = matches!(context.config.mode, Mode::Production);
-            let minify = context.config.minify && is_prod;
+            let minify = context.config.minify.is_some() && is_prod;
             let ascii_only = if context.config.output.mode == OutputMode::Bundless {
                 false
             } else {
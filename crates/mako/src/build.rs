@@ -1,4 +1,5 @@
 pub(crate) mod analyze_deps;
+pub(crate) mod image;
 pub(crate) mod load;
 pub(crate) mod parse;
 pub(crate) mod targets;
@@ -15,7 +16,7 @@ use thiserror::Error;
 use crate::ast::file::{Content, File, JsContent};
 use crate::ast::utils::get_module_system;
 use crate::compiler::{Compiler, Context};
-use crate::generate::chunk_pot::util::hash_hashmap;
+use crate::generate::chunk_pot::util::{hash_hashmap, hash_vec};
 use crate::module::{FedereationModuleType, Module, ModuleAst, ModuleId, ModuleInfo, ModuleSystem};
 use crate::plugin::NextBuildParam;
 use crate::resolve::{ConsumeSharedInfo, RemoteInfo, ResolverResource};
@@ -333,8 +334,16 @@ __mako_require__.loadScript('{}', (e) => e.type === 'load' ? resolve() : reject(
         // raw_hash is only used in watch mode
         // so we don't need to calculate when watch is off
         let raw_hash = if context.args.watch {
-            file.get_raw_hash()
-                .wrapping_add(hash_hashmap(&deps.missing_deps))
+            // the exports of css modules only change with the class names,
+            // hash the generated class map so that the dependents are not updated
+            // when only the declarations are changed
+            let content_hash = match &ast {
+                ModuleAst::Script(ast) if file.has_param("asmodule") => {
+                    hash_vec(&[ast.generate(context.clone())?.code])
+                }
+                _ => file.get_raw_hash(),
+            };
+            content_hash.wrapping_add(hash_hashmap(&deps.missing_deps))
         } else {
             0
         };
@@ -0,0 +1,379 @@
+mod jpeg;
+mod png;
+mod svg;
+
+use std::fs;
+use std::io::Cursor;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::webp::WebPEncoder;
+use image::imageops::FilterType;
+use image::io::Reader;
+use image::{ColorType, DynamicImage, ImageFormat};
+use thiserror::Error;
+
+use crate::ast::file::File;
+use crate::compiler::Context;
+use crate::utils::base64_encode;
+
+pub const IMAGE_EXTENSIONS: [&str; 5] = ["png", "jpg", "jpeg", "webp", "svg"];
+const OUTPUT_FORMATS: [&str; 2] = ["png", "webp"];
+// bump it when the output of the pipeline changes, so that the cache is invalidated
+const CACHE_VERSION: &str = "2";
+// the quality of the resized jpeg, it's re-encoded since the jpeg encoding is lossy
+const JPEG_QUALITY: u8 = 90;
+
+#[derive(Debug, Error)]
+enum ImageError {
+    #[error("Invalid image query `{key}={value}` in {path:?}")]
+    InvalidQuery {
+        key: String,
+        value: String,
+        path: String,
+    },
+    #[error("Unsupported image variant of {path:?}: {reason}")]
+    UnsupportedVariant { path: String, reason: String },
+}
+
+/**
+ * The resized or transcoded variant of an image, e.g. `./a.png?w=400&format=webp`.
+ */
+#[derive(Debug, Default, Clone)]
+pub struct ImageVariant {
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub format: Option<String>,
+}
+
+impl ImageVariant {
+    pub fn from_file(file: &File) -> Result<Self> {
+        let size = |key: &str| -> Result<Option<u32>> {
+            file.param(key)
+                .map(|value| {
+                    value
+                        .parse::<u32>()
+                        .ok()
+                        .filter(|size| *size > 0)
+                        .ok_or_else(|| invalid_query(file, key, &value))
+                })
+                .transpose()
+        };
+        let format = file.param("format");
+        if let Some(format) = &format
+            && !OUTPUT_FORMATS.contains(&format.as_str())
+        {
+            return Err(invalid_query(file, "format", format));
+        }
+        Ok(Self {
+            width: size("w")?,
+            height: size("h")?,
+            format,
+        })
+    }
+
+    fn is_empty(&self) -> bool {
+        self.width.is_none() && self.height.is_none() && self.format.is_none()
+    }
+
+    fn cache_key(&self) -> String {
+        format!(
+            "w={:?}&h={:?}&format={:?}",
+            self.width, self.height, self.format
+        )
+    }
+}
+
+/**
+ * The output of the image pipeline, it's written to the cache directory and emitted to the
+ * output directory as an asset.
+ */
+pub struct ProcessedImage {
+    pub path: PathBuf,
+    pub file_name: String,
+    pub size: u64,
+}
+
+impl ProcessedImage {
+    pub fn get_base64(&self) -> Result<String> {
+        let content = fs::read(&self.path)?;
+        let mime = mime_guess::from_path(&self.file_name).first_or_octet_stream();
+        Ok(format!("data:{};base64,{}", mime, base64_encode(content)))
+    }
+}
+
+pub fn is_image(file: &File) -> bool {
+    IMAGE_EXTENSIONS.contains(&file.extname.as_str())
+}
+
+/**
+ * Optimize the image or create the variant of the query, `None` if nothing needs to be done,
+ * e.g. `optimize` is disabled and there's no query.
+ */
+pub fn process(
+    file: &File,
+    variant: &ImageVariant,
+    context: &Arc<Context>,
+) -> Result<Option<ProcessedImage>> {
+    let Some(config) = &context.config.image else {
+        return Ok(None);
+    };
+    // webp is not optimized, since it's usually optimized when encoded
+    if !is_image(file) || variant.is_empty() && (!config.optimize || file.extname == "webp") {
+        return Ok(None);
+    }
+    let extname = variant
+        .format
+        .clone()
+        .unwrap_or_else(|| file.extname.clone());
+    let content = fs::read(&file.pathname)?;
+    let mut key = md5::Context::new();
+    key.consume(CACHE_VERSION);
+    key.consume(&content);
+    key.consume(variant.cache_key());
+    key.consume([config.optimize as u8]);
+    let cache_dir = cache_dir(context);
+    let path = cache_dir.join(format!("{:x}.{}", key.compute(), extname));
+
+    let output = if path.exists() {
+        fs::read(&path)?
+    } else {
+        let output = transform(file, &content, variant, config.optimize)?;
+        fs::create_dir_all(&cache_dir)?;
+        // written to a temporary file first, since the same image may be processed in parallel
+        let tmp = path.with_extension(format!("{}.{}", extname, nanoid::nanoid!()));
+        fs::write(&tmp, &output)?;
+        fs::rename(&tmp, &path)?;
+        output
+    };
+    let hash = format!("{:x}", md5::compute(&output));
+    Ok(Some(ProcessedImage {
+        path,
+        file_name: format!("{}.{}.{}", file.get_file_stem(), &hash[0..8], extname),
+        size: output.len() as u64,
+    }))
+}
+
+/**
+ * The variants of `?srcset` or `?srcset=400,800`, the widths larger than the image are replaced
+ * with the width of the image. Returns (image, width, height) from the smallest to the largest.
+ */
+pub fn srcset(file: &File, context: &Arc<Context>) -> Result<Vec<(ProcessedImage, u32, u32)>> {
+    let config = context.config.image.as_ref().unwrap();
+    if !matches!(file.extname.as_str(), "png" | "webp" | "jpg" | "jpeg") {
+        return Err(anyhow!(ImageError::UnsupportedVariant {
+            path: file.path.to_string_lossy().to_string(),
+            reason: format!("`?srcset` is not supported for {}", file.extname),
+        }));
+    }
+    let widths = match file.param("srcset").filter(|value| !value.is_empty()) {
+        Some(value) => value
+            .split(',')
+            .map(|width| {
+                width
+                    .trim()
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|width| *width > 0)
+                    .ok_or_else(|| invalid_query(file, "srcset", &value))
+            })
+            .collect::<Result<Vec<_>>>()?,
+        None => config.srcset_widths.clone(),
+    };
+    let (source_width, source_height) = Reader::open(&file.pathname)?
+        .with_guessed_format()?
+        .into_dimensions()?;
+    let mut widths = widths
+        .into_iter()
+        .map(|width| width.min(source_width))
+        .collect::<Vec<_>>();
+    widths.sort();
+    widths.dedup();
+
+    let base = ImageVariant::from_file(file)?;
+    widths
+        .into_iter()
+        .map(|width| {
+            let variant = ImageVariant {
+                width: Some(width),
+                height: None,
+                ..base.clone()
+            };
+            let image = process(file, &variant, context)?.unwrap();
+            let height = scale(source_height, width, source_width);
+            Ok((image, width, height))
+        })
+        .collect()
+}
+
+fn transform(
+    file: &File,
+    content: &[u8],
+    variant: &ImageVariant,
+    optimize: bool,
+) -> Result<Vec<u8>> {
+    let unsupported = |reason: &str| {
+        anyhow!(ImageError::UnsupportedVariant {
+            path: file.path.to_string_lossy().to_string(),
+            reason: reason.to_string(),
+        })
+    };
+    match file.extname.as_str() {
+        "svg" => {
+            if !variant.is_empty() {
+                return Err(unsupported("svg can't be resized or transcoded"));
+            }
+            Ok(svg::minify(&String::from_utf8_lossy(content))?.into_bytes())
+        }
+        "jpg" | "jpeg" if variant.is_empty() => jpeg::optimize(content),
+        _ => {
+            let is_png = file.extname == "png";
+            if variant.is_empty() && is_png && optimize {
+                return png::optimize(content);
+            }
+            let format = ImageFormat::from_extension(&file.extname).unwrap();
+            let image = resize(
+                image::load_from_memory_with_format(content, format)?,
+                variant,
+            );
+            match variant.format.as_deref().unwrap_or(&file.extname) {
+                "jpg" | "jpeg" => {
+                    let mut output = vec![];
+                    JpegEncoder::new_with_quality(&mut output, JPEG_QUALITY)
+                        .encode_image(&image.to_rgb8())?;
+                    Ok(output)
+                }
+                "png" => png::encode(&image, is_png.then_some(content)),
+                _ => {
+                    let mut output = vec![];
+                    WebPEncoder::new_lossless(Cursor::new(&mut output)).encode(
+                        &image.to_rgba8(),
+                        image.width(),
+                        image.height(),
+                        ColorType::Rgba8,
+                    )?;
+                    Ok(output)
+                }
+            }
+        }
+    }
+}
+
+// the aspect ratio is kept if only one of the width and height is specified, otherwise the
+// image is resized to cover the size and cropped
+fn resize(image: DynamicImage, variant: &ImageVariant) -> DynamicImage {
+    let (width, height) = (image.width(), image.height());
+    match (variant.width, variant.height) {
+        (Some(w), Some(h)) => image.resize_to_fill(w, h, FilterType::Lanczos3),
+        (Some(w), None) => image.resize_exact(w, scale(height, w, width), FilterType::Lanczos3),
+        (None, Some(h)) => image.resize_exact(scale(width, h, height), h, FilterType::Lanczos3),
+        (None, None) => image,
+    }
+}
+
+// size * numerator / denominator, at least 1
+fn scale(size: u32, numerator: u32, denominator: u32) -> u32 {
+    ((size as f64 * numerator as f64 / denominator as f64).round() as u32).max(1)
+}
+
+fn cache_dir(context: &Context) -> PathBuf {
+    context.root.join("node_modules/.cache_mako/images")
+}
+
+fn invalid_query(file: &File, key: &str, value: &str) -> anyhow::Error {
+    anyhow!(ImageError::InvalidQuery {
+        key: key.to_string(),
+        value: value.to_string(),
+        path: file.path.to_string_lossy().to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use image::GenericImageView;
+
+    use crate::compiler::Compiler;
+    use crate::config::Config;
+
+    #[test]
+    fn test_image_pipeline() {
+        let root = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test/build/image");
+        let config = Config::new(&root, None, None).unwrap();
+        let compiler = Compiler::new(config, root.clone(), Default::default(), None).unwrap();
+        compiler.compile().unwrap();
+
+        let dist = root.join("dist");
+        let mut assets = compiler
+            .context
+            .assets_info
+            .lock()
+            .unwrap()
+            .values()
+            .cloned()
+            .collect::<Vec<_>>();
+        assets.sort();
+        let find = |prefix: &str, ext: &str| {
+            assets
+                .iter()
+                .filter(|asset| asset.starts_with(prefix) && asset.ends_with(ext))
+                .map(|asset| dist.join(asset))
+                .collect::<Vec<_>>()
+        };
+        // the optimized one, the variant of `?w=16` and the 16 and 32 wide srcset
+        // share the same content, and 128 is replaced with the width of the image
+        let pngs = find("logo.", ".png");
+        assert_eq!(pngs.len(), 4, "{:?}", assets);
+        let original = image::open(root.join("logo.png")).unwrap();
+        let optimized = pngs
+            .iter()
+            .find(|png| image::open(png).unwrap().dimensions() == (64, 48))
+            .unwrap();
+        assert!(
+            fs::metadata(optimized).unwrap().len()
+                < fs::metadata(root.join("logo.png")).unwrap().len()
+        );
+        assert_eq!(
+            image::open(optimized).unwrap().to_rgba8(),
+            original.to_rgba8()
+        );
+
+        let webps = find("logo.", ".webp");
+        assert_eq!(webps.len(), 1);
+        assert_eq!(image::open(&webps[0]).unwrap().dimensions(), (16, 12));
+
+        // jpeg is resized and transcoded like png
+        let photo_webps = find("photo.", ".webp");
+        assert_eq!(photo_webps.len(), 1, "{:?}", assets);
+        assert_eq!(image::open(&photo_webps[0]).unwrap().dimensions(), (32, 24));
+        let photo_jpgs = find("photo.", ".jpg");
+        let mut dimensions = photo_jpgs
+            .iter()
+            .map(|jpg| image::open(jpg).unwrap().dimensions())
+            .collect::<Vec<_>>();
+        dimensions.sort();
+        dimensions.dedup();
+        assert!(dimensions.contains(&(16, 12)), "{:?}", dimensions);
+        assert!(dimensions.contains(&(32, 24)), "{:?}", dimensions);
+        assert!(root.join("node_modules/.cache_mako/images").is_dir());
+
+        let svgs = find("icon.", ".svg");
+        let svg = fs::read_to_string(&svgs[0]).unwrap();
+        assert!(
+            !svg.contains("<!--") && !svg.contains("metadata"),
+            "{}",
+            svg
+        );
+
+        let js = fs::read_to_string(dist.join("index.js")).unwrap();
+        assert!(js.contains("16w, "), "{}", js);
+        assert!(js.contains("32w, "));
+        assert!(js.contains("64w`"));
+
+        fs::remove_dir_all(root.join("node_modules")).unwrap();
+    }
+}
@@ -0,0 +1,114 @@
+use anyhow::Result;
+use exif::{In, Reader, Tag};
+use img_parts::jpeg::{markers, Jpeg};
+use img_parts::Bytes;
+
+const EXIF_PREFIX: &[u8] = b"Exif\0\0";
+
+/**
+ * Optimize the jpeg losslessly by stripping the metadata segments, e.g. the comments, the xmp
+ * and the exif with its thumbnail. The segments which affect the decoded pixels are kept, i.e.
+ * JFIF, the icc profile, the Adobe color transform and the exif with a rotated orientation.
+ */
+pub(crate) fn optimize(content: &[u8]) -> Result<Vec<u8>> {
+    let mut jpeg = Jpeg::from_bytes(Bytes::copy_from_slice(content))?;
+    jpeg.segments_mut()
+        .retain(|segment| keep(segment.marker(), segment.contents()));
+    let out = jpeg.encoder().bytes();
+    if out.len() < content.len() {
+        Ok(out.to_vec())
+    } else {
+        Ok(content.to_vec())
+    }
+}
+
+fn keep(marker: u8, data: &[u8]) -> bool {
+    match marker {
+        markers::APP0 => data.starts_with(b"JFIF\0"),
+        markers::APP1 => exif_orientation(data).is_some_and(|orientation| orientation != 1),
+        markers::APP2 => data.starts_with(b"ICC_PROFILE\0"),
+        markers::APP14 => data.starts_with(b"Adobe"),
+        markers::APP3..=markers::APP15 | markers::COM => false,
+        _ => true,
+    }
+}
+
+// the orientation tag in the IFD0 of the exif
+fn exif_orientation(data: &[u8]) -> Option<u32> {
+    let tiff = data.strip_prefix(EXIF_PREFIX)?;
+    let exif = Reader::new().read_raw(tiff.to_vec()).ok()?;
+    exif.get_field(Tag::Orientation, In::PRIMARY)?
+        .value
+        .get_uint(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use img_parts::jpeg::markers::{APP0, APP1, COM, EOI, SOI, SOS};
+
+    use super::*;
+
+    fn segment(marker: u8, data: &[u8]) -> Vec<u8> {
+        let mut segment = vec![0xff, marker];
+        segment.extend_from_slice(&((data.len() + 2) as u16).to_be_bytes());
+        segment.extend_from_slice(data);
+        segment
+    }
+
+    fn exif(orientation: u16) -> Vec<u8> {
+        let mut data = b"Exif\0\0MM\0\x2a\0\0\0\x08".to_vec();
+        // one entry, tag, type SHORT, count 1, value
+        data.extend_from_slice(&[0, 1, 0x01, 0x12, 0, 3, 0, 0, 0, 1]);
+        data.extend_from_slice(&orientation.to_be_bytes());
+        data.extend_from_slice(&[0, 0, 0, 0, 0, 0]);
+        data
+    }
+
+    #[test]
+    fn test_optimize() {
+        let jfif = segment(APP0, b"JFIF\0\x01\x01\0\0\x01\0\x01\0\0");
+        let dqt = segment(0xdb, &[0; 65]);
+        let sos = segment(SOS, &[1, 1, 0, 0, 0x3f, 0]);
+        // the stuffed 0xff and a restart marker
+        let scan = [0x12, 0xff, 0x00, 0x34, 0xff, 0xd0, 0x56];
+        let jpeg = |segments: &[Vec<u8>]| {
+            let mut jpeg = vec![0xff, SOI];
+            segments.iter().for_each(|s| jpeg.extend_from_slice(s));
+            jpeg.extend_from_slice(&sos);
+            jpeg.extend_from_slice(&scan);
+            jpeg.extend_from_slice(&[0xff, EOI]);
+            jpeg
+        };
+        let original = jpeg(&[
+            jfif.clone(),
+            segment(APP1, &exif(1)),
+            segment(APP1, b"http://ns.adobe.com/xap/1.0/\0<x:xmpmeta/>"),
+            segment(COM, b"created by an editor"),
+            dqt.clone(),
+        ]);
+        assert_eq!(
+            optimize(&original).unwrap(),
+            jpeg(&[jfif.clone(), dqt.clone()])
+        );
+
+        let rotated = segment(APP1, &exif(6));
+        let original = jpeg(&[jfif.clone(), rotated.clone(), dqt.clone()]);
+        assert_eq!(
+            optimize(&original).unwrap(),
+            original,
+            "the rotated exif is kept"
+        );
+    }
+
+    #[test]
+    fn test_optimize_invalid_segment_length() {
+        let jpeg = [0xff, SOI, 0xff, 0xdb, 0x00, 0x01, 0x00, 0xff, EOI];
+        assert!(optimize(&jpeg).is_err());
+    }
+
+    #[test]
+    fn test_exif_orientation() {
+        assert_eq!(exif_orientation(&exif(6)), Some(6));
+        assert_eq!(exif_orientation(b"Exif\0\0"), None);
+    }
+}
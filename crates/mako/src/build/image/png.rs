@@ -0,0 +1,133 @@
+use anyhow::Result;
+use image::DynamicImage;
+use img_parts::png::Png;
+use img_parts::Bytes;
+use oxipng::{BitDepth, ColorType, Options, RawImage, StripChunks};
+
+const ANIMATION_CHUNKS: [&[u8; 4]; 3] = [b"acTL", b"fcTL", b"fdAT"];
+
+// the ancillary chunks which affect how the image is displayed are kept, the others such as
+// the texts and the timestamps are stripped
+fn options() -> Options {
+    Options {
+        strip: StripChunks::Safe,
+        ..Options::from_preset(2)
+    }
+}
+
+/**
+ * Optimize the png losslessly with oxipng, the pixels are re-encoded with the smallest color
+ * type and bit depth, e.g. an opaque rgba image is encoded as rgb, an image with less than 256
+ * colors is encoded with a palette. The original is returned if it's smaller.
+ */
+pub(crate) fn optimize(content: &[u8]) -> Result<Vec<u8>> {
+    Ok(oxipng::optimize_from_memory(content, &options())?)
+}
+
+/**
+ * Encode the image with the smallest color type and bit depth which keep all the pixels, the
+ * display chunks of the source png are kept.
+ */
+pub(crate) fn encode(image: &DynamicImage, source: Option<&[u8]>) -> Result<Vec<u8>> {
+    let color = image.color();
+    let (bit_depth, data) = if color.bytes_per_pixel() > color.channel_count() {
+        let data = image
+            .to_rgba16()
+            .into_raw()
+            .into_iter()
+            .flat_map(u16::to_be_bytes)
+            .collect();
+        (BitDepth::Sixteen, data)
+    } else {
+        (BitDepth::Eight, image.to_rgba8().into_raw())
+    };
+    let mut raw = RawImage::new(
+        image.width(),
+        image.height(),
+        ColorType::RGBA,
+        bit_depth,
+        data,
+    )?;
+    if let Some(source) = source {
+        // the animation is lost when decoded
+        for chunk in Png::from_bytes(Bytes::copy_from_slice(source))?
+            .chunks()
+            .iter()
+            .filter(|chunk| !ANIMATION_CHUNKS.contains(&&chunk.kind()))
+        {
+            raw.add_png_chunk(chunk.kind(), chunk.contents().to_vec());
+        }
+    }
+    Ok(raw.create_optimized_png(&options())?)
+}
+
+#[cfg(test)]
+mod tests {
+    use image::{DynamicImage, GenericImageView, ImageFormat, Rgba, RgbaImage};
+
+    use super::*;
+
+    fn decode(content: &[u8]) -> DynamicImage {
+        image::load_from_memory_with_format(content, ImageFormat::Png).unwrap()
+    }
+
+    // the bit depth and the color type in IHDR
+    fn color_type(content: &[u8]) -> (u8, u8) {
+        (content[24], content[25])
+    }
+
+    #[test]
+    fn test_encode_reduces_color_type() {
+        // opaque with 2 colors
+        let image = RgbaImage::from_fn(256, 256, |x, y| {
+            if (x * 7 + y * 13) % 5 == 0 {
+                Rgba([255, 0, 0, 255])
+            } else {
+                Rgba([0, 0, 255, 255])
+            }
+        });
+        let image = DynamicImage::ImageRgba8(image);
+        let encoded = encode(&image, None).unwrap();
+        assert_eq!(color_type(&encoded), (1, 3));
+        assert_eq!(decode(&encoded).to_rgba8(), image.to_rgba8());
+
+        // black and white
+        let image = RgbaImage::from_fn(9, 9, |x, y| {
+            let v = if (x + y) % 2 == 0 { 0 } else { 255 };
+            Rgba([v, v, v, 255])
+        });
+        let image = DynamicImage::ImageRgba8(image);
+        let encoded = encode(&image, None).unwrap();
+        assert_eq!(decode(&encoded).to_rgba8(), image.to_rgba8());
+
+        // translucent with many colors
+        let image = RgbaImage::from_fn(64, 64, |x, y| {
+            Rgba([
+                (x * 4) as u8,
+                (y * 4) as u8,
+                (x + y) as u8,
+                (x * 2 + 1) as u8,
+            ])
+        });
+        let image = DynamicImage::ImageRgba8(image);
+        let encoded = encode(&image, None).unwrap();
+        assert_eq!(color_type(&encoded), (8, 6));
+        assert_eq!(decode(&encoded).to_rgba8(), image.to_rgba8());
+    }
+
+    #[test]
+    fn test_optimize() {
+        let image = DynamicImage::ImageRgba8(RgbaImage::from_fn(64, 64, |x, y| {
+            Rgba([(x * 4) as u8, (y * 4) as u8, 128, 255])
+        }));
+        let mut original = vec![];
+        image
+            .write_to(&mut std::io::Cursor::new(&mut original), ImageFormat::Png)
+            .unwrap();
+        let optimized = optimize(&original).unwrap();
+        assert!(optimized.len() < original.len());
+        assert_eq!(color_type(&optimized), (8, 2));
+        assert_eq!(decode(&optimized).dimensions(), (64, 64));
+        assert_eq!(decode(&optimized).to_rgba8(), image.to_rgba8());
+    }
+}
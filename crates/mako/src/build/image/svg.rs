@@ -0,0 +1,103 @@
+use anyhow::{anyhow, Result};
+use swc_core::common::sync::Lrc;
+use swc_core::common::{FileName, SourceMap};
+use swc_xml::ast::{Child, Document, Element};
+use swc_xml::codegen::writer::basic::BasicXmlWriter;
+use swc_xml::codegen::{CodeGenerator, CodegenConfig, Emit};
+use swc_xml::parser::parse_file_as_document;
+use swc_xml::visit::{VisitMut, VisitMutWith};
+
+// the elements whose whitespaces are significant
+const PRESERVED_ELEMENTS: [&str; 6] = [
+    "text",
+    "tspan",
+    "textPath",
+    "style",
+    "script",
+    "foreignObject",
+];
+
+/**
+ * Minify the svg with swc_xml, the comments, the processing instructions, the doctype, the
+ * metadata and the whitespaces between the tags are removed.
+ */
+pub(crate) fn minify(svg: &str) -> Result<String> {
+    let cm: Lrc<SourceMap> = Default::default();
+    let fm = cm.new_source_file(FileName::Anon.into(), svg.to_string());
+    let mut errors = vec![];
+    let mut document = parse_file_as_document(&fm, Default::default(), &mut errors)
+        .map_err(|e| anyhow!("Failed to parse svg: {}", e.message()))?;
+    document.visit_mut_with(&mut SvgMinifier { preserved: 0 });
+
+    let mut output = String::new();
+    let writer = BasicXmlWriter::new(&mut output, None, Default::default());
+    let mut generator = CodeGenerator::new(
+        writer,
+        CodegenConfig {
+            minify: true,
+            scripting_enabled: false,
+            context_element: None,
+        },
+    );
+    generator.emit(&document)?;
+    Ok(output)
+}
+
+struct SvgMinifier {
+    // the depth of the preserved elements
+    preserved: usize,
+}
+
+impl SvgMinifier {
+    fn retain(&self, children: &mut Vec<Child>) {
+        children.retain(|child| match child {
+            Child::Comment(_) | Child::ProcessingInstruction(_) | Child::DocumentType(_) => false,
+            Child::Element(element) => &*element.tag_name != "metadata",
+            Child::Text(text) => self.preserved > 0 || !text.data.trim().is_empty(),
+            Child::CdataSection(_) => true,
+        });
+    }
+}
+
+impl VisitMut for SvgMinifier {
+    fn visit_mut_document(&mut self, document: &mut Document) {
+        self.retain(&mut document.children);
+        document.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_element(&mut self, element: &mut Element) {
+        let preserved = PRESERVED_ELEMENTS.contains(&&*element.tag_name);
+        if preserved {
+            self.preserved += 1;
+        }
+        self.retain(&mut element.children);
+        element.visit_mut_children_with(self);
+        if preserved {
+            self.preserved -= 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_minify() {
+        let svg = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE svg PUBLIC "-//W3C//DTD SVG 1.1//EN" "http://www.w3.org/Graphics/SVG/1.1/DTD/svg11.dtd">
+<!-- Generator: an editor -->
+<svg xmlns="http://www.w3.org/2000/svg"   viewBox = "0 0 24 24" >
+  <metadata><rdf:RDF></rdf:RDF></metadata>
+  <title>An icon</title>
+  <path d="M0 0 L10 10" fill='red' />
+  <text x="1"> a <tspan>b</tspan> c</text>
+  <style><![CDATA[ path > a { fill: red } ]]></style>
+</svg>
+"#;
+        assert_eq!(
+            minify(svg).unwrap(),
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24"><title>An icon</title><path d="M0 0 L10 10" fill="red"/><text x="1"> a <tspan>b</tspan> c</text><style><![CDATA[ path > a { fill: red } ]]></style></svg>"#
+        );
+    }
+}
@@ -12,8 +12,9 @@ use toml::{from_str as from_toml_str, Value as TomlValue};
 use tracing::debug;
 
 use crate::ast::file::{Content, File, JsContent};
+use crate::build::image::{self, ImageVariant};
 use crate::compiler::Context;
-use crate::config::Mode;
+use crate::config::{Mode, Platform};
 use crate::plugin::PluginLoadParam;
 use crate::utils::create_cached_regex;
 
@@ -39,6 +40,7 @@ const XML_EXTENSIONS: [&str; 1] = ["xml"];
 const TOML_EXTENSIONS: [&str; 1] = ["toml"];
 const SVG_EXTENSIONS: [&str; 1] = ["svg"];
 const MD_EXTENSIONS: [&str; 2] = ["md", "mdx"];
+pub const NODE_ADDON_EXTENSIONS: [&str; 1] = ["node"];
 const UNSUPPORTED_EXTENSIONS: [&str; 2] = ["sass", "stylus"];
 
 const SVGR_NAMED_EXPORT: &str = r#"ReactComponent"#;
@@ -63,9 +65,16 @@ impl Load {
         if file.path.to_str().unwrap() == "virtual:inline_css:runtime" {
             return Ok(Content::Js(JsContent {
                 content: r#"
-export function moduleToDom(css) {
-    var styleElement = document.createElement("style");
+export function moduleToDom(css, id) {
+    // reuse the style element of the module when it's hot updated
+    var styleElement = id !== undefined && document.querySelector('style[data-mako-css-id="' + id + '"]');
+    if (styleElement) {
+        styleElement.textContent = css;
+        return;
+    }
+    styleElement = document.createElement("style");
     styleElement.type = "text/css";
+    if (id !== undefined) styleElement.setAttribute("data-mako-css-id", id);
     styleElement.appendChild(document.createTextNode(css))
     document.head.appendChild(styleElement);
 }
@@ -90,6 +99,21 @@ export function moduleToDom(css) {
             }));
         }
 
+        // native addons of node, emit as an asset and load it with the native require
+        // relative to the output file, __dirname and __filename are not mocked for it
+        if NODE_ADDON_EXTENSIONS.contains(&file.extname.as_str())
+            && context.config.platform == Platform::Node
+        {
+            let final_file_name = Self::emit_asset(file, context.clone());
+            return Ok(Content::Js(JsContent {
+                content: format!(
+                    "module.exports = require(\"module\").createRequire(__filename)(require(\"path\").join(__dirname, \"{}\"));",
+                    final_file_name
+                ),
+                ..Default::default()
+            }));
+        }
+
         // ?raw
         if file.has_param("raw") {
             let content = FileSystem::read_file(&file.pathname)?;
@@ -210,6 +234,47 @@ export function moduleToDom(css) {
             }));
         }
 
+        // ?srcset
+        if context.config.image.is_some() && image::is_image(file) && file.has_param("srcset") {
+            let images = image::srcset(file, &context)?;
+            let url = |file_name: &str| format!("${{require.publicPath}}{}", file_name);
+            let src_set = images
+                .iter()
+                .map(|(image, width, _)| format!("{} {}w", url(&image.file_name), width))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let list = images
+                .iter()
+                .map(|(image, width, height)| {
+                    format!(
+                        "{{ src: `{}`, width: {}, height: {} }}",
+                        url(&image.file_name),
+                        width,
+                        height
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            let (largest, width, height) = images.last().unwrap();
+            images.iter().for_each(|(image, _, _)| {
+                context.emit_assets(
+                    image.path.to_string_lossy().to_string(),
+                    image.file_name.clone(),
+                )
+            });
+            return Ok(Content::Js(JsContent {
+                content: format!(
+                    "module.exports = {{ src: `{}`, width: {}, height: {}, srcSet: `{}`, images: [{}] }};",
+                    url(&largest.file_name),
+                    width,
+                    height,
+                    src_set,
+                    list
+                ),
+                ..Default::default()
+            }));
+        }
+
         // assets
         let asset_path = Self::handle_asset(file, true, true, context.clone())?;
         Ok(Content::Js(JsContent {
@@ -224,13 +289,31 @@ export function moduleToDom(css) {
         limit: bool,
         context: Arc<Context>,
     ) -> Result<String> {
-        let file_size = file
-            .get_file_size()
-            .map_err(|_| LoadError::ReadFileSizeError {
-                path: file.path.to_string_lossy().to_string(),
-            })?;
+        // the optimized image or the variant of the query, e.g. `?w=400&format=webp`
+        let processed = if context.config.image.is_some() && image::is_image(file) {
+            image::process(file, &ImageVariant::from_file(file)?, &context)?
+        } else {
+            None
+        };
+        let file_size = match &processed {
+            Some(image) => image.size,
+            None => file
+                .get_file_size()
+                .map_err(|_| LoadError::ReadFileSizeError {
+                    path: file.path.to_string_lossy().to_string(),
+                })?,
+        };
         let emit_assets = || -> Result<String> {
-            let final_file_name = Self::emit_asset(file, context.clone());
+            let final_file_name = match &processed {
+                Some(image) => {
+                    context.emit_assets(
+                        image.path.to_string_lossy().to_string(),
+                        image.file_name.clone(),
+                    );
+                    image.file_name.clone()
+                }
+                None => Self::emit_asset(file, context.clone()),
+            };
             if inject_public_path {
                 Ok(format!("`${{require.publicPath}}{}`", final_file_name))
             } else {
@@ -247,13 +330,18 @@ export function moduleToDom(css) {
         let should_not_transform_base64 = inline_excludes_extensions
             .iter()
             .any(|regex| regex.is_match(&file.extname));
-        if !limit
-            || file_size > context.config.inline_limit.try_into().unwrap()
-            || should_not_transform_base64
+        // everything is inlined into the single file bundle
+        if !context.config.single_file
+            && (!limit
+                || file_size > context.config.inline_limit.try_into().unwrap()
+                || should_not_transform_base64)
         {
             emit_assets()
         } else {
-            let base64_result = file.get_base64();
+            let base64_result = match &processed {
+                Some(image) => image.get_base64(),
+                None => file.get_base64(),
+            };
             match base64_result {
                 Ok(base64) => {
                     if inject_public_path {
@@ -29,6 +29,8 @@ pub enum ParseError {
     UnsupportedServerAction { path: String },
     #[error("The `\"{directive:?}\"` directive must be put at the top of the file.")]
     DirectiveNotOnTop { directive: String },
+    #[error("Invalid server action in {path:?}: {reason}")]
+    InvalidServerAction { path: String, reason: String },
     #[error("{path}\n{message}")]
     InvalidExpression { message: String, path: String },
 }
@@ -50,8 +52,8 @@ impl Parse {
         // js
         if let Some(Content::Js(_)) = &file.content {
             debug!("parse js: {:?}", file.path);
-            let ast = JsAst::new(file, context.clone())?;
-            if let Some(ast) = Rsc::parse_js(file, &ast, context.clone())? {
+            let mut ast = JsAst::new(file, context.clone())?;
+            if let Some(ast) = Rsc::parse_js(file, &mut ast, context.clone())? {
                 return Ok(ast);
             }
             return Ok(ModuleAst::Script(ast));
@@ -70,8 +72,8 @@ impl Parse {
                 let content = CssAst::generate_css_modules_exports(
                     &file.pathname.to_string_lossy(),
                     &mut ast.ast,
-                    context.config.css_modules_export_only_locales,
-                );
+                    &context,
+                )?;
                 file.set_content(Content::Js(JsContent {
                     content,
                     ..Default::default()
@@ -123,10 +125,23 @@ impl Parse {
                             r#"
 import {{ moduleToDom }} from 'virtual:inline_css:runtime';
 {}
-moduleToDom({});
+moduleToDom({}{});
+{}
                         "#,
                             deps,
-                            serde_json::to_string(&code)?
+                            serde_json::to_string(&code)?,
+                            // styles are replaced in place when hot updated,
+                            // so the dependents don't need to be executed again
+                            if context.args.watch {
+                                ", module.id"
+                            } else {
+                                ""
+                            },
+                            if context.args.watch {
+                                "if (module.hot) module.hot.accept();"
+                            } else {
+                                ""
+                            }
                         ),
                         ..Default::default()
                     }));
@@ -1,9 +1,241 @@
 use std::collections::HashMap;
 
+use swc_core::css::compat::feature::Features as CssFeatures;
 use swc_core::ecma::preset_env::Targets as SwcPresetEnvTargets;
 
+use crate::config::{CssDirection, CssLoweringConfig};
+
 pub fn swc_preset_env_targets_from_map(map: HashMap<String, f32>) -> SwcPresetEnvTargets {
     let serialized_str = serde_json::to_string(&map).unwrap();
     let targets: SwcPresetEnvTargets = serde_json::from_str(&serialized_str).unwrap();
     targets
 }
+
+// the first browser versions which support the css features natively
+// ref: https://caniuse.com
+const CSS_NESTING: &[(&str, f32)] = &[
+    ("chrome", 112.0),
+    ("edge", 112.0),
+    ("firefox", 117.0),
+    ("safari", 16.5),
+    ("ios", 16.5),
+    ("opera", 98.0),
+    ("samsung", 23.0),
+];
+// no browser supports @custom-media yet
+const CSS_CUSTOM_MEDIA: &[(&str, f32)] = &[];
+const CSS_MEDIA_QUERY_RANGES: &[(&str, f32)] = &[
+    ("chrome", 104.0),
+    ("edge", 104.0),
+    ("firefox", 63.0),
+    ("safari", 16.4),
+    ("ios", 16.4),
+    ("opera", 91.0),
+    ("samsung", 20.0),
+];
+// #rrggbbaa
+const CSS_COLOR_HEX_ALPHA: &[(&str, f32)] = &[
+    ("chrome", 62.0),
+    ("edge", 79.0),
+    ("firefox", 49.0),
+    ("safari", 10.0),
+    ("ios", 10.0),
+    ("opera", 49.0),
+    ("samsung", 8.0),
+];
+// rgb(0 0 0 / 50%), rgb(0, 0, 0, 0.5), hsl(0deg 0% 0%)
+const CSS_COLOR_LEVEL_4_SYNTAX: &[(&str, f32)] = &[
+    ("chrome", 65.0),
+    ("edge", 79.0),
+    ("firefox", 52.0),
+    ("safari", 12.1),
+    ("ios", 12.2),
+    ("opera", 52.0),
+    ("samsung", 9.2),
+];
+const CSS_COLOR_HWB: &[(&str, f32)] = &[
+    ("chrome", 101.0),
+    ("edge", 101.0),
+    ("firefox", 96.0),
+    ("safari", 15.0),
+    ("ios", 15.0),
+    ("opera", 87.0),
+    ("samsung", 19.0),
+];
+// oklab(), oklch()
+const CSS_COLOR_OKLAB: &[(&str, f32)] = &[
+    ("chrome", 111.0),
+    ("edge", 111.0),
+    ("firefox", 113.0),
+    ("safari", 15.4),
+    ("ios", 15.4),
+    ("opera", 97.0),
+    ("samsung", 22.0),
+];
+const CSS_COLOR_MIX: &[(&str, f32)] = &[
+    ("chrome", 111.0),
+    ("edge", 111.0),
+    ("firefox", 113.0),
+    ("safari", 16.2),
+    ("ios", 16.2),
+    ("opera", 97.0),
+    ("samsung", 22.0),
+];
+// margin-inline, inset, inline-size and so on
+const CSS_LOGICAL_PROPERTIES: &[(&str, f32)] = &[
+    ("chrome", 89.0),
+    ("edge", 89.0),
+    ("firefox", 66.0),
+    ("safari", 15.0),
+    ("ios", 15.0),
+    ("opera", 75.0),
+    ("samsung", 15.0),
+];
+const CSS_CASCADE_LAYERS: &[(&str, f32)] = &[
+    ("chrome", 99.0),
+    ("edge", 99.0),
+    ("firefox", 97.0),
+    ("safari", 15.4),
+    ("ios", 15.4),
+    ("opera", 85.0),
+    ("samsung", 18.0),
+];
+
+/**
+ * Whether all browsers in targets support the feature, browsers which are not
+ * in the support list (e.g. ie) are treated as unsupported, node is ignored.
+ */
+fn is_supported(targets: &HashMap<String, f32>, support: &[(&str, f32)]) -> bool {
+    targets
+        .iter()
+        .filter(|(browser, _)| browser.as_str() != "node")
+        .all(|(browser, version)| {
+            support
+                .iter()
+                .find(|(b, _)| b == browser)
+                .is_some_and(|(_, min)| version >= min)
+        })
+}
+
+/**
+ * Css features which should be lowered for the targets.
+ */
+#[derive(Debug, Clone)]
+pub struct CssLowering {
+    // handled by swc_css_compat
+    pub compat: CssFeatures,
+    pub oklab: bool,
+    pub color_mix: bool,
+    // the direction which logical properties are lowered with
+    pub logical_properties: Option<CssDirection>,
+    pub cascade_layers: bool,
+}
+
+pub fn css_lowering(
+    targets: &HashMap<String, f32>,
+    config: Option<&CssLoweringConfig>,
+) -> CssLowering {
+    let Some(config) = config else {
+        // nesting is always compiled, the same as before cssLowering is introduced
+        return CssLowering {
+            compat: CssFeatures::NESTING,
+            oklab: false,
+            color_mix: false,
+            logical_properties: None,
+            cascade_layers: false,
+        };
+    };
+    let lower = |enabled: bool, support: &[(&str, f32)]| enabled && !is_supported(targets, support);
+
+    let mut compat = CssFeatures::empty();
+    compat.set(CssFeatures::NESTING, lower(config.nesting, CSS_NESTING));
+    compat.set(
+        CssFeatures::CUSTOM_MEDIA,
+        lower(config.custom_media, CSS_CUSTOM_MEDIA),
+    );
+    compat.set(
+        CssFeatures::MEDIA_QUERY_RANGES,
+        lower(config.media_query_ranges, CSS_MEDIA_QUERY_RANGES),
+    );
+    compat.set(
+        CssFeatures::COLOR_HEX_ALPHA,
+        lower(config.color_functions, CSS_COLOR_HEX_ALPHA),
+    );
+    let color_level_4_syntax = lower(config.color_functions, CSS_COLOR_LEVEL_4_SYNTAX);
+    compat.set(
+        CssFeatures::COLOR_ALPHA_PARAMETER
+            | CssFeatures::COLOR_SPACE_SEPARATED_PARAMETERS
+            | CssFeatures::COLOR_LEGACY_RGB_AND_HSL,
+        color_level_4_syntax,
+    );
+    compat.set(
+        CssFeatures::COLOR_HWB,
+        lower(config.color_functions, CSS_COLOR_HWB),
+    );
+
+    CssLowering {
+        compat,
+        oklab: lower(config.color_functions, CSS_COLOR_OKLAB),
+        color_mix: lower(config.color_functions, CSS_COLOR_MIX),
+        logical_properties: config
+            .dir
+            .filter(|_| lower(config.logical_properties, CSS_LOGICAL_PROPERTIES)),
+        cascade_layers: lower(config.cascade_layers, CSS_CASCADE_LAYERS),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn targets(targets: &[(&str, f32)]) -> HashMap<String, f32> {
+        targets.iter().map(|(b, v)| (b.to_string(), *v)).collect()
+    }
+
+    #[test]
+    fn test_css_lowering_by_targets() {
+        let config = CssLoweringConfig {
+            dir: Some(CssDirection::Ltr),
+            ..Default::default()
+        };
+
+        let lowering = css_lowering(&targets(&[("chrome", 80.0)]), Some(&config));
+        assert!(lowering.compat.contains(CssFeatures::NESTING));
+        assert!(lowering.compat.contains(CssFeatures::CUSTOM_MEDIA));
+        assert!(!lowering.compat.contains(CssFeatures::COLOR_HEX_ALPHA));
+        assert!(lowering.oklab && lowering.color_mix);
+        assert_eq!(lowering.logical_properties, Some(CssDirection::Ltr));
+        assert!(lowering.cascade_layers);
+
+        let lowering = css_lowering(&targets(&[("chrome", 120.0)]), Some(&config));
+        assert_eq!(lowering.compat.bits(), CssFeatures::CUSTOM_MEDIA.bits());
+        assert!(!lowering.oklab && lowering.logical_properties.is_none());
+
+        // unknown browsers are treated as unsupported
+        let lowering = css_lowering(&targets(&[("chrome", 120.0), ("ie", 11.0)]), Some(&config));
+        assert!(lowering.compat.contains(CssFeatures::COLOR_HEX_ALPHA));
+        assert!(lowering.logical_properties.is_some());
+    }
+
+    #[test]
+    fn test_css_lowering_opt_out() {
+        let config = CssLoweringConfig {
+            nesting: false,
+            logical_properties: false,
+            dir: Some(CssDirection::Rtl),
+            ..Default::default()
+        };
+        let lowering = css_lowering(&targets(&[("chrome", 80.0)]), Some(&config));
+        assert!(!lowering.compat.contains(CssFeatures::NESTING));
+        assert!(lowering.logical_properties.is_none());
+        assert!(lowering.cascade_layers);
+
+        // logical properties are kept without the direction
+        let config = CssLoweringConfig::default();
+        let lowering = css_lowering(&targets(&[("chrome", 80.0)]), Some(&config));
+        assert!(lowering.logical_properties.is_none());
+
+        let lowering = css_lowering(&targets(&[("chrome", 120.0)]), None);
+        assert_eq!(lowering.compat.bits(), CssFeatures::NESTING.bits());
+    }
+}
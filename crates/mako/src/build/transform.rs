@@ -6,7 +6,7 @@ use swc_core::common::errors::HANDLER;
 use swc_core::common::{Mark, GLOBALS};
 use swc_core::css::ast::{AtRule, AtRulePrelude, ImportHref, Rule, Str, Stylesheet, UrlValue};
 use swc_core::css::compat::compiler::{self, Compiler};
-use swc_core::css::{compat as swc_css_compat, prefixer, visit as swc_css_visit};
+use swc_core::css::{prefixer, visit as swc_css_visit};
 use swc_core::ecma::ast::Module;
 use swc_core::ecma::preset_env::{self as swc_preset_env};
 use swc_core::ecma::transforms::base::feature::FeatureFlag;
@@ -21,6 +21,7 @@ use swc_core::ecma::visit::{Fold, VisitMut, VisitMutWith};
 
 use crate::ast::css_ast::CssAst;
 use crate::ast::file::File;
+use crate::build::load::NODE_ADDON_EXTENSIONS;
 use crate::build::targets;
 use crate::build::targets::swc_preset_env_targets_from_map;
 use crate::compiler::Context;
@@ -32,13 +33,17 @@ use crate::plugins::context_module::ContextModuleVisitor;
 use crate::visitors::amd_define_overrides::amd_define_overrides;
 use crate::visitors::clean_ctxt::clean_syntax_context;
 use crate::visitors::css_assets::CSSAssets;
+use crate::visitors::css_cascade_layers::CSSCascadeLayers;
+use crate::visitors::css_color_functions::CSSColorFunctions;
 use crate::visitors::css_flexbugs::CSSFlexbugs;
+use crate::visitors::css_logical_properties::CSSLogicalProperties;
 use crate::visitors::css_px2rem::Px2Rem;
 use crate::visitors::default_export_namer::DefaultExportNamer;
 use crate::visitors::dynamic_import_to_require::DynamicImportToRequire;
 use crate::visitors::env_replacer::{build_env_map, EnvReplacer};
 use crate::visitors::fix_symbol_conflict::FixSymbolConflict;
 use crate::visitors::import_meta_env_replacer::ImportMetaEnvReplacer;
+use crate::visitors::import_meta_hot_replacer::ImportMetaHotReplacer;
 use crate::visitors::import_template_to_string_literal::ImportTemplateToStringLiteral;
 use crate::visitors::new_url_assets::NewUrlAssets;
 use crate::visitors::provide::Provide;
@@ -145,6 +150,9 @@ impl Transform {
                                         .push(Box::new(EnvReplacer::new(env_map, unresolved_mark)));
                                     visitors.push(Box::new(ImportMetaEnvReplacer::new(mode)));
                                 }
+                                visitors.push(Box::new(ImportMetaHotReplacer::new(
+                                    context.args.watch && context.config.hmr.is_some(),
+                                )));
                                 visitors.push(Box::new(TryResolve {
                                     path: file.path.to_string_lossy().to_string(),
                                     context: context.clone(),
@@ -162,7 +170,14 @@ impl Transform {
                                     unresolved_mark,
                                 }));
                                 // TODO: move ContextModuleVisitor out of plugin
-                                visitors.push(Box::new(ContextModuleVisitor { unresolved_mark }));
+                                visitors.push(Box::new(ContextModuleVisitor {
+                                    unresolved_mark,
+                                    comments: context
+                                        .config
+                                        .experimental
+                                        .magic_comment
+                                        .then(|| origin_comments.get_swc_comments().clone()),
+                                }));
                                 visitors.push(Box::new(ImportTemplateToStringLiteral {}));
                                 // DynamicImportToRequire must be after ContextModuleVisitor
                                 // since ContextModuleVisitor will add extra dynamic imports
@@ -172,7 +187,13 @@ impl Transform {
                                     )));
                                 }
                                 if matches!(context.config.platform, crate::config::Platform::Node)
+                                    && !NODE_ADDON_EXTENSIONS.contains(&file.extname.as_str())
                                 {
+                                    visitors.push(Box::new(features::node::DirnameAssets {
+                                        unresolved_mark,
+                                        current_path: file.path.clone(),
+                                        context: context.clone(),
+                                    }));
                                     visitors.push(Box::new(
                                         features::node::MockFilenameAndDirname {
                                             unresolved_mark,
@@ -266,9 +287,26 @@ impl Transform {
                 // replace @import url() to @import before CSSUrlReplacer
                 import_url_to_href(&mut ast.ast);
                 let mut visitors: Vec<Box<dyn swc_css_visit::VisitMut>> = vec![];
+                // lower modern css features by targets
+                let css_lowering = targets::css_lowering(
+                    &context.config.targets,
+                    context.config.css_lowering.as_ref(),
+                );
+                if css_lowering.cascade_layers {
+                    visitors.push(Box::new(CSSCascadeLayers {}));
+                }
+                if css_lowering.oklab || css_lowering.color_mix {
+                    visitors.push(Box::new(CSSColorFunctions {
+                        oklab: css_lowering.oklab,
+                        color_mix: css_lowering.color_mix,
+                    }));
+                }
                 visitors.push(Box::new(Compiler::new(compiler::Config {
-                    process: swc_css_compat::feature::Features::NESTING,
+                    process: css_lowering.compat,
                 })));
+                if let Some(dir) = css_lowering.logical_properties {
+                    visitors.push(Box::new(CSSLogicalProperties { dir }));
+                }
                 let path = file.path.to_string_lossy().to_string();
                 visitors.push(Box::new(CSSAssets {
                     path,
@@ -295,7 +333,11 @@ impl Transform {
                 // css modules
                 let is_modules = file.has_param("modules");
                 if is_modules {
-                    CssAst::compile_css_modules(file.pathname.to_str().unwrap(), &mut ast.ast);
+                    CssAst::compile_css_modules(
+                        file.pathname.to_str().unwrap(),
+                        &mut ast.ast,
+                        &context,
+                    )?;
                 }
 
                 Ok(())
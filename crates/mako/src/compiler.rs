@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fs;
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex, RwLock};
@@ -20,6 +20,7 @@ use crate::ast::file::win_path;
 use crate::config::{Config, Mode, ModuleIdStrategy, OutputMode};
 use crate::generate::chunk_graph::ChunkGraph;
 use crate::generate::optimize_chunk::OptimizeChunksInfo;
+use crate::module::{relative_module_id, ResolveType};
 use crate::module_graph::ModuleGraph;
 use crate::plugin::{Plugin, PluginDriver, PluginGenerateEndParams};
 use crate::plugins;
@@ -27,7 +28,10 @@ use crate::plugins::module_federation::ModuleFederationPlugin;
 use crate::resolve::{get_resolvers, Resolvers};
 use crate::share::helpers::SWC_HELPERS;
 use crate::stats::StatsInfo;
-use crate::utils::id_helper::{assign_numeric_ids, compare_modules_by_incoming_edges};
+use crate::utils::id_helper::{
+    assign_numeric_ids, compare_modules_by_incoming_edges, DeterministicIds,
+};
+use crate::utils::records::Records;
 use crate::utils::{thread_pool, ParseRegex};
 
 pub struct Context {
@@ -37,6 +41,8 @@ pub struct Context {
     pub modules_with_missing_deps: RwLock<Vec<String>>,
     pub config: Config,
     pub numeric_ids_map: RwLock<HashMap<String, usize>>,
+    // module id or chunk name => id, of the deterministic module id strategy
+    pub deterministic_ids_map: RwLock<HashMap<String, String>>,
     pub args: Args,
     pub root: PathBuf,
     pub meta: Meta,
@@ -137,6 +143,7 @@ impl Default for Context {
             optimize_infos: Mutex::new(None),
             static_cache: Default::default(),
             numeric_ids_map: RwLock::new(numeric_ids_map),
+            deterministic_ids_map: Default::default(),
         }
     }
 }
@@ -257,7 +264,6 @@ impl Compiler {
 
         let builtin_plugins: Vec<Arc<dyn Plugin>> = vec![
             // features
-            Arc::new(plugins::manifest::ManifestPlugin {}),
             Arc::new(plugins::copy::CopyPlugin {}),
             Arc::new(plugins::import::ImportPlugin {}),
             // file types
@@ -305,6 +311,52 @@ impl Compiler {
             ));
         }
 
+        if let Some(unused_css) = &config.unused_css {
+            plugins.push(Arc::new(plugins::unused_css::UnusedCssPlugin::new(
+                unused_css,
+            )?));
+        }
+
+        if let Some(critical_css) = &config.critical_css {
+            plugins.push(Arc::new(plugins::critical_css::CriticalCssPlugin::new(
+                critical_css,
+            )?));
+        }
+
+        if config.mode == Mode::Production
+            && let Some(mangle_props) = config
+                .minify
+                .as_ref()
+                .and_then(|minify| minify.mangle_props.as_ref())
+        {
+            plugins.push(Arc::new(plugins::mangle_props::ManglePropsPlugin::new(
+                mangle_props.clone(),
+                &root,
+            )?));
+        }
+
+        if let Some(declaration) = &config.declaration {
+            plugins.push(Arc::new(plugins::declaration::DeclarationPlugin::new(
+                declaration.clone(),
+            )));
+        }
+
+        if config
+            .service_worker
+            .as_ref()
+            .is_some_and(|c| c.precache_manifest)
+        {
+            plugins.push(Arc::new(plugins::service_worker::ServiceWorkerPlugin {}));
+        }
+
+        if config.single_file {
+            plugins.push(Arc::new(plugins::single_file::SingleFilePlugin::new()));
+        }
+
+        if config.platform == crate::config::Platform::Browser {
+            plugins.push(Arc::new(plugins::resource_hints::ResourceHintsPlugin {}));
+        }
+
         if config.experimental.require_context {
             plugins.push(Arc::new(plugins::require_context::RequireContextPlugin {}))
         }
@@ -323,6 +375,17 @@ impl Compiler {
             plugins.push(Arc::new(plugins::ssu::SUPlus::new()));
         }
 
+        if args.watch
+            && config.lazy_compilation
+            && config.hmr.is_some()
+            && config.dev_server.is_some()
+            && config.platform == crate::config::Platform::Browser
+        {
+            plugins.push(Arc::new(
+                plugins::lazy_compilation::LazyCompilationPlugin::new(),
+            ));
+        }
+
         if args.watch && config.experimental.central_ensure {
             plugins.push(Arc::new(plugins::central_ensure::CentralChunkEnsure {}));
         }
@@ -364,6 +427,17 @@ impl Compiler {
             );
         }
 
+        // CompressPlugin runs after the plugins above which rewrite the emitted files in
+        // build_success, e.g. CriticalCssPlugin and SingleFilePlugin. ManifestPlugin is not a
+        // builtin plugin any more but registered after it, since the manifest is written from the
+        // assets in stats_info, which only has the compressed files after CompressPlugin runs.
+        if let Some(compress) = &config.output.compress {
+            plugins.push(Arc::new(plugins::compress::CompressPlugin::new(
+                compress.clone(),
+            )));
+        }
+        plugins.push(Arc::new(plugins::manifest::ManifestPlugin {}));
+
         let ignores = config
             .ignores
             .iter()
@@ -397,6 +471,7 @@ impl Compiler {
                 meta: Meta::new(),
                 plugin_driver,
                 numeric_ids_map: RwLock::new(numeric_ids_map),
+                deterministic_ids_map: Default::default(),
                 stats_info: StatsInfo::new(),
                 resolvers,
                 optimize_infos: Mutex::new(None),
@@ -465,6 +540,9 @@ impl Compiler {
                 },
             )
         }
+        if let ModuleIdStrategy::Deterministic = self.context.config.module_id_strategy {
+            self.assign_deterministic_ids()?;
+        }
 
         let result = {
             crate::mako_profile_scope!("Generate Stage");
@@ -504,6 +582,89 @@ impl Compiler {
         }
     }
 
+    // the module ids and the chunk ids of `webpackChunkName` share the ids, since the chunk ids
+    // of the other async chunks are the module ids of their root modules
+    // it's re-run after rebuilds, where the assigned ids are pinned before the records
+    pub(crate) fn assign_deterministic_ids(&self) -> Result<()> {
+        let module_graph = self.context.module_graph.read().unwrap();
+        let mut modules = module_graph
+            .modules()
+            .iter()
+            .map(|module| {
+                (
+                    relative_module_id(&module.id.id, &self.context),
+                    module.id.id.clone(),
+                )
+            })
+            .collect::<Vec<_>>();
+        modules.sort();
+        let chunk_names = module_graph
+            .modules()
+            .iter()
+            .flat_map(|module| module_graph.get_dependencies(&module.id))
+            .filter_map(|(_, dep)| match &dep.resolve_type {
+                ResolveType::DynamicImport(options) | ResolveType::Worker(options) => {
+                    options.get_chunk_name().clone()
+                }
+                _ => None,
+            })
+            .collect::<BTreeSet<_>>();
+
+        let records_path = Records::path(&self.context);
+        let records = match &records_path {
+            Some(path) => Records::load(path)?,
+            None => Default::default(),
+        };
+        let assigned = self.context.deterministic_ids_map.read().unwrap().clone();
+        let mut ids = DeterministicIds::new(modules.len() + chunk_names.len());
+        let mut module_ids = BTreeMap::new();
+        let mut chunk_ids = BTreeMap::new();
+        for (name, module_id) in &modules {
+            if let Some(id) = assigned
+                .get(module_id)
+                .or_else(|| records.module_ids.get(name))
+                && ids.pin(id)
+            {
+                module_ids.insert(name.clone(), id.clone());
+            }
+        }
+        for name in &chunk_names {
+            if let Some(id) = assigned.get(name).or_else(|| records.chunk_ids.get(name))
+                && ids.pin(id)
+            {
+                chunk_ids.insert(name.clone(), id.clone());
+            }
+        }
+        for (name, _) in &modules {
+            if !module_ids.contains_key(name) {
+                module_ids.insert(name.clone(), ids.assign(name));
+            }
+        }
+        for name in &chunk_names {
+            if !chunk_ids.contains_key(name) {
+                chunk_ids.insert(name.clone(), ids.assign(name));
+            }
+        }
+
+        let mut deterministic_ids_map = self.context.deterministic_ids_map.write().unwrap();
+        deterministic_ids_map.clear();
+        for (name, module_id) in &modules {
+            deterministic_ids_map.insert(module_id.clone(), module_ids[name].clone());
+        }
+        for (name, id) in &chunk_ids {
+            deterministic_ids_map.insert(name.clone(), id.clone());
+        }
+        if let Some(path) = records_path {
+            Records {
+                module_ids,
+                chunk_ids,
+                ..records
+            }
+            .save(&path)?;
+        }
+        Ok(())
+    }
+
     pub fn full_hash(&self) -> u64 {
         crate::mako_profile_function!();
         let cg = self.context.chunk_graph.read().unwrap();
@@ -1,5 +1,10 @@
 mod analyze;
 mod code_splitting;
+mod critical_css;
+mod css_chunking;
+mod css_lowering;
+mod css_modules;
+mod declaration;
 mod dev_server;
 mod devtool;
 mod duplicate_package_checker;
@@ -8,10 +13,12 @@ mod experimental;
 mod external;
 mod generic_usize;
 mod hmr;
+mod image;
 mod inline_css;
 mod macros;
 mod manifest;
 mod minifish;
+mod minify;
 mod mode;
 pub mod module_federation;
 mod module_id_strategy;
@@ -24,10 +31,12 @@ mod react;
 mod resolve;
 mod rsc_client;
 mod rsc_server;
+mod service_worker;
 mod stats;
 mod transform_import;
 mod tree_shaking;
 mod umd;
+mod unused_css;
 mod watch;
 
 use std::collections::HashMap;
@@ -39,6 +48,11 @@ use anyhow::{anyhow, Result};
 pub use code_splitting::*;
 use colored::Colorize;
 use config;
+pub use critical_css::{deserialize_critical_css, CriticalCssConfig};
+pub use css_chunking::{deserialize_css_chunking, CssChunkingConfig};
+pub use css_lowering::{deserialize_css_lowering, CssDirection, CssLoweringConfig};
+pub use css_modules::{CssModulesConfig, CssModulesMode, ExportLocalsConvention};
+pub use declaration::{deserialize_declaration, DeclarationConfig};
 pub use dev_server::{deserialize_dev_server, DevServerConfig};
 pub use devtool::{deserialize_devtool, DevtoolConfig};
 pub use duplicate_package_checker::{
@@ -52,16 +66,18 @@ pub use external::{
 };
 pub use generic_usize::GenericUsizeDefault;
 pub use hmr::{deserialize_hmr, HmrConfig};
+pub use image::{deserialize_image, ImageConfig};
 pub use inline_css::{deserialize_inline_css, InlineCssConfig};
 pub use manifest::{deserialize_manifest, ManifestConfig};
 use miette::{miette, ByteOffset, Diagnostic, NamedSource, SourceOffset, SourceSpan};
 pub use minifish::{deserialize_minifish, MinifishConfig};
+pub use minify::{deserialize_minify, LegalComments, ManglePropsConfig, MinifyConfig};
 pub use mode::Mode;
 use module_federation::ModuleFederationConfig;
 pub use module_id_strategy::ModuleIdStrategy;
 pub use optimization::{deserialize_optimization, OptimizationConfig};
 use output::get_default_chunk_loading_global;
-pub use output::{CrossOriginLoading, OutputConfig, OutputMode};
+pub use output::{CompressAlgorithm, CompressConfig, CrossOriginLoading, OutputConfig, OutputMode};
 pub use progress::{deserialize_progress, ProgressConfig};
 pub use provider::Providers;
 pub use px2rem::{deserialize_px2rem, Px2RemConfig};
@@ -71,11 +87,13 @@ pub use rsc_client::{deserialize_rsc_client, LogServerComponent, RscClientConfig
 pub use rsc_server::{deserialize_rsc_server, RscServerConfig};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
+pub use service_worker::{deserialize_service_worker, ServiceWorkerConfig};
 pub use stats::{deserialize_stats, StatsConfig};
 use thiserror::Error;
 pub use transform_import::{TransformImportConfig, TransformImportStyle};
 pub use tree_shaking::{deserialize_tree_shaking, TreeShakingStrategy};
 pub use umd::{deserialize_umd, Umd};
+pub use unused_css::{deserialize_unused_css, PurgeConfig, UnusedCssConfig};
 pub use watch::WatchConfig;
 
 use crate::build::load::JS_EXTENSIONS;
@@ -145,7 +163,8 @@ pub struct Config {
     #[serde(deserialize_with = "deserialize_manifest", default)]
     pub manifest: Option<ManifestConfig>,
     pub mode: Mode,
-    pub minify: bool,
+    #[serde(deserialize_with = "deserialize_minify")]
+    pub minify: Option<MinifyConfig>,
     #[serde(deserialize_with = "deserialize_devtool")]
     pub devtool: Option<DevtoolConfig>,
     pub externals: HashMap<String, ExternalConfig>,
@@ -154,9 +173,13 @@ pub struct Config {
     pub public_path: String,
     pub inline_limit: usize,
     pub inline_excludes_extensions: Vec<String>,
+    #[serde(deserialize_with = "deserialize_image", default)]
+    pub image: Option<ImageConfig>,
     pub targets: HashMap<String, f32>,
     pub platform: Platform,
     pub module_id_strategy: ModuleIdStrategy,
+    // the records file relative to the root, which pins the deterministic ids
+    pub records_path: Option<String>,
     pub define: HashMap<String, Value>,
     pub analyze: Option<AnalyzeConfig>,
     pub stats: Option<StatsConfig>,
@@ -179,9 +202,12 @@ pub struct Config {
     #[serde(rename = "ignoreCSSParserErrors")]
     pub ignore_css_parser_errors: bool,
     pub dynamic_import_to_require: bool,
+    pub lazy_compilation: bool,
+    pub auto_prefetch: bool,
     #[serde(deserialize_with = "deserialize_umd", default)]
     pub umd: Option<Umd>,
     pub cjs: bool,
+    pub single_file: bool,
     pub write_to_disk: bool,
     pub transform_import: Vec<TransformImportConfig>,
     pub chunk_parallel: bool,
@@ -204,12 +230,40 @@ pub struct Config {
     pub emit_assets: bool,
     #[serde(rename = "cssModulesExportOnlyLocales")]
     pub css_modules_export_only_locales: bool,
+    #[serde(rename = "cssModules", default)]
+    pub css_modules: CssModulesConfig,
     #[serde(
         rename = "inlineCSS",
         deserialize_with = "deserialize_inline_css",
         default
     )]
     pub inline_css: Option<InlineCssConfig>,
+    #[serde(
+        rename = "cssChunking",
+        deserialize_with = "deserialize_css_chunking",
+        default
+    )]
+    pub css_chunking: Option<CssChunkingConfig>,
+    #[serde(
+        rename = "cssLowering",
+        deserialize_with = "deserialize_css_lowering",
+        default
+    )]
+    pub css_lowering: Option<CssLoweringConfig>,
+    #[serde(
+        rename = "unusedCss",
+        deserialize_with = "deserialize_unused_css",
+        default
+    )]
+    pub unused_css: Option<UnusedCssConfig>,
+    #[serde(
+        rename = "criticalCss",
+        deserialize_with = "deserialize_critical_css",
+        default
+    )]
+    pub critical_css: Option<CriticalCssConfig>,
+    #[serde(deserialize_with = "deserialize_declaration", default)]
+    pub declaration: Option<DeclarationConfig>,
     #[serde(
         rename = "rscServer",
         deserialize_with = "deserialize_rsc_server",
@@ -222,6 +276,12 @@ pub struct Config {
         default
     )]
     pub rsc_client: Option<RscClientConfig>,
+    #[serde(
+        rename = "serviceWorker",
+        deserialize_with = "deserialize_service_worker",
+        default
+    )]
+    pub service_worker: Option<ServiceWorkerConfig>,
     pub experimental: ExperimentalConfig,
     pub watch: WatchConfig,
     pub use_define_for_class_fields: bool,
@@ -337,6 +397,16 @@ impl Config {
                 return Err(anyhow!("inlineCSS can only be used with umd",));
             }
 
+            if config.single_file && config.platform != Platform::Node {
+                return Err(anyhow!("singleFile can only be used with node platform",));
+            }
+
+            if config.inline_css.is_some() && config.css_chunking.is_some() {
+                return Err(anyhow!(
+                    "inlineCSS and cssChunking cannot be used at the same time",
+                ));
+            }
+
             let mode = format!("\"{}\"", config.mode);
             config
                 .define
@@ -500,6 +570,27 @@ mod tests {
         assert_eq!(config.platform, Platform::Browser);
     }
 
+    #[test]
+    fn test_config_true_as_default() {
+        let current_dir = std::env::current_dir().unwrap();
+        let config = Config::new(
+            &current_dir.join("test/config/normal"),
+            None,
+            Some(r#"{"minify":true,"declaration":true}"#),
+        )
+        .unwrap();
+        assert!(config.minify.is_some_and(|minify| minify.drop_debugger));
+        assert!(config
+            .declaration
+            .is_some_and(|declaration| !declaration.bundle));
+        assert!(Config::new(
+            &current_dir.join("test/config/normal"),
+            None,
+            Some(r#"{"devServer":true}"#),
+        )
+        .is_err());
+    }
+
     #[test]
     fn test_node_env_conflicts_with_mode() {
         let current_dir = std::env::current_dir().unwrap();
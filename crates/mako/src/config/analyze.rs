@@ -1,4 +1,9 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Serialize, Clone, Debug)]
-pub struct AnalyzeConfig {}
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct AnalyzeConfig {
+    // a previous `analyze.json` relative to the root, to diff the module sizes against
+    #[serde(default)]
+    pub baseline: Option<String>,
+}
@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+
+use crate::create_deserialize_fn;
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CriticalCssConfig {
+    // selectors which are always inlined, `/pattern/` for regex
+    #[serde(default)]
+    pub safelist: Vec<String>,
+    // the max size of inlined css per entry in bytes
+    #[serde(default = "default_max_size")]
+    pub max_size: usize,
+}
+
+// the initial tcp congestion window
+fn default_max_size() -> usize {
+    14 * 1024
+}
+
+create_deserialize_fn!(deserialize_critical_css, CriticalCssConfig);
@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+
+use crate::create_deserialize_fn;
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CssChunkingConfig {
+    // skip the conflicting order warnings, like mini-css-extract-plugin's ignoreOrder
+    #[serde(default)]
+    pub ignore_order: bool,
+}
+
+create_deserialize_fn!(deserialize_css_chunking, CssChunkingConfig);
@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+
+use crate::create_deserialize_fn;
+
+/**
+ * Features are lowered only when some browsers in `targets` don't support them natively,
+ * set a feature to false to ship it as is.
+ */
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CssLoweringConfig {
+    pub nesting: bool,
+    pub custom_media: bool,
+    pub media_query_ranges: bool,
+    pub color_functions: bool,
+    pub logical_properties: bool,
+    pub cascade_layers: bool,
+    // logical properties are only lowered when the direction is known
+    pub dir: Option<CssDirection>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CssDirection {
+    #[serde(rename = "ltr")]
+    Ltr,
+    #[serde(rename = "rtl")]
+    Rtl,
+}
+
+impl Default for CssLoweringConfig {
+    fn default() -> Self {
+        CssLoweringConfig {
+            nesting: true,
+            custom_media: true,
+            media_query_ranges: true,
+            color_functions: true,
+            logical_properties: true,
+            cascade_layers: true,
+            dir: None,
+        }
+    }
+}
+
+create_deserialize_fn!(deserialize_css_lowering, CssLoweringConfig);
@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CssModulesMode {
+    // all class names and keyframes are local unless wrapped with :global
+    #[serde(rename = "local")]
+    #[default]
+    Local,
+    // all class names and keyframes are global unless wrapped with :local
+    #[serde(rename = "global")]
+    Global,
+    // same as local, but every selector must contain at least one local class or id
+    #[serde(rename = "pure")]
+    Pure,
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ExportLocalsConvention {
+    #[serde(rename = "asIs")]
+    #[default]
+    AsIs,
+    #[serde(rename = "camelCase")]
+    CamelCase,
+    #[serde(rename = "camelCaseOnly")]
+    CamelCaseOnly,
+    #[serde(rename = "dashes")]
+    Dashes,
+    #[serde(rename = "dashesOnly")]
+    DashesOnly,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CssModulesConfig {
+    pub local_ident_name: String,
+    pub hash_salt: String,
+    pub mode: CssModulesMode,
+    pub export_locals_convention: ExportLocalsConvention,
+    // emit .d.ts typings next to the css modules files
+    pub dts: bool,
+}
+
+impl Default for CssModulesConfig {
+    fn default() -> Self {
+        CssModulesConfig {
+            local_ident_name: "[local]-[hash:base64:8]".to_string(),
+            hash_salt: "".to_string(),
+            mode: CssModulesMode::default(),
+            export_locals_convention: ExportLocalsConvention::default(),
+            dts: false,
+        }
+    }
+}
@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+
+use crate::create_deserialize_fn;
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct DeclarationConfig {
+    // roll up the declarations into one file per entry, only for bundle mode
+    #[serde(default)]
+    pub bundle: bool,
+}
+
+create_deserialize_fn!(deserialize_declaration, DeclarationConfig, true => Default);
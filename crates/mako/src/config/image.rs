@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+use crate::create_deserialize_fn;
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageConfig {
+    // lossless optimization of png and jpeg, and minification of svg
+    #[serde(default = "default_optimize")]
+    pub optimize: bool,
+    // the widths of `?srcset` without a value
+    #[serde(default = "default_srcset_widths")]
+    pub srcset_widths: Vec<u32>,
+}
+
+fn default_optimize() -> bool {
+    true
+}
+
+fn default_srcset_widths() -> Vec<u32> {
+    vec![320, 640, 960, 1280, 1920]
+}
+
+create_deserialize_fn!(deserialize_image, ImageConfig);
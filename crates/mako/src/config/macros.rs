@@ -4,6 +4,16 @@
 #[macro_export]
 macro_rules! create_deserialize_fn {
     ($fn_name:ident, $struct_type:ty) => {
+        create_deserialize_fn!(@impl $fn_name, $struct_type, {});
+    };
+    // also allow true value for the default struct, e.g. when `{}` can't override the default
+    // value of the option
+    ($fn_name:ident, $struct_type:ty, true => Default) => {
+        create_deserialize_fn!(@impl $fn_name, $struct_type, {
+            serde_json::Value::Bool(true) => Ok(Some(<$struct_type>::default())),
+        });
+    };
+    (@impl $fn_name:ident, $struct_type:ty, { $($true_arm:tt)* }) => {
         pub fn $fn_name<'de, D>(deserializer: D) -> Result<Option<$struct_type>, D::Error>
         where
             D: serde::Deserializer<'de>,
@@ -13,6 +23,7 @@ macro_rules! create_deserialize_fn {
             match value {
                 // allow false value for optional struct
                 serde_json::Value::Bool(false) => Ok(None),
+                $($true_arm)*
                 // try deserialize
                 serde_json::Value::Object(obj) => Ok(Some(
                     serde_json::from_value::<$struct_type>(serde_json::Value::Object(obj))
@@ -0,0 +1,73 @@
+use serde::{Deserialize, Serialize};
+
+use crate::create_deserialize_fn;
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct MinifyConfig {
+    #[serde(default)]
+    pub drop_console: bool,
+    #[serde(default = "default_drop_debugger")]
+    pub drop_debugger: bool,
+    // calls of these functions are removed when the results are unused, e.g. `console.info`
+    #[serde(default)]
+    pub pure_funcs: Vec<String>,
+    #[serde(default)]
+    pub keep_classnames: bool,
+    #[serde(default)]
+    pub keep_fnames: bool,
+    pub passes: Option<usize>,
+    // the ecma version of the output, e.g. 5, 2015, 2020
+    pub ecma: Option<usize>,
+    // the names that should not be mangled
+    #[serde(default)]
+    pub reserved: Vec<String>,
+    pub mangle_props: Option<ManglePropsConfig>,
+    #[serde(default)]
+    pub legal_comments: LegalComments,
+}
+
+impl Default for MinifyConfig {
+    fn default() -> Self {
+        Self {
+            drop_console: false,
+            drop_debugger: default_drop_debugger(),
+            pure_funcs: vec![],
+            keep_classnames: false,
+            keep_fnames: false,
+            passes: None,
+            ecma: None,
+            reserved: vec![],
+            mangle_props: None,
+            legal_comments: LegalComments::default(),
+        }
+    }
+}
+
+fn default_drop_debugger() -> bool {
+    true
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ManglePropsConfig {
+    // only the properties which match the regex are mangled, e.g. `^_`
+    pub regex: String,
+    #[serde(default)]
+    pub reserved: Vec<String>,
+    // a json file relative to the root to persist the mangled names across builds
+    pub name_cache: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum LegalComments {
+    #[default]
+    None,
+    // keep the legal comments at the end of the chunk
+    Inline,
+    // extract the legal comments to `[chunk].LICENSE.txt`
+    External,
+}
+
+create_deserialize_fn!(deserialize_minify, MinifyConfig, true => Default);
@@ -19,20 +19,26 @@ pub struct ModuleFederationConfig {
     pub share_scope: String,
     #[serde(default)]
     pub manifest: bool,
+    #[serde(default)]
+    pub dts: Option<DtsConfig>,
 }
 
 pub type ExposesConfig = HashMap<String, String>;
 
 pub type SharedConfig = HashMap<String, SharedItemConfig>;
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SharedItemConfig {
     #[serde(default)]
-    /// not supported now
+    /// bundle the shared module into the initial chunks
     pub eager: bool,
+    /// `false` means consume only, no fallback module is bundled
+    #[serde(default = "default_import")]
+    pub import: bool,
     #[serde(default)]
     pub singleton: bool,
+    /// inferred from the importer's package.json when not configured
     #[serde(default)]
     pub required_version: Option<String>,
     #[serde(default)]
@@ -41,6 +47,19 @@ pub struct SharedItemConfig {
     pub shared_scope: String,
 }
 
+impl Default for SharedItemConfig {
+    fn default() -> Self {
+        Self {
+            eager: false,
+            import: default_import(),
+            singleton: false,
+            required_version: None,
+            strict_version: false,
+            shared_scope: default_share_scope(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ShareStrategy {
     #[serde(rename = "version-first")]
@@ -57,6 +76,31 @@ impl Default for ShareStrategy {
 
 pub type RemotesConfig = HashMap<String, String>;
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DtsConfig {
+    /// generate the types archive of exposes
+    #[serde(default = "default_generate_types")]
+    pub generate_types: bool,
+    /// remote name to a local types archive or directory, relative to root
+    #[serde(default)]
+    pub consume_types: HashMap<String, String>,
+    #[serde(default = "default_types_folder")]
+    pub types_folder: String,
+}
+
 fn default_share_scope() -> String {
     "default".to_string()
 }
+
+fn default_import() -> bool {
+    true
+}
+
+fn default_generate_types() -> bool {
+    true
+}
+
+fn default_types_folder() -> String {
+    "@mf-types".to_string()
+}
@@ -24,6 +24,8 @@ pub struct OutputConfig {
     #[serde(deserialize_with = "deserialize_cross_origin_loading")]
     pub cross_origin_loading: Option<CrossOriginLoading>,
     pub global_module_registry: bool,
+    #[serde(deserialize_with = "deserialize_compress")]
+    pub compress: Option<CompressConfig>,
 }
 
 #[derive(Deserialize, Serialize, Debug, PartialEq, Eq, ValueEnum, Clone)]
@@ -51,6 +53,43 @@ impl fmt::Display for CrossOriginLoading {
     }
 }
 
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CompressConfig {
+    #[serde(default = "default_compress_algorithms")]
+    pub algorithms: Vec<CompressAlgorithm>,
+    // only the files larger than the threshold in bytes are compressed
+    #[serde(default)]
+    pub threshold: u64,
+    // the compressed file is dropped if it's not smaller than `minRatio` of the original size
+    #[serde(default = "default_compress_min_ratio")]
+    pub min_ratio: f64,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum CompressAlgorithm {
+    Gzip,
+    Brotli,
+}
+
+impl CompressAlgorithm {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            CompressAlgorithm::Gzip => "gz",
+            CompressAlgorithm::Brotli => "br",
+        }
+    }
+}
+
+fn default_compress_algorithms() -> Vec<CompressAlgorithm> {
+    vec![CompressAlgorithm::Gzip, CompressAlgorithm::Brotli]
+}
+
+fn default_compress_min_ratio() -> f64 {
+    0.8
+}
+
 pub fn get_default_chunk_loading_global(umd: Option<Umd>, root: &Path) -> String {
     let unique_name = umd.map_or_else(
         || get_app_info(root).0.unwrap_or("global".to_string()),
@@ -61,3 +100,4 @@ pub fn get_default_chunk_loading_global(umd: Option<Umd>, root: &Path) -> String
 }
 
 create_deserialize_fn!(deserialize_cross_origin_loading, CrossOriginLoading);
+create_deserialize_fn!(deserialize_compress, CompressConfig);
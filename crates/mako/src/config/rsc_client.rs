@@ -7,6 +7,8 @@ use crate::create_deserialize_fn;
 #[serde(rename_all = "camelCase")]
 pub struct RscClientConfig {
     pub log_server_component: LogServerComponent,
+    #[serde(default)]
+    pub server_action_tpl: Option<String>,
 }
 
 #[derive(Deserialize, Serialize, Debug, PartialEq, Eq, ValueEnum, Clone)]
@@ -8,6 +8,8 @@ pub struct RscServerConfig {
     pub client_component_tpl: String,
     #[serde(rename = "emitCSS")]
     pub emit_css: bool,
+    #[serde(default)]
+    pub server_action_tpl: Option<String>,
 }
 
 create_deserialize_fn!(deserialize_rsc_server, RscServerConfig);
@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+
+use crate::create_deserialize_fn;
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceWorkerConfig {
+    // scope of the registrations which don't specify one
+    #[serde(default)]
+    pub scope: Option<String>,
+    // inject the emitted assets into service workers as `self.__MAKO_PRECACHE_MANIFEST__`
+    #[serde(default)]
+    pub precache_manifest: bool,
+}
+
+create_deserialize_fn!(deserialize_service_worker, ServiceWorkerConfig);
@@ -5,6 +5,9 @@ use crate::create_deserialize_fn;
 #[derive(Deserialize, Serialize, Clone, Debug)]
 pub struct StatsConfig {
     pub modules: bool,
+    // print why the modules are not concatenated with `optimization.concatenateModules`
+    #[serde(default)]
+    pub concatenation: bool,
 }
 
 create_deserialize_fn!(deserialize_stats, StatsConfig);
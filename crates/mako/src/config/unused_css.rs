@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+use crate::create_deserialize_fn;
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct UnusedCssConfig {
+    // remove global css selectors which are not found in the content, like purgecss
+    #[serde(default)]
+    pub purge: Option<PurgeConfig>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PurgeConfig {
+    // globs relative to the root, scanned besides the js modules in the module graph
+    #[serde(default)]
+    pub content: Vec<String>,
+    // class names or ids which are always kept, `/pattern/` for regex
+    #[serde(default)]
+    pub safelist: Vec<String>,
+}
+
+create_deserialize_fn!(deserialize_unused_css, UnusedCssConfig);
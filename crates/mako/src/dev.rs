@@ -13,7 +13,8 @@ use get_if_addrs::get_if_addrs;
 use hyper::header::{ACCESS_CONTROL_ALLOW_ORIGIN, CACHE_CONTROL, CONTENT_TYPE};
 use hyper::service::{make_service_fn, service_fn};
 use hyper::{Body, Request, Server};
-use notify_debouncer_full::new_debouncer;
+use notify::EventKind;
+use notify_debouncer_full::{new_debouncer, DebounceEventResult, DebouncedEvent};
 use tokio::sync::broadcast;
 use tracing::debug;
 use tungstenite::Message;
@@ -21,6 +22,7 @@ use {hyper, hyper_staticfile, hyper_tungstenite, open};
 
 use crate::compiler::{Compiler, Context};
 use crate::plugin::PluginGenerateEndParams;
+use crate::plugins::lazy_compilation::LAZY_COMPILATION_PATH;
 use crate::utils::{process_req_url, tokio_runtime};
 
 pub struct DevServer {
@@ -36,18 +38,22 @@ impl DevServer {
     pub async fn serve(&self) {
         let (txws, _) = broadcast::channel::<WsMessage>(256);
 
+        // the file events are also sent by the dev server, i.e. lazy compilation requests
+        let (tx, rx) = mpsc::channel::<DebounceEventResult>();
+
         // watch
         let root = self.root.clone();
         let compiler = self.compiler.clone();
         let txws_watch = txws.clone();
+        let tx_watch = tx.clone();
 
         if self.compiler.context.config.dev_server.is_some() {
             std::thread::spawn(move || {
-                if let Err(e) = Self::watch_for_changes(root, compiler, txws_watch) {
+                if let Err(e) = Self::watch_for_changes(root, compiler, txws_watch, tx_watch, rx) {
                     eprintln!("Error watching files: {:?}", e);
                 }
             });
-        } else if let Err(e) = Self::watch_for_changes(root, compiler, txws_watch) {
+        } else if let Err(e) = Self::watch_for_changes(root, compiler, txws_watch, tx_watch, rx) {
             eprintln!("Error watching files: {:?}", e);
         }
 
@@ -68,17 +74,19 @@ impl DevServer {
             let make_svc = make_service_fn(move |_conn| {
                 let context = context.clone();
                 let txws = txws.clone();
+                let tx = tx.clone();
                 async move {
                     Ok::<_, hyper::Error>(service_fn(move |req| {
                         let context = context.clone();
                         let txws = txws.clone();
+                        let tx = tx.clone();
                         let staticfile = {
                             let mut sf =
                                 hyper_staticfile::Static::new(context.config.output.path.clone());
                             sf.cache_headers(Some(0));
                             sf
                         };
-                        async move { Self::handle_requests(req, context, staticfile, txws).await }
+                        async move { Self::handle_requests(req, context, staticfile, txws, tx).await }
                     }))
                 }
             });
@@ -123,6 +131,7 @@ impl DevServer {
         context: Arc<Context>,
         staticfile: hyper_staticfile::Static,
         txws: broadcast::Sender<WsMessage>,
+        tx: mpsc::Sender<DebounceEventResult>,
     ) -> Result<hyper::Response<Body>> {
         debug!("> {} {}", req.method().to_string(), req.uri().path());
 
@@ -161,6 +170,23 @@ impl DevServer {
                     Ok(not_found_response())
                 }
             }
+            LAZY_COMPILATION_PATH if context.config.lazy_compilation => {
+                match Self::lazy_compilation_module(&req, &context) {
+                    Some(path) => {
+                        debug!("lazy compile: {:?}", path);
+                        // rebuild the proxy module like a changed file
+                        let event = notify::Event::new(EventKind::Any).add_path(path);
+                        tx.send(Ok(vec![DebouncedEvent::new(event, Instant::now())]))?;
+                        Ok(hyper::Response::builder()
+                            .status(hyper::StatusCode::OK)
+                            .header(CACHE_CONTROL, "no-cache")
+                            .header(ACCESS_CONTROL_ALLOW_ORIGIN, "*")
+                            .body(hyper::Body::empty())
+                            .unwrap())
+                    }
+                    None => Ok(not_found_response()),
+                }
+            }
             _ => {
                 // for bundle outputs
 
@@ -225,6 +251,19 @@ impl DevServer {
         }
     }
 
+    // the ?lazy proxy module in the module graph of `/__/lazy-compile?module=<id>`
+    fn lazy_compilation_module(req: &Request<Body>, context: &Arc<Context>) -> Option<PathBuf> {
+        let query = req.uri().query()?;
+        let (_, id) = url::form_urlencoded::parse(query.as_bytes()).find(|(k, _)| k == "module")?;
+        if !id.ends_with("?lazy") {
+            return None;
+        }
+        let module_graph = context.module_graph.read().unwrap();
+        module_graph
+            .has_module(&id.to_string().into())
+            .then(|| PathBuf::from(id.as_ref()))
+    }
+
     fn get_ips() -> Vec<String> {
         let mut ips = vec![];
         match get_if_addrs() {
@@ -285,8 +324,9 @@ impl DevServer {
         root: PathBuf,
         compiler: Arc<Compiler>,
         txws: broadcast::Sender<WsMessage>,
+        tx: mpsc::Sender<DebounceEventResult>,
+        rx: mpsc::Receiver<DebounceEventResult>,
     ) -> Result<()> {
-        let (tx, rx) = mpsc::channel();
         // let mut watcher = RecommendedWatcher::new(tx, notify::Config::default())?;
         let mut debouncer = new_debouncer(Duration::from_millis(10), None, tx).unwrap();
         let mut watcher = watch::Watcher::new(&root, debouncer.watcher(), &compiler);
@@ -1,7 +1,7 @@
 use std::collections::{HashMap, HashSet};
 use std::fmt;
 use std::fmt::Debug;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anyhow::{anyhow, Ok, Result};
 use rayon::prelude::*;
@@ -10,6 +10,7 @@ use tracing::debug;
 use crate::ast::file::File;
 use crate::build::BuildError;
 use crate::compiler::Compiler;
+use crate::config::ModuleIdStrategy;
 use crate::generate::transform::transform_modules;
 use crate::module::{Dependency, Module, ModuleId, ResolveType};
 use crate::module_graph::ModuleGraph;
@@ -95,6 +96,9 @@ impl Compiler {
                     } else {
                         UpdateType::Add
                     }
+                } else if is_query_module(&path, &module_graph) {
+                    // e.g. the ?lazy proxy of lazy compilation, which is rebuilt on demand
+                    UpdateType::Modify
                 } else {
                     UpdateType::Remove
                 };
@@ -256,6 +260,11 @@ impl Compiler {
 
         debug!("update_result: {:?}", &update_result);
 
+        // the added modules are assigned before they're transformed
+        if let ModuleIdStrategy::Deterministic = self.context.config.module_id_strategy {
+            self.assign_deterministic_ids()?;
+        }
+
         self.context.plugin_driver.after_update(self)?;
 
         Result::Ok(update_result)
@@ -366,6 +375,14 @@ impl Compiler {
 
         let mut module_graph = self.context.module_graph.write().unwrap();
         for (modified_module, diff, mut dependence_modules, dependencies) in modified_results {
+            // the class names exported by the css modules are not changed, the new
+            // styles are applied by the `?modules` css module, so the dependent js
+            // modules don't need to be executed again
+            if is_css_modules_exports_unchanged(&modified_module, &module_graph) {
+                debug!("  > {} exports unchanged, skip", modified_module.id.id);
+                continue;
+            }
+
             if diff.dependence_changed(&modified_module.id, &module_graph, &dependencies) {
                 dep_changed_module_ids.insert(modified_module.id.clone());
             }
@@ -525,3 +542,28 @@ fn diff(origin: &[(ModuleId, Dependency)], new_deps: &[(ModuleId, Dependency)])
         modified,
     }
 }
+
+// `a.module.css?asmodule` with the same raw_hash exports the same class names
+fn is_css_modules_exports_unchanged(module: &Module, module_graph: &ModuleGraph) -> bool {
+    if !module.id.id.ends_with("?asmodule") {
+        return false;
+    }
+    let Some(origin) = module_graph.get_module(&module.id) else {
+        return false;
+    };
+    match (origin.info.as_ref(), module.info.as_ref()) {
+        (Some(origin), Some(info)) => origin.raw_hash == info.raw_hash,
+        _ => false,
+    }
+}
+
+// a module with queries in the graph whose file still exists, e.g. `a.tsx?lazy`
+fn is_query_module(path: &Path, module_graph: &ModuleGraph) -> bool {
+    let path = path.to_string_lossy();
+    match path.split_once('?') {
+        Some((pathname, _)) => {
+            Path::new(pathname).exists() && module_graph.has_module(&path.to_string().into())
+        }
+        None => false,
+    }
+}
@@ -1,15 +1,21 @@
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet};
+use std::path::{Component, Path, PathBuf};
 use std::sync::Arc;
 
 use pathdiff::diff_paths;
 use serde_json::Value;
 use swc_core::common::{Mark, DUMMY_SP};
-use swc_core::ecma::ast::{Expr, Lit, Str};
+use swc_core::ecma::ast::{BinExpr, BinaryOp, CallExpr, Callee, Expr, Lit, Str};
+use swc_core::ecma::utils::{quote_str, ExprFactory};
 use swc_core::ecma::visit::{VisitMut, VisitMutWith};
 
+use crate::ast::file::File;
+use crate::ast::utils::{id, is_ident_undefined};
+use crate::build::load::Load;
 use crate::compiler::Context;
 use crate::config::{Config, ExternalConfig, Platform};
+use crate::generate::chunk::{Chunk, ChunkType};
+use crate::generate::generate_chunks::{ChunkFile, ChunkFileType};
 
 pub struct Node {}
 
@@ -176,3 +182,301 @@ impl VisitMut for MockFilenameAndDirname {
         expr.visit_mut_children_with(self);
     }
 }
+
+/**
+ * Emit the files referenced relative to __dirname as assets, so that they can still be found
+ * after the module is bundled into the output directory, e.g.
+ *
+ * path.join(__dirname, 'data', 'x.json') => path.join(__dirname, "x.8e6c05c3.json")
+ * __dirname + '/x.json' => __dirname + "/x.8e6c05c3.json"
+ *
+ * The __dirname of the replaced expressions refers to the output file, so it must go before
+ * MockFilenameAndDirname. The assets are emitted to the output path, and DirnameAssetsOutputDir
+ * fixes the paths of the output files in the sub directories.
+ */
+pub struct DirnameAssets {
+    pub unresolved_mark: Mark,
+    pub current_path: PathBuf,
+    pub context: Arc<Context>,
+}
+
+impl DirnameAssets {
+    fn is_dirname(&self, expr: &Expr) -> bool {
+        expr.as_ident()
+            .is_some_and(|i| is_ident_undefined(i, "__dirname", &self.unresolved_mark))
+    }
+
+    fn emit_asset(&self, segments: &[&str]) -> Option<String> {
+        let mut path = self.current_path.parent()?.to_path_buf();
+        for segment in segments {
+            for component in Path::new(segment).components() {
+                match component {
+                    Component::Normal(c) => path.push(c),
+                    Component::ParentDir => {
+                        path.pop();
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if !path.is_file() {
+            return None;
+        }
+        let file = File::new(path.to_string_lossy().to_string(), self.context.clone());
+        Some(Load::emit_asset(&file, self.context.clone()))
+    }
+
+    fn output_dirname() -> Box<Expr> {
+        id("__dirname").into()
+    }
+}
+
+impl VisitMut for DirnameAssets {
+    fn visit_mut_call_expr(&mut self, call_expr: &mut CallExpr) {
+        // path.join(__dirname, 'x.json') or path.resolve(__dirname, 'x.json')
+        if let Callee::Expr(box Expr::Member(member)) = &call_expr.callee
+            && let Some(method) = member
+                .prop
+                .as_ident()
+                .filter(|p| p.sym == "join" || p.sym == "resolve")
+            && call_expr.args.len() > 1
+            && self.is_dirname(&call_expr.args[0].expr)
+        {
+            let segments = call_expr.args[1..]
+                .iter()
+                .map(|arg| match &*arg.expr {
+                    Expr::Lit(Lit::Str(str)) if arg.spread.is_none() => Some(str.value.as_ref()),
+                    _ => None,
+                })
+                .collect::<Option<Vec<_>>>();
+            // path.resolve(__dirname, '/x.json') is not relative to __dirname, while path.join
+            // joins the absolute segments as relative ones
+            let is_absolute = |segments: &Vec<&str>| {
+                method.sym == "resolve"
+                    && segments
+                        .iter()
+                        .any(|segment| segment.starts_with('/') || Path::new(segment).is_absolute())
+            };
+            if let Some(segments) = segments
+                && !is_absolute(&segments)
+                && let Some(file_name) = self.emit_asset(&segments)
+            {
+                call_expr.args.truncate(1);
+                call_expr.args[0].expr = Self::output_dirname();
+                call_expr.args.push(quote_str!(file_name).as_arg());
+                return;
+            }
+        }
+        call_expr.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_bin_expr(&mut self, bin_expr: &mut BinExpr) {
+        // __dirname + '/x.json'
+        if bin_expr.op == BinaryOp::Add
+            && self.is_dirname(&bin_expr.left)
+            && let Expr::Lit(Lit::Str(str)) = &*bin_expr.right
+            && str.value.starts_with('/')
+            && let Some(file_name) = self.emit_asset(&[str.value.as_ref()])
+        {
+            bin_expr.left = Self::output_dirname();
+            bin_expr.right = Lit::Str(format!("/{}", file_name).into()).into();
+            return;
+        }
+        bin_expr.visit_mut_children_with(self);
+    }
+}
+
+/**
+ * The assets emitted by DirnameAssets are in the output path, so the paths are relative to the
+ * output directory of the chunk if it's in a sub directory, e.g. in
+ * `server/index.js`
+ *
+ * path.join(__dirname, "x.8e6c05c3.json") => path.join(__dirname, "../x.8e6c05c3.json")
+ * __dirname + "/x.8e6c05c3.json" => __dirname + "/../x.8e6c05c3.json"
+ */
+pub struct DirnameAssetsOutputDir {
+    prefix: String,
+    assets: HashSet<String>,
+}
+
+impl DirnameAssetsOutputDir {
+    // the output directory is relative to the output path, none if it's the output path itself
+    pub fn new(output_dir: &Path, context: &Arc<Context>) -> Option<Self> {
+        let depth = output_dir
+            .components()
+            .filter(|c| matches!(c, Component::Normal(_)))
+            .count();
+        if depth == 0 {
+            return None;
+        }
+        Some(Self {
+            prefix: "../".repeat(depth),
+            assets: context
+                .assets_info
+                .lock()
+                .unwrap()
+                .values()
+                .cloned()
+                .collect(),
+        })
+    }
+
+    // the output directory of the chunk relative to the output path, the hash of the file name
+    // template doesn't matter
+    pub fn chunk_output_dir(chunk: &Chunk, context: &Arc<Context>) -> PathBuf {
+        let file_name_template = match &chunk.chunk_type {
+            ChunkType::Entry(_, name, _) => context.config.entry.get(name).and_then(|entry| {
+                entry
+                    .filename
+                    .as_ref()
+                    .xor(context.config.output.filename.as_ref())
+                    .cloned()
+            }),
+            _ => None,
+        };
+        let chunk_file = ChunkFile {
+            raw_hash: 0,
+            content: vec![],
+            source_map: None,
+            hash: None,
+            chunk_name: chunk.name(),
+            file_name: chunk.filename(),
+            chunk_id: chunk.id.id.clone(),
+            file_type: ChunkFileType::JS,
+            file_name_template,
+        };
+        Path::new(&chunk_file.disk_name())
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default()
+    }
+
+    fn is_dirname(expr: &Expr) -> bool {
+        expr.as_ident().is_some_and(|i| i.sym == "__dirname")
+    }
+}
+
+impl VisitMut for DirnameAssetsOutputDir {
+    fn visit_mut_call_expr(&mut self, call_expr: &mut CallExpr) {
+        if let Callee::Expr(box Expr::Member(member)) = &call_expr.callee
+            && member
+                .prop
+                .as_ident()
+                .is_some_and(|p| p.sym == "join" || p.sym == "resolve")
+            && call_expr.args.len() == 2
+            && Self::is_dirname(&call_expr.args[0].expr)
+            && let Expr::Lit(Lit::Str(str)) = &mut *call_expr.args[1].expr
+            && self.assets.contains(str.value.as_str())
+        {
+            *str = format!("{}{}", self.prefix, str.value).into();
+            return;
+        }
+        call_expr.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_bin_expr(&mut self, bin_expr: &mut BinExpr) {
+        if bin_expr.op == BinaryOp::Add
+            && Self::is_dirname(&bin_expr.left)
+            && let Expr::Lit(Lit::Str(str)) = &mut *bin_expr.right
+            && let Some(file_name) = str.value.strip_prefix('/')
+            && self.assets.contains(file_name)
+        {
+            *str = format!("/{}{}", self.prefix, file_name).into();
+            return;
+        }
+        bin_expr.visit_mut_children_with(self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use swc_core::common::GLOBALS;
+    use swc_core::ecma::visit::VisitMutWith;
+
+    use super::{DirnameAssets, DirnameAssetsOutputDir};
+    use crate::ast::tests::TestUtils;
+
+    #[test]
+    fn test_dirname_assets() {
+        assert_eq!(
+            run(r#"path.join(__dirname, 'big.jpg')"#),
+            r#"path.join(__dirname, "big.8e6c05c3.jpg");"#
+        );
+        assert_eq!(
+            run(r#"require("path").resolve(__dirname, '../css_assets', 'big.jpg')"#),
+            r#"require("path").resolve(__dirname, "big.8e6c05c3.jpg");"#
+        );
+        assert_eq!(
+            run(r#"fs.readFileSync(__dirname + '/big.jpg')"#),
+            r#"fs.readFileSync(__dirname + "/big.8e6c05c3.jpg");"#
+        );
+        // not found or not statically analyzable
+        assert_eq!(
+            run(r#"path.join(__dirname, 'not-found.jpg')"#),
+            r#"path.join(__dirname, 'not-found.jpg');"#
+        );
+        assert_eq!(
+            run(r#"path.join(__dirname, name)"#),
+            r#"path.join(__dirname, name);"#
+        );
+        // path.join joins the absolute segments while path.resolve starts from them
+        assert_eq!(
+            run(r#"path.join(__dirname, '/big.jpg')"#),
+            r#"path.join(__dirname, "big.8e6c05c3.jpg");"#
+        );
+        assert_eq!(
+            run(r#"path.resolve(__dirname, '/big.jpg')"#),
+            r#"path.resolve(__dirname, '/big.jpg');"#
+        );
+    }
+
+    #[test]
+    fn test_dirname_assets_output_dir() {
+        let run = |js_code: &str, output_dir: &str| {
+            let mut test_utils = TestUtils::gen_js_ast(js_code);
+            test_utils
+                .context
+                .emit_assets("big.jpg".to_string(), "big.8e6c05c3.jpg".to_string());
+            if let Some(mut visitor) =
+                DirnameAssetsOutputDir::new(Path::new(output_dir), &test_utils.context)
+            {
+                test_utils.ast.js_mut().ast.visit_mut_with(&mut visitor);
+            }
+            test_utils.js_ast_to_code()
+        };
+        assert_eq!(
+            run(r#"path.join(__dirname, "big.8e6c05c3.jpg")"#, "server/api"),
+            r#"path.join(__dirname, "../../big.8e6c05c3.jpg");"#
+        );
+        assert_eq!(
+            run(r#"__dirname + "/big.8e6c05c3.jpg""#, "server"),
+            r#"__dirname + "/../big.8e6c05c3.jpg";"#
+        );
+        assert_eq!(
+            run(r#"path.join(__dirname, "big.8e6c05c3.jpg")"#, ""),
+            r#"path.join(__dirname, "big.8e6c05c3.jpg");"#
+        );
+        // not an emitted asset
+        assert_eq!(
+            run(r#"path.join(__dirname, "small.jpg")"#, "server"),
+            r#"path.join(__dirname, "small.jpg");"#
+        );
+    }
+
+    fn run(js_code: &str) -> String {
+        let mut test_utils = TestUtils::gen_js_ast(js_code);
+        let ast = test_utils.ast.js_mut();
+        GLOBALS.set(&test_utils.context.meta.script.globals, || {
+            let current_dir = std::env::current_dir().unwrap();
+            let mut visitor = DirnameAssets {
+                unresolved_mark: ast.unresolved_mark,
+                current_path: current_dir.join("src/visitors/fixtures/css_assets/test.js"),
+                context: test_utils.context.clone(),
+            };
+            ast.ast.visit_mut_with(&mut visitor);
+        });
+        test_utils.js_ast_to_code()
+    }
+}
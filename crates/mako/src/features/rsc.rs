@@ -3,6 +3,7 @@ use std::sync::Arc;
 use anyhow::{anyhow, Result};
 use serde::Serialize;
 use swc_core::ecma::ast::{Expr, ExprStmt, Lit, Module, ModuleItem, Stmt, Str};
+use swc_core::ecma::visit::VisitMutWith;
 
 use crate::ast::css_ast::CssAst;
 use crate::ast::file::File;
@@ -12,6 +13,12 @@ use crate::compiler::Context;
 use crate::config::{Config, LogServerComponent};
 use crate::module::{ModuleAst, ModuleId};
 
+mod server_actions;
+
+use server_actions::{
+    append_registrations, server_action_exports, server_action_reference, InlineServerActions,
+};
+
 #[derive(Serialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct RscClientInfo {
@@ -27,10 +34,23 @@ pub struct RscCssModules {
     pub modules: bool,
 }
 
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RscServerAction {
+    pub id: String,
+    pub path: String,
+    pub module_id: String,
+    pub name: String,
+}
+
 pub struct Rsc {}
 
 impl Rsc {
-    pub fn parse_js(file: &File, ast: &JsAst, context: Arc<Context>) -> Result<Option<ModuleAst>> {
+    pub fn parse_js(
+        file: &File,
+        ast: &mut JsAst,
+        context: Arc<Context>,
+    ) -> Result<Option<ModuleAst>> {
         if let Some(rsc_server) = context.config.rsc_server.as_ref() {
             if Rsc::is_client(ast)? {
                 Rsc::emit_client(file, context.clone());
@@ -40,10 +60,28 @@ impl Rsc {
                     context.clone(),
                 )));
             }
+            Rsc::compile_server_actions(
+                file,
+                ast,
+                rsc_server.server_action_tpl.as_deref(),
+                context.clone(),
+            )?;
         }
         if let Some(rsc_client) = &context.config.rsc_client {
             let is_server = Rsc::is_server(ast)?;
-            if is_server && matches!(rsc_client.log_server_component, LogServerComponent::Error) {
+            if let Some(tpl) = &rsc_client.server_action_tpl {
+                if is_server {
+                    return Ok(Some(Self::generate_server_references(
+                        file,
+                        ast,
+                        tpl,
+                        context.clone(),
+                    )?));
+                }
+                Rsc::transform_inline_server_actions(file, ast, Some(tpl), context.clone())?;
+            } else if is_server
+                && matches!(rsc_client.log_server_component, LogServerComponent::Error)
+            {
                 return Err(anyhow!(ParseError::UnsupportedServerAction {
                     path: file.path.to_string_lossy().to_string(),
                 }));
@@ -80,6 +118,99 @@ impl Rsc {
         });
     }
 
+    // server actions are registered in the server build and emitted to stats,
+    // exports of "use server" modules and inline actions share the same ids
+    fn compile_server_actions(
+        file: &File,
+        ast: &mut JsAst,
+        tpl: Option<&str>,
+        context: Arc<Context>,
+    ) -> Result<()> {
+        let path = file.relative_path.to_string_lossy().to_string();
+        let module_id = ModuleId::from_path(file.path.clone()).generate(&context);
+        let mut actions = vec![];
+        if Rsc::is_server(ast)? {
+            actions = server_action_exports(&mut ast.ast, &path)?;
+        }
+        actions.extend(
+            Rsc::transform_inline_server_actions(file, ast, None, context.clone())?
+                .into_iter()
+                .map(|name| (name.clone(), name)),
+        );
+        if let Some(tpl) = tpl {
+            append_registrations(
+                &mut ast.ast,
+                &actions,
+                tpl,
+                &module_id,
+                file.path.to_str().unwrap(),
+                context.clone(),
+            )?;
+        }
+        context.stats_info.set_rsc_server_actions(
+            &module_id,
+            actions
+                .into_iter()
+                .map(|(name, _)| RscServerAction {
+                    id: format!("{}#{}", module_id, name),
+                    path: path.clone(),
+                    module_id: module_id.clone(),
+                    name,
+                })
+                .collect(),
+        );
+        Ok(())
+    }
+
+    fn transform_inline_server_actions(
+        file: &File,
+        ast: &mut JsAst,
+        reference_tpl: Option<&str>,
+        context: Arc<Context>,
+    ) -> Result<Vec<String>> {
+        if !file.get_content_raw().contains("use server") {
+            return Ok(vec![]);
+        }
+        let mut visitor = InlineServerActions::new(
+            ModuleId::from_path(file.path.clone()).generate(&context),
+            file.relative_path.to_string_lossy().to_string(),
+            reference_tpl.map(|tpl| tpl.to_string()),
+            context.clone(),
+        );
+        ast.ast.visit_mut_with(&mut visitor);
+        if let Some(error) = visitor.error {
+            return Err(error);
+        }
+        Ok(visitor.actions)
+    }
+
+    fn generate_server_references(
+        file: &File,
+        ast: &JsAst,
+        tpl: &str,
+        context: Arc<Context>,
+    ) -> Result<ModuleAst> {
+        let module_id = ModuleId::from_path(file.path.clone()).generate(&context);
+        let path = file.relative_path.to_string_lossy().to_string();
+        let content = server_action_exports(&mut ast.ast.clone(), &path)?
+            .into_iter()
+            .map(|(name, _)| {
+                let reference = server_action_reference(tpl, &module_id, &name);
+                if name == "default" {
+                    format!("export default {};", reference)
+                } else {
+                    format!("export const {} = {};", name, reference)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        Ok(ModuleAst::Script(JsAst::build(
+            file.path.to_str().unwrap(),
+            &content,
+            context.clone(),
+        )?))
+    }
+
     pub fn parse_css(file: &File, context: Arc<Context>) -> Result<Option<ModuleAst>> {
         if context
             .config
@@ -149,7 +280,10 @@ fn contains_directive(ast: &Module, directive: &str) -> Result<bool> {
 
 #[cfg(test)]
 mod tests {
+    use swc_core::common::GLOBALS;
+
     use super::*;
+    use crate::ast::tests::{TestUtils, TestUtilsOpts};
 
     #[test]
     fn test_is_client() {
@@ -170,6 +304,110 @@ mod tests {
         assert!(Rsc::is_server(&build_ast(r#""use server""#)).unwrap());
     }
 
+    #[test]
+    fn test_server_action_exports() {
+        let mut ast = build_ast(
+            r#""use server";
+export async function a() {}
+export const b = async () => {};
+async function c() {}
+export { c as d };
+export default async function () {}"#,
+        );
+        assert_eq!(
+            server_action_exports(&mut ast.ast, "test.ts").unwrap(),
+            vec![
+                ("a".to_string(), "a".to_string()),
+                ("b".to_string(), "b".to_string()),
+                ("d".to_string(), "c".to_string()),
+                ("default".to_string(), "$$ACTION_default".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_server_action_exports_not_async() {
+        let mut ast = build_ast(r#""use server";export function a() {}"#);
+        assert!(server_action_exports(&mut ast.ast, "test.ts").is_err());
+        let mut ast = build_ast(r#""use server";export * from "./a";"#);
+        assert!(server_action_exports(&mut ast.ast, "test.ts").is_err());
+    }
+
+    #[test]
+    fn test_inline_server_actions() {
+        let code = run_inline(
+            r#"const x = 1;
+export function Page({ id }) {
+    async function save(data) {
+        "use server";
+        return db.save(id, x, data);
+    }
+    return <form action={save} onSubmit={async () => { "use server"; }} />;
+}"#,
+            None,
+        );
+        assert_eq!(
+            code,
+            r#"const x = 1;
+export function Page({ id }) {
+    async function save(...args) {
+        return $$ACTION_34256ae0.bind(null, id)(...args);
+    }
+    save = $$ACTION_34256ae0.bind(null, id);
+    return <form action={save} onSubmit={$$ACTION_02937836}/>;
+}
+export async function $$ACTION_34256ae0(id, data) {
+    return db.save(id, x, data);
+}
+export async function $$ACTION_02937836() {}"#
+        );
+    }
+
+    #[test]
+    fn test_inline_server_actions_stable_ids() {
+        let code = run_inline(
+            r#"const a = async () => { "use server"; };
+const b = async () => { "use server"; };
+async function save() { "use server"; }"#,
+            None,
+        );
+        assert!(code.contains("const a = $$ACTION_02937836;"), "{}", code);
+        assert!(code.contains("const b = $$ACTION_028d8321;"), "{}", code);
+        assert!(code.contains("save = $$ACTION_34256ae0;"), "{}", code);
+    }
+
+    #[test]
+    fn test_inline_server_actions_reference() {
+        let code = run_inline(
+            r#"const save = async () => { "use server"; };"#,
+            Some(r#"createServerReference("{{id}}")"#),
+        );
+        assert_eq!(
+            code,
+            r#"const save = createServerReference("foo#$$ACTION_02937836");"#
+        );
+    }
+
+    fn run_inline(content: &str, reference_tpl: Option<&str>) -> String {
+        let mut test_utils = TestUtils::new(TestUtilsOpts {
+            file: Some("test.jsx".to_string()),
+            content: Some(content.to_string()),
+        });
+        let context = test_utils.context.clone();
+        let ast = test_utils.ast.js_mut();
+        GLOBALS.set(&context.meta.script.globals, || {
+            let mut visitor = InlineServerActions::new(
+                "foo".to_string(),
+                "test.jsx".to_string(),
+                reference_tpl.map(|tpl| tpl.to_string()),
+                context.clone(),
+            );
+            ast.ast.visit_mut_with(&mut visitor);
+            assert!(visitor.error.is_none());
+        });
+        test_utils.js_ast_to_code()
+    }
+
     fn build_ast(content: &str) -> JsAst {
         JsAst::build("test.ts", content, Default::default()).unwrap()
     }
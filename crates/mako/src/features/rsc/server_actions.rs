@@ -0,0 +1,500 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use swc_core::common::collections::AHashSet;
+use swc_core::common::DUMMY_SP;
+use swc_core::ecma::ast::{
+    ArrowExpr, AssignOp, BindingIdent, BlockStmt, BlockStmtOrExpr, Decl, DefaultDecl, ExportDecl,
+    ExportDefaultDecl, ExportNamedSpecifier, ExportSpecifier, Expr, ExprOrSpread, ExprStmt, FnDecl,
+    FnExpr, Function, Id, Ident, Lit, Module, ModuleDecl, ModuleExportName, ModuleItem,
+    NamedExport, Null, Param, Pat, RestPat, ReturnStmt, Stmt, Str, VarDecl, VarDeclKind,
+    VarDeclarator,
+};
+use swc_core::ecma::utils::{collect_decls, quote_ident, ExprFactory};
+use swc_core::ecma::visit::{Visit, VisitMut, VisitMutWith, VisitWith};
+
+use crate::ast::js_ast::JsAst;
+use crate::build::parse::ParseError;
+use crate::compiler::Context;
+use crate::module::md5_hash;
+
+pub const SERVER_ACTION_PREFIX: &str = "$$ACTION_";
+const DEFAULT_SERVER_ACTION: &str = "$$ACTION_default";
+
+// Collects the exported actions of a "use server" module as (exported name,
+// local name) pairs. Anonymous default exports are given a local name so
+// that they can be referenced when registering.
+pub fn server_action_exports(module: &mut Module, path: &str) -> Result<Vec<(String, String)>> {
+    let invalid = |reason: String| {
+        anyhow!(ParseError::InvalidServerAction {
+            path: path.to_string(),
+            reason,
+        })
+    };
+    let not_async = |name: &str| invalid(format!("`{}` must be an async function", name));
+
+    let mut actions = vec![];
+    let mut body = Vec::with_capacity(module.body.len());
+    for mut item in std::mem::take(&mut module.body) {
+        match &mut item {
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl { decl, .. })) => match decl {
+                Decl::Fn(FnDecl {
+                    ident, function, ..
+                }) => {
+                    if !function.is_async {
+                        return Err(not_async(&ident.sym));
+                    }
+                    actions.push((ident.sym.to_string(), ident.sym.to_string()));
+                }
+                Decl::Var(var) => {
+                    for decl in &var.decls {
+                        let Pat::Ident(BindingIdent { id, .. }) = &decl.name else {
+                            return Err(invalid("destructuring exports are not supported".into()));
+                        };
+                        if !decl.init.as_deref().is_some_and(is_async_function) {
+                            return Err(not_async(&id.sym));
+                        }
+                        actions.push((id.sym.to_string(), id.sym.to_string()));
+                    }
+                }
+                Decl::Class(class) => return Err(not_async(&class.ident.sym)),
+                _ => {}
+            },
+            ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(NamedExport {
+                specifiers,
+                src: None,
+                type_only: false,
+                ..
+            })) => {
+                for specifier in specifiers.iter() {
+                    match specifier {
+                        ExportSpecifier::Named(ExportNamedSpecifier {
+                            orig: ModuleExportName::Ident(orig),
+                            exported,
+                            is_type_only: false,
+                            ..
+                        }) => {
+                            let exported = exported
+                                .as_ref()
+                                .map(export_name)
+                                .unwrap_or_else(|| orig.sym.to_string());
+                            actions.push((exported, orig.sym.to_string()));
+                        }
+                        ExportSpecifier::Named(_) => {}
+                        _ => return Err(invalid("namespace exports are not supported".into())),
+                    }
+                }
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(NamedExport {
+                src: Some(_),
+                type_only: false,
+                ..
+            }))
+            | ModuleItem::ModuleDecl(ModuleDecl::ExportAll(_)) => {
+                return Err(invalid("re-exports are not supported".into()));
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(ExportDefaultDecl {
+                decl,
+                ..
+            })) => match decl {
+                DefaultDecl::Fn(FnExpr { ident, function }) => {
+                    if !function.is_async {
+                        return Err(not_async("default"));
+                    }
+                    let ident =
+                        ident.get_or_insert_with(|| quote_ident!(DEFAULT_SERVER_ACTION).into());
+                    actions.push(("default".to_string(), ident.sym.to_string()));
+                }
+                DefaultDecl::Class(_) => return Err(not_async("default")),
+                DefaultDecl::TsInterfaceDecl(_) => {}
+            },
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(export)) => match &*export.expr {
+                Expr::Ident(ident) => {
+                    actions.push(("default".to_string(), ident.sym.to_string()));
+                }
+                expr if is_async_function(expr) => {
+                    let local: Ident = quote_ident!(DEFAULT_SERVER_ACTION).into();
+                    body.push(const_decl(local.clone(), export.expr.clone()));
+                    item = ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(NamedExport {
+                        span: DUMMY_SP,
+                        specifiers: vec![ExportSpecifier::Named(ExportNamedSpecifier {
+                            span: DUMMY_SP,
+                            orig: ModuleExportName::Ident(local.clone()),
+                            exported: Some(ModuleExportName::Ident(quote_ident!("default").into())),
+                            is_type_only: false,
+                        })],
+                        src: None,
+                        type_only: false,
+                        with: None,
+                    }));
+                    actions.push(("default".to_string(), local.sym.to_string()));
+                }
+                _ => return Err(not_async("default")),
+            },
+            _ => {}
+        }
+        body.push(item);
+    }
+    module.body = body;
+    Ok(actions)
+}
+
+// Appends the registration statements of the given actions, rendered from
+// `rscServer.serverActionTpl`. Imports in the template are only kept once.
+pub fn append_registrations(
+    module: &mut Module,
+    actions: &[(String, String)],
+    tpl: &str,
+    module_id: &str,
+    path: &str,
+    context: Arc<Context>,
+) -> Result<()> {
+    for (index, (name, local)) in actions.iter().enumerate() {
+        let content = tpl
+            .replace("{{local}}", local)
+            .replace("{{id}}", module_id)
+            .replace("{{name}}", name);
+        let ast = JsAst::build(path, &content, context.clone())?;
+        module.body.extend(ast.ast.body.into_iter().filter(|item| {
+            index == 0 || !matches!(item, ModuleItem::ModuleDecl(ModuleDecl::Import(_)))
+        }));
+    }
+    Ok(())
+}
+
+pub fn server_action_reference(tpl: &str, module_id: &str, name: &str) -> String {
+    tpl.replace("{{id}}", &format!("{}#{}", module_id, name))
+        .replace("{{name}}", name)
+}
+
+// Handles functions with an inline "use server" directive. On the server
+// they are hoisted to `$$ACTION_<n>` exports and the closure variables they
+// capture are bound as leading arguments, on the client they are replaced
+// with a reference rendered from `rscClient.serverActionTpl`.
+pub struct InlineServerActions {
+    pub module_id: String,
+    pub path: String,
+    pub reference_tpl: Option<String>,
+    pub context: Arc<Context>,
+    pub actions: Vec<String>,
+    pub error: Option<anyhow::Error>,
+    hoisted: Vec<ModuleItem>,
+    scopes: Vec<HashSet<String>>,
+    // the assignments inserted after the transformed function declarations
+    assignments: Vec<Stmt>,
+}
+
+impl InlineServerActions {
+    pub fn new(
+        module_id: String,
+        path: String,
+        reference_tpl: Option<String>,
+        context: Arc<Context>,
+    ) -> Self {
+        Self {
+            module_id,
+            path,
+            reference_tpl,
+            context,
+            actions: vec![],
+            error: None,
+            hoisted: vec![],
+            scopes: vec![],
+            assignments: vec![],
+        }
+    }
+
+    // the id is derived from the path and the function name, so that it's kept
+    // when the other actions of the module are added or removed
+    fn next_action(&mut self, name: Option<&Ident>) -> Ident {
+        let name = name.map_or("anonymous", |ident| ident.sym.as_str());
+        let action = (0..)
+            .map(|index| {
+                let key = if index == 0 {
+                    format!("{}#{}", self.path, name)
+                } else {
+                    format!("{}#{}#{}", self.path, name, index)
+                };
+                format!("{}{}", SERVER_ACTION_PREFIX, md5_hash(&key, 8))
+            })
+            .find(|action| !self.actions.contains(action))
+            .unwrap();
+        self.actions.push(action.clone());
+        quote_ident!(action).into()
+    }
+
+    fn transform_action(&mut self, function: Function, name: Option<&Ident>) -> Expr {
+        let action = self.next_action(name);
+        if let Some(tpl) = &self.reference_tpl {
+            let content = format!(
+                "({});",
+                server_action_reference(tpl, &self.module_id, &action.sym)
+            );
+            return match JsAst::build(&self.path, &content, self.context.clone()) {
+                Ok(JsAst { mut ast, .. }) => match ast.body.pop() {
+                    Some(ModuleItem::Stmt(Stmt::Expr(ExprStmt {
+                        expr: box Expr::Paren(paren),
+                        ..
+                    }))) => *paren.expr,
+                    _ => unreachable!(),
+                },
+                Err(err) => {
+                    self.error.get_or_insert(err);
+                    Expr::Ident(action)
+                }
+            };
+        }
+
+        let declared = names(collect_decls(&function));
+        let mut used = UsedIdents::default();
+        function.visit_with(&mut used);
+        let captured = used
+            .idents
+            .into_iter()
+            .filter(|ident| {
+                !declared.contains(ident.sym.as_str())
+                    && self
+                        .scopes
+                        .iter()
+                        .any(|scope| scope.contains(ident.sym.as_str()))
+            })
+            .collect::<Vec<_>>();
+
+        let mut function = function;
+        function.params.splice(
+            0..0,
+            captured.iter().map(|ident| Param {
+                span: DUMMY_SP,
+                decorators: vec![],
+                pat: Pat::Ident(ident.clone().into()),
+            }),
+        );
+        if let Some(body) = &mut function.body {
+            body.stmts.retain(|stmt| !is_use_server(stmt));
+        }
+        self.hoisted
+            .push(ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                span: DUMMY_SP,
+                decl: Decl::Fn(FnDecl {
+                    ident: action.clone(),
+                    declare: false,
+                    function: Box::new(function),
+                }),
+            })));
+
+        if captured.is_empty() {
+            Expr::Ident(action)
+        } else {
+            let mut args = vec![Expr::Lit(Lit::Null(Null { span: DUMMY_SP })).as_arg()];
+            args.extend(captured.into_iter().map(|ident| ident.as_arg()));
+            Expr::Ident(action)
+                .make_member(quote_ident!("bind"))
+                .as_call(DUMMY_SP, args)
+        }
+    }
+}
+
+impl VisitMut for InlineServerActions {
+    fn visit_mut_module(&mut self, module: &mut Module) {
+        module.visit_mut_children_with(self);
+        module.body.append(&mut self.hoisted);
+    }
+
+    fn visit_mut_function(&mut self, function: &mut Function) {
+        self.scopes.push(names(collect_decls(&*function)));
+        function.visit_mut_children_with(self);
+        self.scopes.pop();
+    }
+
+    fn visit_mut_arrow_expr(&mut self, arrow: &mut ArrowExpr) {
+        self.scopes.push(names(collect_decls(&*arrow)));
+        arrow.visit_mut_children_with(self);
+        self.scopes.pop();
+    }
+
+    fn visit_mut_module_items(&mut self, items: &mut Vec<ModuleItem>) {
+        let mut result = Vec::with_capacity(items.len());
+        for mut item in std::mem::take(items) {
+            item.visit_mut_with(self);
+            result.push(item);
+            result.extend(self.assignments.drain(..).map(ModuleItem::Stmt));
+        }
+        *items = result;
+    }
+
+    fn visit_mut_stmts(&mut self, stmts: &mut Vec<Stmt>) {
+        let mut result = Vec::with_capacity(stmts.len());
+        for mut stmt in std::mem::take(stmts) {
+            stmt.visit_mut_with(self);
+            result.push(stmt);
+            result.append(&mut self.assignments);
+        }
+        *stmts = result;
+    }
+
+    fn visit_mut_expr(&mut self, expr: &mut Expr) {
+        expr.visit_mut_children_with(self);
+        match expr {
+            Expr::Fn(FnExpr { ident, function })
+                if function.body.as_ref().is_some_and(has_use_server) =>
+            {
+                let ident = ident.clone();
+                *expr = self.transform_action(std::mem::take(&mut **function), ident.as_ref());
+            }
+            Expr::Arrow(arrow) if matches!(&*arrow.body, BlockStmtOrExpr::BlockStmt(body) if has_use_server(body)) =>
+            {
+                let arrow = std::mem::take(arrow);
+                let function = Function {
+                    params: arrow
+                        .params
+                        .into_iter()
+                        .map(|pat| Param {
+                            span: DUMMY_SP,
+                            decorators: vec![],
+                            pat,
+                        })
+                        .collect(),
+                    body: match *arrow.body {
+                        BlockStmtOrExpr::BlockStmt(body) => Some(body),
+                        BlockStmtOrExpr::Expr(expr) => Some(BlockStmt {
+                            stmts: vec![Stmt::Return(ReturnStmt {
+                                span: DUMMY_SP,
+                                arg: Some(expr),
+                            })],
+                            ..Default::default()
+                        }),
+                    },
+                    is_async: arrow.is_async,
+                    ..Default::default()
+                };
+                *expr = self.transform_action(function, None);
+            }
+            _ => {}
+        }
+    }
+
+    // the declaration is kept as a function which forwards to the action, so
+    // that it's still hoisted, and it's reassigned with the action in place, so
+    // that the action itself is passed around after that
+    fn visit_mut_stmt(&mut self, stmt: &mut Stmt) {
+        stmt.visit_mut_children_with(self);
+        if let Stmt::Decl(Decl::Fn(FnDecl {
+            ident, function, ..
+        })) = stmt
+            && function.body.as_ref().is_some_and(has_use_server)
+        {
+            let is_async = function.is_async;
+            let init = self.transform_action(std::mem::take(&mut **function), Some(ident));
+            let args = quote_ident!("args");
+            **function = Function {
+                params: vec![Param {
+                    span: DUMMY_SP,
+                    decorators: vec![],
+                    pat: Pat::Rest(RestPat {
+                        span: DUMMY_SP,
+                        dot3_token: DUMMY_SP,
+                        arg: Box::new(Pat::Ident(args.clone().into())),
+                        type_ann: None,
+                    }),
+                }],
+                body: Some(BlockStmt {
+                    stmts: vec![Stmt::Return(ReturnStmt {
+                        span: DUMMY_SP,
+                        arg: Some(Box::new(init.clone().as_call(
+                            DUMMY_SP,
+                            vec![ExprOrSpread {
+                                spread: Some(DUMMY_SP),
+                                expr: Box::new(Expr::Ident(args.into())),
+                            }],
+                        ))),
+                    })],
+                    ..Default::default()
+                }),
+                is_async,
+                ..Default::default()
+            };
+            self.assignments.push(
+                init.make_assign_to(AssignOp::Assign, ident.clone().into())
+                    .into_stmt(),
+            );
+        }
+    }
+}
+
+#[derive(Default)]
+struct UsedIdents {
+    idents: Vec<Ident>,
+}
+
+impl Visit for UsedIdents {
+    fn visit_ident(&mut self, ident: &Ident) {
+        if !self.idents.iter().any(|i| i.sym == ident.sym) {
+            self.idents.push(ident.clone());
+        }
+    }
+}
+
+fn names(ids: AHashSet<Id>) -> HashSet<String> {
+    ids.into_iter().map(|(sym, _)| sym.to_string()).collect()
+}
+
+fn has_use_server(body: &BlockStmt) -> bool {
+    body.stmts
+        .iter()
+        .take_while(|stmt| {
+            matches!(
+                stmt,
+                Stmt::Expr(ExprStmt {
+                    expr: box Expr::Lit(Lit::Str(_)),
+                    ..
+                })
+            )
+        })
+        .any(is_use_server)
+}
+
+fn is_use_server(stmt: &Stmt) -> bool {
+    matches!(
+        stmt,
+        Stmt::Expr(ExprStmt {
+            expr: box Expr::Lit(Lit::Str(Str { value, .. })),
+            ..
+        }) if value == "use server"
+    )
+}
+
+fn is_async_function(expr: &Expr) -> bool {
+    match expr {
+        Expr::Fn(FnExpr { function, .. }) => function.is_async,
+        Expr::Arrow(arrow) => arrow.is_async,
+        Expr::Paren(paren) => is_async_function(&paren.expr),
+        _ => false,
+    }
+}
+
+fn export_name(name: &ModuleExportName) -> String {
+    match name {
+        ModuleExportName::Ident(ident) => ident.sym.to_string(),
+        ModuleExportName::Str(str) => str.value.to_string(),
+    }
+}
+
+fn const_var(ident: Ident, init: Box<Expr>) -> VarDecl {
+    VarDecl {
+        span: DUMMY_SP,
+        kind: VarDeclKind::Const,
+        declare: false,
+        decls: vec![VarDeclarator {
+            span: DUMMY_SP,
+            name: Pat::Ident(ident.into()),
+            init: Some(init),
+            definite: false,
+        }],
+        ..Default::default()
+    }
+}
+
+fn const_decl(ident: Ident, init: Box<Expr>) -> ModuleItem {
+    ModuleItem::Stmt(Stmt::Decl(Decl::Var(Box::new(const_var(ident, init)))))
+}
@@ -2,6 +2,7 @@ pub(crate) mod analyze;
 pub(crate) mod chunk;
 pub(crate) mod chunk_graph;
 pub(crate) mod chunk_pot;
+pub(crate) mod css_chunking;
 pub(crate) mod generate_chunks;
 pub(crate) mod group_chunk;
 pub(crate) mod hmr;
@@ -13,7 +14,7 @@ pub(crate) mod transform;
 use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::ops::DerefMut;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
@@ -24,10 +25,13 @@ use rayon::prelude::*;
 use serde::Serialize;
 use tracing::debug;
 
+use crate::ast::file::win_path;
 use crate::compiler::{Compiler, Context};
-use crate::config::{DevtoolConfig, OutputMode, TreeShakingStrategy};
+use crate::config::{DevtoolConfig, LegalComments, Mode, OutputMode, TreeShakingStrategy};
 use crate::dev::update::UpdateResult;
+use crate::generate::chunk_pot::util::file_content_hash;
 use crate::generate::generate_chunks::{ChunkFile, ChunkFileType};
+use crate::generate::minify::{chunk_legal_comments, collect_legal_comments};
 use crate::module::{Dependency, ModuleId};
 use crate::plugins::bundless_compiler::BundlessCompiler;
 use crate::stats::StatsJsonMap;
@@ -45,11 +49,13 @@ impl Compiler {
         let bundless_compiler = BundlessCompiler::new(self.context.clone());
         bundless_compiler.generate()?;
 
-        let stats = self.create_stats_info();
+        let mut stats = self.create_stats_info();
 
         self.context
             .plugin_driver
             .build_success(&stats, &self.context)?;
+        // the assets emitted in build_success, e.g. the compressed files
+        stats.update_assets(&self.context.stats_info);
         Ok(stats)
     }
 
@@ -113,6 +119,7 @@ impl Compiler {
 
         let t_group_chunks = Instant::now();
         self.group_chunk();
+        let css_order = self.collect_css_order();
         let t_group_chunks = t_group_chunks.elapsed();
 
         let t_optimize_chunks = Instant::now();
@@ -121,7 +128,7 @@ impl Compiler {
             .plugin_driver
             .before_optimize_chunk(&self.context)?;
 
-        self.optimize_chunk();
+        self.optimize_chunk()?;
         let t_optimize_chunks = t_optimize_chunks.elapsed();
 
         {
@@ -135,6 +142,10 @@ impl Compiler {
             )?;
         }
 
+        if let Some(css_order) = css_order {
+            self.sort_chunk_css_modules(&css_order);
+        }
+
         // 为啥单独提前 transform modules？
         // 因为放 chunks 的循环里，一个 module 可能存在于多个 chunk 里，可能会被编译多遍
         let t_transform_modules = Instant::now();
@@ -172,20 +183,26 @@ impl Compiler {
         }
 
         // generate stats
-        let stats = self.create_stats_info();
+        let mut stats = self.create_stats_info();
 
         // build_success hook
         self.context
             .plugin_driver
             .build_success(&stats, &self.context)?;
+        // the assets emitted in build_success, e.g. the compressed files
+        stats.update_assets(&self.context.stats_info);
 
         // print stats
         if !self.context.args.watch {
             self.print_stats();
+            if config.stats.as_ref().is_some_and(|s| s.concatenation) {
+                self.print_concatenation_report();
+            }
+            self.print_split_chunks_report();
         }
 
         if self.context.config.analyze.is_some() {
-            Analyze::write_analyze(&stats, &self.context.config.output.path)?;
+            Analyze::write_analyze(&self.context)?;
         }
 
         debug!("generate done in {}ms", t_generate.elapsed().as_millis());
@@ -209,7 +226,10 @@ impl Compiler {
         // generate chunks
         let t_generate_chunks = Instant::now();
         debug!("generate chunks");
-        let chunk_files = self.generate_chunk_files(full_hash)?;
+        let mut chunk_files = self.generate_chunk_files(full_hash)?;
+        self.context
+            .plugin_driver
+            .transform_chunk_files(&mut chunk_files, &self.context)?;
         self.context
             .plugin_driver
             .after_generate_chunk_files(&chunk_files, &self.context)?;
@@ -283,7 +303,10 @@ impl Compiler {
 
         // generate chunks
         let t_generate_chunks = Instant::now();
-        let chunk_files = self.generate_chunk_files(current_hmr_hash)?;
+        let mut chunk_files = self.generate_chunk_files(current_hmr_hash)?;
+        self.context
+            .plugin_driver
+            .transform_chunk_files(&mut chunk_files, &self.context)?;
 
         if config.hmr.is_some() {
             let mut chunk_id_url_map = ChunksUrlMap {
@@ -377,7 +400,7 @@ impl Compiler {
         let t_group_chunks = t_group_chunks.elapsed();
 
         let t_optimize_chunks = Instant::now();
-        self.optimize_hot_update_chunk(&group_result);
+        self.optimize_hot_update_chunk(&group_result)?;
         let t_optimize_chunks = t_optimize_chunks.elapsed();
 
         let t_transform_modules = Instant::now();
@@ -450,8 +473,15 @@ impl Compiler {
                         IndexSet::from_iter(updated_modules.added.iter().cloned());
                     let merged_ids: IndexSet<ModuleId> =
                         modified_ids.union(&added_ids).cloned().collect();
-                    let (code, sourcemap) =
-                        self.generate_hmr_chunk(chunk, &filename, &merged_ids, current_hmr_hash)?;
+                    let removed_ids: IndexSet<ModuleId> =
+                        IndexSet::from_iter(updated_modules.removed.iter().cloned());
+                    let (code, sourcemap) = self.generate_hmr_chunk(
+                        chunk,
+                        &filename,
+                        &merged_ids,
+                        &removed_ids,
+                        current_hmr_hash,
+                    )?;
                     // TODO the final format should be {name}.{full_hash}.hot-update.{ext}
                     self.write_to_dist(&filename, code);
                     self.write_to_dist(format!("{}.map", &filename), sourcemap);
@@ -559,11 +589,12 @@ fn emit_chunk_file(context: &Arc<Context>, chunk_file: &ChunkFile) {
 
     let to: PathBuf = context.config.output.path.join(dist_name.as_str());
     let stats_info = &context.stats_info;
+    let content = &chunk_file.content;
 
     match context.config.devtool {
         Some(DevtoolConfig::SourceMap) => {
             let mut code = Vec::new();
-            code.extend_from_slice(&chunk_file.content);
+            code.extend_from_slice(content);
 
             if let Some(source_map) = &chunk_file.source_map {
                 let size = source_map.len() as u64;
@@ -616,7 +647,7 @@ fn emit_chunk_file(context: &Arc<Context>, chunk_file: &ChunkFile) {
         }
         Some(DevtoolConfig::InlineSourceMap) => {
             let mut code = Vec::new();
-            code.extend_from_slice(&chunk_file.content);
+            code.extend_from_slice(content);
 
             if let Some(source_map) = &chunk_file.source_map {
                 code.extend_from_slice(
@@ -640,17 +671,89 @@ fn emit_chunk_file(context: &Arc<Context>, chunk_file: &ChunkFile) {
         }
         None => {
             stats_info.add_assets(
-                chunk_file.content.len() as u64,
+                content.len() as u64,
                 chunk_file.file_name.clone(),
                 chunk_file.chunk_id.clone(),
                 to.to_string_lossy().to_string(),
                 dist_name,
             );
-            write_to_file(to.to_str().unwrap(), &chunk_file.content).unwrap();
+            write_to_file(to.to_str().unwrap(), content).unwrap();
         }
     }
 }
 
+// append the legal comments to the minified js chunks, or extract them to `[chunk].LICENSE.txt`,
+// it's called before the content hashes of the chunk files are computed
+fn append_legal_comments<'a>(
+    context: &Arc<Context>,
+    chunk_files: impl Iterator<Item = &'a mut ChunkFile>,
+) -> Result<()> {
+    let legal_comments = match &context.config.minify {
+        Some(minify)
+            if context.config.mode == Mode::Production
+                && minify.legal_comments != LegalComments::None =>
+        {
+            &minify.legal_comments
+        }
+        _ => return Ok(()),
+    };
+    let comments_by_file = collect_legal_comments(context);
+    if comments_by_file.is_empty() {
+        return Ok(());
+    }
+    for chunk_file in chunk_files.filter(|file| matches!(file.file_type, ChunkFileType::JS)) {
+        let comments = chunk_legal_comments(&chunk_file.chunk_id, &comments_by_file, context);
+        if comments.is_empty() {
+            continue;
+        }
+        match legal_comments {
+            LegalComments::Inline => {
+                chunk_file
+                    .content
+                    .extend_from_slice(format!("\n{}", comments.join("\n")).as_bytes());
+            }
+            LegalComments::External => {
+                // named after the file name without the hash, which changes with the banner
+                let license_file_name = format!(
+                    "{}.LICENSE.txt",
+                    Path::new(&chunk_file.file_name)
+                        .file_name()
+                        .unwrap()
+                        .to_string_lossy()
+                );
+                let license_name = win_path(
+                    &Path::new(&chunk_file.disk_name())
+                        .with_file_name(&license_file_name)
+                        .to_string_lossy(),
+                );
+                let license_content = format!("{}\n", comments.join("\n\n"));
+                let to = context.config.output.path.join(&license_name);
+                context.stats_info.add_assets(
+                    license_content.len() as u64,
+                    format!("{}.LICENSE.txt", chunk_file.file_name),
+                    chunk_file.chunk_id.clone(),
+                    to.to_string_lossy().to_string(),
+                    license_name,
+                );
+                write_to_file(to.to_str().unwrap(), &license_content.into_bytes())?;
+                // the banner is relative to the chunk file
+                chunk_file.content.extend_from_slice(
+                    format!(
+                        "\n/*! For license information please see {} */",
+                        license_file_name
+                    )
+                    .as_bytes(),
+                );
+            }
+            LegalComments::None => {}
+        }
+        if chunk_file.hash.is_some() {
+            chunk_file.hash = Some(file_content_hash(&chunk_file.content));
+        }
+    }
+    Ok(())
+}
+
 fn write_to_file(path: &str, content: &Vec<u8>) -> std::io::Result<()> {
     if let Some(parent) = std::path::Path::new(path).parent() {
         if !parent.exists() {
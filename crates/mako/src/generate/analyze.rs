@@ -1,15 +1,152 @@
+use std::collections::{BTreeMap, HashMap, VecDeque};
 use std::fs;
+use std::io::Write;
 use std::path::Path;
+use std::sync::Arc;
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
+use brotli::enc::BrotliEncoderParams;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use pathdiff::diff_paths;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use swc_core::css::codegen::writer::basic::{BasicCssWriter, BasicCssWriterConfig};
+use swc_core::css::codegen::{CodeGenerator, CodegenConfig, Emit};
+use swc_core::ecma::codegen::text_writer::JsWriter;
+use swc_core::ecma::codegen::{Config as JsCodegenConfig, Emitter};
+use tracing::warn;
 
-use crate::stats::StatsJsonMap;
+use crate::compiler::Context;
+use crate::module::{Module, ModuleAst, ModuleId};
+use crate::module_graph::ModuleGraph;
+use crate::plugins::compress::brotli_compress;
+use crate::plugins::duplicate_package_checker::{
+    clean_path_relative_to_context, extract_package_info, find_duplicates,
+};
+
+const MODULE_BROTLI_QUALITY: i32 = 4;
 
 pub struct Analyze {}
 
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct AnalyzeReport {
+    pub chunks: Vec<AnalyzeChunk>,
+    pub duplicates: Vec<AnalyzeDuplicate>,
+    // the modules whose sizes changed since `analyze.baseline`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub diff: Vec<AnalyzeDiffItem>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AnalyzeChunk {
+    pub id: String,
+    pub files: Vec<AnalyzeFile>,
+    pub modules: Vec<AnalyzeModule>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AnalyzeFile {
+    pub name: String,
+    pub size: u64,
+    pub gzip_size: u64,
+    #[serde(default)]
+    pub brotli_size: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AnalyzeModule {
+    pub id: String,
+    // the size of the source file
+    pub stat_size: u64,
+    // the size of the transformed code
+    pub parsed_size: u64,
+    // the size of the transformed code printed without whitespaces and comments, the chunk is
+    // compressed and mangled as a whole so it's an estimate of the module's share
+    pub minified_size: u64,
+    pub gzip_size: u64,
+    #[serde(default)]
+    pub brotli_size: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub package: Option<AnalyzePackage>,
+    // the shortest import chain from an entry to the module
+    pub reasons: Vec<String>,
+    pub issuers: Vec<String>,
+    // the modules concatenated into the module by scope hoisting
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub concatenated: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AnalyzePackage {
+    pub name: String,
+    pub version: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AnalyzeDuplicate {
+    pub name: String,
+    pub versions: Vec<AnalyzeDuplicateVersion>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AnalyzeDuplicateVersion {
+    pub version: String,
+    pub path: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AnalyzeDiffItem {
+    pub id: String,
+    pub baseline_size: u64,
+    pub size: u64,
+    pub baseline_gzip_size: u64,
+    pub gzip_size: u64,
+    #[serde(default)]
+    pub baseline_brotli_size: u64,
+    #[serde(default)]
+    pub brotli_size: u64,
+}
+
+struct ModuleSizes {
+    parsed: u64,
+    minified: u64,
+    gzip: u64,
+    brotli: u64,
+}
+
 impl Analyze {
-    pub fn write_analyze(stats: &StatsJsonMap, path: &Path) -> Result<()> {
-        let stats_json = serde_json::to_string_pretty(&stats).unwrap();
+    pub fn write_analyze(context: &Arc<Context>) -> Result<()> {
+        let mut report = Self::create_report(context)?;
+        let path = &context.config.output.path;
+        let stats_path = path.join("analyze.json");
+        fs::write(&stats_path, serde_json::to_string(&report)?)?;
+        if let Some(baseline) = context
+            .config
+            .analyze
+            .as_ref()
+            .and_then(|analyze| analyze.baseline.as_ref())
+        {
+            let baseline_path = context.root.join(baseline);
+            if baseline_path.exists() {
+                report.diff = Self::diff_stats(&baseline_path, &stats_path)?;
+            } else {
+                warn!(
+                    "analyze baseline {} not found, skip diffing",
+                    baseline_path.to_string_lossy()
+                );
+            }
+        }
+
+        let report_json = serde_json::to_string(&report)?;
         let html_str = format!(
             r#"<!DOCTYPE html>
 <html>
@@ -27,15 +164,409 @@ impl Analyze {
   </body>
 </html>"#,
             include_str!("../../../../client/dist/index.css"),
-            stats_json,
+            report_json.replace("</script>", "<\\/script>"),
             include_str!("../../../../client/dist/index.js").replace("</script>", "<\\/script>")
         );
         let report_path = path.join("analyze-report.html");
-        fs::write(&report_path, html_str).unwrap();
+        fs::write(&report_path, html_str)?;
         println!(
             "Analyze report generated at: {}",
             report_path.to_string_lossy()
         );
         Ok(())
     }
+
+    // diff the module sizes of two `analyze.json`, e.g. of a baseline and the current build
+    pub fn diff_stats(baseline: &Path, current: &Path) -> Result<Vec<AnalyzeDiffItem>> {
+        let read = |path: &Path| -> Result<AnalyzeReport> {
+            let content = fs::read_to_string(path)?;
+            serde_json::from_str(&content)
+                .map_err(|e| anyhow!("invalid analyze stats {}: {}", path.to_string_lossy(), e))
+        };
+        Ok(diff_reports(&read(baseline)?, &read(current)?))
+    }
+
+    pub fn create_report(context: &Arc<Context>) -> Result<AnalyzeReport> {
+        let chunk_graph = context.chunk_graph.read().unwrap();
+        let module_graph = context.module_graph.read().unwrap();
+        let relative_id = |id: &str| {
+            diff_paths(id, &context.root)
+                .map_or(id.to_string(), |p| p.to_string_lossy().to_string())
+        };
+
+        let modules = module_graph.modules();
+        let sizes = modules
+            .par_iter()
+            .map(|module| Ok((module.id.clone(), module_sizes(module, context)?)))
+            .collect::<Result<HashMap<_, _>>>()?;
+        let reasons = shortest_reasons(&module_graph);
+        let concatenated = context
+            .stats_info
+            .get_concatenation()
+            .map(|concatenation| {
+                concatenation
+                    .modules
+                    .into_iter()
+                    .filter(|module| !module.inners.is_empty())
+                    .map(|module| (module.id, module.inners))
+                    .collect::<HashMap<_, _>>()
+            })
+            .unwrap_or_default();
+        let assets = context.stats_info.get_assets();
+
+        let mut chunks = chunk_graph.get_chunks();
+        chunks.sort_by(|a, b| a.id.id.cmp(&b.id.id));
+        let chunks = chunks
+            .into_iter()
+            .map(|chunk| {
+                let mut files = assets
+                    .iter()
+                    .filter(|asset| {
+                        asset.chunk_id == chunk.id.id
+                            && !asset.hashname.ends_with(".map")
+                            && !asset.hashname.ends_with(".LICENSE.txt")
+                    })
+                    .map(|asset| {
+                        let content = fs::read(&asset.path).unwrap_or_default();
+                        AnalyzeFile {
+                            name: asset.hashname.clone(),
+                            size: asset.size,
+                            gzip_size: gzip_size(&content).unwrap_or_default(),
+                            brotli_size: brotli_compress(&content)
+                                .map_or(0, |compressed| compressed.len() as u64),
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                files.sort_by(|a, b| a.name.cmp(&b.name));
+                let modules = chunk
+                    .get_modules()
+                    .iter()
+                    .filter_map(|module_id| {
+                        let module = module_graph.get_module(module_id)?;
+                        let sizes = sizes.get(module_id)?;
+                        let id = relative_id(&module_id.id);
+                        let package = extract_package_info(module).map(|package| AnalyzePackage {
+                            name: package.name,
+                            version: package.version.to_string(),
+                        });
+                        let mut issuers = module_graph
+                            .get_dependents(module_id)
+                            .into_iter()
+                            .map(|(issuer, _)| relative_id(&issuer.id))
+                            .collect::<Vec<_>>();
+                        issuers.sort();
+                        issuers.dedup();
+                        Some(AnalyzeModule {
+                            stat_size: fs::metadata(&module_id.id)
+                                .map(|m| m.len())
+                                .unwrap_or_default(),
+                            parsed_size: sizes.parsed,
+                            minified_size: sizes.minified,
+                            gzip_size: sizes.gzip,
+                            brotli_size: sizes.brotli,
+                            package,
+                            reasons: reasons
+                                .get(module_id)
+                                .map(|chain| chain.iter().map(|id| relative_id(&id.id)).collect())
+                                .unwrap_or_default(),
+                            issuers,
+                            concatenated: concatenated.get(&id).cloned().unwrap_or_default(),
+                            id,
+                        })
+                    })
+                    .collect();
+                AnalyzeChunk {
+                    id: relative_id(&chunk.id.id),
+                    files,
+                    modules,
+                }
+            })
+            .collect();
+
+        let packages = modules
+            .iter()
+            .filter_map(|module| extract_package_info(module))
+            .collect();
+        let mut duplicates = find_duplicates(packages)
+            .into_iter()
+            .map(|(name, mut instances)| {
+                instances.sort_by(|a, b| a.version.cmp(&b.version).then(a.path.cmp(&b.path)));
+                AnalyzeDuplicate {
+                    name,
+                    versions: instances
+                        .into_iter()
+                        .map(|instance| AnalyzeDuplicateVersion {
+                            version: instance.version.to_string(),
+                            path: clean_path_relative_to_context(&instance.path, &context.root)
+                                .to_string_lossy()
+                                .to_string(),
+                        })
+                        .collect(),
+                }
+            })
+            .collect::<Vec<_>>();
+        duplicates.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(AnalyzeReport {
+            chunks,
+            duplicates,
+            diff: vec![],
+        })
+    }
+}
+
+fn module_sizes(module: &Module, context: &Arc<Context>) -> Result<ModuleSizes> {
+    let (parsed, minified) = match module.info.as_ref().map(|info| &info.ast) {
+        Some(ModuleAst::Script(ast)) => {
+            let print = |minify: bool| -> Result<Vec<u8>> {
+                let cm = context.meta.script.cm.clone();
+                let mut buf = vec![];
+                let mut emitter = Emitter {
+                    cfg: JsCodegenConfig::default()
+                        .with_minify(minify)
+                        .with_target(context.config.output.es_version),
+                    cm: cm.clone(),
+                    comments: None,
+                    wr: Box::new(JsWriter::new(cm, "\n", &mut buf, None)),
+                };
+                emitter.emit_module(&ast.ast)?;
+                Ok(buf)
+            };
+            (print(false)?, print(true)?)
+        }
+        Some(ModuleAst::Css(ast)) => {
+            let print = |minify: bool| -> Result<Vec<u8>> {
+                let mut code = String::new();
+                let writer = BasicCssWriter::new(&mut code, None, BasicCssWriterConfig::default());
+                let mut gen = CodeGenerator::new(writer, CodegenConfig { minify });
+                gen.emit(&ast.ast)?;
+                Ok(code.into_bytes())
+            };
+            (print(false)?, print(true)?)
+        }
+        _ => (vec![], vec![]),
+    };
+    Ok(ModuleSizes {
+        parsed: parsed.len() as u64,
+        minified: minified.len() as u64,
+        gzip: if minified.is_empty() {
+            0
+        } else {
+            gzip_size(&minified)?
+        },
+        brotli: if minified.is_empty() {
+            0
+        } else {
+            brotli_size(&minified)?
+        },
+    })
+}
+
+pub(crate) fn gzip_size(content: &[u8]) -> Result<u64> {
+    let mut encoder = GzEncoder::new(vec![], Compression::default());
+    encoder.write_all(content)?;
+    Ok(encoder.finish()?.len() as u64)
+}
+
+// the estimate of every module is compressed, so a fast quality is used instead of the default
+// one of the chunk files
+fn brotli_size(content: &[u8]) -> Result<u64> {
+    let mut compressed = vec![];
+    brotli::BrotliCompress(
+        &mut &content[..],
+        &mut compressed,
+        &BrotliEncoderParams {
+            quality: MODULE_BROTLI_QUALITY,
+            ..Default::default()
+        },
+    )?;
+    Ok(compressed.len() as u64)
+}
+
+// breadth first from the entries, so the chain to each module is one of the shortest
+fn shortest_reasons(module_graph: &ModuleGraph) -> HashMap<ModuleId, Vec<ModuleId>> {
+    let mut issuer_map: HashMap<ModuleId, Option<ModuleId>> = HashMap::new();
+    let mut queue = VecDeque::new();
+    let mut entries = module_graph.get_entry_modules();
+    entries.sort_by(|a, b| a.id.cmp(&b.id));
+    for entry in entries {
+        issuer_map.insert(entry.clone(), None);
+        queue.push_back(entry);
+    }
+    while let Some(module_id) = queue.pop_front() {
+        let mut dependencies = module_graph
+            .get_dependencies(&module_id)
+            .into_iter()
+            .map(|(id, _)| id.clone())
+            .collect::<Vec<_>>();
+        dependencies.sort_by(|a, b| a.id.cmp(&b.id));
+        for dependency in dependencies {
+            if issuer_map.contains_key(&dependency) {
+                continue;
+            }
+            issuer_map.insert(dependency.clone(), Some(module_id.clone()));
+            queue.push_back(dependency);
+        }
+    }
+
+    issuer_map
+        .keys()
+        .map(|module_id| {
+            let mut chain = vec![module_id.clone()];
+            let mut current = module_id;
+            while let Some(Some(issuer)) = issuer_map.get(current) {
+                chain.push(issuer.clone());
+                current = issuer;
+            }
+            chain.reverse();
+            (module_id.clone(), chain)
+        })
+        .collect()
+}
+
+fn diff_reports(baseline: &AnalyzeReport, current: &AnalyzeReport) -> Vec<AnalyzeDiffItem> {
+    // a module may be in more than one chunk
+    let module_sizes = |report: &AnalyzeReport| {
+        let mut sizes: BTreeMap<String, (u64, u64, u64)> = BTreeMap::new();
+        for module in report.chunks.iter().flat_map(|chunk| chunk.modules.iter()) {
+            let size = sizes.entry(module.id.clone()).or_default();
+            size.0 += module.minified_size;
+            size.1 += module.gzip_size;
+            size.2 += module.brotli_size;
+        }
+        sizes
+    };
+    let baseline_sizes = module_sizes(baseline);
+    let current_sizes = module_sizes(current);
+    let mut diff = baseline_sizes
+        .keys()
+        .chain(current_sizes.keys())
+        .collect::<std::collections::BTreeSet<_>>()
+        .into_iter()
+        .filter_map(|id| {
+            let (baseline_size, baseline_gzip_size, baseline_brotli_size) =
+                baseline_sizes.get(id).cloned().unwrap_or_default();
+            let (size, gzip_size, brotli_size) = current_sizes.get(id).cloned().unwrap_or_default();
+            (baseline_size != size).then(|| AnalyzeDiffItem {
+                id: id.clone(),
+                baseline_size,
+                size,
+                baseline_gzip_size,
+                gzip_size,
+                baseline_brotli_size,
+                brotli_size,
+            })
+        })
+        .collect::<Vec<_>>();
+    // the modules grew the most come first
+    diff.sort_by_key(|item| std::cmp::Reverse(item.size as i64 - item.baseline_size as i64));
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use super::{Analyze, AnalyzeChunk, AnalyzeModule, AnalyzeReport};
+    use crate::compiler::Compiler;
+    use crate::config::Config;
+
+    #[test]
+    fn test_analyze_report() {
+        let root = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test/build/duplicate-package");
+        let config = Config::new(&root, None, None).unwrap();
+        let compiler = Compiler::new(config, root, Default::default(), None).unwrap();
+        compiler.compile().unwrap();
+
+        let report = Analyze::create_report(&compiler.context).unwrap();
+        let modules = report
+            .chunks
+            .iter()
+            .flat_map(|chunk| chunk.modules.iter())
+            .collect::<Vec<_>>();
+        let module = modules
+            .iter()
+            .find(|module| module.id == "node_modules/c/node_modules/d/node_modules/b/index.js")
+            .unwrap();
+        assert_eq!(
+            module.reasons,
+            vec![
+                "index.ts",
+                "node_modules/c/index.js",
+                "node_modules/c/node_modules/d/index.js",
+                "node_modules/c/node_modules/d/node_modules/b/index.js"
+            ]
+        );
+        assert_eq!(
+            module.issuers,
+            vec!["node_modules/c/node_modules/d/index.js"]
+        );
+        assert_eq!(module.package.as_ref().unwrap().name, "b");
+        let entry = modules
+            .iter()
+            .find(|module| module.id == "index.ts")
+            .unwrap();
+        assert_eq!(entry.reasons, vec!["index.ts"]);
+        assert!(entry.stat_size > 0);
+        assert!(entry.parsed_size > entry.minified_size && entry.minified_size > 0);
+        assert!(entry.gzip_size > 0 && entry.brotli_size > 0);
+        assert_eq!(
+            report
+                .duplicates
+                .iter()
+                .map(|duplicate| (duplicate.name.as_str(), duplicate.versions.len()))
+                .collect::<Vec<_>>(),
+            vec![("a", 2), ("b", 2)]
+        );
+        assert!(report.chunks[0].files[0].gzip_size > 0);
+        assert!(report.chunks[0].files[0].brotli_size > 0);
+    }
+
+    #[test]
+    fn test_diff_stats() {
+        let report = |sizes: &[(&str, u64)]| AnalyzeReport {
+            chunks: vec![AnalyzeChunk {
+                id: "index.js".to_string(),
+                files: vec![],
+                modules: sizes
+                    .iter()
+                    .map(|(id, size)| AnalyzeModule {
+                        id: id.to_string(),
+                        stat_size: 0,
+                        parsed_size: 0,
+                        minified_size: *size,
+                        gzip_size: *size / 2,
+                        brotli_size: *size / 3,
+                        package: None,
+                        reasons: vec![],
+                        issuers: vec![],
+                        concatenated: vec![],
+                    })
+                    .collect(),
+            }],
+            ..Default::default()
+        };
+        let baseline = report(&[("a.js", 10), ("b.js", 20), ("c.js", 30)]);
+        let current = report(&[("a.js", 10), ("b.js", 40), ("d.js", 5)]);
+        let dir = std::env::temp_dir().join("mako-analyze-diff-stats");
+        fs::create_dir_all(&dir).unwrap();
+        let (baseline_path, current_path) = (dir.join("baseline.json"), dir.join("current.json"));
+        fs::write(&baseline_path, serde_json::to_string(&baseline).unwrap()).unwrap();
+        fs::write(&current_path, serde_json::to_string(&current).unwrap()).unwrap();
+        let diff = Analyze::diff_stats(&baseline_path, &current_path)
+            .unwrap()
+            .into_iter()
+            .map(|item| (item.id, item.baseline_size, item.size))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            diff,
+            vec![
+                ("b.js".to_string(), 20, 40),
+                ("d.js".to_string(), 0, 5),
+                ("c.js".to_string(), 30, 0)
+            ]
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
 }
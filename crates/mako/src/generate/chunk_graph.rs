@@ -137,6 +137,14 @@ impl ChunkGraph {
             .collect::<Vec<ChunkId>>()
     }
 
+    pub fn dependencies_chunk(&self, chunk_id: &ChunkId) -> Vec<ChunkId> {
+        let idx = self.id_index_map.get(chunk_id).unwrap();
+        self.graph
+            .neighbors_directed(*idx, Direction::Outgoing)
+            .map(|idx| self.graph[idx].id.clone())
+            .collect::<Vec<ChunkId>>()
+    }
+
     pub fn dependents_chunk(&self, chunk_id: &ChunkId) -> Vec<ChunkId> {
         let idx = self.id_index_map.get(chunk_id).unwrap();
         self.graph
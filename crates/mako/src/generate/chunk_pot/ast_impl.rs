@@ -19,7 +19,7 @@ use swc_core::ecma::utils::{quote_ident, quote_str, ExprFactory};
 use crate::ast::js_ast::JsAst;
 use crate::ast::sourcemap::{build_source_map, merge_source_map};
 use crate::compiler::Context;
-use crate::config::Mode;
+use crate::config::{Mode, Platform};
 use crate::generate::chunk::{Chunk, ChunkType};
 use crate::generate::chunk_pot::util::{
     file_content_hash, pot_to_chunk_module, pot_to_module_object, runtime_code,
@@ -67,14 +67,15 @@ pub(crate) fn render_css_chunk(
         transform_css_generate(&mut stylesheet, context);
     }
 
-    if context.config.minify && matches!(context.config.mode, Mode::Production) {
+    if context.config.minify.is_some() && matches!(context.config.mode, Mode::Production) {
         minify_css(&mut stylesheet, context)?;
     }
 
     let mut gen = CodeGenerator::new(
         css_writer,
         CodegenConfig {
-            minify: context.config.minify && matches!(context.config.mode, Mode::Production),
+            minify: context.config.minify.is_some()
+                && matches!(context.config.mode, Mode::Production),
         },
     );
     gen.emit(&stylesheet)?;
@@ -165,7 +166,7 @@ pub(crate) fn render_normal_js_chunk(
         path: "".to_string(),
     });
 
-    if context.config.minify && matches!(context.config.mode, Mode::Production) {
+    if context.config.minify.is_some() && matches!(context.config.mode, Mode::Production) {
         minify_js(&mut ast, context)?;
     }
 
@@ -257,6 +258,7 @@ fn render_entry_chunk_js_without_full_hash(
     crate::mako_profile_function!(&pot.chunk_id);
 
     let mut stmts = vec![];
+    let mut shebang = None;
 
     let (js_map_stmt, css_map_stmt) = chunk_map_decls(js_map, css_map);
 
@@ -270,6 +272,15 @@ fn render_entry_chunk_js_without_full_hash(
                 .into();
 
             stmts.push(main_id_decl);
+
+            // keep the hashbang of node entries, e.g. #!/usr/bin/env node
+            if context.config.platform == Platform::Node {
+                shebang = pot
+                    .module_map
+                    .get(&module_id.generate(context))
+                    .and_then(|(module, _)| module.as_script())
+                    .and_then(|ast| ast.ast.shebang.clone());
+            }
         }
         ChunkType::Worker(module_id) => {
             let main_id_decl: Stmt = quote_str!(module_id.generate(context))
@@ -333,9 +344,10 @@ fn render_entry_chunk_js_without_full_hash(
             .splice(0..0, stmts.into_iter().map(|s| s.into()));
 
         ast.ast = wrap_in_iife(ast.ast);
+        ast.ast.shebang = shebang;
     }
 
-    if context.config.minify && matches!(context.config.mode, Mode::Production) {
+    if context.config.minify.is_some() && matches!(context.config.mode, Mode::Production) {
         minify_js(&mut ast, context)?;
     }
 
@@ -338,7 +338,7 @@ mod tests {
         let context = Arc::new(Context {
             config: Config {
                 mode: Mode::Development,
-                minify: true,
+                minify: Some(Default::default()),
                 ..Default::default()
             },
             args: Args { watch: true },
@@ -436,7 +436,7 @@ mod tests {
         let swc_comments = comments.get_swc_comments();
         {
             let with_minify =
-                context.config.minify && matches!(context.config.mode, Mode::Production);
+                context.config.minify.is_some() && matches!(context.config.mode, Mode::Production);
             let mut emitter = Emitter {
                 cfg: JsCodegenConfig::default()
                     .with_minify(with_minify)
@@ -36,7 +36,8 @@ pub(crate) fn render_module_js(
     let mut buf = vec![];
     let mut source_map_buf = Vec::new();
     let cm = context.meta.script.cm.clone();
-    let with_minify = context.config.minify && matches!(context.config.mode, Mode::Production);
+    let with_minify =
+        context.config.minify.is_some() && matches!(context.config.mode, Mode::Production);
     let comments = context.meta.script.origin_comments.read().unwrap();
     let swc_comments = comments.get_swc_comments();
 
@@ -0,0 +1,245 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+use colored::Colorize;
+use hashlink::LinkedHashSet;
+use tracing::debug;
+
+use crate::compiler::Compiler;
+use crate::generate::chunk::ChunkType;
+use crate::module::{relative_to_root, ModuleId, ModuleType};
+
+/**
+ * The global css order of all chunks, css module -> global index.
+ */
+pub type CssOrder = HashMap<ModuleId, usize>;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct CssOrderConflict {
+    pub chunk: String,
+    // the module which is imported before `after` in current chunk
+    pub before: ModuleId,
+    pub after: ModuleId,
+}
+
+impl Compiler {
+    /**
+     * Collect css orders from chunks which are grouped by module graph, this must be called
+     * before optimize_chunk, because the css modules may be moved to split chunks.
+     */
+    pub fn collect_css_order(&self) -> Option<CssOrder> {
+        let css_chunking = self.context.config.css_chunking.as_ref()?;
+        crate::mako_profile_function!();
+        debug!("collect css order");
+
+        let module_graph = self.context.module_graph.read().unwrap();
+        let chunk_graph = self.context.chunk_graph.read().unwrap();
+
+        let mut chunks = chunk_graph
+            .get_chunks()
+            .into_iter()
+            .filter(|c| !matches!(c.chunk_type, ChunkType::Worker(_)))
+            .collect::<Vec<_>>();
+        // make the order stable across builds
+        chunks.sort_by_key(|c| c.id.id.clone());
+
+        let chunk_css_modules = chunks
+            .iter()
+            .map(|c| {
+                (
+                    c.filename(),
+                    c.modules
+                        .iter()
+                        .filter(|m| {
+                            module_graph
+                                .get_module(m)
+                                .is_some_and(|m| m.get_module_type() == ModuleType::Css)
+                        })
+                        .cloned()
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let (order, conflicts) = resolve_css_order(&chunk_css_modules);
+
+        if !css_chunking.ignore_order {
+            for conflict in conflicts {
+                println!(
+                    "{}: Conflicting order in chunk {}, {} is imported before {}, but other chunks import them in the reverse order",
+                    "warning".to_string().yellow(),
+                    conflict.chunk,
+                    relative_to_root(&conflict.before.id, &self.context.root),
+                    relative_to_root(&conflict.after.id, &self.context.root),
+                );
+            }
+        }
+
+        Some(order)
+    }
+
+    /**
+     * Sort the css modules of every chunk by the global css order, js modules keep their positions.
+     */
+    pub fn sort_chunk_css_modules(&self, css_order: &CssOrder) {
+        crate::mako_profile_function!();
+        debug!("sort chunk css modules");
+
+        let mut chunk_graph = self.context.chunk_graph.write().unwrap();
+
+        for chunk in chunk_graph.mut_chunks() {
+            let mut css_modules = chunk
+                .modules
+                .iter()
+                .filter(|m| css_order.contains_key(m))
+                .cloned()
+                .collect::<Vec<_>>();
+
+            if css_modules.len() < 2 {
+                continue;
+            }
+
+            css_modules.sort_by_key(|m| css_order.get(m).unwrap());
+
+            let mut css_modules = css_modules.into_iter();
+            chunk.modules = chunk
+                .modules
+                .iter()
+                .map(|m| {
+                    if css_order.contains_key(m) {
+                        css_modules.next().unwrap()
+                    } else {
+                        m.clone()
+                    }
+                })
+                .collect::<LinkedHashSet<_>>();
+        }
+    }
+}
+
+/**
+ * Merge css module orders of all chunks to a global order, by a topological sort on the
+ * "imported before" relations. When the relations of chunks conflict with each other, the
+ * module which appears first wins, and the conflicts will be returned.
+ */
+pub fn resolve_css_order(chunks: &[(String, Vec<ModuleId>)]) -> (CssOrder, Vec<CssOrderConflict>) {
+    let mut first_seen: HashMap<&ModuleId, usize> = HashMap::new();
+    let mut modules: Vec<&ModuleId> = vec![];
+    let mut edges: HashMap<&ModuleId, HashSet<&ModuleId>> = HashMap::new();
+    let mut in_degree: HashMap<&ModuleId, usize> = HashMap::new();
+
+    for (_, css_modules) in chunks {
+        for module_id in css_modules {
+            if !first_seen.contains_key(module_id) {
+                first_seen.insert(module_id, modules.len());
+                modules.push(module_id);
+                in_degree.insert(module_id, 0);
+            }
+        }
+        for pair in css_modules.windows(2) {
+            if edges.entry(&pair[0]).or_default().insert(&pair[1]) {
+                *in_degree.get_mut(&pair[1]).unwrap() += 1;
+            }
+        }
+    }
+
+    let mut queue = BinaryHeap::new();
+    for (module_id, degree) in &in_degree {
+        if *degree == 0 {
+            queue.push(Reverse(first_seen[module_id]));
+        }
+    }
+
+    let mut order = CssOrder::new();
+    while order.len() < modules.len() {
+        let index = match queue.pop() {
+            Some(Reverse(index)) => index,
+            // there is a cycle, break it with the first seen module
+            None => modules
+                .iter()
+                .enumerate()
+                .find(|(_, m)| !order.contains_key(**m))
+                .map(|(index, _)| index)
+                .unwrap(),
+        };
+        let module_id = modules[index];
+        if order.contains_key(module_id) {
+            continue;
+        }
+        order.insert(module_id.clone(), order.len());
+
+        if let Some(nexts) = edges.get(module_id) {
+            for next in nexts {
+                let degree = in_degree.get_mut(next).unwrap();
+                *degree = degree.saturating_sub(1);
+                if *degree == 0 && !order.contains_key(*next) {
+                    queue.push(Reverse(first_seen[next]));
+                }
+            }
+        }
+    }
+
+    let mut conflicts = vec![];
+    for (chunk, css_modules) in chunks {
+        let mut prev: Option<&ModuleId> = None;
+        for module_id in css_modules {
+            if let Some(prev) = prev
+                && order[module_id] < order[prev]
+            {
+                conflicts.push(CssOrderConflict {
+                    chunk: chunk.clone(),
+                    before: prev.clone(),
+                    after: module_id.clone(),
+                });
+                break;
+            }
+            if prev.map_or(true, |p| order[module_id] > order[p]) {
+                prev = Some(module_id);
+            }
+        }
+    }
+
+    (order, conflicts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(ids: &[&str]) -> Vec<ModuleId> {
+        ids.iter().map(|id| ModuleId::new(id.to_string())).collect()
+    }
+
+    fn sorted(order: &CssOrder) -> Vec<String> {
+        let mut modules = order.iter().collect::<Vec<_>>();
+        modules.sort_by_key(|(_, index)| **index);
+        modules.into_iter().map(|(m, _)| m.id.clone()).collect()
+    }
+
+    #[test]
+    fn test_merge_orders_of_chunks() {
+        let (order, conflicts) = resolve_css_order(&[
+            ("a.js".to_string(), ids(&["reset.css", "a.css", "c.css"])),
+            ("b.js".to_string(), ids(&["reset.css", "b.css", "c.css"])),
+        ]);
+        assert_eq!(sorted(&order), vec!["reset.css", "a.css", "b.css", "c.css"]);
+        assert!(conflicts.is_empty());
+    }
+
+    #[test]
+    fn test_conflicting_orders() {
+        let (order, conflicts) = resolve_css_order(&[
+            ("a.js".to_string(), ids(&["a.css", "b.css"])),
+            ("b.js".to_string(), ids(&["b.css", "a.css"])),
+        ]);
+        assert_eq!(sorted(&order), vec!["a.css", "b.css"]);
+        assert_eq!(
+            conflicts,
+            vec![CssOrderConflict {
+                chunk: "b.js".to_string(),
+                before: "b.css".into(),
+                after: "a.css".into(),
+            }]
+        );
+    }
+}
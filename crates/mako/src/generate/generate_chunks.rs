@@ -14,6 +14,7 @@ use tracing::warn;
 use twox_hash::XxHash64;
 
 use crate::compiler::{Compiler, Context};
+use crate::generate::append_legal_comments;
 use crate::generate::chunk::{Chunk, ChunkType};
 use crate::generate::chunk_pot::util::file_content_hash;
 use crate::generate::chunk_pot::{get_css_chunk_filename, ChunkPot, CHUNK_FILE_NAME_HASH_LENGTH};
@@ -110,10 +111,20 @@ impl Compiler {
             || self.generate_normal_chunk_files(normal_chunks),
         );
 
-        let normal_chunk_files = normal_chunk_files?;
+        let mut normal_chunk_files = normal_chunk_files?;
 
         let mut entry_chunk_files_with_placeholder = entry_chunk_files_with_placeholder?;
 
+        // before the hashed names of the normal chunks are referenced by the entry chunks
+        append_legal_comments(
+            &self.context,
+            normal_chunk_files.iter_mut().chain(
+                entry_chunk_files_with_placeholder
+                    .iter_mut()
+                    .flat_map(|(chunk_files, _, _)| chunk_files.iter_mut()),
+            ),
+        )?;
+
         if self.context.config.hash {
             let (js_chunks_hash_replacer, css_chunks_hash_replacer) =
                 normal_chunk_files.iter().fold(
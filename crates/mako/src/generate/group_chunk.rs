@@ -291,8 +291,10 @@ impl Compiler {
                         .get_dependents(module_id)
                         .iter()
                         .find_map(|(_, dep)| match &dep.resolve_type {
-                            ResolveType::DynamicImport(import_options) => {
-                                Some((module_id.clone(), import_options.clone()))
+                            ResolveType::DynamicImport(import_options)
+                                if !import_options.is_eager() =>
+                            {
+                                Some((module_id.clone(), import_options.for_chunk_group()))
                             }
                             _ => None,
                         })
@@ -366,11 +368,10 @@ impl Compiler {
             let static_deps = module_graph
                 .get_dependencies(head)
                 .into_iter()
-                .filter(|(_, dep)| {
-                    !matches!(
-                        dep.resolve_type,
-                        ResolveType::DynamicImport(_) | ResolveType::Worker(_)
-                    )
+                .filter(|(_, dep)| match &dep.resolve_type {
+                    ResolveType::DynamicImport(import_options) => import_options.is_eager(),
+                    ResolveType::Worker(_) => false,
+                    _ => true,
                 })
                 .collect::<Vec<_>>();
             let mut next_module_ids = vec![];
@@ -460,8 +461,10 @@ impl Compiler {
 
             for (dep_module_id, dep) in module_graph.get_dependencies(head) {
                 match &dep.resolve_type {
-                    ResolveType::DynamicImport(chunk_group) => {
-                        dynamic_entries.push((dep_module_id.clone(), chunk_group.clone()));
+                    // eager dynamic imports fall through to be bundled into the current chunk
+                    ResolveType::DynamicImport(chunk_group) if !chunk_group.is_eager() => {
+                        dynamic_entries
+                            .push((dep_module_id.clone(), chunk_group.for_chunk_group()));
                     }
                     ResolveType::Worker(chunk_group) => {
                         worker_entries.push((dep_module_id.clone(), chunk_group.clone()));
@@ -17,6 +17,7 @@ impl Compiler {
         chunk: &Chunk,
         filename: &str,
         module_ids: &IndexSet<ModuleId>,
+        removed_module_ids: &IndexSet<ModuleId>,
         current_hash: u64,
     ) -> Result<(String, String)> {
         let module_graph = &self.context.module_graph.read().unwrap();
@@ -32,6 +33,16 @@ impl Compiler {
 
         let content = content
             .replace("__CHUNK_ID__", &chunk.id.id)
+            // the prune handlers of the removed modules are called by the hmr runtime
+            .replace(
+                "__REMOVED_MODULES__",
+                &serde_json::to_string(
+                    &removed_module_ids
+                        .iter()
+                        .map(|id| id.generate(&self.context))
+                        .collect::<Vec<_>>(),
+                )?,
+            )
             .replace("__runtime_code__", &runtime_code_snippets.join("\n"));
 
         let mut js_ast = JsAst::build(filename, content.as_str(), self.context.clone())
@@ -49,13 +60,10 @@ impl Compiler {
                     ..
                 } = &mut args[1]
                 {
-                    if props.len() != 1 {
-                        panic!("hmr runtime should only have one modules property");
-                    }
-                    if let PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+                    if let Some(PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
                         value: box Expr::Object(ObjectLit { props, .. }),
                         ..
-                    })) = &mut props[0]
+                    }))) = props.first_mut()
                     {
                         props.extend(js_stmts);
                         break;
@@ -1,13 +1,20 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
+use indexmap::IndexSet;
 use swc_core::base::try_with_handler;
+use swc_core::common::comments::{Comment, CommentKind};
 use swc_core::common::errors::HANDLER;
-use swc_core::common::GLOBALS;
+use swc_core::common::{BytePos, FileName, Spanned, GLOBALS};
 use swc_core::css::ast::Stylesheet;
 use swc_core::css::minifier;
+use swc_core::ecma::ast::EsVersion;
 use swc_core::ecma::minifier::optimize;
-use swc_core::ecma::minifier::option::{ExtraOptions, MinifyOptions};
+use swc_core::ecma::minifier::option::{
+    CompressOptions, ExtraOptions, MangleOptions, MinifyOptions,
+};
+use swc_core::ecma::parser::parse_file_as_expr;
 use swc_core::ecma::transforms::base::fixer::{fixer, paren_remover};
 use swc_core::ecma::transforms::base::helpers::{Helpers, HELPERS};
 use swc_core::ecma::transforms::base::resolver;
@@ -15,6 +22,8 @@ use swc_core::ecma::visit::VisitMutWith;
 
 use crate::ast::js_ast::JsAst;
 use crate::compiler::Context;
+use crate::config::MinifyConfig;
+use crate::module::ModuleAst;
 
 pub fn minify_js(ast: &mut JsAst, context: &Arc<Context>) -> Result<()> {
     crate::mako_profile_function!();
@@ -39,14 +48,15 @@ pub fn minify_js(ast: &mut JsAst, context: &Arc<Context>) -> Result<()> {
                         ));
                         ast.ast.visit_mut_with(&mut paren_remover(Some(comments)));
 
+                        let minify_config = context.config.minify.clone().unwrap_or_default();
                         let mut minified = optimize(
                             ast.ast.clone().into(),
                             context.meta.script.cm.clone(),
                             Some(comments),
                             None,
                             &MinifyOptions {
-                                compress: Some(Default::default()),
-                                mangle: Some(Default::default()),
+                                compress: Some(to_compress_options(&minify_config, context)?),
+                                mangle: Some(to_mangle_options(&minify_config)),
                                 ..Default::default()
                             },
                             &ExtraOptions {
@@ -67,6 +77,74 @@ pub fn minify_js(ast: &mut JsAst, context: &Arc<Context>) -> Result<()> {
     })
 }
 
+fn to_compress_options(config: &MinifyConfig, context: &Arc<Context>) -> Result<CompressOptions> {
+    let default = CompressOptions::default();
+    let pure_funcs = config
+        .pure_funcs
+        .iter()
+        .map(|func| {
+            let fm = context
+                .meta
+                .script
+                .cm
+                .new_source_file(FileName::Anon.into(), func.clone());
+            parse_file_as_expr(
+                &fm,
+                Default::default(),
+                Default::default(),
+                None,
+                &mut vec![],
+            )
+            .map_err(|_| anyhow!("invalid `minify.pureFuncs` value: {}", func))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(CompressOptions {
+        drop_console: config.drop_console,
+        drop_debugger: config.drop_debugger,
+        pure_funcs,
+        keep_classnames: config.keep_classnames,
+        keep_fnames: config.keep_fnames,
+        passes: config.passes.unwrap_or(default.passes),
+        ecma: match config.ecma {
+            Some(ecma) => to_es_version(ecma)?,
+            None => default.ecma,
+        },
+        ..default
+    })
+}
+
+fn to_mangle_options(config: &MinifyConfig) -> MangleOptions {
+    MangleOptions {
+        keep_class_names: config.keep_classnames,
+        keep_fn_names: config.keep_fnames,
+        reserved: config
+            .reserved
+            .iter()
+            .map(|name| name.as_str().into())
+            .collect(),
+        ..Default::default()
+    }
+}
+
+fn to_es_version(ecma: usize) -> Result<EsVersion> {
+    let es_version = match ecma {
+        3 => EsVersion::Es3,
+        5 => EsVersion::Es5,
+        6 | 2015 => EsVersion::Es2015,
+        7 | 2016 => EsVersion::Es2016,
+        8 | 2017 => EsVersion::Es2017,
+        9 | 2018 => EsVersion::Es2018,
+        10 | 2019 => EsVersion::Es2019,
+        11 | 2020 => EsVersion::Es2020,
+        12 | 2021 => EsVersion::Es2021,
+        13 | 2022 => EsVersion::Es2022,
+        // the editions or years after 2022
+        14..=99 | 2023.. => EsVersion::EsNext,
+        _ => return Err(anyhow!("invalid `minify.ecma` value: {}", ecma)),
+    };
+    Ok(es_version)
+}
+
 pub fn minify_css(stylesheet: &mut Stylesheet, context: &Arc<Context>) -> Result<()> {
     crate::mako_profile_function!();
     GLOBALS.set(&context.meta.css.globals, || {
@@ -80,3 +158,131 @@ pub fn minify_css(stylesheet: &mut Stylesheet, context: &Arc<Context>) -> Result
         })
     })
 }
+
+// the legal comments like `/*! ... */`, `/* @license ... */` in the source files, which are
+// dropped by the minifier, indexed by the start position of the source files
+pub fn collect_legal_comments(context: &Arc<Context>) -> HashMap<BytePos, Vec<String>> {
+    let comments_lock = context.meta.script.origin_comments.read().unwrap();
+    let comments = comments_lock.get_swc_comments();
+    let mut legal_comments = comments
+        .leading
+        .iter()
+        .chain(comments.trailing.iter())
+        .flat_map(|entry| {
+            entry
+                .value()
+                .iter()
+                .filter(|comment| is_legal_comment(comment))
+                .cloned()
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    legal_comments.sort_by_key(|comment| comment.span.lo);
+
+    let cm = &context.meta.script.cm;
+    let mut result: HashMap<BytePos, Vec<String>> = HashMap::new();
+    for comment in legal_comments {
+        let file = cm.lookup_source_file(comment.span.lo);
+        result
+            .entry(file.start_pos)
+            .or_default()
+            .push(match comment.kind {
+                CommentKind::Block => format!("/*{}*/", comment.text),
+                CommentKind::Line => format!("//{}", comment.text),
+            });
+    }
+    result
+}
+
+// the legal comments of the source files of the modules in the chunk
+pub fn chunk_legal_comments(
+    chunk_id: &str,
+    legal_comments: &HashMap<BytePos, Vec<String>>,
+    context: &Arc<Context>,
+) -> Vec<String> {
+    let chunk_graph = context.chunk_graph.read().unwrap();
+    let module_graph = context.module_graph.read().unwrap();
+    let Some(chunk) = chunk_graph.chunk(&chunk_id.to_string().into()) else {
+        return vec![];
+    };
+
+    let cm = &context.meta.script.cm;
+    let mut result = IndexSet::new();
+    for module_id in chunk.get_modules() {
+        let Some(info) = module_graph
+            .get_module(module_id)
+            .and_then(|module| module.info.as_ref())
+        else {
+            continue;
+        };
+        let ModuleAst::Script(ast) = &info.ast else {
+            continue;
+        };
+        // the concatenated modules are removed from the chunk, their items are in the root module
+        let files = std::iter::once(ast.ast.span)
+            .chain(ast.ast.body.iter().map(|item| item.span()))
+            .filter(|span| !span.is_dummy())
+            .map(|span| cm.lookup_source_file(span.lo).start_pos)
+            .collect::<IndexSet<_>>();
+        for file in files {
+            if let Some(comments) = legal_comments.get(&file) {
+                result.extend(comments.iter().cloned());
+            }
+        }
+    }
+    result.into_iter().collect()
+}
+
+fn is_legal_comment(comment: &Comment) -> bool {
+    comment.text.starts_with('!')
+        || comment.text.contains("@license")
+        || comment.text.contains("@preserve")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use swc_core::ecma::ast::EsVersion;
+
+    use super::to_es_version;
+    use crate::compiler::Compiler;
+    use crate::config::Config;
+    use crate::generate::chunk_pot::util::file_content_hash;
+
+    #[test]
+    fn test_to_es_version() {
+        assert_eq!(to_es_version(5).unwrap(), EsVersion::Es5);
+        assert_eq!(to_es_version(2022).unwrap(), EsVersion::Es2022);
+        assert_eq!(to_es_version(2024).unwrap(), EsVersion::EsNext);
+        assert_eq!(to_es_version(15).unwrap(), EsVersion::EsNext);
+        assert!(to_es_version(4).is_err());
+    }
+
+    #[test]
+    fn test_legal_comments_before_hash() {
+        let root = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test/build/legal-comments");
+        let config = Config::new(&root, None, None).unwrap();
+        let compiler = Compiler::new(config, root.clone(), Default::default(), None).unwrap();
+        compiler.compile().unwrap();
+
+        let dist = root.join("dist");
+        let license = fs::read_to_string(dist.join("index.js.LICENSE.txt")).unwrap();
+        assert!(license.contains("/*! index v1.0.0 | MIT */"));
+        assert!(license.contains("@license store v1.0.0 | MIT"));
+        let chunk = fs::read_dir(&dist)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+            .find(|name| name.starts_with("index.") && name.ends_with(".js"))
+            .unwrap();
+        let content = fs::read(dist.join(&chunk)).unwrap();
+        assert!(String::from_utf8_lossy(&content)
+            .ends_with("/*! For license information please see index.js.LICENSE.txt */"));
+        assert_eq!(
+            chunk,
+            format!("index.{}.js", file_content_hash(&content)),
+            "the hash is computed with the legal comments"
+        );
+    }
+}
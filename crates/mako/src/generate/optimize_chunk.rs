@@ -1,6 +1,8 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::PathBuf;
 use std::string::String;
 
+use anyhow::Result;
 use hashlink::LinkedHashSet;
 use indexmap::{IndexMap, IndexSet};
 use tracing::debug;
@@ -13,8 +15,9 @@ use crate::config::{
 };
 use crate::generate::chunk::{Chunk, ChunkId, ChunkType};
 use crate::generate::group_chunk::GroupUpdateResult;
-use crate::module::{Module, ModuleId, ModuleInfo};
+use crate::module::{relative_module_id, Module, ModuleId, ModuleInfo};
 use crate::resolve::{ResolvedResource, ResolverResource};
+use crate::utils::records::Records;
 use crate::utils::{create_cached_regex, url_safe_base64_encode};
 
 pub struct OptimizeChunksInfo {
@@ -23,14 +26,13 @@ pub struct OptimizeChunksInfo {
 }
 
 impl Compiler {
-    pub fn optimize_chunk(&self) {
+    pub fn optimize_chunk(&self) -> Result<()> {
         crate::mako_profile_function!();
         debug!("optimize chunk");
         if let Some(mut optimize_options) = self.get_optimize_chunk_options() {
             self.context
                 .plugin_driver
-                .after_optimize_chunk_options(&mut optimize_options)
-                .unwrap();
+                .after_optimize_chunk_options(&mut optimize_options)?;
             debug!("optimize options: {:?}", optimize_options);
             // stage: prepare
             let mut optimize_chunks_infos = optimize_options
@@ -50,26 +52,52 @@ impl Compiler {
             // stage: name_suffix
             self.optimize_name_suffix(&mut optimize_chunks_infos);
 
+            // stage: recorded groups
+            let records_path = Records::path(&self.context);
+            let records = records_path.as_ref().map(Records::load).transpose()?;
+            if let Some(records) = &records {
+                self.keep_recorded_chunk_groups(
+                    &mut optimize_chunks_infos,
+                    &optimize_options.groups,
+                    &records.split_chunk_groups,
+                );
+            }
+            let split_chunk_groups = self.split_chunk_groups(&optimize_chunks_infos);
+
             // stage: size
-            self.optimize_chunk_size(&mut optimize_chunks_infos);
+            self.optimize_chunk_size(
+                &mut optimize_chunks_infos,
+                records.as_ref().map(|records| &records.split_chunks),
+            );
 
             // stage: apply
             self.apply_optimize_infos(&optimize_chunks_infos);
 
+            // stage: records
+            if let (Some(path), Some(records)) = (records_path, records) {
+                self.save_split_chunks_records(
+                    &path,
+                    records,
+                    &optimize_chunks_infos,
+                    split_chunk_groups,
+                )?;
+            }
+
             // save optimize infos for hot update
             if let Ok(mut optimize_info) = self.context.optimize_infos.lock() {
                 *optimize_info = Some(optimize_chunks_infos);
             }
         }
+        Ok(())
     }
 
-    pub fn optimize_hot_update_chunk(&self, group_result: &GroupUpdateResult) {
+    pub fn optimize_hot_update_chunk(&self, group_result: &GroupUpdateResult) -> Result<()> {
         crate::mako_profile_function!();
         debug!("optimize hot update chunk");
 
         // skip if code splitting disabled or group result is invalid
         if self.context.config.code_splitting.is_none() || group_result.is_none() {
-            return;
+            return Ok(());
         }
 
         let (group_new_chunks, group_modules_in_chunk) = group_result.as_ref().unwrap();
@@ -77,7 +105,7 @@ impl Compiler {
         if group_new_chunks.is_empty() && group_modules_in_chunk.is_empty() {
             // full re-optimize if code splitting enabled and received empty group result
             // ref: https://github.com/umijs/mako/blob/d110cbd74e95307c437471185d734e10533b3494/crates/mako/src/group_chunk.rs#L182
-            self.optimize_chunk();
+            self.optimize_chunk()?;
         } else if let Some(optimize_infos) = self.context.optimize_infos.lock().unwrap().as_ref() {
             // only optimize if code splitting enabled and there has valid group update result
             let chunk_graph = self.context.chunk_graph.write().unwrap();
@@ -115,6 +143,7 @@ impl Compiler {
             // stage: apply
             self.apply_hot_update_optimize_infos(&optimize_infos);
         }
+        Ok(())
     }
 
     fn module_to_optimize_infos<'a>(
@@ -206,7 +235,11 @@ impl Compiler {
         }
     }
 
-    fn optimize_chunk_size(&self, optimize_chunks_infos: &mut Vec<OptimizeChunksInfo>) {
+    fn optimize_chunk_size(
+        &self,
+        optimize_chunks_infos: &mut Vec<OptimizeChunksInfo>,
+        split_chunks_records: Option<&BTreeMap<String, Vec<String>>>,
+    ) {
         let chunk_size_map = optimize_chunks_infos
             .iter()
             .map(|info| {
@@ -267,6 +300,56 @@ impl Compiler {
                 },
             );
 
+            let mut split_infos = vec![];
+            let mut used_names = HashSet::new();
+
+            // keep the packages in the split chunks of the records, unless the chunk size of the
+            // recorded chunk is greater than max_size now
+            if chunk_size > info.group_options.max_size
+                && let Some(split_chunks_records) = split_chunks_records
+            {
+                for (name, modules) in
+                    recorded_split_chunks(split_chunks_records, &info.group_options.name)
+                {
+                    let packages = package_size_map
+                        .iter()
+                        .filter(|(_, (_, package_modules))| {
+                            package_modules.keys().any(|module_id| {
+                                modules.contains(&relative_module_id(&module_id.id, &self.context))
+                            })
+                        })
+                        .map(|(package, (size, _))| (package.clone(), *size))
+                        .collect::<Vec<_>>();
+                    let size = packages.iter().map(|(_, size)| size).sum::<usize>();
+                    if packages.is_empty()
+                        || packages.len() > 1 && size > info.group_options.max_size
+                    {
+                        continue;
+                    }
+
+                    let mut new_module_to_chunks = IndexMap::new();
+                    for (package, _) in packages {
+                        let (_, modules) = package_size_map.shift_remove(&package).unwrap();
+                        new_module_to_chunks.extend(modules);
+                    }
+                    chunk_size -= size;
+                    used_names.insert(name.clone());
+                    let mut new_chunk_group_options = info.group_options.clone();
+                    new_chunk_group_options.name = name;
+                    split_infos.push(OptimizeChunksInfo {
+                        group_options: new_chunk_group_options,
+                        module_to_chunks: new_module_to_chunks,
+                    });
+                }
+            }
+            let mut split_chunk_name = || loop {
+                let name = format!("{}_{}", info.group_options.name, split_chunk_count);
+                split_chunk_count += 1;
+                if !used_names.contains(&name) {
+                    break name;
+                }
+            };
+
             // split new chunks until chunk size is less than max_size and there has more than 1 package can be split
             while package_size_map.len() > 1 && chunk_size > info.group_options.max_size {
                 let mut new_chunk_size = 0;
@@ -293,27 +376,35 @@ impl Compiler {
 
                 // clone group options for new chunk
                 let mut new_chunk_group_options = info.group_options.clone();
-                new_chunk_group_options.name =
-                    format!("{}_{}", info.group_options.name, split_chunk_count);
+                new_chunk_group_options.name = split_chunk_name();
 
-                // update original chunk size and split chunk count
+                // update original chunk size
                 chunk_size -= new_chunk_size;
-                split_chunk_count += 1;
 
-                // move modules to new chunk
-                info.module_to_chunks
-                    .retain(|module_id, _| !new_module_to_chunks.contains_key(module_id));
-                extra_optimize_infos.push(OptimizeChunksInfo {
+                split_infos.push(OptimizeChunksInfo {
                     group_options: new_chunk_group_options,
                     module_to_chunks: new_module_to_chunks,
                 });
             }
 
-            // rename original chunk if it has been split
-            if split_chunk_count > 0 {
-                info.group_options.name =
-                    format!("{}_{}", info.group_options.name, split_chunk_count);
+            // move modules to new chunks
+            for split_info in &split_infos {
+                info.module_to_chunks
+                    .retain(|module_id, _| !split_info.module_to_chunks.contains_key(module_id));
             }
+
+            // rename original chunk if it has been split, or replace it with the last split
+            // chunk if all the modules are kept in the recorded chunks
+            if !split_infos.is_empty() {
+                if info.module_to_chunks.is_empty() {
+                    let last = split_infos.pop().unwrap();
+                    info.group_options = last.group_options;
+                    info.module_to_chunks = last.module_to_chunks;
+                } else {
+                    info.group_options.name = split_chunk_name();
+                }
+            }
+            extra_optimize_infos.extend(split_infos);
         }
 
         // add extra optimize infos
@@ -423,6 +514,88 @@ impl Compiler {
         optimize_chunks_infos.extend(extra_optimize_infos);
     }
 
+    // keep the modules in the groups of the records, e.g. a module shared by the chunks `a` and
+    // `b` stays in `shared_{hash of a, b}` after `c` imports it too, as long as both groups are
+    // derived from the same group of the options
+    fn keep_recorded_chunk_groups(
+        &self,
+        optimize_chunks_infos: &mut Vec<OptimizeChunksInfo>,
+        groups: &[ChunkGroup],
+        split_chunk_groups_records: &BTreeMap<String, String>,
+    ) {
+        let mut moves = vec![];
+        for (index, info) in optimize_chunks_infos.iter().enumerate() {
+            let Some(base) = chunk_group_base(groups, &info.group_options.name) else {
+                continue;
+            };
+            for module_id in info.module_to_chunks.keys() {
+                let Some(recorded) = split_chunk_groups_records
+                    .get(&relative_module_id(&module_id.id, &self.context))
+                else {
+                    continue;
+                };
+                if *recorded != info.group_options.name
+                    && chunk_group_base(groups, recorded) == Some(base)
+                {
+                    moves.push((index, module_id.clone(), recorded.clone()));
+                }
+            }
+        }
+
+        let mut emptied = HashSet::new();
+        for (index, module_id, recorded) in moves {
+            let chunk_ids = optimize_chunks_infos[index]
+                .module_to_chunks
+                .shift_remove(&module_id)
+                .unwrap();
+            if optimize_chunks_infos[index].module_to_chunks.is_empty() {
+                emptied.insert(optimize_chunks_infos[index].group_options.name.clone());
+            }
+            let target = match optimize_chunks_infos
+                .iter()
+                .position(|info| info.group_options.name == recorded)
+            {
+                Some(target) => target,
+                None => {
+                    let mut group_options = optimize_chunks_infos[index].group_options.clone();
+                    group_options.name = recorded.clone();
+                    optimize_chunks_infos.push(OptimizeChunksInfo {
+                        group_options,
+                        module_to_chunks: IndexMap::new(),
+                    });
+                    optimize_chunks_infos.len() - 1
+                }
+            };
+            emptied.remove(&recorded);
+            optimize_chunks_infos[target]
+                .module_to_chunks
+                .insert(module_id, chunk_ids);
+        }
+
+        // the groups which only have the modules kept in the recorded groups are gone
+        optimize_chunks_infos.retain(|info| {
+            !(info.module_to_chunks.is_empty() && emptied.contains(&info.group_options.name))
+        });
+    }
+
+    // relative path of the module => name of its group before being split by max_size
+    fn split_chunk_groups(
+        &self,
+        optimize_chunks_infos: &[OptimizeChunksInfo],
+    ) -> BTreeMap<String, String> {
+        optimize_chunks_infos
+            .iter()
+            .flat_map(|info| {
+                info.module_to_chunks.keys().map(|module_id| {
+                    (
+                        relative_module_id(&module_id.id, &self.context),
+                        info.group_options.name.clone(),
+                    )
+                })
+            })
+            .collect()
+    }
+
     fn apply_optimize_infos(&self, optimize_chunks_infos: &Vec<OptimizeChunksInfo>) {
         let mut edges_map: HashMap<ModuleId, IndexSet<ModuleId>> = HashMap::new();
         let mut chunk_graph = self.context.chunk_graph.write().unwrap();
@@ -516,6 +689,40 @@ impl Compiler {
         }
     }
 
+    fn save_split_chunks_records(
+        &self,
+        path: &PathBuf,
+        previous: Records,
+        optimize_chunks_infos: &[OptimizeChunksInfo],
+        split_chunk_groups: BTreeMap<String, String>,
+    ) -> Result<()> {
+        let split_chunks = optimize_chunks_infos
+            .iter()
+            .filter(|info| !info.module_to_chunks.is_empty())
+            .map(|info| {
+                let mut modules = info
+                    .module_to_chunks
+                    .keys()
+                    .map(|module_id| relative_module_id(&module_id.id, &self.context))
+                    .collect::<Vec<_>>();
+                modules.sort();
+                (info.group_options.name.clone(), modules)
+            })
+            .collect::<BTreeMap<_, _>>();
+        let records = Records {
+            split_chunks,
+            split_chunk_groups,
+            ..Records::load(path)?
+        };
+        // nothing to compare with in the first build
+        if !previous.split_chunks.is_empty() {
+            self.context
+                .stats_info
+                .set_split_chunks_diff(records.diff_split_chunks(&previous));
+        }
+        records.save(path)
+    }
+
     /* the following is util methods */
 
     fn check_chunk_type_allow(&self, allow_chunks: &AllowChunks, chunk_type: &ChunkType) -> bool {
@@ -659,6 +866,48 @@ fn code_splitting_strategy_granular(
     }
 }
 
+// the chunks split from the group by max_size in the records, e.g. `vendors_0`, `vendors_1`
+fn recorded_split_chunks(
+    split_chunks_records: &BTreeMap<String, Vec<String>>,
+    group_name: &str,
+) -> Vec<(String, HashSet<String>)> {
+    let prefix = format!("{}_", group_name);
+    let mut chunks = split_chunks_records
+        .iter()
+        .filter_map(|(name, modules)| {
+            let index = name.strip_prefix(&prefix)?;
+            if index.is_empty() || !index.chars().all(|c| c.is_ascii_digit()) {
+                return None;
+            }
+            Some((
+                index.parse::<usize>().ok()?,
+                name.clone(),
+                modules.iter().cloned().collect::<HashSet<_>>(),
+            ))
+        })
+        .collect::<Vec<_>>();
+    chunks.sort_by_key(|(index, _, _)| *index);
+    chunks
+        .into_iter()
+        .map(|(_, name, modules)| (name, modules))
+        .collect()
+}
+
+// the group of the options which the split chunk group is derived from by the name suffix,
+// e.g. `shared` of `shared_{hash}`, the longest name wins
+fn chunk_group_base<'a>(groups: &'a [ChunkGroup], name: &str) -> Option<&'a str> {
+    groups
+        .iter()
+        .map(|group| group.name.as_str())
+        .filter(|base| {
+            name == *base
+                || name
+                    .strip_prefix(base)
+                    .is_some_and(|suffix| suffix.starts_with('_'))
+        })
+        .max_by_key(|base| base.len())
+}
+
 fn md5_chunk_ids(chunk_ids: &[ChunkId]) -> String {
     let mut context = md5::Context::new();
     chunk_ids.iter().for_each(|cd| {
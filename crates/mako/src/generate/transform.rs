@@ -21,6 +21,8 @@ use tracing::debug;
 
 use crate::ast::js_ast::JsAst;
 use crate::compiler::{Compiler, Context};
+use crate::config::Platform;
+use crate::features::node::DirnameAssetsOutputDir;
 use crate::module::{generate_module_id, Dependency, ModuleAst, ModuleId, ModuleType, ResolveType};
 use crate::share::helpers::SWC_HELPERS;
 use crate::utils::thread_pool;
@@ -104,10 +106,14 @@ pub fn transform_modules_in_thread(
                         }
                     }
                     ResolveType::DynamicImport(import_options) => {
-                        let chunk_id = Some(match import_options.get_chunk_name() {
-                            Some(chunk_name) => generate_module_id(chunk_name, &context),
-                            None => id.generate(&context),
-                        });
+                        let chunk_id = if import_options.is_eager() {
+                            None
+                        } else {
+                            Some(match import_options.get_chunk_name() {
+                                Some(chunk_name) => generate_module_id(chunk_name, &context),
+                                None => id.generate(&context),
+                            })
+                        };
 
                         ResolvedReplaceInfo {
                             chunk_id,
@@ -145,6 +151,20 @@ pub fn transform_modules_in_thread(
             if let ModuleAst::Script(mut ast) = ast {
                 let wrap_async = info.is_async && info.external.is_none();
 
+                if matches!(context.config.platform, Platform::Node)
+                    && let Some(chunk) = context
+                        .chunk_graph
+                        .read()
+                        .unwrap()
+                        .get_chunk_for_module(&module_id)
+                    && let Some(mut dirname_assets) = DirnameAssetsOutputDir::new(
+                        &DirnameAssetsOutputDir::chunk_output_dir(chunk, &context),
+                        &context,
+                    )
+                {
+                    ast.ast.visit_mut_with(&mut dirname_assets);
+                }
+
                 let ret = transform_js_generate(TransformJsParam {
                     module_id: &module.id,
                     context: &context,
@@ -133,17 +133,53 @@ impl From<&NamedExport> for NamedExportType {
     }
 }
 
+/// `webpackMode` magic comment of dynamic imports
+#[derive(Eq, Hash, PartialEq, Serialize, Debug, Clone, Default)]
+pub enum ImportMode {
+    /// a separated chunk for each dynamic import
+    #[default]
+    Lazy,
+    /// one chunk for all the modules of a context module, i.e. import(`./locales/${lang}`)
+    LazyOnce,
+    /// no chunk is created, the module is bundled into the parent chunk
+    Eager,
+}
+
 #[derive(Eq, Hash, PartialEq, Serialize, Debug, Clone, Default)]
 pub struct ImportOptions {
     pub chunk_name: Option<String>,
     pub ignore: bool,
     pub _is_federation_expose: bool,
+    /// the worker entry is registered by `navigator.serviceWorker.register`
+    pub service_worker: bool,
+    /// `webpackPrefetch` and `webpackPreload` magic comments
+    pub prefetch: bool,
+    pub preload: bool,
+    pub mode: ImportMode,
+    /// `webpackExports` magic comment, the used exports of the imported module
+    pub exports: Option<Vec<String>>,
 }
 
 impl ImportOptions {
     pub fn get_chunk_name(&self) -> &Option<String> {
         &self.chunk_name
     }
+
+    pub fn is_eager(&self) -> bool {
+        self.mode == ImportMode::Eager
+    }
+
+    // only keep the options which affect the chunk, or else the same module imported with
+    // different hints, modes or exports will be grouped into two chunks with the same id
+    pub fn for_chunk_group(&self) -> Self {
+        Self {
+            chunk_name: self.chunk_name.clone(),
+            ignore: self.ignore,
+            _is_federation_expose: self._is_federation_expose,
+            service_worker: self.service_worker,
+            ..Default::default()
+        }
+    }
 }
 
 #[derive(Eq, Hash, PartialEq, Serialize, Debug, Clone)]
@@ -234,12 +270,8 @@ pub fn md5_hash(source_str: &str, lens: usize) -> String {
 pub fn generate_module_id(origin_module_id: &str, context: &Arc<Context>) -> String {
     match context.config.module_id_strategy {
         ModuleIdStrategy::Hashed => md5_hash(origin_module_id, 8),
-        ModuleIdStrategy::Named => {
-            // readable ids for debugging usage
-            let absolute_path = PathBuf::from(origin_module_id);
-            let relative_path = diff_paths(&absolute_path, &context.root).unwrap_or(absolute_path);
-            win_path(relative_path.to_str().unwrap())
-        }
+        // readable ids for debugging usage
+        ModuleIdStrategy::Named => relative_module_id(origin_module_id, context),
         ModuleIdStrategy::Numeric => {
             let numeric_ids_map = context.numeric_ids_map.read().unwrap();
             if let Some(numeric_id) = numeric_ids_map.get(origin_module_id) {
@@ -248,9 +280,24 @@ pub fn generate_module_id(origin_module_id: &str, context: &Arc<Context>) -> Str
                 md5_hash(origin_module_id, 8)
             }
         }
+        ModuleIdStrategy::Deterministic => {
+            let deterministic_ids_map = context.deterministic_ids_map.read().unwrap();
+            if let Some(id) = deterministic_ids_map.get(origin_module_id) {
+                id.clone()
+            } else {
+                // the relative path, so that it's the same on different machines
+                md5_hash(&relative_module_id(origin_module_id, context), 8)
+            }
+        }
     }
 }
 
+pub fn relative_module_id(origin_module_id: &str, context: &Arc<Context>) -> String {
+    let absolute_path = PathBuf::from(origin_module_id);
+    let relative_path = diff_paths(&absolute_path, &context.root).unwrap_or(absolute_path);
+    win_path(relative_path.to_str().unwrap())
+}
+
 pub fn relative_to_root(module_path: &String, root: &PathBuf) -> String {
     let absolute_path = PathBuf::from(module_path);
     let relative_path = diff_paths(&absolute_path, root).unwrap_or(absolute_path);
@@ -126,6 +126,16 @@ pub trait Plugin: Any + Send + Sync {
         Ok(())
     }
 
+    // modify the generated chunk files before their names are referenced and they are emitted,
+    // the hash and the source map of the modified files should be kept in sync
+    fn transform_chunk_files(
+        &self,
+        _chunk_files: &mut [ChunkFile],
+        _context: &Arc<Context>,
+    ) -> Result<()> {
+        Ok(())
+    }
+
     fn after_generate_chunk_files(
         &self,
         _chunk_files: &[ChunkFile],
@@ -341,6 +351,18 @@ impl PluginDriver {
         Ok(())
     }
 
+    pub(crate) fn transform_chunk_files(
+        &self,
+        chunk_files: &mut [ChunkFile],
+        context: &Arc<Context>,
+    ) -> Result<()> {
+        for plugin in &self.plugins {
+            plugin.transform_chunk_files(chunk_files, context)?;
+        }
+
+        Ok(())
+    }
+
     pub(crate) fn after_generate_chunk_files(
         &self,
         chunk_files: &[ChunkFile],
@@ -2,8 +2,11 @@ pub mod async_runtime;
 pub mod bundless_compiler;
 pub mod case_sensitive;
 pub mod central_ensure;
+pub mod compress;
 pub mod context_module;
 pub mod copy;
+pub mod critical_css;
+pub mod declaration;
 pub mod detect_circular_dependence;
 pub mod duplicate_package_checker;
 pub mod emotion;
@@ -13,12 +16,18 @@ pub mod ignore;
 pub mod import;
 pub mod imports_checker;
 pub mod invalid_webpack_syntax;
+pub mod lazy_compilation;
+pub mod mangle_props;
 pub mod manifest;
 pub mod minifish;
 pub mod module_federation;
 pub mod progress;
 pub mod require_context;
+pub mod resource_hints;
 pub mod runtime;
+pub mod service_worker;
+pub mod single_file;
 pub mod ssu;
 pub mod tree_shaking;
+pub mod unused_css;
 pub mod wasm_runtime;
@@ -47,38 +47,12 @@ impl BundlessCompiler {
                 let module_graph = context.module_graph.read().unwrap();
                 let deps = module_graph.get_dependencies(module_id);
 
-                let module_dist_path = to_dist_path(&module_id.id, context)
-                    .parent()
-                    .unwrap()
-                    .to_path_buf();
-
                 let resolved_deps = deps
                     .clone()
                     .into_iter()
                     // .map(|(id, dep)| (dep.source.clone(), id.generate(context)))
                     .map(|(id, dep)| {
-                        let dep_dist_path = to_dist_path(&id.id, context);
-
-                        let rel_path =
-                            diff_paths(&dep_dist_path, &module_dist_path).ok_or_else(|| {
-                                anyhow!(
-                                    "failed to get relative path from {:?} to {:?}",
-                                    dep_dist_path,
-                                    module_dist_path
-                                )
-                            })?;
-
-                        let rel_path = normalize_extension(rel_path);
-
-                        let replacement: String = {
-                            let mut to_path = rel_path.to_str().unwrap().to_string();
-                            if to_path.starts_with("./") || to_path.starts_with("../") {
-                                to_path
-                            } else {
-                                to_path.insert_str(0, "./");
-                                to_path
-                            }
-                        };
+                        let replacement = to_dist_source(&module_id.id, &id.id, context)?;
 
                         Ok((
                             dep.source.clone(),
@@ -284,6 +258,28 @@ fn transform_js_generate(
         .unwrap();
 }
 
+// the relative source to import the dist file of `to` from the dist file of `from`
+pub(crate) fn to_dist_source(from: &str, to: &str, context: &Arc<Context>) -> Result<String> {
+    let module_dist_path = to_dist_path(from, context).parent().unwrap().to_path_buf();
+    let dep_dist_path = to_dist_path(to, context);
+
+    let rel_path = diff_paths(&dep_dist_path, &module_dist_path).ok_or_else(|| {
+        anyhow!(
+            "failed to get relative path from {:?} to {:?}",
+            dep_dist_path,
+            module_dist_path
+        )
+    })?;
+
+    let rel_path = normalize_extension(rel_path);
+
+    let mut to_path = rel_path.to_str().unwrap().to_string();
+    if !to_path.starts_with("./") && !to_path.starts_with("../") {
+        to_path.insert_str(0, "./");
+    }
+    Ok(to_path)
+}
+
 pub fn to_dist_path<P: AsRef<str>>(abs_path: P, context: &Arc<Context>) -> PathBuf {
     let str = abs_path.as_ref();
 
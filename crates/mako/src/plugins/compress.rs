@@ -0,0 +1,219 @@
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::Result;
+use brotli::enc::BrotliEncoderParams;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use rayon::prelude::*;
+
+use crate::ast::file::win_path;
+use crate::compiler::Context;
+use crate::config::{CompressAlgorithm, CompressConfig};
+use crate::plugin::Plugin;
+use crate::plugins::copy::CopyPlugin;
+use crate::stats::StatsJsonMap;
+
+const EXTENSIONS: [&str; 8] = ["js", "mjs", "cjs", "css", "html", "htm", "svg", "json"];
+
+/**
+ * Write the compressed siblings of the js, css, html, svg and json files emitted by the build
+ * and copied from `copy`, e.g. `index.js.gz` and `index.js.br`, so that they can be served by
+ * the web servers directly. It's registered after the other plugins which modify the files in
+ * `build_success`, e.g. CriticalCssPlugin and SingleFilePlugin, and before ManifestPlugin so that
+ * the compressed files are in the manifest.
+ */
+pub struct CompressPlugin {
+    config: CompressConfig,
+}
+
+impl CompressPlugin {
+    pub fn new(config: CompressConfig) -> Self {
+        Self { config }
+    }
+
+    // (path of the compressed file, size), `None` if the ratio is not good enough
+    fn compress_file(
+        &self,
+        path: &Path,
+        algorithm: CompressAlgorithm,
+    ) -> Result<Option<(PathBuf, u64)>> {
+        let content = fs::read(path)?;
+        let to = PathBuf::from(format!(
+            "{}.{}",
+            path.to_string_lossy(),
+            algorithm.extension()
+        ));
+        let compressed = match algorithm {
+            CompressAlgorithm::Gzip => {
+                let mut encoder = GzEncoder::new(vec![], Compression::best());
+                encoder.write_all(&content)?;
+                encoder.finish()?
+            }
+            CompressAlgorithm::Brotli => brotli_compress(&content)?,
+        };
+        if compressed.len() as f64 >= content.len() as f64 * self.config.min_ratio {
+            // the stale one of the previous build
+            if to.exists() {
+                fs::remove_file(&to)?;
+            }
+            return Ok(None);
+        }
+        fs::write(&to, &compressed)?;
+        Ok(Some((to, compressed.len() as u64)))
+    }
+}
+
+impl Plugin for CompressPlugin {
+    fn name(&self) -> &str {
+        "compress"
+    }
+
+    fn build_success(&self, _stats: &StatsJsonMap, context: &Arc<Context>) -> Result<()> {
+        let output_path = &context.config.output.path;
+        if context.args.watch || !output_path.exists() {
+            return Ok(());
+        }
+        // the stale files of the previous builds are not compressed
+        let assets = context.stats_info.get_assets();
+        let mut files = assets
+            .iter()
+            .map(|asset| output_path.join(&asset.path))
+            .chain(CopyPlugin::copied_files(context)?)
+            .collect::<Vec<_>>();
+        files.sort();
+        files.dedup();
+        let files = files
+            .into_iter()
+            .filter(|path| {
+                path.extension()
+                    .is_some_and(|ext| EXTENSIONS.contains(&ext.to_string_lossy().as_ref()))
+                    && fs::metadata(path).is_ok_and(|m| m.len() > self.config.threshold)
+            })
+            .flat_map(|path| {
+                self.config
+                    .algorithms
+                    .iter()
+                    .map(move |algorithm| (path.clone(), *algorithm))
+            })
+            .collect::<Vec<_>>();
+        let mut compressed = files
+            .par_iter()
+            .map(|(path, algorithm)| {
+                Ok(self
+                    .compress_file(path, *algorithm)?
+                    .map(|(to, size)| (path.clone(), to, size)))
+            })
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+        compressed.sort_by(|a, b| a.1.cmp(&b.1));
+
+        // the compressed files of chunks belong to the chunks
+        for (path, to, size) in compressed {
+            let hashname = win_path(&to.strip_prefix(output_path)?.to_string_lossy());
+            let extension = to.extension().unwrap().to_string_lossy().to_string();
+            let (name, chunk_id) = assets
+                .iter()
+                .find(|asset| Path::new(&asset.path) == path)
+                .map(|asset| {
+                    (
+                        format!("{}.{}", asset.name, extension),
+                        asset.chunk_id.clone(),
+                    )
+                })
+                .unwrap_or_else(|| (hashname.clone(), "".to_string()));
+            context.stats_info.add_assets(
+                size,
+                name,
+                chunk_id,
+                to.to_string_lossy().to_string(),
+                hashname,
+            );
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn brotli_compress(content: &[u8]) -> Result<Vec<u8>> {
+    let mut compressed = vec![];
+    brotli::BrotliCompress(
+        &mut &content[..],
+        &mut compressed,
+        &BrotliEncoderParams::default(),
+    )?;
+    Ok(compressed)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use crate::compiler::Compiler;
+    use crate::config::{CompressAlgorithm, CompressConfig, Config, ManifestConfig};
+
+    #[test]
+    fn test_compress() {
+        let root = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test/build/compress");
+        let mut config = Config::new(&root, None, None).unwrap();
+        config.output.compress = Some(CompressConfig {
+            algorithms: vec![CompressAlgorithm::Gzip, CompressAlgorithm::Brotli],
+            threshold: 1024,
+            min_ratio: 0.8,
+        });
+        config.manifest = Some(ManifestConfig {
+            file_name: "asset-manifest.json".to_string(),
+            base_path: "".to_string(),
+        });
+        let compiler = Compiler::new(config, root.clone(), Default::default(), None).unwrap();
+        compiler.compile().unwrap();
+
+        let dist = root.join("dist");
+        assert!(dist.join("index.js.gz").exists());
+        let mut decompressed = vec![];
+        brotli::BrotliDecompress(
+            &mut fs::read(dist.join("index.js.br")).unwrap().as_slice(),
+            &mut decompressed,
+        )
+        .unwrap();
+        assert_eq!(decompressed, fs::read(dist.join("index.js")).unwrap());
+        assert!(
+            !dist.join("index.js.map.gz").exists(),
+            "source maps are not compressed"
+        );
+        assert!(
+            !dist.join("index.css.gz").exists(),
+            "files under the threshold are not compressed"
+        );
+        assert!(
+            dist.join("index.html.gz").exists() && dist.join("index.html.br").exists(),
+            "the copied public files are compressed"
+        );
+
+        let assets = compiler.context.stats_info.get_assets();
+        let gzip = assets
+            .iter()
+            .find(|asset| asset.hashname == "index.js.gz")
+            .unwrap();
+        assert_eq!(gzip.chunk_id, "index.js");
+        assert_eq!(
+            gzip.size,
+            fs::metadata(dist.join("index.js.gz")).unwrap().len()
+        );
+
+        let manifest: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(dist.join("asset-manifest.json")).unwrap())
+                .unwrap();
+        assert_eq!(manifest["index.js"], "index.js");
+        assert_eq!(manifest["index.js.gz"]["file"], "index.js.gz");
+        assert_eq!(manifest["index.js.gz"]["size"], gzip.size);
+        assert_eq!(
+            manifest["index.html.br"]["size"],
+            fs::metadata(dist.join("index.html.br")).unwrap().len()
+        );
+    }
+}
@@ -5,20 +5,23 @@ use std::sync::Arc;
 
 use anyhow::Result;
 use glob::glob;
-use swc_core::common::{Mark, DUMMY_SP};
+use swc_core::common::{Mark, Spanned, DUMMY_SP};
 use swc_core::ecma::ast::{
     BinExpr, BinaryOp, CallExpr, Expr, ExprOrSpread, Lit, ParenExpr, TplElement,
 };
 use swc_core::ecma::utils::{member_expr, quote_ident, quote_str, ExprExt, ExprFactory};
 use swc_core::ecma::visit::{VisitMut, VisitMutWith};
+use swc_node_comments::SwcComments;
 
 use crate::ast::file::{win_path, Content, JsContent};
 use crate::ast::utils::{is_commonjs_require, is_dynamic_import};
 use crate::ast::DUMMY_CTXT;
 use crate::build::load::JS_EXTENSIONS;
 use crate::compiler::Context;
+use crate::module::ImportMode;
 use crate::plugin::{Plugin, PluginLoadParam};
 use crate::resolve::get_module_extensions;
+use crate::visitors::dep_analyzer::analyze_import_options;
 
 pub struct ContextModulePlugin {}
 
@@ -40,10 +43,13 @@ impl Plugin for ContextModulePlugin {
             let paths = glob(glob_pattern.to_str().unwrap())?;
 
             let mut key_values = BTreeMap::new();
-            let load_by = if param.file.has_param("async") {
-                "import"
+            let load_by = if param.file.has_param("eager") {
+                // lazy-once and eager context imports bundle all the matched modules together
+                "import(/* webpackMode: \"eager\" */ "
+            } else if param.file.has_param("async") {
+                "import("
             } else {
-                "require"
+                "require("
             };
 
             for path in paths {
@@ -97,7 +103,7 @@ impl Plugin for ContextModulePlugin {
 
                 for key in keys {
                     let map_entry =
-                        format!("'{}': () => {}('{}')", key, load_by, path.to_string_lossy());
+                        format!("'{}': () => {}'{}')", key, load_by, path.to_string_lossy());
 
                     key_values.insert(key, map_entry);
                 }
@@ -136,12 +142,15 @@ module.exports = (id) => {{
 
 pub struct ContextModuleVisitor {
     pub unresolved_mark: Mark,
+    // used to read `webpackMode` of dynamic imports, None if magic comments are disabled
+    pub comments: Option<SwcComments>,
 }
 
 impl VisitMut for ContextModuleVisitor {
     fn visit_mut_call_expr(&mut self, expr: &mut CallExpr) {
         let commonjs_require = is_commonjs_require(expr, &self.unresolved_mark);
         let dynamic_import = is_dynamic_import(expr);
+        let first_arg_span = expr.args.first().map_or(DUMMY_SP, |arg| arg.span());
         let first_non_str_arg = match expr.args.first_mut() {
             Some(ExprOrSpread {
                 expr: box Expr::Lit(Lit::Str(_)),
@@ -172,9 +181,17 @@ impl VisitMut for ContextModuleVisitor {
                     // require('./i18n' + n) -> require('./i18n?context&glob=**/*')('.' + n)
                     expr.callee = ctxt_call_expr.as_callee();
                 } else {
+                    let mode = self.comments.as_ref().map_or(ImportMode::Lazy, |comments| {
+                        analyze_import_options(comments, first_arg_span.lo).mode
+                    });
                     // mark async import in params
-                    ctxt_call_expr.args =
-                        vec![quote_str!(format!("{}&{}", args_literals, "async")).as_arg()];
+                    let args_literals = if mode == ImportMode::Lazy {
+                        format!("{}&async", args_literals)
+                    } else {
+                        format!("{}&async&eager", args_literals)
+                    };
+                    // keep the span so that the magic comments still apply to the new import
+                    ctxt_call_expr.args = vec![quote_str!(first_arg_span, args_literals).as_arg()];
 
                     // import('./i18n' + n) -> import('./i18n?context&glob=**/*').then(m => m('.' + n))
                     expr.callee = member_expr!(
@@ -1,5 +1,5 @@
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use anyhow::{anyhow, Result};
@@ -101,6 +101,35 @@ impl CopyPlugin {
         }
         Ok(())
     }
+
+    // the files in the output directory which are copied from `copy`
+    pub(crate) fn copied_files(context: &Arc<Context>) -> Result<Vec<PathBuf>> {
+        let dest = context.config.output.path.as_path();
+        let mut files = vec![];
+        for config in context.config.copy.iter() {
+            let (src, target) = match config {
+                CopyConfig::Basic(src) => (context.root.join(src), dest.to_path_buf()),
+                CopyConfig::Advanced { from, to } => (
+                    context.root.join(from),
+                    dest.join(to.trim_start_matches("/")),
+                ),
+            };
+            for entry in glob(&win_path(src.to_str().unwrap()))? {
+                let entry = entry?;
+                if entry.is_dir() {
+                    for file in glob(&win_path(entry.join("**/*").to_str().unwrap()))? {
+                        let file = file?;
+                        if file.is_file() {
+                            files.push(target.join(file.strip_prefix(&entry)?));
+                        }
+                    }
+                } else {
+                    files.push(target.join(entry.file_name().unwrap()));
+                }
+            }
+        }
+        Ok(files)
+    }
 }
 
 impl Plugin for CopyPlugin {
@@ -0,0 +1,386 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+use colored::Colorize;
+use regex::Regex;
+use swc_core::css::ast::{
+    AtRule, AtRuleName, ComplexSelector, ComplexSelectorChildren, ComponentValue, QualifiedRule,
+    QualifiedRulePrelude, Rule, Stylesheet, SubclassSelector, TypeSelector,
+};
+use swc_core::css::codegen::writer::basic::{BasicCssWriter, BasicCssWriterConfig};
+use swc_core::css::codegen::{CodeGenerator, CodegenConfig, Emit};
+
+use crate::ast::css_ast::CssAst;
+use crate::compiler::Context;
+use crate::config::CriticalCssConfig;
+use crate::generate::chunk::ChunkType;
+use crate::generate::generate_chunks::{ChunkFile, ChunkFileType};
+use crate::plugin::Plugin;
+use crate::plugins::unused_css::parse_safelist_item;
+use crate::stats::StatsJsonMap;
+use crate::utils::create_cached_regex;
+
+/**
+ * Inline the critical css of entries into the html files in the output directory, e.g. the
+ * ones copied from `public`, and load the full stylesheets asynchronously.
+ *
+ * The critical css is the rules whose selectors match the elements in the static html, which
+ * is rendered before any script runs, so no headless browser is needed. Only the stylesheets
+ * of entries linked by the html with `<link rel="stylesheet">` are handled.
+ */
+pub struct CriticalCssPlugin {
+    safelist: Vec<Regex>,
+    max_size: usize,
+    entries: Mutex<Vec<(String, Vec<CssFile>)>>,
+}
+
+impl CriticalCssPlugin {
+    pub fn new(config: &CriticalCssConfig) -> Result<Self> {
+        Ok(Self {
+            safelist: config
+                .safelist
+                .iter()
+                .map(|item| parse_safelist_item(item))
+                .collect::<Result<Vec<_>>>()?,
+            max_size: config.max_size,
+            entries: Mutex::new(vec![]),
+        })
+    }
+}
+
+impl Plugin for CriticalCssPlugin {
+    fn name(&self) -> &str {
+        "critical_css"
+    }
+
+    fn after_generate_chunk_files(
+        &self,
+        chunk_files: &[ChunkFile],
+        context: &Arc<Context>,
+    ) -> Result<()> {
+        let css_files = chunk_files
+            .iter()
+            .filter(|f| matches!(f.file_type, ChunkFileType::Css))
+            .map(|f| {
+                (
+                    f.chunk_id.clone(),
+                    (
+                        f.disk_name(),
+                        String::from_utf8_lossy(&f.content).to_string(),
+                    ),
+                )
+            })
+            .collect::<HashMap<_, _>>();
+
+        let chunk_graph = context.chunk_graph.read().unwrap();
+        let mut entries = vec![];
+        for chunk in chunk_graph.get_chunks() {
+            let ChunkType::Entry(_, name, false) = &chunk.chunk_type else {
+                continue;
+            };
+            // shared chunks are loaded before the entry chunk
+            let mut chunk_ids = chunk_graph.entry_dependencies_chunk(&chunk.id);
+            chunk_ids.push(chunk.id.clone());
+            let files = chunk_ids
+                .iter()
+                .filter_map(|id| css_files.get(&id.id).cloned())
+                .collect::<Vec<_>>();
+            if !files.is_empty() {
+                entries.push((name.clone(), files));
+            }
+        }
+        *self.entries.lock().unwrap() = entries;
+        Ok(())
+    }
+
+    // after the html files are copied to the output directory
+    fn build_success(&self, _stats: &StatsJsonMap, context: &Arc<Context>) -> Result<()> {
+        let output_path = &context.config.output.path;
+        if !output_path.exists() {
+            return Ok(());
+        }
+        let entries = self.entries.lock().unwrap();
+        for entry in fs::read_dir(output_path)? {
+            let path = entry?.path();
+            if !path.extension().is_some_and(|ext| ext == "html") {
+                continue;
+            }
+            let html = fs::read_to_string(&path)?;
+            if html.contains(CRITICAL_STYLE_ATTR) {
+                continue;
+            }
+            let mut result = html.clone();
+            for (name, files) in entries.iter() {
+                let linked = files
+                    .iter()
+                    .filter(|(file, _)| find_stylesheet_link(&result, file).is_some())
+                    .collect::<Vec<_>>();
+                if linked.is_empty() {
+                    continue;
+                }
+                let css = self.critical_css(name, &linked, &html, context)?;
+                result = inline_critical_css(&result, &css, &linked);
+            }
+            if result != html {
+                fs::write(&path, result)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// (file name, content)
+type CssFile = (String, String);
+
+const CRITICAL_STYLE_ATTR: &str = "data-mako-critical";
+
+impl CriticalCssPlugin {
+    fn critical_css(
+        &self,
+        entry: &str,
+        files: &[&CssFile],
+        html: &str,
+        context: &Arc<Context>,
+    ) -> Result<String> {
+        let document = HtmlDocument::new(html);
+        let mut css = String::new();
+        for (file, content) in files {
+            let mut ast = CssAst::build(file, content, context.clone(), false)?;
+            ast.ast
+                .rules
+                .retain_mut(|rule| self.retain_rule(rule, &document));
+            for rule in &ast.ast.rules {
+                let code = to_code(rule)?;
+                if css.len() + code.len() > self.max_size {
+                    println!(
+                        "{}: critical css of entry {} exceeds the max size {} bytes, the rest rules are not inlined",
+                        "warning".to_string().yellow(),
+                        entry,
+                        self.max_size
+                    );
+                    return Ok(css);
+                }
+                css.push_str(&code);
+            }
+        }
+        Ok(css)
+    }
+
+    fn retain_rule(&self, rule: &mut Rule, document: &HtmlDocument) -> bool {
+        match rule {
+            Rule::QualifiedRule(rule) => self.retain_qualified_rule(rule, document),
+            Rule::AtRule(rule) => self.retain_at_rule(rule, document),
+            _ => false,
+        }
+    }
+
+    fn retain_qualified_rule(&self, rule: &mut QualifiedRule, document: &HtmlDocument) -> bool {
+        let QualifiedRulePrelude::SelectorList(list) = &mut rule.prelude else {
+            return false;
+        };
+        list.children.retain(|selector| {
+            document.matches(selector) || {
+                let selector = CssAst::selector_to_string(selector);
+                self.safelist.iter().any(|re| re.is_match(&selector))
+            }
+        });
+        !list.children.is_empty()
+    }
+
+    // conditional group rules are kept if any rule inside is critical, others like
+    // @font-face and @keyframes are left to the full stylesheet
+    fn retain_at_rule(&self, rule: &mut AtRule, document: &HtmlDocument) -> bool {
+        let AtRuleName::Ident(name) = &rule.name else {
+            return false;
+        };
+        if !["media", "supports", "container", "layer"]
+            .iter()
+            .any(|n| name.value.eq_ignore_ascii_case(n))
+        {
+            return false;
+        }
+        let Some(block) = &mut rule.block else {
+            return false;
+        };
+        block.value.retain_mut(|value| match value {
+            ComponentValue::QualifiedRule(rule) => self.retain_qualified_rule(rule, document),
+            ComponentValue::AtRule(rule) => self.retain_at_rule(rule, document),
+            _ => false,
+        });
+        !block.value.is_empty()
+    }
+}
+
+fn to_code(rule: &Rule) -> Result<String> {
+    let mut code = String::new();
+    let writer = BasicCssWriter::new(&mut code, None, BasicCssWriterConfig::default());
+    let mut gen = CodeGenerator::new(writer, CodegenConfig { minify: true });
+    gen.emit(&Stylesheet {
+        span: Default::default(),
+        rules: vec![rule.clone()],
+    })?;
+    Ok(code)
+}
+
+// the elements in the static html, scripts and styles are skipped
+struct HtmlDocument {
+    tags: HashSet<String>,
+    classes: HashSet<String>,
+    ids: HashSet<String>,
+}
+
+impl HtmlDocument {
+    fn new(html: &str) -> Self {
+        let html = create_cached_regex(
+            r"(?is)<!--.*?-->|<script\b.*?</script>|<style\b.*?</style>|<noscript\b.*?</noscript>",
+        )
+        .replace_all(html, "");
+        let tags = create_cached_regex(r"<([a-zA-Z][a-zA-Z0-9-]*)")
+            .captures_iter(&html)
+            .map(|c| c[1].to_ascii_lowercase())
+            .collect();
+        let classes = create_cached_regex(r#"(?i)\sclass\s*=\s*(?:"([^"]*)"|'([^']*)')"#)
+            .captures_iter(&html)
+            .flat_map(|c| {
+                c.get(1)
+                    .or(c.get(2))
+                    .map(|m| m.as_str())
+                    .unwrap_or_default()
+                    .split_whitespace()
+                    .map(|s| s.to_string())
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        let ids = create_cached_regex(r#"(?i)\sid\s*=\s*(?:"([^"]*)"|'([^']*)')"#)
+            .captures_iter(&html)
+            .filter_map(|c| c.get(1).or(c.get(2)).map(|m| m.as_str().trim().to_string()))
+            .collect();
+        Self { tags, classes, ids }
+    }
+
+    // every compound selector should match some element, which is an approximation without
+    // checking the combinators, pseudo classes and attributes
+    fn matches(&self, selector: &ComplexSelector) -> bool {
+        selector.children.iter().all(|child| {
+            let ComplexSelectorChildren::CompoundSelector(compound) = child else {
+                return true;
+            };
+            let tag_matched = match &compound.type_selector {
+                Some(box TypeSelector::TagName(tag)) => self
+                    .tags
+                    .contains(tag.name.value.value.to_ascii_lowercase().as_str()),
+                _ => true,
+            };
+            tag_matched
+                && compound.subclass_selectors.iter().all(|sel| match sel {
+                    SubclassSelector::Class(class) => {
+                        self.classes.contains(class.text.value.as_str())
+                    }
+                    SubclassSelector::Id(id) => self.ids.contains(id.text.value.as_str()),
+                    _ => true,
+                })
+        })
+    }
+}
+
+// the stylesheet link of the css file, `/index.css`, `./index.css` or `https://cdn/index.css`
+fn find_stylesheet_link<'a>(html: &'a str, file: &str) -> Option<&'a str> {
+    create_cached_regex(r"(?i)<link\b[^>]*>")
+        .find_iter(html)
+        .map(|m| m.as_str())
+        .find(|link| {
+            let is_stylesheet =
+                create_cached_regex(r#"(?i)\srel\s*=\s*["']?stylesheet["'\s>/]"#).is_match(link);
+            let href = create_cached_regex(r#"(?i)\shref\s*=\s*["']([^"']+)["']"#)
+                .captures(link)
+                .map(|c| c[1].split(['?', '#']).next().unwrap().to_string());
+            is_stylesheet
+                && href.is_some_and(|href| {
+                    href == file || href.ends_with(&format!("/{}", file.trim_start_matches('/')))
+                })
+        })
+}
+
+// <link rel="stylesheet" href="index.css" />
+// =>
+// <style data-mako-critical>...</style>
+// <link rel="stylesheet" href="index.css" media="print" onload="this.media='all'" />
+// <noscript><link rel="stylesheet" href="index.css" /></noscript>
+fn inline_critical_css(html: &str, css: &str, files: &[&CssFile]) -> String {
+    let mut html = html.to_string();
+    let mut inlined = false;
+    for (file, _) in files {
+        let Some(link) = find_stylesheet_link(&html, file).map(|l| l.to_string()) else {
+            continue;
+        };
+        // the media is set explicitly, e.g. print stylesheets
+        let async_link = if link.to_ascii_lowercase().contains(" media") {
+            link.clone()
+        } else {
+            let (head, tail) = match link.strip_suffix("/>") {
+                Some(head) => (head.trim_end(), " />"),
+                None => (link.strip_suffix('>').unwrap().trim_end(), ">"),
+            };
+            format!(
+                r#"{} media="print" onload="this.media='all'"{}<noscript>{}</noscript>"#,
+                head, tail, link
+            )
+        };
+        let replacement = if inlined || css.is_empty() {
+            async_link
+        } else {
+            inlined = true;
+            format!(
+                "<style {}>{}</style>{}",
+                CRITICAL_STYLE_ATTR, css, async_link
+            )
+        };
+        html = html.replacen(&link, &replacement, 1);
+    }
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::{find_stylesheet_link, inline_critical_css, CriticalCssPlugin};
+    use crate::compiler::Context;
+    use crate::config::CriticalCssConfig;
+
+    #[test]
+    fn test_critical_css() {
+        let html = r#"<html><body><div id="app" class="header"><h1>hi</h1></div><script>"<p class='x'>"</script></body></html>"#;
+        let css = ".header{color:red}.footer{color:red}#app h1,p{margin:0}.x{color:red}@media print{.header{color:blue}.footer{color:blue}}@font-face{font-family:a}.modal{color:red}";
+        assert_eq!(
+            critical_css(html, css, vec!["/^\\.modal/".to_string()], 1024),
+            ".header{color:red}#app h1{margin:0}@media print{.header{color:blue}}.modal{color:red}"
+        );
+        assert_eq!(critical_css(html, css, vec![], 20), ".header{color:red}");
+    }
+
+    #[test]
+    fn test_inline_critical_css() {
+        let html = r#"<head><link rel="stylesheet" href="/index.css?v=1" /><link rel="stylesheet" href="/print.css" media="print"></head>"#;
+        assert!(find_stylesheet_link(html, "print.css").is_some());
+        assert!(find_stylesheet_link(html, "other.css").is_none());
+        let files = [
+            ("index.css".to_string(), "".to_string()),
+            ("print.css".to_string(), "".to_string()),
+        ];
+        assert_eq!(
+            inline_critical_css(html, "a{color:red}", &files.iter().collect::<Vec<_>>()),
+            r#"<head><style data-mako-critical>a{color:red}</style><link rel="stylesheet" href="/index.css?v=1" media="print" onload="this.media='all'" /><noscript><link rel="stylesheet" href="/index.css?v=1" /></noscript><link rel="stylesheet" href="/print.css" media="print"></head>"#
+        );
+    }
+
+    fn critical_css(html: &str, css: &str, safelist: Vec<String>, max_size: usize) -> String {
+        let plugin = CriticalCssPlugin::new(&CriticalCssConfig { safelist, max_size }).unwrap();
+        let file = ("index.css".to_string(), css.to_string());
+        plugin
+            .critical_css("index", &[&file], html, &Arc::new(Context::default()))
+            .unwrap()
+    }
+}
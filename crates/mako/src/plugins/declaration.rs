@@ -0,0 +1,288 @@
+mod bundle;
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::Result;
+use pathdiff::diff_paths;
+use swc_core::ecma::ast::{ExportSpecifier, Module, ModuleDecl, ModuleItem};
+
+use crate::ast::dts::{
+    emit_dts, get_dts_sources, is_ts_file, replace_dts_sources, resolve_ts_file, strip_dts_ext,
+    transform_dts,
+};
+use crate::compiler::Context;
+use crate::config::{DeclarationConfig, OutputMode};
+use crate::module::ModuleId;
+use crate::plugin::{Plugin, PluginGenerateEndParams};
+use crate::plugins::bundless_compiler::{to_dist_path, to_dist_source};
+
+/**
+ * Emit `.d.ts` files for the typescript sources with isolated declarations. In bundless mode,
+ * the declarations are emitted next to the transpiled files and the imports are rewritten in
+ * the same way as the js files. In bundle mode, the declarations of the files reachable from
+ * each entry are emitted with an `[entry].d.ts`, or rolled up into `[entry].d.ts` with
+ * `declaration.bundle`.
+ */
+pub struct DeclarationPlugin {
+    config: DeclarationConfig,
+}
+
+impl DeclarationPlugin {
+    pub fn new(config: DeclarationConfig) -> Self {
+        Self { config }
+    }
+}
+
+impl Plugin for DeclarationPlugin {
+    fn name(&self) -> &str {
+        "declaration"
+    }
+
+    fn generate_end(
+        &self,
+        _params: &PluginGenerateEndParams,
+        context: &Arc<Context>,
+    ) -> Result<()> {
+        if context.config.output.mode == OutputMode::Bundless {
+            let files = context
+                .module_graph
+                .read()
+                .unwrap()
+                .get_module_ids()
+                .into_iter()
+                .map(|id| PathBuf::from(id.id))
+                .collect();
+            emit_declarations(
+                files,
+                context,
+                |file| to_dts_path(&to_dist_path(file.to_string_lossy(), context)),
+                |path, code| write_declaration(path, code, context),
+            )?;
+            return Ok(());
+        }
+
+        let output_path = &context.config.output.path;
+        for (name, entry) in &context.config.entry {
+            let entry_path = context.root.join(&entry.import);
+            if !is_ts_file(&entry_path) {
+                continue;
+            }
+            let entry_path = entry_path.canonicalize()?;
+            let entry_dts_path = output_path.join(format!("{}.d.ts", name));
+            let content = if self.config.bundle {
+                bundle::bundle_declarations(&entry_path, context)?
+            } else {
+                let default_exports = emit_declarations(
+                    vec![entry_path.clone()],
+                    context,
+                    |file| to_dts_path(&output_path.join(diff_paths(file, &context.root).unwrap())),
+                    |path, code| write_declaration(path, code, context),
+                )?;
+                let source = to_dts_source(
+                    &entry_dts_path,
+                    &to_dts_path(
+                        &output_path.join(diff_paths(&entry_path, &context.root).unwrap()),
+                    ),
+                );
+                let mut content = format!("export * from '{}';\n", source);
+                // `export *` doesn't re-export the default
+                if default_exports.contains(&entry_path) {
+                    content.push_str(&format!("export {{ default }} from '{}';\n", source));
+                }
+                content
+            };
+            write_declaration(&entry_dts_path, &content, context)?;
+        }
+        Ok(())
+    }
+}
+
+// emit the declarations of the typescript files and the local typescript files they import
+// to `write`, returns the files whose declarations have a default export
+pub(crate) fn emit_declarations(
+    files: Vec<PathBuf>,
+    context: &Arc<Context>,
+    dts_path: impl Fn(&Path) -> PathBuf,
+    mut write: impl FnMut(&Path, &str) -> Result<()>,
+) -> Result<HashSet<PathBuf>> {
+    let mut default_exports = HashSet::new();
+    let mut visited = HashSet::new();
+    let mut queue = VecDeque::from(files);
+    while let Some(file) = queue.pop_front() {
+        if !is_ts_file(&file) || is_node_modules(&file) || !visited.insert(file.clone()) {
+            continue;
+        }
+        let content = fs::read_to_string(&file)?;
+        let path = dts_path(&file);
+        if file.to_string_lossy().ends_with(".d.ts") {
+            write(&path, &content)?;
+            continue;
+        }
+
+        let mut ast = transform_dts(&file.to_string_lossy(), &content, context.clone())?;
+        let mut replacements = HashMap::new();
+        for (source, target) in resolve_local_sources(&file, &ast, context) {
+            // in bundless mode, the sources point to the js files like the transpiled code,
+            // typescript resolves them to the declarations next to the js files
+            let replacement = if context.config.output.mode == OutputMode::Bundless
+                && !target.to_string_lossy().ends_with(".d.ts")
+            {
+                to_dist_source(&file.to_string_lossy(), &target.to_string_lossy(), context)?
+            } else {
+                to_dts_source(&path, &dts_path(&target))
+            };
+            replacements.insert(source, replacement);
+            if is_ts_file(&target) {
+                queue.push_back(target);
+            }
+        }
+        replace_dts_sources(&mut ast, &|source| replacements.get(source).cloned());
+
+        let code = emit_dts(&ast, context)?;
+        write(&path, &code)?;
+        if has_default_export(&ast) {
+            default_exports.insert(file);
+        }
+    }
+    Ok(default_exports)
+}
+
+pub(crate) fn has_default_export(ast: &Module) -> bool {
+    ast.body.iter().any(|item| match item {
+        ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(_))
+        | ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(_)) => true,
+        ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(export)) => {
+            export.specifiers.iter().any(|specifier| match specifier {
+                ExportSpecifier::Named(named) => {
+                    named.exported.as_ref().unwrap_or(&named.orig).atom() == "default"
+                }
+                ExportSpecifier::Default(_) => true,
+                ExportSpecifier::Namespace(_) => false,
+            })
+        }
+        _ => false,
+    })
+}
+
+// the sources of the declaration which are resolved to local files, from the module graph or
+// as relative typescript files for type only imports
+fn resolve_local_sources(
+    file: &Path,
+    ast: &Module,
+    context: &Arc<Context>,
+) -> Vec<(String, PathBuf)> {
+    let module_graph = context.module_graph.read().unwrap();
+    let module_id = ModuleId::new(file.to_string_lossy().to_string());
+    let deps = if module_graph.has_module(&module_id) {
+        module_graph
+            .get_dependencies(&module_id)
+            .into_iter()
+            .map(|(id, dep)| (dep.source.clone(), PathBuf::from(&id.id)))
+            .collect::<HashMap<_, _>>()
+    } else {
+        HashMap::new()
+    };
+    get_dts_sources(ast)
+        .into_iter()
+        .filter_map(|src| {
+            let source = src.value.to_string();
+            let target = deps.get(&source).cloned().or_else(|| {
+                source
+                    .starts_with('.')
+                    .then(|| resolve_ts_file(file.parent().unwrap(), &source))
+                    .flatten()
+            })?;
+            (target.is_file() && !is_node_modules(&target)).then_some((source, target))
+        })
+        .collect()
+}
+
+fn to_dts_path(path: &Path) -> PathBuf {
+    PathBuf::from(format!("{}.d.ts", strip_dts_ext(&path.to_string_lossy())))
+}
+
+// the extensionless relative source between two declaration files
+fn to_dts_source(from: &Path, to: &Path) -> String {
+    let relative = diff_paths(to, from.parent().unwrap()).unwrap();
+    let relative = relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect::<Vec<_>>()
+        .join("/");
+    let relative = strip_dts_ext(&relative);
+    if relative.starts_with("../") {
+        relative.to_string()
+    } else {
+        format!("./{}", relative)
+    }
+}
+
+fn is_node_modules(path: &Path) -> bool {
+    path.components().any(|c| c.as_os_str() == "node_modules")
+}
+
+fn write_declaration(path: &Path, content: &str, context: &Arc<Context>) -> Result<()> {
+    if context.config.output.skip_write || fs::read_to_string(path).is_ok_and(|c| c == content) {
+        return Ok(());
+    }
+    fs::create_dir_all(path.parent().unwrap())?;
+    fs::write(path, content)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::{has_default_export, to_dts_path, to_dts_source};
+    use crate::ast::js_ast::JsAst;
+
+    #[test]
+    fn test_to_dts_source() {
+        assert_eq!(
+            to_dts_path(Path::new("/dist/src/App.tsx")),
+            Path::new("/dist/src/App.d.ts")
+        );
+        assert_eq!(
+            to_dts_source(
+                Path::new("/dist/index.d.ts"),
+                Path::new("/dist/src/index.d.ts")
+            ),
+            "./src/index"
+        );
+        assert_eq!(
+            to_dts_source(
+                Path::new("/dist/src/a/b.d.ts"),
+                Path::new("/dist/src/c.d.ts")
+            ),
+            "../c"
+        );
+    }
+
+    #[test]
+    fn test_has_default_export() {
+        let has_default_export = |content: &str| {
+            has_default_export(
+                &JsAst::build("test.ts", content, Default::default())
+                    .unwrap()
+                    .ast,
+            )
+        };
+        assert!(has_default_export("export default function foo(): void;"));
+        assert!(has_default_export(
+            "declare const _default: number; export default _default;"
+        ));
+        assert!(has_default_export(
+            "declare const foo: number; export { foo as default };"
+        ));
+        assert!(has_default_export("export { default } from './foo';"));
+        assert!(!has_default_export("export * from './foo';"));
+        assert!(!has_default_export("export declare const foo: string;"));
+        assert!(!has_default_export(
+            "/** export default */ export declare const default_: string;"
+        ));
+    }
+}
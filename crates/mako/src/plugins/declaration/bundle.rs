@@ -0,0 +1,596 @@
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::Result;
+use colored::Colorize;
+use indexmap::{IndexMap, IndexSet};
+use pathdiff::diff_paths;
+use swc_core::common::DUMMY_SP;
+use swc_core::ecma::ast::{
+    ClassDecl, Decl, DefaultDecl, ExportDecl, ExportSpecifier, Expr, FnDecl, Ident, ImportDecl,
+    ImportSpecifier, Module, ModuleDecl, ModuleExportName, ModuleItem, Stmt,
+};
+
+use super::resolve_local_sources;
+use crate::ast::dts::{emit_dts, strip_dts_ext, transform_dts};
+use crate::compiler::Context;
+use crate::module::relative_to_root;
+
+// the alias of the default export in the namespaces
+const DEFAULT_EXPORT: &str = "__default";
+
+struct DtsModule {
+    ast: Module,
+    // source => local file
+    locals: HashMap<String, PathBuf>,
+    namespace: String,
+}
+
+/**
+ * The imports of the external modules hoisted to the top level. The local names which are
+ * declared by the entry or imported from another source are renamed with a suffix, e.g.
+ * `import { Options as Options_1 } from 'b';`, and the namespaces alias them back.
+ */
+#[derive(Default)]
+struct HoistedImports {
+    items: IndexSet<String>,
+    // the names taken at the top level
+    names: HashSet<String>,
+    // (source, imported, local) => local name at the top level
+    bindings: HashMap<(String, String, String), String>,
+}
+
+impl HoistedImports {
+    // hoist the import, returns the locals which are renamed at the top level
+    fn hoist(
+        &mut self,
+        import: &ImportDecl,
+        context: &Arc<Context>,
+    ) -> Result<Vec<(String, String)>> {
+        let mut import = import.clone();
+        let is_side_effect = import.specifiers.is_empty();
+        let mut renamed = vec![];
+        let mut specifiers = vec![];
+        for mut specifier in import.specifiers {
+            let imported = match &specifier {
+                ImportSpecifier::Named(named) => named
+                    .imported
+                    .as_ref()
+                    .map_or(named.local.sym.to_string(), export_name),
+                ImportSpecifier::Default(_) => "default".to_string(),
+                ImportSpecifier::Namespace(_) => "*".to_string(),
+            };
+            let local = specifier.local().sym.to_string();
+            let key = (import.src.value.to_string(), imported, local.clone());
+            let (name, is_hoisted) = match self.bindings.get(&key) {
+                Some(name) => (name.clone(), true),
+                None => {
+                    let name = self.unique_name(&local);
+                    self.bindings.insert(key, name.clone());
+                    (name, false)
+                }
+            };
+            if name != local {
+                if let ImportSpecifier::Named(named) = &mut specifier
+                    && named.imported.is_none()
+                {
+                    named.imported = Some(ModuleExportName::Ident(named.local.clone()));
+                }
+                specifier.local_mut().sym = name.as_str().into();
+                renamed.push((local, name));
+            }
+            if !is_hoisted {
+                specifiers.push(specifier);
+            }
+        }
+        if !is_side_effect && specifiers.is_empty() {
+            return Ok(renamed);
+        }
+
+        // an import alias can't reference a type only import
+        if !renamed.is_empty() {
+            for specifier in &mut specifiers {
+                if let ImportSpecifier::Named(named) = specifier {
+                    let local = &*named.local.sym;
+                    named.is_type_only = if renamed.iter().any(|(_, name)| name == local) {
+                        false
+                    } else {
+                        named.is_type_only || import.type_only
+                    };
+                }
+            }
+            import.type_only = false;
+        }
+        import.specifiers = specifiers;
+        self.items.insert(emit_items(
+            vec![ModuleItem::ModuleDecl(ModuleDecl::Import(import))],
+            context,
+        )?);
+        Ok(renamed)
+    }
+
+    // the local name with a suffix if it's taken, e.g. `Options_1`
+    fn unique_name(&mut self, local: &str) -> String {
+        let mut name = local.to_string();
+        let mut index = 0;
+        while self.names.contains(&name) {
+            index += 1;
+            name = format!("{}_{}", local, index);
+        }
+        self.names.insert(name.clone());
+        name
+    }
+}
+
+/**
+ * Roll up the declarations of the entry and the local files it imports into one file. The
+ * declarations of each imported file are wrapped in a namespace to avoid name conflicts, and
+ * the imports between the files are replaced with import aliases, e.g.
+ *
+ * import { A } from './a';  =>  import A = __src_a.A;
+ */
+pub(super) fn bundle_declarations(entry: &Path, context: &Arc<Context>) -> Result<String> {
+    let modules = collect_modules(entry, context)?;
+
+    let mut hoisted = HoistedImports::default();
+    hoisted
+        .names
+        .extend(modules.values().map(|module| module.namespace.clone()));
+    hoisted.names.insert(DEFAULT_EXPORT.to_string());
+    if let Some(module) = modules.get(entry) {
+        hoisted.names.extend(top_level_names(module));
+    }
+    let mut code = String::new();
+    for (path, module) in &modules {
+        let is_entry = path == entry;
+        let items = render_module(path, module, is_entry, &modules, &mut hoisted, context)?;
+        if is_entry {
+            code.insert_str(0, &items);
+        } else {
+            code.push_str(&format!("declare namespace {} {{\n", module.namespace));
+            for line in items.lines() {
+                if line.is_empty() {
+                    code.push('\n');
+                } else {
+                    code.push_str(&format!("    {}\n", line));
+                }
+            }
+            code.push_str("}\n");
+        }
+    }
+    let hoisted = hoisted.items.into_iter().collect::<Vec<_>>().join("\n");
+    // `export {}` keeps the namespaces from being exported implicitly
+    Ok(format!("{}\n{}export {{}};\n", hoisted, code)
+        .trim_start()
+        .to_string())
+}
+
+fn collect_modules(entry: &Path, context: &Arc<Context>) -> Result<IndexMap<PathBuf, DtsModule>> {
+    let mut modules = IndexMap::new();
+    let mut visited = HashSet::new();
+    let mut queue = VecDeque::from([entry.to_path_buf()]);
+    while let Some(file) = queue.pop_front() {
+        if !visited.insert(file.clone()) {
+            continue;
+        }
+        let content = fs::read_to_string(&file)?;
+        let ast = transform_dts(&file.to_string_lossy(), &content, context.clone())?;
+        let locals = resolve_local_sources(&file, &ast, context);
+        queue.extend(locals.iter().map(|(_, path)| path.clone()));
+        let locals = locals.into_iter().collect::<HashMap<_, _>>();
+        let namespace = to_namespace(&file, &context.root);
+        modules.insert(
+            file,
+            DtsModule {
+                ast,
+                locals,
+                namespace,
+            },
+        );
+    }
+    Ok(modules)
+}
+
+fn render_module(
+    path: &Path,
+    module: &DtsModule,
+    is_entry: bool,
+    modules: &IndexMap<PathBuf, DtsModule>,
+    hoisted: &mut HoistedImports,
+    context: &Arc<Context>,
+) -> Result<String> {
+    let mut code = String::new();
+    // local => target of the import aliases, and exported => target of the export aliases
+    let mut imports = IndexMap::new();
+    let mut exports = IndexMap::new();
+    let local_namespace = |source: &str| {
+        module
+            .locals
+            .get(source)
+            .and_then(|path| modules.get(path).map(|m| (path, m.namespace.as_str())))
+    };
+
+    for item in &module.ast.body {
+        match item {
+            ModuleItem::ModuleDecl(ModuleDecl::Import(import)) => {
+                let Some((_, namespace)) = local_namespace(&import.src.value) else {
+                    imports.extend(hoisted.hoist(import, context)?);
+                    continue;
+                };
+                for specifier in &import.specifiers {
+                    let (local, target) = match specifier {
+                        ImportSpecifier::Named(named) => (
+                            &named.local.sym,
+                            member(
+                                namespace,
+                                &named
+                                    .imported
+                                    .as_ref()
+                                    .map_or(named.local.sym.to_string(), export_name),
+                            ),
+                        ),
+                        ImportSpecifier::Default(default) => {
+                            (&default.local.sym, member(namespace, "default"))
+                        }
+                        ImportSpecifier::Namespace(ns) => (&ns.local.sym, namespace.to_string()),
+                    };
+                    imports.insert(local.to_string(), target);
+                }
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(export)) => {
+                let Some(src) = &export.src else {
+                    if is_entry {
+                        code.push_str(&emit_items(vec![item.clone()], context)?);
+                        continue;
+                    }
+                    // `export { a as b }` => `export import b = a;`
+                    for specifier in &export.specifiers {
+                        if let ExportSpecifier::Named(named) = specifier {
+                            let orig = export_name(&named.orig);
+                            let exported =
+                                named.exported.as_ref().map_or(orig.clone(), export_name);
+                            export_alias(&mut exports, &exported, &orig);
+                        }
+                    }
+                    continue;
+                };
+                match local_namespace(&src.value) {
+                    Some((_, namespace)) => {
+                        for specifier in &export.specifiers {
+                            match specifier {
+                                ExportSpecifier::Named(named) => {
+                                    let orig = export_name(&named.orig);
+                                    let exported =
+                                        named.exported.as_ref().map_or(orig.clone(), export_name);
+                                    let target = member(namespace, &orig);
+                                    if is_entry && exported == "default" {
+                                        code.push_str(&format!(
+                                            "import {} = {};\nexport default {};\n",
+                                            DEFAULT_EXPORT, target, DEFAULT_EXPORT
+                                        ));
+                                    } else {
+                                        export_alias(&mut exports, &exported, &target);
+                                    }
+                                }
+                                ExportSpecifier::Namespace(ns) => {
+                                    export_alias(&mut exports, &export_name(&ns.name), namespace);
+                                }
+                                ExportSpecifier::Default(_) => {}
+                            }
+                        }
+                    }
+                    None if is_entry => code.push_str(&emit_items(vec![item.clone()], context)?),
+                    None => {
+                        // re-exports of the external modules are imported at the top level
+                        for specifier in &export.specifiers {
+                            let (orig, exported) = match specifier {
+                                ExportSpecifier::Named(named) => {
+                                    let orig = export_name(&named.orig);
+                                    let exported =
+                                        named.exported.as_ref().map_or(orig.clone(), export_name);
+                                    (Some(orig), exported)
+                                }
+                                ExportSpecifier::Namespace(ns) => (None, export_name(&ns.name)),
+                                ExportSpecifier::Default(_) => continue,
+                            };
+                            let local = format!("{}_{}", module.namespace, exported);
+                            hoisted.items.insert(match orig {
+                                Some(orig) => format!(
+                                    "import {{ {} as {} }} from '{}';",
+                                    orig, local, src.value
+                                ),
+                                None => format!("import * as {} from '{}';", local, src.value),
+                            });
+                            export_alias(&mut exports, &exported, &local);
+                        }
+                    }
+                }
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportAll(export)) => {
+                match local_namespace(&export.src.value) {
+                    Some((path, namespace)) => {
+                        for name in exported_names(path, modules, &mut HashSet::new()) {
+                            if name != "default" {
+                                export_alias(&mut exports, &name, &member(namespace, &name));
+                            }
+                        }
+                    }
+                    None if is_entry => code.push_str(&emit_items(vec![item.clone()], context)?),
+                    None => warn(
+                        &format!("`export * from '{}'` can't be bundled", export.src.value),
+                        path,
+                        context,
+                    ),
+                }
+            }
+            _ if is_entry => code.push_str(&emit_items(vec![item.clone()], context)?),
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) => {
+                let mut export = export.clone();
+                strip_declare(&mut export.decl);
+                code.push_str(&emit_items(
+                    vec![ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export))],
+                    context,
+                )?);
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(export)) => {
+                let (decl, ident) = match &export.decl {
+                    DefaultDecl::Class(class) => {
+                        let ident = class
+                            .ident
+                            .clone()
+                            .unwrap_or_else(|| Ident::from(DEFAULT_EXPORT));
+                        (
+                            Decl::Class(ClassDecl {
+                                ident: ident.clone(),
+                                declare: false,
+                                class: class.class.clone(),
+                            }),
+                            ident,
+                        )
+                    }
+                    DefaultDecl::Fn(function) => {
+                        let ident = function
+                            .ident
+                            .clone()
+                            .unwrap_or_else(|| Ident::from(DEFAULT_EXPORT));
+                        (
+                            Decl::Fn(FnDecl {
+                                ident: ident.clone(),
+                                declare: false,
+                                function: function.function.clone(),
+                            }),
+                            ident,
+                        )
+                    }
+                    DefaultDecl::TsInterfaceDecl(interface) => {
+                        let mut interface = interface.clone();
+                        interface.declare = false;
+                        let ident = interface.id.clone();
+                        (Decl::TsInterface(interface), ident)
+                    }
+                };
+                code.push_str(&emit_items(
+                    vec![ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                        span: DUMMY_SP,
+                        decl,
+                    }))],
+                    context,
+                )?);
+                if ident.sym != DEFAULT_EXPORT {
+                    export_alias(&mut exports, "default", &ident.sym);
+                }
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(export)) => {
+                if let Expr::Ident(ident) = &*export.expr {
+                    export_alias(&mut exports, "default", &ident.sym);
+                }
+            }
+            ModuleItem::Stmt(Stmt::Decl(decl)) => {
+                let mut decl = decl.clone();
+                strip_declare(&mut decl);
+                code.push_str(&emit_items(
+                    vec![ModuleItem::Stmt(Stmt::Decl(decl))],
+                    context,
+                )?);
+            }
+            ModuleItem::ModuleDecl(_) => warn(
+                "`export =` and `export as namespace` can't be bundled",
+                path,
+                context,
+            ),
+            ModuleItem::Stmt(_) => {}
+        }
+    }
+
+    let mut aliases = String::new();
+    for (local, target) in imports {
+        // `export import a = b;` declares `a` too
+        if exports.get(&local) != Some(&target) {
+            aliases.push_str(&format!("import {} = {};\n", local, target));
+        }
+    }
+    for (exported, target) in exports {
+        aliases.push_str(&format!("export import {} = {};\n", exported, target));
+    }
+    aliases.push_str(&code);
+    Ok(aliases)
+}
+
+// all the export names of a module, including the ones from `export *`
+fn exported_names(
+    path: &Path,
+    modules: &IndexMap<PathBuf, DtsModule>,
+    visited: &mut HashSet<PathBuf>,
+) -> BTreeSet<String> {
+    let mut names = BTreeSet::new();
+    let Some(module) = modules.get(path) else {
+        return names;
+    };
+    if !visited.insert(path.to_path_buf()) {
+        return names;
+    }
+    for item in &module.ast.body {
+        let ModuleItem::ModuleDecl(decl) = item else {
+            continue;
+        };
+        match decl {
+            ModuleDecl::ExportDecl(export) => names.extend(decl_names(&export.decl)),
+            ModuleDecl::ExportNamed(export) => {
+                names.extend(export.specifiers.iter().filter_map(|s| {
+                    match s {
+                        ExportSpecifier::Named(named) => Some(
+                            named
+                                .exported
+                                .as_ref()
+                                .map_or(export_name(&named.orig), export_name),
+                        ),
+                        ExportSpecifier::Namespace(ns) => Some(export_name(&ns.name)),
+                        ExportSpecifier::Default(_) => None,
+                    }
+                }));
+            }
+            ModuleDecl::ExportDefaultDecl(_) | ModuleDecl::ExportDefaultExpr(_) => {
+                names.insert("default".to_string());
+            }
+            ModuleDecl::ExportAll(export) => {
+                if let Some(path) = module.locals.get(&*export.src.value) {
+                    names.extend(
+                        exported_names(path, modules, visited)
+                            .into_iter()
+                            .filter(|n| n != "default"),
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+    names
+}
+
+// the names declared at the top level of the module, including the import aliases of the
+// local imports
+fn top_level_names(module: &DtsModule) -> Vec<String> {
+    module
+        .ast
+        .body
+        .iter()
+        .flat_map(|item| match item {
+            ModuleItem::Stmt(Stmt::Decl(decl)) => decl_names(decl),
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) => decl_names(&export.decl),
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(export)) => match &export.decl {
+                DefaultDecl::Class(class) => {
+                    class.ident.iter().map(|i| i.sym.to_string()).collect()
+                }
+                DefaultDecl::Fn(function) => {
+                    function.ident.iter().map(|i| i.sym.to_string()).collect()
+                }
+                DefaultDecl::TsInterfaceDecl(interface) => vec![interface.id.sym.to_string()],
+            },
+            ModuleItem::ModuleDecl(ModuleDecl::Import(import))
+                if module.locals.contains_key(&*import.src.value) =>
+            {
+                import
+                    .specifiers
+                    .iter()
+                    .map(|specifier| specifier.local().sym.to_string())
+                    .collect()
+            }
+            _ => vec![],
+        })
+        .collect()
+}
+
+fn decl_names(decl: &Decl) -> Vec<String> {
+    match decl {
+        Decl::Class(c) => vec![c.ident.sym.to_string()],
+        Decl::Fn(f) => vec![f.ident.sym.to_string()],
+        Decl::Var(v) => v
+            .decls
+            .iter()
+            .filter_map(|d| d.name.as_ident().map(|i| i.sym.to_string()))
+            .collect(),
+        Decl::TsInterface(i) => vec![i.id.sym.to_string()],
+        Decl::TsTypeAlias(t) => vec![t.id.sym.to_string()],
+        Decl::TsEnum(e) => vec![e.id.sym.to_string()],
+        Decl::TsModule(m) => {
+            m.id.as_ident()
+                .map(|i| i.sym.to_string())
+                .into_iter()
+                .collect()
+        }
+        Decl::Using(_) => vec![],
+    }
+}
+
+fn emit_items(body: Vec<ModuleItem>, context: &Arc<Context>) -> Result<String> {
+    emit_dts(
+        &Module {
+            span: DUMMY_SP,
+            body,
+            shebang: None,
+        },
+        context,
+    )
+}
+
+// declarations in namespaces are already ambient
+fn strip_declare(decl: &mut Decl) {
+    match decl {
+        Decl::Class(c) => c.declare = false,
+        Decl::Fn(f) => f.declare = false,
+        Decl::Var(v) => v.declare = false,
+        Decl::TsInterface(i) => i.declare = false,
+        Decl::TsTypeAlias(t) => t.declare = false,
+        Decl::TsEnum(e) => e.declare = false,
+        Decl::TsModule(m) => m.declare = false,
+        Decl::Using(_) => {}
+    }
+}
+
+fn export_alias(exports: &mut IndexMap<String, String>, exported: &str, target: &str) {
+    let exported = if exported == "default" {
+        DEFAULT_EXPORT
+    } else {
+        exported
+    };
+    exports.insert(exported.to_string(), target.to_string());
+}
+
+fn member(namespace: &str, name: &str) -> String {
+    if name == "default" {
+        format!("{}.{}", namespace, DEFAULT_EXPORT)
+    } else {
+        format!("{}.{}", namespace, name)
+    }
+}
+
+fn export_name(name: &ModuleExportName) -> String {
+    match name {
+        ModuleExportName::Ident(ident) => ident.sym.to_string(),
+        ModuleExportName::Str(str) => str.value.to_string(),
+    }
+}
+
+// `src/utils/index.ts` => `__src_utils_index`
+fn to_namespace(path: &Path, root: &Path) -> String {
+    let relative = diff_paths(path, root).unwrap_or(path.to_path_buf());
+    let relative = relative.to_string_lossy();
+    format!(
+        "__{}",
+        strip_dts_ext(&relative)
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect::<String>()
+    )
+}
+
+fn warn(message: &str, path: &Path, context: &Arc<Context>) {
+    println!(
+        "{}: {} in {}",
+        "warning".to_string().yellow(),
+        message,
+        relative_to_root(&path.to_string_lossy().to_string(), &context.root),
+    );
+}
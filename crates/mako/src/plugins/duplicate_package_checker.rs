@@ -11,10 +11,11 @@ use crate::plugin::Plugin;
 use crate::resolve::ResolverResource;
 
 #[derive(Debug, Clone)]
-struct PackageInfo {
-    name: String,
-    version: Version,
-    path: PathBuf,
+pub(crate) struct PackageInfo {
+    pub name: String,
+    pub version: Version,
+    // the path of package.json
+    pub path: PathBuf,
 }
 
 #[derive(Default)]
@@ -34,7 +35,7 @@ fn clean_path(module_path: &Path) -> PathBuf {
 }
 
 /// Makes the cleaned path relative to the given context
-fn clean_path_relative_to_context(module_path: &Path, context: &Path) -> PathBuf {
+pub(crate) fn clean_path_relative_to_context(module_path: &Path, context: &Path) -> PathBuf {
     let cleaned_path = clean_path(module_path);
     let context_str = context.to_str().unwrap();
     let cleaned_path_str = cleaned_path.to_str().unwrap();
@@ -47,7 +48,7 @@ fn clean_path_relative_to_context(module_path: &Path, context: &Path) -> PathBuf
     }
 }
 
-fn extract_package_info(module: &Module) -> Option<PackageInfo> {
+pub(crate) fn extract_package_info(module: &Module) -> Option<PackageInfo> {
     module
         .info
         .as_ref()
@@ -71,6 +72,27 @@ fn extract_package_info(module: &Module) -> Option<PackageInfo> {
         })
 }
 
+/// Groups the packages by name, and keeps the names found with more than one package.json
+pub(crate) fn find_duplicates(packages: Vec<PackageInfo>) -> HashMap<String, Vec<PackageInfo>> {
+    let mut package_map: HashMap<String, Vec<PackageInfo>> = HashMap::new();
+
+    for package in packages {
+        let instances = package_map.entry(package.name.clone()).or_default();
+        // modules of the same package share the package.json
+        if !instances
+            .iter()
+            .any(|instance| instance.path == package.path)
+        {
+            instances.push(package);
+        }
+    }
+
+    package_map
+        .into_iter()
+        .filter(|(_, versions)| versions.len() > 1)
+        .collect()
+}
+
 impl DuplicatePackageCheckerPlugin {
     pub fn new() -> Self {
         Self::default()
@@ -91,22 +113,6 @@ impl DuplicatePackageCheckerPlugin {
         self
     }
 
-    fn find_duplicates(packages: Vec<PackageInfo>) -> HashMap<String, Vec<PackageInfo>> {
-        let mut package_map: HashMap<String, Vec<PackageInfo>> = HashMap::new();
-
-        for package in packages {
-            package_map
-                .entry(package.name.clone())
-                .or_default()
-                .push(package);
-        }
-
-        package_map
-            .into_iter()
-            .filter(|(_, versions)| versions.len() > 1)
-            .collect()
-    }
-
     fn check_duplicates(
         &self,
         module_graph: &RwLock<ModuleGraph>,
@@ -123,7 +129,7 @@ impl DuplicatePackageCheckerPlugin {
                 packages.push(package_info);
             });
 
-        Self::find_duplicates(packages)
+        find_duplicates(packages)
     }
 }
 
@@ -50,78 +50,191 @@
     }
     return fn;
   };
+  // persisted `hot.data` and the `hot.on` listeners of the modules
+  var hotData = {};
+  var hotListeners = {};
+  var emitHotEvent = function (event, payload) {
+    var listeners = (hotListeners[event] || []).slice();
+    for (var i = 0; i < listeners.length; i++) {
+      listeners[i].callback(payload);
+    }
+  };
+  var removeHotListeners = function (moduleId) {
+    for (var event in hotListeners) {
+      hotListeners[event] = hotListeners[event].filter(function (listener) {
+        return listener.moduleId !== moduleId;
+      });
+    }
+  };
+  var fullReload = function (moduleId) {
+    emitHotEvent('vite:beforeFullReload', {
+      type: 'full-reload',
+      path: moduleId,
+    });
+    location.reload();
+  };
   var applyHotUpdate = function (_chunkId, update) {
     var modules = update.modules,
-      removedModules = update.removedModules;
+      removedModules = update.removedModules || [],
+      invalidatedModules = update.invalidatedModules || [];
     var outdatedModules = [];
-    for (var _i = 0, _a = Object.keys(modules); _i < _a.length; _i++) {
-      var moduleId = _a[_i];
+    // parent id => the accepted dependencies ids which are outdated
+    var outdatedDependencies = {};
+    var queue = [];
+    var bubble = function (moduleId) {
+      var parents = modulesRegistry[moduleId].parents;
+      for (var i = 0; i < parents.length; i++) {
+        var parentId = parents[i];
+        var parent = modulesRegistry[parentId];
+        if (parent && parent.hot._acceptedDependencies[moduleId]) {
+          var deps =
+            outdatedDependencies[parentId] ||
+            (outdatedDependencies[parentId] = []);
+          if (!deps.includes(moduleId)) deps.push(moduleId);
+          continue;
+        }
+        if (outdatedModules.includes(parentId)) continue;
+        outdatedModules.push(parentId);
+        queue.push(parentId);
+      }
+    };
+    for (var moduleId in modules) {
       if (!modulesRegistry[moduleId]) continue;
       if (outdatedModules.includes(moduleId)) continue;
       outdatedModules.push(moduleId);
-      var queue = [moduleId];
-      while (queue.length) {
-        var item = queue.pop();
-        var module = modulesRegistry[item];
-        if (!module) continue;
-        if (module.hot._main) {
-          location.reload();
-        }
-        if (module.hot._selfAccepted) {
-          continue;
-        }
-        for (var _b = 0, _c = module.parents; _b < _c.length; _b++) {
-          var parentModule = _c[_b];
-          if (outdatedModules.includes(parentModule)) continue;
-          outdatedModules.push(parentModule);
-          queue.push(parentModule);
-        }
+      queue.push(moduleId);
+    }
+    // the invalidated module is disposed and re-executed with its parents, even if it's self
+    // accepted
+    for (var i = 0; i < invalidatedModules.length; i++) {
+      var moduleId = invalidatedModules[i];
+      if (!modulesRegistry[moduleId]) continue;
+      if (outdatedModules.includes(moduleId)) continue;
+      outdatedModules.push(moduleId);
+      queue.push(moduleId);
+    }
+    var isSelfAccepted = function (moduleId) {
+      return (
+        modulesRegistry[moduleId].hot._selfAccepted &&
+        !invalidatedModules.includes(moduleId)
+      );
+    };
+    while (queue.length) {
+      var item = queue.pop();
+      var module = modulesRegistry[item];
+      if (!module) continue;
+      if (isSelfAccepted(item)) {
+        continue;
+      }
+      if (module.hot._main || module.hot._selfDeclined) {
+        fullReload(item);
+        return;
       }
+      bubble(item);
     }
+
+    var updates = [];
     var outdatedSelfAcceptedModules = [];
-    for (
-      var _d = 0, outdatedModules_1 = outdatedModules;
-      _d < outdatedModules_1.length;
-      _d++
-    ) {
-      var moduleId = outdatedModules_1[_d];
+    for (var i = 0; i < outdatedModules.length; i++) {
+      var moduleId = outdatedModules[i];
       var module = modulesRegistry[moduleId];
-      if (module.hot._selfAccepted) {
+      if (isSelfAccepted(moduleId)) {
         outdatedSelfAcceptedModules.push(module);
+        updates.push({ path: moduleId, acceptedPath: moduleId });
       }
     }
-    for (
-      var _e = 0, outdatedModules_2 = outdatedModules;
-      _e < outdatedModules_2.length;
-      _e++
-    ) {
-      var moduleId = outdatedModules_2[_e];
+    for (var parentId in outdatedDependencies) {
+      for (var i = 0; i < outdatedDependencies[parentId].length; i++) {
+        updates.push({
+          path: parentId,
+          acceptedPath: outdatedDependencies[parentId][i],
+        });
+      }
+    }
+    var timestamp = Date.now();
+    var payload = {
+      type: 'update',
+      updates: updates.map(function (u) {
+        return {
+          type: 'js-update',
+          path: u.path,
+          acceptedPath: u.acceptedPath,
+          timestamp: timestamp,
+        };
+      }),
+    };
+    if (updates.length) emitHotEvent('vite:beforeUpdate', payload);
+
+    var disposeModule = function (moduleId, handlersKey) {
       var module = modulesRegistry[moduleId];
-      for (var _f = 0, _g = module.hot._disposeHandlers; _f < _g.length; _f++) {
-        var handler = _g[_f];
-        handler();
+      var data = hotData[moduleId] || (hotData[moduleId] = {});
+      var handlers = module.hot[handlersKey];
+      for (var i = 0; i < handlers.length; i++) {
+        handlers[i](data);
       }
       module.hot.active = false;
+      removeHotListeners(moduleId);
       delete modulesRegistry[moduleId];
-      for (var _j = 0, _k = module.children; _j < _k.length; _j++) {
-        var childModule = _k[_j];
-        var child = modulesRegistry[childModule];
+      for (var i = 0; i < module.children.length; i++) {
+        var child = modulesRegistry[module.children[i]];
         if (!child) continue;
         var idx = child.parents.indexOf(moduleId);
         if (idx !== -1) {
           child.parents.splice(idx, 1);
         }
       }
+    };
+    var outdatedDependencyIds = [];
+    for (var parentId in outdatedDependencies) {
+      outdatedDependencies[parentId].forEach(function (id) {
+        if (!outdatedDependencyIds.includes(id)) outdatedDependencyIds.push(id);
+      });
+    }
+    outdatedModules
+      .concat(outdatedDependencyIds)
+      .forEach(function (moduleId) {
+        if (modulesRegistry[moduleId]) {
+          disposeModule(moduleId, '_disposeHandlers');
+        }
+      });
+    for (var i = 0; i < removedModules.length; i++) {
+      var moduleId = removedModules[i];
+      if (modulesRegistry[moduleId]) {
+        disposeModule(moduleId, '_pruneHandlers');
+        delete hotData[moduleId];
+      }
     }
+
     registerModules(modules);
-    for (
-      var _l = 0, outdatedSelfAcceptedModules_1 = outdatedSelfAcceptedModules;
-      _l < outdatedSelfAcceptedModules_1.length;
-      _l++
-    ) {
-      var module = outdatedSelfAcceptedModules_1[_l];
+    for (var i = 0; i < outdatedSelfAcceptedModules.length; i++) {
+      var module = outdatedSelfAcceptedModules[i];
       module.hot._requireSelf();
+      if (typeof module.hot._selfAcceptedCallback === 'function') {
+        module.hot._selfAcceptedCallback(modulesRegistry[module.id].exports);
+      }
+    }
+    for (var parentId in outdatedDependencies) {
+      var parent = modulesRegistry[parentId];
+      if (!parent) continue;
+      var hmrRequire = createHmrRequire(requireModule, parentId);
+      var callbacks = [];
+      outdatedDependencies[parentId].forEach(function (depId) {
+        var accepted = parent.hot._acceptedDependencies[depId];
+        if (!callbacks.includes(accepted)) callbacks.push(accepted);
+      });
+      callbacks.forEach(function (accepted) {
+        // new exports of the accepted deps, `undefined` for the not updated
+        var newModules = accepted.deps.map(function (depId) {
+          return outdatedDependencies[parentId].includes(depId)
+            ? hmrRequire(depId)
+            : undefined;
+        });
+        if (typeof accepted.callback === 'function') {
+          accepted.callback(accepted.single ? newModules[0] : newModules);
+        }
+      });
     }
+    if (updates.length) emitHotEvent('vite:afterUpdate', payload);
   };
   var createModuleHotObject = function (moduleId, me) {
     var _main = currentChildModule !== moduleId;
@@ -129,9 +242,11 @@
       _acceptedDependencies: {},
       _declinedDependencies: {},
       _selfAccepted: false,
+      _selfAcceptedCallback: undefined,
       _selfDeclined: false,
       _selfInvalidated: false,
       _disposeHandlers: [],
+      _pruneHandlers: [],
       _requireSelf: function () {
         currentParents = me.parents.slice();
         currentChildModule = _main ? undefined : moduleId;
@@ -139,13 +254,58 @@
       },
       _main: _main,
       active: true,
-      accept: function () {
-        this._selfAccepted = true;
+      data: hotData[moduleId] || (hotData[moduleId] = {}),
+      // accept(), accept(cb), accept(dep, cb) or accept([deps], cb)
+      accept: function (deps, callback) {
+        if (deps === undefined || typeof deps === 'function') {
+          this._selfAccepted = true;
+          this._selfAcceptedCallback = deps;
+        } else {
+          var single = !Array.isArray(deps);
+          var accepted = {
+            deps: single ? [deps] : deps,
+            single: single,
+            callback: callback,
+          };
+          for (var i = 0; i < accepted.deps.length; i++) {
+            this._acceptedDependencies[accepted.deps[i]] = accepted;
+          }
+        }
+      },
+      decline: function () {
+        this._selfDeclined = true;
       },
       dispose: function (callback) {
         this._disposeHandlers.push(callback);
       },
-      invalidate: function () {},
+      prune: function (callback) {
+        this._pruneHandlers.push(callback);
+      },
+      // propagate the update to the parents
+      invalidate: function (message) {
+        if (this._selfInvalidated) return;
+        this._selfInvalidated = true;
+        emitHotEvent('vite:invalidate', { path: moduleId, message: message });
+        Promise.resolve().then(function () {
+          applyHotUpdate(undefined, {
+            modules: {},
+            invalidatedModules: [moduleId],
+          });
+        });
+      },
+      on: function (event, callback) {
+        (hotListeners[event] || (hotListeners[event] = [])).push({
+          moduleId: moduleId,
+          callback: callback,
+        });
+      },
+      off: function (event, callback) {
+        hotListeners[event] = (hotListeners[event] || []).filter(
+          function (listener) {
+            return listener.callback !== callback;
+          },
+        );
+      },
       updateChunksUrlMap: function () {
         var current_hash = requireModule.currentHash();
         return fetch(
@@ -0,0 +1,144 @@
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+use swc_core::ecma::ast::{CallExpr, Expr, Lit, Module, Str};
+use swc_core::ecma::visit::{VisitMut, VisitMutWith};
+
+use crate::ast::file::{Content, JsContent};
+use crate::ast::utils::{is_dynamic_import, is_remote_or_data};
+use crate::compiler::Context;
+use crate::plugin::{Plugin, PluginLoadParam, PluginTransformJsParam};
+
+pub const LAZY_COMPILATION_PATH: &str = "/__/lazy-compile";
+
+/**
+ * Compile the dynamic imports on demand in dev, `import('./a')` is replaced with
+ * `import('./a?lazy')` and the `?lazy` module is a proxy which doesn't depend on `./a`, so the
+ * subgraph of `./a` is not built until the proxy is executed. The proxy asks the dev server to
+ * compile it, then it's rebuilt as `module.exports = require('./a')` and hot updated, the
+ * pending exports are resolved in the self accepted callback.
+ */
+pub struct LazyCompilationPlugin {
+    activated: Mutex<HashSet<String>>,
+}
+
+impl LazyCompilationPlugin {
+    pub fn new() -> Self {
+        Self {
+            activated: Mutex::new(HashSet::new()),
+        }
+    }
+}
+
+impl Plugin for LazyCompilationPlugin {
+    fn name(&self) -> &str {
+        "lazy_compilation"
+    }
+
+    fn load(&self, param: &PluginLoadParam, _context: &Arc<Context>) -> Result<Option<Content>> {
+        let file = param.file;
+        if !file.has_param("lazy") {
+            return Ok(None);
+        }
+        let path = file.path.to_string_lossy().to_string();
+        let content = if self.activated.lock().unwrap().contains(&path) {
+            format!(
+                "module.exports = require({});",
+                serde_json::to_string(&file.pathname.to_string_lossy())?
+            )
+        } else {
+            format!(
+                r#"var resolve, reject;
+module.exports = new Promise(function (res, rej) {{ resolve = res; reject = rej; }});
+module.hot.accept(function (exports) {{ resolve(exports); }});
+fetch(__mako_require__.publicPath + "{}?module=" + encodeURIComponent({})).then(function (res) {{
+  if (!res.ok) throw new Error("Failed to compile " + {});
+}}).catch(reject);
+"#,
+                LAZY_COMPILATION_PATH.trim_start_matches('/'),
+                serde_json::to_string(&path)?,
+                serde_json::to_string(&file.pathname.to_string_lossy())?,
+            )
+        };
+        Ok(Some(Content::Js(JsContent {
+            content,
+            ..Default::default()
+        })))
+    }
+
+    fn transform_js(
+        &self,
+        param: &PluginTransformJsParam,
+        ast: &mut Module,
+        context: &Arc<Context>,
+    ) -> Result<()> {
+        if context.config.dynamic_import_to_require || param.path.contains("node_modules") {
+            return Ok(());
+        }
+        ast.visit_mut_with(&mut LazyImport {});
+        Ok(())
+    }
+
+    fn before_rebuild(&self, paths: Vec<PathBuf>) -> Result<Vec<PathBuf>> {
+        let mut activated = self.activated.lock().unwrap();
+        for path in &paths {
+            let path = path.to_string_lossy();
+            if path.ends_with("?lazy") {
+                activated.insert(path.to_string());
+            }
+        }
+        Ok(paths)
+    }
+}
+
+struct LazyImport {}
+
+impl VisitMut for LazyImport {
+    fn visit_mut_call_expr(&mut self, call_expr: &mut CallExpr) {
+        if is_dynamic_import(call_expr)
+            && let Some(arg) = call_expr.args.first_mut()
+            && let Expr::Lit(Lit::Str(source)) = &mut *arg.expr
+        {
+            let value = source.value.to_string();
+            if !value.contains('?') && !is_remote_or_data(&value) {
+                let span = source.span;
+                *source = Str::from(format!("{}?lazy", value));
+                source.span = span;
+            }
+        }
+        call_expr.visit_mut_children_with(self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use swc_core::common::GLOBALS;
+    use swc_core::ecma::visit::VisitMutWith;
+
+    use super::LazyImport;
+    use crate::ast::tests::TestUtils;
+
+    #[test]
+    fn test_lazy_import() {
+        assert_eq!(
+            run(
+                r#"import("./a"); import("./b?raw"); import("https://x.com/c.js"); require("./d");"#
+            ),
+            r#"import("./a?lazy");
+import("./b?raw");
+import("https://x.com/c.js");
+require("./d");"#
+        );
+    }
+
+    fn run(js_code: &str) -> String {
+        let mut test_utils = TestUtils::gen_js_ast(js_code);
+        let ast = test_utils.ast.js_mut();
+        GLOBALS.set(&test_utils.context.meta.script.globals, || {
+            ast.ast.visit_mut_with(&mut LazyImport {});
+        });
+        test_utils.js_ast_to_code()
+    }
+}
@@ -0,0 +1,358 @@
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use anyhow::{anyhow, Result};
+use regex::Regex;
+use swc_core::common::DUMMY_SP;
+use swc_core::ecma::ast::{
+    AssignPat, BindingIdent, ComputedPropName, Expr, IdentName, KeyValuePatProp, KeyValueProp, Lit,
+    MemberProp, ObjectPatProp, Pat, Prop, PropName, Str, SuperProp,
+};
+use swc_core::ecma::visit::{Visit, VisitMut, VisitMutWith, VisitWith};
+
+use crate::compiler::Context;
+use crate::config::ManglePropsConfig;
+use crate::generate::chunk_graph::ChunkGraph;
+use crate::module::ModuleAst;
+use crate::module_graph::ModuleGraph;
+use crate::plugin::Plugin;
+
+// the properties which have special meanings at runtime
+const BUILTIN_RESERVED: [&str; 4] = ["__esModule", "__proto__", "constructor", "prototype"];
+
+/**
+ * Mangle the properties matching `minify.mangleProps.regex` across all the modules, so that the
+ * same property gets the same name in every chunk. The names are assigned in the order of the
+ * module ids, and persisted to `minify.mangleProps.nameCache` to keep them stable across builds.
+ */
+pub struct ManglePropsPlugin {
+    config: ManglePropsConfig,
+    regex: Regex,
+    // original name => mangled name
+    names: Mutex<BTreeMap<String, String>>,
+}
+
+impl ManglePropsPlugin {
+    pub fn new(config: ManglePropsConfig, root: &Path) -> Result<Self> {
+        let regex = Regex::new(&config.regex)
+            .map_err(|e| anyhow!("invalid `minify.mangleProps.regex` value: {}", e))?;
+        let names = match &config.name_cache {
+            Some(name_cache) if root.join(name_cache).exists() => {
+                serde_json::from_str(&fs::read_to_string(root.join(name_cache))?)?
+            }
+            _ => BTreeMap::new(),
+        };
+        Ok(Self {
+            config,
+            regex,
+            names: Mutex::new(names),
+        })
+    }
+
+    fn is_reserved(&self, name: &str) -> bool {
+        BUILTIN_RESERVED.contains(&name) || self.config.reserved.iter().any(|r| r == name)
+    }
+}
+
+impl Plugin for ManglePropsPlugin {
+    fn name(&self) -> &str {
+        "mangle_props"
+    }
+
+    fn optimize_chunk(
+        &self,
+        _chunk_graph: &mut ChunkGraph,
+        module_graph: &mut ModuleGraph,
+        context: &Arc<Context>,
+    ) -> Result<()> {
+        let mut modules = module_graph.get_modules_mut();
+        modules.sort_by(|a, b| a.id.id.cmp(&b.id.id));
+
+        let mut collector = PropsCollector::default();
+        for module in modules.iter() {
+            if let Some(info) = &module.info
+                && let ModuleAst::Script(ast) = &info.ast
+            {
+                ast.ast.visit_with(&mut collector);
+            }
+        }
+
+        let mut names = self.names.lock().unwrap();
+        let origin_names = names.clone();
+        let mut used = collector
+            .props
+            .iter()
+            .filter(|prop| !self.regex.is_match(prop) || self.is_reserved(prop))
+            .cloned()
+            .chain(self.config.reserved.iter().cloned())
+            .chain(BUILTIN_RESERVED.iter().map(|name| name.to_string()))
+            .chain(names.values().cloned())
+            .collect::<HashSet<_>>();
+        let mut index = 0;
+        for prop in &collector.props {
+            if !self.regex.is_match(prop) || self.is_reserved(prop) || names.contains_key(prop) {
+                continue;
+            }
+            let name = loop {
+                let name = to_mangled_name(index);
+                index += 1;
+                if !used.contains(&name) {
+                    break name;
+                }
+            };
+            used.insert(name.clone());
+            names.insert(prop.clone(), name);
+        }
+
+        let mut mangler = PropsMangler { names: &names };
+        for module in modules {
+            if let Some(info) = module.info.as_mut()
+                && let ModuleAst::Script(ast) = &mut info.ast
+            {
+                ast.ast.visit_mut_with(&mut mangler);
+            }
+        }
+
+        if let Some(name_cache) = &self.config.name_cache
+            && *names != origin_names
+        {
+            let path = context.root.join(name_cache);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(path, serde_json::to_string_pretty(&*names)?)?;
+        }
+        Ok(())
+    }
+}
+
+// a, b, ..., Z, aa, ba, ...
+fn to_mangled_name(mut index: usize) -> String {
+    const FIRST_CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+    const CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+    let mut name = String::new();
+    name.push(FIRST_CHARS[index % FIRST_CHARS.len()] as char);
+    index /= FIRST_CHARS.len();
+    while index > 0 {
+        index -= 1;
+        name.push(CHARS[index % CHARS.len()] as char);
+        index /= CHARS.len();
+    }
+    name
+}
+
+#[derive(Default)]
+struct PropsCollector {
+    props: BTreeSet<String>,
+}
+
+impl Visit for PropsCollector {
+    fn visit_member_prop(&mut self, n: &MemberProp) {
+        match n {
+            MemberProp::Ident(ident) => {
+                self.props.insert(ident.sym.to_string());
+            }
+            MemberProp::Computed(ComputedPropName { expr, .. }) => {
+                if let Expr::Lit(Lit::Str(str)) = &**expr {
+                    self.props.insert(str.value.to_string());
+                }
+            }
+            MemberProp::PrivateName(_) => {}
+        }
+        n.visit_children_with(self);
+    }
+
+    fn visit_super_prop(&mut self, n: &SuperProp) {
+        if let SuperProp::Ident(ident) = n {
+            self.props.insert(ident.sym.to_string());
+        }
+        n.visit_children_with(self);
+    }
+
+    fn visit_prop_name(&mut self, n: &PropName) {
+        match n {
+            PropName::Ident(ident) => {
+                self.props.insert(ident.sym.to_string());
+            }
+            PropName::Str(str) => {
+                self.props.insert(str.value.to_string());
+            }
+            _ => {}
+        }
+        n.visit_children_with(self);
+    }
+
+    fn visit_prop(&mut self, n: &Prop) {
+        if let Prop::Shorthand(ident) = n {
+            self.props.insert(ident.sym.to_string());
+        }
+        n.visit_children_with(self);
+    }
+
+    fn visit_object_pat_prop(&mut self, n: &ObjectPatProp) {
+        if let ObjectPatProp::Assign(assign) = n {
+            self.props.insert(assign.key.sym.to_string());
+        }
+        n.visit_children_with(self);
+    }
+}
+
+struct PropsMangler<'a> {
+    names: &'a BTreeMap<String, String>,
+}
+
+impl PropsMangler<'_> {
+    fn mangled_key(&self, name: &str) -> Option<PropName> {
+        self.names
+            .get(name)
+            .map(|mangled| PropName::Ident(IdentName::new(mangled.as_str().into(), DUMMY_SP)))
+    }
+}
+
+impl VisitMut for PropsMangler<'_> {
+    fn visit_mut_member_prop(&mut self, n: &mut MemberProp) {
+        match n {
+            MemberProp::Ident(ident) => {
+                if let Some(mangled) = self.names.get(ident.sym.as_str()) {
+                    ident.sym = mangled.as_str().into();
+                }
+            }
+            MemberProp::Computed(ComputedPropName { expr, .. }) => {
+                if let Expr::Lit(Lit::Str(str)) = &mut **expr
+                    && let Some(mangled) = self.names.get(str.value.as_str())
+                {
+                    *str = Str::from(mangled.as_str());
+                }
+            }
+            MemberProp::PrivateName(_) => {}
+        }
+        n.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_super_prop(&mut self, n: &mut SuperProp) {
+        if let SuperProp::Ident(ident) = n
+            && let Some(mangled) = self.names.get(ident.sym.as_str())
+        {
+            ident.sym = mangled.as_str().into();
+        }
+        n.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_prop_name(&mut self, n: &mut PropName) {
+        match n {
+            PropName::Ident(ident) => {
+                if let Some(mangled) = self.names.get(ident.sym.as_str()) {
+                    ident.sym = mangled.as_str().into();
+                }
+            }
+            PropName::Str(str) => {
+                if let Some(mangled) = self.names.get(str.value.as_str()) {
+                    *str = Str::from(mangled.as_str());
+                }
+            }
+            _ => {}
+        }
+        n.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_prop(&mut self, n: &mut Prop) {
+        // `{ _a }` => `{ b: _a }`
+        if let Prop::Shorthand(ident) = n
+            && let Some(key) = self.mangled_key(ident.sym.as_str())
+        {
+            *n = Prop::KeyValue(KeyValueProp {
+                key,
+                value: Box::new(Expr::Ident(ident.clone())),
+            });
+            return;
+        }
+        n.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_object_pat_prop(&mut self, n: &mut ObjectPatProp) {
+        // `{ _a = 1 } = obj` => `{ b: _a = 1 } = obj`
+        if let ObjectPatProp::Assign(assign) = n
+            && let Some(key) = self.mangled_key(assign.key.sym.as_str())
+        {
+            let binding = Pat::Ident(BindingIdent::from(assign.key.id.clone()));
+            let value = match assign.value.take() {
+                Some(default) => Pat::Assign(AssignPat {
+                    span: assign.span,
+                    left: Box::new(binding),
+                    right: default,
+                }),
+                None => binding,
+            };
+            *n = ObjectPatProp::KeyValue(KeyValuePatProp {
+                key,
+                value: Box::new(value),
+            });
+        }
+        n.visit_mut_children_with(self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use swc_core::common::GLOBALS;
+    use swc_core::ecma::visit::{VisitMutWith, VisitWith};
+
+    use super::{to_mangled_name, PropsCollector, PropsMangler};
+    use crate::ast::tests::TestUtils;
+
+    #[test]
+    fn test_to_mangled_name() {
+        assert_eq!(to_mangled_name(0), "a");
+        assert_eq!(to_mangled_name(51), "Z");
+        assert_eq!(to_mangled_name(52), "aa");
+        assert_eq!(to_mangled_name(53), "ba");
+    }
+
+    #[test]
+    fn test_mangle_props() {
+        let mut test_utils = TestUtils::gen_js_ast(
+            r#"
+const obj = { _foo: 1, "_bar": 2, _baz };
+obj._foo = obj["_bar"] + obj.bar;
+const { _foo, _bar: bar, _baz: baz = 1 } = obj;
+class A { _foo() { return super._foo; } }
+            "#,
+        );
+        let ast = test_utils.ast.js_mut();
+        let mut collector = PropsCollector::default();
+        ast.ast.visit_with(&mut collector);
+        assert_eq!(
+            collector.props.into_iter().collect::<Vec<_>>(),
+            vec!["_bar", "_baz", "_foo", "bar"]
+        );
+
+        let names = BTreeMap::from([
+            ("_foo".to_string(), "a".to_string()),
+            ("_bar".to_string(), "b".to_string()),
+            ("_baz".to_string(), "c".to_string()),
+        ]);
+        GLOBALS.set(&test_utils.context.meta.script.globals, || {
+            ast.ast.visit_mut_with(&mut PropsMangler { names: &names });
+        });
+        let code = test_utils.js_ast_to_code();
+        assert_eq!(
+            code,
+            r#"const obj = {
+    a: 1,
+    "b": 2,
+    c: _baz
+};
+obj.a = obj["b"] + obj.bar;
+const { a: _foo, b: bar, c: baz = 1 } = obj;
+class A {
+    a() {
+        return super.a;
+    }
+}"#
+        );
+    }
+}
@@ -4,7 +4,7 @@ use std::sync::Arc;
 
 use anyhow::Result;
 use regex::Regex;
-use serde_json;
+use serde_json::{json, Value};
 
 use crate::compiler::Context;
 use crate::plugin::Plugin;
@@ -24,15 +24,37 @@ impl Plugin for ManifestPlugin {
     fn build_success(&self, _stats: &StatsJsonMap, context: &Arc<Context>) -> Result<()> {
         if let Some(manifest_config) = &context.config.manifest {
             let assets = &context.stats_info.get_assets();
-            let mut manifest: BTreeMap<String, String> = BTreeMap::new();
+            let mut manifest: BTreeMap<String, Value> = BTreeMap::new();
             let file_name = manifest_config.file_name.clone();
             let base_path = manifest_config.base_path.clone();
 
             let path = normalize_path(base_path);
+            // the files written by CompressPlugin, with their transfer sizes
+            let compressed_extensions = context
+                .config
+                .output
+                .compress
+                .as_ref()
+                .map(|compress| {
+                    compress
+                        .algorithms
+                        .iter()
+                        .map(|algorithm| format!(".{}", algorithm.extension()))
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default();
 
             for asset in assets {
                 let key = format!("{}{}", path, remove_key_hash(&asset.hashname));
-                manifest.insert(key, asset.hashname.clone());
+                let value = if compressed_extensions
+                    .iter()
+                    .any(|extension| asset.hashname.ends_with(extension))
+                {
+                    json!({ "file": asset.hashname, "size": asset.size })
+                } else {
+                    json!(asset.hashname)
+                };
+                manifest.insert(key, value);
             }
 
             let manifest_json = serde_json::to_string_pretty(&manifest)?;
@@ -1,10 +1,11 @@
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, RwLock};
 
 use anyhow::Result;
 use constants::{FEDERATION_REMOTE_MODULE_PREFIX, FEDERATION_REMOTE_REFERENCE_PREFIX};
 use provide_shared::SharedDependency;
+use util::PkgJsonCache;
 
 use crate::ast::file::Content;
 use crate::build::analyze_deps::ResolvedDep;
@@ -23,11 +24,13 @@ mod container_reference;
 mod manifest;
 mod provide_for_consume;
 mod provide_shared;
+mod types;
 mod util;
 
 pub struct ModuleFederationPlugin {
     pub config: ModuleFederationConfig,
     shared_dependency_map: RwLock<HashMap<String, SharedDependency>>,
+    pkg_json_cache: PkgJsonCache,
 }
 
 impl ModuleFederationPlugin {
@@ -35,6 +38,7 @@ impl ModuleFederationPlugin {
         Self {
             config,
             shared_dependency_map: RwLock::new(HashMap::new()),
+            pkg_json_cache: RwLock::new(HashMap::new()),
         }
     }
 }
@@ -99,7 +103,12 @@ impl Plugin for ModuleFederationPlugin {
         }
     }
 
+    fn build_start(&self, context: &Arc<Context>) -> Result<()> {
+        self.consume_types(context)
+    }
+
     fn generate_end(&self, params: &PluginGenerateEndParams, context: &Arc<Context>) -> Result<()> {
+        self.generate_types(context)?;
         if self.config.manifest {
             self.generate_federation_manifest(context, params)?;
         }
@@ -111,12 +120,23 @@ impl Plugin for ModuleFederationPlugin {
         Ok(())
     }
 
+    fn watch_changes(&self, id: &str, _event: &str, _context: &Arc<Context>) -> Result<()> {
+        let path = PathBuf::from(id);
+        if path.ends_with("package.json")
+            && let Some(pkg_root) = path.parent()
+        {
+            self.pkg_json_cache.write().unwrap().remove(pkg_root);
+        }
+        Ok(())
+    }
+
     fn optimize_chunk(
         &self,
         chunk_graph: &mut ChunkGraph,
         module_graph: &mut ModuleGraph,
         _context: &Arc<Context>,
     ) -> Result<()> {
+        self.bundle_eager_shared_to_entries(chunk_graph, module_graph);
         self.connect_provide_shared_to_container(chunk_graph, module_graph);
         Ok(())
     }
@@ -4,6 +4,8 @@ use std::sync::Arc;
 use pathdiff::diff_paths;
 
 use super::constants::FEDERATION_SHARED_REFERENCE_PREFIX;
+use super::provide_shared::SharedConfig;
+use super::util::infer_required_version;
 use super::ModuleFederationPlugin;
 use crate::build::analyze_deps::{AnalyzeDepsResult, ResolvedDep};
 use crate::compiler::Context;
@@ -16,7 +18,6 @@ impl ModuleFederationPlugin {
     pub(super) fn init_federation_runtime_consume(&self, context: &Context) -> String {
         let module_graph = context.module_graph.read().unwrap();
         let chunk_graph = context.chunk_graph.read().unwrap();
-        let share_dependencies = self.shared_dependency_map.read().unwrap();
 
         let mut initial_consumes = Vec::<String>::new();
 
@@ -66,44 +67,59 @@ impl ModuleFederationPlugin {
             .iter()
             .map(|s| {
                 let resolved_resource  = s.info.as_ref().unwrap().resolved_resource.as_ref().unwrap();
-                let module_full_path = match resolved_resource {
-                    ResolverResource::Shared(info) =>
-                        info.deps.resolved_deps[0].resolver_resource.get_resolved_path(),
-                    _ =>
-                        panic!("{} is not a shared module", resolved_resource.get_resolved_path())
+                let ResolverResource::Shared(info) = resolved_resource else {
+                    panic!("{} is not a shared module", resolved_resource.get_resolved_path())
                 };
-                let module_relative_path =
-                    diff_paths(&module_full_path, &context.root)
-                    .unwrap()
-                    .to_string_lossy()
-                    .to_string();
-
-                let module_in_chunk = chunk_graph.get_chunk_for_module(&module_full_path.as_str().into()).unwrap();
-
-                let getter = match &module_in_chunk.chunk_type {
-                    ChunkType::Entry(_, _, _) | ChunkType::Worker(_) => {
-                            format!(r#"() => (() => requireModule("{module_relative_path}"))"#
-                        )
-                    },
-                    ChunkType::Async
-                    | ChunkType::Sync
-                        => {
-                        let dependency_chunks = chunk_graph.sync_dependencies_chunk(&module_in_chunk.id);
-                        format!(
-                            r#"() => (Promise.all([{}]).then(() => requireModule("{module_relative_path}")))"#,
-                            [
-                                dependency_chunks,
-                                vec![module_in_chunk.id.clone()]
-                            ]
-                            .concat().iter()
-                            .map(|e| format!(r#"requireModule.ensure("{}")"#, e.id))
-                            .collect::<Vec<String>>().join(",")
-                        )
+
+                let getter = match info.deps.resolved_deps.first() {
+                    Some(dep) => {
+                        let module_full_path = dep.resolver_resource.get_resolved_path();
+                        let module_relative_path =
+                            diff_paths(&module_full_path, &context.root)
+                            .unwrap()
+                            .to_string_lossy()
+                            .to_string();
+
+                        let module_in_chunk = chunk_graph.get_chunk_for_module(&module_full_path.as_str().into()).unwrap();
+
+                        match &module_in_chunk.chunk_type {
+                            ChunkType::Entry(_, _, _) | ChunkType::Worker(_) => {
+                                    format!(r#"() => (() => requireModule("{module_relative_path}"))"#
+                                )
+                            },
+                            ChunkType::Async
+                            | ChunkType::Sync
+                                => {
+                                let dependency_chunks = chunk_graph.sync_dependencies_chunk(&module_in_chunk.id);
+                                format!(
+                                    r#"() => (Promise.all([{}]).then(() => requireModule("{module_relative_path}")))"#,
+                                    [
+                                        dependency_chunks,
+                                        vec![module_in_chunk.id.clone()]
+                                    ]
+                                    .concat().iter()
+                                    .map(|e| format!(r#"requireModule.ensure("{}")"#, e.id))
+                                    .collect::<Vec<String>>().join(",")
+                                )
+                            },
+                            ChunkType::Runtime  =>  panic!("mf shared dependency should not be bundled to runtime chunk")
+                        }
                     },
-                    ChunkType::Runtime  =>  panic!("mf shared dependency should not be bundled to runtime chunk")
+                    // import: false, the module must be provided by the share scope
+                    None => format!(
+                        r#"() => {{ throw new Error("Shared module {name} is not available in share scope \"{scope}\" and has no fallback") }}"#,
+                        name = info.name,
+                        scope = info.share_scope
+                    ),
                 };
 
-                let share_dependency = share_dependencies.get(&s.id.id).unwrap();
+                let share_config = SharedConfig {
+                    fixed_dependencies: false,
+                    eager: info.eager,
+                    strict_version: info.strict_version,
+                    singleton: info.singletion,
+                    required_version: info.required_version.clone(),
+                };
                 format!(
                     r#""{shared_consume_id}": {{
     getter: {getter},
@@ -111,8 +127,8 @@ impl ModuleFederationPlugin {
     shareKey: "{share_key}"
                     }}"#,
                     shared_consume_id = s.id.id,
-                    share_config = serde_json::to_string(&share_dependency.shared_config).unwrap(),
-                    share_key = share_dependency.share_key
+                    share_config = serde_json::to_string(&share_config).unwrap(),
+                    share_key = info.name
 
                 )
             })
@@ -165,49 +181,64 @@ impl ModuleFederationPlugin {
         if let Some(shared) = self.config.shared.as_ref()
             && let Some(shared_info) = shared.get(source)
         {
-            let resolver = if params.dep.resolve_type == ResolveType::Require {
-                context.resolvers.get(&ResolverType::Cjs)
-            } else if params.dep.resolve_type == ResolveType::Css {
-                context.resolvers.get(&ResolverType::Css)
+            // consume only shares have no fallback module to resolve
+            let resolver_resource = if shared_info.import {
+                let resolver = if params.dep.resolve_type == ResolveType::Require {
+                    context.resolvers.get(&ResolverType::Cjs)
+                } else if params.dep.resolve_type == ResolveType::Css {
+                    context.resolvers.get(&ResolverType::Css)
+                } else {
+                    context.resolvers.get(&ResolverType::Esm)
+                }
+                .unwrap();
+                let resolver_resource =
+                    do_resolve(importer, source, resolver, Some(&context.config.externals))?;
+                if !matches!(resolver_resource, ResolverResource::Resolved(_)) {
+                    return Ok(None);
+                }
+                Some(resolver_resource)
             } else {
-                context.resolvers.get(&ResolverType::Esm)
-            }
-            .unwrap();
-            let resolver_resource =
-                do_resolve(importer, source, resolver, Some(&context.config.externals))?;
-            if let ResolverResource::Resolved(_) = resolver_resource {
-                let config_joined_str = format!(
-                    "{}|{}|{}|{}|{}|{}|{}",
+                None
+            };
+            let required_version = shared_info
+                .required_version
+                .clone()
+                .or_else(|| infer_required_version(source, importer, &self.pkg_json_cache));
+            let config_joined_str = format!(
+                "{}|{}|{}|{}|{}|{}|{}",
+                shared_info.shared_scope,
+                source,
+                required_version.as_ref().map_or("", |v| v.as_str()),
+                shared_info.strict_version,
+                resolver_resource
+                    .as_ref()
+                    .map_or(String::new(), |r| r.get_resolved_path()),
+                shared_info.singleton,
+                shared_info.eager
+            );
+            let hash = md5_hash(&config_joined_str, 4);
+            return Ok(Some(ResolverResource::Shared(ConsumeSharedInfo {
+                name: source.to_string(),
+                version: resolver_resource.as_ref().map_or(String::new(), |r| {
+                    r.get_pkg_info().unwrap().version.unwrap_or_default()
+                }),
+                share_scope: shared_info.shared_scope.clone(),
+                eager: shared_info.eager,
+                singletion: shared_info.singleton,
+                required_version,
+                strict_version: shared_info.strict_version,
+                module_id: format!(
+                    "{}{}/{}/{}?{}",
+                    FEDERATION_SHARED_REFERENCE_PREFIX,
                     shared_info.shared_scope,
                     source,
-                    shared_info
-                        .required_version
-                        .as_ref()
-                        .map_or("", |v| v.as_str()),
-                    shared_info.strict_version,
-                    resolver_resource.get_resolved_path(),
-                    shared_info.singleton,
-                    shared_info.eager
-                );
-                let hash = md5_hash(&config_joined_str, 4);
-                return Ok(Some(ResolverResource::Shared(ConsumeSharedInfo {
-                    name: source.to_string(),
-                    version: resolver_resource.get_pkg_info().unwrap().version.unwrap(),
-                    share_scope: shared_info.shared_scope.clone(),
-                    eager: shared_info.eager,
-                    singletion: shared_info.singleton,
-                    required_version: shared_info.required_version.clone(),
-                    strict_version: shared_info.strict_version,
-                    module_id: format!(
-                        "{}{}/{}/{}?{}",
-                        FEDERATION_SHARED_REFERENCE_PREFIX,
-                        shared_info.shared_scope,
-                        source,
-                        source,
-                        hash
-                    ),
-                    deps: AnalyzeDepsResult {
-                        resolved_deps: vec![ResolvedDep {
+                    source,
+                    hash
+                ),
+                deps: AnalyzeDepsResult {
+                    resolved_deps: resolver_resource
+                        .into_iter()
+                        .map(|resolver_resource| ResolvedDep {
                             resolver_resource,
                             dependency: Dependency {
                                 source: params.dep.source.clone(),
@@ -220,11 +251,11 @@ impl ModuleFederationPlugin {
                                 order: params.dep.order,
                                 span: params.dep.span,
                             },
-                        }],
-                        ..Default::default()
-                    },
-                })));
-            }
+                        })
+                        .collect(),
+                    ..Default::default()
+                },
+            })));
         }
         Ok(None)
     }
@@ -138,6 +138,14 @@ impl ModuleFederationPlugin {
                         path: "".to_string(),
                         r#type: "global".to_string(),
                     }),
+                    types: self
+                        .types_config()
+                        .map_or(ManifestMetaTypes::default(), |dts| ManifestMetaTypes {
+                            path: "".to_string(),
+                            name: "".to_string(),
+                            zip: format!("{}.zip", dts.types_folder),
+                            api: format!("{}.d.ts", dts.types_folder),
+                        }),
                     ..Default::default()
                 }
             },
@@ -1,6 +1,9 @@
+use std::collections::HashSet;
+
 use super::ModuleFederationPlugin;
-use crate::generate::chunk::ChunkType;
+use crate::generate::chunk::{ChunkId, ChunkType};
 use crate::generate::chunk_graph::ChunkGraph;
+use crate::module::{ModuleId, ResolveType};
 use crate::module_graph::ModuleGraph;
 
 impl ModuleFederationPlugin {
@@ -25,12 +28,14 @@ impl ModuleFederationPlugin {
 
         let provide_shared_in_chunks = provide_shared_map
             .iter()
-            .map(|m| {
-                chunk_graph
-                    .get_chunk_for_module(&m.0.as_str().into())
-                    .unwrap()
-                    .id
-                    .clone()
+            .filter_map(|m| {
+                let chunk = chunk_graph.get_chunk_for_module(&m.0.as_str().into())?;
+                // eager shared modules are already bundled in the entry chunks
+                if matches!(chunk.chunk_type, ChunkType::Entry(_, _, _)) {
+                    None
+                } else {
+                    Some(chunk.id.clone())
+                }
             })
             .collect::<Vec<_>>();
 
@@ -40,4 +45,83 @@ impl ModuleFederationPlugin {
             });
         });
     }
+
+    // eager shared modules and their sync dependencies are moved into the entry
+    // chunks, so that they can be provided and consumed without async boundary
+    pub(super) fn bundle_eager_shared_to_entries(
+        &self,
+        chunk_graph: &mut ChunkGraph,
+        module_graph: &ModuleGraph,
+    ) {
+        let provide_shared_map = self.shared_dependency_map.read().unwrap();
+        let mut stack = provide_shared_map
+            .values()
+            .filter(|m| m.shared_config.eager)
+            .map(|m| ModuleId::from(m.file_path.as_str()))
+            .collect::<Vec<_>>();
+        stack.sort_by(|a, b| b.id.cmp(&a.id));
+        if stack.is_empty() {
+            return;
+        }
+
+        let mut eager_modules = vec![];
+        let mut visited = HashSet::new();
+        while let Some(module_id) = stack.pop() {
+            if !visited.insert(module_id.clone()) {
+                continue;
+            }
+            module_graph
+                .get_dependencies(&module_id)
+                .into_iter()
+                .filter(|(dep_id, dep)| {
+                    !matches!(
+                        dep.resolve_type,
+                        ResolveType::DynamicImport(_) | ResolveType::Worker(_)
+                    ) && module_graph
+                        .get_module(dep_id)
+                        .is_some_and(|m| !m.is_consume_share() && !m.is_remote())
+                })
+                .for_each(|(dep_id, _)| stack.push(dep_id.clone()));
+            eager_modules.push(module_id);
+        }
+
+        let entry_chunks = chunk_graph
+            .get_chunks()
+            .into_iter()
+            .filter(|c| matches!(c.chunk_type, ChunkType::Entry(_, _, false)))
+            .map(|c| c.id.clone())
+            .collect::<Vec<ChunkId>>();
+
+        for module_id in eager_modules.iter() {
+            let in_initial_chunk = chunk_graph.get_chunks().into_iter().any(|c| {
+                matches!(c.chunk_type, ChunkType::Entry(_, _, _)) && c.has_module(module_id)
+            });
+            if in_initial_chunk {
+                continue;
+            }
+            chunk_graph.mut_chunks().into_iter().for_each(|c| {
+                if matches!(c.chunk_type, ChunkType::Async | ChunkType::Sync)
+                    && c.root_module() != Some(module_id)
+                {
+                    c.remove_module(module_id);
+                }
+            });
+            entry_chunks.iter().for_each(|ec| {
+                chunk_graph
+                    .mut_chunk(ec)
+                    .unwrap()
+                    .add_module(module_id.clone());
+            });
+        }
+
+        let empty_chunks = chunk_graph
+            .get_chunks()
+            .into_iter()
+            .filter(|c| matches!(c.chunk_type, ChunkType::Sync) && c.modules.is_empty())
+            .map(|c| c.id.clone())
+            .collect::<Vec<_>>();
+        empty_chunks
+            .iter()
+            .for_each(|c| chunk_graph.remove_chunk(c));
+    }
 }
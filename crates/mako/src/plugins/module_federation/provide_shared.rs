@@ -7,6 +7,7 @@ use crate::build::analyze_deps::ResolvedDep;
 use crate::compiler::Context;
 use crate::generate::chunk::ChunkType;
 use crate::module::ModuleId;
+use crate::resolve::ResolverResource;
 
 impl ModuleFederationPlugin {
     pub(super) fn init_federation_runtime_sharing(&self, context: &Context) -> String {
@@ -117,7 +118,10 @@ impl ModuleFederationPlugin {
                         eager: shared_info.eager,
                         strict_version: shared_info.strict_version,
                         singleton: shared_info.singleton,
-                        required_version: shared_info.required_version.clone(),
+                        required_version: match &resolved_dep.resolver_resource {
+                            ResolverResource::Shared(info) => info.required_version.clone(),
+                            _ => shared_info.required_version.clone(),
+                        },
                         // FIXME: hard code now
                         fixed_dependencies: false,
                     },
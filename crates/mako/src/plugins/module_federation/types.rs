@@ -0,0 +1,271 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::io::{Cursor, Read, Write};
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use colored::Colorize;
+use pathdiff::diff_paths;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, DateTime, ZipArchive, ZipWriter};
+
+use super::ModuleFederationPlugin;
+use crate::ast::dts::{is_ts_file, strip_dts_ext};
+use crate::compiler::Context;
+use crate::config::module_federation::DtsConfig;
+use crate::plugins::declaration::emit_declarations;
+
+const COMPILED_TYPES_DIR: &str = "compiled-types";
+
+impl ModuleFederationPlugin {
+    pub(super) fn generate_types(&self, context: &Arc<Context>) -> Result<()> {
+        let (Some(dts), Some(exposes)) = (&self.config.dts, &self.config.exposes) else {
+            return Ok(());
+        };
+        if !dts.generate_types || exposes.is_empty() {
+            return Ok(());
+        }
+
+        let mut files: BTreeMap<String, Vec<u8>> = BTreeMap::new();
+        let mut default_exports = HashSet::new();
+        let mut remote_keys = vec![];
+        for (expose, module) in exposes {
+            let path = context.root.join(module).canonicalize()?;
+            if !is_ts_file(&path) {
+                continue;
+            }
+            default_exports.extend(emit_declarations(
+                vec![path.clone()],
+                context,
+                |file| PathBuf::from(compiled_types_path(file, &context.root)),
+                |path, code| {
+                    files.insert(path.to_string_lossy().to_string(), code.as_bytes().to_vec());
+                    Ok(())
+                },
+            )?);
+
+            let name = expose.trim_start_matches("./");
+            let name = if name.is_empty() || name == "." {
+                "index"
+            } else {
+                name
+            };
+            let source = format!(
+                "{}{}/{}",
+                if name.contains('/') {
+                    "../".repeat(name.matches('/').count())
+                } else {
+                    "./".to_string()
+                },
+                COMPILED_TYPES_DIR,
+                strip_dts_ext(&relative_path(&path, &context.root)),
+            );
+            let mut content = format!("export * from '{}';\n", source);
+            if default_exports.contains(&path) {
+                content.push_str(&format!("export {{ default }} from '{}';\n", source));
+            }
+            files.insert(format!("{}.d.ts", name), content.into_bytes());
+            remote_keys.push(format!("{}/{}", self.config.name, name));
+        }
+        remote_keys.sort();
+
+        let output_path = &context.config.output.path;
+        fs::create_dir_all(output_path)?;
+        let files = files.into_iter().collect::<Vec<_>>();
+        fs::write(
+            output_path.join(format!("{}.zip", dts.types_folder)),
+            write_zip(&files)?,
+        )?;
+        fs::write(
+            output_path.join(format!("{}.d.ts", dts.types_folder)),
+            render_remote_keys(&remote_keys),
+        )?;
+        Ok(())
+    }
+
+    pub(super) fn consume_types(&self, context: &Arc<Context>) -> Result<()> {
+        let Some(dts) = &self.config.dts else {
+            return Ok(());
+        };
+        if dts.consume_types.is_empty() {
+            return Ok(());
+        }
+
+        let types_dir = context.root.join(&dts.types_folder);
+        let mut remote_keys = vec![];
+        for (remote, source) in &dts.consume_types {
+            if !self
+                .config
+                .remotes
+                .as_ref()
+                .is_some_and(|r| r.contains_key(remote))
+            {
+                println!(
+                    "{}: Types of {} are configured but it's not in remotes",
+                    "warning".to_string().yellow(),
+                    remote,
+                );
+            }
+            let files = read_types_source(&context.root.join(source)).map_err(|e| {
+                anyhow!(
+                    "Failed to read types of remote {} from {}: {}",
+                    remote,
+                    source,
+                    e
+                )
+            })?;
+            let remote_dir = types_dir.join(remote);
+            if remote_dir.exists() {
+                fs::remove_dir_all(&remote_dir)?;
+            }
+            for (name, content) in files {
+                // skip unsafe entries which may escape the types folder
+                if Path::new(&name)
+                    .components()
+                    .any(|c| !matches!(c, Component::Normal(_)))
+                {
+                    continue;
+                }
+                if name.ends_with(".d.ts") && !name.starts_with(COMPILED_TYPES_DIR) {
+                    remote_keys.push(format!("{}/{}", remote, strip_dts_ext(&name)));
+                }
+                let path = remote_dir.join(&name);
+                fs::create_dir_all(path.parent().unwrap())?;
+                fs::write(path, content)?;
+            }
+        }
+        remote_keys.sort();
+        fs::write(
+            types_dir.join("index.d.ts"),
+            render_remote_keys(&remote_keys),
+        )?;
+        Ok(())
+    }
+
+    pub(super) fn types_config(&self) -> Option<&DtsConfig> {
+        self.config
+            .dts
+            .as_ref()
+            .filter(|dts| dts.generate_types && self.config.exposes.is_some())
+    }
+}
+
+fn read_types_source(path: &Path) -> Result<Vec<(String, Vec<u8>)>> {
+    if path.is_dir() {
+        let mut files = vec![];
+        read_dir_files(path, path, &mut files)?;
+        files.sort();
+        Ok(files)
+    } else {
+        read_zip(&fs::read(path)?)
+    }
+}
+
+fn write_zip(files: &[(String, Vec<u8>)]) -> Result<Vec<u8>> {
+    let mut zip = ZipWriter::new(Cursor::new(vec![]));
+    for (name, content) in files {
+        // a fixed modified time keeps the archive reproducible
+        let options = SimpleFileOptions::default()
+            .compression_method(CompressionMethod::Deflated)
+            .last_modified_time(DateTime::default())
+            .large_file(content.len() as u64 >= u32::MAX as u64);
+        zip.start_file(name, options)?;
+        zip.write_all(content)?;
+    }
+    Ok(zip.finish()?.into_inner())
+}
+
+fn read_zip(data: &[u8]) -> Result<Vec<(String, Vec<u8>)>> {
+    let mut zip = ZipArchive::new(Cursor::new(data))?;
+    let mut files = vec![];
+    for i in 0..zip.len() {
+        let mut file = zip.by_index(i)?;
+        if file.is_dir() {
+            continue;
+        }
+        let mut content = Vec::with_capacity(file.size() as usize);
+        file.read_to_end(&mut content)?;
+        files.push((file.name().to_string(), content));
+    }
+    Ok(files)
+}
+
+fn read_dir_files(root: &Path, dir: &Path, files: &mut Vec<(String, Vec<u8>)>) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            read_dir_files(root, &path, files)?;
+        } else {
+            files.push((relative_path(&path, root), fs::read(&path)?));
+        }
+    }
+    Ok(())
+}
+
+fn render_remote_keys(remote_keys: &[String]) -> String {
+    if remote_keys.is_empty() {
+        return "export type RemoteKeys = never;\ntype PackageType<T> = any;\n".to_string();
+    }
+    let keys = remote_keys
+        .iter()
+        .map(|k| format!("'{}'", k))
+        .collect::<Vec<_>>();
+    let package_types = remote_keys
+        .iter()
+        .map(|k| format!("T extends '{}' ? typeof import('{}') :", k, k))
+        .collect::<Vec<_>>();
+    format!(
+        "export type RemoteKeys = {};\ntype PackageType<T> = {} any;\n",
+        keys.join(" | "),
+        package_types.join(" ")
+    )
+}
+
+fn compiled_types_path(path: &Path, root: &Path) -> String {
+    format!(
+        "{}/{}.d.ts",
+        COMPILED_TYPES_DIR,
+        strip_dts_ext(&relative_path(path, root))
+    )
+}
+
+fn relative_path(path: &Path, root: &Path) -> String {
+    diff_paths(path, root)
+        .unwrap_or(path.to_path_buf())
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{read_zip, render_remote_keys, strip_dts_ext, write_zip};
+
+    #[test]
+    fn test_render_remote_keys() {
+        assert_eq!(
+            render_remote_keys(&["app1/App".to_string(), "app1/utils".to_string()]),
+            r#"export type RemoteKeys = 'app1/App' | 'app1/utils';
+type PackageType<T> = T extends 'app1/App' ? typeof import('app1/App') : T extends 'app1/utils' ? typeof import('app1/utils') : any;
+"#
+        );
+        assert_eq!(strip_dts_ext("src/App.tsx"), "src/App");
+        assert_eq!(strip_dts_ext("App.d.ts"), "App");
+    }
+
+    #[test]
+    fn test_zip_roundtrip() {
+        let files = vec![
+            (
+                "App.d.ts".to_string(),
+                b"export * from './src/App';".to_vec(),
+            ),
+            ("compiled-types/src/App.d.ts".to_string(), vec![]),
+        ];
+        let zip = write_zip(&files).unwrap();
+        assert_eq!(read_zip(&zip).unwrap(), files);
+        assert!(read_zip(b"not a zip").is_err());
+    }
+}
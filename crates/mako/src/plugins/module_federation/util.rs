@@ -1,5 +1,11 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
 use anyhow::{anyhow, Result};
 use serde::{Serialize, Serializer};
+use serde_json::Value;
 
 pub(super) fn parse_remote(remote: &str) -> Result<(String, String)> {
     let (left, right) = remote
@@ -21,3 +27,132 @@ pub(super) fn serialize_none_to_false<T: Serialize, S: Serializer>(
         None => s.serialize_bool(false),
     }
 }
+
+// package root => parsed package.json, none if it's not a valid json
+pub(super) type PkgJsonCache = RwLock<HashMap<PathBuf, Option<Arc<Value>>>>;
+
+// same as webpack, the required version is read from the nearest package.json of the importer
+pub(super) fn infer_required_version(
+    source: &str,
+    importer: &str,
+    cache: &PkgJsonCache,
+) -> Option<String> {
+    let pkg_root = Path::new(importer)
+        .ancestors()
+        .skip(1)
+        .find(|dir| dir.join("package.json").exists())?;
+    let cached = cache.read().unwrap().get(pkg_root).cloned();
+    let pkg_json = match cached {
+        Some(pkg_json) => pkg_json,
+        None => {
+            let pkg_json = fs::read_to_string(pkg_root.join("package.json"))
+                .ok()
+                .and_then(|content| serde_json::from_str(&content).ok())
+                .map(Arc::new);
+            cache
+                .write()
+                .unwrap()
+                .insert(pkg_root.to_path_buf(), pkg_json.clone());
+            pkg_json
+        }
+    }?;
+    required_version_from_pkg_json(source, &pkg_json)
+}
+
+fn required_version_from_pkg_json(source: &str, pkg_json: &Value) -> Option<String> {
+    let name = package_name(source);
+    if pkg_json.get("name").and_then(|n| n.as_str()) == Some(name) {
+        return None;
+    }
+    [
+        "optionalDependencies",
+        "dependencies",
+        "peerDependencies",
+        "devDependencies",
+    ]
+    .iter()
+    .find_map(|field| pkg_json.get(field)?.get(name)?.as_str())
+    // skip protocols like workspace:*, file:, git urls and so on
+    .filter(|version| !version.contains(':') && !version.contains('/'))
+    .map(|version| version.to_string())
+}
+
+fn package_name(source: &str) -> &str {
+    let mut parts = source.splitn(3, '/');
+    match (parts.next(), parts.next()) {
+        (Some(scope), Some(name)) if scope.starts_with('@') => {
+            &source[..scope.len() + name.len() + 1]
+        }
+        (Some(name), _) => name,
+        _ => source,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_required_version_from_pkg_json() {
+        let pkg_json = json!({
+            "name": "app",
+            "dependencies": { "react": "^18.2.0", "@scope/ui": "~1.0.0", "lib": "workspace:*" },
+            "peerDependencies": { "react-dom": ">=18" }
+        });
+        assert_eq!(
+            required_version_from_pkg_json("react", &pkg_json),
+            Some("^18.2.0".to_string())
+        );
+        assert_eq!(
+            required_version_from_pkg_json("react/jsx-runtime", &pkg_json),
+            Some("^18.2.0".to_string())
+        );
+        assert_eq!(
+            required_version_from_pkg_json("@scope/ui/button", &pkg_json),
+            Some("~1.0.0".to_string())
+        );
+        assert_eq!(
+            required_version_from_pkg_json("react-dom", &pkg_json),
+            Some(">=18".to_string())
+        );
+        assert_eq!(required_version_from_pkg_json("lib", &pkg_json), None);
+        assert_eq!(required_version_from_pkg_json("app", &pkg_json), None);
+        assert_eq!(required_version_from_pkg_json("vue", &pkg_json), None);
+    }
+
+    #[test]
+    fn test_infer_required_version_cached() {
+        let root = std::env::temp_dir().join("mako-mf-infer-required-version");
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("src")).unwrap();
+        let write_pkg_json = |version: &str| {
+            let pkg_json = json!({ "name": "app", "dependencies": { "react": version } });
+            fs::write(root.join("package.json"), pkg_json.to_string()).unwrap();
+        };
+        let importer = root.join("src/index.js").to_string_lossy().to_string();
+        let cache = PkgJsonCache::default();
+
+        write_pkg_json("^18.2.0");
+        assert_eq!(
+            infer_required_version("react", &importer, &cache),
+            Some("^18.2.0".to_string())
+        );
+        assert!(cache.read().unwrap().contains_key(&root));
+
+        // the package.json is parsed once per package root
+        write_pkg_json("^17.0.0");
+        assert_eq!(
+            infer_required_version("react", &importer, &cache),
+            Some("^18.2.0".to_string())
+        );
+        cache.write().unwrap().remove(&root);
+        assert_eq!(
+            infer_required_version("react", &importer, &cache),
+            Some("^17.0.0".to_string())
+        );
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
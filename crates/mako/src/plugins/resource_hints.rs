@@ -0,0 +1,113 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use crate::compiler::Context;
+use crate::generate::chunk::ChunkType;
+use crate::module::{generate_module_id, ResolveType};
+use crate::plugin::Plugin;
+
+/**
+ * Emit `<link rel="prefetch">` and `<link rel="preload">` for the async chunks imported with
+ * `webpackPrefetch` and `webpackPreload` magic comments, the hints are added when the parent
+ * chunk is loaded, or when the entry is executed for entry chunks. With `autoPrefetch`, all
+ * the async chunks imported by the entry chunks are prefetched.
+ */
+pub struct ResourceHintsPlugin {}
+
+impl Plugin for ResourceHintsPlugin {
+    fn name(&self) -> &str {
+        "resource_hints"
+    }
+
+    fn runtime_plugins(&self, context: &Arc<Context>) -> Result<Vec<String>> {
+        let resource_hints = collect_resource_hints(context);
+        if resource_hints.is_empty() {
+            return Ok(vec![]);
+        }
+        let cross_origin_loading = context
+            .config
+            .output
+            .cross_origin_loading
+            .as_ref()
+            .map(|c| c.to_string());
+        Ok(vec![include_str!("resource_hints/resource_hints.js")
+            .replace(
+                "__RESOURCE_HINTS__",
+                &serde_json::to_string(&resource_hints)?,
+            )
+            .replace(
+                "__ENTRY_CHUNK_IDS__",
+                &serde_json::to_string(&collect_entry_chunk_ids(context))?,
+            )
+            .replace(
+                "__CROSS_ORIGIN_LOADING__",
+                &serde_json::to_string(&cross_origin_loading)?,
+            )])
+    }
+}
+
+// entry module id => entry chunk id, the runtime only knows the entry module id, and the
+// chunk id can't be derived from it with the hashed, numeric or deterministic ids
+fn collect_entry_chunk_ids(context: &Arc<Context>) -> BTreeMap<String, String> {
+    let chunk_graph = context.chunk_graph.read().unwrap();
+    chunk_graph
+        .get_all_chunks()
+        .into_iter()
+        .filter_map(|chunk| match &chunk.chunk_type {
+            ChunkType::Entry(module_id, ..) => {
+                Some((module_id.generate(context), chunk.id.id.clone()))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+// chunk id => (prefetch chunk ids, preload chunk ids)
+fn collect_resource_hints(
+    context: &Arc<Context>,
+) -> BTreeMap<String, (BTreeSet<String>, BTreeSet<String>)> {
+    let module_graph = context.module_graph.read().unwrap();
+    let chunk_graph = context.chunk_graph.read().unwrap();
+    let mut resource_hints = BTreeMap::new();
+
+    for chunk in chunk_graph.get_all_chunks() {
+        let auto_prefetch = match chunk.chunk_type {
+            ChunkType::Entry(..) => context.config.auto_prefetch,
+            ChunkType::Async | ChunkType::Sync => false,
+            _ => continue,
+        };
+        let mut prefetch = BTreeSet::new();
+        let mut preload = BTreeSet::new();
+        for module_id in chunk.get_modules() {
+            for (dep_module_id, dep) in module_graph.get_dependencies(module_id) {
+                let ResolveType::DynamicImport(import_options) = &dep.resolve_type else {
+                    continue;
+                };
+                let chunk_id = match import_options.get_chunk_name() {
+                    Some(chunk_name) => generate_module_id(chunk_name, context),
+                    None => dep_module_id.generate(context),
+                };
+                if chunk_id == chunk.id.id
+                    || !chunk_graph
+                        .chunk(&chunk_id.clone().into())
+                        .is_some_and(|c| c.chunk_type == ChunkType::Async)
+                {
+                    continue;
+                }
+                if import_options.preload {
+                    preload.insert(chunk_id);
+                } else if import_options.prefetch || auto_prefetch {
+                    prefetch.insert(chunk_id);
+                }
+            }
+        }
+        prefetch.retain(|id| !preload.contains(id));
+        if !prefetch.is_empty() || !preload.is_empty() {
+            resource_hints.insert(chunk.id.id.clone(), (prefetch, preload));
+        }
+    }
+
+    resource_hints
+}
@@ -0,0 +1,53 @@
+/* mako/runtime/resource hints */
+!(function () {
+  // chunkId => [prefetch chunk ids, preload chunk ids]
+  var resourceHints = __RESOURCE_HINTS__;
+  // entryModuleId => entry chunk id
+  var entryChunkIds = __ENTRY_CHUNK_IDS__;
+  var crossOriginLoading = __CROSS_ORIGIN_LOADING__;
+  var hinted = {};
+
+  function hint(rel, chunkId) {
+    if (requireModule.jsonpInstalled[chunkId] !== undefined) return;
+    [chunksIdToUrlMap[chunkId], cssChunksIdToUrlMap[chunkId]].forEach(
+      function (url, index) {
+        if (!url || hinted[rel + url]) return;
+        hinted[rel + url] = true;
+        var link = document.createElement('link');
+        link.rel = rel;
+        if (rel === 'preload') link.as = index === 0 ? 'script' : 'style';
+        link.href = requireModule.publicPath + url;
+        if (
+          crossOriginLoading &&
+          (crossOriginLoading === 'use-credentials' ||
+            link.href.indexOf(window.location.origin + '/') !== 0)
+        ) {
+          link.crossOrigin = crossOriginLoading;
+        }
+        document.head.appendChild(link);
+      },
+    );
+  }
+
+  // preload along with the parent chunk, prefetch after it's loaded
+  function hintChildren(chunkId, loaded) {
+    var hints = resourceHints[chunkId];
+    if (!hints) return;
+    hints[1].forEach(function (id) {
+      hint('preload', id);
+    });
+    loaded.then(
+      function () {
+        hints[0].forEach(function (id) {
+          hint('prefetch', id);
+        });
+      },
+      function () {},
+    );
+  }
+
+  requireModule.chunkEnsures.resourceHints = function (chunkId, promises) {
+    hintChildren(chunkId, Promise.all(promises.slice()));
+  };
+  hintChildren(entryChunkIds[entryModuleId], Promise.resolve());
+})();
@@ -0,0 +1,130 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::compiler::Context;
+use crate::generate::chunk::ChunkType;
+use crate::generate::chunk_pot::util::file_content_hash;
+use crate::generate::generate_chunks::{ChunkFile, ChunkFileType};
+use crate::module::{generate_module_id, ResolveType};
+use crate::plugin::Plugin;
+
+/**
+ * Inject the precache manifest of emitted files into the service workers which are registered
+ * with `navigator.serviceWorker.register(new URL('./sw.ts', import.meta.url))`, e.g.
+ *
+ * self.__MAKO_PRECACHE_MANIFEST__ = [{ "url": "/index.js", "revision": "1f2e3d" }];
+ *
+ * The revision of files with hash in their names is null.
+ */
+pub struct ServiceWorkerPlugin {}
+
+#[derive(Serialize, Clone, Debug)]
+struct PrecacheEntry {
+    url: String,
+    revision: Option<String>,
+}
+
+const PRECACHE_MANIFEST_GLOBAL: &str = "self.__MAKO_PRECACHE_MANIFEST__";
+
+impl Plugin for ServiceWorkerPlugin {
+    fn name(&self) -> &str {
+        "service_worker"
+    }
+
+    fn transform_chunk_files(
+        &self,
+        chunk_files: &mut [ChunkFile],
+        context: &Arc<Context>,
+    ) -> Result<()> {
+        let service_worker_chunks = service_worker_chunks(context);
+        if service_worker_chunks.is_empty() {
+            return Ok(());
+        }
+        let public_path = match context.config.public_path.as_str() {
+            // relative to the service worker, which is in the root of output
+            "auto" | "runtime" => "",
+            public_path => public_path,
+        };
+
+        let mut entries = chunk_files
+            .iter()
+            .filter(|f| !service_worker_chunks.contains(&f.chunk_id))
+            .map(|f| PrecacheEntry {
+                url: format!("{}{}", public_path, f.disk_name()),
+                revision: if f.hash.is_some() {
+                    None
+                } else {
+                    Some(format!("{:x}", md5::compute(&f.content)))
+                },
+            })
+            .chain(
+                context
+                    .assets_info
+                    .lock()
+                    .unwrap()
+                    .values()
+                    .map(|name| PrecacheEntry {
+                        url: format!("{}{}", public_path, name),
+                        revision: None,
+                    }),
+            )
+            .collect::<Vec<_>>();
+        entries.sort_by(|a, b| a.url.cmp(&b.url));
+        entries.dedup_by(|a, b| a.url == b.url);
+        let manifest = format!(
+            "{} = {};\n",
+            PRECACHE_MANIFEST_GLOBAL,
+            serde_json::to_string(&entries)?
+        );
+
+        for file in chunk_files
+            .iter_mut()
+            .filter(|f| service_worker_chunks.contains(&f.chunk_id))
+        {
+            if !matches!(file.file_type, ChunkFileType::JS) {
+                continue;
+            }
+            file.content.splice(0..0, manifest.bytes());
+            // the manifest takes the first line, so the mappings are moved down by one line
+            if let Some(source_map) = &mut file.source_map {
+                let mut map: serde_json::Value = serde_json::from_slice(source_map)?;
+                if let Some(mappings) = map["mappings"].as_str() {
+                    map["mappings"] = format!(";{}", mappings).into();
+                    *source_map = serde_json::to_vec(&map)?;
+                }
+            }
+            if file.hash.is_some() {
+                file.hash = Some(file_content_hash(&file.content));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn service_worker_chunks(context: &Arc<Context>) -> HashSet<String> {
+    let module_graph = context.module_graph.read().unwrap();
+    let chunk_graph = context.chunk_graph.read().unwrap();
+    let mut chunks = HashSet::new();
+    for module_id in module_graph.get_module_ids() {
+        for (dep_module_id, dep) in module_graph.get_dependencies(&module_id) {
+            if let ResolveType::Worker(import_options) = &dep.resolve_type
+                && import_options.service_worker
+            {
+                let chunk_id = match import_options.get_chunk_name() {
+                    Some(chunk_name) => generate_module_id(chunk_name, context),
+                    None => dep_module_id.generate(context),
+                };
+                if chunk_graph
+                    .chunk(&chunk_id.clone().into())
+                    .is_some_and(|c| matches!(c.chunk_type, ChunkType::Worker(_)))
+                {
+                    chunks.insert(chunk_id);
+                }
+            }
+        }
+    }
+    chunks
+}
@@ -0,0 +1,119 @@
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+
+use crate::compiler::Context;
+use crate::generate::chunk::ChunkType;
+use crate::generate::chunk_graph::ChunkGraph;
+use crate::generate::generate_chunks::ChunkFile;
+use crate::module_graph::ModuleGraph;
+use crate::plugin::Plugin;
+use crate::stats::StatsJsonMap;
+
+/**
+ * Bundle everything of a node entry into one self-contained executable file, the async and
+ * split chunks are merged into the entry chunks which load them, assets are inlined as data
+ * urls by `Load::handle_asset`, and the entry files are made executable after written.
+ *
+ * Worker chunks are kept as separated files since they must be loaded as scripts.
+ */
+pub struct SingleFilePlugin {
+    entry_files: Mutex<Vec<String>>,
+}
+
+impl SingleFilePlugin {
+    pub fn new() -> Self {
+        Self {
+            entry_files: Mutex::new(vec![]),
+        }
+    }
+}
+
+impl Plugin for SingleFilePlugin {
+    fn name(&self) -> &str {
+        "single_file"
+    }
+
+    fn optimize_chunk(
+        &self,
+        chunk_graph: &mut ChunkGraph,
+        _module_graph: &mut ModuleGraph,
+        _context: &Arc<Context>,
+    ) -> Result<()> {
+        let roots = chunk_graph
+            .get_chunks()
+            .into_iter()
+            .filter(|c| matches!(c.chunk_type, ChunkType::Entry(..) | ChunkType::Worker(_)))
+            .map(|c| c.id.clone())
+            .collect::<Vec<_>>();
+
+        let mut merged = HashSet::new();
+        for root in roots {
+            let mut modules = vec![];
+            let mut visited = HashSet::new();
+            let mut stack = chunk_graph.dependencies_chunk(&root);
+            while let Some(chunk_id) = stack.pop() {
+                if !visited.insert(chunk_id.clone()) {
+                    continue;
+                }
+                let chunk = chunk_graph.chunk(&chunk_id).unwrap();
+                // other entries and workers are roots themselves
+                if !matches!(chunk.chunk_type, ChunkType::Async | ChunkType::Sync) {
+                    continue;
+                }
+                modules.extend(chunk.get_modules().iter().cloned());
+                stack.extend(chunk_graph.dependencies_chunk(&chunk_id));
+                merged.insert(chunk_id);
+            }
+
+            let root_chunk = chunk_graph.mut_chunk(&root).unwrap();
+            modules
+                .into_iter()
+                .for_each(|module_id| root_chunk.add_module(module_id));
+        }
+
+        merged
+            .iter()
+            .for_each(|chunk_id| chunk_graph.remove_chunk(chunk_id));
+        Ok(())
+    }
+
+    fn after_generate_chunk_files(
+        &self,
+        chunk_files: &[ChunkFile],
+        context: &Arc<Context>,
+    ) -> Result<()> {
+        let chunk_graph = context.chunk_graph.read().unwrap();
+        *self.entry_files.lock().unwrap() = chunk_files
+            .iter()
+            .filter(|f| {
+                chunk_graph
+                    .chunk(&f.chunk_id.clone().into())
+                    .is_some_and(|c| matches!(c.chunk_type, ChunkType::Entry(..)))
+                    && f.disk_name().ends_with(".js")
+            })
+            .map(|f| f.disk_name())
+            .collect();
+        Ok(())
+    }
+
+    // after the chunk files are written to the output directory
+    fn build_success(&self, _stats: &StatsJsonMap, context: &Arc<Context>) -> Result<()> {
+        for file in self.entry_files.lock().unwrap().iter() {
+            let path = context.config.output.path.join(file);
+            if !path.exists() {
+                continue;
+            }
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+
+                let mut permissions = std::fs::metadata(&path)?.permissions();
+                permissions.set_mode(permissions.mode() | 0o111);
+                std::fs::set_permissions(&path, permissions)?;
+            }
+        }
+        Ok(())
+    }
+}
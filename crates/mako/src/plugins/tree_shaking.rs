@@ -16,6 +16,8 @@ mod remove_useless_stmts;
 mod shake;
 mod statement_graph;
 
+pub(crate) use shake::uniq_module_default_export_name;
+
 pub struct FarmTreeShake {}
 
 impl Plugin for FarmTreeShake {
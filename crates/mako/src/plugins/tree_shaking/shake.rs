@@ -13,9 +13,10 @@ use swc_core::common::util::take::Take;
 use swc_core::common::GLOBALS;
 use swc_core::ecma::transforms::base::helpers::{Helpers, HELPERS};
 
+pub(crate) use self::module_concatenate::uniq_module_default_export_name;
 use self::skip_module::skip_module_optimize;
 use crate::compiler::Context;
-use crate::module::{ModuleAst, ModuleId, ModuleSystem, ModuleType, ResolveType};
+use crate::module::{ImportOptions, ModuleAst, ModuleId, ModuleSystem, ModuleType, ResolveType};
 use crate::module_graph::ModuleGraph;
 use crate::plugins::tree_shaking::module::{AllExports, TreeShakeModule};
 use crate::plugins::tree_shaking::shake::module_concatenate::optimize_module_graph;
@@ -577,8 +578,31 @@ fn shake_module(
     }
 
     // add all dynamic imported dependencies as [UsedExports::All]
+    // unless the used exports are declared with `webpackExports`
     for (dep, edge) in module_graph.get_dependencies(tree_shake_module_id) {
-        match edge.resolve_type {
+        match &edge.resolve_type {
+            ResolveType::DynamicImport(ImportOptions {
+                exports: Some(exports),
+                ..
+            }) => {
+                if let Some(ref_cell) = tree_shake_modules_map.get(dep) {
+                    let mut tree_shake_module = ref_cell.borrow_mut();
+                    tree_shake_module.side_effects = true;
+
+                    let mut added = tree_shake_module.add_used_export(None);
+                    for export in exports {
+                        added |= if export == "default" {
+                            tree_shake_module.add_used_export(Some(&module::UsedIdent::Default))
+                        } else {
+                            tree_shake_module
+                                .add_used_export(Some(&module::UsedIdent::SwcIdent(export.clone())))
+                        };
+                    }
+                    if added && tree_shake_module.topo_order < next_index {
+                        next_index = tree_shake_module.topo_order;
+                    }
+                }
+            }
             ResolveType::DynamicImport(_) | ResolveType::Worker(_) => {
                 if let Some(ref_cell) = tree_shake_modules_map.get(dep) {
                     let mut tree_shake_module = ref_cell.borrow_mut();
@@ -1,3 +1,4 @@
+mod bailout;
 mod concatenate_context;
 mod concatenated_transformer;
 mod external_transformer;
@@ -9,6 +10,7 @@ use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
+use bailout::{BailoutReason, Bailouts};
 use concatenated_transformer::ConcatenatedTransform;
 use external_transformer::ExternalTransformer;
 use swc_core::common::util::take::Take;
@@ -18,6 +20,7 @@ use swc_core::ecma::transforms::base::resolver;
 use swc_core::ecma::visit::VisitMutWith;
 
 use self::concatenate_context::EsmDependantFlags;
+pub(crate) use self::utils::uniq_module_default_export_name;
 use self::utils::uniq_module_prefix;
 use crate::ast::js_ast::JsAst;
 use crate::compiler::Context;
@@ -43,9 +46,13 @@ pub fn optimize_module_graph(
 
     let mut root_candidates = vec![];
     let mut inner_candidates = HashSet::new();
+    let mut bailouts = Bailouts::default();
 
     for (order, module_id) in sorted_module_ids.iter().enumerate() {
         if all_in_circles.contains(module_id) {
+            if tree_shake_modules_map.contains_key(module_id) {
+                bailouts.add(module_id, BailoutReason::Circular);
+            }
             continue;
         }
 
@@ -58,6 +65,7 @@ pub fn optimize_module_graph(
             tsm.topo_order = order;
 
             if tsm.module_system != ModuleSystem::ESModule {
+                bailouts.add(module_id, BailoutReason::NotEsm(tsm.module_system.clone()));
                 continue;
             }
 
@@ -69,18 +77,24 @@ pub fn optimize_module_graph(
 
             if dynamic_imported {
                 can_be_inner = false;
+                bailouts.add(module_id, BailoutReason::DynamicImported);
             }
 
             let deps = module_graph.get_dependencies_info(module_id);
 
-            let has_not_supported_syntax = deps.iter().any(|(_, dep, is_async)| {
-                dep.resolve_type.is_dynamic_esm()
-                    || matches!(dep.resolve_type, ResolveType::Worker(_))
-                    || (*is_async && dep.resolve_type.is_sync_esm())
-            });
-            if has_not_supported_syntax {
+            for (_, dep, is_async) in deps.iter() {
+                let reason = if dep.resolve_type.is_dynamic_esm() {
+                    BailoutReason::ImportsDynamic(dep.source.clone())
+                } else if matches!(dep.resolve_type, ResolveType::Worker(_)) {
+                    BailoutReason::ImportsWorker(dep.source.clone())
+                } else if *is_async && dep.resolve_type.is_sync_esm() {
+                    BailoutReason::ImportsAsync(dep.source.clone())
+                } else {
+                    continue;
+                };
                 can_be_inner = false;
                 can_be_root = false;
+                bailouts.add(module_id, reason);
             }
 
             let has_export_star = deps
@@ -89,17 +103,28 @@ pub fn optimize_module_graph(
             // 必须要有清晰的导出
             // ? 是不是不能有 export * from 'foo' 的语法
             // ： 可以有，但是不能有模糊的 export *
-            if matches!(tsm.all_exports, AllExports::Ambiguous(_)) || has_export_star {
+            if matches!(tsm.all_exports, AllExports::Ambiguous(_)) {
                 can_be_inner = false;
+                bailouts.add(module_id, BailoutReason::AmbiguousExports);
+            }
+            if has_export_star {
+                can_be_inner = false;
+                bailouts.add(module_id, BailoutReason::ExportStar);
             }
 
             module_graph
                 .get_module(module_id)
                 .and_then(|module| module.info.as_ref())
                 .inspect(|info| {
-                    if info.is_async || info.is_ignored {
+                    if info.is_async {
                         can_be_inner = false;
                         can_be_root = false;
+                        bailouts.add(module_id, BailoutReason::Async);
+                    }
+                    if info.is_ignored {
+                        can_be_inner = false;
+                        can_be_root = false;
+                        bailouts.add(module_id, BailoutReason::Ignored);
                     }
                 });
 
@@ -121,13 +146,19 @@ pub fn optimize_module_graph(
         config: &mut ConcatenateConfig,
         candidate: &HashSet<ModuleId>,
         module_graph: &ModuleGraph,
+        bailouts: &mut Bailouts,
     ) {
         if current_module_id.ne(&config.root) {
             let parents = module_graph.dependant_module_ids(current_module_id);
 
-            let is_all_parents_in_config = parents.iter().all(|p| config.contains(p));
-
-            if !is_all_parents_in_config {
+            if let Some(parent) = parents.iter().find(|p| !config.contains(p)) {
+                bailouts.add(
+                    current_module_id,
+                    BailoutReason::ImportedOutside {
+                        importer: parent.clone(),
+                        root: config.root.clone(),
+                    },
+                );
                 config.add_external(current_module_id.clone());
                 return;
             }
@@ -154,20 +185,31 @@ pub fn optimize_module_graph(
                     children.push(module_id.clone());
                 }
             } else {
+                if candidate.contains(module_id) {
+                    bailouts.add(
+                        module_id,
+                        BailoutReason::ImportedByRequire(current_module_id.clone()),
+                    );
+                }
                 config.add_external(module_id.clone());
             }
         }
 
         for m in children.iter() {
-            collect_inner_modules(m, config, candidate, module_graph);
+            collect_inner_modules(m, config, candidate, module_graph, bailouts);
         }
     }
 
-    fn extends_external_modules(config: &mut ConcatenateConfig, module_graph: &ModuleGraph) {
-        let mut visited = HashSet::new();
+    fn extends_external_modules(
+        config: &mut ConcatenateConfig,
+        module_graph: &ModuleGraph,
+        bailouts: &mut Bailouts,
+    ) {
+        // visited module => the external module it's reachable from
+        let mut visited = HashMap::new();
 
         for (ext, _) in config.externals.iter() {
-            if visited.contains(ext) {
+            if visited.contains_key(ext) {
                 continue;
             }
 
@@ -176,15 +218,16 @@ pub fn optimize_module_graph(
             while let Some(node) = dfs.next(&module_graph.graph) {
                 let m = &module_graph.graph[node];
 
-                if visited.contains(&m.id) {
+                if visited.contains_key(&m.id) {
                     continue;
                 }
 
-                visited.insert(m.id.clone());
+                visited.insert(m.id.clone(), ext.clone());
             }
         }
-        for visited in visited {
+        for (visited, ext) in visited {
             if config.inners.contains(&visited) {
+                bailouts.add(&visited, BailoutReason::ReachableFromExternal(ext));
                 config.inners.remove(&visited);
                 config.externals.insert(visited, Default::default());
             }
@@ -197,11 +240,18 @@ pub fn optimize_module_graph(
         }
         let mut config = ConcatenateConfig::new(root.clone());
 
-        collect_inner_modules(root, &mut config, &inner_candidates, module_graph);
+        collect_inner_modules(
+            root,
+            &mut config,
+            &inner_candidates,
+            module_graph,
+            &mut bailouts,
+        );
 
-        extends_external_modules(&mut config, module_graph);
+        extends_external_modules(&mut config, module_graph, &mut bailouts);
 
         if config.is_empty() {
+            bailouts.add(root, BailoutReason::NoInner);
         } else {
             used_as_inner.insert(config.root.clone());
             used_as_inner.extend(config.inners.iter().cloned());
@@ -242,6 +292,9 @@ pub fn optimize_module_graph(
         src_2_module_id
     }
 
+    // root => inners
+    let mut concatenated = HashMap::new();
+
     GLOBALS.set(&context.meta.script.globals, || {
         for config in &concat_configurations {
             mako_profile_scope!("concatenate", &config.root.id);
@@ -260,7 +313,14 @@ pub fn optimize_module_graph(
                 ));
             }
 
-            if let Ok(mut concatenate_context) = ConcatenateContext::init(config, module_graph) {
+            let concatenate_context = ConcatenateContext::init(config, module_graph);
+            if let Err(err) = &concatenate_context {
+                for module_id in std::iter::once(&config.root).chain(config.inners.iter()) {
+                    bailouts.add(module_id, BailoutReason::InitFailed(err.to_string()));
+                }
+            }
+
+            if let Ok(mut concatenate_context) = concatenate_context {
                 let mut module_items = concatenate_context.interop_module_items.clone();
 
                 for id in &config.sorted_modules(module_graph) {
@@ -432,10 +492,35 @@ pub fn optimize_module_graph(
                 for inner in config.inners.iter() {
                     module_graph.remove_module(inner);
                 }
+
+                let mut inners = config.inners.iter().cloned().collect::<Vec<_>>();
+                inners.sort();
+                concatenated.insert(config.root.clone(), inners);
             } else {
                 continue;
             }
         }
+
+        let module_ids = sorted_module_ids
+            .iter()
+            .filter(|id| tree_shake_modules_map.contains_key(id))
+            .cloned()
+            .collect::<Vec<_>>();
+        let inners = concatenated.values().flatten().collect::<HashSet<_>>();
+        for module_id in &module_ids {
+            if !concatenated.contains_key(module_id)
+                && !inners.contains(module_id)
+                && !bailouts.has(module_id)
+            {
+                bailouts.add(module_id, BailoutReason::NoRoot);
+            }
+        }
+        context.stats_info.set_concatenation(bailouts.to_stats(
+            &module_ids,
+            &concatenated,
+            context,
+        ));
+
         Ok(())
     })
 }
@@ -0,0 +1,264 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use pathdiff::diff_paths;
+
+use crate::compiler::Context;
+use crate::module::{ModuleId, ModuleSystem};
+use crate::stats::{
+    ConcatenationBailout, ConcatenationModuleInfo, ConcatenationStats, ConcatenationStatus,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum BailoutReason {
+    Circular,
+    NotEsm(ModuleSystem),
+    DynamicImported,
+    ImportsDynamic(String),
+    ImportsWorker(String),
+    ImportsAsync(String),
+    ExportStar,
+    AmbiguousExports,
+    Async,
+    Ignored,
+    ImportedOutside { importer: ModuleId, root: ModuleId },
+    ImportedByRequire(ModuleId),
+    ReachableFromExternal(ModuleId),
+    NoInner,
+    NoRoot,
+    InitFailed(String),
+}
+
+impl BailoutReason {
+    fn to_bailout(&self, context: &Arc<Context>) -> ConcatenationBailout {
+        let id = |module_id: &ModuleId| to_relative_id(module_id, context);
+        let (reason, hint) = match self {
+            BailoutReason::Circular => (
+                "it is in a circular dependency".to_string(),
+                "it was not in a circular dependency".to_string(),
+            ),
+            BailoutReason::NotEsm(ModuleSystem::CommonJS) => (
+                "it is a CommonJS module".to_string(),
+                "it used the ES module syntax".to_string(),
+            ),
+            BailoutReason::NotEsm(_) => (
+                "it is not an ES module".to_string(),
+                "it used the ES module syntax".to_string(),
+            ),
+            BailoutReason::DynamicImported => (
+                "it is imported by `import()`".to_string(),
+                "it was only imported statically".to_string(),
+            ),
+            BailoutReason::ImportsDynamic(source) => (
+                format!("it imports `{}` by `import()`", source),
+                format!("it imported `{}` statically", source),
+            ),
+            BailoutReason::ImportsWorker(source) => (
+                format!("it creates the worker `{}`", source),
+                "the worker was created in another module".to_string(),
+            ),
+            BailoutReason::ImportsAsync(source) => (
+                format!("it imports the async module `{}`", source),
+                format!("`{}` had no top level await", source),
+            ),
+            BailoutReason::ExportStar => (
+                "it has `export * from`".to_string(),
+                "it re-exported the names explicitly".to_string(),
+            ),
+            BailoutReason::AmbiguousExports => (
+                "its exports are ambiguous".to_string(),
+                "the modules it re-exports from were ES modules".to_string(),
+            ),
+            BailoutReason::Async => (
+                "it is an async module".to_string(),
+                "it and its dependencies had no top level await".to_string(),
+            ),
+            BailoutReason::Ignored => (
+                "it is ignored".to_string(),
+                "it was not ignored".to_string(),
+            ),
+            BailoutReason::ImportedOutside { importer, root } => (
+                format!(
+                    "it is imported by `{}` which is not concatenated into `{}`",
+                    id(importer),
+                    id(root)
+                ),
+                format!(
+                    "`{}` was concatenated into `{}` too",
+                    id(importer),
+                    id(root)
+                ),
+            ),
+            BailoutReason::ImportedByRequire(importer) => (
+                format!("it is imported by `require` in `{}`", id(importer)),
+                format!("`{}` imported it by `import`", id(importer)),
+            ),
+            BailoutReason::ReachableFromExternal(external) => (
+                format!(
+                    "it is also a dependency of `{}` which is not concatenated",
+                    id(external)
+                ),
+                format!("it was not a dependency of `{}`", id(external)),
+            ),
+            BailoutReason::NoInner => (
+                "none of its dependencies can be concatenated into it".to_string(),
+                "it imported the ES modules which are only imported by it".to_string(),
+            ),
+            BailoutReason::NoRoot => (
+                "none of its importers can be concatenated".to_string(),
+                "it was imported by an ES module which can be concatenated".to_string(),
+            ),
+            BailoutReason::InitFailed(error) => (
+                format!("failed to concatenate: {}", error),
+                "the error above was fixed".to_string(),
+            ),
+        };
+        ConcatenationBailout {
+            reason,
+            hint: format!("would be concatenated if {}", hint),
+        }
+    }
+}
+
+#[derive(Default)]
+pub(super) struct Bailouts {
+    reasons: HashMap<ModuleId, Vec<BailoutReason>>,
+}
+
+impl Bailouts {
+    pub fn add(&mut self, module_id: &ModuleId, reason: BailoutReason) {
+        let reasons = self.reasons.entry(module_id.clone()).or_default();
+        if !reasons.contains(&reason) {
+            reasons.push(reason);
+        }
+    }
+
+    pub fn has(&self, module_id: &ModuleId) -> bool {
+        self.reasons.contains_key(module_id)
+    }
+
+    pub fn to_stats(
+        &self,
+        module_ids: &[ModuleId],
+        concatenated: &HashMap<ModuleId, Vec<ModuleId>>,
+        context: &Arc<Context>,
+    ) -> ConcatenationStats {
+        let inner_to_root = concatenated
+            .iter()
+            .flat_map(|(root, inners)| inners.iter().map(move |inner| (inner, root)))
+            .collect::<HashMap<_, _>>();
+        let mut stats = ConcatenationStats::default();
+        for module_id in module_ids {
+            let id = to_relative_id(module_id, context);
+            let info = if let Some(inners) = concatenated.get(module_id) {
+                stats.roots += 1;
+                ConcatenationModuleInfo {
+                    id,
+                    status: ConcatenationStatus::Root,
+                    root: None,
+                    inners: inners
+                        .iter()
+                        .map(|inner| to_relative_id(inner, context))
+                        .collect(),
+                    bailouts: vec![],
+                }
+            } else if let Some(root) = inner_to_root.get(module_id) {
+                stats.inners += 1;
+                ConcatenationModuleInfo {
+                    id,
+                    status: ConcatenationStatus::Inner,
+                    root: Some(to_relative_id(root, context)),
+                    inners: vec![],
+                    bailouts: vec![],
+                }
+            } else {
+                stats.bailouts += 1;
+                ConcatenationModuleInfo {
+                    id,
+                    status: ConcatenationStatus::Bailout,
+                    root: None,
+                    inners: vec![],
+                    bailouts: self
+                        .reasons
+                        .get(module_id)
+                        .map(|reasons| {
+                            reasons
+                                .iter()
+                                .map(|reason| reason.to_bailout(context))
+                                .collect()
+                        })
+                        .unwrap_or_default(),
+                }
+            };
+            stats.modules.push(info);
+        }
+        stats.modules.sort_by(|a, b| a.id.cmp(&b.id));
+        stats
+    }
+}
+
+fn to_relative_id(module_id: &ModuleId, context: &Arc<Context>) -> String {
+    diff_paths(&module_id.id, &context.root)
+        .map_or(module_id.id.clone(), |p| p.to_string_lossy().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::compiler::Compiler;
+    use crate::config::Config;
+    use crate::stats::ConcatenationStatus;
+
+    #[test]
+    fn test_concatenation_bailouts() {
+        let root =
+            PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test/build/concatenate-bailouts");
+        let config = Config::new(&root, None, None).unwrap();
+        let compiler = Compiler::new(config, root, Default::default(), None).unwrap();
+        compiler.compile().unwrap();
+
+        let stats = compiler.context.stats_info.get_concatenation().unwrap();
+        assert_eq!((stats.roots, stats.inners, stats.bailouts), (1, 1, 3));
+        let modules = stats
+            .modules
+            .iter()
+            .map(|module| {
+                let bailouts = module
+                    .bailouts
+                    .iter()
+                    .map(|bailout| bailout.reason.as_str())
+                    .collect::<Vec<_>>();
+                (module.id.as_str(), &module.status, bailouts)
+            })
+            .collect::<Vec<_>>();
+        assert!(matches!(modules[0], ("a.js", ConcatenationStatus::Root, _)));
+        assert_eq!(stats.modules[0].inners, vec!["c.js"]);
+        assert!(matches!(
+            modules[1],
+            ("c.js", ConcatenationStatus::Inner, _)
+        ));
+        assert_eq!(
+            modules[2].2,
+            vec!["it is a CommonJS module"],
+            "cjs.js should bail out"
+        );
+        assert_eq!(
+            modules[3].2,
+            vec!["it imports `./lazy` by `import()`"],
+            "index.js should bail out"
+        );
+        assert_eq!(
+            modules[4].2,
+            vec![
+                "it is imported by `import()`",
+                "none of its dependencies can be concatenated into it"
+            ],
+            "lazy.js should bail out"
+        );
+        assert_eq!(
+            stats.modules[4].bailouts[0].hint,
+            "would be concatenated if it was only imported statically"
+        );
+    }
+}
@@ -279,7 +279,7 @@ fn run_test(code: &str, ccn_ctx: &mut ConcatenateContext) -> String {
                 skip_modules: Some(true),
             }),
             mode: Mode::Production,
-            minify: true,
+            minify: Some(Default::default()),
             ..Default::default()
         },
         ..Default::default()
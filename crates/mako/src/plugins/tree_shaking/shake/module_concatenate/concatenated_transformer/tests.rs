@@ -729,7 +729,7 @@ fn inner_trans_code_imported_as(
                 skip_modules: Some(true),
             }),
             mode: Mode::Production,
-            minify: false,
+            minify: None,
             ..Default::default()
         },
         ..Default::default()
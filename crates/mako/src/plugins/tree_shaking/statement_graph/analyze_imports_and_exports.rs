@@ -89,7 +89,7 @@ pub fn analyze_imports_and_exports(
                                 local: named.local.to_string(),
                                 imported: named.imported.as_ref().map(|i| match i {
                                     ModuleExportName::Ident(i) => i.to_string(),
-                                    _ => panic!("non-ident imported is not supported when tree shaking"),
+                                    ModuleExportName::Str(s) => s.value.to_string(),
                                 }),
                             });
                             defined_idents.insert(named.local.to_string());
@@ -271,7 +271,7 @@ pub fn analyze_imports_and_exports(
                                 local: local.to_string(),
                                 exported: named.exported.as_ref().map(|i| match i {
                                     ModuleExportName::Ident(i) => i.to_string(),
-                                    _ => panic!("non-ident exported is not supported when tree shaking"),
+                                    ModuleExportName::Str(s) => s.value.to_string(),
                                 }),
                             });
                         }
@@ -0,0 +1,523 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::sync::Arc;
+
+use anyhow::Result;
+use glob::glob;
+use regex::Regex;
+use swc_core::css::ast::{
+    AtRule, AtRuleName, ComplexSelector, ComplexSelectorChildren, ComponentValue, QualifiedRule,
+    QualifiedRulePrelude, Rule, SimpleBlock, Stylesheet, SubclassSelector,
+};
+use swc_core::css::visit::{VisitMut, VisitMutWith};
+use swc_core::ecma::ast::{
+    Decl, Expr, Id, ImportDecl, ImportSpecifier, Lit, MemberExpr, MemberProp, Module, ModuleDecl,
+    ModuleItem, ObjectLit, Pat, Prop, PropName, PropOrSpread, Stmt, VarDeclarator,
+};
+use swc_core::ecma::visit::{Visit, VisitWith};
+use tracing::debug;
+
+use crate::compiler::Context;
+use crate::config::UnusedCssConfig;
+use crate::module::{ModuleAst, ModuleId};
+use crate::module_graph::ModuleGraph;
+use crate::plugin::Plugin;
+use crate::plugins::tree_shaking::uniq_module_default_export_name;
+use crate::utils::create_cached_regex;
+
+/**
+ * Remove the css rules which are never used after the module graph is optimized.
+ *
+ * For css modules, the class names referenced by the js consumers are collected, e.g.
+ * `styles.button` or `styles["button"]`, rules whose selectors contain other local class
+ * names are removed. A css modules file is kept as is if its exports are used in a way which
+ * can't be analyzed, e.g. `Object.keys(styles)` or `styles[name]`.
+ *
+ * With `purge`, global css is purged like purgecss, class names and ids which can't be found
+ * in the js modules or the content files are removed.
+ */
+pub struct UnusedCssPlugin {
+    pub purge: Option<Purge>,
+}
+
+pub struct Purge {
+    pub content: Vec<String>,
+    pub safelist: Vec<Regex>,
+}
+
+impl UnusedCssPlugin {
+    pub fn new(config: &UnusedCssConfig) -> Result<Self> {
+        let purge = match &config.purge {
+            Some(purge) => Some(Purge {
+                content: purge.content.clone(),
+                safelist: purge
+                    .safelist
+                    .iter()
+                    .map(|item| parse_safelist_item(item))
+                    .collect::<Result<Vec<_>>>()?,
+            }),
+            None => None,
+        };
+        Ok(Self { purge })
+    }
+}
+
+impl Plugin for UnusedCssPlugin {
+    fn name(&self) -> &str {
+        "unused_css"
+    }
+
+    fn optimize_module_graph(
+        &self,
+        module_graph: &mut ModuleGraph,
+        context: &Arc<Context>,
+    ) -> Result<()> {
+        let css_module_ids = module_graph
+            .modules()
+            .into_iter()
+            .filter(|module| {
+                module
+                    .info
+                    .as_ref()
+                    .is_some_and(|info| matches!(info.ast, ModuleAst::Css(_)))
+            })
+            .map(|module| module.id.clone())
+            .collect::<Vec<_>>();
+        let (modules_ids, global_ids): (Vec<_>, Vec<_>) = css_module_ids
+            .into_iter()
+            .partition(|id| id.id.ends_with("?modules"));
+
+        for id in modules_ids {
+            let Some(unused) = unused_css_modules_class_names(module_graph, &id) else {
+                debug!("unused css: skip {}, the exports can't be analyzed", id.id);
+                continue;
+            };
+            if !unused.is_empty() {
+                remove_selectors(module_graph, &id, |name| unused.contains(name));
+            }
+        }
+
+        if let Some(purge) = &self.purge {
+            let tokens = purge.collect_tokens(module_graph, context)?;
+            for id in global_ids {
+                remove_selectors(module_graph, &id, |name| {
+                    !tokens.contains(name) && !purge.safelist.iter().any(|re| re.is_match(name))
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// e.g. "active" matches `active` only, "/^ant-/" is a regex
+pub(crate) fn parse_safelist_item(item: &str) -> Result<Regex> {
+    let regex = match item.strip_prefix('/').and_then(|s| s.strip_suffix('/')) {
+        Some(pattern) => Regex::new(pattern)?,
+        None => Regex::new(&format!("^{}$", regex::escape(item)))?,
+    };
+    Ok(regex)
+}
+
+impl Purge {
+    fn collect_tokens(
+        &self,
+        module_graph: &ModuleGraph,
+        context: &Arc<Context>,
+    ) -> Result<HashSet<String>> {
+        let mut tokens = HashSet::new();
+        module_graph
+            .modules()
+            .iter()
+            .filter_map(|module| module.info.as_ref())
+            .filter(|info| matches!(info.ast, ModuleAst::Script(_)))
+            .for_each(|info| extract_tokens(&info.raw, &mut tokens));
+        for pattern in &self.content {
+            let pattern = context.root.join(pattern);
+            for path in glob(&pattern.to_string_lossy())?.flatten() {
+                if path.is_file() {
+                    extract_tokens(&fs::read_to_string(path)?, &mut tokens);
+                }
+            }
+        }
+        Ok(tokens)
+    }
+}
+
+// both `md:flex` and `flex` are extracted from `md:flex`
+fn extract_tokens(content: &str, tokens: &mut HashSet<String>) {
+    let word = create_cached_regex(r"[A-Za-z0-9_\-]+");
+    let token = create_cached_regex(r"[A-Za-z0-9_\-:/.]+");
+    for re in [word, token] {
+        tokens.extend(re.find_iter(content).map(|m| m.as_str().to_string()));
+    }
+}
+
+fn remove_selectors(
+    module_graph: &mut ModuleGraph,
+    id: &ModuleId,
+    is_unused: impl Fn(&str) -> bool,
+) {
+    let Some(info) = module_graph
+        .get_module_mut(id)
+        .and_then(|module| module.info.as_mut())
+    else {
+        return;
+    };
+    if let ModuleAst::Css(ast) = &mut info.ast {
+        ast.ast
+            .visit_mut_with(&mut UnusedSelectorsRemover { is_unused });
+    }
+}
+
+/**
+ * The generated class names of a css modules file which are never referenced, `None` if it
+ * can't be analyzed. The dependant of `a.module.css?modules` is either the exports module
+ * `a.module.css?asmodule`, or the module which the exports module is concatenated into.
+ */
+fn unused_css_modules_class_names(
+    module_graph: &ModuleGraph,
+    css_module_id: &ModuleId,
+) -> Option<HashSet<String>> {
+    let exports_module_id: ModuleId =
+        format!("{}?asmodule", css_module_id.id.strip_suffix("?modules")?).into();
+    let mut all = HashSet::new();
+    let mut used = HashSet::new();
+
+    for dependant_id in module_graph.dependant_module_ids(css_module_id) {
+        let ast = script_ast(module_graph, &dependant_id)?;
+        let mut usage = ClassNamesUsage::default();
+        let class_names = if dependant_id == exports_module_id {
+            for (consumer_id, dep) in module_graph.get_dependents(&exports_module_id) {
+                let consumer = script_ast(module_graph, consumer_id)?;
+                usage.bindings = import_bindings(consumer, &dep.source)?;
+                consumer.visit_with(&mut usage);
+            }
+            default_export_class_names(ast)?
+        } else {
+            let (binding, class_names) = concatenated_class_names(
+                ast,
+                &uniq_module_default_export_name(&exports_module_id),
+            )?;
+            usage.bindings = HashSet::from([binding]);
+            ast.visit_with(&mut usage);
+            class_names
+        };
+        if usage.all {
+            return None;
+        }
+        all.extend(class_names.values().flatten().cloned());
+        used.extend(
+            usage
+                .keys
+                .iter()
+                .filter_map(|key| class_names.get(key))
+                .flatten()
+                .cloned(),
+        );
+    }
+
+    Some(all.difference(&used).cloned().collect())
+}
+
+fn script_ast<'a>(module_graph: &'a ModuleGraph, id: &ModuleId) -> Option<&'a Module> {
+    let info = module_graph.get_module(id)?.info.as_ref()?;
+    info.ast.as_script().map(|ast| &ast.ast)
+}
+
+// e.g. { "a": `a-hash ${__mako_css_modules_composes_0["b"]}` } => { "a": ["a-hash"]}
+fn class_names_of_object(obj: &ObjectLit) -> Option<HashMap<String, Vec<String>>> {
+    obj.props
+        .iter()
+        .map(|prop| {
+            let PropOrSpread::Prop(box Prop::KeyValue(kv)) = prop else {
+                return None;
+            };
+            let key = match &kv.key {
+                PropName::Str(s) => s.value.to_string(),
+                PropName::Ident(ident) => ident.sym.to_string(),
+                _ => return None,
+            };
+            let class_names = match &*kv.value {
+                Expr::Tpl(tpl) => tpl
+                    .quasis
+                    .iter()
+                    .flat_map(|quasi| quasi.raw.split_whitespace().map(|s| s.to_string()))
+                    .collect(),
+                Expr::Lit(Lit::Str(s)) => {
+                    s.value.split_whitespace().map(|s| s.to_string()).collect()
+                }
+                _ => return None,
+            };
+            Some((key, class_names))
+        })
+        .collect()
+}
+
+fn default_export_class_names(ast: &Module) -> Option<HashMap<String, Vec<String>>> {
+    ast.body.iter().find_map(|item| match item {
+        ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(export)) => match &*export.expr {
+            Expr::Object(obj) => class_names_of_object(obj),
+            _ => None,
+        },
+        _ => None,
+    })
+}
+
+// the default export is renamed to `<name>` or `<name>_<n>` when concatenated
+fn concatenated_class_names(
+    ast: &Module,
+    name: &str,
+) -> Option<(Id, HashMap<String, Vec<String>>)> {
+    let mut found = ast
+        .body
+        .iter()
+        .filter_map(|item| match item {
+            ModuleItem::Stmt(Stmt::Decl(Decl::Var(var))) => Some(var.decls.iter()),
+            _ => None,
+        })
+        .flatten()
+        .filter_map(|decl| {
+            let Pat::Ident(binding) = &decl.name else {
+                return None;
+            };
+            let sym = binding.id.sym.as_str();
+            let is_match = sym == name
+                || sym
+                    .strip_prefix(name)
+                    .and_then(|s| s.strip_prefix('_'))
+                    .is_some_and(|s| s.chars().all(|c| c.is_ascii_digit()));
+            match decl.init.as_deref() {
+                Some(Expr::Object(obj)) if is_match => {
+                    Some((binding.id.to_id(), class_names_of_object(obj)?))
+                }
+                _ => None,
+            }
+        })
+        .collect::<Vec<_>>();
+    if found.len() == 1 {
+        found.pop()
+    } else {
+        None
+    }
+}
+
+// `None` if the css modules exports are imported in a way which can't be analyzed
+fn import_bindings(ast: &Module, source: &str) -> Option<HashSet<Id>> {
+    let mut found = false;
+    let mut bindings = HashSet::new();
+    for item in &ast.body {
+        match item {
+            ModuleItem::ModuleDecl(ModuleDecl::Import(import)) if import.src.value == source => {
+                found = true;
+                for specifier in &import.specifiers {
+                    match specifier {
+                        ImportSpecifier::Default(default) => {
+                            bindings.insert(default.local.to_id());
+                        }
+                        _ => return None,
+                    }
+                }
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(export))
+                if export.src.as_ref().is_some_and(|src| src.value == source) =>
+            {
+                return None;
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportAll(export)) if export.src.value == source => {
+                return None;
+            }
+            _ => {}
+        }
+    }
+    found.then_some(bindings)
+}
+
+#[derive(Default)]
+struct ClassNamesUsage {
+    bindings: HashSet<Id>,
+    keys: HashSet<String>,
+    // the exports object is used as a whole
+    all: bool,
+}
+
+impl Visit for ClassNamesUsage {
+    fn visit_import_decl(&mut self, _n: &ImportDecl) {}
+
+    fn visit_var_declarator(&mut self, n: &VarDeclarator) {
+        match &n.name {
+            Pat::Ident(binding) if self.bindings.contains(&binding.id.to_id()) => {
+                n.init.visit_with(self);
+            }
+            _ => n.visit_children_with(self),
+        }
+    }
+
+    fn visit_member_expr(&mut self, n: &MemberExpr) {
+        match &*n.obj {
+            Expr::Ident(obj) if self.bindings.contains(&obj.to_id()) => match &n.prop {
+                MemberProp::Ident(prop) => {
+                    self.keys.insert(prop.sym.to_string());
+                }
+                MemberProp::Computed(computed) => match &*computed.expr {
+                    Expr::Lit(Lit::Str(s)) => {
+                        self.keys.insert(s.value.to_string());
+                    }
+                    _ => {
+                        self.all = true;
+                    }
+                },
+                MemberProp::PrivateName(_) => {}
+            },
+            _ => n.visit_children_with(self),
+        }
+    }
+
+    fn visit_ident(&mut self, n: &swc_core::ecma::ast::Ident) {
+        if self.bindings.contains(&n.to_id()) {
+            self.all = true;
+        }
+    }
+}
+
+struct UnusedSelectorsRemover<F: Fn(&str) -> bool> {
+    is_unused: F,
+}
+
+impl<F: Fn(&str) -> bool> UnusedSelectorsRemover<F> {
+    // only the class names and ids of compound selectors are checked, not the ones in
+    // pseudo classes like :not(.a)
+    fn is_unused_selector(&self, selector: &ComplexSelector) -> bool {
+        selector.children.iter().any(|child| match child {
+            ComplexSelectorChildren::CompoundSelector(compound) => {
+                compound.subclass_selectors.iter().any(|sel| match sel {
+                    SubclassSelector::Class(class) => (self.is_unused)(&class.text.value),
+                    SubclassSelector::Id(id) => (self.is_unused)(&id.text.value),
+                    _ => false,
+                })
+            }
+            _ => false,
+        })
+    }
+}
+
+fn is_removed_rule(rule: &QualifiedRule) -> bool {
+    matches!(&rule.prelude, QualifiedRulePrelude::SelectorList(list) if list.children.is_empty())
+}
+
+// conditional group rules which contain nothing after the removal
+fn is_empty_at_rule(rule: &AtRule) -> bool {
+    let AtRuleName::Ident(name) = &rule.name else {
+        return false;
+    };
+    ["media", "supports", "container", "layer"]
+        .iter()
+        .any(|n| name.value.eq_ignore_ascii_case(n))
+        && rule
+            .block
+            .as_ref()
+            .is_some_and(|block| block.value.is_empty())
+}
+
+impl<F: Fn(&str) -> bool> VisitMut for UnusedSelectorsRemover<F> {
+    fn visit_mut_stylesheet(&mut self, n: &mut Stylesheet) {
+        n.visit_mut_children_with(self);
+        n.rules.retain(|rule| match rule {
+            Rule::QualifiedRule(rule) => !is_removed_rule(rule),
+            Rule::AtRule(rule) => !is_empty_at_rule(rule),
+            _ => true,
+        });
+    }
+
+    fn visit_mut_simple_block(&mut self, n: &mut SimpleBlock) {
+        n.visit_mut_children_with(self);
+        n.value.retain(|value| match value {
+            ComponentValue::QualifiedRule(rule) => !is_removed_rule(rule),
+            ComponentValue::AtRule(rule) => !is_empty_at_rule(rule),
+            _ => true,
+        });
+    }
+
+    fn visit_mut_qualified_rule(&mut self, n: &mut QualifiedRule) {
+        if let QualifiedRulePrelude::SelectorList(list) = &mut n.prelude {
+            list.children
+                .retain(|selector| !self.is_unused_selector(selector));
+            if list.children.is_empty() {
+                return;
+            }
+        }
+        n.block.visit_mut_with(self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use swc_core::css::visit::VisitMutWith;
+    use swc_core::ecma::visit::VisitWith;
+
+    use super::{
+        extract_tokens, import_bindings, parse_safelist_item, ClassNamesUsage,
+        UnusedSelectorsRemover,
+    };
+    use crate::ast::tests::TestUtils;
+
+    #[test]
+    fn test_remove_selectors() {
+        assert_eq!(
+            remove(
+                ".a{color:red}.b,.c{color:red}.d .b:hover{color:red}.c:not(.b){color:red}@media print{.b{color:red}}",
+                &["b"]
+            ),
+            ".a{color:red}.c{color:red}.c:not(.b){color:red}"
+        );
+    }
+
+    #[test]
+    fn test_class_names_usage() {
+        let (keys, all) = usage(
+            r#"import styles from "./a.module.css?asmodule"; styles.a; styles["b-c"]; foo.d;"#,
+        );
+        assert_eq!(keys, vec!["a", "b-c"]);
+        assert!(!all);
+        let (_, all) =
+            usage(r#"import styles from "./a.module.css?asmodule"; Object.keys(styles);"#);
+        assert!(all);
+        let (_, all) = usage(r#"import styles from "./a.module.css?asmodule"; styles[name];"#);
+        assert!(all);
+    }
+
+    #[test]
+    fn test_purge_tokens() {
+        let mut tokens = HashSet::new();
+        extract_tokens(r#"<div className="md:flex btn-primary" />"#, &mut tokens);
+        assert!(tokens.contains("md:flex"));
+        assert!(tokens.contains("flex"));
+        assert!(tokens.contains("btn-primary"));
+        assert!(parse_safelist_item("/^ant-/").unwrap().is_match("ant-btn"));
+        assert!(!parse_safelist_item("ant").unwrap().is_match("ant-btn"));
+    }
+
+    fn remove(css_code: &str, unused: &[&str]) -> String {
+        let mut test_utils = TestUtils::gen_css_ast(css_code.to_string(), true);
+        let ast = test_utils.ast.css_mut();
+        ast.ast.visit_mut_with(&mut UnusedSelectorsRemover {
+            is_unused: |name: &str| unused.contains(&name),
+        });
+        test_utils.css_ast_to_code()
+    }
+
+    fn usage(code: &str) -> (Vec<String>, bool) {
+        let test_utils = TestUtils::gen_js_ast(code);
+        let ast = &test_utils.ast.js().ast;
+        let mut usage = ClassNamesUsage {
+            bindings: import_bindings(ast, "./a.module.css?asmodule").unwrap(),
+            ..Default::default()
+        };
+        ast.visit_with(&mut usage);
+        let mut keys = usage.keys.into_iter().collect::<Vec<_>>();
+        keys.sort();
+        (keys, usage.all)
+    }
+}
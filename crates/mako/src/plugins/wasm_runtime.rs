@@ -1,4 +1,3 @@
-use std::collections::HashMap;
 use std::fs::File;
 use std::io::Read;
 use std::sync::Arc;
@@ -8,6 +7,7 @@ use wasmparser::{Import, Parser, Payload};
 
 use crate::ast::file::{Content, JsContent};
 use crate::compiler::Context;
+use crate::config::Platform;
 use crate::plugin::{Plugin, PluginLoadParam};
 
 pub struct WasmRuntimePlugin {}
@@ -20,16 +20,29 @@ impl Plugin for WasmRuntimePlugin {
     }
 
     fn runtime_plugins(&self, context: &Arc<Context>) -> anyhow::Result<Vec<String>> {
-        if context
-            .assets_info
-            .lock()
-            .unwrap()
-            .values()
-            .any(|info| info.ends_with(".wasm"))
-        {
-            Ok(vec![
-                include_str!("./wasm_runtime/wasm_runtime.js").to_string()
-            ])
+        let has_wasm = if context.config.single_file {
+            // wasm files are inlined instead of emitted
+            context
+                .module_graph
+                .read()
+                .unwrap()
+                .modules()
+                .iter()
+                .any(|m| m.id.id.ends_with(".wasm"))
+        } else {
+            context
+                .assets_info
+                .lock()
+                .unwrap()
+                .values()
+                .any(|info| info.ends_with(".wasm"))
+        };
+        if has_wasm {
+            Ok(vec![match context.config.platform {
+                Platform::Node => include_str!("./wasm_runtime/wasm_runtime_node.js"),
+                Platform::Browser => include_str!("./wasm_runtime/wasm_runtime.js"),
+            }
+            .to_string()])
         } else {
             Ok(vec![])
         }
@@ -43,45 +56,62 @@ impl Plugin for WasmRuntimePlugin {
         let file = param.file;
 
         if WASM_EXTENSIONS.contains(&file.extname.as_str()) {
-            let final_file_name = format!(
-                "{}.{}.{}",
-                file.get_file_stem(),
-                file.get_content_hash()?,
-                file.extname
-            );
-            _context.emit_assets(
-                file.pathname.to_string_lossy().to_string(),
-                final_file_name.clone(),
-            );
+            let final_file_name = if _context.config.single_file {
+                file.get_base64()?
+            } else {
+                let final_file_name = format!(
+                    "{}.{}.{}",
+                    file.get_file_stem(),
+                    file.get_content_hash()?,
+                    file.extname
+                );
+                _context.emit_assets(
+                    file.pathname.to_string_lossy().to_string(),
+                    final_file_name.clone(),
+                );
+                final_file_name
+            };
 
             let mut buffer = Vec::new();
             File::open(&file.path)?.read_to_end(&mut buffer)?;
-            // Parse wasm file to get imports
-            let mut wasm_import_object_map: HashMap<&str, Vec<String>> = HashMap::new();
-            Parser::new(0).parse_all(&buffer).for_each(|payload| {
-                if let Ok(Payload::ImportSection(imports)) = payload {
-                    imports.into_iter_with_offsets().for_each(|import| {
-                        if let Ok((
-                            _,
-                            Import {
-                                module,
-                                name,
-                                ty: _,
-                            },
-                        )) = import
-                        {
-                            if let Some(import_object) = wasm_import_object_map.get_mut(module) {
-                                import_object.push(name.to_string());
-                            } else {
-                                wasm_import_object_map.insert(module, vec![name.to_string()]);
+            // Parse wasm file to get imports and exports
+            let mut wasm_import_object_map: Vec<(&str, Vec<String>)> = vec![];
+            let mut wasm_exports: Vec<&str> = vec![];
+            Parser::new(0)
+                .parse_all(&buffer)
+                .for_each(|payload| match payload {
+                    Ok(Payload::ImportSection(imports)) => {
+                        imports.into_iter_with_offsets().for_each(|import| {
+                            if let Ok((
+                                _,
+                                Import {
+                                    module,
+                                    name,
+                                    ty: _,
+                                },
+                            )) = import
+                            {
+                                if let Some((_, import_object)) = wasm_import_object_map
+                                    .iter_mut()
+                                    .find(|(m, _)| *m == module)
+                                {
+                                    import_object.push(name.to_string());
+                                } else {
+                                    wasm_import_object_map.push((module, vec![name.to_string()]));
+                                }
                             }
-                        }
-                    });
-                }
-            });
+                        });
+                    }
+                    Ok(Payload::ExportSection(exports)) => {
+                        exports.into_iter().flatten().for_each(|export| {
+                            wasm_exports.push(export.name);
+                        });
+                    }
+                    _ => {}
+                });
 
             let mut module_import_code = String::new();
-            let mut wasm_import_object_code = String::new();
+            let mut wasm_import_object_code = vec![];
 
             for (index, (key, value)) in wasm_import_object_map.iter().enumerate() {
                 module_import_code.push_str(&format!(
@@ -90,7 +120,7 @@ impl Plugin for WasmRuntimePlugin {
                     module = key
                 ));
 
-                wasm_import_object_code.push_str(&format!(
+                wasm_import_object_code.push(format!(
                     "\"{module}\": {{ {names} }}",
                     module = key,
                     names = value
@@ -103,18 +133,27 @@ impl Plugin for WasmRuntimePlugin {
 
             let mut content = String::new();
             content.push_str(&module_import_code);
-
-            if wasm_import_object_code.is_empty() {
-                content.push_str(&format!(
-                    "module.exports = require._interopreRequireWasm(exports, \"{}\")",
-                    final_file_name
-                ));
-            } else {
+            // instantiate with top level await, so that the exports are ready
+            // when the importers are executed
+            content.push_str(&format!(
+                "const {{ instance: __mako_wasm_instance }} = await require._interopreRequireWasm(\"{}\", {{{}}});\n",
+                final_file_name,
+                wasm_import_object_code.join(", ")
+            ));
+            // export each item as a named export, so that the unused ones can be shaken
+            for (index, name) in wasm_exports.iter().enumerate() {
+                let exported = if is_identifier_name(name) {
+                    name.to_string()
+                } else {
+                    serde_json::to_string(name)?
+                };
                 content.push_str(&format!(
-                    "module.exports = require._interopreRequireWasm(exports, \"{}\", {{{}}})",
-                    final_file_name, wasm_import_object_code
+                    "const __mako_wasm_export_{index} = __mako_wasm_instance.exports[{key}];\nexport {{ __mako_wasm_export_{index} as {exported} }};\n",
+                    key = serde_json::to_string(name)?,
                 ));
             }
+            // keep the default export for `import wasm from` and `require()` consumers
+            content.push_str("export default __mako_wasm_instance.exports;\n");
 
             return Ok(Some(Content::Js(JsContent {
                 content,
@@ -126,6 +165,14 @@ impl Plugin for WasmRuntimePlugin {
     }
 }
 
+fn is_identifier_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::Arc;
@@ -150,6 +197,7 @@ mod tests {
         assert!(result.is_some());
         if let Some(Content::Js(js_content)) = result {
             assert!(js_content.content.contains("import * as module0 from"));
+            assert!(js_content.content.contains("as memory };"));
         }
     }
 
@@ -166,7 +214,50 @@ mod tests {
         let result = plugin.load(&param, &context).unwrap();
         assert!(result.is_some());
         if let Some(Content::Js(js_content)) = result {
-            assert!(!js_content.content.contains("import * as module0 from"))
+            assert!(!js_content.content.contains("import * as module0 from"));
+            assert!(js_content
+                .content
+                .contains("await require._interopreRequireWasm(\"add."));
+            assert!(js_content
+                .content
+                .contains("export { __mako_wasm_export_1 as add };"));
+            assert!(js_content
+                .content
+                .contains("export default __mako_wasm_instance.exports;"));
+        } else {
+            panic!("wasm should be loaded as js");
+        }
+    }
+
+    #[test]
+    fn test_wasm_runtime_load_with_non_identifier_export() {
+        let plugin = WasmRuntimePlugin {};
+        let context = Arc::new(Context {
+            ..Default::default()
+        });
+        // a module exporting a single `() -> ()` function named "add-one"
+        let wasm: &[u8] = &[
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // header
+            0x01, 0x04, 0x01, 0x60, 0x00, 0x00, // type section
+            0x03, 0x02, 0x01, 0x00, // function section
+            0x07, 0x0b, 0x01, 0x07, b'a', b'd', b'd', b'-', b'o', b'n', b'e', 0x00,
+            0x00, // export section
+            0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b, // code section
+        ];
+        let wasm_path = std::env::temp_dir().join("mako-wasm-runtime-non-identifier.wasm");
+        std::fs::write(&wasm_path, wasm).unwrap();
+        let file = File::new(wasm_path.to_string_lossy().to_string(), context.clone());
+        let param = PluginLoadParam { file: &file };
+        let result = plugin.load(&param, &context).unwrap();
+        if let Some(Content::Js(js_content)) = result {
+            assert!(js_content
+                .content
+                .contains("__mako_wasm_instance.exports[\"add-one\"]"));
+            assert!(js_content
+                .content
+                .contains("export { __mako_wasm_export_0 as \"add-one\" };"));
+        } else {
+            panic!("wasm should be loaded as js");
         }
     }
 }
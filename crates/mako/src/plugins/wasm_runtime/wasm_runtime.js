@@ -1,12 +1,9 @@
-requireModule._interopreRequireWasm = (exports, wasmModulePath, importsObj) => {
-  const request = fetch(wasmModulePath);
+requireModule._interopreRequireWasm = (wasmModulePath, importsObj) => {
+  const request = fetch(requireModule.publicPath + wasmModulePath);
   if (typeof WebAssembly.instantiateStreaming === 'function') {
-    return WebAssembly.instantiateStreaming(request, importsObj).then((res) =>
-      Object.assign(exports, res.instance.exports),
-    );
+    return WebAssembly.instantiateStreaming(request, importsObj);
   }
   return request
     .then((body) => body.arrayBuffer())
-    .then((bytes) => WebAssembly.instantiate(bytes, importsObj))
-    .then((res) => Object.assign(exports, res.instance.exports));
+    .then((bytes) => WebAssembly.instantiate(bytes, importsObj));
 };
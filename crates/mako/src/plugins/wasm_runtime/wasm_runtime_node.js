@@ -0,0 +1,14 @@
+requireModule._interopreRequireWasm = (wasmModulePath, importsObj) => {
+  // inlined as data url in single file bundles
+  const bytes = wasmModulePath.startsWith('data:')
+    ? Promise.resolve(
+        Buffer.from(
+          wasmModulePath.slice(wasmModulePath.indexOf(',') + 1),
+          'base64',
+        ),
+      )
+    : require('fs').promises.readFile(
+        require('path').join(__dirname, wasmModulePath),
+      );
+  return bytes.then((bytes) => WebAssembly.instantiate(bytes, importsObj));
+};
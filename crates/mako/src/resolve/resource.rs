@@ -94,9 +94,11 @@ impl ResolverResource {
                         .and_then(|v| v.as_str().map(|v| v.to_string()))
                 }),
             }),
-            ResolverResource::Shared(info) => {
-                info.deps.resolved_deps[0].resolver_resource.get_pkg_info()
-            }
+            ResolverResource::Shared(info) => info
+                .deps
+                .resolved_deps
+                .first()
+                .and_then(|dep| dep.resolver_resource.get_pkg_info()),
             _ => None,
         }
     }
@@ -2,6 +2,7 @@ globalThis.makoModuleHotUpdate(
   '__CHUNK_ID__',
   {
     modules: {},
+    removedModules: __REMOVED_MODULES__,
   },
   function (runtime) {
     __runtime_code__;
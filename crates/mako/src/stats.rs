@@ -13,8 +13,9 @@ use swc_core::common::source_map::SmallPos;
 
 use crate::ast::file::win_path;
 use crate::compiler::{Compiler, Context};
-use crate::features::rsc::{RscClientInfo, RscCssModules};
+use crate::features::rsc::{RscClientInfo, RscCssModules, RscServerAction};
 use crate::generate::chunk::ChunkType;
+use crate::utils::records::SplitChunkDiff;
 
 impl Compiler {
     pub fn create_stats_info(&self) -> StatsJsonMap {
@@ -59,16 +60,7 @@ impl Compiler {
             });
 
         // 获取 assets
-        stats_map.assets = stats_info
-            .get_assets()
-            .iter()
-            .map(|asset| StatsJsonAssetsItem {
-                assets_type: StatsJsonType::Asset(asset.assets_type.clone()),
-                size: asset.size,
-                name: asset.hashname.clone(),
-                path: asset.path.clone(),
-            })
-            .collect();
+        stats_map.update_assets(stats_info);
 
         let chunk_graph = self.context.chunk_graph.read().unwrap();
         let module_graph = self.context.module_graph.read().unwrap();
@@ -203,6 +195,8 @@ impl Compiler {
         stats_map.modules = stats_info.get_modules();
         stats_map.rsc_client_components = stats_info.get_rsc_client_components();
         stats_map.rsc_css_modules = stats_info.get_rsc_css_modules();
+        stats_map.rsc_server_actions = stats_info.get_rsc_server_actions();
+        stats_map.concatenation = stats_info.get_concatenation();
 
         stats_map
     }
@@ -227,6 +221,7 @@ impl Compiler {
         let mut max_map_size = 0;
         // 记录 name size map_size 的数组
         let mut assets_vec: Vec<(String, u64, u64)> = vec![];
+        let mut compressed_sizes: HashMap<String, Vec<(String, u64)>> = HashMap::new();
 
         // 生成 (name, size, map_size) 的 vec
         for asset in assets {
@@ -252,6 +247,17 @@ impl Compiler {
                     }
                 }
             }
+            // the compressed files of `output.compress` are printed in the line of the origin file
+            if let Some((origin, ext)) = name.rsplit_once('.')
+                && (ext == "gz" || ext == "br")
+                && assets_vec.last().is_some_and(|last| last.0 == origin)
+            {
+                compressed_sizes
+                    .entry(origin.to_string())
+                    .or_default()
+                    .push((ext.to_string(), asset.size));
+                continue;
+            }
             // 记录较长的 size
             if size_length > max_size {
                 max_size = size_length;
@@ -272,14 +278,28 @@ impl Compiler {
                 s if s.ends_with(".css") => file_name_str.magenta(),
                 _ => file_name_str.green(),
             };
+            let compressed = compressed_sizes
+                .get(&asset.0)
+                .map(|sizes| {
+                    sizes
+                        .iter()
+                        .map(|(ext, size)| {
+                            format!(" │ {}: {}", ext, human_readable_size(*size))
+                                .truecolor(128, 128, 128)
+                                .to_string()
+                        })
+                        .collect::<String>()
+                })
+                .unwrap_or_default();
             // 没有 map 的输出
             if asset.2 == 0 {
                 let size = human_readable_size(asset.1);
                 s.push_str(
                     format!(
-                        "{} {}\n",
+                        "{} {}{}\n",
                         color_file_name_str,
                         pad_string(&size, max_size, true),
+                        compressed,
                     )
                     .as_str(),
                 );
@@ -289,13 +309,14 @@ impl Compiler {
                 let map_size = human_readable_size(asset.2);
                 s.push_str(
                     format!(
-                        "{} {} {} {}\n",
+                        "{} {} {} {}{}\n",
                         color_file_name_str,
                         pad_string(&size, max_size, true)
                             .truecolor(128, 128, 128)
                             .bold(),
                         "│ map:".truecolor(128, 128, 128),
-                        pad_string(&map_size, max_map_size, true).truecolor(128, 128, 128)
+                        pad_string(&map_size, max_map_size, true).truecolor(128, 128, 128),
+                        compressed,
                     )
                     .as_str(),
                 );
@@ -304,6 +325,62 @@ impl Compiler {
 
         println!("{}", s.trim_end_matches('\n'));
     }
+
+    pub fn print_concatenation_report(&self) {
+        let Some(concatenation) = self.context.stats_info.get_concatenation() else {
+            return;
+        };
+        let mut s = format!(
+            "{} {} roots, {} inner modules, {} modules not concatenated\n",
+            "Module concatenation:".bold(),
+            concatenation.roots,
+            concatenation.inners,
+            concatenation.bailouts,
+        );
+        for module in &concatenation.modules {
+            if !matches!(module.status, ConcatenationStatus::Bailout) {
+                continue;
+            }
+            s.push_str(&format!("{}\n", module.id.cyan()));
+            for bailout in &module.bailouts {
+                s.push_str(&format!(
+                    "  - {} {}\n",
+                    bailout.reason,
+                    format!("({})", bailout.hint).truecolor(128, 128, 128)
+                ));
+            }
+        }
+        println!("{}", s.trim_end_matches('\n'));
+    }
+
+    pub fn print_split_chunks_report(&self) {
+        let Some(diff) = self.context.stats_info.get_split_chunks_diff() else {
+            return;
+        };
+        if diff.is_empty() {
+            println!(
+                "{} {}",
+                "Split chunks:".bold(),
+                "the same as the records".green()
+            );
+            return;
+        }
+        let mut s = format!(
+            "{} {} changed compared with the records\n",
+            "Split chunks:".bold(),
+            diff.len()
+        );
+        for chunk in &diff {
+            s.push_str(&format!("{}\n", chunk.name.cyan()));
+            for module in &chunk.added {
+                s.push_str(&format!("  {}\n", format!("+ {}", module).green()));
+            }
+            for module in &chunk.removed {
+                s.push_str(&format!("  {}\n", format!("- {}", module).red()));
+            }
+        }
+        println!("{}", s.trim_end_matches('\n'));
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -336,12 +413,54 @@ pub struct ModuleInfo {
     pub dependents: Vec<String>,
 }
 
+#[derive(Serialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ConcatenationStats {
+    // the modules other modules are concatenated into
+    pub roots: usize,
+    // the modules concatenated into the roots
+    pub inners: usize,
+    // the modules kept with their own module wrappers
+    pub bailouts: usize,
+    pub modules: Vec<ConcatenationModuleInfo>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub enum ConcatenationStatus {
+    Root,
+    Inner,
+    Bailout,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ConcatenationModuleInfo {
+    pub id: String,
+    pub status: ConcatenationStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub root: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub inners: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub bailouts: Vec<ConcatenationBailout>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct ConcatenationBailout {
+    pub reason: String,
+    pub hint: String,
+}
+
 #[derive(Debug)]
 pub struct StatsInfo {
     pub assets: Mutex<Vec<AssetsInfo>>,
     pub rsc_client_components: Mutex<Vec<RscClientInfo>>,
     pub rsc_css_modules: Mutex<Vec<RscCssModules>>,
+    pub rsc_server_actions: Mutex<Vec<RscServerAction>>,
     pub modules: Mutex<HashMap<String, ModuleInfo>>,
+    pub concatenation: Mutex<Option<ConcatenationStats>>,
+    pub split_chunks_diff: Mutex<Option<Vec<SplitChunkDiff>>>,
 }
 
 impl StatsInfo {
@@ -350,7 +469,10 @@ impl StatsInfo {
             assets: Mutex::new(vec![]),
             rsc_client_components: Mutex::new(vec![]),
             rsc_css_modules: Mutex::new(vec![]),
+            rsc_server_actions: Mutex::new(vec![]),
             modules: Mutex::new(HashMap::new()),
+            concatenation: Mutex::new(None),
+            split_chunks_diff: Mutex::new(None),
         }
     }
 
@@ -411,6 +533,22 @@ impl StatsInfo {
         self.modules.lock().unwrap().clone()
     }
 
+    pub fn get_concatenation(&self) -> Option<ConcatenationStats> {
+        self.concatenation.lock().unwrap().clone()
+    }
+
+    pub fn set_concatenation(&self, concatenation: ConcatenationStats) {
+        *self.concatenation.lock().unwrap() = Some(concatenation);
+    }
+
+    pub fn get_split_chunks_diff(&self) -> Option<Vec<SplitChunkDiff>> {
+        self.split_chunks_diff.lock().unwrap().clone()
+    }
+
+    pub fn set_split_chunks_diff(&self, diff: Vec<SplitChunkDiff>) {
+        *self.split_chunks_diff.lock().unwrap() = Some(diff);
+    }
+
     pub fn get_rsc_client_components(&self) -> Vec<RscClientInfo> {
         self.rsc_client_components.lock().unwrap().clone()
     }
@@ -429,6 +567,19 @@ impl StatsInfo {
     pub fn add_rsc_css_module(&self, rsc_css_module: RscCssModules) {
         self.rsc_css_modules.lock().unwrap().push(rsc_css_module)
     }
+
+    pub fn get_rsc_server_actions(&self) -> Vec<RscServerAction> {
+        let mut rsc_server_actions = self.rsc_server_actions.lock().unwrap().clone();
+        rsc_server_actions.sort_by(|a, b| a.id.cmp(&b.id));
+        rsc_server_actions
+    }
+
+    // replace the actions of the module, since it may be rebuilt in watch mode
+    pub fn set_rsc_server_actions(&self, module_id: &str, actions: Vec<RscServerAction>) {
+        let mut rsc_server_actions = self.rsc_server_actions.lock().unwrap();
+        rsc_server_actions.retain(|action| action.module_id != module_id);
+        rsc_server_actions.extend(actions);
+    }
 }
 
 impl Default for StatsInfo {
@@ -511,11 +662,27 @@ pub struct StatsJsonMap {
     rsc_client_components: Vec<RscClientInfo>,
     #[serde(rename = "rscCSSModules")]
     rsc_css_modules: Vec<RscCssModules>,
+    rsc_server_actions: Vec<RscServerAction>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    concatenation: Option<ConcatenationStats>,
     pub start_time: i64,
     pub end_time: i64,
 }
 
 impl StatsJsonMap {
+    pub(crate) fn update_assets(&mut self, stats_info: &StatsInfo) {
+        self.assets = stats_info
+            .get_assets()
+            .iter()
+            .map(|asset| StatsJsonAssetsItem {
+                assets_type: StatsJsonType::Asset(asset.assets_type.clone()),
+                size: asset.size,
+                name: asset.hashname.clone(),
+                path: asset.path.clone(),
+            })
+            .collect();
+    }
+
     fn new() -> Self {
         Self {
             hash: 0,
@@ -529,6 +696,8 @@ impl StatsJsonMap {
             entrypoints: HashMap::new(),
             rsc_client_components: vec![],
             rsc_css_modules: vec![],
+            rsc_server_actions: vec![],
+            concatenation: None,
             start_time: 0,
             end_time: 0,
         }
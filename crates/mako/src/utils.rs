@@ -2,6 +2,7 @@ pub(crate) mod id_helper;
 pub mod logger;
 #[cfg(feature = "profile")]
 pub mod profile_gui;
+pub(crate) mod records;
 #[cfg(test)]
 pub(crate) mod test_helper;
 pub mod thread_pool;
@@ -1,8 +1,9 @@
 use std::cmp::Ordering;
+use std::collections::HashSet;
 
 use petgraph::Direction::Incoming;
 
-use crate::module::ModuleId;
+use crate::module::{md5_hash, ModuleId};
 use crate::module_graph::ModuleGraph;
 
 pub fn compare_modules_by_incoming_edges(
@@ -27,3 +28,82 @@ pub fn assign_numeric_ids<T>(
         .enumerate()
         .for_each(|(i, item)| assign_id(item, i))
 }
+
+/**
+ * The short hashes of the names as the ids, which only change when the names change. The
+ * previously assigned ids are pinned first, then the others are assigned in the order of the
+ * names, and the colliding ones are rehashed with a salt, so that the ids don't depend on the
+ * order of the modules in the graph.
+ */
+pub struct DeterministicIds {
+    len: usize,
+    used: HashSet<String>,
+}
+
+impl DeterministicIds {
+    pub fn new(count: usize) -> Self {
+        Self {
+            len: deterministic_id_length(count),
+            used: HashSet::new(),
+        }
+    }
+
+    // false if the id is taken
+    pub fn pin(&mut self, id: &str) -> bool {
+        self.used.insert(id.to_string())
+    }
+
+    pub fn assign(&mut self, name: &str) -> String {
+        let mut salt = 0;
+        loop {
+            let id = deterministic_id(name, self.len, salt);
+            if self.used.insert(id.clone()) {
+                return id;
+            }
+            salt += 1;
+        }
+    }
+}
+
+pub fn deterministic_id(name: &str, len: usize, salt: usize) -> String {
+    if salt == 0 {
+        md5_hash(name, len)
+    } else {
+        md5_hash(&format!("{}#{}", name, salt), len)
+    }
+}
+
+// at least 4 hex digits, and 10 times of the count, so that the collisions are rare
+fn deterministic_id_length(count: usize) -> usize {
+    let mut len = 4;
+    while 16usize.pow(len as u32) < count * 10 {
+        len += 1;
+    }
+    len
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deterministic_ids() {
+        let mut ids = DeterministicIds::new(3);
+        let a = ids.assign("src/a.ts");
+        assert_eq!(a, md5_hash("src/a.ts", 4));
+        assert_eq!(ids.assign("src/b.ts"), md5_hash("src/b.ts", 4));
+
+        // the pinned id is kept, and the colliding one is rehashed with a salt
+        let mut ids = DeterministicIds::new(3);
+        assert!(ids.pin(&a));
+        assert!(!ids.pin(&a));
+        assert_eq!(ids.assign("src/a.ts"), deterministic_id("src/a.ts", 4, 1));
+    }
+
+    #[test]
+    fn test_deterministic_id_length() {
+        assert_eq!(deterministic_id_length(0), 4);
+        assert_eq!(deterministic_id_length(6553), 4);
+        assert_eq!(deterministic_id_length(6554), 5);
+    }
+}
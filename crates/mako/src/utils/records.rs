@@ -0,0 +1,314 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::compiler::Context;
+
+/**
+ * The records of the previous builds, they're committed with the sources so that the ids stay
+ * the same across the builds, e.g. a module keeps its id when a new module with a colliding
+ * hash is added.
+ */
+#[derive(Serialize, Deserialize, Default, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Records {
+    // relative path of the module => id
+    #[serde(default)]
+    pub module_ids: BTreeMap<String, String>,
+    // chunk name of `webpackChunkName` => id
+    #[serde(default)]
+    pub chunk_ids: BTreeMap<String, String>,
+    // name of the chunk split by code splitting => relative paths of its modules
+    #[serde(default)]
+    pub split_chunks: BTreeMap<String, Vec<String>>,
+    // relative path of the module => name of its split chunk group, e.g. `shared_{hash}`
+    #[serde(default)]
+    pub split_chunk_groups: BTreeMap<String, String>,
+}
+
+/**
+ * The modules added to or removed from a split chunk compared with the records, a new chunk
+ * only has the added ones and a removed chunk only has the removed ones.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct SplitChunkDiff {
+    pub name: String,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+impl Records {
+    pub fn path(context: &Arc<Context>) -> Option<PathBuf> {
+        context
+            .config
+            .records_path
+            .as_ref()
+            .map(|path| context.root.join(path))
+    }
+
+    pub fn load(path: &PathBuf) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Default::default());
+        }
+        let content = fs::read_to_string(path)?;
+        serde_json::from_str(&content)
+            .map_err(|e| anyhow!("Invalid records file {}: {}", path.display(), e))
+    }
+
+    pub fn diff_split_chunks(&self, previous: &Records) -> Vec<SplitChunkDiff> {
+        let empty = vec![];
+        let names = previous
+            .split_chunks
+            .keys()
+            .chain(self.split_chunks.keys())
+            .collect::<BTreeSet<_>>();
+        names
+            .into_iter()
+            .filter_map(|name| {
+                let before = previous.split_chunks.get(name).unwrap_or(&empty);
+                let after = self.split_chunks.get(name).unwrap_or(&empty);
+                let added = after
+                    .iter()
+                    .filter(|module| !before.contains(module))
+                    .cloned()
+                    .collect::<Vec<_>>();
+                let removed = before
+                    .iter()
+                    .filter(|module| !after.contains(module))
+                    .cloned()
+                    .collect::<Vec<_>>();
+                if added.is_empty() && removed.is_empty() {
+                    None
+                } else {
+                    Some(SplitChunkDiff {
+                        name: name.clone(),
+                        added,
+                        removed,
+                    })
+                }
+            })
+            .collect()
+    }
+
+    pub fn save(&self, path: &PathBuf) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, format!("{}\n", serde_json::to_string_pretty(self)?))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::compiler::Compiler;
+    use crate::config::Config;
+    use crate::module::md5_hash;
+
+    #[test]
+    fn test_diff_split_chunks() {
+        let records = |chunks: &[(&str, &[&str])]| Records {
+            split_chunks: chunks
+                .iter()
+                .map(|(name, modules)| {
+                    let modules = modules.iter().map(|m| m.to_string()).collect();
+                    (name.to_string(), modules)
+                })
+                .collect(),
+            ..Default::default()
+        };
+        let previous = records(&[("vendors_0", &["a", "b"]), ("vendors_1", &["c"])]);
+        let current = records(&[("vendors_0", &["a", "b"]), ("vendors_2", &["c", "d"])]);
+        assert_eq!(
+            current.diff_split_chunks(&previous),
+            vec![
+                SplitChunkDiff {
+                    name: "vendors_1".to_string(),
+                    added: vec![],
+                    removed: vec!["c".to_string()],
+                },
+                SplitChunkDiff {
+                    name: "vendors_2".to_string(),
+                    added: vec!["c".to_string(), "d".to_string()],
+                    removed: vec![],
+                },
+            ]
+        );
+        assert!(previous.diff_split_chunks(&previous).is_empty());
+    }
+
+    #[test]
+    fn test_deterministic_ids_with_records() {
+        let root = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test/build/deterministic-ids");
+        let records_path = root.join("records.json");
+        let compile = || {
+            let config = Config::new(&root, None, None).unwrap();
+            let compiler = Compiler::new(config, root.clone(), Default::default(), None).unwrap();
+            compiler.compile().unwrap();
+            let output = fs::read_to_string(root.join("dist/index.js")).unwrap();
+            (Records::load(&records_path).unwrap(), output)
+        };
+
+        let _ = fs::remove_file(&records_path);
+        let (records, output) = compile();
+        let a = &records.module_ids["a.js"];
+        assert_eq!(a, &md5_hash("a.js", 4));
+        assert!(output.contains(&format!("\"{}\"", a)), "{}", output);
+        assert_eq!(records.chunk_ids["lazy"], md5_hash("lazy", 4));
+
+        // the pinned ids are reused
+        let mut pinned = records;
+        pinned
+            .module_ids
+            .insert("a.js".to_string(), "beef".to_string());
+        pinned.save(&records_path).unwrap();
+        let (records, output) = compile();
+        assert_eq!(records.module_ids["a.js"], "beef");
+        assert!(output.contains("\"beef\""), "{}", output);
+        assert_eq!(records, pinned);
+
+        fs::remove_file(&records_path).unwrap();
+        fs::remove_dir_all(root.join("dist")).unwrap();
+    }
+
+    #[test]
+    fn test_deterministic_ids_after_update() {
+        let root = std::env::temp_dir().join("mako-deterministic-ids-update");
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        fs::write(
+            root.join("mako.config.json"),
+            r#"{ "moduleIdStrategy": "deterministic" }"#,
+        )
+        .unwrap();
+        fs::write(root.join("a.js"), "export const a = 'a';").unwrap();
+        fs::write(
+            root.join("index.js"),
+            "import { a } from './a';\nconsole.log(a);",
+        )
+        .unwrap();
+        let config = Config::new(&root, None, None).unwrap();
+        let compiler = Compiler::new(config, root.clone(), Default::default(), None).unwrap();
+        compiler.compile().unwrap();
+        let a = root.join("a.js").to_string_lossy().to_string();
+        compiler
+            .context
+            .deterministic_ids_map
+            .write()
+            .unwrap()
+            .insert(a.clone(), "beef".to_string());
+
+        fs::write(root.join("c.js"), "export const c = 'c';").unwrap();
+        fs::write(
+            root.join("index.js"),
+            "import { a } from './a';\nimport { c } from './c';\nconsole.log(a, c);",
+        )
+        .unwrap();
+        compiler.update(vec![root.join("index.js")]).unwrap();
+        let ids = compiler.context.deterministic_ids_map.read().unwrap();
+        assert_eq!(ids[&a], "beef");
+        let c = root.join("c.js").to_string_lossy().to_string();
+        assert_eq!(ids[&c], md5_hash("c.js", 4));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_split_chunks_with_records() {
+        let root =
+            PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test/build/split-chunks-records");
+        let records_path = root.join("records.json");
+        let compile = |cli_config: Option<&str>| {
+            let config = Config::new(&root, None, cli_config).unwrap();
+            let compiler = Compiler::new(config, root.clone(), Default::default(), None).unwrap();
+            compiler.compile().unwrap();
+            let diff = compiler.context.stats_info.get_split_chunks_diff();
+            (Records::load(&records_path).unwrap().split_chunks, diff)
+        };
+        let modules = |packages: &[&str]| {
+            packages
+                .iter()
+                .map(|p| format!("node_modules/{}/index.js", p))
+                .collect::<Vec<_>>()
+        };
+
+        let _ = fs::remove_file(&records_path);
+        let (split_chunks, diff) = compile(None);
+        assert_eq!(split_chunks["vendors_0"], modules(&["a", "c"]));
+        assert_eq!(split_chunks["vendors_1"], modules(&["b"]));
+        assert_eq!(diff, None);
+
+        // the new package would be packed with `a` without the records
+        let (split_chunks, diff) = compile(Some(r#"{ "entry": { "index": "index2.js" } }"#));
+        assert_eq!(split_chunks["vendors_0"], modules(&["a", "c"]));
+        assert_eq!(split_chunks["vendors_1"], modules(&["b"]));
+        assert_eq!(split_chunks["vendors_2"], modules(&["d"]));
+        assert_eq!(
+            diff,
+            Some(vec![SplitChunkDiff {
+                name: "vendors_2".to_string(),
+                added: modules(&["d"]),
+                removed: vec![],
+            }])
+        );
+
+        fs::remove_file(&records_path).unwrap();
+        fs::remove_dir_all(root.join("dist")).unwrap();
+    }
+
+    #[test]
+    fn test_split_chunk_groups_with_records() {
+        let root =
+            PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test/build/split-chunk-groups-records");
+        let records_path = root.join("records.json");
+        let compile = |cli_config: Option<&str>| {
+            let config = Config::new(&root, None, cli_config).unwrap();
+            let compiler = Compiler::new(config, root.clone(), Default::default(), None).unwrap();
+            compiler.compile().unwrap();
+            let chunk_graph = compiler.context.chunk_graph.read().unwrap();
+            let third_dependencies = chunk_graph
+                .get_chunk_by_name(&"third.js".to_string())
+                .map(|chunk| chunk_graph.dependencies_chunk(&chunk.id))
+                .unwrap_or_default()
+                .into_iter()
+                .map(|chunk_id| chunk_id.id)
+                .collect::<Vec<_>>();
+            (Records::load(&records_path).unwrap(), third_dependencies)
+        };
+        let a = "node_modules/a/index.js";
+        let d = "node_modules/d/index.js";
+
+        let _ = fs::remove_file(&records_path);
+        let (records, _) = compile(None);
+        let group = records.split_chunk_groups[a].clone();
+        assert!(group.starts_with("vendors_"), "{}", group);
+        assert_eq!(records.split_chunks[&group], vec![a.to_string()]);
+
+        // `a` would be moved to the group of the hash of all the three chunks without the
+        // records, and the new package gets its own group
+        let (records, third_dependencies) = compile(Some(
+            r#"{ "entry": { "index": "index.js", "other": "other.js", "third": "third.js" } }"#,
+        ));
+        assert_eq!(records.split_chunk_groups[a], group);
+        assert_eq!(records.split_chunks[&group], vec![a.to_string()]);
+        let new_group = &records.split_chunk_groups[d];
+        assert_ne!(new_group, &group);
+        assert_eq!(records.split_chunks[new_group], vec![d.to_string()]);
+        assert!(
+            third_dependencies.contains(&group),
+            "{:?}",
+            third_dependencies
+        );
+
+        fs::remove_file(&records_path).unwrap();
+        fs::remove_dir_all(root.join("dist")).unwrap();
+    }
+}
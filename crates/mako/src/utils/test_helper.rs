@@ -62,7 +62,7 @@ pub fn setup_compiler(base: &str, cleanup: bool) -> Compiler {
     }
     let mut config = Config::new(&root, None, None).unwrap();
     config.hmr = None;
-    config.minify = false;
+    config.minify = None;
     config.mode = Mode::Production;
     config.optimization = None;
 
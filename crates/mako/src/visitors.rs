@@ -3,9 +3,13 @@ pub(crate) mod async_module;
 pub(crate) mod clean_ctxt;
 pub(crate) mod common_js;
 pub(crate) mod css_assets;
+pub(crate) mod css_cascade_layers;
+pub(crate) mod css_color_functions;
 pub(crate) mod css_dep_analyzer;
 pub(crate) mod css_flexbugs;
 pub(crate) mod css_imports;
+pub(crate) mod css_logical_properties;
+pub(crate) mod css_modules_scope;
 pub(crate) mod css_px2rem;
 pub(crate) mod default_export_namer;
 pub(crate) mod dep_analyzer;
@@ -15,6 +19,7 @@ pub(crate) mod dynamic_import_to_require;
 pub(crate) mod env_replacer;
 pub(crate) mod fix_symbol_conflict;
 pub(crate) mod import_meta_env_replacer;
+pub(crate) mod import_meta_hot_replacer;
 pub(crate) mod import_template_to_string_literal;
 pub(crate) mod mako_require;
 pub(crate) mod meta_url_replacer;
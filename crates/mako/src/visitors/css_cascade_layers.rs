@@ -0,0 +1,171 @@
+use swc_core::css::ast::{
+    AtRule, AtRuleName, AtRulePrelude, ComponentValue, LayerName, LayerPrelude, Rule, SimpleBlock,
+    Stylesheet,
+};
+use swc_core::css::visit::{VisitMut, VisitMutWith};
+
+/**
+ * Lower @layer by unwrapping the layer blocks, top level layers are hoisted in the order
+ * they are declared, and unlayered rules are placed at last since they have the highest
+ * priority. Priority inside a single file is kept, but not across files.
+ */
+pub struct CSSCascadeLayers;
+
+fn is_layer(at_rule: &AtRule) -> bool {
+    matches!(&at_rule.name, AtRuleName::Ident(ident) if ident.value.eq_ignore_ascii_case("layer"))
+}
+
+fn layer_name(name: &LayerName) -> String {
+    name.name
+        .iter()
+        .map(|n| n.value.to_string())
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+// the index of layer, the layer is appended if it's not declared yet
+fn layer_index(layers: &mut Vec<(String, Vec<Rule>)>, name: String) -> usize {
+    match layers.iter().position(|(n, _)| *n == name) {
+        Some(index) => index,
+        None => {
+            layers.push((name, vec![]));
+            layers.len() - 1
+        }
+    }
+}
+
+fn block_to_rules(block: Option<SimpleBlock>) -> Vec<Rule> {
+    block
+        .map(|block| block.value)
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|v| match v {
+            ComponentValue::QualifiedRule(rule) => Some(Rule::QualifiedRule(rule)),
+            ComponentValue::AtRule(rule) => Some(Rule::AtRule(rule)),
+            _ => None,
+        })
+        .collect()
+}
+
+impl VisitMut for CSSCascadeLayers {
+    fn visit_mut_stylesheet(&mut self, n: &mut Stylesheet) {
+        // unwrap nested layers first, e.g. @layer a { @layer b {} } or @media { @layer a {} }
+        n.visit_mut_children_with(self);
+
+        if !n
+            .rules
+            .iter()
+            .any(|rule| matches!(rule, Rule::AtRule(box at_rule) if is_layer(at_rule)))
+        {
+            return;
+        }
+
+        // @charset and @import must be placed at the top
+        let mut leading = vec![];
+        let mut layers: Vec<(String, Vec<Rule>)> = vec![];
+        let mut unlayered = vec![];
+
+        for rule in n.rules.drain(..) {
+            match rule {
+                Rule::AtRule(box at_rule) if is_layer(&at_rule) => match at_rule.prelude {
+                    // @layer a, b;
+                    Some(box AtRulePrelude::LayerPrelude(LayerPrelude::NameList(list))) => {
+                        list.name_list.iter().for_each(|name| {
+                            layer_index(&mut layers, layer_name(name));
+                        });
+                    }
+                    // @layer a {}
+                    Some(box AtRulePrelude::LayerPrelude(LayerPrelude::Name(name))) => {
+                        let index = layer_index(&mut layers, layer_name(&name));
+                        layers[index].1.extend(block_to_rules(at_rule.block));
+                    }
+                    // anonymous layer
+                    _ => {
+                        let index = layers.len();
+                        layers.push((format!("<anonymous {}>", index), vec![]));
+                        layers[index].1.extend(block_to_rules(at_rule.block));
+                    }
+                },
+                Rule::AtRule(box AtRule {
+                    name: AtRuleName::Ident(ref ident),
+                    ..
+                }) if (ident.value.eq_ignore_ascii_case("charset")
+                    || ident.value.eq_ignore_ascii_case("import"))
+                    && unlayered.is_empty()
+                    && layers.iter().all(|(_, rules)| rules.is_empty()) =>
+                {
+                    leading.push(rule);
+                }
+                _ => unlayered.push(rule),
+            }
+        }
+
+        n.rules = leading
+            .into_iter()
+            .chain(layers.into_iter().flat_map(|(_, rules)| rules))
+            .chain(unlayered)
+            .collect();
+    }
+
+    fn visit_mut_simple_block(&mut self, n: &mut SimpleBlock) {
+        n.visit_mut_children_with(self);
+
+        if !n
+            .value
+            .iter()
+            .any(|v| matches!(v, ComponentValue::AtRule(box at_rule) if is_layer(at_rule)))
+        {
+            return;
+        }
+        // layers in blocks are unwrapped in place
+        n.value = n
+            .value
+            .drain(..)
+            .flat_map(|v| match v {
+                ComponentValue::AtRule(box at_rule) if is_layer(&at_rule) => {
+                    at_rule.block.map(|block| block.value).unwrap_or_default()
+                }
+                _ => vec![v],
+            })
+            .collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use swc_core::css::visit::VisitMutWith;
+
+    use crate::ast::tests::TestUtils;
+
+    #[test]
+    fn test_layer_order() {
+        assert_eq!(
+            run("@layer base, theme;a{color:red}@layer theme{b{color:blue}}@layer base{c{color:green}}"),
+            "c{color:green}b{color:blue}a{color:red}"
+        );
+    }
+
+    #[test]
+    fn test_import_kept_at_top() {
+        assert_eq!(
+            run(r#"@import "a.css";@layer a{b{color:blue}}"#),
+            r#"@import"a.css";b{color:blue}"#
+        );
+    }
+
+    #[test]
+    fn test_nested_layers() {
+        assert_eq!(
+            run("@layer a{@layer b{c{color:red}}}@media print{@layer a{d{color:red}}}"),
+            "c{color:red}@media print{d{color:red}}"
+        );
+    }
+
+    fn run(css_code: &str) -> String {
+        let mut test_utils = TestUtils::gen_css_ast(css_code.to_string(), true);
+        let ast = test_utils.ast.css_mut();
+        let mut visitor = super::CSSCascadeLayers {};
+        ast.ast.visit_mut_with(&mut visitor);
+        test_utils.css_ast_to_code()
+    }
+}
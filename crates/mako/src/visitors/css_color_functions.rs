@@ -0,0 +1,404 @@
+use std::f64::consts::PI;
+
+use swc_core::common::DUMMY_SP;
+use swc_core::css::ast::{
+    AbsoluteColorBase, AlphaValue, Angle, Color, ComponentValue, Delimiter, DelimiterValue,
+    Function, FunctionName, HexColor, Hue, Ident, Integer, Number, Percentage,
+};
+use swc_core::css::utils::{angle_to_deg, round_alpha, NAMED_COLORS};
+use swc_core::css::visit::{VisitMut, VisitMutWith};
+
+/**
+ * Lower oklab(), oklch() and color-mix() with static arguments to rgb colors, colors with
+ * var() or other unknown arguments are kept as is.
+ */
+pub struct CSSColorFunctions {
+    pub oklab: bool,
+    pub color_mix: bool,
+}
+
+// srgb color with alpha, all in 0..1
+type Rgba = [f64; 4];
+
+impl VisitMut for CSSColorFunctions {
+    fn visit_mut_component_value(&mut self, n: &mut ComponentValue) {
+        // lower the nested colors first, e.g. color-mix(in srgb, oklch(...), red)
+        n.visit_mut_children_with(self);
+
+        if let ComponentValue::Color(box Color::AbsoluteColorBase(AbsoluteColorBase::Function(
+            function,
+        ))) = n
+        {
+            let name = function_name(function);
+            let rgba = match name.as_str() {
+                "oklab" | "oklch" if self.oklab => parse_color_function(function),
+                "color-mix" if self.color_mix => parse_color_mix(function),
+                _ => None,
+            };
+            if let Some(rgba) = rgba {
+                *n = to_component_value(rgba);
+            }
+        }
+    }
+}
+
+fn function_name(function: &Function) -> String {
+    match &function.name {
+        FunctionName::Ident(ident) => ident.value.to_ascii_lowercase().to_string(),
+        FunctionName::DashedIdent(ident) => ident.value.to_string(),
+    }
+}
+
+fn parse_color(value: &ComponentValue) -> Option<Rgba> {
+    match value {
+        ComponentValue::Color(box Color::AbsoluteColorBase(base)) => match base {
+            AbsoluteColorBase::HexColor(HexColor { value, .. }) => parse_hex(value),
+            AbsoluteColorBase::NamedColorOrTransparent(Ident { value, .. }) => parse_named(value),
+            AbsoluteColorBase::Function(function) => parse_color_function(function),
+        },
+        ComponentValue::Ident(box Ident { value, .. }) => parse_named(value),
+        _ => None,
+    }
+}
+
+fn parse_hex(hex: &str) -> Option<Rgba> {
+    let digits = hex
+        .chars()
+        .map(|c| c.to_digit(16).map(|d| d as f64))
+        .collect::<Option<Vec<_>>>()?;
+    let channels = match digits.len() {
+        3 | 4 => digits.iter().map(|d| d * 17.0 / 255.0).collect::<Vec<_>>(),
+        6 | 8 => digits
+            .chunks(2)
+            .map(|c| (c[0] * 16.0 + c[1]) / 255.0)
+            .collect::<Vec<_>>(),
+        _ => return None,
+    };
+    Some([
+        channels[0],
+        channels[1],
+        channels[2],
+        channels.get(3).cloned().unwrap_or(1.0),
+    ])
+}
+
+fn parse_named(name: &str) -> Option<Rgba> {
+    let name = name.to_ascii_lowercase();
+    if name == "transparent" {
+        return Some([0.0, 0.0, 0.0, 0.0]);
+    }
+    NAMED_COLORS.get(&name.as_str().into()).map(|color| {
+        [
+            color.rgb[0] as f64 / 255.0,
+            color.rgb[1] as f64 / 255.0,
+            color.rgb[2] as f64 / 255.0,
+            1.0,
+        ]
+    })
+}
+
+/**
+ * Numeric value of an argument, percentages are mapped to 0..percent_scale.
+ */
+fn number(value: &ComponentValue, percent_scale: f64) -> Option<f64> {
+    match value {
+        ComponentValue::Number(box Number { value, .. }) => Some(*value),
+        ComponentValue::Integer(box Integer { value, .. }) => Some(*value as f64),
+        ComponentValue::Percentage(box Percentage {
+            value: Number { value, .. },
+            ..
+        }) => Some(value / 100.0 * percent_scale),
+        ComponentValue::AlphaValue(box AlphaValue::Number(Number { value, .. })) => Some(*value),
+        ComponentValue::AlphaValue(box AlphaValue::Percentage(Percentage {
+            value: Number { value, .. },
+            ..
+        })) => Some(value / 100.0 * percent_scale),
+        ComponentValue::Hue(box Hue::Number(Number { value, .. })) => Some(*value),
+        ComponentValue::Hue(box Hue::Angle(Angle {
+            value: Number { value, .. },
+            unit,
+            ..
+        })) => Some(angle_to_deg(*value, &unit.value)),
+        // none is treated as 0
+        ComponentValue::Ident(box Ident { value, .. }) if value.eq_ignore_ascii_case("none") => {
+            Some(0.0)
+        }
+        _ => None,
+    }
+}
+
+// rgb(), rgba(), oklab() and oklch()
+fn parse_color_function(function: &Function) -> Option<Rgba> {
+    let name = function_name(function);
+    let args = function
+        .value
+        .iter()
+        .filter(|v| {
+            !matches!(
+                v,
+                ComponentValue::Delimiter(box Delimiter {
+                    value: DelimiterValue::Comma | DelimiterValue::Solidus,
+                    ..
+                })
+            )
+        })
+        .collect::<Vec<_>>();
+    if args.len() != 3 && args.len() != 4 {
+        return None;
+    }
+    let alpha = match args.get(3) {
+        Some(alpha) => number(alpha, 1.0)?,
+        None => 1.0,
+    };
+    let [a, b, c] = [args[0], args[1], args[2]];
+
+    let rgb = match name.as_str() {
+        "rgb" | "rgba" => [
+            number(a, 255.0)? / 255.0,
+            number(b, 255.0)? / 255.0,
+            number(c, 255.0)? / 255.0,
+        ],
+        // 100% of a and b are 0.4
+        "oklab" => oklab_to_srgb([number(a, 1.0)?, number(b, 0.4)?, number(c, 0.4)?]),
+        // 100% of chroma is 0.4
+        "oklch" => {
+            let (l, chroma, hue) = (number(a, 1.0)?, number(b, 0.4)?, number(c, 1.0)?);
+            let hue = hue * PI / 180.0;
+            oklab_to_srgb([l, chroma * hue.cos(), chroma * hue.sin()])
+        }
+        _ => return None,
+    };
+    Some([
+        rgb[0].clamp(0.0, 1.0),
+        rgb[1].clamp(0.0, 1.0),
+        rgb[2].clamp(0.0, 1.0),
+        alpha.clamp(0.0, 1.0),
+    ])
+}
+
+// color-mix(in <space>, <color> <percentage>?, <color> <percentage>?)
+// ref: https://www.w3.org/TR/css-color-5/#color-mix
+fn parse_color_mix(function: &Function) -> Option<Rgba> {
+    let groups = function
+        .value
+        .split(|v| {
+            matches!(
+                v,
+                ComponentValue::Delimiter(box Delimiter {
+                    value: DelimiterValue::Comma,
+                    ..
+                })
+            )
+        })
+        .collect::<Vec<_>>();
+    let [space, first, second] = groups.as_slice() else {
+        return None;
+    };
+    let space = match space {
+        [ComponentValue::Ident(box Ident { value: keyword, .. }), ComponentValue::Ident(box Ident { value: space, .. })]
+            if keyword.eq_ignore_ascii_case("in") =>
+        {
+            space.to_ascii_lowercase()
+        }
+        _ => return None,
+    };
+    if space != "srgb" && space != "oklab" {
+        return None;
+    }
+
+    let parse_item = |item: &[ComponentValue]| -> Option<(Rgba, Option<f64>)> {
+        match item {
+            [color] => Some((parse_color(color)?, None)),
+            [color, percentage @ ComponentValue::Percentage(_)]
+            | [percentage @ ComponentValue::Percentage(_), color] => {
+                Some((parse_color(color)?, Some(number(percentage, 1.0)?)))
+            }
+            _ => None,
+        }
+    };
+    let (first, p1) = parse_item(first)?;
+    let (second, p2) = parse_item(second)?;
+
+    let (p1, p2) = match (p1, p2) {
+        (None, None) => (0.5, 0.5),
+        (Some(p1), None) => (p1, 1.0 - p1),
+        (None, Some(p2)) => (1.0 - p2, p2),
+        (Some(p1), Some(p2)) => (p1, p2),
+    };
+    let sum = p1 + p2;
+    if sum <= 0.0 {
+        return None;
+    }
+    let (w1, w2) = (p1 / sum, p2 / sum);
+    // the alpha is scaled when the sum of percentages is less than 100%
+    let alpha_multiplier = sum.min(1.0);
+
+    // mix with premultiplied alpha
+    let alpha = first[3] * w1 + second[3] * w2;
+    let (c1, c2) = if space == "oklab" {
+        (
+            srgb_to_oklab([first[0], first[1], first[2]]),
+            srgb_to_oklab([second[0], second[1], second[2]]),
+        )
+    } else {
+        (
+            [first[0], first[1], first[2]],
+            [second[0], second[1], second[2]],
+        )
+    };
+    let mut mixed = [0.0; 3];
+    for i in 0..3 {
+        mixed[i] = if alpha == 0.0 {
+            0.0
+        } else {
+            (c1[i] * first[3] * w1 + c2[i] * second[3] * w2) / alpha
+        };
+    }
+    let rgb = if space == "oklab" {
+        oklab_to_srgb(mixed)
+    } else {
+        mixed
+    };
+    Some([
+        rgb[0].clamp(0.0, 1.0),
+        rgb[1].clamp(0.0, 1.0),
+        rgb[2].clamp(0.0, 1.0),
+        (alpha * alpha_multiplier).clamp(0.0, 1.0),
+    ])
+}
+
+// ref: https://bottosson.github.io/posts/oklab/
+fn oklab_to_srgb([l, a, b]: [f64; 3]) -> [f64; 3] {
+    let l_ = (l + 0.3963377774 * a + 0.2158037573 * b).powi(3);
+    let m_ = (l - 0.1055613458 * a - 0.0638541728 * b).powi(3);
+    let s_ = (l - 0.0894841775 * a - 1.2914855480 * b).powi(3);
+    [
+        4.0767416621 * l_ - 3.3077115913 * m_ + 0.2309699292 * s_,
+        -1.2684380046 * l_ + 2.6097574011 * m_ - 0.3413193965 * s_,
+        -0.0041960863 * l_ - 0.7034186147 * m_ + 1.7076147010 * s_,
+    ]
+    .map(|c| {
+        if c <= 0.0031308 {
+            12.92 * c
+        } else {
+            1.055 * c.powf(1.0 / 2.4) - 0.055
+        }
+    })
+}
+
+fn srgb_to_oklab(rgb: [f64; 3]) -> [f64; 3] {
+    let [r, g, b] = rgb.map(|c| {
+        if c <= 0.04045 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    });
+    let l = (0.4122214708 * r + 0.5363325363 * g + 0.0514459929 * b).cbrt();
+    let m = (0.2119034982 * r + 0.6806995451 * g + 0.1073969566 * b).cbrt();
+    let s = (0.0883024619 * r + 0.2817188376 * g + 0.6299787005 * b).cbrt();
+    [
+        0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s,
+        1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s,
+        0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s,
+    ]
+}
+
+fn to_component_value(rgba: Rgba) -> ComponentValue {
+    let [r, g, b] = [rgba[0], rgba[1], rgba[2]].map(|c| (c * 255.0).round() as i64);
+    let alpha = round_alpha(rgba[3]);
+
+    let color = if alpha == 1.0 {
+        let hex = format!("{:02x}{:02x}{:02x}", r, g, b);
+        AbsoluteColorBase::HexColor(HexColor {
+            span: DUMMY_SP,
+            value: hex.clone().into(),
+            raw: Some(hex.into()),
+        })
+    } else {
+        let integer = |value: i64| {
+            ComponentValue::Integer(Box::new(Integer {
+                span: DUMMY_SP,
+                value,
+                raw: None,
+            }))
+        };
+        let comma = || {
+            ComponentValue::Delimiter(Box::new(Delimiter {
+                span: DUMMY_SP,
+                value: DelimiterValue::Comma,
+            }))
+        };
+        AbsoluteColorBase::Function(Function {
+            span: DUMMY_SP,
+            name: FunctionName::Ident(Ident {
+                span: DUMMY_SP,
+                value: "rgba".into(),
+                raw: None,
+            }),
+            value: vec![
+                integer(r),
+                comma(),
+                integer(g),
+                comma(),
+                integer(b),
+                comma(),
+                ComponentValue::AlphaValue(Box::new(AlphaValue::Number(Number {
+                    span: DUMMY_SP,
+                    value: alpha,
+                    raw: None,
+                }))),
+            ],
+        })
+    };
+    ComponentValue::Color(Box::new(Color::AbsoluteColorBase(color)))
+}
+
+#[cfg(test)]
+mod tests {
+    use swc_core::css::visit::VisitMutWith;
+
+    use crate::ast::tests::TestUtils;
+
+    #[test]
+    fn test_oklab() {
+        assert_eq!(run("a{color:oklch(62.8% 0.2577 29.23)}"), "a{color:#f00}");
+        assert_eq!(
+            run("a{color:oklab(0% 0 0 / 50%)}"),
+            "a{color:rgba(0,0,0,.5)}"
+        );
+        // kept as is when the arguments are not static
+        assert!(run("a{color:oklch(50% var(--c) 20)}").contains("oklch("));
+    }
+
+    #[test]
+    fn test_color_mix() {
+        assert_eq!(
+            run("a{color:color-mix(in srgb, red, blue)}"),
+            "a{color:#800080}"
+        );
+        assert_eq!(
+            run("a{color:color-mix(in srgb, #fff 25%, black)}"),
+            "a{color:#404040}"
+        );
+        assert_eq!(
+            run("a{color:color-mix(in srgb, red 20%, blue 20%)}"),
+            "a{color:rgba(128,0,128,.4)}"
+        );
+        assert_eq!(
+            run("a{color:color-mix(in srgb, var(--a), blue)}"),
+            "a{color:color-mix(in srgb,var(--a),blue)}"
+        );
+    }
+
+    fn run(css_code: &str) -> String {
+        let mut test_utils = TestUtils::gen_css_ast(css_code.to_string(), true);
+        let ast = test_utils.ast.css_mut();
+        let mut visitor = super::CSSColorFunctions {
+            oklab: true,
+            color_mix: true,
+        };
+        ast.ast.visit_mut_with(&mut visitor);
+        test_utils.css_ast_to_code()
+    }
+}
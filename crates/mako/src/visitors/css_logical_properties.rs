@@ -0,0 +1,253 @@
+use swc_core::common::DUMMY_SP;
+use swc_core::css::ast::{ComponentValue, Declaration, DeclarationName, Ident, SimpleBlock};
+use swc_core::css::visit::{VisitMut, VisitMutWith};
+
+use crate::config::CssDirection;
+
+/**
+ * Lower logical properties to physical ones, like postcss-logical with `dir: ltr` or `dir: rtl`,
+ * e.g. margin-inline: 1px 2px => margin-left: 1px; margin-right: 2px
+ */
+pub struct CSSLogicalProperties {
+    pub dir: CssDirection,
+}
+
+impl CSSLogicalProperties {
+    fn lower(&self, decl: &Declaration) -> Option<Vec<Declaration>> {
+        let DeclarationName::Ident(Ident { value: name, .. }) = &decl.name else {
+            return None;
+        };
+        let name = name.to_string();
+
+        if let Some(physical) = physical_property(&name) {
+            return Some(vec![self.with_name(decl, physical, decl.value.clone())]);
+        }
+
+        // shorthands with start and end values, e.g. margin-inline: 1px 2px
+        if let Some((start, end)) = match name.as_str() {
+            "margin-inline" => Some(("margin-left", "margin-right")),
+            "margin-block" => Some(("margin-top", "margin-bottom")),
+            "padding-inline" => Some(("padding-left", "padding-right")),
+            "padding-block" => Some(("padding-top", "padding-bottom")),
+            "inset-inline" => Some(("left", "right")),
+            "inset-block" => Some(("top", "bottom")),
+            _ => None,
+        } {
+            let (start_value, end_value) = match decl.value.as_slice() {
+                [value] => (vec![value.clone()], vec![value.clone()]),
+                [start_value, end_value] => (vec![start_value.clone()], vec![end_value.clone()]),
+                // var() or something else which can't be split
+                _ => return None,
+            };
+            return Some(vec![
+                self.with_name(decl, start, start_value),
+                self.with_name(decl, end, end_value),
+            ]);
+        }
+
+        // shorthands which set the same value to both sides, e.g. border-inline: 1px solid
+        let sides = match name.as_str() {
+            "border-inline" => ["border-left", "border-right"],
+            "border-block" => ["border-top", "border-bottom"],
+            "border-inline-width" => ["border-left-width", "border-right-width"],
+            "border-block-width" => ["border-top-width", "border-bottom-width"],
+            "border-inline-style" => ["border-left-style", "border-right-style"],
+            "border-block-style" => ["border-top-style", "border-bottom-style"],
+            "border-inline-color" => ["border-left-color", "border-right-color"],
+            "border-block-color" => ["border-top-color", "border-bottom-color"],
+            "inset" => return self.lower_inset(decl),
+            _ => return None,
+        };
+        Some(
+            sides
+                .iter()
+                .map(|side| self.with_name(decl, side, decl.value.clone()))
+                .collect(),
+        )
+    }
+
+    // the physical properties above are of `ltr`, left and right are swapped for `rtl`
+    fn with_name(&self, decl: &Declaration, name: &str, value: Vec<ComponentValue>) -> Declaration {
+        let name = match self.dir {
+            CssDirection::Ltr => name.to_string(),
+            // a physical property has one side at most
+            CssDirection::Rtl if name.contains("left") => name.replace("left", "right"),
+            CssDirection::Rtl => name.replace("right", "left"),
+        };
+        with_name(decl, &name, value)
+    }
+
+    // inset: top right bottom left, with the same rules as margin, it's not affected by `dir`
+    fn lower_inset(&self, decl: &Declaration) -> Option<Vec<Declaration>> {
+        let values = &decl.value;
+        let (top, right, bottom, left) = match values.as_slice() {
+            [all] => (all, all, all, all),
+            [vertical, horizontal] => (vertical, horizontal, vertical, horizontal),
+            [top, horizontal, bottom] => (top, horizontal, bottom, horizontal),
+            [top, right, bottom, left] => (top, right, bottom, left),
+            _ => return None,
+        };
+        Some(
+            [
+                ("top", top),
+                ("right", right),
+                ("bottom", bottom),
+                ("left", left),
+            ]
+            .into_iter()
+            .map(|(side, value)| with_name(decl, side, vec![value.clone()]))
+            .collect(),
+        )
+    }
+}
+
+fn physical_property(name: &str) -> Option<&'static str> {
+    let physical = match name {
+        "margin-inline-start" => "margin-left",
+        "margin-inline-end" => "margin-right",
+        "margin-block-start" => "margin-top",
+        "margin-block-end" => "margin-bottom",
+        "padding-inline-start" => "padding-left",
+        "padding-inline-end" => "padding-right",
+        "padding-block-start" => "padding-top",
+        "padding-block-end" => "padding-bottom",
+        "inset-inline-start" => "left",
+        "inset-inline-end" => "right",
+        "inset-block-start" => "top",
+        "inset-block-end" => "bottom",
+        "border-inline-start" => "border-left",
+        "border-inline-end" => "border-right",
+        "border-block-start" => "border-top",
+        "border-block-end" => "border-bottom",
+        "border-inline-start-width" => "border-left-width",
+        "border-inline-end-width" => "border-right-width",
+        "border-block-start-width" => "border-top-width",
+        "border-block-end-width" => "border-bottom-width",
+        "border-inline-start-style" => "border-left-style",
+        "border-inline-end-style" => "border-right-style",
+        "border-block-start-style" => "border-top-style",
+        "border-block-end-style" => "border-bottom-style",
+        "border-inline-start-color" => "border-left-color",
+        "border-inline-end-color" => "border-right-color",
+        "border-block-start-color" => "border-top-color",
+        "border-block-end-color" => "border-bottom-color",
+        "border-start-start-radius" => "border-top-left-radius",
+        "border-start-end-radius" => "border-top-right-radius",
+        "border-end-start-radius" => "border-bottom-left-radius",
+        "border-end-end-radius" => "border-bottom-right-radius",
+        "inline-size" => "width",
+        "block-size" => "height",
+        "min-inline-size" => "min-width",
+        "min-block-size" => "min-height",
+        "max-inline-size" => "max-width",
+        "max-block-size" => "max-height",
+        _ => return None,
+    };
+    Some(physical)
+}
+
+fn with_name(decl: &Declaration, name: &str, value: Vec<ComponentValue>) -> Declaration {
+    Declaration {
+        span: decl.span,
+        name: DeclarationName::Ident(Ident {
+            span: DUMMY_SP,
+            value: name.into(),
+            raw: None,
+        }),
+        value,
+        important: decl.important.clone(),
+    }
+}
+
+impl VisitMut for CSSLogicalProperties {
+    fn visit_mut_simple_block(&mut self, n: &mut SimpleBlock) {
+        n.visit_mut_children_with(self);
+
+        if !n.value.iter().any(
+            |v| matches!(v, ComponentValue::Declaration(box decl) if self.lower(decl).is_some()),
+        ) {
+            return;
+        }
+        n.value = n
+            .value
+            .drain(..)
+            .flat_map(|v| match &v {
+                ComponentValue::Declaration(box decl) => match self.lower(decl) {
+                    Some(decls) => decls
+                        .into_iter()
+                        .map(|d| ComponentValue::Declaration(Box::new(d)))
+                        .collect(),
+                    None => vec![v],
+                },
+                _ => vec![v],
+            })
+            .collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use swc_core::css::visit::VisitMutWith;
+
+    use crate::ast::tests::TestUtils;
+    use crate::config::CssDirection;
+
+    #[test]
+    fn test_longhands() {
+        assert_eq!(
+            run("a{margin-inline-start:1px;padding-block-end:2px;inline-size:10px}"),
+            "a{margin-left:1px;padding-bottom:2px;width:10px}"
+        );
+        assert_eq!(
+            run("a{border-inline-start:1px solid red!important}"),
+            "a{border-left:1px solid red!important}"
+        );
+    }
+
+    #[test]
+    fn test_shorthands() {
+        assert_eq!(
+            run("a{margin-inline:1px 2px;padding-block:3px}"),
+            "a{margin-left:1px;margin-right:2px;padding-top:3px;padding-bottom:3px}"
+        );
+        assert_eq!(
+            run("a{inset:0 1px}"),
+            "a{top:0;right:1px;bottom:0;left:1px}"
+        );
+        assert_eq!(
+            run("a{border-block:1px solid}"),
+            "a{border-top:1px solid;border-bottom:1px solid}"
+        );
+    }
+
+    #[test]
+    fn test_rtl() {
+        assert_eq!(
+            run_with_dir(
+                "a{margin-inline:1px 2px;border-start-end-radius:3px;inset:0 1px 2px 3px}",
+                CssDirection::Rtl
+            ),
+            "a{margin-right:1px;margin-left:2px;border-top-left-radius:3px;top:0;right:1px;bottom:2px;left:3px}"
+        );
+    }
+
+    #[test]
+    fn test_nested_blocks() {
+        assert_eq!(
+            run("@media (min-width:100px){a{margin-inline-end:1px}}"),
+            "@media(min-width:100px){a{margin-right:1px}}"
+        );
+    }
+
+    fn run(css_code: &str) -> String {
+        run_with_dir(css_code, CssDirection::Ltr)
+    }
+
+    fn run_with_dir(css_code: &str, dir: CssDirection) -> String {
+        let mut test_utils = TestUtils::gen_css_ast(css_code.to_string(), true);
+        let ast = test_utils.ast.css_mut();
+        let mut visitor = super::CSSLogicalProperties { dir };
+        ast.ast.visit_mut_with(&mut visitor);
+        test_utils.css_ast_to_code()
+    }
+}
@@ -0,0 +1,172 @@
+use std::collections::HashSet;
+
+use swc_core::css::ast::{
+    ComplexSelector, ComplexSelectorChildren, KeyframesName, PseudoClassSelectorChildren,
+    QualifiedRule, QualifiedRulePrelude, SubclassSelector,
+};
+use swc_core::css::visit::{Visit, VisitWith};
+use swc_core::ecma::atoms::JsWord;
+
+use crate::ast::css_ast::CssAst;
+
+/**
+ * Collect class names, ids and keyframes which are explicitly marked as local with `:local`,
+ * used by the global mode of css modules.
+ */
+#[derive(Default)]
+pub struct CSSLocalNames {
+    pub names: HashSet<JsWord>,
+}
+
+impl CSSLocalNames {
+    fn collect_complex_selector(&mut self, n: &ComplexSelector, mut is_local: bool) {
+        for child in &n.children {
+            let ComplexSelectorChildren::CompoundSelector(compound) = child else {
+                continue;
+            };
+            for sel in &compound.subclass_selectors {
+                match sel {
+                    SubclassSelector::Class(class) if is_local => {
+                        self.names.insert(class.text.value.clone());
+                    }
+                    SubclassSelector::Id(id) if is_local => {
+                        self.names.insert(id.text.value.clone());
+                    }
+                    SubclassSelector::PseudoClass(pseudo) => {
+                        let is_local_pseudo = pseudo.name.value == "local";
+                        if !is_local_pseudo && pseudo.name.value != "global" {
+                            continue;
+                        }
+                        match pseudo.children.as_ref().and_then(|c| c.first()) {
+                            // :local(.a) or :global(.a)
+                            Some(PseudoClassSelectorChildren::ComplexSelector(selector)) => {
+                                self.collect_complex_selector(selector, is_local_pseudo);
+                            }
+                            // :local .a or :global .a
+                            _ => is_local = is_local_pseudo,
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+impl Visit for CSSLocalNames {
+    fn visit_complex_selector(&mut self, n: &ComplexSelector) {
+        self.collect_complex_selector(n, false);
+    }
+
+    fn visit_keyframes_name(&mut self, n: &KeyframesName) {
+        match n {
+            KeyframesName::PseudoFunction(f) if f.pseudo.value == "local" => {
+                if let KeyframesName::CustomIdent(ident) = &f.name {
+                    self.names.insert(ident.value.clone());
+                }
+            }
+            KeyframesName::PseudoPrefix(p) if p.pseudo.value == "local" => {
+                if let KeyframesName::CustomIdent(ident) = &p.name {
+                    self.names.insert(ident.value.clone());
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/**
+ * Find the selectors which don't contain any local class or id, used by the pure mode of
+ * css modules, same as postcss-modules-local-by-default.
+ */
+#[derive(Default)]
+pub struct CSSImpureSelectors {
+    pub selectors: Vec<String>,
+}
+
+fn is_pure(n: &ComplexSelector, mut is_local: bool) -> bool {
+    for child in &n.children {
+        let ComplexSelectorChildren::CompoundSelector(compound) = child else {
+            continue;
+        };
+        // the nesting selector & refers to the parent, which is checked already
+        if compound.nesting_selector.is_some() {
+            return true;
+        }
+        for sel in &compound.subclass_selectors {
+            match sel {
+                SubclassSelector::Class(_) | SubclassSelector::Id(_) if is_local => {
+                    return true;
+                }
+                SubclassSelector::PseudoClass(pseudo)
+                    if pseudo.name.value == "local" || pseudo.name.value == "global" =>
+                {
+                    let is_local_pseudo = pseudo.name.value == "local";
+                    match pseudo.children.as_ref().and_then(|c| c.first()) {
+                        Some(PseudoClassSelectorChildren::ComplexSelector(selector)) => {
+                            if is_local_pseudo && is_pure(selector, true) {
+                                return true;
+                            }
+                        }
+                        _ => is_local = is_local_pseudo,
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    false
+}
+
+impl Visit for CSSImpureSelectors {
+    fn visit_qualified_rule(&mut self, n: &QualifiedRule) {
+        if let QualifiedRulePrelude::SelectorList(list) = &n.prelude {
+            for selector in &list.children {
+                if !is_pure(selector, true) {
+                    self.selectors.push(CssAst::selector_to_string(selector));
+                }
+            }
+        }
+        n.block.visit_with(self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use swc_core::css::visit::VisitWith;
+
+    use super::{CSSImpureSelectors, CSSLocalNames};
+    use crate::ast::css_ast::CssAst;
+    use crate::compiler::Context;
+
+    #[test]
+    fn test_local_names() {
+        let mut names = local_names(".a{} :local(.b) .c{} :local .d .e{} @keyframes :local(f){}");
+        names.sort();
+        assert_eq!(names, vec!["b", "d", "e", "f"]);
+    }
+
+    #[test]
+    fn test_impure_selectors() {
+        assert_eq!(
+            impure_selectors(".a{} div .a{} :global(.b){} div{} :local(.c){} .d{&:hover{}}"),
+            vec![":global(.b)", "div"]
+        );
+    }
+
+    fn local_names(css_code: &str) -> Vec<String> {
+        let ast = CssAst::build("test.css", css_code, Arc::new(Context::default()), true).unwrap();
+        let mut visitor = CSSLocalNames::default();
+        ast.ast.visit_with(&mut visitor);
+        visitor.names.iter().map(|n| n.to_string()).collect()
+    }
+
+    fn impure_selectors(css_code: &str) -> Vec<String> {
+        let ast = CssAst::build("test.css", css_code, Arc::new(Context::default()), true).unwrap();
+        let mut visitor = CSSImpureSelectors::default();
+        ast.ast.visit_with(&mut visitor);
+        visitor.selectors
+    }
+}
@@ -3,13 +3,17 @@ use std::sync::Arc;
 use regex::Regex;
 use swc_core::common::comments::Comments;
 use swc_core::common::{BytePos, Mark, Span, Spanned};
-use swc_core::ecma::ast::{CallExpr, Expr, Lit, ModuleDecl, NewExpr, Str};
+use swc_core::ecma::ast::{CallExpr, Expr, ModuleDecl, NewExpr};
 use swc_core::ecma::visit::{Visit, VisitWith};
+use swc_node_comments::SwcComments;
 
 use crate::ast::utils;
 use crate::compiler::Context;
-use crate::module::{Dependency, ImportOptions, ResolveType};
+use crate::module::{Dependency, ImportMode, ImportOptions, ResolveType};
 use crate::utils::create_cached_regex;
+use crate::visitors::worker_module::{
+    worker_type_of_call_expr, worker_type_of_new_expr, worker_url, WorkerType,
+};
 
 pub struct DepAnalyzer {
     pub dependencies: Vec<Dependency>,
@@ -39,46 +43,16 @@ impl DepAnalyzer {
     }
 
     fn analyze_import_options(&self, magic_comments_pos: BytePos) -> ImportOptions {
-        let comments_texts = self
-            .context
-            .meta
-            .script
-            .origin_comments
-            .read()
-            .unwrap()
-            .get_swc_comments()
-            .get_leading(magic_comments_pos)
-            .map_or(Vec::new(), |cms| {
-                cms.iter().map(|c| c.text.to_string()).collect()
-            });
-
-        let chunk_name = comments_texts.iter().find_map(|t| {
-            get_magic_comment_chunk_name_regex()
-                .captures(t.trim())
-                .and_then(|matched| matched.get(2).map(|m| m.as_str().to_string()))
-        });
-
-        let ignore = comments_texts.iter().any(|t| {
-            get_magic_comment_ignore_regex()
-                .captures(t.trim())
-                .map_or(false, |cap| {
-                    cap.get(2).map_or(false, |m| m.as_str() == "true")
-                })
-        });
-
-        let _is_federation_expose = comments_texts.iter().any(|t| {
-            get_magic_federation_expose_regex()
-                .captures(t.trim())
-                .map_or(false, |cap| {
-                    cap.get(2).map_or(false, |m| m.as_str() == "true")
-                })
-        });
-
-        ImportOptions {
-            chunk_name,
-            ignore,
-            _is_federation_expose,
-        }
+        analyze_import_options(
+            self.context
+                .meta
+                .script
+                .origin_comments
+                .read()
+                .unwrap()
+                .get_swc_comments(),
+            magic_comments_pos,
+        )
     }
 }
 
@@ -124,6 +98,12 @@ impl Visit for DepAnalyzer {
     }
 
     fn visit_call_expr(&mut self, expr: &CallExpr) {
+        // e.g.
+        // navigator.serviceWorker.register(new URL('a', import.meta.url))
+        // CSS.paintWorklet.addModule(new URL('a', import.meta.url))
+        if let Some(worker_type) = worker_type_of_call_expr(expr, &self.unresolved_mark) {
+            self.add_worker_dependency(&expr.args[0].expr, worker_type);
+        }
         // e.g.
         // require('a')
         if utils::is_commonjs_require(expr, &self.unresolved_mark) {
@@ -171,78 +151,119 @@ impl Visit for DepAnalyzer {
         // Web workers
         // e.g.
         // new Worker(new URL('a', import.meta.url));
-        if let Some(str) = resolve_web_worker(expr, self.unresolved_mark) {
-            let maybe_magic_comments_pos = expr.args.as_ref().and_then(|args| {
-                if !args.is_empty() {
-                    match &args[0].expr {
-                        box Expr::New(s) => Some(s.span().lo),
-                        _ => None,
-                    }
-                } else {
-                    None
-                }
-            });
-
-            let import_options = if self.context.config.experimental.magic_comment {
-                maybe_magic_comments_pos.map_or(ImportOptions::default(), |magic_comments_pos| {
-                    self.analyze_import_options(magic_comments_pos)
-                })
-            } else {
-                ImportOptions::default()
-            };
-            self.add_dependency(
-                str.value.to_string(),
-                ResolveType::Worker(import_options),
-                Some(str.span),
-            );
+        // new SharedWorker(new URL('a', import.meta.url));
+        if let Some(worker_type) = worker_type_of_new_expr(expr, &self.unresolved_mark) {
+            self.add_worker_dependency(&expr.args.as_ref().unwrap()[0].expr, worker_type);
         }
         expr.visit_children_with(self);
     }
 }
 
-// get the value of url when the following conditions are met
-// notice: only add dependency when the second argument is import.meta.url
-// e.g.
-// new Worker(new URL('a', import.meta.url));
-fn resolve_web_worker(expr: &NewExpr, unresolved_mark: Mark) -> Option<&Str> {
-    if !expr.args.as_ref().is_some_and(|args| !args.is_empty()) || !expr.callee.is_ident() {
-        return None;
+impl DepAnalyzer {
+    // notice: only add dependency when the second argument of URL is import.meta.url
+    fn add_worker_dependency(&mut self, url: &Expr, worker_type: WorkerType) {
+        let Some(str) = worker_url(url, &self.unresolved_mark, true) else {
+            return;
+        };
+        let mut import_options = if self.context.config.experimental.magic_comment {
+            self.analyze_import_options(url.span().lo)
+        } else {
+            ImportOptions::default()
+        };
+        import_options.service_worker = worker_type == WorkerType::ServiceWorker;
+        self.add_dependency(
+            str.value.to_string(),
+            ResolveType::Worker(import_options),
+            Some(str.span),
+        );
     }
+}
 
-    if let box Expr::Ident(ident) = &expr.callee {
-        #[allow(clippy::needless_borrow)]
-        if utils::is_ident_undefined(&ident, "Worker", &unresolved_mark) {
-            let args = expr.args.as_ref().unwrap();
-            if let Expr::New(expr) = &*args[0].expr {
-                // TODO: refactor
-                // use too many not operation, not intuitive
-                if !expr.args.as_ref().is_some_and(|args| !args.is_empty())
-                    || !expr.callee.is_ident()
-                {
-                    return None;
-                }
+/// parse the magic comments before `magic_comments_pos`, e.g. `import(/* webpackChunkName: 'a' */ './a')`
+pub(crate) fn analyze_import_options(
+    comments: &SwcComments,
+    magic_comments_pos: BytePos,
+) -> ImportOptions {
+    let comments_texts = comments
+        .get_leading(magic_comments_pos)
+        .map_or(Vec::new(), |cms| {
+            cms.iter().map(|c| c.text.to_string()).collect()
+        });
 
-                if let box Expr::Ident(ident) = &expr.callee {
-                    #[allow(clippy::needless_borrow)]
-                    if utils::is_ident_undefined(&ident, "URL", &unresolved_mark) {
-                        let args = expr.args.as_ref().unwrap();
-                        if args
-                            .get(1)
-                            .is_some_and(|arg| utils::is_import_meta_url(&arg.expr))
-                        {
-                            if let box Expr::Lit(Lit::Str(ref str)) = &args[0].expr {
-                                if !utils::is_remote_or_data(&str.value) {
-                                    return Some(str);
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
-    }
+    let chunk_name = comments_texts.iter().find_map(|t| {
+        get_magic_comment_chunk_name_regex()
+            .captures(t.trim())
+            .and_then(|matched| matched.get(2).map(|m| m.as_str().to_string()))
+    });
 
-    None
+    let ignore = comments_texts.iter().any(|t| {
+        get_magic_comment_ignore_regex()
+            .captures(t.trim())
+            .map_or(false, |cap| {
+                cap.get(2).map_or(false, |m| m.as_str() == "true")
+            })
+    });
+
+    let _is_federation_expose = comments_texts.iter().any(|t| {
+        get_magic_federation_expose_regex()
+            .captures(t.trim())
+            .map_or(false, |cap| {
+                cap.get(2).map_or(false, |m| m.as_str() == "true")
+            })
+    });
+
+    let prefetch = comments_texts.iter().any(|t| {
+        get_magic_comment_prefetch_regex()
+            .captures(t.trim())
+            .map_or(false, |cap| {
+                cap.get(2).map_or(false, |m| m.as_str() != "false")
+            })
+    });
+
+    let preload = comments_texts.iter().any(|t| {
+        get_magic_comment_preload_regex()
+            .captures(t.trim())
+            .map_or(false, |cap| {
+                cap.get(2).map_or(false, |m| m.as_str() != "false")
+            })
+    });
+
+    let mode = comments_texts
+        .iter()
+        .find_map(|t| {
+            get_magic_comment_mode_regex()
+                .captures(t.trim())
+                .and_then(|cap| match cap.get(2).map(|m| m.as_str()) {
+                    Some("eager") => Some(ImportMode::Eager),
+                    Some("lazy-once") => Some(ImportMode::LazyOnce),
+                    Some("lazy") => Some(ImportMode::Lazy),
+                    _ => None,
+                })
+        })
+        .unwrap_or_default();
+
+    let exports = comments_texts.iter().find_map(|t| {
+        get_magic_comment_exports_regex()
+            .captures(t.trim())
+            .and_then(|cap| cap.get(2))
+            .map(|m| {
+                get_quoted_regex()
+                    .captures_iter(m.as_str())
+                    .filter_map(|c| c.get(1).map(|e| e.as_str().to_string()))
+                    .collect::<Vec<_>>()
+            })
+    });
+
+    ImportOptions {
+        chunk_name,
+        ignore,
+        _is_federation_expose,
+        prefetch,
+        preload,
+        mode,
+        exports,
+        ..Default::default()
+    }
 }
 
 fn get_magic_comment_chunk_name_regex() -> Regex {
@@ -253,6 +274,29 @@ fn get_magic_comment_ignore_regex() -> Regex {
     create_cached_regex(r#"(makoIgnore|webpackIgnore):\s*(true|false)"#)
 }
 
+// the order number of webpack is accepted but not respected
+fn get_magic_comment_prefetch_regex() -> Regex {
+    create_cached_regex(r#"(makoPrefetch|webpackPrefetch):\s*(true|false|-?\d+)"#)
+}
+
+fn get_magic_comment_preload_regex() -> Regex {
+    create_cached_regex(r#"(makoPreload|webpackPreload):\s*(true|false|-?\d+)"#)
+}
+
+// webpackMode: "weak" is not supported
+fn get_magic_comment_mode_regex() -> Regex {
+    create_cached_regex(r#"(makoMode|webpackMode):\s*['"`]([\w-]+)['"`]"#)
+}
+
+// webpackExports: "a" or webpackExports: ["a", "b"]
+fn get_magic_comment_exports_regex() -> Regex {
+    create_cached_regex(r#"(makoExports|webpackExports):\s*(\[[^\]]*\]|['"`][^'"`]+['"`])"#)
+}
+
+fn get_quoted_regex() -> Regex {
+    create_cached_regex(r#"['"`]([^'"`]+)['"`]"#)
+}
+
 fn get_magic_federation_expose_regex() -> Regex {
     create_cached_regex(r#"(federationExpose):\s*(true|false)"#)
 }
@@ -263,6 +307,7 @@ mod tests {
     use swc_core::ecma::visit::VisitWith;
 
     use crate::ast::tests::TestUtils;
+    use crate::module::{ImportMode, ImportOptions, ResolveType};
 
     #[test]
     fn test_normal() {
@@ -309,6 +354,20 @@ mod tests {
         assert!(run(r#"new Worker('a');"#).is_empty());
         // ignore remote
         assert!(run(r#"new Worker(new URL('https://a', import.meta.url));"#).is_empty());
+        // worker-like entries
+        assert_eq!(
+            run(r#"new SharedWorker(new URL('a', import.meta.url));"#),
+            vec!["a"]
+        );
+        assert_eq!(
+            run(r#"navigator.serviceWorker.register(new URL('a', import.meta.url));"#),
+            vec!["a"]
+        );
+        assert_eq!(
+            run(r#"CSS.paintWorklet.addModule(new URL('a', import.meta.url));"#),
+            vec!["a"]
+        );
+        assert!(run(r#"navigator.serviceWorker.register('a');"#).is_empty());
     }
 
     #[test]
@@ -318,6 +377,71 @@ mod tests {
         assert_eq!(run(r#"require(require("b"))"#), vec!["b"]);
     }
 
+    #[test]
+    fn test_resource_hints() {
+        let options = run_import_options(
+            r#"
+import(/* webpackPrefetch: true */ 'a');
+import(/* webpackPreload: true */ 'b');
+import(/* makoPrefetch: 1 */ 'c');
+import(/* webpackPrefetch: false */ 'd');
+"#,
+        );
+        assert_eq!(
+            options
+                .iter()
+                .map(|o| (o.prefetch, o.preload))
+                .collect::<Vec<_>>(),
+            vec![(true, false), (false, true), (true, false), (false, false)]
+        );
+    }
+
+    #[test]
+    fn test_mode_and_exports() {
+        let options = run_import_options(
+            r#"
+import(/* webpackMode: "eager" */ 'a');
+import(/* webpackMode: 'lazy-once' */ 'b');
+import(/* makoExports: ["default", "named"] */ 'c');
+import(/* webpackExports: "named" */ 'd');
+import('e');
+"#,
+        );
+        assert_eq!(
+            options
+                .iter()
+                .map(|o| (o.mode.clone(), o.exports.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                (ImportMode::Eager, None),
+                (ImportMode::LazyOnce, None),
+                (
+                    ImportMode::Lazy,
+                    Some(vec!["default".to_string(), "named".to_string()])
+                ),
+                (ImportMode::Lazy, Some(vec!["named".to_string()])),
+                (ImportMode::Lazy, None),
+            ]
+        );
+    }
+
+    fn run_import_options(js_code: &str) -> Vec<ImportOptions> {
+        let mut test_utils = TestUtils::gen_js_ast(js_code);
+        let ast = test_utils.ast.js_mut();
+        let mut analyzer = super::DepAnalyzer::new(ast.unresolved_mark, test_utils.context.clone());
+        GLOBALS.set(&test_utils.context.meta.script.globals, || {
+            ast.ast.visit_with(&mut analyzer);
+        });
+        analyzer
+            .dependencies
+            .iter()
+            .filter_map(|dep| match &dep.resolve_type {
+                ResolveType::DynamicImport(options) => Some(options.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
     fn run(js_code: &str) -> Vec<String> {
         let mut test_utils = TestUtils::gen_js_ast(js_code);
         let ast = test_utils.ast.js_mut();
@@ -1,20 +1,26 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
+use swc_core::common::util::take::Take;
 use swc_core::common::{Mark, DUMMY_SP};
 use swc_core::ecma::ast::{
-    AssignOp, BlockStmt, Expr, ExprOrSpread, FnExpr, Function, Ident, ImportDecl, Lit, NamedExport,
-    NewExpr, Stmt, Str, ThrowStmt, VarDeclKind,
+    AssignOp, BinExpr, BinaryOp, BlockStmt, CallExpr, Expr, ExprOrSpread, FnExpr, Function,
+    ImportDecl, KeyValueProp, Lit, NamedExport, NewExpr, ObjectLit, Prop, PropOrSpread, Stmt, Str,
+    ThrowStmt, VarDeclKind,
 };
 use swc_core::ecma::utils::{member_expr, quote_ident, quote_str, ExprFactory};
 use swc_core::ecma::visit::{VisitMut, VisitMutWith};
 
 use crate::ast::file::parse_path;
-use crate::ast::utils::{is_commonjs_require, is_dynamic_import, is_remote_or_data};
+use crate::ast::utils::{is_commonjs_require, is_dynamic_import};
 use crate::ast::DUMMY_CTXT;
 use crate::compiler::Context;
+use crate::config::Platform;
 use crate::module::{Dependency, ModuleId};
 use crate::visitors::virtual_css_modules::is_css_path;
+use crate::visitors::worker_module::{
+    worker_type_of_call_expr, worker_type_of_new_expr, worker_url_mut, WorkerType,
+};
 
 pub struct DepReplacer<'a> {
     pub module_id: &'a ModuleId,
@@ -180,12 +186,44 @@ impl VisitMut for DepReplacer<'_> {
     }
 
     fn visit_mut_new_expr(&mut self, new_expr: &mut NewExpr) {
-        if let Some(str) = resolve_web_worker_mut(new_expr, self.unresolved_mark) {
-            self.replace_source(str);
+        if worker_type_of_new_expr(new_expr, &self.unresolved_mark).is_some() {
+            let args = new_expr.args.as_mut().unwrap();
+            // new Worker(new URL(''), base);
+            if let Some(str) = worker_url_mut(&mut args[0].expr, &self.unresolved_mark, false) {
+                self.replace_source(str);
+            }
         }
         new_expr.visit_mut_children_with(self);
     }
 
+    fn visit_mut_call_expr(&mut self, call_expr: &mut CallExpr) {
+        if let Some(worker_type) = worker_type_of_call_expr(call_expr, &self.unresolved_mark) {
+            if let Some(str) =
+                worker_url_mut(&mut call_expr.args[0].expr, &self.unresolved_mark, false)
+            {
+                self.replace_source(str);
+                if worker_type == WorkerType::ServiceWorker {
+                    self.handle_service_worker(call_expr);
+                }
+            }
+        }
+        // module.hot.accept('./a', cb) or module.hot.accept(['./a', './b'], cb)
+        if is_hot_accept(call_expr)
+            && let Some(arg) = call_expr.args.first_mut()
+        {
+            match &mut *arg.expr {
+                Expr::Lit(Lit::Str(source)) => self.replace_source(source),
+                Expr::Array(array) => array.elems.iter_mut().flatten().for_each(|elem| {
+                    if let box Expr::Lit(Lit::Str(source)) = &mut elem.expr {
+                        self.replace_source(source);
+                    }
+                }),
+                _ => {}
+            }
+        }
+        call_expr.visit_mut_children_with(self);
+    }
+
     fn visit_mut_import_decl(&mut self, import_decl: &mut ImportDecl) {
         self.replace_source(&mut import_decl.src);
     }
@@ -197,7 +235,65 @@ impl VisitMut for DepReplacer<'_> {
     }
 }
 
+fn is_hot_accept(call_expr: &CallExpr) -> bool {
+    call_expr
+        .callee
+        .as_expr()
+        .and_then(|callee| callee.as_member())
+        .is_some_and(|member| {
+            member
+                .prop
+                .as_ident()
+                .is_some_and(|prop| prop.sym == "accept")
+                && member.obj.as_member().is_some_and(|obj| {
+                    obj.prop.as_ident().is_some_and(|prop| prop.sym == "hot")
+                        && obj.obj.as_ident().is_some_and(|obj| obj.sym == "module")
+                })
+        })
+}
+
 impl DepReplacer<'_> {
+    // service workers are resolved against the public path instead of the document,
+    // and get the configured scope when it's not specified
+    fn handle_service_worker(&mut self, call_expr: &mut CallExpr) {
+        if matches!(self.context.config.platform, Platform::Browser)
+            && let box Expr::New(new_expr) = &mut call_expr.args[0].expr
+            && let Some(url_args) = new_expr.args.as_mut()
+        {
+            let url = url_args[0].expr.take();
+            url_args[0].expr = Expr::Bin(BinExpr {
+                span: DUMMY_SP,
+                op: BinaryOp::Add,
+                left: member_expr!(DUMMY_CTXT, DUMMY_SP, __mako_require__.publicPath).into(),
+                right: url,
+            })
+            .into();
+        }
+
+        if call_expr.args.len() == 1
+            && let Some(scope) = self
+                .context
+                .config
+                .service_worker
+                .as_ref()
+                .and_then(|c| c.scope.as_ref())
+        {
+            call_expr.args.push(
+                ObjectLit {
+                    span: DUMMY_SP,
+                    props: vec![PropOrSpread::Prop(
+                        Prop::KeyValue(KeyValueProp {
+                            key: quote_ident!("scope").into(),
+                            value: quote_str!(scope.as_str()).into(),
+                        })
+                        .into(),
+                    )],
+                }
+                .as_arg(),
+            );
+        }
+    }
+
     fn replace_source(&mut self, source: &mut Str) {
         if let Some(replacement) = self.to_replace.resolved.get(&source.value.to_string()) {
             let module_id = replacement.to_replace_source.clone();
@@ -208,43 +304,6 @@ impl DepReplacer<'_> {
     }
 }
 
-// TODO: duplicated code with dep_analyzer.rs
-pub fn resolve_web_worker_mut(new_expr: &mut NewExpr, unresolved_mark: Mark) -> Option<&mut Str> {
-    if !new_expr.args.as_ref().is_some_and(|args| !args.is_empty()) || !new_expr.callee.is_ident() {
-        return None;
-    }
-
-    if let box Expr::Ident(Ident { sym, ctxt, .. }) = &mut new_expr.callee {
-        // `Worker` must be unresolved
-        if sym == "Worker" && (ctxt.outer() == unresolved_mark) {
-            let args = new_expr.args.as_mut().unwrap();
-
-            // new Worker(new URL(''), base);
-            if let Expr::New(new_expr) = &mut *args[0].expr {
-                if !new_expr.args.as_ref().is_some_and(|args| !args.is_empty())
-                    || !new_expr.callee.is_ident()
-                {
-                    return None;
-                }
-
-                if let box Expr::Ident(Ident { sym, ctxt, .. }) = &new_expr.callee {
-                    if sym == "URL" && (ctxt.outer() == unresolved_mark) {
-                        // new URL('');
-                        let args = new_expr.args.as_mut().unwrap();
-                        if let box Expr::Lit(Lit::Str(ref mut str)) = &mut args[0].expr {
-                            if !is_remote_or_data(&str.value) {
-                                return Some(str);
-                            }
-                        }
-                    }
-                }
-            }
-        }
-    }
-
-    None
-}
-
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
@@ -305,6 +364,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_hot_accept() {
+        assert_eq!(
+            run(
+                r#"module.hot.accept(['x', 'y'], cb); module.hot.accept('x');"#,
+                build_resolved("x", "/x/index.js"),
+                Default::default()
+            ),
+            r#"module.hot.accept([
+    "/x/index.js",
+    'y'
+], cb);
+module.hot.accept("/x/index.js");"#,
+        );
+    }
+
+    #[test]
+    fn test_service_worker() {
+        assert_eq!(
+            run(
+                r#"navigator.serviceWorker.register(new URL('x', import.meta.url))"#,
+                build_resolved("x", "sw.js"),
+                Default::default()
+            ),
+            r#"navigator.serviceWorker.register(new URL(__mako_require__.publicPath + "sw.js", import.meta.url));"#,
+        );
+    }
+
     #[test]
     fn test_missing_dep() {
         assert_eq!(
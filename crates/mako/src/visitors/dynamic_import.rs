@@ -10,7 +10,9 @@ use swc_core::ecma::utils::{
 use swc_core::ecma::visit::{VisitMut, VisitMutWith};
 
 use super::dep_replacer::{miss_throw_stmt, ResolvedReplaceInfo};
-use crate::ast::utils::{is_dynamic_import, promise_all, require_ensure};
+use crate::ast::utils::{
+    id, is_dynamic_import, member_call, member_prop, promise_all, require_ensure,
+};
 use crate::ast::DUMMY_CTXT;
 use crate::compiler::Context;
 use crate::generate::chunk::ChunkId;
@@ -113,7 +115,12 @@ impl<'a> VisitMut for DynamicImport<'a> {
                     *expr = {
                         // let load_promise = self.make_load_promise(&chunk_ids);
 
-                        let load_promise = if self.context.args.watch
+                        let load_promise = if resolved_info.chunk_id.is_none() {
+                            // e.g.
+                            // import(/* webpackMode: "eager" */ "foo")
+                            // is bundled into the current chunk
+                            member_call(Expr::Ident(id("Promise")), member_prop("resolve"), vec![])
+                        } else if self.context.args.watch
                             && self.context.config.experimental.central_ensure
                         {
                             self.central_ensure(&generated_module_id)
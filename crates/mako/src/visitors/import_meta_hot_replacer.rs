@@ -0,0 +1,174 @@
+use swc_core::ecma::ast::{
+    BinExpr, BinaryOp, Expr, IdentName, MemberExpr, MemberProp, MetaPropExpr, MetaPropKind,
+    ModuleItem, OptChainBase, Stmt,
+};
+use swc_core::ecma::utils::{quote_ident, ExprFactory, StmtLike};
+use swc_core::ecma::visit::{VisitMut, VisitMutWith};
+
+use crate::ast::utils::id;
+
+/**
+ * Vite style `import.meta.hot` api, it's mapped to `module.hot` of the hmr runtime when hmr is
+ * enabled, or else the guarded statements like `if (import.meta.hot) {}` and
+ * `import.meta.hot?.accept()` are removed and the rest are replaced with `undefined`.
+ */
+#[derive(Debug)]
+pub(crate) struct ImportMetaHotReplacer {
+    pub(crate) hmr: bool,
+}
+
+impl ImportMetaHotReplacer {
+    pub(crate) fn new(hmr: bool) -> Self {
+        Self { hmr }
+    }
+
+    fn strip<T: StmtLike + VisitMutWith<Self>>(&mut self, stmts: &mut Vec<T>) {
+        stmts.retain_mut(|stmt| {
+            if let Some(s) = stmt.as_stmt_mut() {
+                match s {
+                    // if (import.meta.hot) { ... } else { ... }
+                    Stmt::If(if_stmt) if is_hot_test(&if_stmt.test) => match if_stmt.alt.take() {
+                        Some(alt) => *s = *alt,
+                        None => return false,
+                    },
+                    // import.meta.hot?.accept();
+                    Stmt::Expr(expr_stmt) if is_import_meta_hot(chain_root(&expr_stmt.expr)) => {
+                        return false;
+                    }
+                    _ => {}
+                }
+            }
+            stmt.visit_mut_with(self);
+            true
+        });
+    }
+}
+
+impl VisitMut for ImportMetaHotReplacer {
+    fn visit_mut_expr(&mut self, expr: &mut Expr) {
+        if is_import_meta_hot(expr) {
+            *expr = if self.hmr {
+                // replace import.meta.hot with module.hot
+                id("module").make_member(quote_ident!("hot")).into()
+            } else {
+                id("undefined").into()
+            };
+        } else {
+            expr.visit_mut_children_with(self);
+        }
+    }
+
+    fn visit_mut_module_items(&mut self, items: &mut Vec<ModuleItem>) {
+        if self.hmr {
+            items.visit_mut_children_with(self);
+        } else {
+            self.strip(items);
+        }
+    }
+
+    fn visit_mut_stmts(&mut self, stmts: &mut Vec<Stmt>) {
+        if self.hmr {
+            stmts.visit_mut_children_with(self);
+        } else {
+            self.strip(stmts);
+        }
+    }
+}
+
+fn is_import_meta_hot(expr: &Expr) -> bool {
+    matches!(
+        expr,
+        Expr::Member(MemberExpr {
+            obj:
+                box Expr::MetaProp(MetaPropExpr {
+                    kind: MetaPropKind::ImportMeta,
+                    ..
+                }),
+            prop: MemberProp::Ident(IdentName { sym, .. }),
+            ..
+        }) if sym == "hot"
+    )
+}
+
+// import.meta.hot or import.meta.hot && ...
+fn is_hot_test(expr: &Expr) -> bool {
+    match expr {
+        Expr::Paren(paren) => is_hot_test(&paren.expr),
+        Expr::Bin(BinExpr {
+            op: BinaryOp::LogicalAnd,
+            left,
+            ..
+        }) => is_hot_test(left),
+        _ => is_import_meta_hot(expr),
+    }
+}
+
+// the object of a member or call chain, e.g. `import.meta.hot` of `import.meta.hot?.data.x`
+fn chain_root(expr: &Expr) -> &Expr {
+    match expr {
+        Expr::Paren(paren) => chain_root(&paren.expr),
+        Expr::Member(member) if !is_import_meta_hot(expr) => chain_root(&member.obj),
+        Expr::Call(call) => match call.callee.as_expr() {
+            Some(callee) => chain_root(callee),
+            None => expr,
+        },
+        Expr::OptChain(opt_chain) => match &*opt_chain.base {
+            OptChainBase::Member(member) => chain_root(&member.obj),
+            OptChainBase::Call(call) => chain_root(&call.callee),
+        },
+        _ => expr,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use swc_core::common::GLOBALS;
+    use swc_core::ecma::visit::VisitMutWith;
+
+    use super::ImportMetaHotReplacer;
+    use crate::ast::tests::TestUtils;
+
+    #[test]
+    fn test_hmr() {
+        assert_eq!(
+            run(
+                r#"if (import.meta.hot) { import.meta.hot.accept('./a', (m) => {}); }"#,
+                true
+            ),
+            r#"if (module.hot) {
+    module.hot.accept('./a', (m)=>{});
+}"#
+        );
+    }
+
+    #[test]
+    fn test_strip() {
+        assert_eq!(
+            run(
+                r#"
+if (import.meta.hot) { import.meta.hot.accept(); }
+if (import.meta.hot && a) { b(); } else c();
+import.meta.hot?.dispose(() => {});
+function f() { import.meta.hot?.invalidate(); return 1; }
+const hot = import.meta.hot;
+"#,
+                false
+            ),
+            r#"c();
+function f() {
+    return 1;
+}
+const hot = undefined;"#
+        );
+    }
+
+    fn run(js_code: &str, hmr: bool) -> String {
+        let mut test_utils = TestUtils::gen_js_ast(js_code);
+        let ast = test_utils.ast.js_mut();
+        GLOBALS.set(&test_utils.context.meta.script.globals, || {
+            let mut visitor = ImportMetaHotReplacer::new(hmr);
+            ast.ast.visit_mut_with(&mut visitor);
+        });
+        test_utils.js_ast_to_code()
+    }
+}
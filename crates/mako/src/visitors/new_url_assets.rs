@@ -3,17 +3,19 @@ use std::sync::Arc;
 
 use anyhow::Result;
 use swc_core::common::{Mark, DUMMY_SP};
-use swc_core::ecma::ast::{BinExpr, BinaryOp, Expr, Lit};
-use swc_core::ecma::utils::{member_expr, quote_str};
-use swc_core::ecma::visit::VisitMut;
+use swc_core::ecma::ast::{BinExpr, BinaryOp, CallExpr, Expr, Lit};
+use swc_core::ecma::utils::{member_expr, quote_str, ExprFactory};
+use swc_core::ecma::visit::{VisitMut, VisitMutWith};
 
 use crate::ast::file::File;
+use crate::ast::utils::{id, member_call, member_prop};
 use crate::ast::{utils, DUMMY_CTXT};
 use crate::build::load::Load;
 use crate::compiler::Context;
 use crate::config::Platform;
 use crate::module::{Dependency, ResolveType};
 use crate::resolve;
+use crate::visitors::worker_module::{worker_type_of_call_expr, worker_url};
 
 pub struct NewUrlAssets {
     pub context: Arc<Context>,
@@ -55,18 +57,32 @@ impl NewUrlAssets {
                 right: member_expr!(DUMMY_CTXT, DUMMY_SP, self.location.href).into(),
             })
         } else {
-            Expr::Lit(
-                quote_str!(format!(
-                    "file://{}",
-                    self.path.to_string_lossy().to_string()
-                ))
-                .into(),
+            // the url of the output file, since assets are emitted next to it,
+            // require("url").pathToFileURL(__filename)
+            member_call(
+                id("require").as_call(DUMMY_SP, vec![quote_str!("url").as_arg()]),
+                member_prop("pathToFileURL"),
+                vec![Expr::from(id("__filename")).as_arg()],
             )
         }
     }
 }
 
 impl VisitMut for NewUrlAssets {
+    fn visit_mut_call_expr(&mut self, n: &mut CallExpr) {
+        // urls of service workers and worklets are bundled as worker entries
+        if worker_type_of_call_expr(n, &self.unresolved_mark).is_some()
+            && worker_url(&n.args[0].expr, &self.unresolved_mark, true).is_some()
+        {
+            n.args
+                .iter_mut()
+                .skip(1)
+                .for_each(|arg| arg.visit_mut_with(self));
+            return;
+        }
+        n.visit_mut_children_with(self);
+    }
+
     fn visit_mut_new_expr(&mut self, n: &mut swc_core::ecma::ast::NewExpr) {
         // new URL('', import.meta.url)
         if let box Expr::Ident(ident) = &n.callee {
@@ -1,8 +1,16 @@
 use swc_core::common::Mark;
-use swc_core::ecma::ast::{Expr, Lit, NewExpr, Str};
+use swc_core::ecma::ast::{CallExpr, Callee, Expr, Lit, MemberExpr, MemberProp, NewExpr, Str};
 use swc_core::ecma::visit::{VisitMut, VisitMutWith};
 
-use crate::ast::utils::{is_ident_undefined, is_remote_or_data};
+use crate::ast::utils::{is_ident_undefined, is_import_meta_url, is_remote_or_data};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkerType {
+    Worker,
+    SharedWorker,
+    ServiceWorker,
+    Worklet,
+}
 
 pub struct WorkerModule {
     unresolved_mark: Mark,
@@ -16,41 +24,28 @@ impl WorkerModule {
 
 impl VisitMut for WorkerModule {
     fn visit_mut_new_expr(&mut self, new_expr: &mut NewExpr) {
-        if !new_expr.args.as_ref().is_some_and(|args| !args.is_empty())
-            || !new_expr.callee.is_ident()
-        {
-            return;
+        if worker_type_of_new_expr(new_expr, &self.unresolved_mark).is_some() {
+            let args = new_expr.args.as_mut().unwrap();
+            // new Worker(new URL(''), base);
+            if let Some(str) = worker_url_mut(&mut args[0].expr, &self.unresolved_mark, false) {
+                self.replace_source(str);
+            }
         }
 
-        if let box Expr::Ident(ident) = &mut new_expr.callee {
-            #[allow(clippy::needless_borrow)]
-            if is_ident_undefined(&ident, "Worker", &self.unresolved_mark) {
-                let args = new_expr.args.as_mut().unwrap();
-
-                // new Worker(new URL(''), base);
-                if let Expr::New(new_expr) = &mut *args[0].expr {
-                    if !new_expr.args.as_ref().is_some_and(|args| !args.is_empty())
-                        || !new_expr.callee.is_ident()
-                    {
-                        return;
-                    }
-
-                    if let Some(ident) = &new_expr.callee.as_ident() {
-                        if is_ident_undefined(ident, "URL", &self.unresolved_mark) {
-                            // new URL('');
-                            let args = new_expr.args.as_mut().unwrap();
-                            if let Some(Lit::Str(ref mut str)) = &mut args[0].expr.as_mut_lit() {
-                                if !is_remote_or_data(&str.value) {
-                                    self.replace_source(str);
-                                }
-                            }
-                        }
-                    }
-                }
+        new_expr.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_call_expr(&mut self, call_expr: &mut CallExpr) {
+        if worker_type_of_call_expr(call_expr, &self.unresolved_mark).is_some() {
+            // navigator.serviceWorker.register(new URL(''), options);
+            if let Some(str) =
+                worker_url_mut(&mut call_expr.args[0].expr, &self.unresolved_mark, false)
+            {
+                self.replace_source(str);
             }
         }
 
-        new_expr.visit_mut_children_with(self);
+        call_expr.visit_mut_children_with(self);
     }
 }
 
@@ -66,3 +61,139 @@ impl WorkerModule {
         source.span = span;
     }
 }
+
+// new Worker(...) or new SharedWorker(...)
+pub fn worker_type_of_new_expr(new_expr: &NewExpr, unresolved_mark: &Mark) -> Option<WorkerType> {
+    if !new_expr.args.as_ref().is_some_and(|args| !args.is_empty()) {
+        return None;
+    }
+    let ident = new_expr.callee.as_ident()?;
+    if is_ident_undefined(ident, "Worker", unresolved_mark) {
+        Some(WorkerType::Worker)
+    } else if is_ident_undefined(ident, "SharedWorker", unresolved_mark) {
+        Some(WorkerType::SharedWorker)
+    } else {
+        None
+    }
+}
+
+// navigator.serviceWorker.register(...) or CSS.paintWorklet.addModule(...),
+// audio, layout and animation worklets are matched by the `*Worklet` property
+pub fn worker_type_of_call_expr(
+    call_expr: &CallExpr,
+    unresolved_mark: &Mark,
+) -> Option<WorkerType> {
+    if call_expr.args.is_empty() {
+        return None;
+    }
+    let Callee::Expr(box Expr::Member(MemberExpr {
+        obj:
+            box Expr::Member(MemberExpr {
+                obj,
+                prop: MemberProp::Ident(container),
+                ..
+            }),
+        prop: MemberProp::Ident(method),
+        ..
+    })) = &call_expr.callee
+    else {
+        return None;
+    };
+
+    match (container.sym.as_ref(), method.sym.as_ref()) {
+        ("serviceWorker", "register")
+            if obj
+                .as_ident()
+                .is_some_and(|i| is_ident_undefined(i, "navigator", unresolved_mark)) =>
+        {
+            Some(WorkerType::ServiceWorker)
+        }
+        (container, "addModule") if container.ends_with("Worklet") => Some(WorkerType::Worklet),
+        _ => None,
+    }
+}
+
+// new URL('a', import.meta.url)
+pub fn worker_url<'a>(
+    expr: &'a Expr,
+    unresolved_mark: &Mark,
+    with_meta_url: bool,
+) -> Option<&'a Str> {
+    let Expr::New(new_expr) = expr else {
+        return None;
+    };
+    let args = new_expr.args.as_ref()?;
+    if !new_expr
+        .callee
+        .as_ident()
+        .is_some_and(|i| is_ident_undefined(i, "URL", unresolved_mark))
+        || (with_meta_url && !args.get(1).is_some_and(|arg| is_import_meta_url(&arg.expr)))
+    {
+        return None;
+    }
+    match args.first() {
+        Some(arg) => match &*arg.expr {
+            Expr::Lit(Lit::Str(str)) if !is_remote_or_data(&str.value) => Some(str),
+            _ => None,
+        },
+        None => None,
+    }
+}
+
+pub fn worker_url_mut<'a>(
+    expr: &'a mut Expr,
+    unresolved_mark: &Mark,
+    with_meta_url: bool,
+) -> Option<&'a mut Str> {
+    worker_url(expr, unresolved_mark, with_meta_url)?;
+    let Expr::New(new_expr) = expr else {
+        return None;
+    };
+    match &mut *new_expr.args.as_mut()?[0].expr {
+        Expr::Lit(Lit::Str(str)) => Some(str),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use swc_core::common::GLOBALS;
+    use swc_core::ecma::visit::VisitMutWith;
+
+    use super::WorkerModule;
+    use crate::ast::tests::TestUtils;
+
+    #[test]
+    fn test_worker_like_entries() {
+        assert_eq!(
+            run(r#"new SharedWorker(new URL('./a', import.meta.url));"#),
+            r#"new SharedWorker(new URL("./a?asworker", import.meta.url));"#
+        );
+        assert_eq!(
+            run(r#"navigator.serviceWorker.register(new URL('./a', import.meta.url));"#),
+            r#"navigator.serviceWorker.register(new URL("./a?asworker", import.meta.url));"#
+        );
+        assert_eq!(
+            run(r#"CSS.paintWorklet.addModule(new URL('./a', import.meta.url));"#),
+            r#"CSS.paintWorklet.addModule(new URL("./a?asworker", import.meta.url));"#
+        );
+        // navigator is defined
+        assert_eq!(
+            run(
+                r#"const navigator = {};navigator.serviceWorker.register(new URL('./a', import.meta.url));"#
+            ),
+            r#"const navigator = {};
+navigator.serviceWorker.register(new URL('./a', import.meta.url));"#
+        );
+    }
+
+    fn run(js_code: &str) -> String {
+        let mut test_utils = TestUtils::gen_js_ast(js_code);
+        let ast = test_utils.ast.js_mut();
+        GLOBALS.set(&test_utils.context.meta.script.globals, || {
+            let mut visitor = WorkerModule::new(ast.unresolved_mark);
+            ast.ast.visit_mut_with(&mut visitor);
+        });
+        test_utils.js_ast_to_code()
+    }
+}
@@ -0,0 +1,242 @@
+import './small.css';
+
+export function handler0(event) {
+  console.log('handling event 0', event.type, event.target);
+  return event.type === 'click' ? 0 : -0;
+}
+export function handler1(event) {
+  console.log('handling event 1', event.type, event.target);
+  return event.type === 'click' ? 1 : -1;
+}
+export function handler2(event) {
+  console.log('handling event 2', event.type, event.target);
+  return event.type === 'click' ? 2 : -2;
+}
+export function handler3(event) {
+  console.log('handling event 3', event.type, event.target);
+  return event.type === 'click' ? 3 : -3;
+}
+export function handler4(event) {
+  console.log('handling event 4', event.type, event.target);
+  return event.type === 'click' ? 4 : -4;
+}
+export function handler5(event) {
+  console.log('handling event 5', event.type, event.target);
+  return event.type === 'click' ? 5 : -5;
+}
+export function handler6(event) {
+  console.log('handling event 6', event.type, event.target);
+  return event.type === 'click' ? 6 : -6;
+}
+export function handler7(event) {
+  console.log('handling event 7', event.type, event.target);
+  return event.type === 'click' ? 7 : -7;
+}
+export function handler8(event) {
+  console.log('handling event 8', event.type, event.target);
+  return event.type === 'click' ? 8 : -8;
+}
+export function handler9(event) {
+  console.log('handling event 9', event.type, event.target);
+  return event.type === 'click' ? 9 : -9;
+}
+export function handler10(event) {
+  console.log('handling event 10', event.type, event.target);
+  return event.type === 'click' ? 10 : -10;
+}
+export function handler11(event) {
+  console.log('handling event 11', event.type, event.target);
+  return event.type === 'click' ? 11 : -11;
+}
+export function handler12(event) {
+  console.log('handling event 12', event.type, event.target);
+  return event.type === 'click' ? 12 : -12;
+}
+export function handler13(event) {
+  console.log('handling event 13', event.type, event.target);
+  return event.type === 'click' ? 13 : -13;
+}
+export function handler14(event) {
+  console.log('handling event 14', event.type, event.target);
+  return event.type === 'click' ? 14 : -14;
+}
+export function handler15(event) {
+  console.log('handling event 15', event.type, event.target);
+  return event.type === 'click' ? 15 : -15;
+}
+export function handler16(event) {
+  console.log('handling event 16', event.type, event.target);
+  return event.type === 'click' ? 16 : -16;
+}
+export function handler17(event) {
+  console.log('handling event 17', event.type, event.target);
+  return event.type === 'click' ? 17 : -17;
+}
+export function handler18(event) {
+  console.log('handling event 18', event.type, event.target);
+  return event.type === 'click' ? 18 : -18;
+}
+export function handler19(event) {
+  console.log('handling event 19', event.type, event.target);
+  return event.type === 'click' ? 19 : -19;
+}
+export function handler20(event) {
+  console.log('handling event 20', event.type, event.target);
+  return event.type === 'click' ? 20 : -20;
+}
+export function handler21(event) {
+  console.log('handling event 21', event.type, event.target);
+  return event.type === 'click' ? 21 : -21;
+}
+export function handler22(event) {
+  console.log('handling event 22', event.type, event.target);
+  return event.type === 'click' ? 22 : -22;
+}
+export function handler23(event) {
+  console.log('handling event 23', event.type, event.target);
+  return event.type === 'click' ? 23 : -23;
+}
+export function handler24(event) {
+  console.log('handling event 24', event.type, event.target);
+  return event.type === 'click' ? 24 : -24;
+}
+export function handler25(event) {
+  console.log('handling event 25', event.type, event.target);
+  return event.type === 'click' ? 25 : -25;
+}
+export function handler26(event) {
+  console.log('handling event 26', event.type, event.target);
+  return event.type === 'click' ? 26 : -26;
+}
+export function handler27(event) {
+  console.log('handling event 27', event.type, event.target);
+  return event.type === 'click' ? 27 : -27;
+}
+export function handler28(event) {
+  console.log('handling event 28', event.type, event.target);
+  return event.type === 'click' ? 28 : -28;
+}
+export function handler29(event) {
+  console.log('handling event 29', event.type, event.target);
+  return event.type === 'click' ? 29 : -29;
+}
+export function handler30(event) {
+  console.log('handling event 30', event.type, event.target);
+  return event.type === 'click' ? 30 : -30;
+}
+export function handler31(event) {
+  console.log('handling event 31', event.type, event.target);
+  return event.type === 'click' ? 31 : -31;
+}
+export function handler32(event) {
+  console.log('handling event 32', event.type, event.target);
+  return event.type === 'click' ? 32 : -32;
+}
+export function handler33(event) {
+  console.log('handling event 33', event.type, event.target);
+  return event.type === 'click' ? 33 : -33;
+}
+export function handler34(event) {
+  console.log('handling event 34', event.type, event.target);
+  return event.type === 'click' ? 34 : -34;
+}
+export function handler35(event) {
+  console.log('handling event 35', event.type, event.target);
+  return event.type === 'click' ? 35 : -35;
+}
+export function handler36(event) {
+  console.log('handling event 36', event.type, event.target);
+  return event.type === 'click' ? 36 : -36;
+}
+export function handler37(event) {
+  console.log('handling event 37', event.type, event.target);
+  return event.type === 'click' ? 37 : -37;
+}
+export function handler38(event) {
+  console.log('handling event 38', event.type, event.target);
+  return event.type === 'click' ? 38 : -38;
+}
+export function handler39(event) {
+  console.log('handling event 39', event.type, event.target);
+  return event.type === 'click' ? 39 : -39;
+}
+export function handler40(event) {
+  console.log('handling event 40', event.type, event.target);
+  return event.type === 'click' ? 40 : -40;
+}
+export function handler41(event) {
+  console.log('handling event 41', event.type, event.target);
+  return event.type === 'click' ? 41 : -41;
+}
+export function handler42(event) {
+  console.log('handling event 42', event.type, event.target);
+  return event.type === 'click' ? 42 : -42;
+}
+export function handler43(event) {
+  console.log('handling event 43', event.type, event.target);
+  return event.type === 'click' ? 43 : -43;
+}
+export function handler44(event) {
+  console.log('handling event 44', event.type, event.target);
+  return event.type === 'click' ? 44 : -44;
+}
+export function handler45(event) {
+  console.log('handling event 45', event.type, event.target);
+  return event.type === 'click' ? 45 : -45;
+}
+export function handler46(event) {
+  console.log('handling event 46', event.type, event.target);
+  return event.type === 'click' ? 46 : -46;
+}
+export function handler47(event) {
+  console.log('handling event 47', event.type, event.target);
+  return event.type === 'click' ? 47 : -47;
+}
+export function handler48(event) {
+  console.log('handling event 48', event.type, event.target);
+  return event.type === 'click' ? 48 : -48;
+}
+export function handler49(event) {
+  console.log('handling event 49', event.type, event.target);
+  return event.type === 'click' ? 49 : -49;
+}
+export function handler50(event) {
+  console.log('handling event 50', event.type, event.target);
+  return event.type === 'click' ? 50 : -50;
+}
+export function handler51(event) {
+  console.log('handling event 51', event.type, event.target);
+  return event.type === 'click' ? 51 : -51;
+}
+export function handler52(event) {
+  console.log('handling event 52', event.type, event.target);
+  return event.type === 'click' ? 52 : -52;
+}
+export function handler53(event) {
+  console.log('handling event 53', event.type, event.target);
+  return event.type === 'click' ? 53 : -53;
+}
+export function handler54(event) {
+  console.log('handling event 54', event.type, event.target);
+  return event.type === 'click' ? 54 : -54;
+}
+export function handler55(event) {
+  console.log('handling event 55', event.type, event.target);
+  return event.type === 'click' ? 55 : -55;
+}
+export function handler56(event) {
+  console.log('handling event 56', event.type, event.target);
+  return event.type === 'click' ? 56 : -56;
+}
+export function handler57(event) {
+  console.log('handling event 57', event.type, event.target);
+  return event.type === 'click' ? 57 : -57;
+}
+export function handler58(event) {
+  console.log('handling event 58', event.type, event.target);
+  return event.type === 'click' ? 58 : -58;
+}
+export function handler59(event) {
+  console.log('handling event 59', event.type, event.target);
+  return event.type === 'click' ? 59 : -59;
+}
@@ -0,0 +1,4 @@
+import { a } from './a';
+import cjs from './cjs';
+
+import('./lazy').then(({ lazy }) => console.log(a, cjs, lazy));
@@ -0,0 +1,16 @@
+import logo from './logo.png';
+import thumbnail from './logo.png?w=16&format=webp';
+import responsive from './logo.png?srcset=16,32,128';
+import photo from './photo.jpg?w=32&format=webp';
+import photoThumbnail from './photo.jpg?w=16';
+import photoResponsive from './photo.jpg?srcset=16,32';
+import './index.css';
+
+console.log(
+  logo,
+  thumbnail,
+  responsive,
+  photo,
+  photoThumbnail,
+  photoResponsive,
+);
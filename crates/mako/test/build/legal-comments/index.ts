@@ -0,0 +1,4 @@
+/*! index v1.0.0 | MIT */
+import { store } from './store';
+
+console.log(store);
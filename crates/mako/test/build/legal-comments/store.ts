@@ -0,0 +1,4 @@
+/**
+ * @license store v1.0.0 | MIT
+ */
+export const store = { value: 1 };
@@ -0,0 +1,6 @@
+import { a } from 'a';
+import { b } from 'b';
+import { c } from 'c';
+import { d } from 'd';
+
+console.log(a, b, c, d);
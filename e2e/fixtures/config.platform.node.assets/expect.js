@@ -0,0 +1,36 @@
+const assert = require("assert");
+const fs = require("fs");
+const path = require("path");
+const { parseBuildResult } = require("../../../scripts/test-utils");
+const { files } = parseBuildResult(__dirname);
+
+const content = files["index.js"];
+const addon = Object.keys(files).find((f) => /^addon\.\w+\.node$/.test(f));
+
+assert(addon, "should emit native addon");
+assert(
+  content.includes(
+    `require("module").createRequire(__filename)(require("path").join(__dirname, "${addon}"))`
+  ),
+  "should load native addon relative to the output file"
+);
+
+module.exports = async () => {
+  const dist = require("./dist");
+  assert.deepStrictEqual(
+    dist.readConfig(),
+    { name: "config" },
+    "should emit path.join(__dirname, ...) as asset"
+  );
+  assert.strictEqual(
+    dist.readHello(),
+    "hello",
+    "should emit __dirname + ... as asset"
+  );
+  assert.strictEqual(
+    fs.readFileSync(dist.helloUrl(), "utf-8"),
+    "hello",
+    "should resolve new URL(..., import.meta.url) against the output file"
+  );
+  assert.throws(() => dist.loadAddon(), "should load native addon with native require");
+};
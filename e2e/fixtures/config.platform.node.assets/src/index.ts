@@ -0,0 +1,20 @@
+import fs from 'fs';
+import path from 'path';
+
+export function readConfig() {
+  return JSON.parse(
+    fs.readFileSync(path.join(__dirname, 'data', 'config.json'), 'utf-8'),
+  );
+}
+
+export function readHello() {
+  return fs.readFileSync(__dirname + '/data/hello.txt', 'utf-8');
+}
+
+export function helloUrl() {
+  return new URL('./data/hello.txt', import.meta.url);
+}
+
+export function loadAddon() {
+  return require('./addon.node');
+}
@@ -0,0 +1,30 @@
+const assert = require("assert");
+const fs = require("fs");
+const path = require("path");
+const { parseBuildResult } = require("../../../scripts/test-utils");
+const { files } = parseBuildResult(__dirname);
+
+const jsFiles = Object.keys(files).filter((f) => f.endsWith(".js"));
+assert.strictEqual(jsFiles.length, 1, "should bundle async chunks into the entry");
+assert(
+  Object.keys(files).every((f) => !/\.(txt|wasm)$/.test(f)),
+  "should inline assets"
+);
+
+const entry = jsFiles[0];
+const content = files[entry];
+assert(content.startsWith("#!/usr/bin/env node\n"), "should keep hashbang of entry");
+assert(!/require\("dep"\)/.test(content), "should bundle node_modules");
+if (process.platform !== "win32") {
+  const mode = fs.statSync(path.join(__dirname, "dist", entry)).mode;
+  assert(mode & 0o111, "should make the entry executable");
+}
+
+module.exports = async () => {
+  const result = await require(`./dist/${entry}`).run();
+  assert.deepStrictEqual(result, {
+    name: "dep",
+    hello: "data:text/plain;base64,aGVsbG8=",
+    sum: 3,
+  });
+};
@@ -0,0 +1,8 @@
+#!/usr/bin/env node
+import { name } from 'dep';
+import hello from './hello.txt';
+
+export async function run() {
+  const { sum } = await import('./lazy');
+  return { name, hello, sum };
+}
@@ -0,0 +1,15 @@
+const assert = require("assert");
+
+const { parseBuildResult, trim } = require("../../../scripts/test-utils");
+const { files } = parseBuildResult(__dirname);
+const html = files["index.html"];
+
+assert(
+  html.includes(`<style data-mako-critical>.header{color:red}.header .title{font-size:24px}.modal{display:none}</style>`),
+  "critical css should be inlined"
+);
+assert(
+  html.includes(`<link rel="stylesheet" href="/index.css" media="print" onload="this.media='all'" /><noscript><link rel="stylesheet" href="/index.css" /></noscript>`),
+  "full stylesheet should be loaded asynchronously"
+);
+assert(trim(files["index.css"]).includes(`.footer{color:blue;}`), "full stylesheet should be kept");
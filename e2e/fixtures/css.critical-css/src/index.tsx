@@ -0,0 +1,3 @@
+import './index.css';
+
+document.getElementById('root')!.innerHTML = '<footer class="footer"></footer>';
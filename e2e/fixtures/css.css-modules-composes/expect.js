@@ -0,0 +1,27 @@
+const assert = require("assert");
+
+const { parseBuildResult, trim } = require("../../../scripts/test-utils");
+const { files } = parseBuildResult(__dirname);
+
+assert.match(
+  trim(files["index.css"]),
+  /\.base__base-button--.{5}\{padding:4px;\}/,
+  "class names should follow localIdentName"
+);
+
+assert.match(
+  trim(files["index.css"]),
+  /\.index__primary-button--.{5}\{color:blue;\}/,
+  "composes should be removed from css"
+);
+
+assert(
+  files["index.js"].includes(`"primaryButton":`),
+  "camelCase keys should be exported"
+);
+
+assert.match(
+  files["index.js"],
+  /base\.module\.css\?asmodule|base_module_css_asmodule/,
+  "composes from other files should be resolved by the module graph"
+);
@@ -0,0 +1,3 @@
+import styles from './index.module.css';
+
+console.log(styles.primaryButton);
@@ -0,0 +1,13 @@
+const assert = require("assert");
+
+const { parseBuildResult, trim } = require("../../../scripts/test-utils");
+const { files } = parseBuildResult(__dirname);
+const css = trim(files["index.css"]);
+
+assert.match(css, /\.used-.{8}\{color:red;\}/, "used css modules class should be kept");
+assert.doesNotMatch(css, /\.unused-/, "unused css modules class should be removed");
+assert(css.includes(`.global-in-modules{color:gray;}`), "global class in css modules should be kept");
+assert(css.includes(`.used-global{color:red;}`), "used global class should be kept");
+assert(!css.includes(`.unused-global`), "unused global class should be purged");
+assert(css.includes(`.keep-me{color:green;}`), "safelisted class should be kept");
+assert(css.includes(`div{margin:0;}`), "tag selectors should be kept");
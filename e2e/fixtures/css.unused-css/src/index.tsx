@@ -0,0 +1,4 @@
+import './global.css';
+import styles from './index.module.css';
+
+document.body.className = `${styles.used} used-global`;
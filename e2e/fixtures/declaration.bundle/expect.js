@@ -0,0 +1,34 @@
+const assert = require("assert");
+const { parseBuildResult } = require("../../../scripts/test-utils");
+const { files } = parseBuildResult(__dirname);
+
+const dts = files["index.d.ts"];
+assert(!dts.includes(`from "./`), "local imports should be rolled up");
+assert(
+  dts.includes(`export import add = __src_utils.add;`) &&
+    dts.includes(`export import VERSION = __src_utils.VERSION;`),
+  "export * should be expanded",
+);
+assert(
+  dts.includes(`export import Button = __src_Button.__default;`),
+  "default export should be re-exported by name",
+);
+assert(
+  dts.includes(`export declare function render(props: Props): number;`),
+  "entry declarations should be kept at the top level",
+);
+assert.match(
+  dts,
+  /declare namespace __src_types \{\s*export interface Props/,
+  "imported declarations should be wrapped in namespaces",
+);
+assert(
+  dts.includes(`import { Options as Options_1 } from "pkg-b";`) &&
+    dts.includes(`import Options = Options_1;`),
+  "external imports with the same name should be renamed",
+);
+assert(
+  dts.includes(`import { Config as Config_1 } from "pkg-c";`) &&
+    dts.includes(`import Config = Config_1;`),
+  "external imports clashing with the entry should be renamed",
+);
@@ -0,0 +1,10 @@
+import type { Props } from "./types";
+import type { Options } from "pkg-b";
+
+export default class Button {
+  props?: Props;
+  options?: Options;
+  render(): string {
+    return "button";
+  }
+}
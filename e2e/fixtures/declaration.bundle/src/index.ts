@@ -0,0 +1,13 @@
+import type { Props } from "./types";
+import { add } from "./utils";
+
+export * from "./utils";
+export { default as Button } from "./Button";
+
+export interface Config {
+  debug: boolean;
+}
+
+export function render(props: Props): number {
+  return add(props.count, 1);
+}
@@ -0,0 +1,6 @@
+import type { Options } from "pkg-a";
+
+export interface Props {
+  count: number;
+  options?: Options;
+}
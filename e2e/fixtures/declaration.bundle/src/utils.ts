@@ -0,0 +1,11 @@
+import type { Config } from "pkg-c";
+
+export function add(a: number, b: number): number {
+  return a + b;
+}
+
+export function configure(config: Config): Config {
+  return config;
+}
+
+export const VERSION: string = "1.0.0";
@@ -0,0 +1,27 @@
+const assert = require("assert");
+const { parseBuildResult } = require("../../../scripts/test-utils");
+const { files } = parseBuildResult(__dirname);
+
+const index = files["index.d.ts"];
+assert(
+  index.includes(`import { add } from "./utils/index.js";`),
+  "imports should be rewritten in the same way as js",
+);
+assert(
+  index.includes(`import type { Props } from "./types.js";`),
+  "type only imports should be rewritten too",
+);
+assert(
+  index.includes(`export declare function render(props: Props): number;`),
+  "declarations should be emitted",
+);
+assert(
+  files["types.d.ts"].includes(`export interface Props`),
+  "type only imported files should be emitted",
+);
+assert(
+  files["utils/index.d.ts"].includes(
+    `export declare function add(a: number, b: number): number;`,
+  ),
+  "declarations should be emitted next to the js files",
+);
@@ -0,0 +1,12 @@
+import type { Props } from "./types";
+import { add } from "@/utils";
+
+export { add };
+
+export function render(props: Props): number {
+  return add(props.count, 1);
+}
+
+export default class App {
+  private count = 0;
+}
@@ -0,0 +1,3 @@
+export interface Props {
+  count: number;
+}
@@ -0,0 +1,9 @@
+const assert = require("assert");
+const { parseBuildResult, injectSimpleJest } = require("../../../scripts/test-utils");
+const { files, distDir } = parseBuildResult(__dirname);
+
+assert(!files["index.js"].includes("hot accepted"), "should remove the guarded accept");
+assert(!files["index.js"].includes("hot disposed"), "should remove the dispose call");
+
+injectSimpleJest()
+require(path.join(distDir, 'index.js'));
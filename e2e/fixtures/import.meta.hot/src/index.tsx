@@ -0,0 +1,12 @@
+if (import.meta.hot) {
+  import.meta.hot.accept(() => {
+    console.log("hot accepted");
+  });
+}
+import.meta.hot?.dispose(() => {
+  console.log("hot disposed");
+});
+
+it("should strip import.meta.hot without hmr", () => {
+  expect(import.meta.hot).toEqual(undefined);
+});
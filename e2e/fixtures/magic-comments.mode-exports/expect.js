@@ -0,0 +1,34 @@
+const assert = require("assert");
+const { parseBuildResult } = require("../../../scripts/test-utils");
+const { files } = parseBuildResult(__dirname);
+
+const index = files["index.js"];
+assert(
+  index.includes(`console.log("eager")`),
+  "eager import should be bundled into the entry chunk",
+);
+assert(
+  !Object.keys(files).some((f) => f.includes("eager")),
+  "eager import should not have its own chunk",
+);
+
+const asyncFiles = Object.keys(files).filter(
+  (f) => f !== "index.js" && f.endsWith(".js"),
+);
+const locales = asyncFiles.filter((f) => files[f].includes("en_locale"));
+assert.equal(
+  locales.length,
+  1,
+  "lazy-once context modules should be in one chunk",
+);
+assert(
+  files[locales[0]].includes("zh_locale"),
+  "lazy-once context modules should be in one chunk",
+);
+
+const exportsFile = asyncFiles.find((f) => files[f].includes("used_export"));
+assert(exportsFile, "used export should be kept");
+assert(
+  !files[exportsFile].includes("unused_export"),
+  "unused export should be removed with webpackExports",
+);
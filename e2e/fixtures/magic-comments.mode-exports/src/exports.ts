@@ -0,0 +1,6 @@
+export function used() {
+  console.log("used_export");
+}
+export function unused() {
+  console.log("unused_export");
+}
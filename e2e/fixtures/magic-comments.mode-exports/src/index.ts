@@ -0,0 +1,4 @@
+import { lang } from "./lang";
+import(/* webpackMode: "eager" */ "./eager").then((m) => m.default());
+import(/* webpackMode: "lazy-once" */ `./locales/${lang}`);
+import(/* webpackExports: ["used"] */ "./exports").then((m) => m.used());
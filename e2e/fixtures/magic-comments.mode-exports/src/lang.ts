@@ -0,0 +1 @@
+export const lang = navigator.language;
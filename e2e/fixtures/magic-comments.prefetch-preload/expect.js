@@ -0,0 +1,24 @@
+const assert = require("assert");
+const { parseBuildResult } = require("../../../scripts/test-utils");
+const { files } = parseBuildResult(__dirname);
+
+let index = files["index.js"];
+assert(
+  index.includes("/* mako/runtime/resource hints */"),
+  "should include resource hints runtime",
+);
+assert.match(
+  index,
+  /"src\/index.ts": \[\s*\[\s*"src\/prefetch.ts"\s*\],\s*\[\s*"src\/preload.ts"\s*\]\s*\]/,
+  "entry chunk should prefetch prefetch.ts and preload preload.ts",
+);
+assert.match(
+  index,
+  /"src\/normal.ts": \[\s*\[\s*"src\/nested.ts"\s*\],\s*\[\]\s*\]/,
+  "async chunk normal.ts should prefetch nested.ts",
+);
+assert.match(
+  index,
+  /var entryChunkIds = \{\s*"src\/index.ts": "src\/index.ts"\s*\}/,
+  "should map the entry module to its chunk",
+);
@@ -0,0 +1,3 @@
+import(/* webpackPrefetch: true */ "./prefetch");
+import(/* webpackPreload: true */ "./preload");
+import("./normal");
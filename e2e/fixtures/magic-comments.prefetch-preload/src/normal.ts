@@ -0,0 +1,2 @@
+import(/* webpackPrefetch: true */ "./nested");
+console.log("normal");
@@ -0,0 +1,2 @@
+import "./preload.css";
+console.log("preload");
@@ -0,0 +1,26 @@
+const assert = require("assert");
+const { parseBuildResult } = require("../../../scripts/test-utils");
+const { files } = parseBuildResult(__dirname);
+
+const content = files["index.js"];
+const license = files["index.js.LICENSE.txt"];
+
+assert(!content.includes("console.log"), "console should be dropped");
+assert(!content.includes("debugger"), "debugger should be dropped");
+assert(
+  !content.includes("_value") && content.includes("this.v=e"),
+  "properties should be mangled with the name cache"
+);
+assert(content.includes(".s(1)"), "methods should be mangled with the name cache");
+assert(
+  content.includes(
+    "/*! For license information please see index.js.LICENSE.txt */"
+  ),
+  "chunk should reference the license file"
+);
+assert(!content.includes("v1.0.0"), "legal comments should be extracted");
+assert(
+  license.includes("/*! index v1.0.0 | MIT */") &&
+    license.includes("@license store v1.0.0 | MIT"),
+  "legal comments of all the modules should be extracted"
+);
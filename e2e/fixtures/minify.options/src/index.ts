@@ -0,0 +1,8 @@
+/*! index v1.0.0 | MIT */
+import { Store } from './store';
+
+const store = new Store();
+store._setValue(1);
+console.log(store);
+debugger;
+globalThis.result = store._getValue();
@@ -0,0 +1,15 @@
+/**
+ * @license store v1.0.0 | MIT
+ */
+export class Store {
+  _value: number;
+  constructor() {
+    this._value = 0;
+  }
+  _setValue(value: number) {
+    this._value = value;
+  }
+  _getValue() {
+    return this._value;
+  }
+}
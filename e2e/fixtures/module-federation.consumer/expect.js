@@ -20,3 +20,8 @@ assert(
   manifest.shared.every(s => s.assets.js.sync.length !== 0),
   "should include mf shared assets"
 )
+
+assert(
+  /react\/index\.js":/.test(files["app1.js"]),
+  "should bundle eager shared dependencies into the initial chunk"
+)
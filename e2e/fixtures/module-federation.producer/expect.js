@@ -28,3 +28,14 @@ assert(
   manifest.shared.every(s => s.assets.js.sync.length !== 0),
   "should include mf shared assets"
 )
+
+assert(
+  manifest.metaData.types.zip === '@mf-types.zip' &&
+    manifest.metaData.types.api === '@mf-types.d.ts',
+  "should include mf types"
+)
+
+assert(
+  files["@mf-types.d.ts"].includes("export type RemoteKeys = 'producer/App';"),
+  "should generate mf types api"
+)
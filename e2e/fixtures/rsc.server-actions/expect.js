@@ -0,0 +1,30 @@
+const assert = require("assert");
+const { parseBuildResult } = require("../../../scripts/test-utils");
+const { files } = parseBuildResult(__dirname);
+
+const content = files["index.js"];
+
+assert(
+  content.includes(`registerServerReference(create, "src/actions.ts", "create");`),
+  "should register exported server actions"
+);
+
+assert(
+  content.includes(`registerServerReference($$ACTION_default, "src/actions.ts", "default");`),
+  "should register anonymous default server action"
+);
+
+assert(
+  content.includes(`update = $$ACTION_8abbf94b.bind(null, id);`),
+  "should bind captured variables of inline server action"
+);
+
+assert(
+  content.includes(`async function update(...args)`),
+  "should keep the inline server action declaration hoisted"
+);
+
+assert(
+  content.includes(`registerServerReference($$ACTION_8abbf94b, "src/page.ts", "$$ACTION_8abbf94b");`),
+  "should register inline server action"
+);
@@ -0,0 +1,7 @@
+"use server";
+
+export async function create(name: string) {
+  return name;
+}
+
+export default async () => {};
@@ -0,0 +1,4 @@
+import remove from './actions';
+import { Page } from './page';
+
+console.log(remove, Page);
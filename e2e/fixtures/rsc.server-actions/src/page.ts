@@ -0,0 +1,9 @@
+import { create } from './actions';
+
+export function Page({ id }: { id: string }) {
+  async function update() {
+    'use server';
+    return create(id);
+  }
+  return { action: update };
+}
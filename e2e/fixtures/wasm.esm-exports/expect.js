@@ -0,0 +1,30 @@
+const assert = require("assert");
+const { parseBuildResult } = require("../../../scripts/test-utils");
+const { files } = parseBuildResult(__dirname);
+
+const content = files["index.js"];
+
+assert(
+  Object.keys(files).some((f) => /^add\.\w+\.wasm$/.test(f)),
+  "should emit wasm asset"
+);
+assert.match(
+  content,
+  /await __mako_require__._interopreRequireWasm\("add\.\w+\.wasm", \{\}\)/,
+  "should instantiate wasm with top level await"
+);
+assert.match(
+  content,
+  /__mako_require__\.d\(exports, "add"/,
+  "should export used wasm exports"
+);
+assert.doesNotMatch(
+  content,
+  /__mako_require__\.d\(exports, "memory"/,
+  "should shake unused wasm exports"
+);
+assert.match(
+  content,
+  /require\('fs'\)\s*\.promises\.readFile/,
+  "should read wasm with fs in node"
+);
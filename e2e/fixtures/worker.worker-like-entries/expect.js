@@ -0,0 +1,40 @@
+const assert = require("assert");
+const { parseBuildResult } = require("../../../scripts/test-utils");
+const { files } = parseBuildResult(__dirname);
+
+const content = files["index.js"];
+const workerFile = (name) =>
+  Object.keys(files).find((f) => f.startsWith(`src_${name}_ts`) && f.endsWith("-worker.js"));
+
+["sw", "shared-worker", "paint"].forEach((name) => {
+  assert(workerFile(name), `should generate worker file for ${name}.ts`);
+});
+assert(
+  content.includes(
+    `navigator.serviceWorker.register(new URL(__mako_require__.publicPath + "${workerFile("sw")}"`
+  ),
+  "should resolve service worker against publicPath"
+);
+assert.match(content, /scope: "\/"/, "should add the configured scope");
+assert(
+  content.includes(`new SharedWorker(new URL("${workerFile("shared-worker")}"`),
+  "should bundle shared worker"
+);
+assert(
+  content.includes(`CSS.paintWorklet.addModule(new URL("${workerFile("paint")}"`),
+  "should bundle paint worklet"
+);
+
+const sw = files[workerFile("sw")];
+assert(
+  sw.startsWith("self.__MAKO_PRECACHE_MANIFEST__ = "),
+  "should inject precache manifest"
+);
+const manifest = JSON.parse(
+  sw.slice("self.__MAKO_PRECACHE_MANIFEST__ = ".length, sw.indexOf(";\n"))
+);
+assert(
+  manifest.some((e) => e.url === "/static/index.js") &&
+    !manifest.some((e) => e.url.includes(workerFile("sw"))),
+  "should precache emitted files except the service worker"
+);
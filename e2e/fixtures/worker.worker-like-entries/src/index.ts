@@ -0,0 +1,5 @@
+import { msg } from './shared';
+navigator.serviceWorker.register(new URL('./sw.ts', import.meta.url));
+const shared = new SharedWorker(new URL('./shared-worker.ts', import.meta.url));
+CSS.paintWorklet.addModule(new URL('./paint.ts', import.meta.url));
+console.log(msg, shared);
@@ -0,0 +1 @@
+registerPaint('x', class { paint() {} });
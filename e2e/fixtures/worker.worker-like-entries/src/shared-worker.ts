@@ -0,0 +1 @@
+import { msg } from './shared'; onconnect = () => console.log(msg);
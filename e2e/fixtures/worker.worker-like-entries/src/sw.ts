@@ -0,0 +1,3 @@
+import { msg } from './shared';
+declare const self: any;
+self.addEventListener('install', () => console.log(msg, self.__MAKO_PRECACHE_MANIFEST__));
@@ -47,6 +47,24 @@ export interface JsHooks {
       entrypoints: Record<string, { name: string; chunks: string[] }>;
       rscClientComponents: { path; string; moduleId: string }[];
       rscCSSModules: { path; string; moduleId: string; modules: boolean }[];
+      rscServerActions: {
+        id: string;
+        path: string;
+        moduleId: string;
+        name: string;
+      }[];
+      concatenation?: {
+        roots: number;
+        inners: number;
+        bailouts: number;
+        modules: {
+          id: string;
+          status: 'root' | 'inner' | 'bailout';
+          root?: string;
+          inners?: string[];
+          bailouts?: { reason: string; hint: string }[];
+        }[];
+      };
       startTime: number;
       endTime: number;
     };
@@ -105,6 +123,13 @@ export interface BuildParams {
       preserveModules?: boolean;
       preserveModulesRoot?: string;
       skipWrite?: boolean;
+      compress?:
+        | false
+        | {
+            algorithms?: ('gzip' | 'brotli')[];
+            threshold?: number;
+            minRatio?: number;
+          };
     };
     resolve?: {
       alias?: Array<[string, string]>;
@@ -136,6 +161,13 @@ export interface BuildParams {
         }
     >;
     copy?: (string | { from: string; to: string })[];
+    criticalCss?:
+        | false
+        | {
+              safelist?: string[];
+              maxSize?: number;
+          };
+    declaration?: boolean | { bundle?: boolean };
     codeSplitting?:
       | false
       | {
@@ -167,6 +199,12 @@ export interface BuildParams {
     publicPath?: string;
     inlineLimit?: number;
     inlineExcludesExtensions?: string[];
+    image?:
+      | false
+      | {
+          optimize?: boolean;
+          srcsetWidths?: number[];
+        };
     targets?: Record<string, number>;
     platform?: 'node' | 'browser';
     hmr?: false | {};
@@ -186,13 +224,30 @@ export interface BuildParams {
       | false
       | {
           modules?: boolean;
+          concatenation?: boolean;
+        };
+    analyze?:
+      | false
+      | {
+          baseline?: string;
         };
     hash?: boolean;
     autoCSSModules?: boolean;
     ignoreCSSParserErrors?: boolean;
     dynamicImportToRequire?: boolean;
+    lazyCompilation?: boolean;
+    autoPrefetch?: boolean;
     umd?: false | string | { name: string; export?: string[] };
+    unusedCss?:
+        | false
+        | {
+              purge?: {
+                  content?: string[];
+                  safelist?: string[];
+              };
+          };
     cjs?: boolean;
+    singleFile?: boolean;
     writeToDisk?: boolean;
     transformImport?: {
       libraryName: string;
@@ -203,8 +258,26 @@ export interface BuildParams {
     clean?: boolean;
     nodePolyfill?: boolean;
     ignores?: string[];
-    moduleIdStrategy?: 'hashed' | 'named';
-    minify?: boolean;
+    moduleIdStrategy?: 'hashed' | 'named' | 'numeric' | 'deterministic';
+    recordsPath?: string;
+    minify?:
+      | boolean
+      | {
+          dropConsole?: boolean;
+          dropDebugger?: boolean;
+          pureFuncs?: string[];
+          keepClassnames?: boolean;
+          keepFnames?: boolean;
+          passes?: number;
+          ecma?: number;
+          reserved?: string[];
+          mangleProps?: {
+            regex: string;
+            reserved?: string[];
+            nameCache?: string;
+          };
+          legalComments?: 'none' | 'inline' | 'external';
+        };
     _minifish?:
       | false
       | {
@@ -248,17 +321,49 @@ export interface BuildParams {
     };
     emitAssets?: boolean;
     cssModulesExportOnlyLocales?: boolean;
+    cssModules?: {
+        localIdentName?: string;
+        hashSalt?: string;
+        mode?: "local" | "global" | "pure";
+        exportLocalsConvention?:
+            | "asIs"
+            | "camelCase"
+            | "camelCaseOnly"
+            | "dashes"
+            | "dashesOnly";
+        dts?: boolean;
+    };
     inlineCSS?: false | {};
+    cssChunking?: false | {
+        ignoreOrder?: boolean;
+    };
+    cssLowering?: false | {
+        nesting?: boolean;
+        customMedia?: boolean;
+        mediaQueryRanges?: boolean;
+        colorFunctions?: boolean;
+        logicalProperties?: boolean;
+        cascadeLayers?: boolean;
+        dir?: 'ltr' | 'rtl';
+    };
     rscServer?:
       | false
       | {
           emitCSS: boolean;
           clientComponentTpl: string;
+          serverActionTpl?: string;
         };
     rscClient?:
       | false
       | {
           logServerComponent: 'error' | 'ignore';
+          serverActionTpl?: string;
+        };
+    serviceWorker?:
+      | false
+      | {
+          scope?: string;
+          precacheManifest?: boolean;
         };
     moduleFederation?: {
       name: string;
@@ -271,6 +376,7 @@ export interface BuildParams {
           strictVersion?: bool;
           requiredVersion?: string;
           eager?: bool;
+          import?: false;
           shareScope?: string;
         }
       >;
@@ -279,6 +385,12 @@ export interface BuildParams {
       shareScope?: string;
       shareStrategy?: 'version-first' | 'loaded-first';
       implementation: string;
+      manifest?: bool;
+      dts?: {
+        generateTypes?: bool;
+        consumeTypes?: Record<string, string>;
+        typesFolder?: string;
+      };
     };
     experimental?: {
       webpackSyntaxValidate?: string[];
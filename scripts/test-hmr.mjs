@@ -139,6 +139,65 @@ export default Foo;
   await cleanup({ process, browser });
 });
 
+runTest('js: import() entry + lazy compilation', async () => {
+  write(
+    normalizeFiles(
+      {
+        '/src/index.tsx': `
+import React, { Suspense } from 'react';
+import ReactDOM from "react-dom/client";
+let Foo = React.lazy(() => import('./Foo'));
+function App() {
+  return <div>App<Suspense><Foo /></Suspense><section>{Math.random()}</section></div>;
+}
+ReactDOM.createRoot(document.getElementById("root")!).render(<App />);
+    `,
+        'src/Foo.tsx': `
+import React from 'react';
+function Foo() {
+  return <div>Foo</div>;
+}
+export default Foo;
+    `,
+      },
+      { lazyCompilation: true },
+    ),
+  );
+  const { process } = await startMakoDevServer();
+  await delay(DELAY_TIME);
+  const { browser, page } = await startBrowser();
+  await delay(DELAY_TIME);
+  let lastResult;
+  let thisResult;
+  let isReload;
+  lastResult = normalizeHtml(await getRootHtml(page));
+  assert.equal(
+    lastResult.html,
+    '<div>App<div>Foo</div></div>',
+    'Initial render',
+  );
+  write({
+    '/src/Foo.tsx': `
+import React from 'react';
+function Foo() {
+  return <div>Bar</div>;
+}
+export default Foo;
+    `,
+  });
+  await delay(DELAY_TIME);
+  thisResult = normalizeHtml(await getRootHtml(page));
+  console.log(`new html`, thisResult.html);
+  assert.equal(
+    thisResult.html,
+    '<div>App<div>Bar</div></div>',
+    'Second render',
+  );
+  isReload = lastResult.random !== thisResult.random;
+  assert.equal(isReload, false, 'should not reload');
+  await cleanup({ process, browser });
+});
+
 runTest('js: hmr with runtime promise reject error', async () => {
   write(
     normalizeFiles({
@@ -335,7 +394,48 @@ ReactDOM.createRoot(document.getElementById("root")!).render(<App />);
   const thisColor = await getElementColor(page, '.foo');
   assert.equal(thisColor, 'rgb(0, 0, 255)', 'Second render');
   isReload = lastResult.random !== thisResult.random;
-  assert.equal(isReload, true, 'should reload');
+  assert.equal(isReload, false, 'should not reload');
+  lastResult = thisResult;
+  await cleanup({ process, browser });
+});
+
+runTest('css: entry > react component > css modules with class names changed', async () => {
+  write(
+    normalizeFiles({
+      '/src/index.module.css': `.foo {color:red;}`,
+      '/src/App.tsx': `
+import styles from "./index.module.css";
+function App() {
+  return <div className={\`\${styles.foo} \${styles.bar} foo\`}>App</div>;
+}
+export default App;
+      `,
+      '/src/index.tsx': `
+import React from 'react';
+import ReactDOM from "react-dom/client";
+import App from './App';
+ReactDOM.createRoot(document.getElementById("root")!).render(<><App /><section>{Math.random()}</section></>);
+    `,
+    }),
+  );
+  await startMakoDevServer();
+  await delay(DELAY_TIME);
+  const { browser, page } = await startBrowser();
+  let lastResult;
+  let thisResult;
+  let isReload;
+  lastResult = normalizeHtml(await getRootHtml(page));
+  const lastColor = await getElementColor(page, '.foo');
+  assert.equal(lastColor, 'rgb(255, 0, 0)', 'Initial render');
+  write({
+    '/src/index.module.css': `.foo {color:red;} .bar {color:blue;}`,
+  });
+  await delay(DELAY_TIME);
+  thisResult = normalizeHtml(await getRootHtml(page));
+  const thisColor = await getElementColor(page, '.foo');
+  assert.equal(thisColor, 'rgb(0, 0, 255)', 'Second render');
+  isReload = lastResult.random !== thisResult.random;
+  assert.equal(isReload, false, 'should not reload');
   lastResult = thisResult;
   await cleanup({ process, browser });
 });
@@ -690,6 +790,103 @@ ReactDOM.createRoot(document.getElementById("root")!).render(<><App /><section>{
   );
 });
 
+runTest('js: entry > js, accepted with import.meta.hot', async () => {
+  await commonTest(
+    {
+      '/src/util.ts': `
+export function foo() {
+  return 'foo';
+}
+`,
+      '/src/index.tsx': `
+import React from 'react';
+import ReactDOM from "react-dom/client";
+import { foo } from './util';
+const root = ReactDOM.createRoot(document.getElementById("root")!);
+const random = Math.random();
+function render(text) {
+  root.render(<><div>App {text}</div><section>{random}</section></>);
+}
+render(foo());
+if (import.meta.hot) {
+  import.meta.hot.accept('./util', (util) => {
+    render(util.foo());
+  });
+}
+  `,
+    },
+    (lastResult) => {
+      assert.equal(lastResult.html, '<div>App foo</div>', 'Initial render');
+    },
+    {
+      '/src/util.ts': `
+  export function foo() {
+  return 'bar';
+  }
+      `,
+    },
+    (thisResult) => {
+      assert.equal(thisResult.html, '<div>App bar</div>', 'Second render');
+    },
+    false,
+  );
+});
+
+runTest(
+  'js: entry > react component > util > js, invalidated with import.meta.hot',
+  async () => {
+    await commonTest(
+      {
+        '/src/child.ts': `
+export const text = 'foo';
+export const count = (import.meta.hot.data.count || 0) + 1;
+import.meta.hot.data.count = count;
+import.meta.hot.accept(() => {
+  import.meta.hot.invalidate();
+});
+`,
+        '/src/util.ts': `
+import { text, count } from './child';
+export function label() {
+  return text + ' ' + count;
+}
+`,
+        '/src/App.tsx': `
+import { label } from './util';
+function App() {
+  return <div>App {label()}</div>;
+}
+export default App;
+`,
+        '/src/index.tsx': `
+import React from 'react';
+import ReactDOM from "react-dom/client";
+import App from './App';
+ReactDOM.createRoot(document.getElementById("root")!).render(<><App /><section>{Math.random()}</section></>);
+    `,
+      },
+      (lastResult) => {
+        assert.equal(lastResult.html, '<div>App foo 1</div>', 'Initial render');
+      },
+      {
+        '/src/child.ts': `
+export const text = 'bar';
+export const count = (import.meta.hot.data.count || 0) + 1;
+import.meta.hot.data.count = count;
+import.meta.hot.accept(() => {
+  import.meta.hot.invalidate();
+});
+`,
+      },
+      (thisResult) => {
+        // executed by the update, then again by the self accepted App
+        assert.equal(thisResult.html, '<div>App bar 3</div>', 'Second render');
+      },
+      false,
+    );
+  },
+);
+
 runTest('js: entry > react component', async () => {
   await commonTest(
     {